@@ -390,9 +390,9 @@
                 pkgs.cargo-deny
                 pkgs.cargo-machete
                 pkgs.cargo-make
+                pkgs.cargo-semver-checks
                 pkgs.typos
                 pkgs.markdownlint-cli2
-                pkgs.python313Packages.msgpack # For sequence decoder
               ]
               ++ pkgs.lib.optionals pkgs.stdenv.hostPlatform.isLinux [
                 pkgs.cargo-llvm-cov
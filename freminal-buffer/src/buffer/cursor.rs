@@ -7,7 +7,8 @@
 //!
 //! Covers absolute cursor placement (`set_cursor_pos`, `set_cursor_pos_raw`),
 //! relative movement (`move_cursor_relative`), screen-coordinate projection
-//! (`get_cursor_screen_pos`, `cursor_screen_y`), and DECSC/DECRC save/restore.
+//! (`get_cursor_screen_pos`, `cursor_screen_y`), DECSC/DECRC save/restore, and
+//! the pending-wrap state DECCIR reports and DECRSPS restores.
 
 use freminal_common::buffer_states::{
     cursor::CursorPos,
@@ -66,6 +67,35 @@ impl Buffer {
         }
     }
 
+    /// Returns `true` when the last column has been written and the next
+    /// printable character wraps (Freminal keeps the cursor at `x == width`).
+    #[must_use]
+    pub const fn is_wrap_pending(&self) -> bool {
+        self.width > 0 && self.cursor.pos.x >= self.width
+    }
+
+    /// Move the cursor to (`x`, `y`) on the screen, ignoring DECOM (DECRSPS).
+    ///
+    /// Both are 0-indexed and clamped to the screen.  With `wrap_pending`
+    /// set and `x` on the last column, the cursor is left waiting to wrap,
+    /// as it would be after writing that column.
+    pub fn set_cursor_screen_pos(&mut self, x: usize, y: usize, wrap_pending: bool) {
+        let last_col = self.width.saturating_sub(1);
+        let x = x.min(last_col);
+        let buffer_y = self.visible_window_start(0) + y.min(self.height.saturating_sub(1));
+        while buffer_y >= self.rows.len() {
+            self.push_row(RowOrigin::ScrollFill, RowJoin::NewLogicalLine);
+        }
+
+        self.cursor.pos.x = if wrap_pending && x == last_col {
+            self.width
+        } else {
+            x
+        };
+        self.cursor.pos.y = buffer_y;
+        self.debug_assert_invariants();
+    }
+
     /// Move cursor to absolute position (CUP, HVP).
     ///
     /// `x` and `y` are 0-indexed screen coordinates.  `None` means "leave this
@@ -6,7 +6,8 @@
 //! Erase operations for [`Buffer`].
 //!
 //! Covers character erase (ECH), line erase (EL 0/1/2), display erase
-//! (ED 0/1/2), scrollback erase (ED 3), and the internal helper
+//! (ED 0/1/2), scrollback erase (ED 3), their selective forms (DECSEL,
+//! DECSED) that spare DECSCA-protected cells, and the internal helper
 //! `collect_and_clear_image_ids_in_rows` that sweeps non-Kitty image
 //! placements before any bulk clear.
 
@@ -312,6 +313,67 @@ impl Buffer {
         self.debug_assert_invariants();
     }
 
+    /// Selectively erase from cursor to end of display (DECSED 0).
+    ///
+    /// Like the DECSEL/DECSED siblings below, this blanks only the cells
+    /// DECSCA does not protect and leaves every cell's attributes alone.
+    pub fn selective_erase_to_end_of_display(&mut self) {
+        let cursor_y = self.cursor.pos.y;
+        let visible_end = self.visible_window_start(0) + self.height;
+        self.selective_erase_span(cursor_y, self.cursor.pos.x, self.width);
+        for row in (cursor_y + 1)..visible_end {
+            self.selective_erase_span(row, 0, self.width);
+        }
+        self.debug_assert_invariants();
+    }
+
+    /// Selectively erase from beginning of display to cursor (DECSED 1).
+    pub fn selective_erase_to_beginning_of_display(&mut self) {
+        let cursor_y = self.cursor.pos.y;
+        for row in self.visible_window_start(0)..cursor_y {
+            self.selective_erase_span(row, 0, self.width);
+        }
+        self.selective_erase_span(cursor_y, 0, self.cursor.pos.x + 1);
+        self.debug_assert_invariants();
+    }
+
+    /// Selectively erase the entire display (DECSED 2).
+    pub fn selective_erase_display(&mut self) {
+        let visible_start = self.visible_window_start(0);
+        for row in visible_start..visible_start + self.height {
+            self.selective_erase_span(row, 0, self.width);
+        }
+        self.debug_assert_invariants();
+    }
+
+    /// Selectively erase from cursor to end of line (DECSEL 0).
+    pub fn selective_erase_line_to_end(&mut self) {
+        self.selective_erase_span(self.cursor.pos.y, self.cursor.pos.x, self.width);
+        self.debug_assert_invariants();
+    }
+
+    /// Selectively erase from beginning of line to cursor (DECSEL 1).
+    pub fn selective_erase_line_to_beginning(&mut self) {
+        self.selective_erase_span(self.cursor.pos.y, 0, self.cursor.pos.x + 1);
+        self.debug_assert_invariants();
+    }
+
+    /// Selectively erase the entire line (DECSEL 2).
+    pub fn selective_erase_line(&mut self) {
+        self.selective_erase_span(self.cursor.pos.y, 0, self.width);
+        self.debug_assert_invariants();
+    }
+
+    /// Blank the unprotected cells of `[left, right)` on buffer row `row`.
+    ///
+    /// Rows that do not exist yet hold nothing to erase.  Images are not
+    /// swept as a whole: a protected image cell has to survive.
+    fn selective_erase_span(&mut self, row: usize, left: usize, right: usize) {
+        if let Some(target) = self.rows.get_mut(row) {
+            self.image_cell_count -= target.selective_erase(left, right);
+        }
+    }
+
     /// Scan rows in `[row_start..row_end)` for image cells and clear every
     /// cell of each found image across the entire buffer.
     ///
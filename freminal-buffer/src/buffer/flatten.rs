@@ -1225,7 +1225,9 @@ impl Buffer {
                         end: char_idx + 1,
                         colors: cell_tag.colors,
                         font_weight: cell_tag.font_weight,
-                        font_decorations: cell_tag.font_decorations,
+                        // Protection is not visual, and `tags_same_format`
+                        // ignores it.
+                        font_decorations: cell_tag.font_decorations.without_protection(),
                         url: cell_tag.url.clone(),
                         blink: cell_tag.blink,
                     });
@@ -1236,7 +1238,7 @@ impl Buffer {
                     end: char_idx + 1,
                     colors: cell_tag.colors,
                     font_weight: cell_tag.font_weight,
-                    font_decorations: cell_tag.font_decorations,
+                    font_decorations: cell_tag.font_decorations.without_protection(),
                     url: cell_tag.url.clone(),
                     blink: cell_tag.blink,
                 });
@@ -305,6 +305,32 @@ impl Buffer {
         None
     }
 
+    /// Record the command text reported by the shell on the newest block
+    /// whose `fid` matches.  No-op if no matching block exists.
+    pub fn set_command_line(&mut self, fid: &str, command_line: String) {
+        if let Some(block) = self.command_blocks.iter_mut().rev().find(|b| b.fid == fid) {
+            block.command_line = Some(command_line);
+        }
+    }
+
+    /// Remove the newest block whose `fid` matches, together with the
+    /// prompt-row marker recorded for it.  Used when a block opened from a
+    /// guessed marker turns out to be a duplicate.  No-op if no matching
+    /// block exists.
+    pub fn discard_command_block(&mut self, fid: &str) {
+        let Some(index) = self.command_blocks.iter().rposition(|b| b.fid == fid) else {
+            return;
+        };
+        if let Some(block) = self.command_blocks.remove(index)
+            && let Some(row) = self
+                .prompt_rows
+                .iter()
+                .rposition(|r| *r == block.prompt_start_row)
+        {
+            self.prompt_rows.remove(row);
+        }
+    }
+
     /// Read-only view of all stored command blocks, oldest first.
     #[must_use]
     pub const fn command_blocks(&self) -> &VecDeque<CommandBlock> {
@@ -799,6 +825,44 @@ mod command_block_tests {
         );
     }
 
+    // ── 18: set_command_line records the text on the matching block ───────
+
+    #[test]
+    fn set_command_line_targets_matching_block() {
+        let mut buf = make_buf();
+        buf.start_command_block(None, "fid-a".to_owned());
+        buf.start_command_block(None, "fid-b".to_owned());
+
+        buf.set_command_line("fid-a", "make test".to_owned());
+        buf.set_command_line("fid-z", "ignored".to_owned());
+
+        assert_eq!(
+            buf.command_blocks[0].command_line.as_deref(),
+            Some("make test")
+        );
+        assert_eq!(buf.command_blocks[1].command_line, None);
+    }
+
+    // ── 19: discard_command_block removes the block and its prompt row ────
+
+    #[test]
+    fn discard_command_block_removes_block_and_prompt_row() {
+        let mut buf = make_buf();
+        buf.cursor.pos.y = 1;
+        buf.mark_prompt_row();
+        buf.start_command_block(None, "fid-a".to_owned());
+        buf.cursor.pos.y = 4;
+        buf.mark_prompt_row();
+        buf.start_command_block(None, "fid-b".to_owned());
+
+        buf.discard_command_block("fid-b");
+        buf.discard_command_block("fid-z");
+
+        assert_eq!(buf.command_blocks.len(), 1);
+        assert_eq!(buf.command_blocks[0].fid, "fid-a");
+        assert_eq!(buf.prompt_rows(), &[1]);
+    }
+
     // ── 14: erase_display drops command_blocks on visible rows ───────────
 
     #[test]
@@ -867,6 +931,7 @@ mod command_block_tests {
             executed_at: Some(SystemTime::now()),
             finished_at: Some(SystemTime::now()),
             cwd: None,
+            command_line: None,
             exit_code: Some(0),
         };
         buf.command_blocks.push_back(block);
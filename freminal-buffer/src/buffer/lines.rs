@@ -8,7 +8,8 @@
 //! Covers backspace (`handle_backspace`, `reverse_wrap_up`), line feed
 //! variants (`handle_lf`, `handle_ind`, `handle_nel`, `handle_ri`), screen
 //! alignment test (`screen_alignment_test`), and insert/delete line/character
-//! operations (`insert_lines`, `delete_lines`, `insert_spaces`, `delete_chars`).
+//! operations (`insert_lines`, `delete_lines`, `insert_spaces`, `delete_chars`,
+//! `insert_columns`, `delete_columns`).
 
 use freminal_common::buffer_states::{
     buffer_type::BufferType,
@@ -18,6 +19,7 @@ use freminal_common::buffer_states::{
     },
 };
 
+use crate::cell::Cell;
 use crate::row::{Row, RowJoin, RowOrigin};
 
 use crate::buffer::Buffer;
@@ -556,4 +558,111 @@ impl Buffer {
         self.image_cell_count -= remaining_images;
         self.debug_assert_invariants();
     }
+
+    /// Implements DECIC – Insert Column.
+    ///
+    /// Inserts `n` blank columns at the cursor column on every row of the
+    /// scrolling region.  The columns up to the right margin move right and
+    /// those pushed past it are lost.  Ignored when the cursor is outside
+    /// the margins; the cursor does not move.
+    pub fn insert_columns(&mut self, n: usize) {
+        let Some((top, bottom, col, right)) = self.column_shift_area() else {
+            return;
+        };
+        let n = n.min(right - col);
+        if n == 0 {
+            return;
+        }
+
+        self.shift_columns(top..=bottom, col, right, right - n, |cells| {
+            cells.rotate_right(n);
+            0..n
+        });
+    }
+
+    /// Implements DECDC – Delete Column.
+    ///
+    /// Deletes `n` columns at the cursor column on every row of the
+    /// scrolling region.  The columns up to the right margin move left and
+    /// blank columns come in at the margin.  Ignored when the cursor is
+    /// outside the margins; the cursor does not move.
+    pub fn delete_columns(&mut self, n: usize) {
+        let Some((top, bottom, col, right)) = self.column_shift_area() else {
+            return;
+        };
+        let n = n.min(right - col);
+        if n == 0 {
+            return;
+        }
+
+        self.shift_columns(top..=bottom, col, right, col + n, |cells| {
+            cells.rotate_left(n);
+            cells.len() - n..cells.len()
+        });
+    }
+
+    /// The area DECIC / DECDC work on: the first and last buffer rows of the
+    /// scrolling region and the columns from the cursor to the right margin
+    /// (end exclusive).  `None` when the cursor is outside the margins.
+    fn column_shift_area(&mut self) -> Option<(usize, usize, usize, usize)> {
+        let (left, right) = if self.declrmm_enabled == Declrmm::Enabled {
+            (self.scroll_region_left, self.scroll_region_right + 1)
+        } else {
+            (0, self.width)
+        };
+        let col = self.cursor.pos.x;
+        let sy = self.cursor_screen_y();
+        if col < left
+            || col >= right
+            || sy < self.scroll_region_top
+            || sy > self.scroll_region_bottom
+        {
+            return None;
+        }
+
+        let (top, bottom) = self.scroll_region_rows();
+        Some((top, bottom, col, right))
+    }
+
+    /// Shift the cells in `[col, right)` of each row in `rows` with `shift`,
+    /// which returns the range of the slice to blank afterwards.
+    ///
+    /// `cut` is the column where the cells that leave the area part from
+    /// those that stay.  Wide glyphs straddling `col`, `cut` or `right` are
+    /// blanked first, and sixel / iTerm2 images touching the area are
+    /// removed whole; Kitty cells move with the text.
+    fn shift_columns(
+        &mut self,
+        rows: std::ops::RangeInclusive<usize>,
+        col: usize,
+        right: usize,
+        cut: usize,
+        shift: impl Fn(&mut [Cell]) -> std::ops::Range<usize>,
+    ) {
+        let tag = self.current_tag.clone();
+        for row in rows {
+            self.collect_and_clear_image_ids_in_rows(row, row + 1, Some(col), Some(right));
+            let images_before = if self.image_cell_count > 0 {
+                self.rows[row].count_image_cells_in_range(col, right)
+            } else {
+                0
+            };
+
+            self.rows[row].clear_wide_glyphs_crossing(col, right);
+            self.rows[row].clear_wide_glyphs_crossing(cut, cut);
+            self.rows[row].update_cells_in(col, right, |cells| {
+                let vacated = shift(cells);
+                for cell in &mut cells[vacated] {
+                    *cell = Cell::blank_with_tag(tag.clone());
+                }
+            });
+
+            if images_before > 0 {
+                let images_after = self.rows[row].count_image_cells_in_range(col, right);
+                self.image_cell_count -= images_before.saturating_sub(images_after);
+            }
+        }
+
+        self.debug_assert_invariants();
+    }
 }
@@ -43,6 +43,7 @@ mod flatten;
 mod images;
 mod lifecycle;
 mod lines;
+mod rect;
 mod resize_and_alt;
 mod scroll;
 mod tabs;
@@ -953,7 +954,7 @@ impl Buffer {
 pub(in crate::buffer) fn tags_same_format(a: &FormatTag, b: &FormatTag) -> bool {
     a.colors == b.colors
         && a.font_weight == b.font_weight
-        && a.font_decorations == b.font_decorations
+        && a.font_decorations.without_protection() == b.font_decorations.without_protection()
         && a.url == b.url
         && a.blink == b.blink
 }
@@ -4681,6 +4682,43 @@ mod image_tests {
             "each of the 3 image rows should be clipped from 10 to 8 columns"
         );
     }
+
+    #[test]
+    fn column_shift_removes_sixel_images_in_the_area() {
+        let mut buf = Buffer::new(20, 10);
+        let img = make_image(4, 2);
+        let img_id = img.id;
+        buf.cursor.pos = CursorPos { x: 2, y: 0 };
+        buf.place_image(img, 0, ImageProtocol::Sixel, None, None, 0, None, 1, None);
+        assert_eq!(buf.image_cell_count, 8);
+
+        // The image straddles the cursor column: it goes whole, including
+        // the cells left of the cut.
+        buf.cursor.pos = CursorPos { x: 4, y: 0 };
+        buf.insert_columns(1);
+        assert_eq!(count_image_cells(&buf, img_id), 0);
+        assert_eq!(buf.image_cell_count, 0);
+    }
+
+    #[test]
+    fn column_shift_keeps_kitty_image_count_in_step() {
+        let mut buf = Buffer::new(10, 5);
+        let img = make_image(3, 1);
+        let img_id = img.id;
+        buf.cursor.pos = CursorPos { x: 6, y: 0 };
+        buf.place_image(img, 0, ImageProtocol::Kitty, None, None, 0, None, 1, None);
+        assert_eq!(buf.image_cell_count, 3);
+
+        // One of the three cells is pushed off the right edge.
+        buf.cursor.pos = CursorPos { x: 0, y: 0 };
+        buf.insert_columns(2);
+        assert_eq!(count_image_cells(&buf, img_id), 2);
+        assert_eq!(buf.image_cell_count, 2);
+
+        buf.delete_columns(10);
+        assert_eq!(count_image_cells(&buf, img_id), 0);
+        assert_eq!(buf.image_cell_count, 0);
+    }
 }
 
 // ============================================================================
@@ -6195,7 +6233,7 @@ mod reflow_to_width_tests {
 #[cfg(test)]
 mod erase_operations_tests {
     use super::*;
-    use freminal_common::buffer_states::tchar::TChar;
+    use freminal_common::buffer_states::{fonts::FontWeight, tchar::TChar};
 
     fn t(s: &str) -> Vec<TChar> {
         s.bytes().map(TChar::Ascii).collect()
@@ -6464,6 +6502,122 @@ mod erase_operations_tests {
             "entire line must be blanked when cursor is at col 0; got {row:?}"
         );
     }
+
+    // -------------------------------------------------------------------------
+    // DECSED / DECSEL tests
+    // -------------------------------------------------------------------------
+
+    /// Write `text` with the DECSCA protected attribute, then switch it off.
+    fn write_protected(buf: &mut Buffer, text: &str) {
+        let mut protected = FormatTag::default();
+        protected.set_protected(true);
+        buf.set_format(protected);
+        buf.insert_text(&t(text));
+        buf.set_format(FormatTag::default());
+    }
+
+    #[test]
+    fn selective_erase_line_spares_protected_cells() {
+        let mut buf = Buffer::new(10, 5);
+        buf.insert_text(&t("ab"));
+        write_protected(&mut buf, "CD");
+        buf.insert_text(&t("ef"));
+
+        buf.selective_erase_line();
+
+        let vis_start = buf.visible_window_start(0);
+        assert_eq!(cell_str(&buf, vis_start), "  CD      ");
+        assert!(buf.rows[vis_start].resolve_cell(2).tag().is_protected());
+    }
+
+    #[test]
+    fn selective_erase_line_to_end_and_beginning() {
+        let mut buf = Buffer::new(10, 5);
+        write_protected(&mut buf, "P");
+        buf.insert_text(&t("abcdefghi"));
+        let vis_start = buf.visible_window_start(0);
+        buf.cursor.pos.x = 4;
+
+        buf.selective_erase_line_to_beginning();
+        assert_eq!(cell_str(&buf, vis_start), "P    efghi");
+
+        buf.selective_erase_line_to_end();
+        assert_eq!(cell_str(&buf, vis_start), "P         ");
+    }
+
+    #[test]
+    fn selective_erase_keeps_attributes_of_erased_cells() {
+        let mut buf = Buffer::new(10, 5);
+        let bold = FormatTag {
+            font_weight: FontWeight::Bold,
+            ..FormatTag::default()
+        };
+        buf.set_format(bold.clone());
+        buf.insert_text(&t("abc"));
+
+        buf.selective_erase_line();
+
+        let vis_start = buf.visible_window_start(0);
+        assert_eq!(cell_str(&buf, vis_start), "          ");
+        assert_eq!(buf.rows[vis_start].resolve_cell(1).tag(), &bold);
+    }
+
+    #[test]
+    fn selective_erase_display_modes() {
+        let mut buf = Buffer::new(10, 5);
+        write_line(&mut buf, "AAAAAAAAAA");
+        write_protected(&mut buf, "PP");
+        write_line(&mut buf, "BBBBBBBB");
+        write_line(&mut buf, "CCCCCCCCCC");
+        let vis_start = buf.visible_window_start(0);
+
+        buf.cursor.pos.y = vis_start + 1;
+        buf.cursor.pos.x = 4;
+        buf.selective_erase_to_end_of_display();
+        assert_eq!(cell_str(&buf, vis_start), "AAAAAAAAAA");
+        assert_eq!(cell_str(&buf, vis_start + 1), "PPBB      ");
+        assert_eq!(cell_str(&buf, vis_start + 2), "          ");
+
+        buf.selective_erase_to_beginning_of_display();
+        assert_eq!(cell_str(&buf, vis_start), "          ");
+        assert_eq!(cell_str(&buf, vis_start + 1), "PP        ");
+
+        buf.selective_erase_display();
+        assert_eq!(cell_str(&buf, vis_start + 1), "PP        ");
+    }
+
+    #[test]
+    fn selective_erase_keeps_protected_wide_glyph_whole() {
+        let mut buf = Buffer::new(10, 5);
+        buf.insert_text(&t("a"));
+        let mut protected = FormatTag::default();
+        protected.set_protected(true);
+        buf.set_format(protected);
+        buf.insert_text(&[TChar::from('中')]);
+        buf.set_format(FormatTag::default());
+        buf.insert_text(&t("b"));
+        let vis_start = buf.visible_window_start(0);
+
+        // Cursor on the continuation cell: the glyph is still protected.
+        buf.cursor.pos.x = 2;
+        buf.selective_erase_line_to_end();
+        let row = &buf.rows[vis_start];
+        assert_eq!(row.resolve_cell(0).tchar(), &TChar::Ascii(b'a'));
+        assert_eq!(row.resolve_cell(1).tchar(), &TChar::from('中'));
+        assert!(row.resolve_cell(2).is_continuation());
+        assert_eq!(row.resolve_cell(3).tchar(), &TChar::Space);
+    }
+
+    #[test]
+    fn plain_erase_ignores_protection() {
+        let mut buf = Buffer::new(10, 5);
+        write_protected(&mut buf, "PROTECTED");
+
+        buf.erase_line();
+
+        let vis_start = buf.visible_window_start(0);
+        assert_eq!(cell_str(&buf, vis_start), "          ");
+    }
 }
 
 // ============================================================================
@@ -7565,6 +7719,111 @@ mod column_scroll_and_misc_tests {
         assert_eq!(cell_char(&buf, 0, 7), ascii('H'));
     }
 
+    /// Fill the first `rows` rows with `text`, one copy per row.
+    fn fill_rows(buf: &mut Buffer, rows: usize, text: &str) {
+        for r in 0..rows {
+            buf.cursor.pos = CursorPos { x: 0, y: r };
+            buf.insert_text(&t(text));
+        }
+    }
+
+    fn row_text(buf: &Buffer, row: usize) -> String {
+        (0..buf.width)
+            .map(|col| buf.rows[row].resolve_cell(col).tchar().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn insert_columns_within_region_and_margins() {
+        let mut buf = Buffer::new(10, 5);
+        fill_rows(&mut buf, 5, "ABCDEFGHIJ");
+        buf.set_scroll_region(2, 4);
+        buf.set_declrmm(Declrmm::Enabled);
+        buf.scroll_region_left = 2;
+        buf.scroll_region_right = 6;
+
+        buf.cursor.pos = CursorPos { x: 3, y: 2 };
+        buf.insert_columns(2);
+
+        assert_eq!(row_text(&buf, 0), "ABCDEFGHIJ");
+        for row in 1..4 {
+            assert_eq!(row_text(&buf, row), "ABC  DEHIJ");
+        }
+        assert_eq!(row_text(&buf, 4), "ABCDEFGHIJ");
+        assert_eq!(buf.cursor.pos, CursorPos { x: 3, y: 2 });
+    }
+
+    #[test]
+    fn delete_columns_within_region_and_margins() {
+        let mut buf = Buffer::new(10, 5);
+        fill_rows(&mut buf, 5, "ABCDEFGHIJ");
+        buf.set_scroll_region(2, 4);
+        buf.set_declrmm(Declrmm::Enabled);
+        buf.scroll_region_left = 2;
+        buf.scroll_region_right = 6;
+
+        buf.cursor.pos = CursorPos { x: 3, y: 2 };
+        buf.delete_columns(10);
+
+        assert_eq!(row_text(&buf, 0), "ABCDEFGHIJ");
+        for row in 1..4 {
+            assert_eq!(row_text(&buf, row), "ABC    HIJ");
+        }
+        assert_eq!(row_text(&buf, 4), "ABCDEFGHIJ");
+    }
+
+    #[test]
+    fn column_shift_outside_margins_is_ignored() {
+        let mut buf = Buffer::new(10, 3);
+        fill_rows(&mut buf, 3, "ABCDEFGHIJ");
+        buf.set_declrmm(Declrmm::Enabled);
+        buf.scroll_region_left = 2;
+        buf.scroll_region_right = 6;
+
+        buf.cursor.pos = CursorPos { x: 1, y: 0 };
+        buf.insert_columns(1);
+        buf.delete_columns(1);
+
+        for row in 0..3 {
+            assert_eq!(row_text(&buf, row), "ABCDEFGHIJ");
+        }
+    }
+
+    #[test]
+    fn column_shift_blanks_cut_wide_glyphs() {
+        let mut buf = Buffer::new(6, 1);
+        buf.insert_text(&[
+            TChar::Ascii(b'A'),
+            TChar::from('あ'),
+            TChar::from('い'),
+            TChar::Ascii(b'B'),
+        ]);
+
+        // The cursor lands on the continuation of あ, and い is pushed half
+        // off the right edge: both are blanked, B falls off.
+        buf.cursor.pos = CursorPos { x: 2, y: 0 };
+        buf.insert_columns(2);
+        assert_eq!(row_text(&buf, 0), "A     ");
+
+        let mut buf = Buffer::new(6, 1);
+        buf.insert_text(&[
+            TChar::Ascii(b'A'),
+            TChar::from('あ'),
+            TChar::from('い'),
+            TChar::Ascii(b'B'),
+        ]);
+
+        // Deleting A and the head of あ would orphan its continuation, so
+        // あ is blanked; い and B move left whole.
+        buf.cursor.pos = CursorPos { x: 0, y: 0 };
+        buf.delete_columns(2);
+        let row = &buf.rows[0];
+        assert_eq!(row.resolve_cell(0).tchar(), &TChar::Space);
+        assert_eq!(row.resolve_cell(1).tchar(), &TChar::from('い'));
+        assert!(row.resolve_cell(2).is_continuation());
+        assert_eq!(row.resolve_cell(3).tchar(), &TChar::Ascii(b'B'));
+    }
+
     #[test]
     fn erase_chars_with_declrmm() {
         let mut buf = Buffer::new(10, 5);
@@ -7621,6 +7880,33 @@ mod column_scroll_and_misc_tests {
         // Row 2 should have what was row 0 ('A')
         assert_eq!(cell_char(&buf, 2, 0), ascii('A'));
     }
+
+    #[test]
+    fn tab_stop_columns_round_trip() {
+        let mut buf = Buffer::new(20, 5);
+        assert_eq!(buf.tab_stop_columns(), vec![8, 16]);
+
+        buf.set_tab_stop_columns(&[3, 12, 40]);
+        assert_eq!(buf.tab_stop_columns(), vec![3, 12]);
+        buf.advance_to_next_tab_stop();
+        assert_eq!(buf.cursor.pos.x, 3);
+    }
+
+    #[test]
+    fn set_cursor_screen_pos_ignores_origin_mode_and_keeps_pending_wrap() {
+        let mut buf = Buffer::new(10, 5);
+        buf.set_scroll_region(2, 4);
+        buf.set_decom(Decom::OriginMode);
+
+        buf.set_cursor_screen_pos(9, 0, true);
+        assert_eq!(buf.cursor_screen_pos().y, 0);
+        assert_eq!(buf.cursor.pos.x, 10);
+        assert!(buf.is_wrap_pending());
+
+        buf.set_cursor_screen_pos(4, 20, true);
+        assert_eq!(buf.cursor_screen_pos(), CursorPos { x: 4, y: 4 });
+        assert!(!buf.is_wrap_pending());
+    }
 }
 
 // ════════════════════════════════════════════════════════════════════════════
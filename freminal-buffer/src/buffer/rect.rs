@@ -0,0 +1,702 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Rectangular area operations for [`Buffer`].
+//!
+//! Covers the VT420 rectangle family: copy (DECCRA), fill (DECFRA), erase
+//! (DECERA), selective erase (DECSERA), and attribute change / reverse
+//! (DECCARA / DECRARA), plus the XTREPORTSGR and DECRQCRA queries.
+//! Rectangles arrive 1-based and inclusive; with DECOM set they are relative
+//! to the margins and clipped to them, otherwise to the screen.
+
+use freminal_common::buffer_states::{
+    format_tag::FormatTag,
+    modes::{declrmm::Declrmm, decom::Decom},
+    rectangle::{
+        AttributeChange, AttributeChangeExtent, ChecksumExtension, RectAttributes, Rectangle,
+    },
+    tchar::TChar,
+};
+
+use crate::buffer::Buffer;
+use crate::cell::Cell;
+use crate::row::{Row, RowJoin, RowOrigin};
+
+/// An area of the visible screen, 0-based and inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScreenRect {
+    top: usize,
+    left: usize,
+    bottom: usize,
+    right: usize,
+}
+
+impl Buffer {
+    /// The area rectangle coordinates are relative to and clipped to: the
+    /// margins when DECOM is set, the whole screen otherwise.
+    fn rectangle_bounds(&self) -> ScreenRect {
+        let full = ScreenRect {
+            top: 0,
+            left: 0,
+            bottom: self.height.saturating_sub(1),
+            right: self.width.saturating_sub(1),
+        };
+        if self.decom_enabled != Decom::OriginMode {
+            return full;
+        }
+
+        let (left, right) = if self.declrmm_enabled == Declrmm::Enabled {
+            (self.scroll_region_left, self.scroll_region_right)
+        } else {
+            (full.left, full.right)
+        };
+        ScreenRect {
+            top: self.scroll_region_top,
+            left,
+            bottom: self.scroll_region_bottom,
+            right,
+        }
+    }
+
+    /// Resolve a 1-based rectangle against [`Self::rectangle_bounds`].
+    ///
+    /// Returns `None` when nothing of it is left after clipping, or when the
+    /// application sent it inverted (top below bottom or left past right).
+    fn resolve_rectangle(&self, area: Rectangle) -> Option<ScreenRect> {
+        let rect = self.clip_rectangle(area);
+        (rect.top <= rect.bottom && rect.left <= rect.right).then_some(rect)
+    }
+
+    /// Translate a 1-based rectangle into screen coordinates and clip it to
+    /// [`Self::rectangle_bounds`], without checking that it is non-empty.
+    fn clip_rectangle(&self, area: Rectangle) -> ScreenRect {
+        let bounds = self.rectangle_bounds();
+        let offset = |origin: usize, value: usize, limit: usize| {
+            origin
+                .saturating_add(value.max(1) - 1)
+                .min(limit.saturating_add(1))
+        };
+
+        ScreenRect {
+            top: offset(bounds.top, area.top, bounds.bottom),
+            left: offset(bounds.left, area.left, bounds.right),
+            bottom: offset(bounds.top, area.bottom, bounds.bottom).min(bounds.bottom),
+            right: offset(bounds.left, area.right, bounds.right).min(bounds.right),
+        }
+    }
+
+    /// Make sure every screen row down to `bottom` exists and return the
+    /// buffer index of screen row 0.
+    fn materialize_screen_rows(&mut self, bottom: usize) -> usize {
+        // Rows are pushed only while the buffer is shorter than the screen,
+        // so the visible window does not move.
+        while self.rows.len() <= self.visible_window_start(0) + bottom {
+            self.push_row(RowOrigin::ScrollFill, RowJoin::NewLogicalLine);
+        }
+        self.visible_window_start(0)
+    }
+
+    /// Overwrite the cells of `rect`, one row at a time, with `fill`.
+    ///
+    /// `fill` receives the row's offset from `rect.top` and the row's cells
+    /// in the rectangle.  Images under the rectangle are removed first and
+    /// wide glyphs cut by its left or right edge are blanked.
+    fn overwrite_rectangle(&mut self, rect: ScreenRect, mut fill: impl FnMut(usize, &mut [Cell])) {
+        let start = self.materialize_screen_rows(rect.bottom);
+        let (left, right) = (rect.left, rect.right + 1);
+
+        for screen_row in rect.top..=rect.bottom {
+            let row = start + screen_row;
+            self.collect_and_clear_image_ids_in_rows(row, row + 1, Some(left), Some(right));
+            let remaining = if self.image_cell_count > 0 {
+                self.rows[row].count_image_cells_in_range(left, right)
+            } else {
+                0
+            };
+
+            self.rows[row].clear_wide_glyphs_crossing(left, right);
+            self.rows[row].update_cells_in(left, right, |cells| {
+                fill(screen_row - rect.top, cells);
+            });
+            self.image_cell_count -= remaining;
+        }
+
+        self.debug_assert_invariants();
+    }
+
+    /// Apply `update` to the format tag of every cell DECCARA / DECRARA
+    /// reach: the rectangle itself, or in stream mode everything from its
+    /// top-left to its bottom-right corner, wrapping at the bounds.  A
+    /// stream spanning several rows may end left of where it starts.
+    fn update_rectangle_tags(
+        &mut self,
+        area: Rectangle,
+        extent: AttributeChangeExtent,
+        update: impl Fn(&mut FormatTag),
+    ) {
+        let rect = self.clip_rectangle(area);
+        let valid = rect.top <= rect.bottom
+            && (rect.left <= rect.right
+                || (extent == AttributeChangeExtent::Stream && rect.top < rect.bottom));
+        if !valid {
+            return;
+        }
+        let bounds = self.rectangle_bounds();
+        let start = self.materialize_screen_rows(rect.bottom);
+
+        for screen_row in rect.top..=rect.bottom {
+            let (left, right) = match extent {
+                AttributeChangeExtent::Rectangle => (rect.left, rect.right),
+                AttributeChangeExtent::Stream => (
+                    if screen_row == rect.top {
+                        rect.left
+                    } else {
+                        bounds.left
+                    },
+                    if screen_row == rect.bottom {
+                        rect.right
+                    } else {
+                        bounds.right
+                    },
+                ),
+            };
+            self.rows[start + screen_row].update_cells_in(left, right + 1, |cells| {
+                for cell in cells {
+                    update(cell.tag_mut());
+                }
+            });
+        }
+
+        self.debug_assert_invariants();
+    }
+
+    /// DECCRA — copy the `source` rectangle so its top-left corner lands at
+    /// (`dest_top`, `dest_left`), 1-based.
+    ///
+    /// The destination is clipped to the bounds; overlapping source and
+    /// destination copy as if through a temporary.  Images are not copied
+    /// and wide glyphs cut by the source edges arrive as blanks.
+    pub fn copy_rectangle(&mut self, source: Rectangle, dest_top: usize, dest_left: usize) {
+        let Some(src) = self.resolve_rectangle(source) else {
+            return;
+        };
+        let Some(dest) = self.resolve_rectangle(Rectangle {
+            top: dest_top,
+            left: dest_left,
+            bottom: dest_top.saturating_add(src.bottom - src.top),
+            right: dest_left.saturating_add(src.right - src.left),
+        }) else {
+            return;
+        };
+
+        let start = self.materialize_screen_rows(src.bottom);
+        let block: Vec<Vec<Cell>> = (src.top..=src.bottom)
+            .map(|screen_row| {
+                let row = &self.rows[start + screen_row];
+                (src.left..=src.right)
+                    .map(|col| {
+                        let mut cell = row.resolve_cell(col);
+                        let cut_at_left = col == src.left && cell.is_continuation();
+                        let cut_at_right = col == src.right && cell.display_width() > 1;
+                        if cell.has_image() || cut_at_left || cut_at_right {
+                            cell = Cell::blank_with_tag(cell.tag().clone());
+                        }
+                        cell
+                    })
+                    .collect()
+            })
+            .collect();
+
+        self.overwrite_rectangle(dest, |dy, cells| {
+            cells.clone_from_slice(&block[dy][..cells.len()]);
+        });
+    }
+
+    /// DECFRA — fill `area` with `ch` in the current SGR attributes.
+    pub fn fill_rectangle(&mut self, area: Rectangle, ch: char) {
+        let Some(rect) = self.resolve_rectangle(area) else {
+            return;
+        };
+        let cell = Cell::new(TChar::from(ch), self.current_tag.clone());
+        self.overwrite_rectangle(rect, |_, cells| cells.fill(cell.clone()));
+    }
+
+    /// DECERA — erase `area` to blanks with default attributes.
+    pub fn erase_rectangle(&mut self, area: Rectangle) {
+        let Some(rect) = self.resolve_rectangle(area) else {
+            return;
+        };
+        self.overwrite_rectangle(rect, |_, cells| {
+            cells.fill(Cell::blank_with_tag(FormatTag::default()));
+        });
+    }
+
+    /// DECSERA — erase the characters in `area` that DECSCA does not
+    /// protect, leaving each cell's attributes in place.
+    pub fn selective_erase_rectangle(&mut self, area: Rectangle) {
+        let Some(rect) = self.resolve_rectangle(area) else {
+            return;
+        };
+        let start = self.materialize_screen_rows(rect.bottom);
+        for screen_row in rect.top..=rect.bottom {
+            let row = start + screen_row;
+            self.image_cell_count -= self.rows[row].selective_erase(rect.left, rect.right + 1);
+        }
+        self.debug_assert_invariants();
+    }
+
+    /// DECCARA — turn the attributes in `change` on and off in `area`.
+    pub fn change_rectangle_attributes(
+        &mut self,
+        area: Rectangle,
+        change: AttributeChange,
+        extent: AttributeChangeExtent,
+    ) {
+        self.update_rectangle_tags(area, extent, |tag| {
+            change.clear.apply_to(tag, false);
+            change.set.apply_to(tag, true);
+        });
+    }
+
+    /// DECRARA — reverse the attributes in `toggle` in `area`.
+    pub fn reverse_rectangle_attributes(
+        &mut self,
+        area: Rectangle,
+        toggle: RectAttributes,
+        extent: AttributeChangeExtent,
+    ) {
+        self.update_rectangle_tags(area, extent, |tag| {
+            let mut on = RectAttributes::empty();
+            let mut off = RectAttributes::empty();
+            for attr in [
+                RectAttributes::BOLD,
+                RectAttributes::UNDERLINE,
+                RectAttributes::BLINK,
+                RectAttributes::INVERSE,
+            ] {
+                if toggle.contains(attr) {
+                    if RectAttributes::of(tag).contains(attr) {
+                        off.insert(attr);
+                    } else {
+                        on.insert(attr);
+                    }
+                }
+            }
+            off.apply_to(tag, false);
+            on.apply_to(tag, true);
+        });
+    }
+
+    /// The format tag of every cell in `area`, row by row (XTREPORTSGR).
+    ///
+    /// Cells past the end of a row read as default blanks and wide-glyph
+    /// continuations are skipped.  Empty when the rectangle is.
+    #[must_use]
+    pub fn rectangle_tags(&self, area: Rectangle) -> Vec<FormatTag> {
+        let Some(rect) = self.resolve_rectangle(area) else {
+            return Vec::new();
+        };
+        let rows = self.visible_rows(0);
+        let mut tags = Vec::new();
+        for screen_row in rect.top..=rect.bottom {
+            for col in rect.left..=rect.right {
+                let cell = rows.get(screen_row).map_or_else(
+                    || Cell::blank_with_tag(FormatTag::default()),
+                    |row| row.resolve_cell(col),
+                );
+                if !cell.is_continuation() {
+                    tags.push(cell.tag().clone());
+                }
+            }
+        }
+        tags
+    }
+
+    /// The DECRQCRA checksum of `area`, computed as `ext` selects.
+    ///
+    /// Each counted cell adds its character and, unless
+    /// [`ChecksumExtension::NO_ATTRIBUTES`] is set, 0x80 for bold, 0x40 for
+    /// blink, 0x20 for inverse and 0x10 for underline.  The sum is kept to
+    /// 16 bits and negated unless [`ChecksumExtension::POSITIVE`] is set.
+    /// Wide-glyph continuations never count.
+    #[must_use]
+    pub fn rectangle_checksum(&self, area: Rectangle, ext: ChecksumExtension) -> u16 {
+        let mut total: u32 = 0;
+        if let Some(rect) = self.resolve_rectangle(area) {
+            let rows = self.visible_rows(0);
+            for screen_row in rect.top..=rect.bottom {
+                let row = rows.get(screen_row);
+                let written = row.map_or(0, Row::stored_cell_count);
+                for col in rect.left..=rect.right {
+                    if col >= written && ext.contains(ChecksumExtension::SKIP_UNWRITTEN) {
+                        continue;
+                    }
+                    let cell = row.map_or_else(
+                        || Cell::blank_with_tag(FormatTag::default()),
+                        |row| row.resolve_cell(col),
+                    );
+                    total = total.wrapping_add(checksum_of(&cell, ext));
+                }
+            }
+        }
+
+        let sum = u16::try_from(total & 0xffff).unwrap_or_default();
+        if ext.contains(ChecksumExtension::POSITIVE) {
+            sum
+        } else {
+            sum.wrapping_neg()
+        }
+    }
+}
+
+/// What one cell adds to a DECRQCRA checksum.
+fn checksum_of(cell: &Cell, ext: ChecksumExtension) -> u32 {
+    if cell.is_continuation()
+        || (*cell.tchar() == TChar::Space && !ext.contains(ChecksumExtension::KEEP_BLANKS))
+    {
+        return 0;
+    }
+
+    let text = cell.into_utf8();
+    let mut chars = text.chars().map(u32::from);
+    let mut value = if ext.contains(ChecksumExtension::FULL_CODE_POINTS) {
+        chars.fold(0, u32::wrapping_add)
+    } else {
+        chars.next().unwrap_or_default() & 0xff
+    };
+
+    if !ext.contains(ChecksumExtension::NO_ATTRIBUTES) {
+        let attrs = RectAttributes::of(cell.tag());
+        for (attr, bit) in [
+            (RectAttributes::BOLD, 0x80),
+            (RectAttributes::BLINK, 0x40),
+            (RectAttributes::INVERSE, 0x20),
+            (RectAttributes::UNDERLINE, 0x10),
+        ] {
+            if attrs.contains(attr) {
+                value += bit;
+            }
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use freminal_common::buffer_states::{
+        cursor::ReverseVideo,
+        fonts::{FontWeight, UnderlineStyle},
+    };
+
+    fn rect(top: usize, left: usize, bottom: usize, right: usize) -> Rectangle {
+        Rectangle {
+            top,
+            left,
+            bottom,
+            right,
+        }
+    }
+
+    /// A 10x5 buffer whose rows read `0123456789`, `abcdefghij`, ...
+    fn filled_buf() -> Buffer {
+        let mut buf = Buffer::new(10, 5);
+        for (i, line) in [
+            "0123456789",
+            "abcdefghij",
+            "ABCDEFGHIJ",
+            "klmnopqrst",
+            "KLMNOPQRST",
+        ]
+        .iter()
+        .enumerate()
+        {
+            buf.set_cursor_pos(Some(0), Some(i));
+            let text: Vec<TChar> = line.bytes().map(TChar::from).collect();
+            buf.insert_text(&text);
+        }
+        buf
+    }
+
+    fn screen(buf: &Buffer) -> Vec<String> {
+        buf.visible_rows(0)
+            .iter()
+            .map(|row| {
+                (0..10)
+                    .map(|col| row.resolve_cell(col).into_utf8())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn tag_at(buf: &Buffer, row: usize, col: usize) -> FormatTag {
+        buf.visible_rows(0)[row].resolve_cell(col).tag().clone()
+    }
+
+    #[test]
+    fn erase_rectangle_blanks_only_the_area() {
+        let mut buf = filled_buf();
+        buf.erase_rectangle(rect(2, 3, 3, 5));
+        assert_eq!(
+            screen(&buf),
+            [
+                "0123456789",
+                "ab   fghij",
+                "AB   FGHIJ",
+                "klmnopqrst",
+                "KLMNOPQRST"
+            ]
+        );
+    }
+
+    #[test]
+    fn inverted_rectangle_is_ignored() {
+        let mut buf = filled_buf();
+        buf.erase_rectangle(rect(3, 1, 2, 10));
+        buf.erase_rectangle(rect(1, 5, 5, 4));
+        assert_eq!(screen(&buf), screen(&filled_buf()));
+    }
+
+    #[test]
+    fn oversized_rectangle_is_clipped_to_screen() {
+        let mut buf = filled_buf();
+        buf.fill_rectangle(rect(4, 9, 100, 100), '*');
+        assert_eq!(screen(&buf)[3], "klmnopqr**");
+        assert_eq!(screen(&buf)[4], "KLMNOPQR**");
+    }
+
+    #[test]
+    fn fill_uses_current_attributes() {
+        let mut buf = filled_buf();
+        let tag = FormatTag {
+            font_weight: FontWeight::Bold,
+            ..FormatTag::default()
+        };
+        buf.set_format(tag.clone());
+        buf.fill_rectangle(rect(1, 1, 1, 2), 'x');
+        assert_eq!(screen(&buf)[0], "xx23456789");
+        assert_eq!(tag_at(&buf, 0, 1), tag);
+        assert_eq!(tag_at(&buf, 0, 2), FormatTag::default());
+    }
+
+    #[test]
+    fn copy_handles_overlap() {
+        let mut buf = filled_buf();
+        buf.copy_rectangle(rect(1, 1, 2, 4), 2, 3);
+        assert_eq!(
+            screen(&buf),
+            [
+                "0123456789",
+                "ab0123ghij",
+                "ABabcdGHIJ",
+                "klmnopqrst",
+                "KLMNOPQRST"
+            ]
+        );
+    }
+
+    #[test]
+    fn copy_destination_is_clipped() {
+        let mut buf = filled_buf();
+        buf.copy_rectangle(rect(1, 1, 3, 3), 5, 9);
+        assert_eq!(screen(&buf)[4], "KLMNOPQR01");
+    }
+
+    #[test]
+    fn selective_erase_keeps_attributes() {
+        let mut buf = filled_buf();
+        buf.change_rectangle_attributes(
+            rect(1, 1, 1, 3),
+            AttributeChange::from_sgr_params(&[7]),
+            AttributeChangeExtent::Rectangle,
+        );
+        buf.selective_erase_rectangle(rect(1, 2, 1, 10));
+        assert_eq!(screen(&buf)[0], "0         ");
+        assert_eq!(tag_at(&buf, 0, 2).colors.reverse_video, ReverseVideo::On);
+        assert_eq!(tag_at(&buf, 0, 3), FormatTag::default());
+    }
+
+    #[test]
+    fn selective_erase_skips_protected_cells() {
+        let mut buf = filled_buf();
+        let mut protected = FormatTag::default();
+        protected.set_protected(true);
+        buf.set_format(protected);
+        buf.fill_rectangle(rect(2, 3, 2, 4), '#');
+        buf.selective_erase_rectangle(rect(1, 1, 2, 10));
+        assert_eq!(screen(&buf)[0], "          ");
+        assert_eq!(screen(&buf)[1], "  ##      ");
+        assert_eq!(screen(&buf)[2], "ABCDEFGHIJ");
+    }
+
+    #[test]
+    fn origin_mode_makes_coordinates_margin_relative() {
+        let mut buf = filled_buf();
+        buf.set_scroll_region(2, 4);
+        buf.set_declrmm(Declrmm::Enabled);
+        buf.set_left_right_margins(3, 8);
+        buf.set_decom(Decom::OriginMode);
+        buf.fill_rectangle(rect(1, 1, 100, 2), '#');
+        assert_eq!(
+            screen(&buf),
+            [
+                "0123456789",
+                "ab##efghij",
+                "AB##EFGHIJ",
+                "kl##opqrst",
+                "KLMNOPQRST"
+            ]
+        );
+        buf.erase_rectangle(rect(3, 5, 3, 100));
+        assert_eq!(screen(&buf)[3], "kl##op  st");
+    }
+
+    #[test]
+    fn stream_extent_wraps_at_bounds() {
+        let mut buf = filled_buf();
+        buf.change_rectangle_attributes(
+            rect(1, 8, 2, 2),
+            AttributeChange::from_sgr_params(&[1]),
+            AttributeChangeExtent::Stream,
+        );
+        let bold = |row, col| tag_at(&buf, row, col).font_weight == FontWeight::Bold;
+        assert!(!bold(0, 6));
+        assert!(bold(0, 7) && bold(0, 9));
+        assert!(bold(1, 0) && bold(1, 1));
+        assert!(!bold(1, 2));
+    }
+
+    #[test]
+    fn rectangle_extent_keeps_to_columns() {
+        let mut buf = filled_buf();
+        buf.change_rectangle_attributes(
+            rect(1, 8, 2, 9),
+            AttributeChange::from_sgr_params(&[1]),
+            AttributeChangeExtent::Rectangle,
+        );
+        let bold = |row, col| tag_at(&buf, row, col).font_weight == FontWeight::Bold;
+        assert!(bold(0, 7) && bold(1, 8));
+        assert!(!bold(1, 0) && !bold(0, 9));
+    }
+
+    #[test]
+    fn reverse_toggles_each_cell() {
+        let mut buf = filled_buf();
+        buf.change_rectangle_attributes(
+            rect(1, 1, 1, 1),
+            AttributeChange::from_sgr_params(&[4, 7]),
+            AttributeChangeExtent::Rectangle,
+        );
+        let mut toggle = RectAttributes::UNDERLINE;
+        toggle.insert(RectAttributes::INVERSE);
+        buf.reverse_rectangle_attributes(
+            rect(1, 1, 1, 2),
+            toggle,
+            AttributeChangeExtent::Rectangle,
+        );
+
+        assert_eq!(tag_at(&buf, 0, 0), FormatTag::default());
+        let second = tag_at(&buf, 0, 1);
+        assert_eq!(second.colors.reverse_video, ReverseVideo::On);
+        assert_eq!(
+            second.font_decorations.underline_style(),
+            UnderlineStyle::Single
+        );
+    }
+
+    #[test]
+    fn change_keeps_existing_underline_style() {
+        let mut buf = filled_buf();
+        let mut tag = FormatTag::default();
+        tag.font_decorations
+            .set_underline_style(UnderlineStyle::Curly);
+        buf.set_format(tag);
+        buf.fill_rectangle(rect(1, 1, 1, 1), 'x');
+        buf.change_rectangle_attributes(
+            rect(1, 1, 1, 1),
+            AttributeChange::from_sgr_params(&[4]),
+            AttributeChangeExtent::Rectangle,
+        );
+        assert_eq!(
+            tag_at(&buf, 0, 0).font_decorations.underline_style(),
+            UnderlineStyle::Curly
+        );
+    }
+
+    #[test]
+    fn erase_edge_through_wide_glyph_blanks_it() {
+        let mut buf = Buffer::new(10, 2);
+        buf.insert_text(&[TChar::from('a'), TChar::from('漢'), TChar::from('b')]);
+        buf.erase_rectangle(rect(1, 3, 1, 10));
+        assert_eq!(screen(&buf)[0], "a         ");
+        assert!(
+            buf.visible_rows(0)[0]
+                .cells()
+                .iter()
+                .all(|cell| !cell.is_continuation())
+        );
+    }
+
+    #[test]
+    fn checksum_sums_characters_and_attributes() {
+        let mut buf = filled_buf();
+        let positive = ChecksumExtension::from_param(1);
+        // '0' + '1' + 'a' + 'b'
+        let plain = 0x30 + 0x31 + 0x61 + 0x62;
+        assert_eq!(buf.rectangle_checksum(rect(1, 1, 2, 2), positive), plain);
+        assert_eq!(
+            buf.rectangle_checksum(rect(1, 1, 2, 2), ChecksumExtension::default()),
+            plain.wrapping_neg()
+        );
+
+        buf.change_rectangle_attributes(
+            rect(1, 1, 1, 1),
+            AttributeChange::from_sgr_params(&[1, 7]),
+            AttributeChangeExtent::Rectangle,
+        );
+        assert_eq!(
+            buf.rectangle_checksum(rect(1, 1, 2, 2), positive),
+            plain + 0x80 + 0x20
+        );
+        assert_eq!(
+            buf.rectangle_checksum(rect(1, 1, 2, 2), ChecksumExtension::from_param(3)),
+            plain
+        );
+    }
+
+    #[test]
+    fn checksum_blanks_count_only_when_asked() {
+        let buf = Buffer::new(10, 5);
+        let area = rect(1, 1, 2, 3);
+        assert_eq!(
+            buf.rectangle_checksum(area, ChecksumExtension::from_param(1)),
+            0
+        );
+        assert_eq!(
+            buf.rectangle_checksum(area, ChecksumExtension::from_param(5)),
+            6 * 0x20
+        );
+        assert_eq!(
+            buf.rectangle_checksum(area, ChecksumExtension::from_param(13)),
+            0
+        );
+    }
+
+    #[test]
+    fn checksum_masks_code_points_unless_asked() {
+        let mut buf = Buffer::new(10, 5);
+        buf.insert_text(&TChar::from_string("\u{0100}").unwrap());
+        let area = rect(1, 1, 1, 1);
+        assert_eq!(
+            buf.rectangle_checksum(area, ChecksumExtension::from_param(1)),
+            0
+        );
+        assert_eq!(
+            buf.rectangle_checksum(area, ChecksumExtension::from_param(17)),
+            0x100
+        );
+    }
+}
@@ -6,7 +6,8 @@
 //! Tab stop manipulation for [`Buffer`].
 //!
 //! Covers HT (horizontal tab), HTS (set tab stop), TBC (clear tab stops), CBT
-//! (cursor backward tab), and CHT (cursor horizontal tab) operations. Tab
+//! (cursor backward tab), and CHT (cursor horizontal tab) operations, plus
+//! reading and replacing the whole set for DECTABSR / DECRSPS. Tab
 //! stops are stored as a `Vec<bool>` indexed by column; default stops are at
 //! every 8 columns.
 
@@ -54,6 +55,27 @@ impl Buffer {
         self.tab_stops.iter_mut().for_each(|s| *s = false);
     }
 
+    /// The columns (0-based) that have a tab stop, left to right.
+    #[must_use]
+    pub fn tab_stop_columns(&self) -> Vec<usize> {
+        self.tab_stops
+            .iter()
+            .enumerate()
+            .filter_map(|(col, &is_stop)| is_stop.then_some(col))
+            .collect()
+    }
+
+    /// Replace every tab stop with stops at `columns` (0-based).  Columns
+    /// past the right edge are ignored.
+    pub fn set_tab_stop_columns(&mut self, columns: &[usize]) {
+        self.clear_all_tab_stops();
+        for &col in columns {
+            if let Some(stop) = self.tab_stops.get_mut(col) {
+                *stop = true;
+            }
+        }
+    }
+
     /// Move cursor backward to the Ps-th previous tab stop (CBT — CSI Z).
     ///
     /// If there is no previous tab stop, moves to column 0.
@@ -113,6 +113,11 @@ impl Cell {
         &self.format
     }
 
+    /// Returns a mutable reference to the format tag of this cell.
+    pub const fn tag_mut(&mut self) -> &mut FormatTag {
+        &mut self.format
+    }
+
     /// Returns the display column width of this cell's character (0, 1, or 2).
     #[must_use]
     pub fn display_width(&self) -> usize {
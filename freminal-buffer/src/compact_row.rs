@@ -231,18 +231,18 @@ impl CompactRow {
     /// actually stored in a `Row`/`CompactRow` carries a tag derived from
     /// `TerminalHandler::current_format`, which starts as
     /// `FormatTag::default()` and is only ever mutated on its visual fields
-    /// (colors, weight, decorations, url, blink) by SGR handling; `start`/
-    /// `end` are never touched there and remain `(0, usize::MAX)` for the
-    /// lifetime of every stored cell. `start`/`end` are populated only in
-    /// the separate, ephemeral `FormatTag`s built by `buffer/flatten.rs`
-    /// from the flat character vector — a different `FormatTag` value that
-    /// never reaches `Cell`/`CompactRow` storage. Confirmed by this
-    /// module's own `assert_round_trip_exact` (used by every round-trip
-    /// test below): it compares full `Cell` equality — which includes the
-    /// full `FormatTag`, `start`/`end` included, via `Cell`'s derived
-    /// `PartialEq` — and passes for every stored tag despite `start`/`end`
-    /// never being serialized, because they are always the defaults on the
-    /// stored side already.
+    /// (colors, weight, decorations, url, blink) by SGR handling and on its
+    /// protection bit by DECSCA; `start`/`end` are never touched there and
+    /// remain `(0, usize::MAX)` for the lifetime of every stored cell.
+    /// `start`/`end` are populated only in the separate, ephemeral
+    /// `FormatTag`s built by `buffer/flatten.rs` from the flat character
+    /// vector — a different `FormatTag` value that never reaches
+    /// `Cell`/`CompactRow` storage. Confirmed by this module's own
+    /// `assert_round_trip_exact` (used by every round-trip test below): it
+    /// compares full `Cell` equality — which includes the full `FormatTag`,
+    /// `start`/`end` included, via `Cell`'s derived `PartialEq` — and passes
+    /// for every stored tag despite `start`/`end` never being serialized,
+    /// because they are always the defaults on the stored side already.
     #[must_use]
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut out = Vec::new();
@@ -530,6 +530,8 @@ const FONT_DECO_ITALIC_BIT: u8 = 0b0000_0001;
 const FONT_DECO_FAINT_BIT: u8 = 0b0000_0010;
 const FONT_DECO_STRIKETHROUGH_BIT: u8 = 0b0000_0100;
 const FONT_DECO_UNDERLINE_SHIFT: u8 = 3;
+const FONT_DECO_UNDERLINE_MASK: u8 = 0b0000_0111;
+const FONT_DECO_PROTECTED_BIT: u8 = 0b0100_0000;
 
 /// Encode via `FontDecorationFlags`'s public API (`contains`/
 /// `underline_style`) rather than reaching into its private inner `u8` —
@@ -547,6 +549,9 @@ const fn encode_font_decorations(flags: FontDecorationFlags) -> u8 {
     if flags.contains(FontDecorations::Strikethrough) {
         byte |= FONT_DECO_STRIKETHROUGH_BIT;
     }
+    if flags.is_protected() {
+        byte |= FONT_DECO_PROTECTED_BIT;
+    }
     let underline_bits: u8 = match flags.underline_style() {
         UnderlineStyle::None => 0,
         UnderlineStyle::Single => 1,
@@ -572,7 +577,8 @@ const fn decode_font_decorations(byte: u8) -> FontDecorationFlags {
     if byte & FONT_DECO_STRIKETHROUGH_BIT != 0 {
         flags.insert(FontDecorations::Strikethrough);
     }
-    let underline_bits = byte >> FONT_DECO_UNDERLINE_SHIFT;
+    flags.set_protected(byte & FONT_DECO_PROTECTED_BIT != 0);
+    let underline_bits = (byte >> FONT_DECO_UNDERLINE_SHIFT) & FONT_DECO_UNDERLINE_MASK;
     let style = match underline_bits {
         1 => UnderlineStyle::Single,
         2 => UnderlineStyle::Double,
@@ -1329,6 +1335,17 @@ mod tests {
         assert_byte_round_trip_exact(&row);
     }
 
+    #[test]
+    fn byte_round_trip_protected_cells() {
+        let mut row = Row::new(4);
+        let mut protected = FormatTag::default();
+        protected.set_protected(true);
+        row.insert_text(0, &[TChar::Ascii(b'p'), TChar::Ascii(b'q')], &protected);
+        row.insert_text(2, &[TChar::Ascii(b'u')], &FormatTag::default());
+        row.insert_text(3, &[TChar::Ascii(b'p')], &protected);
+        assert_byte_round_trip_exact(&row);
+    }
+
     #[test]
     fn byte_round_trip_wide_char_head_and_continuation() {
         let mut row = Row::new(10);
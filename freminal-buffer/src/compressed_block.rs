@@ -277,6 +277,22 @@ mod tests {
         assert_block_round_trip_exact(&[row]);
     }
 
+    #[test]
+    fn round_trip_protected_cells() {
+        let mut row = Row::new(10);
+        let mut protected_tag = freminal_common::buffer_states::format_tag::FormatTag::default();
+        protected_tag.set_protected(true);
+        row.insert_text(0, &[TChar::Ascii(b'a')], &protected_tag);
+        row.insert_text(
+            1,
+            &[TChar::Ascii(b'b')],
+            &freminal_common::buffer_states::format_tag::FormatTag::default(),
+        );
+        // A protected blank is not a default cell and must not be trimmed.
+        row.insert_text(2, &[TChar::Space], &protected_tag);
+        assert_block_round_trip_exact(&[row]);
+    }
+
     #[test]
     fn round_trip_blank_and_sparse_rows() {
         let rows = vec![Row::new(80), Row::new(80), ascii_row(80, "x")];
@@ -1049,6 +1049,99 @@ impl Row {
         }
     }
 
+    /// Blank any wide glyph that straddles either edge of `[left, right)`, so
+    /// the range can be overwritten without leaving half a glyph behind.
+    pub fn clear_wide_glyphs_crossing(&mut self, left: usize, right: usize) {
+        for edge in [left, right] {
+            if self.char_at(edge).is_some_and(Cell::is_continuation) {
+                self.cleanup_wide_overwrite(edge);
+            }
+        }
+    }
+
+    /// Run `f` over the cells in `[left, right)`, clamped to the row width.
+    ///
+    /// Missing cells are materialized as default blanks first, and trailing
+    /// default blanks are trimmed afterwards to keep the row sparse.  Wide
+    /// glyphs are not touched; see [`Row::clear_wide_glyphs_crossing`].
+    pub fn update_cells_in(&mut self, left: usize, right: usize, f: impl FnOnce(&mut [Cell])) {
+        let right = right.min(self.width);
+        if left >= right {
+            return;
+        }
+
+        self.dirty = true;
+
+        let cells = self.cells_vec_mut();
+        if cells.len() < right {
+            cells.resize(right, Cell::blank_with_tag(FormatTag::default()));
+        }
+
+        f(&mut cells[left..right]);
+
+        while let Some(last) = cells.last() {
+            if last.tchar() == &TChar::Space
+                && last.tag() == &FormatTag::default()
+                && !last.is_continuation()
+                && !last.has_image()
+            {
+                cells.pop();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Blank the cells in `[left, right)` that DECSCA does not protect,
+    /// keeping each cell's attributes (DECSED, DECSEL, DECSERA).
+    ///
+    /// A wide glyph is protected when its head is; an unprotected one that
+    /// straddles either edge is blanked whole.  Returns the number of image
+    /// cells blanked so the caller can keep its image count in step.
+    pub fn selective_erase(&mut self, left: usize, right: usize) -> usize {
+        let right = right.min(self.width);
+        if left >= right {
+            return 0;
+        }
+
+        for edge in [left, right] {
+            if self.char_at(edge).is_some_and(Cell::is_continuation)
+                && !self.glyph_is_protected(edge)
+            {
+                self.cleanup_wide_overwrite(edge);
+            }
+        }
+
+        let mut in_protected_glyph = self.glyph_is_protected(left);
+        let mut erased_images = 0;
+        self.update_cells_in(left, right, |cells| {
+            for cell in cells {
+                if !cell.is_continuation() {
+                    in_protected_glyph = cell.tag().is_protected();
+                }
+                if in_protected_glyph {
+                    continue;
+                }
+                if cell.has_image() {
+                    erased_images += 1;
+                }
+                *cell = Cell::blank_with_tag(cell.tag().clone());
+            }
+        });
+        erased_images
+    }
+
+    /// Whether the glyph covering `col` is protected, looking through a
+    /// wide-glyph continuation to its head.
+    fn glyph_is_protected(&self, col: usize) -> bool {
+        let cells = self.cells();
+        let mut idx = col;
+        while idx > 0 && cells.get(idx).is_some_and(Cell::is_continuation) {
+            idx -= 1;
+        }
+        cells.get(idx).is_some_and(|cell| cell.tag().is_protected())
+    }
+
     /// Like `insert_spaces_at`, but shifts only within `[col, right_limit)`.
     /// Cells at or beyond `right_limit` are not affected; cells shifted beyond
     /// the limit are discarded.  `right_limit` must be ≤ `self.width`.
@@ -3,13 +3,16 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+
+use crate::remote_control::{RemoteCommand, SplitAxis, TextExtent};
 
 /// Freminal — a modern terminal emulator written in Rust
 #[derive(Parser, Debug, Clone)]
-#[command(name = "freminal", version, about)]
+#[command(name = "freminal", version, about, disable_help_subcommand = true)]
 pub struct Args {
     /// Shell to run (overrides config file and default shell)
     #[arg(long)]
@@ -49,6 +52,13 @@ pub struct Args {
     #[arg(long = "recording-path")]
     pub recording_path: Option<PathBuf>,
 
+    /// Open a FREC v2 recording in a read-only playback window.
+    ///
+    /// The recorded windows, tabs, and panes are rebuilt and replayed with
+    /// play/pause, speed, and a timeline scrubber. No shell is started.
+    #[arg(long = "play", value_name = "FILE", conflicts_with = "recording_path")]
+    pub play: Option<PathBuf>,
+
     /// Layout to load on startup.
     ///
     /// Can be a bare name (e.g. `dev`) to load
@@ -84,6 +94,354 @@ pub struct Args {
     ///   freminal htop
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
+
+    /// Utility subcommand to run instead of opening a terminal window.
+    ///
+    /// A program whose name collides with a subcommand can still be launched
+    /// after `--` (e.g. `freminal -- msg`).
+    #[command(subcommand)]
+    pub subcommand: Option<CliCommand>,
+}
+
+/// Utility subcommands that run without opening a terminal window.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    /// Control a running Freminal instance over its remote-control socket.
+    ///
+    /// Requires `[security] allow_remote_control = true` in the target
+    /// instance's config.
+    Msg(MsgArgs),
+
+    /// Inspect, slice, and dump FREC session recordings.
+    Rec(RecArgs),
+
+    /// Run a program, a byte stream, or a recorded pane through a
+    /// windowless terminal and write the final screen.
+    Render(RenderArgs),
+}
+
+/// Arguments for `freminal msg`.
+#[derive(clap::Args, Debug, Clone, PartialEq, Eq)]
+pub struct MsgArgs {
+    /// Socket of the instance to control.
+    ///
+    /// Defaults to `$FREMINAL_LISTEN_ON`, then to the only running instance.
+    #[arg(long = "to", value_name = "SOCKET")]
+    pub to: Option<PathBuf>,
+
+    /// Print the raw JSON response instead of human-readable output.
+    #[arg(long = "json")]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: MsgCommand,
+}
+
+/// Commands accepted by `freminal msg`.
+///
+/// Pane ids come from `freminal msg ls`.  When `--pane` is omitted, the
+/// active pane of the focused window is targeted.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum MsgCommand {
+    /// List windows, tabs, and panes.
+    Ls,
+
+    /// Send text to a pane as if it had been typed.
+    ///
+    /// The text is sent verbatim; include a carriage return to run a
+    /// command (e.g. `freminal msg send-text $'make\r'`).
+    SendText {
+        #[arg(long)]
+        pane: Option<u64>,
+        text: String,
+    },
+
+    /// Print the text of a pane's visible screen.
+    GetText {
+        #[arg(long)]
+        pane: Option<u64>,
+        /// Include the scrollback above the visible screen.
+        #[arg(long)]
+        scrollback: bool,
+    },
+
+    /// Split a pane and start a new shell in the new half.
+    Split {
+        #[arg(long)]
+        pane: Option<u64>,
+        /// `vertical` places panes side by side, `horizontal` stacks them.
+        #[arg(value_enum)]
+        direction: SplitAxis,
+    },
+
+    /// Focus a pane, raising its window and switching to its tab.
+    Focus { pane: u64 },
+
+    /// Set the custom title of the tab holding a pane (omit to clear it).
+    SetTabTitle {
+        #[arg(long)]
+        pane: Option<u64>,
+        title: Option<String>,
+    },
+
+    /// Load a saved layout by name or path into the focused window.
+    LoadLayout {
+        layout: String,
+        /// Positional layout arguments (`$1`, `$2`, ...).
+        args: Vec<String>,
+        /// Override a named layout variable (`NAME=VALUE`, repeatable).
+        #[arg(long = "var", value_name = "NAME=VALUE", action = clap::ArgAction::Append)]
+        vars: Vec<String>,
+    },
+
+    /// Run a key-binding action by name (e.g. `new_tab`, `zoom_pane`).
+    Action {
+        #[arg(long)]
+        pane: Option<u64>,
+        action: String,
+    },
+
+    /// Save the focused window's flight recorder ring to a FREC file and
+    /// print its path.
+    DumpFlightRecorder,
+}
+
+impl From<MsgCommand> for RemoteCommand {
+    fn from(command: MsgCommand) -> Self {
+        match command {
+            MsgCommand::Ls => Self::Ls,
+            MsgCommand::SendText { pane, text } => Self::SendText { pane, text },
+            MsgCommand::GetText { pane, scrollback } => Self::GetText {
+                pane,
+                extent: if scrollback {
+                    TextExtent::All
+                } else {
+                    TextExtent::Screen
+                },
+            },
+            MsgCommand::Split { pane, direction } => Self::Split { pane, direction },
+            MsgCommand::Focus { pane } => Self::Focus { pane },
+            MsgCommand::SetTabTitle { pane, title } => Self::SetTabTitle { pane, title },
+            MsgCommand::LoadLayout { layout, args, vars } => Self::LoadLayout {
+                layout,
+                args,
+                vars: parse_var_pairs(&vars),
+            },
+            MsgCommand::Action { pane, action } => Self::Action { pane, action },
+            MsgCommand::DumpFlightRecorder => Self::DumpFlightRecorder,
+        }
+    }
+}
+
+/// Arguments for `freminal rec`.
+#[derive(clap::Args, Debug, Clone, PartialEq, Eq)]
+pub struct RecArgs {
+    #[command(subcommand)]
+    pub command: RecCommand,
+}
+
+/// Commands accepted by `freminal rec`.
+///
+/// Times are given in seconds from the start of the recording, either as a
+/// decimal (`12.5`) or as `m:ss` (`1:02.5`).
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum RecCommand {
+    /// Print metadata, topology, duration, and event counts.
+    Info { file: PathBuf },
+
+    /// Dump one pane's PTY stream to stdout.
+    ///
+    /// `--pane` may be omitted when the recording contains a single pane.
+    Cat {
+        file: PathBuf,
+        #[arg(long)]
+        pane: Option<u32>,
+        /// Make escape sequences and control characters visible (`^[`, `^M`, ...).
+        #[arg(long, conflicts_with = "framed")]
+        visible: bool,
+        /// Dump the input sent to the PTY instead of its output.
+        #[arg(long)]
+        input: bool,
+        /// Prefix each chunk with its length as a little-endian `u32`.
+        #[arg(long)]
+        framed: bool,
+    },
+
+    /// Write the events in a time range to a new FREC file.
+    ///
+    /// The output starts with the topology in effect at `--from` and has its
+    /// own seek index and footer.
+    Extract {
+        file: PathBuf,
+        output: PathBuf,
+        #[arg(long, value_name = "TIME", value_parser = parse_time_us)]
+        from: Option<u64>,
+        #[arg(long, value_name = "TIME", value_parser = parse_time_us)]
+        to: Option<u64>,
+    },
+
+    /// Print events as JSON lines.
+    Events {
+        file: PathBuf,
+        /// Only events involving this pane.
+        #[arg(long)]
+        pane: Option<u32>,
+        /// Only events of this type (e.g. `PtyOutput`, repeatable).
+        #[arg(long = "type", value_name = "TYPE", action = clap::ArgAction::Append)]
+        types: Vec<String>,
+        #[arg(long, value_name = "TIME", value_parser = parse_time_us)]
+        from: Option<u64>,
+        #[arg(long, value_name = "TIME", value_parser = parse_time_us)]
+        to: Option<u64>,
+    },
+
+    /// Convert one pane to an asciicast v2 (`.cast`) file.
+    ///
+    /// `--pane` may be omitted when the recording contains a single pane.
+    ExportCast {
+        file: PathBuf,
+        output: PathBuf,
+        #[arg(long)]
+        pane: Option<u32>,
+    },
+
+    /// Convert an asciicast v2 (`.cast`) file to a single-pane FREC file.
+    ImportCast { file: PathBuf, output: PathBuf },
+}
+
+/// Arguments for `freminal render`.
+///
+/// The screen comes from exactly one source: the command after the options
+/// (run on a PTY), `--input`, or `--frec`.  Rendering stops when the source
+/// ends, at `--timeout`, or as soon as the screen text matches `--until`.
+///
+/// Example:
+///   `freminal render --cols 100 --format svg -o ls.svg -- ls --color=always`
+#[derive(clap::Args, Debug, Clone, PartialEq, Eq)]
+#[command(group(
+    clap::ArgGroup::new("source")
+        .required(true)
+        .args(["command", "input", "frec"])
+))]
+pub struct RenderArgs {
+    /// Terminal width in columns [default: 80, or the recorded size for
+    /// `--frec`].
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub cols: Option<u16>,
+
+    /// Terminal height in rows [default: 24, or the recorded size for
+    /// `--frec`].
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub rows: Option<u16>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ScreenFormat::Text)]
+    pub format: ScreenFormat,
+
+    /// Write the screen to FILE instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Also write a PNG of the screen to FILE, drawn with the configured
+    /// font by the software renderer.
+    #[arg(long, value_name = "FILE")]
+    pub screenshot: Option<PathBuf>,
+
+    /// Theme to draw with (e.g. `dracula`).  Defaults to the configured
+    /// theme.
+    #[arg(long, value_name = "SLUG")]
+    pub theme: Option<String>,
+
+    /// Read terminal output from FILE (`-` for stdin) instead of running a
+    /// command.
+    #[arg(long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
+    /// Replay one pane of a FREC recording.
+    #[arg(long, value_name = "FILE")]
+    pub frec: Option<PathBuf>,
+
+    /// Pane to replay from `--frec`; may be omitted when the recording has
+    /// a single pane.
+    #[arg(long, conflicts_with_all = ["command", "input"])]
+    pub pane: Option<u32>,
+
+    /// Stop after TIME (`12.5` or `1:02.5`).  For `--frec` this is a
+    /// position in the recording.
+    #[arg(long, value_name = "TIME", value_parser = parse_time_us)]
+    pub timeout: Option<u64>,
+
+    /// Stop as soon as the screen text matches REGEX.  Exits with status 3
+    /// when the source ends or times out without a match.
+    #[arg(long, value_name = "REGEX")]
+    pub until: Option<String>,
+
+    /// Program to run, followed by its arguments.
+    #[arg(trailing_var_arg = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
+}
+
+/// Output formats for `freminal render`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ScreenFormat {
+    /// Plain text, trailing spaces removed.
+    #[default]
+    Text,
+    /// Text with the SGR sequences that reproduce its colors and styles.
+    Ansi,
+    /// An HTML `<pre>` block with inline styles.
+    Html,
+    /// An SVG image of the terminal grid.
+    Svg,
+}
+
+/// Parse a `--from`/`--to` time (`12.5` or `1:02.5`) into microseconds.
+///
+/// # Errors
+///
+/// Returns a message when the value is not a non-negative number of seconds.
+pub fn parse_time_us(value: &str) -> Result<u64, String> {
+    let (minutes, seconds) = match value.split_once(':') {
+        Some((m, s)) => (
+            m.parse::<u64>()
+                .map_err(|_| format!("invalid minutes in `{value}`"))?,
+            s,
+        ),
+        None => (0, value),
+    };
+    let (whole, frac) = seconds.split_once('.').unwrap_or((seconds, ""));
+    if frac.len() > 6 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid fraction in `{value}`"));
+    }
+    let whole = whole
+        .parse::<u64>()
+        .map_err(|_| format!("invalid seconds in `{value}`"))?;
+    let frac_us = if frac.is_empty() {
+        0
+    } else {
+        format!("{frac:0<6}")
+            .parse::<u64>()
+            .map_err(|_| format!("invalid fraction in `{value}`"))?
+    };
+    minutes
+        .checked_mul(60)
+        .and_then(|m| m.checked_add(whole))
+        .and_then(|s| s.checked_mul(1_000_000))
+        .and_then(|us| us.checked_add(frac_us))
+        .ok_or_else(|| format!("time `{value}` is out of range"))
+}
+
+/// Parse `NAME=VALUE` strings into a map, ignoring entries without `=`.
+fn parse_var_pairs(pairs: &[String]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .filter_map(|s| {
+            let mut parts = s.splitn(2, '=');
+            let key = parts.next()?.to_string();
+            let val = parts.next()?.to_string();
+            Some((key, val))
+        })
+        .collect()
 }
 
 impl Args {
@@ -91,15 +449,7 @@ impl Args {
     ///
     /// Values that do not contain `=` are silently ignored.
     #[must_use]
-    pub fn layout_var_map(&self) -> std::collections::HashMap<String, String> {
-        self.layout_vars
-            .iter()
-            .filter_map(|s| {
-                let mut parts = s.splitn(2, '=');
-                let key = parts.next()?.to_string();
-                let val = parts.next()?.to_string();
-                Some((key, val))
-            })
-            .collect()
+    pub fn layout_var_map(&self) -> HashMap<String, String> {
+        parse_var_pairs(&self.layout_vars)
     }
 }
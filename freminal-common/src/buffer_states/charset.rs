@@ -0,0 +1,771 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The G0–G3 character sets, their invocation into GL and GR, and the tables
+//! that map GL bytes to Unicode.
+//!
+//! SCS (`ESC ( F` and friends) designates a set into one of G0–G3.  SI, SO,
+//! LS2 and LS3 invoke one into GL; LS1R, LS2R and LS3R into GR.  SS2 and SS3
+//! borrow G2 or G3 for the next character only.  The PTY stream is UTF-8, so
+//! only GL (0x20–0x7F) is ever remapped; GR is tracked for DECCIR.  Soft
+//! sets from DECDLD map to the private-use characters of [`super::soft_font`].
+//! National replacement sets only take effect while DECNRCM (`?42`) is set;
+//! otherwise they are shown as ASCII, as on a VT220.
+
+use std::borrow::Cow;
+
+use super::modes::decnrcm::Decnrcm;
+use super::soft_font::soft_char;
+
+/// Shown for the positions of a DEC set that have no Unicode equivalent.
+const UNDEFINED: char = '\u{FFFD}';
+
+/// One of the four designation slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GSet {
+    #[default]
+    G0,
+    G1,
+    G2,
+    G3,
+}
+
+impl GSet {
+    /// G0 through G3, in order.
+    pub const ALL: [Self; 4] = [Self::G0, Self::G1, Self::G2, Self::G3];
+
+    /// The slot number, 0–3.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::G0 => 0,
+            Self::G1 => 1,
+            Self::G2 => 2,
+            Self::G3 => 3,
+        }
+    }
+
+    /// The slot numbered `index`, if any.
+    #[must_use]
+    pub const fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Self::G0),
+            1 => Some(Self::G1),
+            2 => Some(Self::G2),
+            3 => Some(Self::G3),
+            _ => None,
+        }
+    }
+}
+
+/// A character set that can be designated into G0–G3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    /// US-ASCII (`B`).
+    #[default]
+    Ascii,
+    /// DEC Special Graphics, the VT100 line-drawing set (`0`).
+    DecSpecialGraphics,
+    /// DEC Supplemental Graphics, the upper half of DEC MCS (`<` or `%5`).
+    DecSupplemental,
+    /// DEC Technical (`>`).
+    DecTechnical,
+    /// ISO Latin-1 Supplemental, a 96-character set (`A` after `-`, `.` or `/`).
+    Latin1Supplemental,
+    /// United Kingdom NRCS (`A`).
+    British,
+    /// Dutch NRCS (`4`).
+    Dutch,
+    /// Finnish NRCS (`C` or `5`).
+    Finnish,
+    /// French NRCS (`R` or `f`).
+    French,
+    /// French Canadian NRCS (`Q` or `9`).
+    FrenchCanadian,
+    /// German NRCS (`K`).
+    German,
+    /// Italian NRCS (`Y`).
+    Italian,
+    /// Norwegian/Danish NRCS (`E`, `6` or `` ` ``).
+    NorwegianDanish,
+    /// Portuguese NRCS (`%6`).
+    Portuguese,
+    /// Spanish NRCS (`Z`).
+    Spanish,
+    /// Swedish NRCS (`H` or `7`).
+    Swedish,
+    /// Swiss NRCS (`=`).
+    Swiss,
+    /// A soft set loaded by DECDLD (`SP F`), named by its final byte.
+    Soft { final_byte: u8, is_96: bool },
+}
+
+impl Charset {
+    /// The set named by an SCS designator: any intermediates followed by the
+    /// final byte, e.g. `b"0"` or `b"%5"`.  `is_96` is set when the SCS used
+    /// one of the 96-character intermediates (`-`, `.`, `/`).
+    #[must_use]
+    pub const fn from_designator(designator: &[u8], is_96: bool) -> Option<Self> {
+        if let [b' ', final_byte @ 0x30..=0x7e] = designator {
+            return Some(Self::Soft {
+                final_byte: *final_byte,
+                is_96,
+            });
+        }
+        if is_96 {
+            return match designator {
+                b"A" => Some(Self::Latin1Supplemental),
+                _ => None,
+            };
+        }
+
+        match designator {
+            b"B" => Some(Self::Ascii),
+            b"0" => Some(Self::DecSpecialGraphics),
+            b"<" | b"%5" => Some(Self::DecSupplemental),
+            b">" => Some(Self::DecTechnical),
+            b"A" => Some(Self::British),
+            b"4" => Some(Self::Dutch),
+            b"C" | b"5" => Some(Self::Finnish),
+            b"R" | b"f" => Some(Self::French),
+            b"Q" | b"9" => Some(Self::FrenchCanadian),
+            b"K" => Some(Self::German),
+            b"Y" => Some(Self::Italian),
+            b"E" | b"6" | b"`" => Some(Self::NorwegianDanish),
+            b"%6" => Some(Self::Portuguese),
+            b"Z" => Some(Self::Spanish),
+            b"H" | b"7" => Some(Self::Swedish),
+            b"=" => Some(Self::Swiss),
+            _ => None,
+        }
+    }
+
+    /// The designator DECCIR reports for this set.
+    #[must_use]
+    pub fn designator(self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::Ascii => "B",
+            Self::DecSpecialGraphics => "0",
+            Self::DecSupplemental => "%5",
+            Self::DecTechnical => ">",
+            Self::Latin1Supplemental | Self::British => "A",
+            Self::Dutch => "4",
+            Self::Finnish => "C",
+            Self::French => "R",
+            Self::FrenchCanadian => "Q",
+            Self::German => "K",
+            Self::Italian => "Y",
+            Self::NorwegianDanish => "E",
+            Self::Portuguese => "%6",
+            Self::Spanish => "Z",
+            Self::Swedish => "H",
+            Self::Swiss => "=",
+            Self::Soft { final_byte, .. } => {
+                return Cow::Owned(format!(" {}", char::from(final_byte)));
+            }
+        })
+    }
+
+    /// Whether this is a 96-character set.
+    #[must_use]
+    pub const fn is_96(self) -> bool {
+        matches!(
+            self,
+            Self::Latin1Supplemental | Self::Soft { is_96: true, .. }
+        )
+    }
+
+    /// Whether this is a national replacement set, which DECNRCM gates.
+    #[must_use]
+    pub const fn is_nrcs(self) -> bool {
+        !matches!(
+            self,
+            Self::Ascii
+                | Self::DecSpecialGraphics
+                | Self::DecSupplemental
+                | Self::DecTechnical
+                | Self::Latin1Supplemental
+                | Self::Soft { .. }
+        )
+    }
+
+    /// The character `byte` stands for when this set is in GL, or `None` if
+    /// the byte is shown as itself.
+    #[must_use]
+    pub fn map(self, byte: u8) -> Option<char> {
+        match self {
+            Self::Ascii => None,
+            Self::DecSpecialGraphics => dec_special_graphics(byte),
+            Self::DecSupplemental => dec_supplemental(byte),
+            Self::DecTechnical => dec_technical(byte),
+            Self::Latin1Supplemental => (0x20..=0x7f)
+                .contains(&byte)
+                .then(|| char::from(byte + 0x80)),
+            Self::Soft { final_byte, is_96 } => {
+                let first = if is_96 { 0x20 } else { 0x21 };
+                let last = if is_96 { 0x7f } else { 0x7e };
+                (first..=last)
+                    .contains(&byte)
+                    .then(|| soft_char(final_byte, byte))
+                    .flatten()
+            }
+            nrcs => nrcs
+                .replacements()
+                .iter()
+                .find(|(from, _)| *from == byte)
+                .map(|(_, to)| *to),
+        }
+    }
+
+    /// The positions a national replacement set changes from ASCII.
+    // Inherently large: one table per NRC set.
+    #[allow(clippy::too_many_lines)]
+    const fn replacements(self) -> &'static [(u8, char)] {
+        match self {
+            Self::British => &[(b'#', '£')],
+            Self::Dutch => &[
+                (b'#', '£'),
+                (b'@', '¾'),
+                (b'[', 'ĳ'),
+                (b'\\', '½'),
+                (b']', '|'),
+                (b'{', '¨'),
+                (b'|', 'ƒ'),
+                (b'}', '¼'),
+                (b'~', '´'),
+            ],
+            Self::Finnish => &[
+                (b'[', 'Ä'),
+                (b'\\', 'Ö'),
+                (b']', 'Å'),
+                (b'^', 'Ü'),
+                (b'`', 'é'),
+                (b'{', 'ä'),
+                (b'|', 'ö'),
+                (b'}', 'å'),
+                (b'~', 'ü'),
+            ],
+            Self::French => &[
+                (b'#', '£'),
+                (b'@', 'à'),
+                (b'[', '°'),
+                (b'\\', 'ç'),
+                (b']', '§'),
+                (b'{', 'é'),
+                (b'|', 'ù'),
+                (b'}', 'è'),
+                (b'~', '¨'),
+            ],
+            Self::FrenchCanadian => &[
+                (b'@', 'à'),
+                (b'[', 'â'),
+                (b'\\', 'ç'),
+                (b']', 'ê'),
+                (b'^', 'î'),
+                (b'`', 'ô'),
+                (b'{', 'é'),
+                (b'|', 'ù'),
+                (b'}', 'è'),
+                (b'~', 'û'),
+            ],
+            Self::German => &[
+                (b'@', '§'),
+                (b'[', 'Ä'),
+                (b'\\', 'Ö'),
+                (b']', 'Ü'),
+                (b'{', 'ä'),
+                (b'|', 'ö'),
+                (b'}', 'ü'),
+                (b'~', 'ß'),
+            ],
+            Self::Italian => &[
+                (b'#', '£'),
+                (b'@', '§'),
+                (b'[', '°'),
+                (b'\\', 'ç'),
+                (b']', 'é'),
+                (b'`', 'ù'),
+                (b'{', 'à'),
+                (b'|', 'ò'),
+                (b'}', 'è'),
+                (b'~', 'ì'),
+            ],
+            Self::NorwegianDanish => &[
+                (b'@', 'Ä'),
+                (b'[', 'Æ'),
+                (b'\\', 'Ø'),
+                (b']', 'Å'),
+                (b'^', 'Ü'),
+                (b'`', 'ä'),
+                (b'{', 'æ'),
+                (b'|', 'ø'),
+                (b'}', 'å'),
+                (b'~', 'ü'),
+            ],
+            Self::Portuguese => &[
+                (b'[', 'Ã'),
+                (b'\\', 'Ç'),
+                (b']', 'Õ'),
+                (b'{', 'ã'),
+                (b'|', 'ç'),
+                (b'}', 'õ'),
+            ],
+            Self::Spanish => &[
+                (b'#', '£'),
+                (b'@', '§'),
+                (b'[', '¡'),
+                (b'\\', 'Ñ'),
+                (b']', '¿'),
+                (b'{', '°'),
+                (b'|', 'ñ'),
+                (b'}', 'ç'),
+            ],
+            Self::Swedish => &[
+                (b'@', 'É'),
+                (b'[', 'Ä'),
+                (b'\\', 'Ö'),
+                (b']', 'Å'),
+                (b'^', 'Ü'),
+                (b'`', 'é'),
+                (b'{', 'ä'),
+                (b'|', 'ö'),
+                (b'}', 'å'),
+                (b'~', 'ü'),
+            ],
+            Self::Swiss => &[
+                (b'#', 'ù'),
+                (b'@', 'à'),
+                (b'[', 'é'),
+                (b'\\', 'ç'),
+                (b']', 'ê'),
+                (b'^', 'î'),
+                (b'_', 'è'),
+                (b'`', 'ô'),
+                (b'{', 'ä'),
+                (b'|', 'ö'),
+                (b'}', 'ü'),
+                (b'~', 'û'),
+            ],
+            _ => &[],
+        }
+    }
+}
+
+/// DEC Special Graphics: 0x5F–0x7E become box drawing, Greek and symbols.
+///
+/// Reference: <https://en.wikipedia.org/wiki/DEC_Special_Graphics>
+const fn dec_special_graphics(byte: u8) -> Option<char> {
+    Some(match byte {
+        0x5f => '\u{00A0}', // NO-BREAK SPACE
+        0x60 => '\u{25C6}', // BLACK DIAMOND
+        0x61 => '\u{2592}', // MEDIUM SHADE
+        0x62 => '\u{2409}', // SYMBOL FOR HT
+        0x63 => '\u{240C}', // SYMBOL FOR FF
+        0x64 => '\u{240D}', // SYMBOL FOR CR
+        0x65 => '\u{240A}', // SYMBOL FOR LF
+        0x66 => '\u{00B0}', // DEGREE SIGN
+        0x67 => '\u{00B1}', // PLUS-MINUS SIGN
+        0x68 => '\u{2424}', // SYMBOL FOR NEWLINE
+        0x69 => '\u{240B}', // SYMBOL FOR VT
+        0x6a => '\u{2518}', // BOX LIGHT UP AND LEFT
+        0x6b => '\u{2510}', // BOX LIGHT DOWN AND LEFT
+        0x6c => '\u{250C}', // BOX LIGHT DOWN AND RIGHT
+        0x6d => '\u{2514}', // BOX LIGHT UP AND RIGHT
+        0x6e => '\u{253C}', // BOX LIGHT VERTICAL AND HORIZONTAL
+        0x6f => '\u{23BA}', // HORIZONTAL SCAN LINE-1
+        0x70 => '\u{23BB}', // HORIZONTAL SCAN LINE-3
+        0x71 => '\u{2500}', // BOX LIGHT HORIZONTAL
+        0x72 => '\u{23BC}', // HORIZONTAL SCAN LINE-7
+        0x73 => '\u{23BD}', // HORIZONTAL SCAN LINE-9
+        0x74 => '\u{251C}', // BOX LIGHT VERTICAL AND RIGHT
+        0x75 => '\u{2524}', // BOX LIGHT VERTICAL AND LEFT
+        0x76 => '\u{2534}', // BOX LIGHT UP AND HORIZONTAL
+        0x77 => '\u{252C}', // BOX LIGHT DOWN AND HORIZONTAL
+        0x78 => '\u{2502}', // BOX LIGHT VERTICAL
+        0x79 => '\u{2264}', // LESS-THAN OR EQUAL TO
+        0x7a => '\u{2265}', // GREATER-THAN OR EQUAL TO
+        0x7b => '\u{03C0}', // GREEK SMALL LETTER PI
+        0x7c => '\u{2260}', // NOT EQUAL TO
+        0x7d => '\u{00A3}', // POUND SIGN
+        0x7e => '\u{00B7}', // MIDDLE DOT
+        _ => return None,
+    })
+}
+
+/// DEC Supplemental Graphics: Latin-1 shifted down to GL, except where DEC
+/// MCS differs from it.
+fn dec_supplemental(byte: u8) -> Option<char> {
+    Some(match byte {
+        0x28 => '¤',
+        0x57 => 'Œ',
+        0x5d => 'Ÿ',
+        0x77 => 'œ',
+        0x7d => 'ÿ',
+        0x24 | 0x26 | 0x2c..=0x2f | 0x34 | 0x38 | 0x3e | 0x50 | 0x5e | 0x70 | 0x7e => UNDEFINED,
+        0x21..=0x7e => char::from(byte + 0x80),
+        _ => return None,
+    })
+}
+
+/// DEC Technical: large-symbol pieces, mathematical operators and Greek.
+const fn dec_technical(byte: u8) -> Option<char> {
+    Some(match byte {
+        0x21 => '\u{23B7}', // RADICAL SYMBOL BOTTOM
+        0x22 => '\u{250C}', // BOX LIGHT DOWN AND RIGHT
+        0x23 => '\u{2500}', // BOX LIGHT HORIZONTAL
+        0x24 => '\u{2320}', // TOP HALF INTEGRAL
+        0x25 => '\u{2321}', // BOTTOM HALF INTEGRAL
+        0x26 => '\u{2502}', // BOX LIGHT VERTICAL
+        0x27 => '\u{23A1}', // LEFT SQUARE BRACKET UPPER CORNER
+        0x28 => '\u{23A3}', // LEFT SQUARE BRACKET LOWER CORNER
+        0x29 => '\u{23A4}', // RIGHT SQUARE BRACKET UPPER CORNER
+        0x2a => '\u{23A6}', // RIGHT SQUARE BRACKET LOWER CORNER
+        0x2b => '\u{239B}', // LEFT PARENTHESIS UPPER HOOK
+        0x2c => '\u{239D}', // LEFT PARENTHESIS LOWER HOOK
+        0x2d => '\u{239E}', // RIGHT PARENTHESIS UPPER HOOK
+        0x2e => '\u{23A0}', // RIGHT PARENTHESIS LOWER HOOK
+        0x2f => '\u{23A8}', // LEFT CURLY BRACKET MIDDLE PIECE
+        0x30 => '\u{23AC}', // RIGHT CURLY BRACKET MIDDLE PIECE
+        0x3c => '\u{2264}', // LESS-THAN OR EQUAL TO
+        0x3d => '\u{2260}', // NOT EQUAL TO
+        0x3e => '\u{2265}', // GREATER-THAN OR EQUAL TO
+        0x3f => '\u{222B}', // INTEGRAL
+        0x40 => '\u{2234}', // THEREFORE
+        0x41 => '\u{221D}', // PROPORTIONAL TO
+        0x42 => '\u{221E}', // INFINITY
+        0x43 => '\u{00F7}', // DIVISION SIGN
+        0x44 => '\u{0394}', // GREEK CAPITAL LETTER DELTA
+        0x45 => '\u{2207}', // NABLA
+        0x46 => '\u{03A6}', // GREEK CAPITAL LETTER PHI
+        0x47 => '\u{0393}', // GREEK CAPITAL LETTER GAMMA
+        0x48 => '\u{223C}', // TILDE OPERATOR
+        0x49 => '\u{2243}', // ASYMPTOTICALLY EQUAL TO
+        0x4a => '\u{0398}', // GREEK CAPITAL LETTER THETA
+        0x4b => '\u{00D7}', // MULTIPLICATION SIGN
+        0x4c => '\u{039B}', // GREEK CAPITAL LETTER LAMDA
+        0x4d => '\u{21D4}', // LEFT RIGHT DOUBLE ARROW
+        0x4e => '\u{21D2}', // RIGHTWARDS DOUBLE ARROW
+        0x4f => '\u{2261}', // IDENTICAL TO
+        0x50 => '\u{03A0}', // GREEK CAPITAL LETTER PI
+        0x51 => '\u{03A8}', // GREEK CAPITAL LETTER PSI
+        0x53 => '\u{03A3}', // GREEK CAPITAL LETTER SIGMA
+        0x56 => '\u{221A}', // SQUARE ROOT
+        0x57 => '\u{03A9}', // GREEK CAPITAL LETTER OMEGA
+        0x58 => '\u{039E}', // GREEK CAPITAL LETTER XI
+        0x59 => '\u{03A5}', // GREEK CAPITAL LETTER UPSILON
+        0x5a => '\u{2282}', // SUBSET OF
+        0x5b => '\u{2283}', // SUPERSET OF
+        0x5c => '\u{2229}', // INTERSECTION
+        0x5d => '\u{222A}', // UNION
+        0x5e => '\u{2227}', // LOGICAL AND
+        0x5f => '\u{2228}', // LOGICAL OR
+        0x60 => '\u{00AC}', // NOT SIGN
+        0x61 => '\u{03B1}', // GREEK SMALL LETTER ALPHA
+        0x62 => '\u{03B2}', // GREEK SMALL LETTER BETA
+        0x63 => '\u{03C7}', // GREEK SMALL LETTER CHI
+        0x64 => '\u{03B4}', // GREEK SMALL LETTER DELTA
+        0x65 => '\u{03B5}', // GREEK SMALL LETTER EPSILON
+        0x66 => '\u{03C6}', // GREEK SMALL LETTER PHI
+        0x67 => '\u{03B3}', // GREEK SMALL LETTER GAMMA
+        0x68 => '\u{03B7}', // GREEK SMALL LETTER ETA
+        0x69 => '\u{03B9}', // GREEK SMALL LETTER IOTA
+        0x6a => '\u{03B8}', // GREEK SMALL LETTER THETA
+        0x6b => '\u{03BA}', // GREEK SMALL LETTER KAPPA
+        0x6c => '\u{03BB}', // GREEK SMALL LETTER LAMDA
+        0x6e => '\u{03BD}', // GREEK SMALL LETTER NU
+        0x6f => '\u{2202}', // PARTIAL DIFFERENTIAL
+        0x70 => '\u{03C0}', // GREEK SMALL LETTER PI
+        0x71 => '\u{03C8}', // GREEK SMALL LETTER PSI
+        0x72 => '\u{03C1}', // GREEK SMALL LETTER RHO
+        0x73 => '\u{03C3}', // GREEK SMALL LETTER SIGMA
+        0x74 => '\u{03C4}', // GREEK SMALL LETTER TAU
+        0x76 => '\u{0192}', // LATIN SMALL LETTER F WITH HOOK
+        0x77 => '\u{03C9}', // GREEK SMALL LETTER OMEGA
+        0x78 => '\u{03BE}', // GREEK SMALL LETTER XI
+        0x79 => '\u{03C5}', // GREEK SMALL LETTER UPSILON
+        0x7a => '\u{03B6}', // GREEK SMALL LETTER ZETA
+        0x7b => '\u{2190}', // LEFTWARDS ARROW
+        0x7c => '\u{2191}', // UPWARDS ARROW
+        0x7d => '\u{2192}', // RIGHTWARDS ARROW
+        0x7e => '\u{2193}', // DOWNWARDS ARROW
+        0x31..=0x3b | 0x52 | 0x54 | 0x55 | 0x6d | 0x75 => UNDEFINED,
+        _ => return None,
+    })
+}
+
+/// The designations of G0–G3, what is invoked into GL and GR, and any
+/// pending single shift.
+///
+/// DECSC saves all of it and DECRC puts it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharsetState {
+    designations: [Charset; 4],
+    gl: GSet,
+    gr: GSet,
+    single_shift: Option<GSet>,
+}
+
+impl Default for CharsetState {
+    fn default() -> Self {
+        Self {
+            designations: [Charset::Ascii; 4],
+            gl: GSet::G0,
+            gr: GSet::G2,
+            single_shift: None,
+        }
+    }
+}
+
+impl CharsetState {
+    /// The set designated into `set`.
+    #[must_use]
+    pub const fn designation(&self, set: GSet) -> Charset {
+        self.designations[set.index()]
+    }
+
+    /// The slot invoked into GL.
+    #[must_use]
+    pub const fn gl(&self) -> GSet {
+        self.gl
+    }
+
+    /// The slot invoked into GR.
+    #[must_use]
+    pub const fn gr(&self) -> GSet {
+        self.gr
+    }
+
+    /// The slot SS2 or SS3 selected for the next character, if any.
+    #[must_use]
+    pub const fn single_shift(&self) -> Option<GSet> {
+        self.single_shift
+    }
+
+    /// SCS: designate `charset` into `set`.
+    pub const fn designate(&mut self, set: GSet, charset: Charset) {
+        self.designations[set.index()] = charset;
+    }
+
+    /// SI, SO, LS2, LS3: invoke `set` into GL.
+    pub const fn invoke_gl(&mut self, set: GSet) {
+        self.gl = set;
+    }
+
+    /// LS1R, LS2R, LS3R: invoke `set` into GR.
+    pub const fn invoke_gr(&mut self, set: GSet) {
+        self.gr = set;
+    }
+
+    /// SS2, SS3: use `set` for the next character only.  `None` cancels a
+    /// pending shift.
+    pub const fn set_single_shift(&mut self, set: Option<GSet>) {
+        self.single_shift = set;
+    }
+
+    /// The set `set` maps through: its designation, or ASCII for a national
+    /// replacement set while `nrc_mode` is not `NrcEnabled`.
+    fn effective(&self, set: GSet, nrc_mode: Decnrcm) -> Charset {
+        let charset = self.designation(set);
+        if charset.is_nrcs() && nrc_mode != Decnrcm::NrcEnabled {
+            Charset::Ascii
+        } else {
+            charset
+        }
+    }
+
+    /// Map the GL bytes of `data` through the invoked sets, consuming any
+    /// pending single shift on the first character.  National replacement
+    /// sets apply only when `nrc_mode` is `NrcEnabled`.
+    ///
+    /// Returns `Cow::Borrowed(data)` when GL holds ASCII and no shift is
+    /// pending, which is the overwhelmingly common case.
+    pub fn translate<'a>(&mut self, data: &'a [u8], nrc_mode: Decnrcm) -> Cow<'a, [u8]> {
+        if self.single_shift.is_none() && self.effective(self.gl, nrc_mode) == Charset::Ascii {
+            return Cow::Borrowed(data);
+        }
+
+        let mut out = Vec::with_capacity(data.len() * 3);
+        for &byte in data {
+            // Continuation bytes of a UTF-8 character never start one.
+            if (0x80..0xc0).contains(&byte) {
+                out.push(byte);
+                continue;
+            }
+
+            let set = self.single_shift.take().unwrap_or(self.gl);
+            let mapped = if byte.is_ascii() {
+                self.effective(set, nrc_mode).map(byte)
+            } else {
+                None
+            };
+            match mapped {
+                Some(ch) => out.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+                None => out.push(byte),
+            }
+        }
+        Cow::Owned(out)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn translated(state: &mut CharsetState, data: &[u8]) -> String {
+        String::from_utf8(state.translate(data, Decnrcm::NrcEnabled).into_owned())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn ascii_is_borrowed() {
+        let mut state = CharsetState::default();
+        assert!(matches!(
+            state.translate(b"hello", Decnrcm::NrcEnabled),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn dec_special_graphics_box_drawing() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G0, Charset::DecSpecialGraphics);
+        assert_eq!(translated(&mut state, b"lqk"), "┌─┐");
+        // Below 0x5F nothing changes.
+        assert_eq!(translated(&mut state, b"AZ"), "AZ");
+    }
+
+    #[test]
+    fn shift_out_uses_g1() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G1, Charset::DecSpecialGraphics);
+        assert_eq!(translated(&mut state, b"q"), "q");
+
+        state.invoke_gl(GSet::G1);
+        assert_eq!(translated(&mut state, b"q"), "─");
+    }
+
+    #[test]
+    fn single_shift_applies_to_one_character() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G2, Charset::German);
+        state.set_single_shift(Some(GSet::G2));
+        assert_eq!(translated(&mut state, b"{{"), "ä{");
+        assert_eq!(state.single_shift(), None);
+    }
+
+    #[test]
+    fn single_shift_is_consumed_by_utf8() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G3, Charset::DecSpecialGraphics);
+        state.set_single_shift(Some(GSet::G3));
+        assert_eq!(translated(&mut state, "éq".as_bytes()), "éq");
+    }
+
+    #[test]
+    fn national_replacement_sets() {
+        for (charset, expected) in [
+            (Charset::British, "£@[\\]{|}~"),
+            (Charset::German, "#§ÄÖÜäöüß"),
+            (Charset::French, "£à°ç§éùè¨"),
+            (Charset::Swedish, "#ÉÄÖÅäöåü"),
+            (Charset::Spanish, "£§¡Ñ¿°ñç~"),
+        ] {
+            let mut state = CharsetState::default();
+            state.designate(GSet::G0, charset);
+            assert_eq!(
+                translated(&mut state, b"#@[\\]{|}~"),
+                expected,
+                "{charset:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn national_replacement_sets_need_nrc_mode() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G0, Charset::German);
+        assert!(matches!(
+            state.translate(b"{|}", Decnrcm::NrcDisabled),
+            Cow::Borrowed(_)
+        ));
+
+        // DEC sets are not national replacement sets and ignore DECNRCM.
+        state.designate(GSet::G0, Charset::DecSpecialGraphics);
+        assert_eq!(
+            String::from_utf8(state.translate(b"q", Decnrcm::NrcDisabled).into_owned()).unwrap(),
+            "─"
+        );
+    }
+
+    #[test]
+    fn dec_supplemental_and_technical() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G0, Charset::DecSupplemental);
+        assert_eq!(translated(&mut state, b"!(WwA"), "¡¤ŒœÁ");
+
+        state.designate(GSet::G0, Charset::DecTechnical);
+        assert_eq!(translated(&mut state, b"DajB"), "Δαθ∞");
+    }
+
+    #[test]
+    fn latin1_supplemental_is_a_96_set() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G1, Charset::Latin1Supplemental);
+        state.invoke_gl(GSet::G1);
+        assert_eq!(translated(&mut state, b" Ai"), "\u{a0}Áé");
+    }
+
+    #[test]
+    fn designators_round_trip() {
+        for (designator, is_96) in [
+            ("B", false),
+            ("0", false),
+            ("%5", false),
+            (">", false),
+            ("A", false),
+            ("A", true),
+            ("K", false),
+            ("%6", false),
+            ("=", false),
+        ] {
+            let charset = Charset::from_designator(designator.as_bytes(), is_96).unwrap();
+            assert_eq!(charset.designator(), designator);
+            assert_eq!(charset.is_96(), is_96);
+        }
+        assert_eq!(
+            Charset::from_designator(b"<", false),
+            Some(Charset::DecSupplemental)
+        );
+        assert_eq!(
+            Charset::from_designator(b"6", false),
+            Some(Charset::NorwegianDanish)
+        );
+        assert_eq!(Charset::from_designator(b"B", true), None);
+        assert_eq!(Charset::from_designator(b"%9", false), None);
+
+        let soft = Charset::from_designator(b" @", true).unwrap();
+        assert_eq!(
+            soft,
+            Charset::Soft {
+                final_byte: b'@',
+                is_96: true
+            }
+        );
+        assert_eq!(soft.designator(), " @");
+        assert!(soft.is_96());
+    }
+
+    #[test]
+    fn soft_sets_map_to_private_use_characters() {
+        let mut state = CharsetState::default();
+        state.designate(GSet::G0, Charset::from_designator(b" @", false).unwrap());
+        let translated = translated(&mut state, b" !~");
+        let chars: Vec<char> = translated.chars().collect();
+        assert_eq!(chars[0], ' ');
+        assert_eq!(chars[1], soft_char(b'@', b'!').unwrap());
+        assert_eq!(chars[2], soft_char(b'@', b'~').unwrap());
+    }
+}
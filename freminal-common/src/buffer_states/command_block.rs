@@ -99,6 +99,10 @@ pub struct CommandBlock {
     /// CWD captured from OSC 7 at the time of prompt start.
     pub cwd: Option<String>,
 
+    /// Command text reported by the shell (`OSC 633 E`).  `None` when the
+    /// shell does not report it; the text is then read from the grid.
+    pub command_line: Option<String>,
+
     /// Wall-clock timestamp of prompt start (`OSC 133 A`).
     ///
     /// This is when the prompt was drawn, NOT when the command began
@@ -134,6 +138,7 @@ impl CommandBlock {
             end_row: None,
             exit_code: None,
             cwd,
+            command_line: None,
             started_at: SystemTime::now(),
             executed_at: None,
             finished_at: None,
@@ -95,10 +95,16 @@ impl UnderlineStyle {
 /// Bits 1–3: Underline style (3-bit field, 0=none, 1–5=styles)
 /// Bit 4:   Faint
 /// Bit 5:   Strikethrough
+/// Bit 6:   DECSCA protection (not a decoration; see below)
 /// ```
 ///
 /// Underline styles are mutually exclusive (only one can be active), so they
 /// share a 3-bit field rather than separate bits.
+///
+/// The protection bit rides in this byte so that `FormatTag`, and with it every
+/// stored cell, does not grow by a whole `bool`.  It is invisible to
+/// [`contains`](Self::contains), [`iter`](Self::iter) and
+/// [`is_empty`](Self::is_empty).
 #[derive(Clone, Copy, Eq, PartialEq, Default, Hash)]
 pub struct FontDecorationFlags(u8);
 
@@ -108,6 +114,7 @@ impl FontDecorationFlags {
     const UNDERLINE_SHIFT: u8 = 1;
     const FAINT: u8 = 0b0001_0000;
     const STRIKETHROUGH: u8 = 0b0010_0000;
+    const PROTECTED: u8 = 0b0100_0000;
 
     /// An empty decoration set (no decorations active).
     #[must_use]
@@ -115,10 +122,33 @@ impl FontDecorationFlags {
         Self(0)
     }
 
-    /// Returns `true` if no decorations are active.
+    /// Returns `true` if no decorations are active.  The protection bit is
+    /// not a decoration and is ignored.
     #[must_use]
     pub const fn is_empty(self) -> bool {
-        self.0 == 0
+        self.0 & !Self::PROTECTED == 0
+    }
+
+    /// Whether DECSCA marked the cell protected.
+    #[must_use]
+    pub const fn is_protected(self) -> bool {
+        self.0 & Self::PROTECTED != 0
+    }
+
+    /// These decorations with the protection bit cleared, for comparing or
+    /// drawing cells by appearance alone.
+    #[must_use]
+    pub const fn without_protection(self) -> Self {
+        Self(self.0 & !Self::PROTECTED)
+    }
+
+    /// Set or clear the DECSCA protection bit.
+    pub const fn set_protected(&mut self, protected: bool) {
+        if protected {
+            self.0 |= Self::PROTECTED;
+        } else {
+            self.0 &= !Self::PROTECTED;
+        }
     }
 
     /// Returns `true` if the given decoration is active.
@@ -209,6 +239,9 @@ impl fmt::Debug for FontDecorationFlags {
         if style.is_active() && style != UnderlineStyle::Single {
             list.entry(&style);
         }
+        if self.is_protected() {
+            list.entry(&"Protected");
+        }
         list.finish()
     }
 }
@@ -313,6 +346,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn protection_is_not_a_decoration() {
+        let mut flags = FontDecorationFlags::empty();
+        flags.set_protected(true);
+        assert!(flags.is_protected());
+        assert!(flags.is_empty());
+        assert_eq!(flags.iter().count(), 0);
+        assert_ne!(flags, FontDecorationFlags::empty());
+
+        flags.insert(FontDecorations::Italic);
+        flags.set_protected(false);
+        assert!(!flags.is_protected());
+        assert!(flags.contains(FontDecorations::Italic));
+    }
+
     #[test]
     fn default_is_empty() {
         let flags = FontDecorationFlags::default();
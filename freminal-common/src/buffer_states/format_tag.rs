@@ -35,7 +35,8 @@ pub struct FormatTag {
     /// Active font decorations (underline, strikethrough, etc.) for this range.
     ///
     /// Stored as a compact bitfield — at most 4 decorations (italic, underline,
-    /// faint, strikethrough) — to avoid heap allocation on clone.
+    /// faint, strikethrough) — to avoid heap allocation on clone.  Also holds
+    /// the DECSCA protection bit; see [`FormatTag::is_protected`].
     pub font_decorations: FontDecorationFlags,
     /// OSC 8 hyperlink URL active for this range, if any.
     ///
@@ -47,9 +48,25 @@ pub struct FormatTag {
 }
 
 impl FormatTag {
+    /// DECSCA character protection: DECSED, DECSEL and DECSERA leave
+    /// protected cells alone.
+    ///
+    /// Not a visual attribute, and SGR 0 does not clear it.  Kept in a spare
+    /// bit of `font_decorations` so it costs no cell memory.
+    #[must_use]
+    pub const fn is_protected(&self) -> bool {
+        self.font_decorations.is_protected()
+    }
+
+    /// Set or clear DECSCA character protection.
+    pub const fn set_protected(&mut self, protected: bool) {
+        self.font_decorations.set_protected(protected);
+    }
+
     /// Returns `true` if this tag's visual attributes (colors, weight, decorations,
     /// URL, and blink) are all at their default values.
     ///
+    /// Protection is ignored because it does not change how a cell looks.
     /// The positional fields `start` and `end` are ignored because they are only
     /// meaningful in the flat `TChar` representation and have no bearing on the
     /// visual appearance of a cell.
@@ -61,7 +78,7 @@ impl FormatTag {
     pub fn is_visually_default(&self) -> bool {
         self.colors == StateColors::default()
             && self.font_weight == FontWeight::Normal
-            && self.font_decorations == FontDecorationFlags::empty()
+            && self.font_decorations.is_empty()
             && self.url.is_none()
             && self.blink == BlinkState::None
     }
@@ -125,6 +142,15 @@ mod tests {
         assert_eq!(tag_a, tag_b);
     }
 
+    #[test]
+    fn protection_is_not_visual() {
+        let mut tag = FormatTag::default();
+        tag.set_protected(true);
+        assert!(tag.is_protected());
+        assert!(tag.is_visually_default());
+        assert_ne!(tag, FormatTag::default());
+    }
+
     #[test]
     fn blink_state_default_is_none() {
         assert_eq!(BlinkState::default(), BlinkState::None);
@@ -0,0 +1,265 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Shell-integration markers from emitters other than freminal's own scripts.
+//!
+//! Starship, oh-my-posh, the iTerm2 / kitty / Ghostty integrations and shells
+//! on remote hosts send plain `OSC 133 ; A/B/C/D` markers, without the
+//! `freminal=1 ; fid=` extension that [`super::ftcs::parse_ftcs_params`]
+//! requires.  VS Code's integration sends the same four markers as
+//! `OSC 633`, plus two of its own:
+//!
+//! - `OSC 633 ; E ; <command line> [; <nonce>] ST` — the command about to run
+//! - `OSC 633 ; P ; <key>=<value> ST` — a shell property, such as `Cwd`
+//!
+//! These markers carry no correlation id, so the terminal pairs them by
+//! order (see `terminal_handler::shell_integration`).
+//!
+//! Reference: <https://code.visualstudio.com/docs/terminal/shell-integration#_supported-escape-sequences>
+
+use std::fmt;
+
+/// A shell-integration marker without freminal's correlation id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompatMarker {
+    /// `A` — prompt start.
+    PromptStart,
+    /// `B` — prompt end / command input start.
+    CommandStart,
+    /// `C` — the command is about to run; output follows.
+    OutputStart,
+    /// `D [; exitcode]` — the command finished.
+    CommandFinished {
+        /// Exit code, when the shell sent a numeric one.
+        exit_code: Option<i32>,
+    },
+    /// `633 ; E` — the command line, with its escapes decoded.
+    CommandLine(String),
+    /// `633 ; P ; key=value` — a shell property, with the value decoded.
+    Property {
+        /// Property name, e.g. `Cwd`.
+        key: String,
+        /// Property value.
+        value: String,
+    },
+}
+
+impl fmt::Display for CompatMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PromptStart => write!(f, "A"),
+            Self::CommandStart => write!(f, "B"),
+            Self::OutputStart => write!(f, "C"),
+            Self::CommandFinished {
+                exit_code: Some(code),
+            } => write!(f, "D;{code}"),
+            Self::CommandFinished { exit_code: None } => write!(f, "D"),
+            Self::CommandLine(command) => write!(f, "E({command:?})"),
+            Self::Property { key, value } => write!(f, "P({key}={value:?})"),
+        }
+    }
+}
+
+/// Parse a plain `OSC 133` marker from the `;`-separated parameters after
+/// `133`.
+///
+/// Accepts `A`, `B`, `C` and `D [; exitcode]` with any extra `key=value`
+/// parameters (`aid=`, `cl=`, `k=`).  Returns `None` for other letters and
+/// for markers that carry a `freminal=` tag, which belong to
+/// [`super::ftcs::parse_ftcs_params`].
+#[must_use]
+pub fn parse_plain_ftcs_params(params: &[&str]) -> Option<CompatMarker> {
+    if params.iter().skip(1).any(|p| p.starts_with("freminal=")) {
+        return None;
+    }
+    parse_marker(params, false)
+}
+
+/// Parse the body of an `OSC 633` sequence (the text after `633;`).
+///
+/// Returns `None` for sub-commands freminal does not use and for a `P`
+/// without `=`.
+#[must_use]
+pub fn parse_osc633(body: &str) -> Option<CompatMarker> {
+    let params: Vec<&str> = body.split(';').collect();
+    parse_marker(&params, true)
+}
+
+fn parse_marker(params: &[&str], vscode: bool) -> Option<CompatMarker> {
+    match *params.first()? {
+        "A" => Some(CompatMarker::PromptStart),
+        "B" => Some(CompatMarker::CommandStart),
+        "C" => Some(CompatMarker::OutputStart),
+        "D" => Some(CompatMarker::CommandFinished {
+            exit_code: params.get(1).and_then(|code| code.parse().ok()),
+        }),
+        // The nonce that may follow the command line only guards against
+        // spoofing inside VS Code itself; it is not needed here.
+        "E" if vscode => Some(CompatMarker::CommandLine(unescape_osc633(
+            params.get(1).copied().unwrap_or(""),
+        ))),
+        "P" if vscode => {
+            let (key, value) = params.get(1)?.split_once('=')?;
+            Some(CompatMarker::Property {
+                key: key.to_owned(),
+                value: unescape_osc633(value),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Decode VS Code's escaping: `\\` is a backslash and `\xAB` is the byte
+/// `0xAB` (used for `;` and control characters).  Anything else is kept.
+fn unescape_osc633(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            if bytes.get(i + 1) == Some(&b'\\') {
+                out.push(b'\\');
+                i += 2;
+                continue;
+            }
+            if bytes.get(i + 1) == Some(&b'x')
+                && let Some(hex) = text.get(i + 2..i + 4)
+                && let Ok(byte) = u8::from_str_radix(hex, 16)
+            {
+                out.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_markers() {
+        assert_eq!(
+            parse_plain_ftcs_params(&["A"]),
+            Some(CompatMarker::PromptStart)
+        );
+        assert_eq!(
+            parse_plain_ftcs_params(&["A", "cl=m", "aid=4242"]),
+            Some(CompatMarker::PromptStart)
+        );
+        assert_eq!(
+            parse_plain_ftcs_params(&["B"]),
+            Some(CompatMarker::CommandStart)
+        );
+        assert_eq!(
+            parse_plain_ftcs_params(&["C"]),
+            Some(CompatMarker::OutputStart)
+        );
+        assert_eq!(
+            parse_plain_ftcs_params(&["D", "1", "aid=4242"]),
+            Some(CompatMarker::CommandFinished { exit_code: Some(1) })
+        );
+        assert_eq!(
+            parse_plain_ftcs_params(&["D"]),
+            Some(CompatMarker::CommandFinished { exit_code: None })
+        );
+        assert_eq!(
+            parse_plain_ftcs_params(&["D", "aid=4242"]),
+            Some(CompatMarker::CommandFinished { exit_code: None })
+        );
+    }
+
+    #[test]
+    fn plain_parser_leaves_tagged_and_unknown_markers_alone() {
+        assert_eq!(parse_plain_ftcs_params(&["A", "freminal=1"]), None);
+        assert_eq!(
+            parse_plain_ftcs_params(&["D", "0", "freminal=1", "fid=x"]),
+            None
+        );
+        // E and P are VS Code sub-commands; OSC 133 P is handled by the
+        // freminal parser.
+        assert_eq!(parse_plain_ftcs_params(&["E", "ls"]), None);
+        assert_eq!(parse_plain_ftcs_params(&["P", "k=i"]), None);
+        assert_eq!(parse_plain_ftcs_params(&["Z"]), None);
+        assert_eq!(parse_plain_ftcs_params(&[]), None);
+    }
+
+    #[test]
+    fn osc633_markers() {
+        assert_eq!(parse_osc633("A"), Some(CompatMarker::PromptStart));
+        assert_eq!(parse_osc633("B"), Some(CompatMarker::CommandStart));
+        assert_eq!(parse_osc633("C"), Some(CompatMarker::OutputStart));
+        assert_eq!(
+            parse_osc633("D;130"),
+            Some(CompatMarker::CommandFinished {
+                exit_code: Some(130)
+            })
+        );
+        assert_eq!(parse_osc633("F"), None);
+        assert_eq!(parse_osc633(""), None);
+    }
+
+    #[test]
+    fn osc633_command_line_is_unescaped() {
+        assert_eq!(
+            parse_osc633("E;echo a\\x3bb \\\\n;a1b2c3"),
+            Some(CompatMarker::CommandLine("echo a;b \\n".to_owned()))
+        );
+        assert_eq!(
+            parse_osc633("E;printf '\\x0a'\\x20ok"),
+            Some(CompatMarker::CommandLine("printf '\n' ok".to_owned()))
+        );
+        assert_eq!(
+            parse_osc633("E"),
+            Some(CompatMarker::CommandLine(String::new()))
+        );
+        // A malformed escape is kept as written.
+        assert_eq!(
+            parse_osc633("E;a\\xZZ\\x4"),
+            Some(CompatMarker::CommandLine("a\\xZZ\\x4".to_owned()))
+        );
+        assert_eq!(
+            parse_osc633("E;caf\u{e9}"),
+            Some(CompatMarker::CommandLine("caf\u{e9}".to_owned()))
+        );
+    }
+
+    #[test]
+    fn osc633_properties() {
+        assert_eq!(
+            parse_osc633("P;Cwd=/home/user/a\\x3bb"),
+            Some(CompatMarker::Property {
+                key: "Cwd".to_owned(),
+                value: "/home/user/a;b".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_osc633("P;IsWindows=False"),
+            Some(CompatMarker::Property {
+                key: "IsWindows".to_owned(),
+                value: "False".to_owned(),
+            })
+        );
+        assert_eq!(parse_osc633("P;Cwd"), None);
+        assert_eq!(parse_osc633("P"), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(CompatMarker::PromptStart.to_string(), "A");
+        assert_eq!(
+            CompatMarker::CommandFinished { exit_code: Some(2) }.to_string(),
+            "D;2"
+        );
+        assert_eq!(
+            CompatMarker::CommandLine("ls".to_owned()).to_string(),
+            "E(\"ls\")"
+        );
+    }
+}
@@ -0,0 +1,450 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The DEC locator: the VT330/VT340 pointer protocol xterm calls "DEC
+//! locator mode".
+//!
+//! DECELR turns reporting on, DECSLE picks the button transitions that are
+//! reported, DECEFR arms a filter rectangle and DECRQLP asks for the
+//! position.  Every answer is a DECLRP report,
+//! `CSI Pe ; Pb ; Pr ; Pc ; Pp & w`.
+
+/// How long DECELR (`CSI Ps ; Pu ' z`) turns reporting on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocatorReporting {
+    /// Ps = 0: no reports.
+    #[default]
+    Off,
+    /// Ps = 1: report until turned off.
+    On,
+    /// Ps = 2: send one report, then turn off.
+    OneShot,
+}
+
+impl LocatorReporting {
+    /// The reporting selected by `ps`, if any.
+    #[must_use]
+    pub const fn from_param(ps: usize) -> Option<Self> {
+        match ps {
+            0 => Some(Self::Off),
+            1 => Some(Self::On),
+            2 => Some(Self::OneShot),
+            _ => None,
+        }
+    }
+}
+
+/// The coordinate units of DECLRP reports and DECEFR rectangles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocatorUnits {
+    /// Pu = 0 or 2: character cells.
+    #[default]
+    Cells,
+    /// Pu = 1: device pixels.
+    Pixels,
+}
+
+impl LocatorUnits {
+    /// The units selected by `pu`, if any.
+    #[must_use]
+    pub const fn from_param(pu: usize) -> Option<Self> {
+        match pu {
+            0 | 2 => Some(Self::Cells),
+            1 => Some(Self::Pixels),
+            _ => None,
+        }
+    }
+}
+
+/// A locator button, in DEC's numbering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocatorButton {
+    Left,
+    Middle,
+    Right,
+    /// The fourth button.
+    M4,
+}
+
+impl LocatorButton {
+    /// The button's bit in the DECLRP `Pb` mask.
+    const fn mask(self) -> u8 {
+        match self {
+            Self::Right => 1,
+            Self::Middle => 2,
+            Self::Left => 4,
+            Self::M4 => 8,
+        }
+    }
+
+    /// The DECLRP `Pe` event for pressing (`pressed`) or releasing it.
+    const fn event(self, pressed: bool) -> usize {
+        let down = match self {
+            Self::Left => 2,
+            Self::Middle => 4,
+            Self::Right => 6,
+            Self::M4 => 8,
+        };
+        if pressed { down } else { down + 1 }
+    }
+}
+
+/// Where the pointer is, 0-based from the top-left of the grid, both in
+/// cells and in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocatorPosition {
+    pub col: usize,
+    pub row: usize,
+    pub x_px: usize,
+    pub y_px: usize,
+}
+
+impl LocatorPosition {
+    /// The 1-based `(row, column)` DECLRP reports in `units`.
+    #[must_use]
+    pub const fn coordinates(&self, units: LocatorUnits) -> (usize, usize) {
+        match units {
+            LocatorUnits::Cells => (self.row + 1, self.col + 1),
+            LocatorUnits::Pixels => (self.y_px + 1, self.x_px + 1),
+        }
+    }
+}
+
+/// What the GUI tells the emulator about the pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocatorInput {
+    /// The pointer moved to a new position over the grid.
+    Moved(LocatorPosition),
+    /// A button was pressed or released at `position`.
+    Button {
+        button: LocatorButton,
+        pressed: bool,
+        position: LocatorPosition,
+    },
+    /// The pointer left the grid.
+    Left,
+}
+
+/// A DECEFR (`CSI Pt ; Pl ; Pb ; Pr ' w`) filter rectangle, 1-based and
+/// inclusive in the locator units.
+///
+/// `None` (a missing or zero parameter) stands for the pointer's current
+/// row or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocatorFilter {
+    pub top: Option<usize>,
+    pub left: Option<usize>,
+    pub bottom: Option<usize>,
+    pub right: Option<usize>,
+}
+
+/// A filter rectangle with every edge resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FilterRect {
+    top: usize,
+    left: usize,
+    bottom: usize,
+    right: usize,
+}
+
+impl FilterRect {
+    const fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row >= self.top && row <= self.bottom && col >= self.left && col <= self.right
+    }
+}
+
+/// The DECLRP `Pe` answering DECRQLP.
+const EVENT_REQUEST: usize = 1;
+/// The DECLRP `Pe` sent when the pointer leaves the filter rectangle.
+const EVENT_LEFT_FILTER: usize = 10;
+/// The DECLRP sent when the pointer is not over the grid.
+const UNAVAILABLE: &str = "0&w";
+
+/// The locator settings and the last pointer state the GUI sent.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LocatorState {
+    reporting: LocatorReporting,
+    units: LocatorUnits,
+    report_button_down: bool,
+    report_button_up: bool,
+    filter: Option<FilterRect>,
+    position: Option<LocatorPosition>,
+    buttons: u8,
+}
+
+impl LocatorState {
+    /// Whether DECELR has reporting on, so the GUI must send pointer input.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        !matches!(self.reporting, LocatorReporting::Off)
+    }
+
+    /// DECELR — turn reporting on or off.  Turning it off also drops the
+    /// filter rectangle.
+    pub const fn enable(&mut self, reporting: LocatorReporting, units: LocatorUnits) {
+        self.reporting = reporting;
+        self.units = units;
+        if matches!(reporting, LocatorReporting::Off) {
+            self.filter = None;
+        }
+    }
+
+    /// DECSLE — apply each selection in `params` in order: 0 reports on
+    /// request only, 1 / 2 turn button-down reports on / off and 3 / 4 do
+    /// the same for button-up.  An empty list means 0.
+    pub fn select_events(&mut self, params: &[usize]) {
+        if params.is_empty() {
+            self.select_events(&[0]);
+            return;
+        }
+        for &ps in params {
+            match ps {
+                0 => {
+                    self.report_button_down = false;
+                    self.report_button_up = false;
+                }
+                1 => self.report_button_down = true,
+                2 => self.report_button_down = false,
+                3 => self.report_button_up = true,
+                4 => self.report_button_up = false,
+                _ => {}
+            }
+        }
+    }
+
+    /// DECEFR — arm a filter rectangle.  Returns the report to send, if the
+    /// pointer is already outside it or not over the grid at all.
+    pub fn set_filter(&mut self, filter: LocatorFilter) -> Option<String> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let Some(position) = self.position else {
+            return Some(self.sent(UNAVAILABLE.to_string()));
+        };
+
+        let (row, col) = position.coordinates(self.units);
+        let rect = FilterRect {
+            top: filter.top.unwrap_or(row),
+            left: filter.left.unwrap_or(col),
+            bottom: filter.bottom.unwrap_or(row),
+            right: filter.right.unwrap_or(col),
+        };
+        self.filter = Some(rect);
+        self.check_filter()
+    }
+
+    /// DECRQLP — the current position, if reporting is on.
+    pub fn request_position(&mut self) -> Option<String> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let report = self.report(EVENT_REQUEST);
+        Some(self.sent(report))
+    }
+
+    /// Record pointer input from the GUI.  Returns the report it triggers,
+    /// if any.
+    pub fn input(&mut self, input: LocatorInput) -> Option<String> {
+        match input {
+            LocatorInput::Moved(position) => {
+                self.position = Some(position);
+                self.check_filter()
+            }
+            LocatorInput::Button {
+                button,
+                pressed,
+                position,
+            } => {
+                self.position = Some(position);
+                if pressed {
+                    self.buttons |= button.mask();
+                } else {
+                    self.buttons &= !button.mask();
+                }
+
+                let wanted = if pressed {
+                    self.report_button_down
+                } else {
+                    self.report_button_up
+                };
+                if !self.is_enabled() || !wanted {
+                    return self.check_filter();
+                }
+                // A button report also ends any filter.
+                self.filter = None;
+                let report = self.report(button.event(pressed));
+                Some(self.sent(report))
+            }
+            LocatorInput::Left => {
+                // Leaving the grid leaves any filter rectangle too; the
+                // report carries the last position seen over the grid.
+                let report = self.filter.take().map(|_| self.report(EVENT_LEFT_FILTER));
+                self.position = None;
+                report.map(|report| self.sent(report))
+            }
+        }
+    }
+
+    /// The report for leaving the filter rectangle, if the pointer has.
+    fn check_filter(&mut self) -> Option<String> {
+        let (filter, position) = (self.filter?, self.position?);
+        if !self.is_enabled() || filter.contains(position.coordinates(self.units)) {
+            return None;
+        }
+
+        self.filter = None;
+        let report = self.report(EVENT_LEFT_FILTER);
+        Some(self.sent(report))
+    }
+
+    /// The DECLRP body (without the CSI) for event `pe` at the current
+    /// position, or the "unavailable" report off the grid.
+    fn report(&self, pe: usize) -> String {
+        self.position.map_or_else(
+            || UNAVAILABLE.to_string(),
+            |position| {
+                let (row, col) = position.coordinates(self.units);
+                format!("{pe};{};{row};{col};1&w", self.buttons)
+            },
+        )
+    }
+
+    /// Account for `report` being sent: one-shot reporting ends with it.
+    fn sent(&mut self, report: String) -> String {
+        if self.reporting == LocatorReporting::OneShot {
+            self.reporting = LocatorReporting::Off;
+            self.filter = None;
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(col: usize, row: usize) -> LocatorPosition {
+        LocatorPosition {
+            col,
+            row,
+            x_px: col * 8 + 3,
+            y_px: row * 16 + 5,
+        }
+    }
+
+    fn enabled() -> LocatorState {
+        let mut locator = LocatorState::default();
+        locator.enable(LocatorReporting::On, LocatorUnits::Cells);
+        locator
+    }
+
+    #[test]
+    fn request_reports_position_once_enabled() {
+        let mut locator = LocatorState::default();
+        assert_eq!(locator.input(LocatorInput::Moved(at(4, 2))), None);
+        assert_eq!(locator.request_position(), None);
+
+        locator.enable(LocatorReporting::On, LocatorUnits::Cells);
+        assert_eq!(locator.request_position().as_deref(), Some("1;0;3;5;1&w"));
+
+        locator.enable(LocatorReporting::On, LocatorUnits::Pixels);
+        assert_eq!(locator.request_position().as_deref(), Some("1;0;38;36;1&w"));
+    }
+
+    #[test]
+    fn request_off_the_grid_is_unavailable() {
+        let mut locator = enabled();
+        assert_eq!(locator.request_position().as_deref(), Some("0&w"));
+    }
+
+    #[test]
+    fn one_shot_turns_off_after_a_report() {
+        let mut locator = LocatorState::default();
+        locator.enable(LocatorReporting::OneShot, LocatorUnits::Cells);
+        locator.input(LocatorInput::Moved(at(0, 0)));
+        assert!(locator.request_position().is_some());
+        assert!(!locator.is_enabled());
+        assert_eq!(locator.request_position(), None);
+    }
+
+    #[test]
+    fn button_events_follow_the_selection() {
+        let mut locator = enabled();
+        let press = |button, pressed| LocatorInput::Button {
+            button,
+            pressed,
+            position: at(1, 1),
+        };
+
+        assert_eq!(locator.input(press(LocatorButton::Left, true)), None);
+        locator.select_events(&[1, 3]);
+        assert_eq!(
+            locator.input(press(LocatorButton::Right, true)).as_deref(),
+            Some("6;5;2;2;1&w")
+        );
+        assert_eq!(
+            locator.input(press(LocatorButton::Left, false)).as_deref(),
+            Some("3;1;2;2;1&w")
+        );
+
+        locator.select_events(&[]);
+        assert_eq!(locator.input(press(LocatorButton::Right, false)), None);
+    }
+
+    #[test]
+    fn leaving_the_filter_reports_once() {
+        let mut locator = enabled();
+        locator.input(LocatorInput::Moved(at(5, 5)));
+        let filter = LocatorFilter {
+            top: Some(5),
+            left: Some(5),
+            bottom: Some(7),
+            right: Some(7),
+        };
+        assert_eq!(locator.set_filter(filter), None);
+        assert_eq!(locator.input(LocatorInput::Moved(at(6, 6))), None);
+        assert_eq!(
+            locator.input(LocatorInput::Moved(at(7, 6))).as_deref(),
+            Some("10;0;7;8;1&w")
+        );
+        assert_eq!(locator.input(LocatorInput::Moved(at(9, 9))), None);
+    }
+
+    #[test]
+    fn default_filter_is_the_current_cell() {
+        let mut locator = enabled();
+        locator.input(LocatorInput::Moved(at(2, 2)));
+        assert_eq!(locator.set_filter(LocatorFilter::default()), None);
+        assert_eq!(
+            locator.input(LocatorInput::Moved(at(2, 3))).as_deref(),
+            Some("10;0;4;3;1&w")
+        );
+    }
+
+    #[test]
+    fn filter_outside_the_pointer_reports_at_once() {
+        let mut locator = enabled();
+        locator.input(LocatorInput::Moved(at(0, 0)));
+        let filter = LocatorFilter {
+            top: Some(5),
+            left: Some(5),
+            bottom: Some(6),
+            right: Some(6),
+        };
+        assert_eq!(locator.set_filter(filter).as_deref(), Some("10;0;1;1;1&w"));
+    }
+
+    #[test]
+    fn leaving_the_grid_ends_the_filter() {
+        let mut locator = enabled();
+        locator.input(LocatorInput::Moved(at(3, 3)));
+        locator.set_filter(LocatorFilter::default());
+        assert_eq!(
+            locator.input(LocatorInput::Left).as_deref(),
+            Some("10;0;4;4;1&w")
+        );
+        assert_eq!(locator.request_position().as_deref(), Some("0&w"));
+    }
+}
@@ -11,6 +11,7 @@
 
 /// Which of the two terminal buffers is currently active.
 pub mod buffer_type;
+pub mod charset;
 /// `CommandBlock` — a single shell command's full lifecycle from OSC 133 markers.
 pub mod command_block;
 /// Cursor state types: position, colors, decorations, and reverse-video.
@@ -23,24 +24,42 @@ pub mod fonts;
 pub mod format_tag;
 /// OSC 133 (FTCS) shell integration state.
 pub mod ftcs;
+/// Plain OSC 133 and VS Code OSC 633 shell-integration markers.
+pub mod ftcs_compat;
 /// Kitty graphics protocol types and helpers.
 pub mod kitty_graphics;
 /// DEC Special Graphics (line-drawing) character remapping.
 pub mod line_draw;
 /// Soft-wrap line join metadata.
 pub mod line_wrap;
+/// The DEC locator (DECELR / DECSLE / DECEFR / DECRQLP) and its reports.
+pub mod locator;
 /// `Mode` and `SetMode` — generic set/reset mode command types.
 pub mod mode;
 /// Typed DEC private mode enums (one module per mode number).
 pub mod modes;
 /// OSC parameter types and inline-image data.
 pub mod osc;
+/// `ConEmu` OSC 9 sub-commands, including `9;4` task progress.
+pub mod osc_conemu;
+/// `iTerm2` OSC 1337 commands other than inline images.
+pub mod osc_iterm2;
 /// OSC 99 (kitty desktop notifications) parser and typed command types.
 pub mod osc_notify_99;
 /// `PointerShape` — typed cursor shape set by OSC 22.
 pub mod pointer_shape;
+/// DECCIR and DECTABSR presentation state reports (DECRQPSR / DECRSPS).
+pub mod presentation_state;
+/// Parameter types for the VT420 rectangular area operations.
+pub mod rectangle;
+/// The attribute selection saved by xterm's SGR stack (XTPUSHSGR).
+pub mod sgr_stack;
 /// Sixel graphics types.
 pub mod sixel;
+/// Downloadable soft character sets (DECDLD).
+pub mod soft_font;
+/// The DEC status line selections (DECSSDT / DECSASD).
+pub mod status_line;
 /// `TChar` — a single terminal character with optional wide-character metadata.
 pub mod tchar;
 /// `TerminalOutput` — the parsed command enum produced by the ANSI parser.
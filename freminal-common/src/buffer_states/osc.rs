@@ -7,7 +7,10 @@ use std::convert::Infallible;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use crate::buffer_states::{ftcs::FtcsMarker, pointer_shape::PointerShape, url::Url};
+use crate::buffer_states::{
+    ftcs::FtcsMarker, ftcs_compat::CompatMarker, pointer_shape::PointerShape, url::Url,
+};
+use crate::colors::SpecialColor;
 use std::fmt;
 
 /// iTerm2 inline image dimension specification.
@@ -131,6 +134,8 @@ pub enum OscTarget {
     Foreground,
     // https://iterm2.com/documentation-escape-codes.html
     Ftcs,
+    /// OSC 633 — VS Code shell integration (`A`–`E`, `P`).
+    VsCode,
     Clipboard,
     PaletteColor,
     ResetPaletteColor,
@@ -143,24 +148,35 @@ pub enum OscTarget {
     ResetForeground,
     /// OSC 111 — reset text background color to the theme default.
     ResetBackground,
-    /// OSC 13 — mouse cursor foreground color (X11 concept; not applicable to
-    /// GPU-rendered terminals).  Recognised and silently consumed.
+    /// OSC 13 — set or query the mouse pointer foreground color.  The OS
+    /// draws the pointer, so the color is only stored and reported.
     MouseForeground,
-    /// OSC 14 — mouse cursor background color (X11 concept; not applicable to
-    /// GPU-rendered terminals).  Recognised and silently consumed.
+    /// OSC 14 — set or query the mouse pointer background color.  The OS
+    /// draws the pointer, so the color is only stored and reported.
     MouseBackground,
+    /// OSC 113 — reset the mouse pointer foreground color.
+    ResetMouseForeground,
+    /// OSC 114 — reset the mouse pointer background color.
+    ResetMouseBackground,
     /// OSC 15 — Tektronix foreground color (legacy VT100 graphics mode;
     /// unimplemented).  Recognised and silently consumed.
     TekForeground,
     /// OSC 16 — Tektronix cursor/background color (legacy VT100 graphics mode;
     /// unimplemented).  Recognised and silently consumed.
     TekBackground,
-    /// OSC 17 — highlight (selection) background color.  Recognised and
-    /// silently consumed; candidate for future response implementation.
+    /// OSC 17 — set or query the highlight (selection) background color.
     HighlightBackground,
-    /// OSC 19 — highlight (selection) foreground color.  Recognised and
-    /// silently consumed; candidate for future response implementation.
+    /// OSC 19 — set or query the highlight (selection) foreground color.
     HighlightForeground,
+    /// OSC 117 — reset the highlight background color.
+    ResetHighlightBackground,
+    /// OSC 119 — reset the highlight foreground color.
+    ResetHighlightForeground,
+    /// OSC 5 — set or query a special color (bold, underline, blink,
+    /// reverse).
+    SpecialColor,
+    /// OSC 105 — reset special colors.
+    ResetSpecialColor,
     /// OSC 22 — set/reset the X11 pointer (mouse cursor) shape.  One-way
     /// command, no response expected.
     PointerShape,
@@ -229,6 +245,7 @@ impl From<&AnsiOscToken> for OscTarget {
             AnsiOscToken::OscValue(0 | 2) => Self::TitleBar,
             AnsiOscToken::OscValue(1) => Self::IconName,
             AnsiOscToken::OscValue(4) => Self::PaletteColor,
+            AnsiOscToken::OscValue(5) => Self::SpecialColor,
             AnsiOscToken::OscValue(7) => Self::RemoteHost,
             AnsiOscToken::OscValue(8) => Self::Url,
             AnsiOscToken::OscValue(9) => Self::Notify9,
@@ -246,18 +263,51 @@ impl From<&AnsiOscToken> for OscTarget {
             AnsiOscToken::OscValue(52) => Self::Clipboard,
             AnsiOscToken::OscValue(66) => Self::ColorSchemeNotification,
             AnsiOscToken::OscValue(104) => Self::ResetPaletteColor,
+            AnsiOscToken::OscValue(105) => Self::ResetSpecialColor,
             AnsiOscToken::OscValue(112) => Self::ResetCursorColor,
             AnsiOscToken::OscValue(133) => Self::Ftcs,
+            AnsiOscToken::OscValue(633) => Self::VsCode,
             AnsiOscToken::OscValue(1337) => Self::ITerm2,
             AnsiOscToken::OscValue(1338) => Self::ShellInfo,
             AnsiOscToken::OscValue(110) => Self::ResetForeground,
             AnsiOscToken::OscValue(111) => Self::ResetBackground,
+            AnsiOscToken::OscValue(113) => Self::ResetMouseForeground,
+            AnsiOscToken::OscValue(114) => Self::ResetMouseBackground,
+            AnsiOscToken::OscValue(117) => Self::ResetHighlightBackground,
+            AnsiOscToken::OscValue(119) => Self::ResetHighlightForeground,
             AnsiOscToken::OscValue(777) => Self::Notify777,
             _ => Self::Unknown,
         }
     }
 }
 
+/// A dynamic color other than the text foreground, background and cursor,
+/// which have their own [`AnsiOscType`] variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicColor {
+    /// OSC 13 / 113 — mouse pointer foreground.
+    MouseForeground,
+    /// OSC 14 / 114 — mouse pointer background.
+    MouseBackground,
+    /// OSC 17 / 117 — highlight (selection) background.
+    HighlightBackground,
+    /// OSC 19 / 119 — highlight (selection) foreground.
+    HighlightForeground,
+}
+
+impl DynamicColor {
+    /// The OSC number that sets or queries this color.
+    #[must_use]
+    pub const fn osc_number(self) -> u16 {
+        match self {
+            Self::MouseForeground => 13,
+            Self::MouseBackground => 14,
+            Self::HighlightBackground => 17,
+            Self::HighlightForeground => 19,
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub enum UrlResponse {
     Url(Url),
@@ -308,6 +358,9 @@ pub enum AnsiOscType {
     RequestColorQueryBackground(AnsiOscInternalType),
     RequestColorQueryForeground(AnsiOscInternalType),
     Ftcs(FtcsMarker),
+    /// OSC 133 without the `freminal=1` tag, or OSC 633 — a marker from
+    /// another shell integration, paired by order.
+    FtcsCompat(CompatMarker),
     // NOTE: OSC 0 and 2 are conflated as title-bar-only. If tabs are added,
     // OSC 0 should also set the icon name and OSC 2 should set only the title.
     SetTitleBar(String),
@@ -327,6 +380,8 @@ pub enum AnsiOscType {
     /// OSC 1337 `FileEnd` (iTerm2 multipart protocol).
     /// Signals the end of a multipart file transfer.
     ITerm2FileEnd,
+    /// OSC 1337 text command, such as `SetUserVar=` or `SetMark`.
+    ITerm2(crate::buffer_states::osc_iterm2::ITerm2Command),
     /// OSC 1337 unrecognised sub-command (silently consumed).
     ITerm2Unknown,
     /// OSC 52 clipboard set: selection name + decoded (plaintext) content.
@@ -343,6 +398,16 @@ pub enum AnsiOscType {
     ResetForegroundColor,
     /// OSC 111 — reset the dynamic background color override.
     ResetBackgroundColor,
+    /// OSC 13 / 14 / 17 / 19 — query or set a dynamic color.
+    RequestColorQueryDynamic(DynamicColor, AnsiOscInternalType),
+    /// OSC 113 / 114 / 117 / 119 — reset a dynamic color override.
+    ResetDynamicColor(DynamicColor),
+    /// OSC 5 set special color: which color, r, g, b.
+    SetSpecialColor(SpecialColor, u8, u8, u8),
+    /// OSC 5 query special color.
+    QuerySpecialColor(SpecialColor),
+    /// OSC 105 reset a special color, or all if `None`.
+    ResetSpecialColor(Option<SpecialColor>),
     /// OSC 22 — set the pointer (mouse cursor) shape.
     ///
     /// An empty name or `"default"` resets to the OS default.
@@ -375,6 +440,9 @@ pub enum AnsiOscType {
     /// fully-parsed [`crate::buffer_states::osc_notify_99::Osc99Command`].
     /// Chunk reassembly / transport / GUI are handled downstream (Tasks 99.3+).
     Notify99(crate::buffer_states::osc_notify_99::Osc99Command),
+    /// OSC 9 — a `ConEmu` sub-command (`9;1` to `9;12`), such as `9;4`
+    /// progress.  Bodies that are not sub-commands are [`Self::Notify`].
+    ConEmu(crate::buffer_states::osc_conemu::ConEmuCommand),
 }
 
 /// Which OSC sequence produced a one-way text notification, so the GUI can
@@ -401,6 +469,7 @@ impl std::fmt::Display for AnsiOscType {
             Self::Url(url) => write!(f, "Url({url})"),
             Self::SetTitleBar(value) => write!(f, "SetTitleBar({value:?})"),
             Self::Ftcs(marker) => write!(f, "Ftcs ({marker})"),
+            Self::FtcsCompat(marker) => write!(f, "FtcsCompat ({marker})"),
             Self::RemoteHost(value) => write!(f, "RemoteHost ({value:?})"),
             Self::RequestColorQueryCursor(value) => {
                 write!(f, "RequestColorQueryCursor({value:?})")
@@ -426,6 +495,7 @@ impl std::fmt::Display for AnsiOscType {
                 write!(f, "ITerm2FilePart({}B)", bytes.len())
             }
             Self::ITerm2FileEnd => write!(f, "ITerm2FileEnd"),
+            Self::ITerm2(cmd) => write!(f, "ITerm2({cmd})"),
             Self::ITerm2Unknown => write!(f, "ITerm2Unknown"),
             Self::SetClipboard(sel, content) => write!(f, "SetClipboard({sel:?}, {content:?})"),
             Self::QueryClipboard(sel) => write!(f, "QueryClipboard({sel:?})"),
@@ -436,6 +506,15 @@ impl std::fmt::Display for AnsiOscType {
             Self::ResetPaletteColor(idx) => write!(f, "ResetPaletteColor({idx:?})"),
             Self::ResetForegroundColor => write!(f, "ResetForegroundColor"),
             Self::ResetBackgroundColor => write!(f, "ResetBackgroundColor"),
+            Self::RequestColorQueryDynamic(color, value) => {
+                write!(f, "RequestColorQueryDynamic({color:?}, {value:?})")
+            }
+            Self::ResetDynamicColor(color) => write!(f, "ResetDynamicColor({color:?})"),
+            Self::SetSpecialColor(color, r, g, b) => {
+                write!(f, "SetSpecialColor({color:?}, {r}, {g}, {b})")
+            }
+            Self::QuerySpecialColor(color) => write!(f, "QuerySpecialColor({color:?})"),
+            Self::ResetSpecialColor(color) => write!(f, "ResetSpecialColor({color:?})"),
             Self::SetPointerShape(shape) => write!(f, "SetPointerShape({shape})"),
             Self::ShellInfoHistFile(path) => write!(f, "ShellInfoHistFile({})", path.display()),
             Self::Notify {
@@ -447,6 +526,7 @@ impl std::fmt::Display for AnsiOscType {
                 "Notify(source={source:?}, title={title:?}, body={body:?})"
             ),
             Self::Notify99(cmd) => write!(f, "Notify99(id={:?})", cmd.id),
+            Self::ConEmu(cmd) => write!(f, "ConEmu({cmd})"),
         }
     }
 }
@@ -726,6 +806,19 @@ mod tests {
         assert!(s.contains("255"), "got: {s}");
     }
 
+    #[test]
+    fn display_ansi_osc_dynamic_and_special_colors() {
+        let s = AnsiOscType::RequestColorQueryDynamic(
+            DynamicColor::HighlightBackground,
+            AnsiOscInternalType::Query,
+        )
+        .to_string();
+        assert_eq!(s, "RequestColorQueryDynamic(HighlightBackground, Query)");
+        let s = AnsiOscType::SetSpecialColor(SpecialColor::Bold, 1, 2, 3).to_string();
+        assert_eq!(s, "SetSpecialColor(Bold, 1, 2, 3)");
+        assert_eq!(DynamicColor::HighlightForeground.osc_number(), 19);
+    }
+
     #[test]
     fn display_ansi_osc_set_pointer_shape() {
         let s =
@@ -793,6 +886,15 @@ mod tests {
         assert_eq!(AnsiOscType::ITerm2Unknown.to_string(), "ITerm2Unknown");
     }
 
+    #[test]
+    fn display_ansi_osc_iterm2_command() {
+        use crate::buffer_states::osc_iterm2::ITerm2Command;
+        assert_eq!(
+            AnsiOscType::ITerm2(ITerm2Command::SetMark).to_string(),
+            "ITerm2(SetMark)"
+        );
+    }
+
     #[test]
     fn display_ansi_osc_shell_info_histfile() {
         let s =
@@ -1006,6 +1108,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn osc_target_from_token_dynamic_and_special_color_resets() {
+        for (number, target) in [
+            (5, OscTarget::SpecialColor),
+            (105, OscTarget::ResetSpecialColor),
+            (113, OscTarget::ResetMouseForeground),
+            (114, OscTarget::ResetMouseBackground),
+            (117, OscTarget::ResetHighlightBackground),
+            (119, OscTarget::ResetHighlightForeground),
+        ] {
+            assert_eq!(OscTarget::from(&AnsiOscToken::OscValue(number)), target);
+        }
+    }
+
     #[test]
     fn osc_target_from_token_pointer_shape() {
         assert_eq!(
@@ -1054,6 +1170,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn osc_target_from_token_vscode() {
+        assert_eq!(
+            OscTarget::from(&AnsiOscToken::OscValue(633)),
+            OscTarget::VsCode
+        );
+    }
+
     #[test]
     fn osc_target_from_token_iterm2() {
         assert_eq!(
@@ -0,0 +1,285 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Parser for the `ConEmu` `OSC 9 ; Ps ; …` sub-commands.
+//!
+//! Reference: <https://conemu.github.io/en/AnsiEscapeCodes.html#ConEmu_specific_OSC>
+//!
+//! `ConEmu` overloads OSC 9, which iTerm2 and `WezTerm` use for a plain
+//! notification body.  A body whose first `;`-separated field is a number
+//! from 1 to 12 is a `ConEmu` sub-command; anything else is a notification,
+//! reported here as [`ConEmuParseError::NotConEmu`].
+//!
+//! `9;4` is the one most programs send: systemd, winget, `PowerShell` and
+//! cargo-style tools report task progress with it.
+
+use std::fmt;
+
+/// The highest percentage a progress report can carry.
+const MAX_PERCENT: u8 = 100;
+
+/// The state shown by a `9;4` progress report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressState {
+    /// `st = 1`: normal progress.
+    Normal,
+    /// `st = 2`: the task failed.
+    Error,
+    /// `st = 3`: the task is busy but its progress is unknown.
+    Indeterminate,
+    /// `st = 4`: the task is paused (`ConEmu`'s "warning" state).
+    Paused,
+}
+
+/// A pane's current task progress, as last set by `OSC 9 ; 4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskProgress {
+    /// What the progress indicator shows.
+    pub state: ProgressState,
+    /// Completion from 0 to 100.  Meaningless for
+    /// [`ProgressState::Indeterminate`].
+    pub percent: u8,
+}
+
+/// One `ConEmu` OSC 9 sub-command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConEmuCommand {
+    /// `9;1;ms` — pause the console for `ms` milliseconds.
+    Sleep(u32),
+    /// `9;2;txt` — show a message box.
+    MessageBox(String),
+    /// `9;3;txt` — rename the tab; an empty title restores the default.
+    TabTitle(String),
+    /// `9;4;st;pr` — set the progress indicator.  `percent` is `None` when
+    /// `pr` is missing or zero, which for the error and paused states keeps
+    /// the previous percentage.
+    SetProgress {
+        /// The state to show.
+        state: ProgressState,
+        /// The new percentage, clamped to 100.
+        percent: Option<u8>,
+    },
+    /// `9;4;0` — remove the progress indicator.
+    ClearProgress,
+    /// `9;5` — wait for the Enter key.
+    WaitForInput,
+    /// `9;6;macro` — run a `ConEmu` GUI macro.
+    GuiMacro(String),
+    /// `9;7;cmd` — run a process.
+    RunProcess(String),
+    /// `9;8;env` — print the value of an environment variable.
+    EnvironmentVariable(String),
+    /// `9;9;cwd` — report the shell's current directory.
+    WorkingDirectory(String),
+    /// `9;10` or `9;10;n` — xterm keyboard emulation.
+    XtermKeyboard(Option<u8>),
+    /// `9;11;txt` — write to the `ConEmu` debug log.
+    DebugLog(String),
+    /// `9;12` — the cursor is at the start of a prompt.
+    PromptStart,
+}
+
+/// Error produced while parsing an OSC 9 body as a `ConEmu` sub-command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConEmuParseError {
+    /// The body is not a `ConEmu` sub-command (it is a notification).
+    NotConEmu,
+    /// A `ConEmu` sub-command had an argument it does not accept.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for ConEmuParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConEmu => write!(f, "not a ConEmu sub-command"),
+            Self::InvalidArgument(s) => write!(f, "invalid argument: {s}"),
+        }
+    }
+}
+
+impl fmt::Display for ConEmuCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sleep(ms) => write!(f, "Sleep({ms}ms)"),
+            Self::MessageBox(text) => write!(f, "MessageBox({text:?})"),
+            Self::TabTitle(title) => write!(f, "TabTitle({title:?})"),
+            Self::SetProgress { state, percent } => {
+                write!(f, "SetProgress({state:?}, {percent:?})")
+            }
+            Self::ClearProgress => write!(f, "ClearProgress"),
+            Self::WaitForInput => write!(f, "WaitForInput"),
+            Self::GuiMacro(text) => write!(f, "GuiMacro({text:?})"),
+            Self::RunProcess(cmd) => write!(f, "RunProcess({cmd:?})"),
+            Self::EnvironmentVariable(name) => write!(f, "EnvironmentVariable({name:?})"),
+            Self::WorkingDirectory(cwd) => write!(f, "WorkingDirectory({cwd:?})"),
+            Self::XtermKeyboard(mode) => write!(f, "XtermKeyboard({mode:?})"),
+            Self::DebugLog(text) => write!(f, "DebugLog({text:?})"),
+            Self::PromptStart => write!(f, "PromptStart"),
+        }
+    }
+}
+
+/// Parse an OSC 9 body (the text after `9;`) as a `ConEmu` sub-command.
+///
+/// # Errors
+/// [`ConEmuParseError::NotConEmu`] if the body does not start with a
+/// sub-command number; [`ConEmuParseError::InvalidArgument`] if a numeric
+/// argument is malformed or out of range.
+pub fn parse_conemu(body: &str) -> Result<ConEmuCommand, ConEmuParseError> {
+    let (number, args) = body.split_once(';').unwrap_or((body, ""));
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ConEmuParseError::NotConEmu);
+    }
+
+    let text = || unquote(args).to_owned();
+    match number.parse::<u8>() {
+        Ok(1) => parse_number(args).map(ConEmuCommand::Sleep),
+        Ok(2) => Ok(ConEmuCommand::MessageBox(text())),
+        Ok(3) => Ok(ConEmuCommand::TabTitle(text())),
+        Ok(4) => parse_progress(args),
+        Ok(5) => Ok(ConEmuCommand::WaitForInput),
+        Ok(6) => Ok(ConEmuCommand::GuiMacro(text())),
+        Ok(7) => Ok(ConEmuCommand::RunProcess(text())),
+        Ok(8) => Ok(ConEmuCommand::EnvironmentVariable(text())),
+        Ok(9) => Ok(ConEmuCommand::WorkingDirectory(text())),
+        Ok(10) if args.is_empty() => Ok(ConEmuCommand::XtermKeyboard(None)),
+        Ok(10) => parse_number(args).map(|mode| ConEmuCommand::XtermKeyboard(Some(mode))),
+        Ok(11) => Ok(ConEmuCommand::DebugLog(text())),
+        Ok(12) => Ok(ConEmuCommand::PromptStart),
+        _ => Err(ConEmuParseError::NotConEmu),
+    }
+}
+
+/// Parse the `st;pr` arguments of `9;4`.  Both default to 0.
+fn parse_progress(args: &str) -> Result<ConEmuCommand, ConEmuParseError> {
+    let (st, pr) = args.split_once(';').unwrap_or((args, ""));
+    let st = if st.is_empty() { 0 } else { parse_number(st)? };
+    let pr: u32 = if pr.is_empty() { 0 } else { parse_number(pr)? };
+    let percent = u8::try_from(pr.min(u32::from(MAX_PERCENT))).unwrap_or(MAX_PERCENT);
+
+    let state = match st {
+        0 => return Ok(ConEmuCommand::ClearProgress),
+        1 => ProgressState::Normal,
+        2 => ProgressState::Error,
+        3 => ProgressState::Indeterminate,
+        4 => ProgressState::Paused,
+        _ => {
+            return Err(ConEmuParseError::InvalidArgument(format!(
+                "progress state {st}"
+            )));
+        }
+    };
+    // A normal report always sets the percentage; the others keep the last
+    // one unless they carry their own.
+    let percent = (state == ProgressState::Normal || percent > 0).then_some(percent);
+    Ok(ConEmuCommand::SetProgress { state, percent })
+}
+
+fn parse_number<T: std::str::FromStr>(field: &str) -> Result<T, ConEmuParseError> {
+    field
+        .parse()
+        .map_err(|_| ConEmuParseError::InvalidArgument(field.to_owned()))
+}
+
+/// Strip one pair of surrounding double quotes, which `ConEmu` allows around
+/// text arguments.
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_states() {
+        assert_eq!(
+            parse_conemu("4;1;42"),
+            Ok(ConEmuCommand::SetProgress {
+                state: ProgressState::Normal,
+                percent: Some(42),
+            })
+        );
+        assert_eq!(
+            parse_conemu("4;1"),
+            Ok(ConEmuCommand::SetProgress {
+                state: ProgressState::Normal,
+                percent: Some(0),
+            })
+        );
+        assert_eq!(
+            parse_conemu("4;2"),
+            Ok(ConEmuCommand::SetProgress {
+                state: ProgressState::Error,
+                percent: None,
+            })
+        );
+        assert_eq!(
+            parse_conemu("4;3;50"),
+            Ok(ConEmuCommand::SetProgress {
+                state: ProgressState::Indeterminate,
+                percent: Some(50),
+            })
+        );
+        assert_eq!(
+            parse_conemu("4;4;250"),
+            Ok(ConEmuCommand::SetProgress {
+                state: ProgressState::Paused,
+                percent: Some(100),
+            })
+        );
+        assert_eq!(parse_conemu("4;0;80"), Ok(ConEmuCommand::ClearProgress));
+        assert_eq!(parse_conemu("4"), Ok(ConEmuCommand::ClearProgress));
+    }
+
+    #[test]
+    fn malformed_progress_is_rejected() {
+        assert!(matches!(
+            parse_conemu("4;5;10"),
+            Err(ConEmuParseError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            parse_conemu("4;1;ten"),
+            Err(ConEmuParseError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn other_sub_commands() {
+        assert_eq!(parse_conemu("1;250"), Ok(ConEmuCommand::Sleep(250)));
+        assert_eq!(
+            parse_conemu("2;\"Hello; world\""),
+            Ok(ConEmuCommand::MessageBox("Hello; world".to_owned()))
+        );
+        assert_eq!(
+            parse_conemu("3;"),
+            Ok(ConEmuCommand::TabTitle(String::new()))
+        );
+        assert_eq!(parse_conemu("5"), Ok(ConEmuCommand::WaitForInput));
+        assert_eq!(
+            parse_conemu("9;\"C:\\Users\""),
+            Ok(ConEmuCommand::WorkingDirectory("C:\\Users".to_owned()))
+        );
+        assert_eq!(parse_conemu("10"), Ok(ConEmuCommand::XtermKeyboard(None)));
+        assert_eq!(
+            parse_conemu("10;1"),
+            Ok(ConEmuCommand::XtermKeyboard(Some(1)))
+        );
+        assert_eq!(parse_conemu("12"), Ok(ConEmuCommand::PromptStart));
+    }
+
+    #[test]
+    fn notifications_are_not_conemu() {
+        for body in ["Build finished", "1 file changed", "13;x", "", ";4", "04x"] {
+            assert_eq!(
+                parse_conemu(body),
+                Err(ConEmuParseError::NotConEmu),
+                "{body:?}"
+            );
+        }
+    }
+}
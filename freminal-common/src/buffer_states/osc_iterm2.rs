@@ -0,0 +1,292 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Parser for the `iTerm2` `OSC 1337 ; key [= value]` commands other than
+//! inline images.
+//!
+//! Reference: <https://iterm2.com/documentation-escape-codes.html>
+//!
+//! The `File=` / `MultipartFile=` / `FilePart=` / `FileEnd` image transfer
+//! is parsed separately, because its payload is binary.  The commands here
+//! carry text: user variables, the working directory, marks, attention
+//! requests, the cell-size report and the badge.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What `OSC 1337 ; RequestAttention=` asks the window to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttentionRequest {
+    /// `yes` / `true` — ask for attention until the window is focused.
+    Start,
+    /// `once` — ask for attention briefly.
+    Once,
+    /// `no` / `false` — withdraw an earlier request.
+    Stop,
+    /// `fireworks` — `iTerm2` draws fireworks at the cursor; a brief
+    /// request here.
+    Fireworks,
+}
+
+/// One `iTerm2` `OSC 1337` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ITerm2Command {
+    /// `SetUserVar=name=base64` — set a per-pane user variable.  An empty
+    /// value removes the variable.
+    SetUserVar {
+        /// Variable name.
+        name: String,
+        /// Decoded value.
+        value: String,
+    },
+    /// `CurrentDir=path` — report the shell's current directory.
+    CurrentDir(String),
+    /// `SetMark` — bookmark the cursor row in the scrollback.
+    SetMark,
+    /// `RequestAttention=value` — ask for the user's attention.
+    RequestAttention(AttentionRequest),
+    /// `ReportCellSize` — report the cell size in pixels.
+    ReportCellSize,
+    /// `SetBadgeFormat=base64` — set the badge shown over the pane.  An
+    /// empty format removes the badge.
+    SetBadgeFormat(String),
+    /// `ClearScrollback` — erase the scrollback.
+    ClearScrollback,
+}
+
+/// Error produced while parsing an `OSC 1337` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ITerm2ParseError {
+    /// The key is not one of the commands freminal handles.
+    Unsupported,
+    /// A supported command had an argument it does not accept.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for ITerm2ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "unsupported command"),
+            Self::InvalidArgument(s) => write!(f, "invalid argument: {s}"),
+        }
+    }
+}
+
+impl fmt::Display for ITerm2Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetUserVar { name, value } => write!(f, "SetUserVar({name}={value:?})"),
+            Self::CurrentDir(path) => write!(f, "CurrentDir({path:?})"),
+            Self::SetMark => write!(f, "SetMark"),
+            Self::RequestAttention(request) => write!(f, "RequestAttention({request:?})"),
+            Self::ReportCellSize => write!(f, "ReportCellSize"),
+            Self::SetBadgeFormat(format) => write!(f, "SetBadgeFormat({format:?})"),
+            Self::ClearScrollback => write!(f, "ClearScrollback"),
+        }
+    }
+}
+
+/// Parse an `OSC 1337` body (the text after `1337;`).
+///
+/// # Errors
+/// [`ITerm2ParseError::Unsupported`] for keys freminal does not handle;
+/// [`ITerm2ParseError::InvalidArgument`] for a malformed value, such as
+/// base64 that does not decode.
+pub fn parse_iterm2_command(body: &str) -> Result<ITerm2Command, ITerm2ParseError> {
+    let (key, value) = match body.split_once('=') {
+        Some((key, value)) => (key, Some(value)),
+        None => (body, None),
+    };
+
+    match (key, value) {
+        ("SetUserVar", Some(value)) => {
+            let (name, encoded) = value
+                .split_once('=')
+                .filter(|(name, _)| !name.is_empty())
+                .ok_or_else(|| ITerm2ParseError::InvalidArgument(value.to_owned()))?;
+            Ok(ITerm2Command::SetUserVar {
+                name: name.to_owned(),
+                value: decode_text(encoded)?,
+            })
+        }
+        ("CurrentDir", Some(path)) => Ok(ITerm2Command::CurrentDir(path.to_owned())),
+        ("SetMark", None) => Ok(ITerm2Command::SetMark),
+        ("RequestAttention", Some(value)) => {
+            let request = match value {
+                "yes" | "true" => AttentionRequest::Start,
+                "once" => AttentionRequest::Once,
+                "no" | "false" => AttentionRequest::Stop,
+                "fireworks" => AttentionRequest::Fireworks,
+                _ => return Err(ITerm2ParseError::InvalidArgument(value.to_owned())),
+            };
+            Ok(ITerm2Command::RequestAttention(request))
+        }
+        ("ReportCellSize", None) => Ok(ITerm2Command::ReportCellSize),
+        ("SetBadgeFormat", Some(encoded)) => {
+            decode_text(encoded).map(ITerm2Command::SetBadgeFormat)
+        }
+        ("ClearScrollback", None) => Ok(ITerm2Command::ClearScrollback),
+        _ => Err(ITerm2ParseError::Unsupported),
+    }
+}
+
+fn decode_text(encoded: &str) -> Result<String, ITerm2ParseError> {
+    crate::base64::decode(encoded)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .map_err(|_| ITerm2ParseError::InvalidArgument(encoded.to_owned()))
+}
+
+/// Expand a badge format against the pane's user variables.
+///
+/// `iTerm2` badges interpolate `\(expression)`; the expressions freminal
+/// resolves are `user.<name>`, which expand to the variable's value or to
+/// nothing when it is unset.  Other expressions also expand to nothing.
+/// A `\(` without a closing `)` is kept as written.
+#[must_use]
+pub fn expand_badge(format: &str, user_vars: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(start) = rest.find("\\(") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        if let Some(value) = after[..end]
+            .trim()
+            .strip_prefix("user.")
+            .and_then(|name| user_vars.get(name))
+        {
+            out.push_str(value);
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn b64(text: &str) -> String {
+        crate::base64::encode(text.as_bytes())
+    }
+
+    #[test]
+    fn user_vars() {
+        assert_eq!(
+            parse_iterm2_command(&format!("SetUserVar=gitBranch={}", b64("main"))),
+            Ok(ITerm2Command::SetUserVar {
+                name: "gitBranch".to_owned(),
+                value: "main".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_iterm2_command("SetUserVar=empty="),
+            Ok(ITerm2Command::SetUserVar {
+                name: "empty".to_owned(),
+                value: String::new(),
+            })
+        );
+        for body in [
+            "SetUserVar=novalue",
+            "SetUserVar==bWFpbg==",
+            "SetUserVar=x=!!",
+        ] {
+            assert!(
+                matches!(
+                    parse_iterm2_command(body),
+                    Err(ITerm2ParseError::InvalidArgument(_))
+                ),
+                "{body:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn attention_requests() {
+        for (value, request) in [
+            ("yes", AttentionRequest::Start),
+            ("true", AttentionRequest::Start),
+            ("once", AttentionRequest::Once),
+            ("no", AttentionRequest::Stop),
+            ("false", AttentionRequest::Stop),
+            ("fireworks", AttentionRequest::Fireworks),
+        ] {
+            assert_eq!(
+                parse_iterm2_command(&format!("RequestAttention={value}")),
+                Ok(ITerm2Command::RequestAttention(request))
+            );
+        }
+        assert!(matches!(
+            parse_iterm2_command("RequestAttention=maybe"),
+            Err(ITerm2ParseError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn other_commands() {
+        assert_eq!(
+            parse_iterm2_command("CurrentDir=/home/user/a=b"),
+            Ok(ITerm2Command::CurrentDir("/home/user/a=b".to_owned()))
+        );
+        assert_eq!(parse_iterm2_command("SetMark"), Ok(ITerm2Command::SetMark));
+        assert_eq!(
+            parse_iterm2_command("ReportCellSize"),
+            Ok(ITerm2Command::ReportCellSize)
+        );
+        assert_eq!(
+            parse_iterm2_command(&format!("SetBadgeFormat={}", b64("\\(user.host)"))),
+            Ok(ITerm2Command::SetBadgeFormat("\\(user.host)".to_owned()))
+        );
+        assert_eq!(
+            parse_iterm2_command("ClearScrollback"),
+            Ok(ITerm2Command::ClearScrollback)
+        );
+    }
+
+    #[test]
+    fn unsupported_commands() {
+        for body in [
+            "StealFocus",
+            "CursorShape=1",
+            "SetMark=1",
+            "",
+            "ClearScrollback=x",
+        ] {
+            assert_eq!(
+                parse_iterm2_command(body),
+                Err(ITerm2ParseError::Unsupported),
+                "{body:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn badge_expands_user_vars() {
+        let vars = BTreeMap::from([
+            ("host".to_owned(), "db1".to_owned()),
+            ("env".to_owned(), "prod".to_owned()),
+        ]);
+        assert_eq!(
+            expand_badge("\\(user.host) [\\(user.env)]", &vars),
+            "db1 [prod]"
+        );
+        assert_eq!(expand_badge("\\(user.unset)\\(session.name)x", &vars), "x");
+        assert_eq!(expand_badge("plain", &vars), "plain");
+        assert_eq!(expand_badge("a \\(user.host", &vars), "a \\(user.host");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(ITerm2Command::SetMark.to_string(), "SetMark");
+        assert_eq!(
+            ITerm2Command::RequestAttention(AttentionRequest::Once).to_string(),
+            "RequestAttention(Once)"
+        );
+    }
+}
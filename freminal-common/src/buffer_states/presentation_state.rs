@@ -0,0 +1,297 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The DECCIR and DECTABSR presentation state reports.
+//!
+//! DECRQPSR (`CSI Ps $ w`) asks for one of them, the terminal answers with
+//! `DCS Ps $ u … ST`, and DECRSPS (`DCS Ps $ t … ST`) hands the same text
+//! back to restore it.
+
+use crate::buffer_states::rectangle::RectAttributes;
+
+/// Which report DECRQPSR asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresentationStateReport {
+    /// Ps = 1: the cursor information report (DECCIR).
+    CursorInformation,
+    /// Ps = 2: the tab stop report (DECTABSR).
+    TabStops,
+}
+
+impl PresentationStateReport {
+    /// The `Ps` of the report, as used by DECRQPSR, the reply and DECRSPS.
+    #[must_use]
+    pub const fn ps(self) -> usize {
+        match self {
+            Self::CursorInformation => 1,
+            Self::TabStops => 2,
+        }
+    }
+
+    /// The report selected by `ps`, if any.
+    #[must_use]
+    pub const fn from_ps(ps: usize) -> Option<Self> {
+        match ps {
+            1 => Some(Self::CursorInformation),
+            2 => Some(Self::TabStops),
+            _ => None,
+        }
+    }
+}
+
+/// The contents of a DECCIR report.
+///
+/// Rows and columns are 1-based and absolute, whatever DECOM says.  A
+/// column one past the right edge never appears; a cursor waiting to wrap
+/// reports the last column with `wrap_pending` set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // One flag per DECCIR bit.
+pub struct CursorInformation {
+    pub row: usize,
+    pub col: usize,
+    pub page: usize,
+    /// Bold, underline, blink and inverse of the current rendition.
+    pub rendition: RectAttributes,
+    /// DECSCA protection of the characters written from now on.
+    pub protected: bool,
+    /// DECOM.
+    pub origin_mode: bool,
+    /// SS2 is waiting for the next character.
+    pub single_shift_2: bool,
+    /// SS3 is waiting for the next character.
+    pub single_shift_3: bool,
+    /// The last column has been written and the next character wraps.
+    pub wrap_pending: bool,
+    /// The G set invoked into GL (0–3).
+    pub gl: usize,
+    /// The G set invoked into GR (0–3).
+    pub gr: usize,
+    /// The final (and any intermediate) bytes designating G0–G3, with
+    /// whether each is a 96-character set.
+    pub charsets: [(String, bool); 4],
+}
+
+impl Default for CursorInformation {
+    fn default() -> Self {
+        Self {
+            row: 1,
+            col: 1,
+            page: 1,
+            rendition: RectAttributes::empty(),
+            protected: false,
+            origin_mode: false,
+            single_shift_2: false,
+            single_shift_3: false,
+            wrap_pending: false,
+            gl: 0,
+            gr: 2,
+            charsets: std::array::from_fn(|_| ("B".to_string(), false)),
+        }
+    }
+}
+
+/// A DECCIR flag byte: `0x40` plus the bits.
+fn flag_byte(bits: u8) -> char {
+    char::from(0x40 | bits)
+}
+
+/// The bits of a DECCIR flag byte, or `None` if it is not one.
+fn flag_bits(byte: u8) -> Option<u8> {
+    (0x40..=0x4f).contains(&byte).then_some(byte & 0x0f)
+}
+
+impl CursorInformation {
+    /// The `Pr ; Pc ; Pp ; Srend ; Satt ; Sflag ; Pgl ; Pgr ; Scss ; Sdesig`
+    /// body of the report.
+    #[must_use]
+    pub fn to_report(&self) -> String {
+        let mut rend = 0;
+        for (attr, bit) in [
+            (RectAttributes::BOLD, 1),
+            (RectAttributes::UNDERLINE, 2),
+            (RectAttributes::BLINK, 4),
+            (RectAttributes::INVERSE, 8),
+        ] {
+            if self.rendition.contains(attr) {
+                rend |= bit;
+            }
+        }
+        let flags = u8::from(self.origin_mode)
+            | u8::from(self.single_shift_2) << 1
+            | u8::from(self.single_shift_3) << 2
+            | u8::from(self.wrap_pending) << 3;
+        let mut css = 0;
+        let mut desig = String::new();
+        for (n, (designator, is_96)) in self.charsets.iter().enumerate() {
+            if *is_96 {
+                css |= 1 << n;
+            }
+            desig.push_str(designator);
+        }
+
+        format!(
+            "{};{};{};{};{};{};{};{};{};{}",
+            self.row,
+            self.col,
+            self.page,
+            flag_byte(rend),
+            flag_byte(u8::from(self.protected)),
+            flag_byte(flags),
+            self.gl,
+            self.gr,
+            flag_byte(css),
+            desig,
+        )
+    }
+
+    /// Parse a DECRSPS cursor information body (the text [`Self::to_report`]
+    /// produces).  Returns `None` if any field is malformed.
+    #[must_use]
+    pub fn parse(body: &str) -> Option<Self> {
+        let fields: Vec<&str> = body.split(';').collect();
+        let [row, col, page, rend, att, flags, gl, gr, css, desig] = fields.as_slice() else {
+            return None;
+        };
+        let flag = |field: &str| match field.as_bytes() {
+            [byte] => flag_bits(*byte),
+            _ => None,
+        };
+
+        let rend = flag(rend)?;
+        let mut rendition = RectAttributes::empty();
+        for (attr, bit) in [
+            (RectAttributes::BOLD, 1),
+            (RectAttributes::UNDERLINE, 2),
+            (RectAttributes::BLINK, 4),
+            (RectAttributes::INVERSE, 8),
+        ] {
+            if rend & bit != 0 {
+                rendition.insert(attr);
+            }
+        }
+        let flags = flag(flags)?;
+        let css = flag(css)?;
+
+        let designators = split_designators(desig)?;
+        let charsets = std::array::from_fn(|n| (designators[n].clone(), css & (1 << n) != 0));
+
+        Some(Self {
+            row: row.parse().ok()?,
+            col: col.parse().ok()?,
+            page: page.parse().ok()?,
+            rendition,
+            protected: flag(att)? & 1 != 0,
+            origin_mode: flags & 1 != 0,
+            single_shift_2: flags & 2 != 0,
+            single_shift_3: flags & 4 != 0,
+            wrap_pending: flags & 8 != 0,
+            gl: gl.parse().ok().filter(|&g| g < 4)?,
+            gr: gr.parse().ok().filter(|&g| g < 4)?,
+            charsets,
+        })
+    }
+}
+
+/// Split `Sdesig` into exactly four designators, each any intermediates
+/// (0x20–0x2F) followed by one final byte (0x30–0x7E).
+fn split_designators(desig: &str) -> Option<[String; 4]> {
+    let mut designators = Vec::with_capacity(4);
+    let mut current = String::new();
+    for ch in desig.chars() {
+        current.push(ch);
+        match ch {
+            ' '..='/' => {}
+            '0'..='~' => designators.push(std::mem::take(&mut current)),
+            _ => return None,
+        }
+    }
+    if !current.is_empty() {
+        return None;
+    }
+    designators.try_into().ok()
+}
+
+/// The DECTABSR body: the 1-based tab stop columns separated by `/`.
+#[must_use]
+pub fn tab_stops_report(columns: &[usize]) -> String {
+    columns
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parse a DECRSPS tab stop body into 1-based columns.  Returns `None` if a
+/// stop is not a positive number; an empty body means no stops.
+#[must_use]
+pub fn parse_tab_stops(body: &str) -> Option<Vec<usize>> {
+    if body.is_empty() {
+        return Some(Vec::new());
+    }
+    body.split('/')
+        .map(|stop| stop.parse().ok().filter(|&col| col > 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_report() {
+        assert_eq!(
+            CursorInformation::default().to_report(),
+            "1;1;1;@;@;@;0;2;@;BBBB"
+        );
+    }
+
+    #[test]
+    fn report_round_trips() {
+        let mut rendition = RectAttributes::BOLD;
+        rendition.insert(RectAttributes::INVERSE);
+        let info = CursorInformation {
+            row: 5,
+            col: 80,
+            rendition,
+            protected: true,
+            origin_mode: true,
+            wrap_pending: true,
+            charsets: [
+                ("0".to_string(), false),
+                ("B".to_string(), false),
+                ("A".to_string(), true),
+                ("%5".to_string(), false),
+            ],
+            ..CursorInformation::default()
+        };
+
+        let report = info.to_report();
+        assert_eq!(report, "5;80;1;I;A;I;0;2;D;0BA%5");
+        assert_eq!(CursorInformation::parse(&report), Some(info));
+    }
+
+    #[test]
+    fn malformed_reports_are_rejected() {
+        for body in [
+            "",
+            "1;1;1;@;@;@;0;2;@",
+            "1;1;1;x;@;@;0;2;@;BBBB",
+            "1;1;1;@;@;@;4;2;@;BBBB",
+            "1;1;1;@;@;@;0;2;@;BBB",
+            "1;1;1;@;@;@;0;2;@;BBB%",
+        ] {
+            assert_eq!(CursorInformation::parse(body), None, "{body:?}");
+        }
+    }
+
+    #[test]
+    fn tab_stops_round_trip() {
+        assert_eq!(tab_stops_report(&[9, 17, 25]), "9/17/25");
+        assert_eq!(parse_tab_stops("9/17/25"), Some(vec![9, 17, 25]));
+        assert_eq!(parse_tab_stops(""), Some(Vec::new()));
+        assert_eq!(parse_tab_stops("9/0"), None);
+        assert_eq!(parse_tab_stops("9//17"), None);
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Parameter types for the VT420 rectangular area operations (DECCRA,
+//! DECFRA, DECERA, DECSERA, DECCARA, DECRARA), DECSACE and XTCHECKSUM.
+
+use crate::buffer_states::{
+    cursor::ReverseVideo,
+    fonts::{BlinkState, FontWeight, UnderlineStyle},
+    format_tag::FormatTag,
+};
+
+/// A rectangular area as the application sent it: 1-based, inclusive.
+///
+/// `top`/`left` of 0 mean 1.  `bottom`/`right` of `usize::MAX` mean the last
+/// row / column; the buffer clips everything else to the screen (or to the
+/// margins when DECOM is set).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+impl Default for Rectangle {
+    /// The whole screen (`CSI $ z` with no parameters).
+    fn default() -> Self {
+        Self {
+            top: 1,
+            left: 1,
+            bottom: usize::MAX,
+            right: usize::MAX,
+        }
+    }
+}
+
+/// DECSACE — which cells DECCARA and DECRARA change (`CSI Ps * x`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AttributeChangeExtent {
+    /// Ps = 0 or 1: the character stream from the start position to the end
+    /// position, wrapping at the line ends (the power-on default).
+    #[default]
+    Stream,
+    /// Ps = 2: exactly the rectangle.
+    Rectangle,
+}
+
+/// The visual attributes DECCARA and DECRARA can change, as a bitfield.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct RectAttributes(u8);
+
+impl RectAttributes {
+    pub const BOLD: Self = Self(0b0001);
+    pub const UNDERLINE: Self = Self(0b0010);
+    pub const BLINK: Self = Self(0b0100);
+    pub const INVERSE: Self = Self(0b1000);
+    pub const ALL: Self = Self(0b1111);
+
+    /// No attributes.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if no attribute is set.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if every attribute in `other` is set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set every attribute in `other`.
+    pub const fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clear every attribute in `other`.
+    pub const fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// The attributes `tag` currently has.
+    #[must_use]
+    pub fn of(tag: &FormatTag) -> Self {
+        let mut attrs = Self::empty();
+        if tag.font_weight == FontWeight::Bold {
+            attrs.insert(Self::BOLD);
+        }
+        if tag.font_decorations.underline_style() != UnderlineStyle::None {
+            attrs.insert(Self::UNDERLINE);
+        }
+        if tag.blink != BlinkState::None {
+            attrs.insert(Self::BLINK);
+        }
+        if tag.colors.reverse_video == ReverseVideo::On {
+            attrs.insert(Self::INVERSE);
+        }
+        attrs
+    }
+
+    /// Turn these attributes on or off in `tag`.  Turning on an attribute
+    /// the tag already has keeps its current style (a curly underline stays
+    /// curly, a fast blink stays fast).
+    pub fn apply_to(self, tag: &mut FormatTag, on: bool) {
+        let current = Self::of(tag);
+        if self.contains(Self::BOLD) {
+            tag.font_weight = if on {
+                FontWeight::Bold
+            } else {
+                FontWeight::Normal
+            };
+        }
+        if self.contains(Self::UNDERLINE) && current.contains(Self::UNDERLINE) != on {
+            tag.font_decorations.set_underline_style(if on {
+                UnderlineStyle::Single
+            } else {
+                UnderlineStyle::None
+            });
+        }
+        if self.contains(Self::BLINK) && current.contains(Self::BLINK) != on {
+            tag.blink = if on {
+                BlinkState::Slow
+            } else {
+                BlinkState::None
+            };
+        }
+        if self.contains(Self::INVERSE) {
+            tag.colors.reverse_video = if on {
+                ReverseVideo::On
+            } else {
+                ReverseVideo::Off
+            };
+        }
+    }
+
+    /// The attribute an SGR parameter turns on (1, 4, 5, 7) or off (22, 24,
+    /// 25, 27), with `true` for on.  `0` turns off all of them.  Other
+    /// values are not rectangle attributes.
+    #[must_use]
+    pub const fn from_sgr(ps: usize) -> Option<(Self, bool)> {
+        match ps {
+            0 => Some((Self::ALL, false)),
+            1 => Some((Self::BOLD, true)),
+            4 => Some((Self::UNDERLINE, true)),
+            5 => Some((Self::BLINK, true)),
+            7 => Some((Self::INVERSE, true)),
+            22 => Some((Self::BOLD, false)),
+            24 => Some((Self::UNDERLINE, false)),
+            25 => Some((Self::BLINK, false)),
+            27 => Some((Self::INVERSE, false)),
+            _ => None,
+        }
+    }
+}
+
+/// DECCARA — attributes to turn on and off in an area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AttributeChange {
+    pub set: RectAttributes,
+    pub clear: RectAttributes,
+}
+
+impl AttributeChange {
+    /// Build from the DECCARA `Ps` list, applied left to right; unsupported
+    /// values are ignored as the VT420 does.  An empty list means `0`.
+    #[must_use]
+    pub fn from_sgr_params(params: &[usize]) -> Self {
+        let mut change = Self::default();
+        let params = if params.is_empty() { &[0][..] } else { params };
+        for &ps in params {
+            match RectAttributes::from_sgr(ps) {
+                Some((attrs, true)) => {
+                    change.set.insert(attrs);
+                    change.clear.remove(attrs);
+                }
+                Some((attrs, false)) => {
+                    change.clear.insert(attrs);
+                    change.set.remove(attrs);
+                }
+                None => {}
+            }
+        }
+        change
+    }
+}
+
+/// DECRARA — attributes to reverse in an area.
+///
+/// Only 0 (all), 1, 4, 5 and 7 are meaningful; other values are ignored.
+/// An empty list means `0`.
+#[must_use]
+pub fn reverse_attributes_from_sgr_params(params: &[usize]) -> RectAttributes {
+    let mut toggle = RectAttributes::empty();
+    let params = if params.is_empty() { &[0][..] } else { params };
+    for &ps in params {
+        match RectAttributes::from_sgr(ps) {
+            Some((_, false)) if ps == 0 => toggle.insert(RectAttributes::ALL),
+            Some((attrs, true)) => toggle.insert(attrs),
+            _ => {}
+        }
+    }
+    toggle
+}
+
+/// XTCHECKSUM — how DECRQCRA computes its checksum (`CSI Ps # y`).
+///
+/// `Ps` is a bitmask; the default of 0 gives the VT520 checksum.  Bits 0–4
+/// follow xterm, higher bits are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct ChecksumExtension(u8);
+
+impl ChecksumExtension {
+    /// Bit 0: report the sum itself instead of its negation.
+    pub const POSITIVE: Self = Self(1 << 0);
+    /// Bit 1: leave the bold / blink / inverse / underline bits out.
+    pub const NO_ATTRIBUTES: Self = Self(1 << 1);
+    /// Bit 2: count blank cells too.
+    pub const KEEP_BLANKS: Self = Self(1 << 2);
+    /// Bit 3: skip never-written cells, even with [`Self::KEEP_BLANKS`].
+    pub const SKIP_UNWRITTEN: Self = Self(1 << 3);
+    /// Bit 4: add whole code points and combining characters, not low bytes.
+    pub const FULL_CODE_POINTS: Self = Self(1 << 4);
+    const KNOWN: u8 = 0x1f;
+
+    /// Build from the XTCHECKSUM `Ps`.
+    #[must_use]
+    pub fn from_param(ps: usize) -> Self {
+        Self(u8::try_from(ps & usize::from(Self::KNOWN)).unwrap_or_default())
+    }
+
+    /// Returns `true` if every option in `other` is set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_applies_params_in_order() {
+        let change = AttributeChange::from_sgr_params(&[1, 7, 0, 4]);
+        assert_eq!(change.set, RectAttributes::UNDERLINE);
+        let mut cleared = RectAttributes::ALL;
+        cleared.remove(RectAttributes::UNDERLINE);
+        assert_eq!(change.clear, cleared);
+    }
+
+    #[test]
+    fn change_with_no_params_clears_everything() {
+        let change = AttributeChange::from_sgr_params(&[]);
+        assert!(change.set.is_empty());
+        assert_eq!(change.clear, RectAttributes::ALL);
+    }
+
+    #[test]
+    fn change_ignores_colors() {
+        let change = AttributeChange::from_sgr_params(&[31, 5]);
+        assert_eq!(change.set, RectAttributes::BLINK);
+        assert!(change.clear.is_empty());
+    }
+
+    #[test]
+    fn reverse_ignores_off_params() {
+        let toggle = reverse_attributes_from_sgr_params(&[1, 27, 7]);
+        let mut expected = RectAttributes::BOLD;
+        expected.insert(RectAttributes::INVERSE);
+        assert_eq!(toggle, expected);
+        assert_eq!(reverse_attributes_from_sgr_params(&[]), RectAttributes::ALL);
+    }
+
+    #[test]
+    fn checksum_extension_keeps_known_bits() {
+        let ext = ChecksumExtension::from_param(0x23);
+        assert!(ext.contains(ChecksumExtension::POSITIVE));
+        assert!(ext.contains(ChecksumExtension::NO_ATTRIBUTES));
+        assert!(!ext.contains(ChecksumExtension::KEEP_BLANKS));
+        assert_eq!(ext, ChecksumExtension::from_param(3));
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The attribute groups xterm's SGR stack (XTPUSHSGR / XTPOPSGR) saves.
+
+use crate::buffer_states::{fonts::FontDecorations, format_tag::FormatTag};
+
+/// Which parts of the rendition an XTPUSHSGR saves, as a bitfield.
+///
+/// XTPOPSGR restores only these; everything else keeps its current value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct SgrStackAttributes(u16);
+
+impl SgrStackAttributes {
+    pub const BOLD: Self = Self(1 << 0);
+    pub const FAINT: Self = Self(1 << 1);
+    pub const ITALIC: Self = Self(1 << 2);
+    /// Underline style and underline color (XTPUSHSGR 4 and 21).
+    pub const UNDERLINE: Self = Self(1 << 3);
+    pub const BLINK: Self = Self(1 << 4);
+    pub const INVERSE: Self = Self(1 << 5);
+    pub const STRIKETHROUGH: Self = Self(1 << 6);
+    pub const FOREGROUND: Self = Self(1 << 7);
+    pub const BACKGROUND: Self = Self(1 << 8);
+    pub const ALL: Self = Self(0x1ff);
+
+    /// No attributes.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns `true` if every attribute in `other` is set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set every attribute in `other`.
+    pub const fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Build from the XTPUSHSGR `Pm` list: 1 bold, 2 faint, 3 italic,
+    /// 4 or 21 underline, 5 blink, 7 inverse, 9 crossed-out, 30 foreground,
+    /// 31 background.  An empty list means everything.  8 (invisible) and
+    /// unknown values are ignored.
+    #[must_use]
+    pub fn from_params(params: &[usize]) -> Self {
+        if params.is_empty() {
+            return Self::ALL;
+        }
+
+        let mut attrs = Self::empty();
+        for &ps in params {
+            match ps {
+                1 => attrs.insert(Self::BOLD),
+                2 => attrs.insert(Self::FAINT),
+                3 => attrs.insert(Self::ITALIC),
+                4 | 21 => attrs.insert(Self::UNDERLINE),
+                5 => attrs.insert(Self::BLINK),
+                7 => attrs.insert(Self::INVERSE),
+                9 => attrs.insert(Self::STRIKETHROUGH),
+                30 => attrs.insert(Self::FOREGROUND),
+                31 => attrs.insert(Self::BACKGROUND),
+                _ => {}
+            }
+        }
+        attrs
+    }
+
+    /// Copy these attributes from `saved` into `tag`, leaving the rest of
+    /// `tag` alone.
+    pub fn restore(self, tag: &mut FormatTag, saved: &FormatTag) {
+        if self.contains(Self::BOLD) {
+            tag.font_weight = saved.font_weight;
+        }
+        for (attr, decoration) in [
+            (Self::FAINT, FontDecorations::Faint),
+            (Self::ITALIC, FontDecorations::Italic),
+            (Self::STRIKETHROUGH, FontDecorations::Strikethrough),
+        ] {
+            if self.contains(attr) {
+                if saved.font_decorations.contains(decoration) {
+                    tag.font_decorations.insert(decoration);
+                } else {
+                    tag.font_decorations.remove(decoration);
+                }
+            }
+        }
+        if self.contains(Self::UNDERLINE) {
+            tag.font_decorations
+                .set_underline_style(saved.font_decorations.underline_style());
+            tag.colors.underline_color = saved.colors.underline_color;
+        }
+        if self.contains(Self::BLINK) {
+            tag.blink = saved.blink;
+        }
+        if self.contains(Self::INVERSE) {
+            tag.colors.reverse_video = saved.colors.reverse_video;
+        }
+        if self.contains(Self::FOREGROUND) {
+            tag.colors.color = saved.colors.color;
+        }
+        if self.contains(Self::BACKGROUND) {
+            tag.colors.background_color = saved.colors.background_color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer_states::{
+            cursor::ReverseVideo,
+            fonts::{BlinkState, FontWeight, UnderlineStyle},
+        },
+        colors::TerminalColor,
+    };
+
+    fn styled() -> FormatTag {
+        let mut tag = FormatTag {
+            font_weight: FontWeight::Bold,
+            blink: BlinkState::Slow,
+            ..FormatTag::default()
+        };
+        tag.font_decorations.insert(FontDecorations::Italic);
+        tag.font_decorations
+            .set_underline_style(UnderlineStyle::Curly);
+        tag.colors.set_color(TerminalColor::Red);
+        tag.colors.set_background_color(TerminalColor::Blue);
+        tag.colors.set_reverse_video(ReverseVideo::On);
+        tag
+    }
+
+    #[test]
+    fn empty_params_select_everything() {
+        assert_eq!(
+            SgrStackAttributes::from_params(&[]),
+            SgrStackAttributes::ALL
+        );
+    }
+
+    #[test]
+    fn unknown_params_are_ignored() {
+        let attrs = SgrStackAttributes::from_params(&[8, 30, 99]);
+        assert_eq!(attrs, SgrStackAttributes::FOREGROUND);
+    }
+
+    #[test]
+    fn restore_all_copies_every_attribute() {
+        let saved = styled();
+        let mut tag = FormatTag::default();
+        SgrStackAttributes::ALL.restore(&mut tag, &saved);
+        assert_eq!(tag, saved);
+
+        let mut tag = styled();
+        SgrStackAttributes::ALL.restore(&mut tag, &FormatTag::default());
+        assert_eq!(tag, FormatTag::default());
+    }
+
+    #[test]
+    fn restore_leaves_unselected_attributes_alone() {
+        let saved = FormatTag::default();
+        let mut tag = styled();
+        SgrStackAttributes::from_params(&[1, 31]).restore(&mut tag, &saved);
+
+        assert_eq!(tag.font_weight, FontWeight::Normal);
+        assert_eq!(tag.colors.background_color, saved.colors.background_color);
+        assert_eq!(tag.colors.color, TerminalColor::Red);
+        assert_eq!(
+            tag.font_decorations.underline_style(),
+            UnderlineStyle::Curly
+        );
+        assert!(tag.font_decorations.contains(FontDecorations::Italic));
+        assert_eq!(tag.blink, BlinkState::Slow);
+    }
+}
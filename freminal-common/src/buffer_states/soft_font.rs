@@ -0,0 +1,404 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Downloadable soft character sets (DECDLD) and the glyphs they define.
+//!
+//! A soft set is named by its `Dscs`, taken here in the ISO 2022 DRCS form
+//! `SP F` (a bare final `F` in DECDLD names the same set), and designated
+//! with `ESC ( SP F` and friends.  Its characters are written to the buffer
+//! as private-use code points in plane 16, one block of 96 per final byte,
+//! which the renderer looks up in [`SoftFont`] to draw the bitmap.
+
+use std::collections::BTreeMap;
+
+/// The first code point of the soft character blocks.
+const SOFT_CHAR_BASE: u32 = 0x10_0000;
+/// Positions per soft set: 0x20 through 0x7F.
+const SET_POSITIONS: u32 = 96;
+/// The largest glyph matrix accepted, in either direction.
+const MAX_MATRIX: usize = 64;
+
+/// The code point that stands for `byte` of the soft set named `final_byte`.
+#[must_use]
+pub fn soft_char(final_byte: u8, byte: u8) -> Option<char> {
+    if !(0x30..=0x7e).contains(&final_byte) || !(0x20..=0x7f).contains(&byte) {
+        return None;
+    }
+    let block = u32::from(final_byte - 0x30) * SET_POSITIONS;
+    char::from_u32(SOFT_CHAR_BASE + block + u32::from(byte - 0x20))
+}
+
+/// The soft set final byte and position a code point from [`soft_char`]
+/// stands for.
+#[must_use]
+pub fn soft_position(c: char) -> Option<(u8, u8)> {
+    let offset = u32::from(c).checked_sub(SOFT_CHAR_BASE)?;
+    let final_byte = u8::try_from(0x30 + offset / SET_POSITIONS).ok()?;
+    let byte = u8::try_from(0x20 + offset % SET_POSITIONS).ok()?;
+    (final_byte <= 0x7e).then_some((final_byte, byte))
+}
+
+/// Whether `c` is in the soft character blocks.
+#[must_use]
+pub fn is_soft_char(c: char) -> bool {
+    soft_position(c).is_some()
+}
+
+/// One glyph: a `width` × `height` matrix of on/off pixels, row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoftGlyph {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl SoftGlyph {
+    /// A glyph with no pixels set.
+    #[must_use]
+    pub fn blank(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// The matrix width in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The matrix height in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the pixel at `(x, y)` is on.  Outside the matrix it is off.
+    #[must_use]
+    pub fn is_set(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = true;
+        }
+    }
+
+    /// Decode one glyph's sixel data: `?`–`~` paint six rows, `/` starts the
+    /// next band of six.  Anything past the matrix is dropped.
+    fn from_sixels(data: &[u8], width: usize, height: usize) -> Self {
+        let mut glyph = Self::blank(width, height);
+        let (mut x, mut band) = (0, 0);
+        for &b in data {
+            match b {
+                b'?'..=b'~' => {
+                    let bits = b - b'?';
+                    for bit in 0..6 {
+                        if bits & (1 << bit) != 0 {
+                            glyph.set(x, band * 6 + bit);
+                        }
+                    }
+                    x += 1;
+                }
+                b'/' => {
+                    x = 0;
+                    band += 1;
+                }
+                _ => {}
+            }
+        }
+        glyph
+    }
+}
+
+/// How much DECDLD erases before loading (`Pe`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoftFontErase {
+    /// 0 — every character of the set being loaded.
+    #[default]
+    Set,
+    /// 1 — only the characters being loaded.
+    Loaded,
+    /// 2 — every soft set.
+    All,
+}
+
+/// A parsed DECDLD: the glyphs to load into one soft set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoftFontLoad {
+    /// The final byte of the set's `Dscs`.
+    pub final_byte: u8,
+    /// The position of the first glyph, 0x20–0x7F.
+    pub first: u8,
+    /// Whether the set has 96 characters rather than 94.
+    pub is_96: bool,
+    /// What to erase first.
+    pub erase: SoftFontErase,
+    /// The glyphs, for consecutive positions from `first`.
+    pub glyphs: Vec<SoftGlyph>,
+}
+
+impl SoftFontLoad {
+    /// Parse the body of a DECDLD (`Pfn;Pcn;Pe;Pcmw;Pss;Pt;Pcmh;Pcss{Dscs…`,
+    /// without the `DCS` and `ST`).  Returns `None` if it is malformed.
+    #[must_use]
+    pub fn parse(body: &[u8]) -> Option<Self> {
+        let brace = body.iter().position(|&b| b == b'{')?;
+        let params = parse_params(&body[..brace])?;
+        let param = |idx: usize| params.get(idx).copied().unwrap_or(0);
+
+        let (width, height) = matrix_size(param(3), param(4), param(6))?;
+        let is_96 = match param(7) {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        let erase = match param(2) {
+            0 => SoftFontErase::Set,
+            1 => SoftFontErase::Loaded,
+            2 => SoftFontErase::All,
+            _ => return None,
+        };
+        // A 94-character set has no position 0x20; Pcn 0 means its first.
+        let first = u8::try_from(param(1).max(usize::from(!is_96)))
+            .ok()
+            .and_then(|pcn| pcn.checked_add(0x20))
+            .filter(|&first| first <= last_position(is_96))?;
+
+        // Dscs: an optional SP intermediate and a final byte.
+        let rest = &body[brace + 1..];
+        let (final_byte, data) = match rest {
+            [b' ', f @ 0x30..=0x7e, data @ ..] | [f @ 0x30..=0x7e, data @ ..] => (*f, data),
+            _ => return None,
+        };
+
+        let glyphs = data
+            .split(|&b| b == b';')
+            .map(|sixels| SoftGlyph::from_sixels(sixels, width, height))
+            .collect();
+
+        Some(Self {
+            final_byte,
+            first,
+            is_96,
+            erase,
+            glyphs,
+        })
+    }
+}
+
+/// The last position of a 94- or 96-character set.
+const fn last_position(is_96: bool) -> u8 {
+    if is_96 { 0x7f } else { 0x7e }
+}
+
+/// Split `Pn;Pn;…` into numbers, empty fields being 0.
+fn parse_params(params: &[u8]) -> Option<Vec<usize>> {
+    params
+        .split(|&b| b == b';')
+        .map(|field| {
+            if field.is_empty() {
+                return Some(0);
+            }
+            std::str::from_utf8(field).ok()?.parse().ok()
+        })
+        .collect()
+}
+
+/// The glyph matrix from `Pcmw`, `Pss` and `Pcmh`.
+///
+/// `Pcmw` 2–4 are the VT220's 5, 6 and 7 × 10 cells; larger values are the
+/// width itself.  A zero width or height takes the VT510 default for the
+/// font set size, so that the glyphs fill a cell of an 80- or 132-column,
+/// 24-, 36- or 48-line screen.
+fn matrix_size(
+    cell_width: usize,
+    font_set_size: usize,
+    cell_height: usize,
+) -> Option<(usize, usize)> {
+    let (wide, lines) = match font_set_size {
+        0 | 1 => (false, 24),
+        2 => (true, 24),
+        11 => (false, 36),
+        12 => (true, 36),
+        21 => (false, 48),
+        22 => (true, 48),
+        _ => return None,
+    };
+
+    let (width, vt220_height) = match cell_width {
+        0 | 1 => (if wide { 6 } else { 10 }, None),
+        2..=4 => (cell_width + 3, Some(10)),
+        _ => (cell_width, None),
+    };
+    let height = match cell_height {
+        0 => vt220_height.unwrap_or(match lines {
+            24 => 16,
+            36 => 10,
+            _ => 8,
+        }),
+        _ => cell_height,
+    };
+
+    (width <= MAX_MATRIX && height <= MAX_MATRIX).then_some((width, height))
+}
+
+/// The glyphs of one soft set, keyed by position.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct SoftCharset {
+    glyphs: BTreeMap<u8, SoftGlyph>,
+}
+
+/// Every loaded soft set.
+///
+/// `generation` changes with each load, so a renderer caching rasterised
+/// glyphs knows when to drop them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SoftFont {
+    sets: BTreeMap<u8, SoftCharset>,
+    generation: u64,
+}
+
+impl SoftFont {
+    /// Apply a DECDLD.
+    pub fn load(&mut self, load: SoftFontLoad) {
+        match load.erase {
+            SoftFontErase::All => self.sets.clear(),
+            SoftFontErase::Set => {
+                self.sets.remove(&load.final_byte);
+            }
+            SoftFontErase::Loaded => {}
+        }
+
+        let set = self.sets.entry(load.final_byte).or_default();
+        for (byte, glyph) in (load.first..=last_position(load.is_96)).zip(load.glyphs) {
+            set.glyphs.insert(byte, glyph);
+        }
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// The glyph `c` stands for, if it is a loaded soft character.
+    #[must_use]
+    pub fn glyph(&self, c: char) -> Option<&SoftGlyph> {
+        let (final_byte, byte) = soft_position(c)?;
+        self.sets.get(&final_byte)?.glyphs.get(&byte)
+    }
+
+    /// Whether no soft set is loaded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Changes with every load.
+    #[must_use]
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soft_chars_round_trip() {
+        for (final_byte, byte) in [(b'0', b' '), (b'@', b'A'), (b'~', 0x7f)] {
+            let c = soft_char(final_byte, byte).unwrap();
+            assert_eq!(soft_position(c), Some((final_byte, byte)));
+        }
+        assert_eq!(soft_char(b'@', b'!'), Some('\u{100601}'));
+        assert_eq!(soft_char(b'/', b'A'), None);
+        assert_eq!(soft_char(b'@', 0x1f), None);
+        assert!(!is_soft_char('A'));
+        assert!(!is_soft_char('\u{10FFFD}'));
+    }
+
+    #[test]
+    fn sixels_fill_bands_of_six_rows() {
+        // `~` is all six rows; `@` only the top; `/` moves down a band.
+        let glyph = SoftGlyph::from_sixels(b"~@/A", 3, 8);
+        assert!((0..6).all(|y| glyph.is_set(0, y)));
+        assert!(glyph.is_set(1, 0) && !glyph.is_set(1, 1));
+        assert!(glyph.is_set(0, 7) && !glyph.is_set(0, 6));
+        assert!(!glyph.is_set(2, 0));
+    }
+
+    #[test]
+    fn parse_reads_params_and_dscs() {
+        let load = SoftFontLoad::parse(b"1;1;1;6;0;2;8;0{ @~~~~~~;??").unwrap();
+        assert_eq!(load.final_byte, b'@');
+        assert_eq!(load.first, 0x21);
+        assert!(!load.is_96);
+        assert_eq!(load.erase, SoftFontErase::Loaded);
+        assert_eq!(load.glyphs.len(), 2);
+        assert_eq!((load.glyphs[0].width(), load.glyphs[0].height()), (6, 8));
+        assert!(load.glyphs[0].is_set(5, 5));
+        assert!(!load.glyphs[1].is_set(0, 0));
+    }
+
+    #[test]
+    fn parse_defaults() {
+        // Empty params: 94 set, Pcn 0 → 0x21, 10 × 16 for 80 × 24.
+        let load = SoftFontLoad::parse(b"{A").unwrap();
+        assert_eq!(load.first, 0x21);
+        assert_eq!((load.glyphs[0].width(), load.glyphs[0].height()), (10, 16));
+
+        let load = SoftFontLoad::parse(b"0;0;0;3;2;0;0;1{B").unwrap();
+        assert_eq!(load.first, 0x20);
+        assert!(load.is_96);
+        assert_eq!((load.glyphs[0].width(), load.glyphs[0].height()), (6, 10));
+
+        let load = SoftFontLoad::parse(b"0;0;0;0;12{B").unwrap();
+        assert_eq!((load.glyphs[0].width(), load.glyphs[0].height()), (6, 10));
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        assert_eq!(SoftFontLoad::parse(b"0;1;0;8"), None);
+        assert_eq!(SoftFontLoad::parse(b"0;1;3{@"), None);
+        assert_eq!(SoftFontLoad::parse(b"0;1;0;8;7{@"), None);
+        assert_eq!(SoftFontLoad::parse(b"0;1;0;99{@"), None);
+        assert_eq!(SoftFontLoad::parse(b"0;1{!!"), None);
+        assert_eq!(SoftFontLoad::parse(b"0;95{@"), None);
+    }
+
+    #[test]
+    fn load_places_glyphs_and_stops_at_the_end_of_the_set() {
+        let mut font = SoftFont::default();
+        font.load(SoftFontLoad::parse(b"0;93;0;5{@~;~;~").unwrap());
+        assert!(font.glyph(soft_char(b'@', 0x7d).unwrap()).is_some());
+        assert!(font.glyph(soft_char(b'@', 0x7e).unwrap()).is_some());
+        assert!(font.glyph(soft_char(b'@', 0x7f).unwrap()).is_none());
+        assert!(font.glyph(soft_char(b'A', 0x7d).unwrap()).is_none());
+    }
+
+    #[test]
+    fn erase_control() {
+        let mut font = SoftFont::default();
+        font.load(SoftFontLoad::parse(b"0;1;0;5{@~~").unwrap());
+        font.load(SoftFontLoad::parse(b"0;1;0;5{A~~").unwrap());
+        let generation = font.generation();
+
+        // Pe 1 keeps the rest of the set.
+        font.load(SoftFontLoad::parse(b"0;2;1;5{@~~").unwrap());
+        assert!(font.glyph(soft_char(b'@', b'!').unwrap()).is_some());
+        assert_ne!(font.generation(), generation);
+
+        // Pe 0 clears the set being loaded only.
+        font.load(SoftFontLoad::parse(b"0;2;0;5{@~~").unwrap());
+        assert!(font.glyph(soft_char(b'@', b'!').unwrap()).is_none());
+        assert!(font.glyph(soft_char(b'A', b'!').unwrap()).is_some());
+
+        // Pe 2 clears every set.
+        font.load(SoftFontLoad::parse(b"0;2;2;5{@~~").unwrap());
+        assert!(font.glyph(soft_char(b'A', b'!').unwrap()).is_none());
+        assert!(font.glyph(soft_char(b'@', b'"').unwrap()).is_some());
+    }
+}
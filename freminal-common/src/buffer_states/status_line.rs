@@ -0,0 +1,54 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The DEC status line: which one is shown (DECSSDT) and where output goes
+//! (DECSASD).
+
+/// The status line selected by DECSSDT (`CSI Ps $ ~`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusLineType {
+    /// Ps = 0: no status line; the main display uses every row.
+    #[default]
+    None,
+    /// Ps = 1: the terminal's own indicator line.
+    Indicator,
+    /// Ps = 2: a line the host writes through DECSASD.
+    HostWritable,
+}
+
+impl StatusLineType {
+    /// The type selected by `ps`, if any.
+    #[must_use]
+    pub const fn from_param(ps: usize) -> Option<Self> {
+        match ps {
+            0 => Some(Self::None),
+            1 => Some(Self::Indicator),
+            2 => Some(Self::HostWritable),
+            _ => None,
+        }
+    }
+}
+
+/// The display DECSASD (`CSI Ps $ }`) sends output to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusDisplay {
+    /// Ps = 0: the main display.
+    #[default]
+    Main,
+    /// Ps = 1: the host-writable status line.
+    StatusLine,
+}
+
+impl StatusDisplay {
+    /// The display selected by `ps`, if any.
+    #[must_use]
+    pub const fn from_param(ps: usize) -> Option<Self> {
+        match ps {
+            0 => Some(Self::Main),
+            1 => Some(Self::StatusLine),
+            _ => None,
+        }
+    }
+}
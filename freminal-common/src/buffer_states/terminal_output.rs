@@ -5,7 +5,17 @@
 
 use crate::{
     buffer_states::{
-        line_draw::DecSpecialGraphics, mode::Mode, osc::AnsiOscType,
+        charset::{Charset, GSet},
+        line_draw::DecSpecialGraphics,
+        locator::{LocatorFilter, LocatorReporting, LocatorUnits},
+        mode::Mode,
+        osc::AnsiOscType,
+        presentation_state::PresentationStateReport,
+        rectangle::{
+            AttributeChange, AttributeChangeExtent, ChecksumExtension, RectAttributes, Rectangle,
+        },
+        sgr_stack::SgrStackAttributes,
+        status_line::{StatusDisplay, StatusLineType},
         window_manipulation::WindowManipulation,
     },
     cursor::CursorVisualStyle,
@@ -94,6 +104,10 @@ pub enum TerminalOutput {
     Mode(Mode),
     // ich (8.3.64 of ecma-48)
     InsertSpaces(usize),
+    /// DECIC — insert Pn blank columns at the cursor column.
+    InsertColumns(usize),
+    /// DECDC — delete Pn columns at the cursor column.
+    DeleteColumns(usize),
     OscResponse(AnsiOscType),
     CursorReport,
     /// DSR ?996 — Color theme query.
@@ -102,6 +116,8 @@ pub enum TerminalOutput {
     DeviceStatusReport,
     Invalid,
     Skipped,
+    /// VT52 `ESC F` / `ESC G` — enter or leave graphics mode, i.e. designate
+    /// DEC Special Graphics or ASCII into G0.
     DecSpecialGraphics(DecSpecialGraphics),
     CursorVisualStyle(CursorVisualStyle),
     WindowManipulation(WindowManipulation),
@@ -114,6 +130,94 @@ pub enum TerminalOutput {
         top_margin: usize,
         bottom_margin: usize,
     },
+    /// DECCRA — copy `source` so its top-left corner lands at
+    /// (`dest_top`, `dest_left`), 1-based.
+    CopyRectangle {
+        source: Rectangle,
+        dest_top: usize,
+        dest_left: usize,
+    },
+    /// DECFRA — fill `area` with `ch` using the current SGR attributes.
+    FillRectangle {
+        ch: char,
+        area: Rectangle,
+    },
+    /// DECERA — erase `area` to blanks with default attributes.
+    EraseRectangle(Rectangle),
+    /// DECSERA — erase the characters in `area`, keeping their attributes.
+    SelectiveEraseRectangle(Rectangle),
+    /// DECCARA — turn attributes on and off in `area`.
+    ChangeRectangleAttributes {
+        area: Rectangle,
+        change: AttributeChange,
+    },
+    /// DECRARA — reverse attributes in `area`.
+    ReverseRectangleAttributes {
+        area: Rectangle,
+        toggle: RectAttributes,
+    },
+    /// DECSACE — select how DECCARA and DECRARA walk their area.
+    SelectAttributeChangeExtent(AttributeChangeExtent),
+    /// DECRQCRA — report the checksum of `area`, tagged with the request `id`.
+    RequestRectangleChecksum {
+        id: usize,
+        area: Rectangle,
+    },
+    /// XTCHECKSUM — select how DECRQCRA computes its checksum.
+    SelectChecksumExtension(ChecksumExtension),
+    /// DECRQPSR — report the cursor information (DECCIR) or the tab stops
+    /// (DECTABSR).
+    RequestPresentationState(PresentationStateReport),
+    /// DECSSDT — select no status line, the indicator line or a
+    /// host-writable one.
+    SelectStatusLineType(StatusLineType),
+    /// DECSASD — send output to the main display or the status line.
+    SelectActiveStatusDisplay(StatusDisplay),
+    /// DECELR — turn DEC locator reporting on, off or on for one report.
+    EnableLocatorReporting {
+        reporting: LocatorReporting,
+        units: LocatorUnits,
+    },
+    /// DECSLE — the raw selections, applied in order.
+    SelectLocatorEvents(Vec<usize>),
+    /// DECEFR — report once the pointer leaves this rectangle.
+    SetLocatorFilter(LocatorFilter),
+    /// DECRQLP — report the locator position.
+    RequestLocatorPosition,
+    /// DECSCA — protect (`true`) or stop protecting the characters written
+    /// from now on against DECSED, DECSEL and DECSERA.
+    SetCharacterProtection(bool),
+    /// DECSED 0 — erase unprotected characters from the cursor to the end
+    /// of the display.
+    SelectiveClearDisplayForwards,
+    /// DECSED 1 — erase unprotected characters from the start of the display
+    /// to the cursor.
+    SelectiveClearDisplayBackwards,
+    /// DECSED 2 — erase unprotected characters on the whole display.
+    SelectiveClearDisplay,
+    /// DECSEL 0 — erase unprotected characters from the cursor to the end of
+    /// the line.
+    SelectiveClearLineForwards,
+    /// DECSEL 1 — erase unprotected characters from the start of the line to
+    /// the cursor.
+    SelectiveClearLineBackwards,
+    /// DECSEL 2 — erase unprotected characters on the whole line.
+    SelectiveClearLine,
+    /// XTPUSHSGR — save the current rendition; XTPOPSGR restores the
+    /// selected attributes.
+    PushSgr(SgrStackAttributes),
+    /// XTPOPSGR — restore the rendition saved by the last XTPUSHSGR.
+    PopSgr,
+    /// XTREPORTSGR — report the rendition shared by every cell in the area.
+    ReportSgr(Rectangle),
+    /// XTPUSHCOLORS — save the palette and the dynamic colors, onto the
+    /// stack (`None`) or into the numbered slot.
+    PushColors(Option<usize>),
+    /// XTPOPCOLORS — restore the colors saved by XTPUSHCOLORS, popping the
+    /// stack (`None`) or reading the numbered slot.
+    PopColors(Option<usize>),
+    /// XTREPORTCOLORS — report the palette stack depth.
+    ReportColors,
     EightBitControl,
     SevenBitControl,
     AnsiConformanceLevelOne,
@@ -126,29 +230,25 @@ pub enum TerminalOutput {
     ScreenAlignmentTest,
     CharsetDefault,
     CharsetUTF8,
-    CharsetG0,
-    CharsetG1,
+    /// SCS — designate `charset` into G0–G3 (`ESC ( F` through `ESC / F`).
+    DesignateCharset {
+        set: GSet,
+        charset: Charset,
+    },
+    /// SI — invoke G0 into GL.
+    CharsetG0AsGL,
+    /// SO — invoke G1 into GL.
+    CharsetG1AsGL,
     CharsetG1AsGR,
-    CharsetG2,
     CharsetG2AsGR,
     CharsetG2AsGL,
-    CharsetG3,
     CharsetG3AsGR,
     CharsetG3AsGL,
+    /// SS2 — take the next character from G2.
+    SingleShiftG2,
+    /// SS3 — take the next character from G3.
+    SingleShiftG3,
     DecSpecial,
-    CharsetUK,
-    CharsetUS,
-    CharsetUSASCII,
-    CharsetDutch,
-    CharsetFinnish,
-    CharsetFrench,
-    CharsetFrenchCanadian,
-    CharsetGerman,
-    CharsetItalian,
-    CharsetNorwegianDanish,
-    CharsetSpanish,
-    CharsetSwedish,
-    CharsetSwiss,
     SaveCursor,
     RestoreCursor,
     CursorToLowerLeftCorner,
@@ -157,6 +257,14 @@ pub enum TerminalOutput {
     MemoryUnlock,
     DeviceControlString(Vec<u8>),
     ApplicationProgramCommand(Vec<u8>),
+    /// `DCS 1000 p` — tmux control mode (`tmux -CC`) has started. Until the
+    /// closing ST the stream is line-oriented notifications, not terminal
+    /// output.
+    TmuxControlModeStart,
+    /// One line of the tmux control-mode stream, without its line ending.
+    TmuxControlLine(Vec<u8>),
+    /// ST closing a `DCS 1000 p` control-mode stream.
+    TmuxControlModeEnd,
     RequestDeviceNameAndVersion,
     RequestSecondaryDeviceAttributes {
         param: usize,
@@ -252,6 +360,8 @@ impl std::fmt::Display for TerminalOutput {
             }
             Self::Mode(mode) => write!(f, "SetMode({mode})"),
             Self::InsertSpaces(n) => write!(f, "InsertSpaces({n})"),
+            Self::InsertColumns(n) => write!(f, "InsertColumns({n})"),
+            Self::DeleteColumns(n) => write!(f, "DeleteColumns({n})"),
             Self::OscResponse(n) => write!(f, "OscResponse({n})"),
             Self::DecSpecialGraphics(dec_special_graphics) => {
                 write!(f, "DecSpecialGraphics({dec_special_graphics:?})")
@@ -281,6 +391,57 @@ impl std::fmt::Display for TerminalOutput {
             } => {
                 write!(f, "SetTopAndBottomMargins({top_margin}, {bottom_margin})")
             }
+            Self::CopyRectangle {
+                source,
+                dest_top,
+                dest_left,
+            } => write!(f, "CopyRectangle({source:?}, {dest_top}, {dest_left})"),
+            Self::FillRectangle { ch, area } => write!(f, "FillRectangle({ch:?}, {area:?})"),
+            Self::EraseRectangle(area) => write!(f, "EraseRectangle({area:?})"),
+            Self::SelectiveEraseRectangle(area) => {
+                write!(f, "SelectiveEraseRectangle({area:?})")
+            }
+            Self::ChangeRectangleAttributes { area, change } => {
+                write!(f, "ChangeRectangleAttributes({area:?}, {change:?})")
+            }
+            Self::ReverseRectangleAttributes { area, toggle } => {
+                write!(f, "ReverseRectangleAttributes({area:?}, {toggle:?})")
+            }
+            Self::SelectAttributeChangeExtent(extent) => {
+                write!(f, "SelectAttributeChangeExtent({extent:?})")
+            }
+            Self::RequestRectangleChecksum { id, area } => {
+                write!(f, "RequestRectangleChecksum({id}, {area:?})")
+            }
+            Self::SelectChecksumExtension(ext) => write!(f, "SelectChecksumExtension({ext:?})"),
+            Self::RequestPresentationState(report) => {
+                write!(f, "RequestPresentationState({report:?})")
+            }
+            Self::SelectStatusLineType(kind) => write!(f, "SelectStatusLineType({kind:?})"),
+            Self::SelectActiveStatusDisplay(display) => {
+                write!(f, "SelectActiveStatusDisplay({display:?})")
+            }
+            Self::EnableLocatorReporting { reporting, units } => {
+                write!(f, "EnableLocatorReporting({reporting:?}, {units:?})")
+            }
+            Self::SelectLocatorEvents(events) => write!(f, "SelectLocatorEvents({events:?})"),
+            Self::SetLocatorFilter(filter) => write!(f, "SetLocatorFilter({filter:?})"),
+            Self::RequestLocatorPosition => write!(f, "RequestLocatorPosition"),
+            Self::SetCharacterProtection(protected) => {
+                write!(f, "SetCharacterProtection({protected})")
+            }
+            Self::SelectiveClearDisplayForwards => write!(f, "SelectiveClearForwards"),
+            Self::SelectiveClearDisplayBackwards => write!(f, "SelectiveClearBackwards"),
+            Self::SelectiveClearDisplay => write!(f, "SelectiveClearDisplay"),
+            Self::SelectiveClearLineForwards => write!(f, "SelectiveClearLineForwards"),
+            Self::SelectiveClearLineBackwards => write!(f, "SelectiveClearLineBackwards"),
+            Self::SelectiveClearLine => write!(f, "SelectiveClearLine"),
+            Self::PushSgr(attrs) => write!(f, "PushSgr({attrs:?})"),
+            Self::PopSgr => write!(f, "PopSgr"),
+            Self::ReportSgr(area) => write!(f, "ReportSgr({area:?})"),
+            Self::PushColors(slot) => write!(f, "PushColors({slot:?})"),
+            Self::PopColors(slot) => write!(f, "PopColors({slot:?})"),
+            Self::ReportColors => write!(f, "ReportColors"),
             Self::RequestDeviceAttributes => write!(f, "RequestDeviceAttributes"),
             Self::EightBitControl => write!(f, "EightBitControl"),
             Self::SevenBitControl => write!(f, "SevenBitControl"),
@@ -294,29 +455,19 @@ impl std::fmt::Display for TerminalOutput {
             Self::ScreenAlignmentTest => write!(f, "ScreenAlignmentTest"),
             Self::CharsetDefault => write!(f, "CharsetDefault"),
             Self::CharsetUTF8 => write!(f, "CharsetUTF8"),
-            Self::CharsetG0 => write!(f, "CharsetG0"),
-            Self::CharsetG1 => write!(f, "CharsetG1"),
+            Self::DesignateCharset { set, charset } => {
+                write!(f, "DesignateCharset({set:?}, {charset:?})")
+            }
+            Self::CharsetG0AsGL => write!(f, "CharsetG0AsGL"),
+            Self::CharsetG1AsGL => write!(f, "CharsetG1AsGL"),
             Self::CharsetG1AsGR => write!(f, "CharsetG1AsGR"),
-            Self::CharsetG2 => write!(f, "CharsetG2"),
             Self::CharsetG2AsGR => write!(f, "CharsetG2AsGR"),
             Self::CharsetG2AsGL => write!(f, "CharsetG2AsGL"),
-            Self::CharsetG3 => write!(f, "CharsetG3"),
             Self::CharsetG3AsGR => write!(f, "CharsetG3AsGR"),
             Self::CharsetG3AsGL => write!(f, "CharsetG3AsGL"),
+            Self::SingleShiftG2 => write!(f, "SingleShiftG2"),
+            Self::SingleShiftG3 => write!(f, "SingleShiftG3"),
             Self::DecSpecial => write!(f, "DecSpecial"),
-            Self::CharsetUK => write!(f, "CharsetUK"),
-            Self::CharsetUS => write!(f, "CharsetUS"),
-            Self::CharsetUSASCII => write!(f, "CharsetUSASCII"),
-            Self::CharsetDutch => write!(f, "CharsetDutch"),
-            Self::CharsetFinnish => write!(f, "CharsetFinnish"),
-            Self::CharsetFrench => write!(f, "CharsetFrench"),
-            Self::CharsetFrenchCanadian => write!(f, "CharsetFrenchCanadian"),
-            Self::CharsetGerman => write!(f, "CharsetGerman"),
-            Self::CharsetItalian => write!(f, "CharsetItalian"),
-            Self::CharsetNorwegianDanish => write!(f, "CharsetNorwegianDanish"),
-            Self::CharsetSpanish => write!(f, "CharsetSpanish"),
-            Self::CharsetSwedish => write!(f, "CharsetSwedish"),
-            Self::CharsetSwiss => write!(f, "CharsetSwiss"),
             Self::SaveCursor => write!(f, "SaveCursor"),
             Self::RestoreCursor => write!(f, "RestoreCursor"),
             Self::CursorToLowerLeftCorner => write!(f, "CursorToLowerLeftCorner"),
@@ -333,6 +484,11 @@ impl std::fmt::Display for TerminalOutput {
                     String::from_utf8_lossy(data)
                 )
             }
+            Self::TmuxControlModeStart => write!(f, "TmuxControlModeStart"),
+            Self::TmuxControlLine(line) => {
+                write!(f, "TmuxControlLine({})", String::from_utf8_lossy(line))
+            }
+            Self::TmuxControlModeEnd => write!(f, "TmuxControlModeEnd"),
             Self::RequestDeviceNameAndVersion => write!(f, "RequestDeviceNameAndVersion"),
             Self::RequestSecondaryDeviceAttributes { param } => {
                 write!(f, "RequestSecondaryDeviceAttributes({param})")
@@ -368,35 +524,24 @@ mod tests {
     fn display_charset_variants() {
         assert_eq!(TerminalOutput::CharsetDefault.to_string(), "CharsetDefault");
         assert_eq!(TerminalOutput::CharsetUTF8.to_string(), "CharsetUTF8");
-        assert_eq!(TerminalOutput::CharsetG0.to_string(), "CharsetG0");
-        assert_eq!(TerminalOutput::CharsetG1.to_string(), "CharsetG1");
+        assert_eq!(
+            TerminalOutput::DesignateCharset {
+                set: GSet::G1,
+                charset: Charset::German
+            }
+            .to_string(),
+            "DesignateCharset(G1, German)"
+        );
+        assert_eq!(TerminalOutput::CharsetG0AsGL.to_string(), "CharsetG0AsGL");
+        assert_eq!(TerminalOutput::CharsetG1AsGL.to_string(), "CharsetG1AsGL");
         assert_eq!(TerminalOutput::CharsetG1AsGR.to_string(), "CharsetG1AsGR");
-        assert_eq!(TerminalOutput::CharsetG2.to_string(), "CharsetG2");
         assert_eq!(TerminalOutput::CharsetG2AsGR.to_string(), "CharsetG2AsGR");
         assert_eq!(TerminalOutput::CharsetG2AsGL.to_string(), "CharsetG2AsGL");
-        assert_eq!(TerminalOutput::CharsetG3.to_string(), "CharsetG3");
         assert_eq!(TerminalOutput::CharsetG3AsGR.to_string(), "CharsetG3AsGR");
         assert_eq!(TerminalOutput::CharsetG3AsGL.to_string(), "CharsetG3AsGL");
+        assert_eq!(TerminalOutput::SingleShiftG2.to_string(), "SingleShiftG2");
+        assert_eq!(TerminalOutput::SingleShiftG3.to_string(), "SingleShiftG3");
         assert_eq!(TerminalOutput::DecSpecial.to_string(), "DecSpecial");
-        assert_eq!(TerminalOutput::CharsetUK.to_string(), "CharsetUK");
-        assert_eq!(TerminalOutput::CharsetUS.to_string(), "CharsetUS");
-        assert_eq!(TerminalOutput::CharsetUSASCII.to_string(), "CharsetUSASCII");
-        assert_eq!(TerminalOutput::CharsetDutch.to_string(), "CharsetDutch");
-        assert_eq!(TerminalOutput::CharsetFinnish.to_string(), "CharsetFinnish");
-        assert_eq!(TerminalOutput::CharsetFrench.to_string(), "CharsetFrench");
-        assert_eq!(
-            TerminalOutput::CharsetFrenchCanadian.to_string(),
-            "CharsetFrenchCanadian"
-        );
-        assert_eq!(TerminalOutput::CharsetGerman.to_string(), "CharsetGerman");
-        assert_eq!(TerminalOutput::CharsetItalian.to_string(), "CharsetItalian");
-        assert_eq!(
-            TerminalOutput::CharsetNorwegianDanish.to_string(),
-            "CharsetNorwegianDanish"
-        );
-        assert_eq!(TerminalOutput::CharsetSpanish.to_string(), "CharsetSpanish");
-        assert_eq!(TerminalOutput::CharsetSwedish.to_string(), "CharsetSwedish");
-        assert_eq!(TerminalOutput::CharsetSwiss.to_string(), "CharsetSwiss");
     }
 
     #[test]
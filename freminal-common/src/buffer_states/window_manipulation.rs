@@ -167,6 +167,9 @@ pub enum WindowManipulation {
     /// Forwarded to the GUI so it can trigger a visual bell indicator
     /// and/or mark the originating tab as having an unacknowledged bell.
     Bell,
+    /// `iTerm2` `OSC 1337 ; RequestAttention=` — ask the OS to draw the
+    /// user's attention to the window, or withdraw an earlier request.
+    RequestAttention(crate::buffer_states::osc_iterm2::AttentionRequest),
     /// Desktop/in-app notification request (OSC 9 / OSC 777, Task 76).
     ///
     /// Forwarded to the GUI so it can route the notification to an in-app
@@ -81,6 +81,95 @@ impl ColorPalette {
     }
 }
 
+/// An xterm special color, set with `OSC 5 ; c ; spec` and reset with
+/// `OSC 105 ; c`.  Text with the attribute and the default foreground is
+/// drawn in the special color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecialColor {
+    /// `c = 0` — bold text.
+    Bold,
+    /// `c = 1` — underlined text.
+    Underline,
+    /// `c = 2` — blinking text.
+    Blink,
+    /// `c = 3` — reverse-video text.
+    Reverse,
+}
+
+impl SpecialColor {
+    /// The special colors in `OSC 5` index order.
+    pub const ALL: [Self; 4] = [Self::Bold, Self::Underline, Self::Blink, Self::Reverse];
+
+    /// The special color for an `OSC 5` index, if freminal supports it.
+    #[must_use]
+    pub const fn from_index(index: u16) -> Option<Self> {
+        match index {
+            0 => Some(Self::Bold),
+            1 => Some(Self::Underline),
+            2 => Some(Self::Blink),
+            3 => Some(Self::Reverse),
+            _ => None,
+        }
+    }
+
+    /// The `OSC 5` index of this special color.
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        match self {
+            Self::Bold => 0,
+            Self::Underline => 1,
+            Self::Blink => 2,
+            Self::Reverse => 3,
+        }
+    }
+}
+
+/// The `OSC 5` special-color overrides.
+///
+/// `None` means the special color is unset: text with the attribute keeps
+/// the default foreground.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpecialColors {
+    overrides: [Option<(u8, u8, u8)>; 4],
+}
+
+impl SpecialColors {
+    /// No special colors set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            overrides: [None; 4],
+        }
+    }
+
+    /// Set a special color.
+    pub const fn set(&mut self, color: SpecialColor, rgb: (u8, u8, u8)) {
+        self.overrides[color.index() as usize] = Some(rgb);
+    }
+
+    /// Reset a special color.
+    pub const fn reset(&mut self, color: SpecialColor) {
+        self.overrides[color.index() as usize] = None;
+    }
+
+    /// Reset all special colors.
+    pub const fn reset_all(&mut self) {
+        self.overrides = [None; 4];
+    }
+
+    /// The special color, if set.
+    #[must_use]
+    pub const fn get(&self, color: SpecialColor) -> Option<(u8, u8, u8)> {
+        self.overrides[color.index() as usize]
+    }
+
+    /// Whether no special color is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.overrides.iter().all(Option::is_none)
+    }
+}
+
 /// Look up the default (un-overridden) color for a 256-color index.
 ///
 /// This is the original stateless lookup. Prefer `ColorPalette::lookup()`
@@ -378,4 +467,31 @@ mod tests {
         // 0xABCD >> 8 = 0xAB = 171
         assert_eq!(scale_hex_channel("ABCD"), Some(0xAB));
     }
+
+    #[test]
+    fn special_colors_set_get_reset() {
+        let mut special = SpecialColors::default();
+        assert!(special.is_empty());
+        special.set(SpecialColor::Bold, (1, 2, 3));
+        special.set(SpecialColor::Reverse, (4, 5, 6));
+        assert_eq!(special.get(SpecialColor::Bold), Some((1, 2, 3)));
+        assert_eq!(special.get(SpecialColor::Underline), None);
+
+        special.reset(SpecialColor::Bold);
+        assert_eq!(special.get(SpecialColor::Bold), None);
+        assert!(!special.is_empty());
+        special.reset_all();
+        assert!(special.is_empty());
+    }
+
+    #[test]
+    fn special_color_indices_round_trip() {
+        for color in SpecialColor::ALL {
+            assert_eq!(
+                SpecialColor::from_index(u16::from(color.index())),
+                Some(color)
+            );
+        }
+        assert_eq!(SpecialColor::from_index(4), None);
+    }
 }
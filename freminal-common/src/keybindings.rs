@@ -796,6 +796,12 @@ pub enum KeyAction {
     /// writer thread. The default recording path is
     /// `~/.config/freminal/recordings/YYYY-MM-DD_HHMMSS.frec`.
     ToggleRecording,
+    /// Write the flight recorder's in-memory ring to a `.frec` file.
+    ///
+    /// Only meaningful when `recording.flight_recorder` is enabled; the
+    /// dump lands in the recordings directory as
+    /// `freminal-flight-YYYY-MM-DD_HHMMSS.frec`. Unbound by default.
+    DumpFlightRecorder,
     /// Open the Quick Command History palette for the focused pane.
     ///
     /// The palette presents a fuzzy-searchable view over (a) the user's
@@ -917,6 +923,7 @@ impl KeyAction {
             Self::CopyLastCommandOutput => "copy_last_command_output",
             Self::CopyCommandOutputAtCursor => "copy_command_output_at_cursor",
             Self::ToggleRecording => "toggle_recording",
+            Self::DumpFlightRecorder => "dump_flight_recorder",
             Self::ShowCommandHistory => "show_command_history",
             Self::SplitVertical => "split_vertical",
             Self::SplitHorizontal => "split_horizontal",
@@ -990,6 +997,7 @@ impl KeyAction {
             Self::CopyLastCommandOutput => "Copy Last Command Output",
             Self::CopyCommandOutputAtCursor => "Copy Command Output at Cursor",
             Self::ToggleRecording => "Toggle Recording",
+            Self::DumpFlightRecorder => "Dump Flight Recorder",
             Self::ShowCommandHistory => "Show Command History",
             Self::SplitVertical => "Split Vertical",
             Self::SplitHorizontal => "Split Horizontal",
@@ -1060,6 +1068,7 @@ impl KeyAction {
         Self::CopyLastCommandOutput,
         Self::CopyCommandOutputAtCursor,
         Self::ToggleRecording,
+        Self::DumpFlightRecorder,
         Self::ShowCommandHistory,
         Self::SplitVertical,
         Self::SplitHorizontal,
@@ -1142,6 +1151,7 @@ impl FromStr for KeyAction {
             "copy_last_command_output" => Ok(Self::CopyLastCommandOutput),
             "copy_command_output_at_cursor" => Ok(Self::CopyCommandOutputAtCursor),
             "toggle_recording" => Ok(Self::ToggleRecording),
+            "dump_flight_recorder" => Ok(Self::DumpFlightRecorder),
             "show_command_history" => Ok(Self::ShowCommandHistory),
             "split_vertical" => Ok(Self::SplitVertical),
             "split_horizontal" => Ok(Self::SplitHorizontal),
@@ -1877,7 +1887,7 @@ mod tests {
         // roundtrip test above covers ALL, and name() is exhaustive.
         assert_eq!(
             KeyAction::ALL.len(),
-            62,
+            63,
             "KeyAction::ALL should contain all variants"
         );
     }
@@ -2305,6 +2315,7 @@ mod tests {
             KeyAction::FoldAll,
             KeyAction::CopyCommandOutputAtCursor,
             KeyAction::ForceClose,
+            KeyAction::DumpFlightRecorder,
         ];
         for action in unbound {
             assert!(
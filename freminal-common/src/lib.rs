@@ -80,6 +80,8 @@ pub mod layout;
 pub mod logging;
 /// PTY write command types shared between the emulator and the OS PTY writer.
 pub mod pty_write;
+/// Remote-control socket protocol shared by the GUI server and `freminal msg`.
+pub mod remote_control;
 /// SGR (Select Graphic Rendition) parameter types.
 pub mod sgr;
 /// Terminal window size type.
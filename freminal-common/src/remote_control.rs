@@ -0,0 +1,679 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Remote-control protocol shared by the GUI's per-instance socket listener
+//! and the `freminal msg` client.
+//!
+//! Every running Freminal instance with `[security] allow_remote_control`
+//! enabled listens on a Unix-domain socket.  Scripts talk to it the way they
+//! talk to `kitty @` or `wezterm cli`: list windows/tabs/panes, send text,
+//! read a pane's screen or scrollback, split, focus, retitle, load layouts,
+//! and run any [`KeyAction`](crate::keybindings::KeyAction) by name.
+//!
+//! ## Wire format
+//!
+//! Newline-delimited JSON.  The client writes one [`RemoteRequest`] per line
+//! and the server answers each with exactly one [`RemoteResponse`] line, in
+//! order.  A connection may carry any number of requests.  Every message
+//! carries the protocol `version`; a server rejects requests whose version it
+//! does not speak rather than guessing at their meaning.
+//!
+//! ```text
+//! > {"version":1,"command":"send-text","pane":3,"text":"ls\r"}
+//! < {"status":"ok","version":1}
+//! > {"version":1,"command":"get-text","pane":3,"extent":"screen"}
+//! < {"status":"ok","version":1,"result":{"text":"..."}}
+//! ```
+//!
+//! ## Socket location
+//!
+//! | Platform  | Path                                                  |
+//! |-----------|-------------------------------------------------------|
+//! | Linux/BSD | `$XDG_RUNTIME_DIR/freminal/freminal-<pid>.sock`       |
+//! | fallback  | `$TMPDIR/freminal-<user>/freminal-<pid>.sock`         |
+//!
+//! The directory is created with mode `0700` so only the owning user can
+//! connect.  Setting [`LISTEN_ON_ENV`] in the client's environment selects an
+//! instance explicitly; otherwise the client uses the only live socket it
+//! finds.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Protocol version spoken by this build.  Bumped on any incompatible change
+/// to [`RemoteRequest`] or [`RemoteResponse`].
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Environment variable naming the socket a `freminal msg` client should use.
+pub const LISTEN_ON_ENV: &str = "FREMINAL_LISTEN_ON";
+
+/// File-name prefix shared by every instance socket.
+const SOCKET_PREFIX: &str = "freminal-";
+
+/// File-name suffix shared by every instance socket.
+const SOCKET_SUFFIX: &str = ".sock";
+
+/// One request line sent by a client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteRequest {
+    /// Protocol version the client speaks.
+    pub version: u32,
+
+    /// The command to run.
+    #[serde(flatten)]
+    pub command: RemoteCommand,
+}
+
+impl RemoteRequest {
+    /// Wrap `command` in a request stamped with [`PROTOCOL_VERSION`].
+    #[must_use]
+    pub const fn new(command: RemoteCommand) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            command,
+        }
+    }
+}
+
+/// A remote-control command.
+///
+/// Commands that target a pane take an optional `pane` id (as reported by
+/// [`RemoteCommand::Ls`]).  When omitted, the active pane of the focused
+/// window is used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum RemoteCommand {
+    /// List every window, tab, and pane.
+    Ls,
+
+    /// Write `text` to the pane's PTY exactly as if it had been typed.
+    SendText {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pane: Option<u64>,
+        text: String,
+    },
+
+    /// Read the text content of a pane.
+    GetText {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pane: Option<u64>,
+        #[serde(default)]
+        extent: TextExtent,
+    },
+
+    /// Split a pane, spawning a new shell in the new half.
+    Split {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pane: Option<u64>,
+        direction: SplitAxis,
+    },
+
+    /// Focus a pane: raise its window, switch to its tab, make it active.
+    Focus { pane: u64 },
+
+    /// Set (or with `title: None`, clear) the custom name of the tab holding
+    /// the pane.
+    SetTabTitle {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pane: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
+
+    /// Load a saved layout by library name or path.
+    LoadLayout {
+        layout: String,
+        /// Positional layout arguments (`$1`, `$2`, ...).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        /// Named layout variable overrides.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        vars: HashMap<String, String>,
+    },
+
+    /// Run a [`KeyAction`](crate::keybindings::KeyAction) by its config
+    /// name (e.g. `"new_tab"`, `"zoom_pane"`) against the pane's window, after
+    /// focusing the pane.
+    Action {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pane: Option<u64>,
+        action: String,
+    },
+
+    /// Save the focused window's flight recorder events to a new FREC file
+    /// in the recordings directory.  Fails when `[recording]
+    /// flight_recorder` is off.
+    DumpFlightRecorder,
+}
+
+/// How much of a pane's content [`RemoteCommand::GetText`] returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextExtent {
+    /// The live visible screen only.
+    #[default]
+    Screen,
+    /// All scrollback followed by the visible screen.
+    All,
+}
+
+/// Split orientation for [`RemoteCommand::Split`], named after the matching
+/// `SplitVertical` / `SplitHorizontal` key actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum SplitAxis {
+    /// Side by side (left | right).
+    Vertical,
+    /// Stacked (top / bottom).
+    Horizontal,
+}
+
+/// One response line sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum RemoteResponse {
+    /// The command succeeded.
+    Ok {
+        version: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        result: Option<RemoteResult>,
+    },
+    /// The command failed; `message` is human readable.
+    Error { version: u32, message: String },
+}
+
+impl RemoteResponse {
+    /// A successful response with no payload.
+    #[must_use]
+    pub const fn ok() -> Self {
+        Self::Ok {
+            version: PROTOCOL_VERSION,
+            result: None,
+        }
+    }
+
+    /// A successful response carrying `result`.
+    #[must_use]
+    pub const fn with_result(result: RemoteResult) -> Self {
+        Self::Ok {
+            version: PROTOCOL_VERSION,
+            result: Some(result),
+        }
+    }
+
+    /// A failed response.
+    #[must_use]
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::Error {
+            version: PROTOCOL_VERSION,
+            message: message.into(),
+        }
+    }
+}
+
+/// Payload of a successful [`RemoteResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteResult {
+    /// Answer to [`RemoteCommand::Ls`].
+    Windows(Vec<WindowInfo>),
+    /// Answer to [`RemoteCommand::GetText`].
+    Text(String),
+    /// The pane created by [`RemoteCommand::Split`].
+    Pane(u64),
+    /// The file written by [`RemoteCommand::DumpFlightRecorder`].
+    Path(PathBuf),
+}
+
+/// A window as reported by [`RemoteCommand::Ls`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowInfo {
+    /// Stable per-process window id (the same id FREC recordings use).
+    pub id: u32,
+    /// Whether this window currently has OS focus.
+    pub focused: bool,
+    /// Tabs in display order.
+    pub tabs: Vec<TabInfo>,
+}
+
+/// A tab as reported by [`RemoteCommand::Ls`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TabInfo {
+    /// Zero-based position in the tab bar.
+    pub index: usize,
+    /// The title shown in the tab bar (empty when neither a custom name nor
+    /// an OSC title is set).
+    pub title: String,
+    /// Whether this is the window's visible tab.
+    pub active: bool,
+    /// Panes in tree order.
+    pub panes: Vec<PaneInfo>,
+}
+
+/// A pane as reported by [`RemoteCommand::Ls`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneInfo {
+    /// Process-unique pane id, used to address the pane in other commands.
+    pub id: u64,
+    /// OSC 0/2 title.
+    pub title: String,
+    /// Whether this pane has keyboard focus within its tab.
+    pub active: bool,
+    /// Working directory reported by the shell (OSC 7), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Width in cells.
+    pub columns: usize,
+    /// Height in cells.
+    pub rows: usize,
+}
+
+/// Errors produced while encoding, decoding, or exchanging remote-control
+/// messages.
+#[derive(Debug, Error)]
+pub enum RemoteControlError {
+    /// A message was not valid protocol JSON.
+    #[error("invalid remote-control message: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The peer speaks a different protocol version.
+    #[error("unsupported remote-control protocol version {found} (expected {PROTOCOL_VERSION})")]
+    VersionMismatch { found: u32 },
+
+    /// Socket I/O failed.
+    #[error("remote-control socket I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The server closed the connection without answering.
+    #[error("remote-control connection closed before a response arrived")]
+    NoResponse,
+
+    /// No running instance could be found.
+    #[error(
+        "no running Freminal instance found; enable `[security] allow_remote_control` \
+         or pass --to <socket>"
+    )]
+    NoInstance,
+
+    /// Several instances are running and none was selected.
+    #[error("several Freminal instances are running; choose one with --to or ${LISTEN_ON_ENV}: {}", .0.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", "))]
+    AmbiguousInstance(Vec<PathBuf>),
+
+    /// Remote control is not available on this platform.
+    #[error("remote control is only supported on Unix platforms")]
+    Unsupported,
+}
+
+/// Serialize `message` as a single protocol line, including the trailing
+/// newline.
+///
+/// # Errors
+///
+/// Returns [`RemoteControlError::Json`] if serialization fails.
+pub fn encode_line<T: Serialize>(message: &T) -> Result<String, RemoteControlError> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    Ok(line)
+}
+
+/// Parse one request line, rejecting versions this build does not speak.
+///
+/// # Errors
+///
+/// Returns [`RemoteControlError::Json`] for malformed JSON or an unknown
+/// command, and [`RemoteControlError::VersionMismatch`] for a foreign
+/// protocol version.
+pub fn decode_request(line: &str) -> Result<RemoteRequest, RemoteControlError> {
+    let request: RemoteRequest = serde_json::from_str(line.trim_end())?;
+    if request.version != PROTOCOL_VERSION {
+        return Err(RemoteControlError::VersionMismatch {
+            found: request.version,
+        });
+    }
+    Ok(request)
+}
+
+/// Parse one response line, rejecting versions this build does not speak.
+///
+/// # Errors
+///
+/// Same as [`decode_request`].
+pub fn decode_response(line: &str) -> Result<RemoteResponse, RemoteControlError> {
+    let response: RemoteResponse = serde_json::from_str(line.trim_end())?;
+    let version = match &response {
+        RemoteResponse::Ok { version, .. } | RemoteResponse::Error { version, .. } => *version,
+    };
+    if version != PROTOCOL_VERSION {
+        return Err(RemoteControlError::VersionMismatch { found: version });
+    }
+    Ok(response)
+}
+
+/// Directory that holds every instance's socket.
+///
+/// Prefers `$XDG_RUNTIME_DIR/freminal` and falls back to a per-user
+/// directory under the system temp dir.  Does not create the directory.
+#[must_use]
+pub fn socket_dir() -> PathBuf {
+    if let Some(runtime) = std::env::var_os("XDG_RUNTIME_DIR").filter(|v| !v.is_empty()) {
+        return PathBuf::from(runtime).join("freminal");
+    }
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "user".to_owned());
+    std::env::temp_dir().join(format!("{SOCKET_PREFIX}{user}"))
+}
+
+/// Socket path for the instance running as process `pid` inside `dir`.
+#[must_use]
+pub fn socket_path_in(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("{SOCKET_PREFIX}{pid}{SOCKET_SUFFIX}"))
+}
+
+/// Socket this process is listening on, recorded once by the GUI server so
+/// PTY spawns can export it to child shells as [`LISTEN_ON_ENV`].
+static LISTENING_SOCKET: OnceLock<PathBuf> = OnceLock::new();
+
+/// Record the socket this process listens on.  Only the first call has any
+/// effect; the server binds at most once per process.
+pub fn set_listening_socket(path: PathBuf) {
+    if LISTENING_SOCKET.set(path).is_err() {
+        warn!("remote-control listening socket already recorded; ignoring");
+    }
+}
+
+/// The socket this process listens on, if remote control is enabled.
+#[must_use]
+pub fn listening_socket() -> Option<&'static Path> {
+    LISTENING_SOCKET.get().map(PathBuf::as_path)
+}
+
+/// Every socket file in `dir` that looks like an instance socket, sorted.
+///
+/// Liveness is not checked; stale sockets left by a crashed instance are
+/// included.
+#[must_use]
+pub fn list_sockets_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sockets: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(SOCKET_PREFIX) && n.ends_with(SOCKET_SUFFIX))
+        })
+        .collect();
+    sockets.sort();
+    sockets
+}
+
+#[cfg(unix)]
+mod client {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::path::{Path, PathBuf};
+
+    use super::{
+        LISTEN_ON_ENV, RemoteControlError, RemoteRequest, RemoteResponse, decode_response,
+        encode_line, list_sockets_in, socket_dir,
+    };
+
+    /// Choose the socket a client should talk to.
+    ///
+    /// Order: the explicit `to` path, then [`LISTEN_ON_ENV`], then the only
+    /// live socket in [`socket_dir`].  Stale sockets (nothing accepting) are
+    /// skipped during discovery.
+    ///
+    /// # Errors
+    ///
+    /// [`RemoteControlError::NoInstance`] when nothing is listening and
+    /// [`RemoteControlError::AmbiguousInstance`] when several instances are.
+    pub fn resolve_socket(to: Option<&Path>) -> Result<PathBuf, RemoteControlError> {
+        if let Some(path) = to {
+            return Ok(path.to_path_buf());
+        }
+        if let Some(path) = std::env::var_os(LISTEN_ON_ENV).filter(|v| !v.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
+        let mut live: Vec<PathBuf> = list_sockets_in(&socket_dir())
+            .into_iter()
+            .filter(|p| UnixStream::connect(p).is_ok())
+            .collect();
+        match live.len() {
+            0 => Err(RemoteControlError::NoInstance),
+            1 => Ok(live.remove(0)),
+            _ => Err(RemoteControlError::AmbiguousInstance(live)),
+        }
+    }
+
+    /// Send one request over a fresh connection and wait for its response.
+    ///
+    /// # Errors
+    ///
+    /// Socket, encoding, and version errors as [`RemoteControlError`].
+    pub fn send_request(
+        socket: &Path,
+        request: &RemoteRequest,
+    ) -> Result<RemoteResponse, RemoteControlError> {
+        let mut stream = UnixStream::connect(socket)?;
+        stream.write_all(encode_line(request)?.as_bytes())?;
+        stream.flush()?;
+        let mut line = String::new();
+        let read = BufReader::new(stream).read_line(&mut line)?;
+        if read == 0 {
+            return Err(RemoteControlError::NoResponse);
+        }
+        decode_response(&line)
+    }
+}
+
+#[cfg(unix)]
+pub use client::{resolve_socket, send_request};
+
+/// Choose the socket a client should talk to (unsupported off Unix).
+///
+/// # Errors
+///
+/// Always [`RemoteControlError::Unsupported`].
+#[cfg(not(unix))]
+pub const fn resolve_socket(_to: Option<&Path>) -> Result<PathBuf, RemoteControlError> {
+    Err(RemoteControlError::Unsupported)
+}
+
+/// Send one request (unsupported off Unix).
+///
+/// # Errors
+///
+/// Always [`RemoteControlError::Unsupported`].
+#[cfg(not(unix))]
+pub const fn send_request(
+    _socket: &Path,
+    _request: &RemoteRequest,
+) -> Result<RemoteResponse, RemoteControlError> {
+    Err(RemoteControlError::Unsupported)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_round_trips_through_a_line() {
+        let request = RemoteRequest::new(RemoteCommand::SendText {
+            pane: Some(3),
+            text: "ls\r".to_owned(),
+        });
+        let line = encode_line(&request).unwrap();
+        assert!(line.ends_with('\n'));
+        assert_eq!(line.matches('\n').count(), 1);
+        assert_eq!(decode_request(&line).unwrap(), request);
+    }
+
+    #[test]
+    fn request_wire_shape_is_flat_and_kebab_case() {
+        let request = RemoteRequest::new(RemoteCommand::GetText {
+            pane: None,
+            extent: TextExtent::All,
+        });
+        let json: serde_json::Value =
+            serde_json::from_str(&encode_line(&request).unwrap()).unwrap();
+        assert_eq!(json["version"], PROTOCOL_VERSION);
+        assert_eq!(json["command"], "get-text");
+        assert_eq!(json["extent"], "all");
+        assert!(json.get("pane").is_none());
+    }
+
+    #[test]
+    fn optional_fields_default_when_absent() {
+        let request = decode_request(r#"{"version":1,"command":"get-text"}"#).unwrap();
+        assert_eq!(
+            request.command,
+            RemoteCommand::GetText {
+                pane: None,
+                extent: TextExtent::Screen,
+            }
+        );
+        let request =
+            decode_request(r#"{"version":1,"command":"load-layout","layout":"dev"}"#).unwrap();
+        assert_eq!(
+            request.command,
+            RemoteCommand::LoadLayout {
+                layout: "dev".to_owned(),
+                args: Vec::new(),
+                vars: HashMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn foreign_request_version_is_rejected() {
+        let err = decode_request(r#"{"version":99,"command":"ls"}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            RemoteControlError::VersionMismatch { found: 99 }
+        ));
+    }
+
+    #[test]
+    fn unknown_command_is_a_json_error() {
+        let err = decode_request(r#"{"version":1,"command":"explode"}"#).unwrap_err();
+        assert!(matches!(err, RemoteControlError::Json(_)));
+    }
+
+    #[test]
+    fn response_round_trips() {
+        let responses = [
+            RemoteResponse::ok(),
+            RemoteResponse::with_result(RemoteResult::Text("hello\n".to_owned())),
+            RemoteResponse::with_result(RemoteResult::Pane(7)),
+            RemoteResponse::with_result(RemoteResult::Path(PathBuf::from(
+                "/tmp/freminal-flight-1.frec",
+            ))),
+            RemoteResponse::with_result(RemoteResult::Windows(vec![WindowInfo {
+                id: 0,
+                focused: true,
+                tabs: vec![TabInfo {
+                    index: 0,
+                    title: "zsh".to_owned(),
+                    active: true,
+                    panes: vec![PaneInfo {
+                        id: 1,
+                        title: "zsh".to_owned(),
+                        active: true,
+                        cwd: Some("/home/user".to_owned()),
+                        columns: 80,
+                        rows: 24,
+                    }],
+                }],
+            }])),
+            RemoteResponse::error("no such pane: 42"),
+        ];
+        for response in responses {
+            let line = encode_line(&response).unwrap();
+            assert_eq!(decode_response(&line).unwrap(), response);
+        }
+    }
+
+    #[test]
+    fn foreign_response_version_is_rejected() {
+        let err = decode_response(r#"{"status":"ok","version":2}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            RemoteControlError::VersionMismatch { found: 2 }
+        ));
+    }
+
+    #[test]
+    fn socket_listing_filters_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "freminal-2.sock",
+            "freminal-10.sock",
+            "other.sock",
+            "freminal-1.txt",
+        ] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        let found = list_sockets_in(dir.path());
+        assert_eq!(
+            found,
+            vec![
+                socket_path_in(dir.path(), 10),
+                socket_path_in(dir.path(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn socket_listing_of_missing_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(list_sockets_in(&dir.path().join("absent")).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn explicit_target_wins_over_discovery() {
+        let path = PathBuf::from("/tmp/explicit.sock");
+        assert_eq!(resolve_socket(Some(&path)).unwrap(), path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn send_request_exchanges_one_line() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = socket_path_in(dir.path(), 1);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let request = decode_request(&line).unwrap();
+            assert_eq!(request.command, RemoteCommand::Ls);
+            let mut stream = stream;
+            stream
+                .write_all(
+                    encode_line(&RemoteResponse::error("nope"))
+                        .unwrap()
+                        .as_bytes(),
+                )
+                .unwrap();
+        });
+        let response = send_request(&path, &RemoteRequest::new(RemoteCommand::Ls)).unwrap();
+        assert_eq!(response, RemoteResponse::error("nope"));
+        server.join().unwrap();
+    }
+}
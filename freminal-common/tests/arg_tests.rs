@@ -4,8 +4,11 @@
 // https://opensource.org/licenses/MIT.
 
 use clap::Parser;
-use freminal_common::args::Args;
-use proptest::{prop_assert_eq, proptest};
+use freminal_common::args::{
+    Args, CliCommand, MsgArgs, MsgCommand, RecCommand, RenderArgs, ScreenFormat, parse_time_us,
+};
+use freminal_common::remote_control::{RemoteCommand, SplitAxis, TextExtent};
+use proptest::{prop_assert_eq, prop_assume, proptest};
 
 /// Helper: run the parser with a simple iterator of strings
 fn parse_from<I: IntoIterator<Item = S>, S: Into<std::ffi::OsString> + Clone>(
@@ -28,7 +31,9 @@ fn parses_empty_args_defaults() {
     assert!(args.write_logs_to_file.is_none());
     assert!(args.config.is_none());
     assert!(args.recording_path.is_none());
+    assert!(args.play.is_none());
     assert!(args.command.is_empty());
+    assert!(args.subcommand.is_none());
 }
 
 #[test]
@@ -96,6 +101,27 @@ fn missing_recording_path_argument() {
     assert!(result.is_err());
 }
 
+#[test]
+fn parses_play() {
+    let args = parse_from(["freminal", "--play", "/tmp/session.frec"]).unwrap();
+    assert_eq!(
+        args.play.as_deref(),
+        Some(std::path::Path::new("/tmp/session.frec"))
+    );
+}
+
+#[test]
+fn play_conflicts_with_recording_path() {
+    let result = parse_from([
+        "freminal",
+        "--play",
+        "/tmp/a.frec",
+        "--recording-path",
+        "/tmp/b.frec",
+    ]);
+    assert!(result.is_err());
+}
+
 #[test]
 fn parses_config_path() {
     let args = parse_from(["freminal", "--config", "/path/to/config.toml"]).unwrap();
@@ -163,6 +189,297 @@ fn command_with_flags_uses_double_dash() {
     assert_eq!(args.command, vec!["htop", "-d", "10"]);
 }
 
+#[test]
+fn program_named_like_a_subcommand_runs_after_double_dash() {
+    let args = parse_from(["freminal", "--", "msg", "ls"]).unwrap();
+    assert!(args.subcommand.is_none());
+    assert_eq!(args.command, vec!["msg", "ls"]);
+}
+
+// ---- `msg` subcommand tests ----
+
+/// Helper: parse a `freminal msg ...` invocation and return its arguments.
+fn parse_msg(rest: &[&str]) -> MsgArgs {
+    let argv = ["freminal", "msg"].iter().chain(rest.iter()).copied();
+    match parse_from(argv).unwrap().subcommand {
+        Some(CliCommand::Msg(msg)) => msg,
+        other => panic!("expected msg subcommand, got {other:?}"),
+    }
+}
+
+#[test]
+fn parses_msg_ls() {
+    let msg = parse_msg(&["ls"]);
+    assert_eq!(msg.command, MsgCommand::Ls);
+    assert!(msg.to.is_none());
+    assert!(!msg.json);
+}
+
+#[test]
+fn parses_msg_global_options() {
+    let msg = parse_msg(&[
+        "--to",
+        "/run/user/1000/freminal/freminal-1.sock",
+        "--json",
+        "ls",
+    ]);
+    assert_eq!(
+        msg.to.as_deref(),
+        Some(std::path::Path::new(
+            "/run/user/1000/freminal/freminal-1.sock"
+        ))
+    );
+    assert!(msg.json);
+}
+
+#[test]
+fn msg_without_command_is_error() {
+    assert!(parse_from(["freminal", "msg"]).is_err());
+}
+
+#[test]
+fn msg_send_text_converts_to_remote_command() {
+    let msg = parse_msg(&["send-text", "--pane", "4", "echo hi\r"]);
+    assert_eq!(
+        RemoteCommand::from(msg.command),
+        RemoteCommand::SendText {
+            pane: Some(4),
+            text: "echo hi\r".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn msg_get_text_scrollback_selects_full_extent() {
+    let screen = parse_msg(&["get-text"]);
+    assert_eq!(
+        RemoteCommand::from(screen.command),
+        RemoteCommand::GetText {
+            pane: None,
+            extent: TextExtent::Screen,
+        }
+    );
+    let all = parse_msg(&["get-text", "--scrollback"]);
+    assert_eq!(
+        RemoteCommand::from(all.command),
+        RemoteCommand::GetText {
+            pane: None,
+            extent: TextExtent::All,
+        }
+    );
+}
+
+#[test]
+fn msg_split_requires_known_direction() {
+    let msg = parse_msg(&["split", "horizontal"]);
+    assert_eq!(
+        RemoteCommand::from(msg.command),
+        RemoteCommand::Split {
+            pane: None,
+            direction: SplitAxis::Horizontal,
+        }
+    );
+    assert!(parse_from(["freminal", "msg", "split", "diagonal"]).is_err());
+}
+
+#[test]
+fn msg_focus_requires_pane() {
+    assert_eq!(
+        parse_msg(&["focus", "9"]).command,
+        MsgCommand::Focus { pane: 9 }
+    );
+    assert!(parse_from(["freminal", "msg", "focus"]).is_err());
+}
+
+#[test]
+fn msg_set_tab_title_without_title_clears() {
+    let msg = parse_msg(&["set-tab-title", "--pane", "2"]);
+    assert_eq!(
+        RemoteCommand::from(msg.command),
+        RemoteCommand::SetTabTitle {
+            pane: Some(2),
+            title: None,
+        }
+    );
+}
+
+#[test]
+fn msg_load_layout_collects_args_and_vars() {
+    let msg = parse_msg(&[
+        "load-layout",
+        "dev",
+        "~/src/app",
+        "--var",
+        "branch=main",
+        "--var",
+        "novalue",
+    ]);
+    let RemoteCommand::LoadLayout { layout, args, vars } = RemoteCommand::from(msg.command) else {
+        panic!("expected load-layout");
+    };
+    assert_eq!(layout, "dev");
+    assert_eq!(args, vec!["~/src/app"]);
+    assert_eq!(vars.len(), 1);
+    assert_eq!(vars.get("branch").map(String::as_str), Some("main"));
+}
+
+#[test]
+fn msg_action_takes_action_name() {
+    let msg = parse_msg(&["action", "--pane", "1", "zoom_pane"]);
+    assert_eq!(
+        RemoteCommand::from(msg.command),
+        RemoteCommand::Action {
+            pane: Some(1),
+            action: "zoom_pane".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn msg_dump_flight_recorder_parses() {
+    let msg = parse_msg(&["dump-flight-recorder"]);
+    assert_eq!(
+        RemoteCommand::from(msg.command),
+        RemoteCommand::DumpFlightRecorder
+    );
+}
+
+// ---- `rec` subcommand tests ----
+
+/// Helper: parse a `freminal rec ...` invocation and return its command.
+fn parse_rec(rest: &[&str]) -> RecCommand {
+    let argv = ["freminal", "rec"].iter().chain(rest.iter()).copied();
+    match parse_from(argv).unwrap().subcommand {
+        Some(CliCommand::Rec(rec)) => rec.command,
+        other => panic!("expected rec subcommand, got {other:?}"),
+    }
+}
+
+#[test]
+fn parses_rec_extract_time_range() {
+    assert_eq!(
+        parse_rec(&[
+            "extract", "in.frec", "out.frec", "--from", "1:02.5", "--to", "90"
+        ]),
+        RecCommand::Extract {
+            file: "in.frec".into(),
+            output: "out.frec".into(),
+            from: Some(62_500_000),
+            to: Some(90_000_000),
+        }
+    );
+}
+
+#[test]
+fn parses_rec_events_repeated_types() {
+    assert_eq!(
+        parse_rec(&[
+            "events",
+            "s.frec",
+            "--pane",
+            "2",
+            "--type",
+            "PaneResize",
+            "--type",
+            "BellEvent"
+        ]),
+        RecCommand::Events {
+            file: "s.frec".into(),
+            pane: Some(2),
+            types: vec!["PaneResize".to_owned(), "BellEvent".to_owned()],
+            from: None,
+            to: None,
+        }
+    );
+}
+
+#[test]
+fn parses_rec_cast_conversions() {
+    assert_eq!(
+        parse_rec(&["export-cast", "s.frec", "s.cast", "--pane", "3"]),
+        RecCommand::ExportCast {
+            file: "s.frec".into(),
+            output: "s.cast".into(),
+            pane: Some(3),
+        }
+    );
+    assert_eq!(
+        parse_rec(&["import-cast", "s.cast", "s.frec"]),
+        RecCommand::ImportCast {
+            file: "s.cast".into(),
+            output: "s.frec".into(),
+        }
+    );
+}
+
+#[test]
+fn rec_cat_visible_conflicts_with_framed() {
+    assert!(parse_from(["freminal", "rec", "cat", "s.frec", "--visible", "--framed"]).is_err());
+}
+
+// ---- `render` subcommand tests ----
+
+/// Helper: parse a `freminal render ...` invocation.
+fn parse_render(rest: &[&str]) -> Result<RenderArgs, clap::Error> {
+    let argv = ["freminal", "render"].iter().chain(rest.iter()).copied();
+    parse_from(argv).map(|args| match args.subcommand {
+        Some(CliCommand::Render(render)) => render,
+        other => panic!("expected render subcommand, got {other:?}"),
+    })
+}
+
+#[test]
+fn parses_render_command_with_options() {
+    let render = parse_render(&[
+        "--cols", "100", "--format", "svg", "-o", "ls.svg", "--until", "\\$ $", "--", "ls", "-la",
+    ])
+    .unwrap();
+    assert_eq!(render.cols, Some(100));
+    assert_eq!(render.rows, None);
+    assert_eq!(render.format, ScreenFormat::Svg);
+    assert_eq!(render.output, Some("ls.svg".into()));
+    assert_eq!(render.until.as_deref(), Some("\\$ $"));
+    assert_eq!(render.command, vec!["ls".to_owned(), "-la".to_owned()]);
+    assert_eq!(render.screenshot, None);
+    assert!(parse_render(&["--timeout", "1.5", "htop", "-d", "10"]).is_ok());
+}
+
+#[test]
+fn parses_render_frec_pane() {
+    let render = parse_render(&["--frec", "s.frec", "--pane", "2", "--timeout", "0:30"]).unwrap();
+    assert_eq!(render.frec, Some("s.frec".into()));
+    assert_eq!(render.pane, Some(2));
+    assert_eq!(render.timeout, Some(30_000_000));
+    assert_eq!(render.format, ScreenFormat::Text);
+}
+
+#[test]
+fn parses_render_screenshot() {
+    let render = parse_render(&["--screenshot", "box.png", "-o", "box.txt", "--", "tree"]).unwrap();
+    assert_eq!(render.screenshot, Some("box.png".into()));
+    assert_eq!(render.output, Some("box.txt".into()));
+    assert_eq!(render.command, vec!["tree".to_owned()]);
+}
+
+#[test]
+fn render_needs_exactly_one_source() {
+    assert!(parse_render(&[]).is_err());
+    assert!(parse_render(&["--input", "-", "--frec", "s.frec"]).is_err());
+    assert!(parse_render(&["--input", "out.txt", "ls"]).is_err());
+    assert!(parse_render(&["--pane", "1", "ls"]).is_err());
+    assert!(parse_render(&["--cols", "0", "ls"]).is_err());
+}
+
+#[test]
+fn parse_time_us_accepts_seconds_and_minutes() {
+    assert_eq!(parse_time_us("0"), Ok(0));
+    assert_eq!(parse_time_us("2.25"), Ok(2_250_000));
+    assert_eq!(parse_time_us("10:00"), Ok(600_000_000));
+    assert!(parse_time_us("-1").is_err());
+    assert!(parse_time_us("1.2345678").is_err());
+    assert!(parse_time_us("a:10").is_err());
+}
+
 // ------------------------
 // Property-based tests
 // ------------------------
@@ -182,6 +499,8 @@ proptest! {
     /// `args.command`.
     #[test]
     fn positional_strings_become_command(s in "[a-zA-Z0-9_]+") {
+        // Subcommand names are reserved; such programs must follow `--`.
+        prop_assume!(s != "msg");
         let result = parse_from(["freminal", &s]);
         let args = result.unwrap();
         prop_assert_eq!(&args.command, &[s]);
@@ -0,0 +1,42 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Feed stdin through a terminal and print the final screen, marking bold
+//! and colored cells.
+//!
+//! ```sh
+//! ls --color=always | cargo run -p freminal-embed --example dump_screen
+//! ```
+
+use std::io::Read;
+
+use freminal_embed::{Color, Terminal};
+
+fn main() -> std::io::Result<()> {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input)?;
+
+    let mut term = Terminal::new(80, 24, ());
+    term.feed(&input);
+
+    let screen = term.screen();
+    print!("{}", screen.text());
+
+    println!("--- styled cells ---");
+    for (row, cells) in screen.rows().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            let attributes = cell.attributes();
+            if attributes.bold || attributes.foreground != Color::Default {
+                println!(
+                    "{row:>3}:{col:<3} {:?} fg={:?} bold={}",
+                    cell.text(),
+                    attributes.foreground,
+                    attributes.bold
+                );
+            }
+        }
+    }
+    Ok(())
+}
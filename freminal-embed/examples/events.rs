@@ -0,0 +1,61 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Print every event a byte stream raises, and resize with reflow.
+//!
+//! ```sh
+//! cargo run -p freminal-embed --example events
+//! ```
+
+use freminal_embed::{EventHandler, Notification, Terminal};
+
+/// Logs each callback; a real host would set its window title, beep, and
+/// write replies to the program's input.
+struct Logger;
+
+impl EventHandler for Logger {
+    fn title_changed(&mut self, title: &str) {
+        println!("title: {title}");
+    }
+
+    fn bell(&mut self) {
+        println!("bell");
+    }
+
+    fn clipboard_store(&mut self, selection: &str, text: &str) {
+        println!("clipboard {selection}: {text:?}");
+    }
+
+    fn clipboard_load(&mut self, selection: &str) -> Option<String> {
+        println!("clipboard {selection} read");
+        Some(String::from("pasted"))
+    }
+
+    fn notification(&mut self, notification: &Notification) {
+        println!(
+            "notification: {:?} {:?}",
+            notification.title, notification.body
+        );
+    }
+
+    fn reply(&mut self, bytes: &[u8]) {
+        println!("reply: {:?}", String::from_utf8_lossy(bytes));
+    }
+}
+
+fn main() {
+    let mut term = Terminal::new(12, 4, Logger);
+    term.feed(b"\x1b]0;events demo\x07");
+    term.feed(b"\x07");
+    term.feed(b"\x1b]52;c;aGVsbG8=\x07\x1b]52;c;?\x07");
+    term.feed(b"\x1b]777;notify;Build;finished\x07");
+    term.feed(b"\x1b[c");
+
+    term.feed(b"a line that wraps twice");
+    print!("{}", term.screen().text());
+    term.resize(24, 4);
+    println!("--- after resize ---");
+    print!("{}", term.screen().text());
+}
@@ -0,0 +1,70 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Callbacks from a [`crate::Terminal`] to its embedder.
+
+/// Receives what the program running in a [`crate::Terminal`] asks of its
+/// host.
+///
+/// Every method has a default that ignores the request, so implement only
+/// the ones you care about.  `()` implements this trait and ignores
+/// everything.
+///
+/// Callbacks run inside [`crate::Terminal::feed`], after the whole chunk has
+/// been parsed: first the requests in the order the program made them, then
+/// the replies.
+pub trait EventHandler {
+    /// The window title changed (OSC 0 / OSC 2, or a title restored from the
+    /// XTWINOPS title stack).
+    fn title_changed(&mut self, title: &str) {
+        let _ = title;
+    }
+
+    /// The program rang the bell (BEL).
+    fn bell(&mut self) {}
+
+    /// The program wants `text` on the clipboard (OSC 52).
+    ///
+    /// `selection` is the OSC 52 selection parameter as sent: `c` for the
+    /// clipboard, `p` for the primary selection, and so on.
+    fn clipboard_store(&mut self, selection: &str, text: &str) {
+        let _ = (selection, text);
+    }
+
+    /// The program asked to read the clipboard (OSC 52 `?`).
+    ///
+    /// Return the text to send back, or `None` to answer with an empty
+    /// clipboard.  The default refuses, which is the safe answer: anything
+    /// returned here is readable by the program and, over SSH, by the remote
+    /// host.
+    fn clipboard_load(&mut self, selection: &str) -> Option<String> {
+        let _ = selection;
+        None
+    }
+
+    /// The program posted a desktop notification (OSC 9, OSC 777 or OSC 99).
+    fn notification(&mut self, notification: &Notification) {
+        let _ = notification;
+    }
+
+    /// Bytes the terminal sends back to the program: answers to device
+    /// attribute, cursor position and mode queries, key-encoding reports and
+    /// the like.  Write them to the program's input.
+    fn reply(&mut self, bytes: &[u8]) {
+        let _ = bytes;
+    }
+}
+
+impl EventHandler for () {}
+
+/// A desktop notification posted by the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Notification {
+    /// The title, when the sequence carried one.
+    pub title: Option<String>,
+    /// The body text.  Empty for an OSC 99 notification with only a title.
+    pub body: String,
+}
@@ -0,0 +1,253 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Freminal's terminal emulator as a library.
+//!
+//! [`Terminal`] runs the same ANSI parser and buffer as the Freminal
+//! application, with no window, GPU or PTY.  Feed it a program's output with
+//! [`Terminal::feed`]; what the program asks of its host (a new title, the
+//! bell, the clipboard, notifications, replies to its queries) arrives on
+//! the [`EventHandler`] you give it, and [`Terminal::screen`] returns the
+//! cell grid with its attributes.  [`Terminal::resize`] reflows wrapped
+//! lines like the application does.
+//!
+//! ```
+//! use freminal_embed::{EventHandler, Terminal};
+//!
+//! #[derive(Default)]
+//! struct Host {
+//!     title: String,
+//!     replies: Vec<u8>,
+//! }
+//!
+//! impl EventHandler for Host {
+//!     fn title_changed(&mut self, title: &str) {
+//!         self.title = title.to_owned();
+//!     }
+//!
+//!     fn reply(&mut self, bytes: &[u8]) {
+//!         self.replies.extend_from_slice(bytes);
+//!     }
+//! }
+//!
+//! let mut term = Terminal::new(20, 3, Host::default());
+//! term.feed(b"\x1b]2;demo\x07\x1b[1mbold\x1b[0m plain\x1b[6n");
+//!
+//! assert_eq!(term.handler().title, "demo");
+//! assert_eq!(term.handler().replies, b"\x1b[1;11R");
+//!
+//! let screen = term.screen();
+//! assert_eq!(screen.text(), "bold plain\n\n\n");
+//! assert!(screen.cell(0, 0).is_some_and(|cell| cell.attributes().bold));
+//! ```
+//!
+//! This crate's API is semver-checked and does not expose types from the
+//! other Freminal crates, which change without notice.
+
+#![deny(
+    clippy::pedantic,
+    clippy::cargo,
+    clippy::nursery,
+    clippy::style,
+    clippy::correctness,
+    clippy::all,
+    clippy::suspicious,
+    clippy::complexity,
+    clippy::perf,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap,
+    missing_docs
+)]
+#![allow(clippy::multiple_crate_versions)] // Allow multiple versions from transitive dependencies
+#![allow(clippy::cargo_common_metadata)] // Metadata is inherited from workspace
+
+mod events;
+mod screen;
+
+pub use events::{EventHandler, Notification};
+pub use screen::{Attributes, Blink, Cell, Color, Cursor, Screen, Underline};
+
+use crossbeam_channel::Receiver;
+use freminal_common::base64;
+use freminal_common::buffer_states::window_manipulation::WindowManipulation;
+use freminal_common::pty_write::PtyWrite;
+use freminal_terminal_emulator::interface::TerminalEmulator;
+
+/// Scrollback lines kept by [`Terminal::new`].
+pub const DEFAULT_SCROLLBACK_LIMIT: usize = 10_000;
+
+/// A terminal emulator with no window and no PTY.
+pub struct Terminal<H: EventHandler> {
+    emulator: TerminalEmulator,
+    responses: Receiver<PtyWrite>,
+    handler: H,
+    title: String,
+    title_stack: Vec<String>,
+    screen: Option<Screen>,
+}
+
+impl<H: EventHandler> Terminal<H> {
+    /// Create a `cols` x `rows` terminal that reports to `handler`, keeping
+    /// [`DEFAULT_SCROLLBACK_LIMIT`] lines of scrollback.
+    #[must_use]
+    pub fn new(cols: usize, rows: usize, handler: H) -> Self {
+        Self::with_scrollback_limit(cols, rows, DEFAULT_SCROLLBACK_LIMIT, handler)
+    }
+
+    /// Like [`Terminal::new`], keeping at most `scrollback_limit` lines that
+    /// scrolled off the top.
+    #[must_use]
+    pub fn with_scrollback_limit(
+        cols: usize,
+        rows: usize,
+        scrollback_limit: usize,
+        handler: H,
+    ) -> Self {
+        let (mut emulator, responses) = TerminalEmulator::new_headless(Some(scrollback_limit));
+        emulator
+            .internal
+            .set_win_size(cols.max(1), rows.max(1), 0, 0);
+        Self {
+            emulator,
+            responses,
+            handler,
+            title: String::new(),
+            title_stack: Vec::new(),
+            screen: None,
+        }
+    }
+
+    /// Process output from the program, then deliver the resulting events to
+    /// the handler.
+    ///
+    /// `data` may end anywhere, even inside an escape sequence or a UTF-8
+    /// character; the rest is picked up by the next call.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.emulator.handle_incoming_data(data);
+        self.screen = None;
+
+        let commands = std::mem::take(&mut self.emulator.internal.window_commands);
+        let mut replies = Vec::new();
+        for command in commands {
+            self.dispatch(command, &mut replies);
+        }
+        let responses: Vec<u8> = self
+            .responses
+            .try_iter()
+            .filter_map(|write| match write {
+                PtyWrite::Write(bytes) => Some(bytes),
+                PtyWrite::Resize(_) => None,
+            })
+            .flatten()
+            .chain(replies)
+            .collect();
+        if !responses.is_empty() {
+            self.handler.reply(&responses);
+        }
+    }
+
+    /// Change the size to `cols` x `rows`.  Lines the program wrapped are
+    /// rewrapped to the new width.
+    pub fn resize(&mut self, cols: usize, rows: usize) {
+        self.emulator
+            .internal
+            .set_win_size(cols.max(1), rows.max(1), 0, 0);
+        self.screen = None;
+    }
+
+    /// The size as `(cols, rows)`.
+    #[must_use]
+    pub const fn size(&self) -> (usize, usize) {
+        self.emulator.internal.handler.win_size()
+    }
+
+    /// The visible screen.
+    ///
+    /// Built on the first call after [`Terminal::feed`] or
+    /// [`Terminal::resize`] and reused until the next one.
+    pub fn screen(&mut self) -> &Screen {
+        self.screen
+            .get_or_insert_with(|| Screen::from_snapshot(&self.emulator.build_snapshot()))
+    }
+
+    /// The current window title; empty until the program sets one.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The event handler.
+    #[must_use]
+    pub const fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// The event handler, mutably.
+    pub const fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Consume the terminal and return its event handler.
+    #[must_use]
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+
+    /// Act on one request from the program, queueing any answer in
+    /// `replies`.  Requests about the window itself (moving, resizing,
+    /// iconifying, reporting its geometry) have no meaning here and are
+    /// dropped.
+    fn dispatch(&mut self, command: WindowManipulation, replies: &mut Vec<u8>) {
+        match command {
+            WindowManipulation::SetTitleBarText(title) => self.set_title(title),
+            WindowManipulation::SaveWindowTitleToStack => {
+                self.title_stack.push(self.title.clone());
+            }
+            WindowManipulation::RestoreWindowTitleFromStack => {
+                if let Some(title) = self.title_stack.pop() {
+                    self.set_title(title);
+                }
+            }
+            WindowManipulation::ReportTitle => {
+                replies.extend_from_slice(format!("\x1b]l{}\x1b\\", self.title).as_bytes());
+            }
+            WindowManipulation::ReportIconLabel => {
+                replies.extend_from_slice(format!("\x1b]L{}\x1b\\", self.title).as_bytes());
+            }
+            WindowManipulation::Bell => self.handler.bell(),
+            WindowManipulation::SetClipboard(selection, text) => {
+                self.handler.clipboard_store(&selection, &text);
+            }
+            WindowManipulation::QueryClipboard(selection) => {
+                let payload = self
+                    .handler
+                    .clipboard_load(&selection)
+                    .map(|text| base64::encode(text.as_bytes()))
+                    .unwrap_or_default();
+                replies
+                    .extend_from_slice(format!("\x1b]52;{selection};{payload}\x1b\\").as_bytes());
+            }
+            WindowManipulation::Notification { title, body, .. } => {
+                self.handler.notification(&Notification { title, body });
+            }
+            WindowManipulation::Notification99(data) => {
+                let data = *data;
+                self.handler.notification(&Notification {
+                    title: data.title,
+                    body: data.body.unwrap_or_default(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn set_title(&mut self, title: String) {
+        self.handler.title_changed(&title);
+        self.title = title;
+    }
+}
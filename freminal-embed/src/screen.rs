@@ -0,0 +1,340 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The visible cell grid of a [`crate::Terminal`].
+
+use std::sync::{Arc, LazyLock};
+
+use freminal_common::buffer_states::{
+    cursor::ReverseVideo,
+    fonts::{BlinkState, FontDecorations, FontWeight, UnderlineStyle},
+    format_tag::FormatTag,
+    tchar::TChar,
+    url::Url,
+};
+use freminal_common::colors::TerminalColor;
+use freminal_terminal_emulator::snapshot::TerminalSnapshot;
+
+/// A cell color.
+///
+/// The default colors and the 16 ANSI colors are left for the embedder to
+/// pick.  Colors from the 256-color palette (SGR 38;5 and 48;5) are looked
+/// up when the program selects them, so they arrive as [`Color::Rgb`] and
+/// reflect any palette changes the program made with OSC 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Color {
+    /// The default foreground or background, depending on where it is used.
+    #[default]
+    Default,
+    /// An ANSI color: 0–7 are the normal colors, 8–15 their bright
+    /// variants.
+    Indexed(u8),
+    /// A direct 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    const fn from_terminal(color: TerminalColor) -> Self {
+        match color {
+            TerminalColor::Default
+            | TerminalColor::DefaultBackground
+            | TerminalColor::DefaultUnderlineColor
+            | TerminalColor::DefaultCursorColor => Self::Default,
+            TerminalColor::Black => Self::Indexed(0),
+            TerminalColor::Red => Self::Indexed(1),
+            TerminalColor::Green => Self::Indexed(2),
+            TerminalColor::Yellow => Self::Indexed(3),
+            TerminalColor::Blue => Self::Indexed(4),
+            TerminalColor::Magenta => Self::Indexed(5),
+            TerminalColor::Cyan => Self::Indexed(6),
+            TerminalColor::White => Self::Indexed(7),
+            TerminalColor::BrightBlack => Self::Indexed(8),
+            TerminalColor::BrightRed => Self::Indexed(9),
+            TerminalColor::BrightGreen => Self::Indexed(10),
+            TerminalColor::BrightYellow => Self::Indexed(11),
+            TerminalColor::BrightBlue => Self::Indexed(12),
+            TerminalColor::BrightMagenta => Self::Indexed(13),
+            TerminalColor::BrightCyan => Self::Indexed(14),
+            TerminalColor::BrightWhite => Self::Indexed(15),
+            TerminalColor::PaletteIndex(index) => Self::Indexed(index),
+            TerminalColor::Custom(r, g, b) => Self::Rgb(r, g, b),
+        }
+    }
+}
+
+/// Underline style (SGR 4, 4:x and 21).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Underline {
+    /// Not underlined.
+    #[default]
+    None,
+    /// A single straight line.
+    Single,
+    /// Two straight lines.
+    Double,
+    /// A wavy line.
+    Curly,
+    /// A dotted line.
+    Dotted,
+    /// A dashed line.
+    Dashed,
+}
+
+/// Blink speed (SGR 5 and 6).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Blink {
+    /// Not blinking.
+    #[default]
+    None,
+    /// Slow blink (SGR 5).
+    Slow,
+    /// Rapid blink (SGR 6).
+    Rapid,
+}
+
+/// The text attributes of a cell.
+///
+/// Colors are stored as set; with `inverse` the embedder swaps foreground
+/// and background when drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)] // One independent flag per SGR attribute.
+pub struct Attributes {
+    /// Foreground color.
+    pub foreground: Color,
+    /// Background color.
+    pub background: Color,
+    /// Underline color; `Default` means the foreground color.
+    pub underline_color: Color,
+    /// Bold (SGR 1).
+    pub bold: bool,
+    /// Faint (SGR 2).
+    pub faint: bool,
+    /// Italic (SGR 3).
+    pub italic: bool,
+    /// Underline style.
+    pub underline: Underline,
+    /// Blink speed.
+    pub blink: Blink,
+    /// Reverse video (SGR 7).
+    pub inverse: bool,
+    /// Strikethrough (SGR 9).
+    pub strikethrough: bool,
+}
+
+impl Attributes {
+    fn from_tag(tag: &FormatTag) -> Self {
+        let decorations = tag.font_decorations;
+        Self {
+            foreground: Color::from_terminal(tag.colors.color),
+            background: Color::from_terminal(tag.colors.background_color),
+            underline_color: Color::from_terminal(tag.colors.underline_color),
+            bold: tag.font_weight == FontWeight::Bold,
+            faint: decorations.contains(FontDecorations::Faint),
+            italic: decorations.contains(FontDecorations::Italic),
+            underline: match decorations.underline_style() {
+                UnderlineStyle::None => Underline::None,
+                UnderlineStyle::Single => Underline::Single,
+                UnderlineStyle::Double => Underline::Double,
+                UnderlineStyle::Curly => Underline::Curly,
+                UnderlineStyle::Dotted => Underline::Dotted,
+                UnderlineStyle::Dashed => Underline::Dashed,
+            },
+            blink: match tag.blink {
+                BlinkState::None => Blink::None,
+                BlinkState::Slow => Blink::Slow,
+                BlinkState::Fast => Blink::Rapid,
+            },
+            inverse: tag.colors.reverse_video == ReverseVideo::On,
+            strikethrough: decorations.contains(FontDecorations::Strikethrough),
+        }
+    }
+}
+
+/// One cell of the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    text: String,
+    width: usize,
+    attributes: Attributes,
+    hyperlink: Option<Arc<Url>>,
+}
+
+impl Cell {
+    fn blank() -> Self {
+        Self {
+            text: String::from(" "),
+            width: 1,
+            attributes: Attributes::default(),
+            hyperlink: None,
+        }
+    }
+
+    /// The grapheme in this cell: a space for blank cells, and empty for the
+    /// second column of a wide character.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Columns the text takes: 1, 2 for a wide character, 0 for the column
+    /// a wide character spills into.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Whether this is the second column of a wide character.
+    #[must_use]
+    pub const fn is_wide_continuation(&self) -> bool {
+        self.width == 0
+    }
+
+    /// The text attributes.
+    #[must_use]
+    pub const fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    /// The OSC 8 hyperlink target, if the cell is part of a link.
+    #[must_use]
+    pub fn hyperlink(&self) -> Option<&str> {
+        self.hyperlink.as_deref().map(|url| url.url.as_str())
+    }
+}
+
+/// Where the cursor is and whether it is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Cursor {
+    /// Row, from 0 at the top of the screen.
+    pub row: usize,
+    /// Column, from 0 at the left.
+    pub col: usize,
+    /// `false` when the program hid the cursor (DECTCEM).
+    pub visible: bool,
+}
+
+/// The visible screen: `rows` rows of exactly `cols` cells each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screen {
+    cols: usize,
+    rows: Vec<Vec<Cell>>,
+    cursor: Cursor,
+    alternate: bool,
+}
+
+impl Screen {
+    pub(crate) fn from_snapshot(snap: &TerminalSnapshot) -> Self {
+        static DEFAULT_TAG: LazyLock<FormatTag> = LazyLock::new(FormatTag::default);
+
+        let tags = &snap.visible_tags;
+        let mut rows: Vec<Vec<Cell>> = vec![Vec::new()];
+        let mut tag_index = 0;
+        for (index, ch) in snap.visible_chars.iter().enumerate() {
+            if *ch == TChar::NewLine {
+                rows.push(Vec::new());
+                continue;
+            }
+            while tags.get(tag_index).is_some_and(|tag| tag.end <= index) {
+                tag_index += 1;
+            }
+            let tag = tags
+                .get(tag_index)
+                .filter(|tag| tag.start <= index)
+                .unwrap_or(&DEFAULT_TAG);
+            let Some(row) = rows.last_mut() else {
+                continue;
+            };
+            let attributes = Attributes::from_tag(tag);
+            let width = ch.display_width().max(1);
+            row.push(Cell {
+                text: String::from_utf8_lossy(ch.as_bytes()).into_owned(),
+                width,
+                attributes,
+                hyperlink: tag.url.clone(),
+            });
+            for _ in 1..width {
+                row.push(Cell {
+                    text: String::new(),
+                    width: 0,
+                    attributes,
+                    hyperlink: tag.url.clone(),
+                });
+            }
+        }
+
+        let mut rows: Vec<Vec<Cell>> = rows
+            .into_iter()
+            .skip(snap.window_extra_rows)
+            .take(snap.term_height)
+            .collect();
+        rows.resize_with(snap.term_height, Vec::new);
+        for row in &mut rows {
+            row.resize_with(snap.term_width, Cell::blank);
+        }
+
+        Self {
+            cols: snap.term_width,
+            rows,
+            cursor: Cursor {
+                row: snap.cursor_pos.y,
+                col: snap.cursor_pos.x,
+                visible: snap.show_cursor,
+            },
+            alternate: snap.is_alternate_screen,
+        }
+    }
+
+    /// The size as `(cols, rows)`.
+    #[must_use]
+    pub const fn size(&self) -> (usize, usize) {
+        (self.cols, self.rows.len())
+    }
+
+    /// The cell at `row`, `col`, or `None` outside the screen.
+    #[must_use]
+    pub fn cell(&self, row: usize, col: usize) -> Option<&Cell> {
+        self.rows.get(row).and_then(|cells| cells.get(col))
+    }
+
+    /// The cells of `row`, or `None` past the last row.
+    #[must_use]
+    pub fn row(&self, row: usize) -> Option<&[Cell]> {
+        self.rows.get(row).map(Vec::as_slice)
+    }
+
+    /// All rows, top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[Cell]> {
+        self.rows.iter().map(Vec::as_slice)
+    }
+
+    /// The cursor.
+    #[must_use]
+    pub const fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+
+    /// Whether the program switched to the alternate screen (full-screen
+    /// programs such as editors and pagers do).
+    #[must_use]
+    pub const fn is_alternate(&self) -> bool {
+        self.alternate
+    }
+
+    /// The screen as plain text: one line per row, trailing spaces removed.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        for row in &self.rows {
+            let line: String = row.iter().map(Cell::text).collect();
+            out.push_str(line.trim_end_matches(' '));
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+#![allow(clippy::unwrap_used)]
+
+use freminal_embed::{Blink, Color, EventHandler, Notification, Terminal, Underline};
+
+#[derive(Default)]
+struct Recorder {
+    titles: Vec<String>,
+    bells: usize,
+    clipboard: Vec<(String, String)>,
+    clipboard_contents: Option<String>,
+    notifications: Vec<Notification>,
+    replies: Vec<u8>,
+}
+
+impl EventHandler for Recorder {
+    fn title_changed(&mut self, title: &str) {
+        self.titles.push(title.to_owned());
+    }
+
+    fn bell(&mut self) {
+        self.bells += 1;
+    }
+
+    fn clipboard_store(&mut self, selection: &str, text: &str) {
+        self.clipboard.push((selection.to_owned(), text.to_owned()));
+    }
+
+    fn clipboard_load(&mut self, _selection: &str) -> Option<String> {
+        self.clipboard_contents.clone()
+    }
+
+    fn notification(&mut self, notification: &Notification) {
+        self.notifications.push(notification.clone());
+    }
+
+    fn reply(&mut self, bytes: &[u8]) {
+        self.replies.extend_from_slice(bytes);
+    }
+}
+
+fn terminal(cols: usize, rows: usize) -> Terminal<Recorder> {
+    Terminal::new(cols, rows, Recorder::default())
+}
+
+#[test]
+fn text_lands_on_the_grid() {
+    let mut term = terminal(10, 3);
+    term.feed(b"hello\r\nworld");
+    let screen = term.screen();
+    assert_eq!(screen.size(), (10, 3));
+    assert_eq!(screen.text(), "hello\nworld\n\n");
+    assert!(screen.rows().all(|row| row.len() == 10));
+    assert_eq!(screen.cell(1, 4).unwrap().text(), "d");
+    assert_eq!(screen.cell(2, 9).unwrap().text(), " ");
+    assert!(screen.cell(3, 0).is_none());
+    let cursor = screen.cursor();
+    assert_eq!((cursor.row, cursor.col, cursor.visible), (1, 5, true));
+}
+
+#[test]
+fn split_sequences_are_joined() {
+    let mut term = terminal(10, 1);
+    term.feed(b"\x1b[3");
+    term.feed(b"1mr\xc3");
+    term.feed(b"\xa9d");
+    let screen = term.screen();
+    assert_eq!(screen.text(), "réd\n");
+    assert_eq!(
+        screen.cell(0, 1).unwrap().attributes().foreground,
+        Color::Indexed(1)
+    );
+}
+
+#[test]
+fn attributes_are_reported() {
+    let mut term = terminal(20, 1);
+    term.feed(b"\x1b[1;3;4:3;5;7;9;38;5;200;48;2;1;2;3mx\x1b[0my");
+    let screen = term.screen();
+    let styled = screen.cell(0, 0).unwrap().attributes();
+    assert!(styled.bold && styled.italic && styled.inverse && styled.strikethrough);
+    assert!(!styled.faint);
+    assert_eq!(styled.underline, Underline::Curly);
+    assert_eq!(styled.blink, Blink::Slow);
+    // 256-color indices are looked up in the xterm palette.
+    assert_eq!(styled.foreground, Color::Rgb(0xff, 0x00, 0xd7));
+    assert_eq!(styled.background, Color::Rgb(1, 2, 3));
+    assert_eq!(
+        *screen.cell(0, 1).unwrap().attributes(),
+        freminal_embed::Attributes::default()
+    );
+}
+
+#[test]
+fn wide_characters_take_two_cells() {
+    let mut term = terminal(6, 1);
+    term.feed("漢x".as_bytes());
+    let screen = term.screen();
+    let row = screen.row(0).unwrap();
+    assert_eq!(row[0].text(), "漢");
+    assert_eq!(row[0].width(), 2);
+    assert!(row[1].is_wide_continuation());
+    assert_eq!(row[2].text(), "x");
+    assert_eq!(screen.text(), "漢x\n");
+}
+
+#[test]
+fn hyperlinks_are_reported() {
+    let mut term = terminal(10, 1);
+    term.feed(b"\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\ no");
+    let screen = term.screen();
+    assert_eq!(
+        screen.cell(0, 0).unwrap().hyperlink(),
+        Some("https://example.com")
+    );
+    assert_eq!(screen.cell(0, 5).unwrap().hyperlink(), None);
+}
+
+#[test]
+fn title_and_title_stack() {
+    let mut term = terminal(10, 1);
+    term.feed(b"\x1b]2;first\x07\x1b[22;0t\x1b]2;second\x07");
+    assert_eq!(term.title(), "second");
+    term.feed(b"\x1b[23;0t\x1b[21t");
+    assert_eq!(term.title(), "first");
+    assert_eq!(term.handler().titles, ["first", "second", "first"]);
+    assert_eq!(term.handler().replies, b"\x1b]lfirst\x1b\\");
+}
+
+#[test]
+fn bell_clipboard_and_notifications() {
+    let mut term = terminal(10, 1);
+    term.feed(b"\x07\x07\x1b]52;c;aGVsbG8=\x07");
+    term.feed(b"\x1b]9;done\x07\x1b]777;notify;Build;ok\x07");
+    let handler = term.handler();
+    assert_eq!(handler.bells, 2);
+    assert_eq!(
+        handler.clipboard,
+        [(String::from("c"), String::from("hello"))]
+    );
+    let notifications: Vec<_> = handler
+        .notifications
+        .iter()
+        .map(|n| (n.title.as_deref(), n.body.as_str()))
+        .collect();
+    assert_eq!(notifications, [(None, "done"), (Some("Build"), "ok")]);
+}
+
+#[test]
+fn clipboard_reads_go_through_the_handler() {
+    let mut term = terminal(10, 1);
+    term.feed(b"\x1b]52;c;?\x07");
+    assert_eq!(term.handler().replies, b"\x1b]52;c;\x1b\\");
+
+    term.handler_mut().replies.clear();
+    term.handler_mut().clipboard_contents = Some(String::from("hello"));
+    term.feed(b"\x1b]52;c;?\x07");
+    assert_eq!(term.handler().replies, b"\x1b]52;c;aGVsbG8=\x1b\\");
+}
+
+#[test]
+fn queries_are_answered() {
+    let mut term = terminal(10, 3);
+    term.feed(b"ab\x1b[6n");
+    assert_eq!(term.into_handler().replies, b"\x1b[1;3R");
+}
+
+#[test]
+fn resize_reflows_wrapped_lines() {
+    let mut term = terminal(5, 3);
+    term.feed(b"abcdefgh");
+    assert_eq!(term.screen().text(), "abcde\nfgh\n\n");
+    term.resize(10, 3);
+    assert_eq!(term.size(), (10, 3));
+    assert_eq!(term.screen().text(), "abcdefgh\n\n\n");
+}
+
+#[test]
+fn alternate_screen_is_reported() {
+    let mut term = terminal(10, 2);
+    term.feed(b"main");
+    term.feed(b"\x1b[?1049h\x1b[?25lalt");
+    let screen = term.screen();
+    assert!(screen.is_alternate());
+    assert!(!screen.cursor().visible);
+    assert_eq!(screen.text(), "alt\n\n");
+    term.feed(b"\x1b[?1049l");
+    assert_eq!(term.screen().text(), "main\n\n");
+}
@@ -8,7 +8,7 @@ use core::fmt;
 use crate::{
     ansi_components::{
         apc::ApcParser, csi::AnsiCsiParser, dcs::DcsParser, osc::AnsiOscParser,
-        standard::StandardParser, tracer::SequenceTraceable,
+        standard::StandardParser, tmux_control::TmuxControlParser, tracer::SequenceTraceable,
     },
     error::ParserFailures,
 };
@@ -113,6 +113,8 @@ pub enum ParserInner {
     Osc(AnsiOscParser),
     Standard(StandardParser),
     Dcs(DcsParser),
+    /// Inside a tmux control-mode stream (`DCS 1000 p` … ST).
+    TmuxControl(TmuxControlParser),
     Apc(ApcParser),
     /// VT52: waiting for the command byte after ESC.
     Vt52Escape,
@@ -214,11 +216,13 @@ impl FreminalAnsiParser {
 
         // SI (0x0F) — Shift In: invoke G0 into GL.
         // SO (0x0E) — Shift Out: invoke G1 into GL.
-        // Silently consumed as control characters.  Freminal uses a simplified
-        // single-slot charset model — G0 is always the active GL set, so SI is
-        // a no-op and SO has no visible effect until full G0/G1 support is added.
         if b == 0x0F || b == 0x0E {
             push_data_if_non_empty(data_output, output);
+            output.push(if b == 0x0F {
+                TerminalOutput::CharsetG0AsGL
+            } else {
+                TerminalOutput::CharsetG1AsGL
+            });
             return Err(());
         }
 
@@ -266,7 +270,10 @@ impl FreminalAnsiParser {
                 0x9F => {
                     self.inner = ParserInner::Apc(ApcParser::new());
                 }
-                // SS2 (0x8E), SS3 (0x8F) — Single Shift G2/G3 (no-op for now)
+                // SS2 (0x8E) ≡ ESC N — Single Shift G2
+                0x8E => output.push(TerminalOutput::SingleShiftG2),
+                // SS3 (0x8F) ≡ ESC O — Single Shift G3
+                0x8F => output.push(TerminalOutput::SingleShiftG3),
                 // ST (0x9C) — String Terminator (no-op when no string is open)
                 // PM (0x9E) — Privacy Message (ignored, no handler)
                 // Other bytes in 0x80–0x9F without defined C1 meaning — silently ignored
@@ -379,6 +386,9 @@ impl FreminalAnsiParser {
     ///                  (ESC '\') terminates it; then dispatches to osc.rs.
     /// Standard       — handles two-byte Fe sequences (ESC # Pn, ESC ( G, …).
     /// Dcs            — Device Control String; passes bytes to dcs.rs until ST.
+    ///                  The tmux `DCS 1000 p` handshake moves to TmuxControl.
+    /// TmuxControl    — tmux control-mode stream; emits one output per line
+    ///                  via tmux_control.rs until ST.
     /// Apc            — Application Program Command; passes bytes to apc.rs
     ///                  until ST (used by tmux passthrough passthrough).
     /// Vt52Escape     — waiting for the single command byte after ESC in VT52
@@ -445,7 +455,12 @@ impl FreminalAnsiParser {
                 }
                 ParserInner::Dcs(parser) => match parser.dcs_parser_inner(b, &mut output) {
                     ParserOutcome::Finished => {
-                        self.inner = ParserInner::Empty;
+                        self.inner = if output.last() == Some(&TerminalOutput::TmuxControlModeStart)
+                        {
+                            ParserInner::TmuxControl(TmuxControlParser::new())
+                        } else {
+                            ParserInner::Empty
+                        };
                     }
                     ParserOutcome::Continue => (),
                     ParserOutcome::Invalid(_) | ParserOutcome::InvalidParserFailure(_) => {
@@ -453,6 +468,11 @@ impl FreminalAnsiParser {
                         self.inner = ParserInner::Empty;
                     }
                 },
+                ParserInner::TmuxControl(parser) => {
+                    if parser.tmux_control_parser_inner(b, &mut output) == ParserOutcome::Finished {
+                        self.inner = ParserInner::Empty;
+                    }
+                }
                 ParserInner::Apc(parser) => match parser.apc_parser_inner(b, &mut output) {
                     ParserOutcome::Finished => {
                         self.inner = ParserInner::Empty;
@@ -700,6 +720,7 @@ impl FreminalAnsiParser {
             ParserInner::Csi(p) => p.trace_str(),
             ParserInner::Standard(p) => p.trace_str(),
             ParserInner::Dcs(p) => p.trace_str(),
+            ParserInner::TmuxControl(p) => p.trace_str(),
             ParserInner::Apc(p) => p.trace_str(),
             _ => self.seq_trace.as_str(),
         }
@@ -878,29 +899,20 @@ mod tests {
             ScreenAlignmentTest,
             CharsetDefault,
             CharsetUTF8,
-            CharsetG0,
-            CharsetG1,
+            DesignateCharset {
+                set: freminal_common::buffer_states::charset::GSet::G1,
+                charset: freminal_common::buffer_states::charset::Charset::German,
+            },
+            CharsetG0AsGL,
+            CharsetG1AsGL,
             CharsetG1AsGR,
-            CharsetG2,
             CharsetG2AsGR,
             CharsetG2AsGL,
-            CharsetG3,
             CharsetG3AsGR,
             CharsetG3AsGL,
+            SingleShiftG2,
+            SingleShiftG3,
             DecSpecial,
-            CharsetUK,
-            CharsetUS,
-            CharsetUSASCII,
-            CharsetDutch,
-            CharsetFinnish,
-            CharsetFrench,
-            CharsetFrenchCanadian,
-            CharsetGerman,
-            CharsetItalian,
-            CharsetNorwegianDanish,
-            CharsetSpanish,
-            CharsetSwedish,
-            CharsetSwiss,
             SaveCursor,
             RestoreCursor,
             CursorToLowerLeftCorner,
@@ -1496,15 +1508,30 @@ mod tests {
 
     #[test]
     fn esc_plus_0_designates_g3_as_dec_special_graphics() {
-        // ESC + 0 should designate the G3 character set as DEC Special Graphics,
-        // producing the same output as ESC ( 0 does for G0.
         let mut parser = FreminalAnsiParser::new();
         let result = parser.push(b"\x1b+0");
         assert_eq!(
             result,
-            vec![TerminalOutput::DecSpecialGraphics(
-                freminal_common::buffer_states::line_draw::DecSpecialGraphics::Replace
-            )]
+            vec![TerminalOutput::DesignateCharset {
+                set: freminal_common::buffer_states::charset::GSet::G3,
+                charset: freminal_common::buffer_states::charset::Charset::DecSpecialGraphics,
+            }]
+        );
+    }
+
+    #[test]
+    fn shift_in_and_shift_out_invoke_g0_and_g1() {
+        let mut parser = FreminalAnsiParser::new();
+        let result = parser.push(b"a\x0eb\x0fc");
+        assert_eq!(
+            result,
+            vec![
+                TerminalOutput::Data(b"a".to_vec()),
+                TerminalOutput::CharsetG1AsGL,
+                TerminalOutput::Data(b"b".to_vec()),
+                TerminalOutput::CharsetG0AsGL,
+                TerminalOutput::Data(b"c".to_vec()),
+            ]
         );
     }
 
@@ -1626,12 +1653,9 @@ mod tests {
         );
         assert_eq!(p.inner, ParserInner::Empty);
 
-        // 0x8E (SS2) also falls through to the catch-all
-        let result2 = p.push(b"\x8E");
-        assert!(
-            result2.is_empty(),
-            "SS2 (0x8E) should be silently ignored: {result2:?}"
-        );
+        // 0x8E and 0x8F are SS2 and SS3
+        assert_eq!(p.push(b"\x8E"), vec![TerminalOutput::SingleShiftG2]);
+        assert_eq!(p.push(b"\x8F"), vec![TerminalOutput::SingleShiftG3]);
     }
 
     // ── Escape state: ESC ESC is logged as debug (line 308) ─────────────────
@@ -1684,8 +1708,8 @@ mod tests {
         assert!(
             result
                 .iter()
-                .any(|o| matches!(o, TerminalOutput::DecSpecialGraphics(_))),
-            "ESC ( 0 should produce DecSpecialGraphics: {result:?}"
+                .any(|o| matches!(o, TerminalOutput::DesignateCharset { .. })),
+            "ESC ( 0 should produce DesignateCharset: {result:?}"
         );
         assert_eq!(p.inner, ParserInner::Empty);
     }
@@ -1693,12 +1717,12 @@ mod tests {
     #[test]
     fn standard_parser_invalid_charset_designator() {
         let mut p = FreminalAnsiParser::new();
-        // ESC ( followed by an unknown charset designator 'Z'
-        // This goes through StandardParser Continue on '(' then Invalid on 'Z'
-        let result = p.push(b"\x1b(Z");
+        // ESC ( followed by an unknown charset designator 'W'
+        // This goes through StandardParser Continue on '(' then Invalid on 'W'
+        let result = p.push(b"\x1b(W");
         // Should produce Invalid output
         let has_invalid = result.iter().any(|o| matches!(o, TerminalOutput::Invalid));
-        assert!(has_invalid, "ESC ( Z should produce Invalid: {result:?}");
+        assert!(has_invalid, "ESC ( W should produce Invalid: {result:?}");
         assert_eq!(p.inner, ParserInner::Empty);
     }
 
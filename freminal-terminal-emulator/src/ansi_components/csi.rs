@@ -52,24 +52,64 @@ pub(crate) fn push_split_mode_params(
 }
 
 use super::csi_commands::{
-    cbt::ansi_parser_inner_csi_finished_cbt, cha::ansi_parser_inner_csi_finished_cha,
-    cht::ansi_parser_inner_csi_finished_cht, cnl::ansi_parser_inner_csi_finished_cnl,
-    cpl::ansi_parser_inner_csi_finished_cpl, cub::ansi_parser_inner_csi_finished_cub,
-    cud::ansi_parser_inner_csi_finished_cud, cuf::ansi_parser_inner_csi_finished_cuf,
-    cup::ansi_parser_inner_csi_finished_cup, cuu::ansi_parser_inner_csi_finished_cuu,
-    da::ansi_parser_inner_csi_finished_da, dch::ansi_parser_inner_csi_finished_dch,
+    cbt::ansi_parser_inner_csi_finished_cbt,
+    cha::ansi_parser_inner_csi_finished_cha,
+    cht::ansi_parser_inner_csi_finished_cht,
+    cnl::ansi_parser_inner_csi_finished_cnl,
+    color_stack::{
+        ansi_parser_inner_csi_finished_xtpopcolors, ansi_parser_inner_csi_finished_xtpushcolors,
+        ansi_parser_inner_csi_finished_xtreportcolors,
+    },
+    columns::{ansi_parser_inner_csi_finished_decdc, ansi_parser_inner_csi_finished_decic},
+    cpl::ansi_parser_inner_csi_finished_cpl,
+    cub::ansi_parser_inner_csi_finished_cub,
+    cud::ansi_parser_inner_csi_finished_cud,
+    cuf::ansi_parser_inner_csi_finished_cuf,
+    cup::ansi_parser_inner_csi_finished_cup,
+    cuu::ansi_parser_inner_csi_finished_cuu,
+    da::ansi_parser_inner_csi_finished_da,
+    dch::ansi_parser_inner_csi_finished_dch,
     decrqm::ansi_parser_inner_csi_finished_decrqm,
+    decrqpsr::ansi_parser_inner_csi_finished_decrqpsr,
     decscusr::ansi_parser_inner_csi_finished_decscusr,
     decslpp::ansi_parser_inner_csi_finished_decslpp,
     decslrm::ansi_parser_inner_csi_finished_decslrm,
-    decstbm::ansi_parser_inner_csi_finished_decstbm, dl::ansi_parser_inner_csi_finished_dl,
-    dsr::ansi_parser_inner_csi_finished_dsr, ech::ansi_parser_inner_csi_finished_ech,
-    ed::ansi_parser_inner_csi_finished_ed, el::ansi_parser_inner_csi_finished_el,
-    ich::ansi_parser_inner_csi_finished_ich, il::ansi_parser_inner_csi_finished_il,
-    rep::ansi_parser_inner_csi_finished_rep, scorc::ansi_parser_inner_csi_finished_scorc,
-    sd::ansi_parser_inner_csi_finished_sd, sgr::ansi_parser_inner_csi_finished_sgr,
-    su::ansi_parser_inner_csi_finished_su, tbc::ansi_parser_inner_csi_finished_tbc,
-    vpa::ansi_parser_inner_csi_finished_vpa, xtversion::ansi_parser_inner_csi_finished_xtversion,
+    decstbm::ansi_parser_inner_csi_finished_decstbm,
+    dl::ansi_parser_inner_csi_finished_dl,
+    dsr::ansi_parser_inner_csi_finished_dsr,
+    ech::ansi_parser_inner_csi_finished_ech,
+    ed::ansi_parser_inner_csi_finished_ed,
+    el::ansi_parser_inner_csi_finished_el,
+    ich::ansi_parser_inner_csi_finished_ich,
+    il::ansi_parser_inner_csi_finished_il,
+    locator::{
+        ansi_parser_inner_csi_finished_decefr, ansi_parser_inner_csi_finished_decelr,
+        ansi_parser_inner_csi_finished_decrqlp, ansi_parser_inner_csi_finished_decsle,
+    },
+    rectangle::{
+        ansi_parser_inner_csi_finished_deccara, ansi_parser_inner_csi_finished_deccra,
+        ansi_parser_inner_csi_finished_decera, ansi_parser_inner_csi_finished_decfra,
+        ansi_parser_inner_csi_finished_decrara, ansi_parser_inner_csi_finished_decrqcra,
+        ansi_parser_inner_csi_finished_decsace, ansi_parser_inner_csi_finished_decsera,
+        ansi_parser_inner_csi_finished_xtchecksum,
+    },
+    rep::ansi_parser_inner_csi_finished_rep,
+    scorc::ansi_parser_inner_csi_finished_scorc,
+    sd::ansi_parser_inner_csi_finished_sd,
+    selective_erase::{
+        ansi_parser_inner_csi_finished_decsca, ansi_parser_inner_csi_finished_decsed,
+        ansi_parser_inner_csi_finished_decsel,
+    },
+    sgr::ansi_parser_inner_csi_finished_sgr,
+    sgr_stack::{
+        ansi_parser_inner_csi_finished_xtpopsgr, ansi_parser_inner_csi_finished_xtpushsgr,
+        ansi_parser_inner_csi_finished_xtreportsgr,
+    },
+    status_line::{ansi_parser_inner_csi_finished_decsasd, ansi_parser_inner_csi_finished_decssdt},
+    su::ansi_parser_inner_csi_finished_su,
+    tbc::ansi_parser_inner_csi_finished_tbc,
+    vpa::ansi_parser_inner_csi_finished_vpa,
+    xtversion::ansi_parser_inner_csi_finished_xtversion,
 };
 use crate::ansi_components::tracer::{SequenceTracer, escape_sequence_for_log};
 use crate::{ansi::ParserOutcome, ansi_components::tracer::SequenceTraceable};
@@ -261,6 +301,92 @@ impl AnsiCsiParser {
         let push_result = self.push(b);
 
         match self.state {
+            // VT420 rectangular area operations and checksums, DECRQPSR, the
+            // status line selections, DECIC / DECDC, the DEC locator, DECSCA,
+            // the selective erases and the xterm SGR and palette stacks share
+            // final bytes with unrelated sequences and are told apart by their
+            // intermediate or `?` prefix, so they are matched first.
+            AnsiCsiParserState::Finished(b'v') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_deccra(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'x') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decfra(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'z') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decera(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'{') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decsera(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'r') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_deccara(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b't') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decrara(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'w') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decrqpsr(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'~') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decssdt(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'}') if self.intermediates == b"$" => {
+                ansi_parser_inner_csi_finished_decsasd(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'}') if self.intermediates == b"'" => {
+                ansi_parser_inner_csi_finished_decic(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'~') if self.intermediates == b"'" => {
+                ansi_parser_inner_csi_finished_decdc(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'z') if self.intermediates == b"'" => {
+                ansi_parser_inner_csi_finished_decelr(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'{') if self.intermediates == b"'" => {
+                ansi_parser_inner_csi_finished_decsle(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'w') if self.intermediates == b"'" => {
+                ansi_parser_inner_csi_finished_decefr(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'|') if self.intermediates == b"'" => {
+                ansi_parser_inner_csi_finished_decrqlp(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'x') if self.intermediates == b"*" => {
+                ansi_parser_inner_csi_finished_decsace(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'y') if self.intermediates == b"*" => {
+                ansi_parser_inner_csi_finished_decrqcra(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'y') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtchecksum(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'q') if self.intermediates == b"\"" => {
+                ansi_parser_inner_csi_finished_decsca(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'{') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtpushsgr(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'}') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtpopsgr(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'|') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtreportsgr(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'P') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtpushcolors(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'Q') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtpopcolors(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'R') if self.intermediates == b"#" => {
+                ansi_parser_inner_csi_finished_xtreportcolors(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'J') if self.params.first() == Some(&b'?') => {
+                ansi_parser_inner_csi_finished_decsed(&self.params, output)
+            }
+            AnsiCsiParserState::Finished(b'K') if self.params.first() == Some(&b'?') => {
+                ansi_parser_inner_csi_finished_decsel(&self.params, output)
+            }
             AnsiCsiParserState::Finished(b'A') => {
                 ansi_parser_inner_csi_finished_cuu(&self.params, output)
             }
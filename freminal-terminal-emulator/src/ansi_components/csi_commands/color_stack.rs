@@ -0,0 +1,120 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! xterm's palette stack (XTPUSHCOLORS, XTPOPCOLORS) and XTREPORTCOLORS.
+
+use crate::ansi::{ParserOutcome, parse_param_as};
+use crate::error::{AnsiParseError, ParserFailures};
+use freminal_common::buffer_states::terminal_output::TerminalOutput;
+
+/// The slot a push or pop names: `None` for the default `0`, which pushes
+/// onto or pops off the top of the stack.
+fn slot(params: &[u8]) -> Result<Option<usize>, AnsiParseError> {
+    parse_param_as::<usize>(params).map(|ps| ps.filter(|&ps| ps != 0))
+}
+
+/// XTPUSHCOLORS — Push the palette (`CSI Ps # P`)
+///
+/// Saves the 256-color palette and the dynamic foreground, background and
+/// cursor colors.  `Ps` = 0 (the default) pushes them onto the stack; 1 to
+/// 10 store them in that slot without pushing.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_xtpushcolors(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(slot) = slot(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledXTPUSHCOLORSCommand(
+            String::from_utf8_lossy(params).to_string(),
+        ));
+    };
+
+    output.push(TerminalOutput::PushColors(slot));
+    ParserOutcome::Finished
+}
+
+/// XTPOPCOLORS — Pop the palette (`CSI Ps # Q`)
+///
+/// Restores the colors saved by XTPUSHCOLORS.  `Ps` = 0 (the default) pops
+/// them off the stack; 1 to 10 restore that slot without popping.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_xtpopcolors(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(slot) = slot(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledXTPOPCOLORSCommand(
+            String::from_utf8_lossy(params).to_string(),
+        ));
+    };
+
+    output.push(TerminalOutput::PopColors(slot));
+    ParserOutcome::Finished
+}
+
+/// XTREPORTCOLORS — Report the palette stack (`CSI # R`)
+///
+/// Answers `CSI Ps ; Ps # Q` with the stack depth and the number of saved
+/// palettes.  Parameters are ignored.
+pub fn ansi_parser_inner_csi_finished_xtreportcolors(
+    _params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    output.push(TerminalOutput::ReportColors);
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_defaults_to_the_top_of_the_stack() {
+        for params in [&b""[..], b"0"] {
+            let mut output = Vec::new();
+            let result = ansi_parser_inner_csi_finished_xtpushcolors(params, &mut output);
+            assert_eq!(result, ParserOutcome::Finished);
+            assert_eq!(output, [TerminalOutput::PushColors(None)]);
+        }
+    }
+
+    #[test]
+    fn push_and_pop_name_a_slot() {
+        let mut output = Vec::new();
+        ansi_parser_inner_csi_finished_xtpushcolors(b"3", &mut output);
+        ansi_parser_inner_csi_finished_xtpopcolors(b"3", &mut output);
+        ansi_parser_inner_csi_finished_xtpopcolors(b"", &mut output);
+        assert_eq!(
+            output,
+            [
+                TerminalOutput::PushColors(Some(3)),
+                TerminalOutput::PopColors(Some(3)),
+                TerminalOutput::PopColors(None),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_params() {
+        let mut output = Vec::new();
+        let result = ansi_parser_inner_csi_finished_xtpushcolors(b"x", &mut output);
+        assert!(matches!(result, ParserOutcome::InvalidParserFailure(_)));
+        let result = ansi_parser_inner_csi_finished_xtpopcolors(b"1;x", &mut output);
+        assert!(matches!(result, ParserOutcome::InvalidParserFailure(_)));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn report() {
+        let mut output = Vec::new();
+        let result = ansi_parser_inner_csi_finished_xtreportcolors(b"", &mut output);
+        assert_eq!(result, ParserOutcome::Finished);
+        assert_eq!(output, [TerminalOutput::ReportColors]);
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The VT420 column editing functions, DECIC and DECDC.
+
+use crate::ansi::{ParserOutcome, parse_param_as};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::terminal_output::TerminalOutput;
+
+/// The column count of DECIC / DECDC: 0 or missing means 1.
+fn column_count(params: &[u8]) -> Option<usize> {
+    parse_param_as::<usize>(params)
+        .ok()
+        .map(|pn| pn.unwrap_or(1).max(1))
+}
+
+/// DECIC — Insert Column (`CSI Pn ' }`)
+///
+/// Insert Pn blank columns at the cursor column, within the scrolling region
+/// and left/right margins (default = 1).
+///
+/// # Errors
+/// Returns an error outcome if the param is not a number.
+pub fn ansi_parser_inner_csi_finished_decic(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Some(count) = column_count(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECICCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::InsertColumns(count));
+    ParserOutcome::Finished
+}
+
+/// DECDC — Delete Column (`CSI Pn ' ~`)
+///
+/// Delete Pn columns at the cursor column, within the scrolling region and
+/// left/right margins (default = 1).
+///
+/// # Errors
+/// Returns an error outcome if the param is not a number.
+pub fn ansi_parser_inner_csi_finished_decdc(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Some(count) = column_count(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECDCCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::DeleteColumns(count));
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_defaults_to_one() {
+        let mut out = Vec::new();
+        for params in [&b""[..], b"0", b"3"] {
+            ansi_parser_inner_csi_finished_decic(params, &mut out);
+            ansi_parser_inner_csi_finished_decdc(params, &mut out);
+        }
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::InsertColumns(1),
+                TerminalOutput::DeleteColumns(1),
+                TerminalOutput::InsertColumns(1),
+                TerminalOutput::DeleteColumns(1),
+                TerminalOutput::InsertColumns(3),
+                TerminalOutput::DeleteColumns(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_params_are_rejected() {
+        let mut out = Vec::new();
+        let outcome = ansi_parser_inner_csi_finished_decic(b"x", &mut out);
+        assert!(matches!(outcome, ParserOutcome::InvalidParserFailure(_)));
+        let outcome = ansi_parser_inner_csi_finished_decdc(b"x", &mut out);
+        assert!(matches!(outcome, ParserOutcome::InvalidParserFailure(_)));
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+use crate::ansi::{ParserOutcome, parse_param_as};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::{
+    presentation_state::PresentationStateReport, terminal_output::TerminalOutput,
+};
+
+/// DECRQPSR — Request Presentation State Report (`CSI Ps $ w`)
+///
+/// - Ps = 1 → cursor information report (DECCIR)
+/// - Ps = 2 → tab stop report (DECTABSR)
+///
+/// # Errors
+/// Returns an error outcome if the param is not 1 or 2.
+pub fn ansi_parser_inner_csi_finished_decrqpsr(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Some(report) = parse_param_as::<usize>(params)
+        .ok()
+        .flatten()
+        .and_then(PresentationStateReport::from_ps)
+    else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECRQPSRCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::RequestPresentationState(report));
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrqpsr_selects_report() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decrqpsr(b"1", &mut out);
+        ansi_parser_inner_csi_finished_decrqpsr(b"2", &mut out);
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::RequestPresentationState(
+                    PresentationStateReport::CursorInformation
+                ),
+                TerminalOutput::RequestPresentationState(PresentationStateReport::TabStops),
+            ]
+        );
+    }
+
+    #[test]
+    fn decrqpsr_rejects_other_reports() {
+        for params in [&b""[..], b"0", b"3", b"x"] {
+            let mut out = Vec::new();
+            let outcome = ansi_parser_inner_csi_finished_decrqpsr(params, &mut out);
+            assert!(
+                matches!(outcome, ParserOutcome::InvalidParserFailure(_)),
+                "{params:?}"
+            );
+            assert!(out.is_empty());
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The DEC locator sequences: DECELR, DECSLE, DECEFR and DECRQLP.
+
+use crate::ansi::{ParserOutcome, split_params_into_semicolon_delimited_usize};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::{
+    locator::{LocatorFilter, LocatorReporting, LocatorUnits},
+    terminal_output::TerminalOutput,
+};
+
+use super::util::param_or;
+
+/// DECELR — Enable Locator Reporting (`CSI Ps ; Pu ' z`)
+///
+/// - Ps = 0 (default) → off, 1 → on, 2 → one report only
+/// - Pu = 0 (default) or 2 → character cells, 1 → pixels
+///
+/// # Errors
+/// Returns an error outcome if either param is out of range.
+pub fn ansi_parser_inner_csi_finished_decelr(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let parsed = split_params_into_semicolon_delimited_usize(params)
+        .ok()
+        .and_then(|params| {
+            Some((
+                LocatorReporting::from_param(param_or(&params, 0, 0))?,
+                LocatorUnits::from_param(param_or(&params, 1, 0))?,
+            ))
+        });
+    let Some((reporting, units)) = parsed else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECELRCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::EnableLocatorReporting { reporting, units });
+    ParserOutcome::Finished
+}
+
+/// DECSLE — Select Locator Events (`CSI Pm ' {`)
+///
+/// - Pm = 0 (default) → explicit requests only
+/// - Pm = 1 / 2 → report button down / stop
+/// - Pm = 3 / 4 → report button up / stop
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decsle(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSLECommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    let selected = params.into_iter().map(|ps| ps.unwrap_or(0)).collect();
+    output.push(TerminalOutput::SelectLocatorEvents(selected));
+    ParserOutcome::Finished
+}
+
+/// DECEFR — Enable Filter Rectangle (`CSI Pt ; Pl ; Pb ; Pr ' w`)
+///
+/// Report once the pointer leaves the rectangle.  A missing or zero edge is
+/// the pointer's current row or column.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decefr(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECEFRCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    let edge = |idx| Some(param_or(&params, idx, 0)).filter(|&n| n > 0);
+    output.push(TerminalOutput::SetLocatorFilter(LocatorFilter {
+        top: edge(0),
+        left: edge(1),
+        bottom: edge(2),
+        right: edge(3),
+    }));
+    ParserOutcome::Finished
+}
+
+/// DECRQLP — Request Locator Position (`CSI Ps ' |`)
+///
+/// Ps is ignored.
+pub fn ansi_parser_inner_csi_finished_decrqlp(
+    _params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    output.push(TerminalOutput::RequestLocatorPosition);
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decelr_params() {
+        let mut out = Vec::new();
+        for params in [&b""[..], b"1", b"2;1", b"1;2"] {
+            ansi_parser_inner_csi_finished_decelr(params, &mut out);
+        }
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::EnableLocatorReporting {
+                    reporting: LocatorReporting::Off,
+                    units: LocatorUnits::Cells,
+                },
+                TerminalOutput::EnableLocatorReporting {
+                    reporting: LocatorReporting::On,
+                    units: LocatorUnits::Cells,
+                },
+                TerminalOutput::EnableLocatorReporting {
+                    reporting: LocatorReporting::OneShot,
+                    units: LocatorUnits::Pixels,
+                },
+                TerminalOutput::EnableLocatorReporting {
+                    reporting: LocatorReporting::On,
+                    units: LocatorUnits::Cells,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decelr_rejects_out_of_range() {
+        let mut out = Vec::new();
+        let outcome = ansi_parser_inner_csi_finished_decelr(b"3", &mut out);
+        assert!(matches!(outcome, ParserOutcome::InvalidParserFailure(_)));
+        let outcome = ansi_parser_inner_csi_finished_decelr(b"1;3", &mut out);
+        assert!(matches!(outcome, ParserOutcome::InvalidParserFailure(_)));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn decsle_keeps_the_selection_order() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decsle(b"", &mut out);
+        ansi_parser_inner_csi_finished_decsle(b"1;;4", &mut out);
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::SelectLocatorEvents(vec![0]),
+                TerminalOutput::SelectLocatorEvents(vec![1, 0, 4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decefr_zero_edges_are_the_pointer() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decefr(b"2;0;9", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::SetLocatorFilter(LocatorFilter {
+                top: Some(2),
+                left: None,
+                bottom: Some(9),
+                right: None,
+            })]
+        );
+    }
+}
@@ -24,7 +24,9 @@
 //! | `H` / `f`  | —            | CUP / HVP   | `cup`         |
 //! | `I`        | —            | CHT         | `cht`         |
 //! | `J`        | —            | ED          | `ed`          |
+//! | `J`        | `?`          | DECSED      | `selective_erase` |
 //! | `K`        | —            | EL          | `el`          |
+//! | `K`        | `?`          | DECSEL      | `selective_erase` |
 //! | `L`        | —            | IL          | `il`          |
 //! | `M`        | —            | DL          | `dl`          |
 //! | `P`        | —            | DCH         | `dch`         |
@@ -48,13 +50,40 @@
 //! | `t`        | —            | XTWINOPS    | *(csi.rs)*    |
 //! | `~`        | —            | REP / misc  | `rep`         |
 //! | `q`        | ` ` (SP)     | DECSCUSR    | `decscusr`    |
+//! | `q`        | `"`          | DECSCA      | `selective_erase` |
 //! | `p`        | `$`          | DECSLPP     | `decslpp`     |
 //! | `p`        | `$`+`?`      | DECRQM      | `decrqm`      |
+//! | `w`        | `$`          | DECRQPSR    | `decrqpsr`    |
+//! | `~`        | `$`          | DECSSDT     | `status_line` |
+//! | `}`        | `$`          | DECSASD     | `status_line` |
+//! | `}`        | `'`          | DECIC       | `columns`     |
+//! | `~`        | `'`          | DECDC       | `columns`     |
+//! | `z`        | `'`          | DECELR      | `locator`     |
+//! | `{`        | `'`          | DECSLE      | `locator`     |
+//! | `w`        | `'`          | DECEFR      | `locator`     |
+//! | `\|`       | `'`          | DECRQLP     | `locator`     |
+//! | `v`        | `$`          | DECCRA      | `rectangle`   |
+//! | `x`        | `$`          | DECFRA      | `rectangle`   |
+//! | `z`        | `$`          | DECERA      | `rectangle`   |
+//! | `{`        | `$`          | DECSERA     | `rectangle`   |
+//! | `r`        | `$`          | DECCARA     | `rectangle`   |
+//! | `t`        | `$`          | DECRARA     | `rectangle`   |
+//! | `x`        | `*`          | DECSACE     | `rectangle`   |
+//! | `y`        | `*`          | DECRQCRA    | `rectangle`   |
+//! | `y`        | `#`          | XTCHECKSUM  | `rectangle`   |
+//! | `{`        | `#`          | XTPUSHSGR   | `sgr_stack`   |
+//! | `}`        | `#`          | XTPOPSGR    | `sgr_stack`   |
+//! | `\|`       | `#`          | XTREPORTSGR | `sgr_stack`   |
+//! | `P`        | `#`          | XTPUSHCOLORS | `color_stack` |
+//! | `Q`        | `#`          | XTPOPCOLORS | `color_stack` |
+//! | `R`        | `#`          | XTREPORTCOLORS | `color_stack` |
 
 pub mod cbt;
 pub mod cha;
 pub mod cht;
 pub mod cnl;
+pub mod color_stack;
+pub mod columns;
 pub mod cpl;
 pub mod cub;
 pub mod cud;
@@ -64,6 +93,7 @@ pub mod cuu;
 pub mod da;
 pub mod dch;
 pub mod decrqm;
+pub mod decrqpsr;
 pub mod decscusr;
 pub mod decslpp;
 pub mod decslrm;
@@ -75,11 +105,16 @@ pub mod ed;
 pub mod el;
 pub mod ich;
 pub mod il;
+pub mod locator;
 pub mod modify_other_keys;
+pub mod rectangle;
 pub mod rep;
 pub mod scorc;
 pub mod sd;
+pub mod selective_erase;
 pub mod sgr;
+pub mod sgr_stack;
+pub mod status_line;
 pub mod su;
 pub mod tbc;
 pub mod util;
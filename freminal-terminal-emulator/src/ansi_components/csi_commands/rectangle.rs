@@ -0,0 +1,485 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! VT420 rectangular area operations, DECSACE, and the DECRQCRA checksum
+//! report with its XTCHECKSUM options.
+//!
+//! All of these take a rectangle as `Pt ; Pl ; Pb ; Pr` (1-based,
+//! inclusive).  Omitted or zero `Pt`/`Pl` mean 1; omitted or zero `Pb`/`Pr`
+//! mean the last row / column, which is passed on as `usize::MAX` for the
+//! buffer to resolve.  Page parameters are accepted and ignored: Freminal
+//! has a single page.
+
+use crate::ansi::{ParserOutcome, split_params_into_semicolon_delimited_usize};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::{
+    rectangle::{
+        AttributeChange, AttributeChangeExtent, ChecksumExtension, Rectangle,
+        reverse_attributes_from_sgr_params,
+    },
+    terminal_output::TerminalOutput,
+};
+
+use super::util::param_or;
+
+/// Build a [`Rectangle`] from the four parameters starting at `idx`.
+pub(super) fn rectangle_at(params: &[Option<usize>], idx: usize) -> Rectangle {
+    let edge = |offset: usize, default: usize| match param_or(params, idx + offset, 0) {
+        0 => default,
+        n => n,
+    };
+
+    Rectangle {
+        top: edge(0, 1),
+        left: edge(1, 1),
+        bottom: edge(2, usize::MAX),
+        right: edge(3, usize::MAX),
+    }
+}
+
+/// DECCRA — Copy Rectangular Area (`CSI Pts ; Pls ; Pbs ; Prs ; Pps ; Ptd ; Pld ; Ppd $ v`)
+///
+/// Copies the source rectangle so its top-left corner lands at
+/// (`Ptd`, `Pld`).  `Pps` and `Ppd` are page numbers and are ignored.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_deccra(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECCRACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::CopyRectangle {
+        source: rectangle_at(&params, 0),
+        dest_top: param_or(&params, 5, 1).max(1),
+        dest_left: param_or(&params, 6, 1).max(1),
+    });
+
+    ParserOutcome::Finished
+}
+
+/// DECFRA — Fill Rectangular Area (`CSI Pch ; Pt ; Pl ; Pb ; Pr $ x`)
+///
+/// `Pch` is the decimal code of the fill character.  Only the printable
+/// ranges 32–126 and 160–255 are accepted; anything else makes the whole
+/// sequence a no-op, as on the VT420.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decfra(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECFRACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    let ch = match u8::try_from(param_or(&params, 0, 0)) {
+        Ok(code @ (32..=126 | 160..=255)) => char::from(code),
+        _ => return ParserOutcome::Finished,
+    };
+
+    output.push(TerminalOutput::FillRectangle {
+        ch,
+        area: rectangle_at(&params, 1),
+    });
+
+    ParserOutcome::Finished
+}
+
+/// DECERA — Erase Rectangular Area (`CSI Pt ; Pl ; Pb ; Pr $ z`)
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decera(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECERACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::EraseRectangle(rectangle_at(&params, 0)));
+
+    ParserOutcome::Finished
+}
+
+/// DECSERA — Selective Erase Rectangular Area (`CSI Pt ; Pl ; Pb ; Pr $ {`)
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decsera(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSERACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::SelectiveEraseRectangle(rectangle_at(
+        &params, 0,
+    )));
+
+    ParserOutcome::Finished
+}
+
+/// The SGR values after the rectangle, with omitted values read as 0.
+fn attribute_params(params: &[Option<usize>]) -> Vec<usize> {
+    params
+        .iter()
+        .skip(4)
+        .map(|param| param.unwrap_or(0))
+        .collect()
+}
+
+/// DECCARA — Change Attributes in Rectangular Area
+/// (`CSI Pt ; Pl ; Pb ; Pr ; Ps... $ r`)
+///
+/// `Ps` are SGR values: 0, 1, 4, 5, 7 and their 22–27 "off" forms.  Other
+/// values (colors included) are ignored.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_deccara(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECCARACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::ChangeRectangleAttributes {
+        area: rectangle_at(&params, 0),
+        change: AttributeChange::from_sgr_params(&attribute_params(&params)),
+    });
+
+    ParserOutcome::Finished
+}
+
+/// DECRARA — Reverse Attributes in Rectangular Area
+/// (`CSI Pt ; Pl ; Pb ; Pr ; Ps... $ t`)
+///
+/// `Ps` selects the attributes to reverse: 0 (all), 1, 4, 5 and 7.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decrara(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECRARACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::ReverseRectangleAttributes {
+        area: rectangle_at(&params, 0),
+        toggle: reverse_attributes_from_sgr_params(&attribute_params(&params)),
+    });
+
+    ParserOutcome::Finished
+}
+
+/// DECSACE — Select Attribute Change Extent (`CSI Ps * x`)
+///
+/// Ps = 0 or 1 selects stream mode (the default), 2 selects rectangle mode.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed or Ps is out of
+/// range.
+pub fn ansi_parser_inner_csi_finished_decsace(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(parsed) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSACECommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    let extent = match param_or(&parsed, 0, 0) {
+        0 | 1 => AttributeChangeExtent::Stream,
+        2 => AttributeChangeExtent::Rectangle,
+        _ => {
+            return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSACECommand(
+                format!("{parsed:?}"),
+            ));
+        }
+    };
+
+    output.push(TerminalOutput::SelectAttributeChangeExtent(extent));
+
+    ParserOutcome::Finished
+}
+
+/// DECRQCRA — Request Checksum of Rectangular Area
+/// (`CSI Pi ; Pg ; Pt ; Pl ; Pb ; Pr * y`)
+///
+/// `Pi` is echoed back in the `DCS Pi ! ~ xxxx ST` reply.  `Pg` is the page
+/// and is ignored.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_decrqcra(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECRQCRACommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::RequestRectangleChecksum {
+        id: param_or(&params, 0, 0),
+        area: rectangle_at(&params, 2),
+    });
+
+    ParserOutcome::Finished
+}
+
+/// XTCHECKSUM — Select checksum extension (`CSI Ps # y`)
+///
+/// `Ps` is a bitmask of [`ChecksumExtension`] options; omitted means 0.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_xtchecksum(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledXTCHECKSUMCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::SelectChecksumExtension(
+        ChecksumExtension::from_param(param_or(&params, 0, 0)),
+    ));
+
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use freminal_common::buffer_states::rectangle::RectAttributes;
+
+    fn rect(top: usize, left: usize, bottom: usize, right: usize) -> Rectangle {
+        Rectangle {
+            top,
+            left,
+            bottom,
+            right,
+        }
+    }
+
+    #[test]
+    fn deccra_parses_source_and_destination() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_deccra(b"2;3;4;5;1;10;20;1", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::CopyRectangle {
+                source: rect(2, 3, 4, 5),
+                dest_top: 10,
+                dest_left: 20,
+            }]
+        );
+    }
+
+    #[test]
+    fn deccra_defaults_to_whole_screen_at_origin() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_deccra(b"", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::CopyRectangle {
+                source: Rectangle::default(),
+                dest_top: 1,
+                dest_left: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn decfra_parses_character_and_area() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decfra(b"88;1;2;3;4", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::FillRectangle {
+                ch: 'X',
+                area: rect(1, 2, 3, 4),
+            }]
+        );
+
+        out.clear();
+        ansi_parser_inner_csi_finished_decfra(b"233", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::FillRectangle {
+                ch: 'é',
+                area: Rectangle::default(),
+            }]
+        );
+    }
+
+    #[test]
+    fn decfra_ignores_unprintable_characters() {
+        for pch in [&b"0"[..], b"31", b"127", b"159", b"256", b""] {
+            let mut out = Vec::new();
+            let result = ansi_parser_inner_csi_finished_decfra(pch, &mut out);
+            assert_eq!(result, ParserOutcome::Finished);
+            assert!(out.is_empty(), "Pch {pch:?} should be ignored");
+        }
+    }
+
+    #[test]
+    fn decera_zero_edges_mean_defaults() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decera(b"0;0;0;0", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::EraseRectangle(Rectangle::default())]
+        );
+    }
+
+    #[test]
+    fn decsera_parses_area() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decsera(b"5;6", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::SelectiveEraseRectangle(rect(
+                5,
+                6,
+                usize::MAX,
+                usize::MAX
+            ))]
+        );
+    }
+
+    #[test]
+    fn deccara_empty_attribute_is_zero() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_deccara(b"1;1;2;2;1;;4", &mut out);
+        let set = RectAttributes::UNDERLINE;
+        let mut clear = RectAttributes::ALL;
+        clear.remove(set);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::ChangeRectangleAttributes {
+                area: rect(1, 1, 2, 2),
+                change: AttributeChange { set, clear },
+            }]
+        );
+    }
+
+    #[test]
+    fn decrara_without_attributes_reverses_all() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decrara(b"1;1;2;2", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::ReverseRectangleAttributes {
+                area: rect(1, 1, 2, 2),
+                toggle: RectAttributes::ALL,
+            }]
+        );
+    }
+
+    #[test]
+    fn decsace_selects_extent() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decsace(b"2", &mut out);
+        ansi_parser_inner_csi_finished_decsace(b"1", &mut out);
+        ansi_parser_inner_csi_finished_decsace(b"", &mut out);
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::SelectAttributeChangeExtent(AttributeChangeExtent::Rectangle),
+                TerminalOutput::SelectAttributeChangeExtent(AttributeChangeExtent::Stream),
+                TerminalOutput::SelectAttributeChangeExtent(AttributeChangeExtent::Stream),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_params_are_rejected() {
+        let mut out = Vec::new();
+        assert!(matches!(
+            ansi_parser_inner_csi_finished_decera(b"x", &mut out),
+            ParserOutcome::InvalidParserFailure(_)
+        ));
+        assert!(matches!(
+            ansi_parser_inner_csi_finished_decsace(b"3", &mut out),
+            ParserOutcome::InvalidParserFailure(_)
+        ));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn decrqcra_parses_id_and_area() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decrqcra(b"7;1;2;3;4;5", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::RequestRectangleChecksum {
+                id: 7,
+                area: rect(2, 3, 4, 5),
+            }]
+        );
+    }
+
+    #[test]
+    fn decrqcra_defaults_to_whole_screen() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_decrqcra(b"", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::RequestRectangleChecksum {
+                id: 0,
+                area: Rectangle::default(),
+            }]
+        );
+    }
+
+    #[test]
+    fn xtchecksum_parses_bitmask() {
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_xtchecksum(b"3", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::SelectChecksumExtension(
+                ChecksumExtension::from_param(3)
+            )]
+        );
+
+        let mut out = Vec::new();
+        ansi_parser_inner_csi_finished_xtchecksum(b"", &mut out);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::SelectChecksumExtension(
+                ChecksumExtension::default()
+            )]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DECSCA character protection and the selective erases that honour it.
+//!
+//! DECSED and DECSEL blank only the characters DECSCA left unprotected and
+//! keep every cell's attributes.  Plain ED and EL erase everything.
+
+use crate::ansi::{ParserOutcome, parse_param_as};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::terminal_output::TerminalOutput;
+
+/// DECSCA — Select Character Protection Attribute (`CSI Ps " q`)
+///
+/// - Ps = 0 or 2 → following characters can be erased by DECSED/DECSEL (default)
+/// - Ps = 1 → following characters are protected from DECSED/DECSEL
+///
+/// # Errors
+/// Returns an error outcome if the param is not 0, 1 or 2.
+pub fn ansi_parser_inner_csi_finished_decsca(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let protected = match parse_param_as::<usize>(params) {
+        Ok(None | Some(0 | 2)) => false,
+        Ok(Some(1)) => true,
+        _ => {
+            return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSCACommand(
+                format!("{params:?}"),
+            ));
+        }
+    };
+
+    output.push(TerminalOutput::SetCharacterProtection(protected));
+    ParserOutcome::Finished
+}
+
+/// DECSED — Selective Erase in Display (`CSI ? Ps J`)
+///
+/// Erase the unprotected characters in part of the display:
+/// - Ps = 0 → From cursor to end of display (default)
+/// - Ps = 1 → From start of display to cursor
+/// - Ps = 2 → Entire display
+///
+/// # Errors
+/// Returns an error outcome if the param is not 0, 1 or 2.
+pub fn ansi_parser_inner_csi_finished_decsed(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let params = params.strip_prefix(b"?").unwrap_or(params);
+    let ret = match parse_param_as::<usize>(params) {
+        Ok(None | Some(0)) => TerminalOutput::SelectiveClearDisplayForwards,
+        Ok(Some(1)) => TerminalOutput::SelectiveClearDisplayBackwards,
+        Ok(Some(2)) => TerminalOutput::SelectiveClearDisplay,
+        _ => {
+            return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSEDCommand(
+                format!("{params:?}"),
+            ));
+        }
+    };
+
+    output.push(ret);
+    ParserOutcome::Finished
+}
+
+/// DECSEL — Selective Erase in Line (`CSI ? Ps K`)
+///
+/// Erase the unprotected characters in part of the current line:
+/// - Ps = 0 → From cursor to end of line (default)
+/// - Ps = 1 → From start of line to cursor
+/// - Ps = 2 → Entire line
+///
+/// # Errors
+/// Returns an error outcome if the param is not 0, 1 or 2.
+pub fn ansi_parser_inner_csi_finished_decsel(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let params = params.strip_prefix(b"?").unwrap_or(params);
+    let ret = match parse_param_as::<usize>(params) {
+        Ok(None | Some(0)) => TerminalOutput::SelectiveClearLineForwards,
+        Ok(Some(1)) => TerminalOutput::SelectiveClearLineBackwards,
+        Ok(Some(2)) => TerminalOutput::SelectiveClearLine,
+        _ => {
+            return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSELCommand(
+                format!("{params:?}"),
+            ));
+        }
+    };
+
+    output.push(ret);
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(
+        f: fn(&[u8], &mut Vec<TerminalOutput>) -> ParserOutcome,
+        params: &[u8],
+    ) -> (ParserOutcome, Vec<TerminalOutput>) {
+        let mut output = Vec::new();
+        let result = f(params, &mut output);
+        (result, output)
+    }
+
+    #[test]
+    fn decsca_values() {
+        for (params, protected) in [
+            (&b""[..], false),
+            (b"0", false),
+            (b"1", true),
+            (b"2", false),
+        ] {
+            let (result, output) = parse(ansi_parser_inner_csi_finished_decsca, params);
+            assert_eq!(result, ParserOutcome::Finished);
+            assert_eq!(output, [TerminalOutput::SetCharacterProtection(protected)]);
+        }
+    }
+
+    #[test]
+    fn decsca_unknown_value_is_invalid() {
+        let (result, output) = parse(ansi_parser_inner_csi_finished_decsca, b"3");
+        assert!(matches!(result, ParserOutcome::InvalidParserFailure(_)));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn decsed_modes() {
+        for (params, expected) in [
+            (&b"?"[..], TerminalOutput::SelectiveClearDisplayForwards),
+            (b"?0", TerminalOutput::SelectiveClearDisplayForwards),
+            (b"?1", TerminalOutput::SelectiveClearDisplayBackwards),
+            (b"?2", TerminalOutput::SelectiveClearDisplay),
+        ] {
+            let (result, output) = parse(ansi_parser_inner_csi_finished_decsed, params);
+            assert_eq!(result, ParserOutcome::Finished);
+            assert_eq!(output, [expected]);
+        }
+    }
+
+    #[test]
+    fn decsed_does_not_clear_scrollback() {
+        let (result, output) = parse(ansi_parser_inner_csi_finished_decsed, b"?3");
+        assert!(matches!(result, ParserOutcome::InvalidParserFailure(_)));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn decsel_modes() {
+        for (params, expected) in [
+            (&b"?"[..], TerminalOutput::SelectiveClearLineForwards),
+            (b"?0", TerminalOutput::SelectiveClearLineForwards),
+            (b"?1", TerminalOutput::SelectiveClearLineBackwards),
+            (b"?2", TerminalOutput::SelectiveClearLine),
+        ] {
+            let (result, output) = parse(ansi_parser_inner_csi_finished_decsel, params);
+            assert_eq!(result, ParserOutcome::Finished);
+            assert_eq!(output, [expected]);
+        }
+    }
+
+    #[test]
+    fn decsel_non_numeric_is_invalid() {
+        let (result, _) = parse(ansi_parser_inner_csi_finished_decsel, b"?x");
+        assert!(matches!(result, ParserOutcome::InvalidParserFailure(_)));
+    }
+}
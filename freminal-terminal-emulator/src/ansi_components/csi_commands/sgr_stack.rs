@@ -0,0 +1,127 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! xterm's SGR stack (XTPUSHSGR, XTPOPSGR) and XTREPORTSGR.
+
+use crate::ansi::{ParserOutcome, split_params_into_semicolon_delimited_usize};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::{
+    sgr_stack::SgrStackAttributes, terminal_output::TerminalOutput,
+};
+
+use super::rectangle::rectangle_at;
+
+/// XTPUSHSGR — Push SGR attributes (`CSI Pm # {`)
+///
+/// Saves the current rendition on a stack.  `Pm` picks which attributes
+/// XTPOPSGR will restore (see [`SgrStackAttributes::from_params`]); with no
+/// parameters it restores all of them.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_xtpushsgr(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledXTPUSHSGRCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    let selected: Vec<usize> = params.into_iter().flatten().collect();
+    output.push(TerminalOutput::PushSgr(SgrStackAttributes::from_params(
+        &selected,
+    )));
+
+    ParserOutcome::Finished
+}
+
+/// XTPOPSGR — Pop SGR attributes (`CSI # }`)
+///
+/// Restores the attributes saved by the matching XTPUSHSGR.  Parameters
+/// are ignored.
+pub fn ansi_parser_inner_csi_finished_xtpopsgr(
+    _params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    output.push(TerminalOutput::PopSgr);
+    ParserOutcome::Finished
+}
+
+/// XTREPORTSGR — Report SGR in a rectangle (`CSI Pt ; Pl ; Pb ; Pr # |`)
+///
+/// Answers with an SGR sequence holding the attributes every cell of the
+/// rectangle has in common.  Coordinates follow the DEC rectangle rules.
+///
+/// # Errors
+/// Returns an error outcome if the params cannot be parsed.
+pub fn ansi_parser_inner_csi_finished_xtreportsgr(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Ok(params) = split_params_into_semicolon_delimited_usize(params) else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledXTREPORTSGRCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::ReportSgr(rectangle_at(&params, 0)));
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use freminal_common::buffer_states::rectangle::Rectangle;
+
+    #[test]
+    fn push_without_params_saves_everything() {
+        let mut output = Vec::new();
+        let result = ansi_parser_inner_csi_finished_xtpushsgr(b"", &mut output);
+        assert_eq!(result, ParserOutcome::Finished);
+        assert_eq!(output, [TerminalOutput::PushSgr(SgrStackAttributes::ALL)]);
+    }
+
+    #[test]
+    fn push_with_selection() {
+        let mut output = Vec::new();
+        ansi_parser_inner_csi_finished_xtpushsgr(b"1;30", &mut output);
+        let mut expected = SgrStackAttributes::BOLD;
+        expected.insert(SgrStackAttributes::FOREGROUND);
+        assert_eq!(output, [TerminalOutput::PushSgr(expected)]);
+    }
+
+    #[test]
+    fn push_invalid_params() {
+        let mut output = Vec::new();
+        let result = ansi_parser_inner_csi_finished_xtpushsgr(b"x", &mut output);
+        assert!(matches!(result, ParserOutcome::InvalidParserFailure(_)));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn pop() {
+        let mut output = Vec::new();
+        let result = ansi_parser_inner_csi_finished_xtpopsgr(b"", &mut output);
+        assert_eq!(result, ParserOutcome::Finished);
+        assert_eq!(output, [TerminalOutput::PopSgr]);
+    }
+
+    #[test]
+    fn report_takes_a_rectangle() {
+        let mut output = Vec::new();
+        ansi_parser_inner_csi_finished_xtreportsgr(b"2;3;4", &mut output);
+        assert_eq!(
+            output,
+            [TerminalOutput::ReportSgr(Rectangle {
+                top: 2,
+                left: 3,
+                bottom: 4,
+                right: usize::MAX,
+            })]
+        );
+    }
+}
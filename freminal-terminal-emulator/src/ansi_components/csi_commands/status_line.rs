@@ -0,0 +1,110 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The DEC status line selections, DECSSDT and DECSASD.
+
+use crate::ansi::{ParserOutcome, parse_param_as};
+use crate::error::ParserFailures;
+use freminal_common::buffer_states::{
+    status_line::{StatusDisplay, StatusLineType},
+    terminal_output::TerminalOutput,
+};
+
+/// DECSSDT — Select Status Display Type (`CSI Ps $ ~`)
+///
+/// - Ps = 0 (default) → no status line
+/// - Ps = 1 → indicator status line
+/// - Ps = 2 → host-writable status line
+///
+/// # Errors
+/// Returns an error outcome if the param is not 0, 1 or 2.
+pub fn ansi_parser_inner_csi_finished_decssdt(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Some(kind) = parse_param_as::<usize>(params)
+        .ok()
+        .map(|ps| ps.unwrap_or(0))
+        .and_then(StatusLineType::from_param)
+    else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSSDTCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::SelectStatusLineType(kind));
+    ParserOutcome::Finished
+}
+
+/// DECSASD — Select Active Status Display (`CSI Ps $ }`)
+///
+/// - Ps = 0 (default) → main display
+/// - Ps = 1 → status line
+///
+/// # Errors
+/// Returns an error outcome if the param is not 0 or 1.
+pub fn ansi_parser_inner_csi_finished_decsasd(
+    params: &[u8],
+    output: &mut Vec<TerminalOutput>,
+) -> ParserOutcome {
+    let Some(display) = parse_param_as::<usize>(params)
+        .ok()
+        .map(|ps| ps.unwrap_or(0))
+        .and_then(StatusDisplay::from_param)
+    else {
+        return ParserOutcome::InvalidParserFailure(ParserFailures::UnhandledDECSASDCommand(
+            format!("{params:?}"),
+        ));
+    };
+
+    output.push(TerminalOutput::SelectActiveStatusDisplay(display));
+    ParserOutcome::Finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decssdt_selects_type() {
+        let mut out = Vec::new();
+        for params in [&b""[..], b"1", b"2"] {
+            ansi_parser_inner_csi_finished_decssdt(params, &mut out);
+        }
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::SelectStatusLineType(StatusLineType::None),
+                TerminalOutput::SelectStatusLineType(StatusLineType::Indicator),
+                TerminalOutput::SelectStatusLineType(StatusLineType::HostWritable),
+            ]
+        );
+    }
+
+    #[test]
+    fn decsasd_selects_display() {
+        let mut out = Vec::new();
+        for params in [&b"1"[..], b"0"] {
+            ansi_parser_inner_csi_finished_decsasd(params, &mut out);
+        }
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::SelectActiveStatusDisplay(StatusDisplay::StatusLine),
+                TerminalOutput::SelectActiveStatusDisplay(StatusDisplay::Main),
+            ]
+        );
+    }
+
+    #[test]
+    fn out_of_range_params_are_rejected() {
+        let mut out = Vec::new();
+        let outcome = ansi_parser_inner_csi_finished_decssdt(b"3", &mut out);
+        assert!(matches!(outcome, ParserOutcome::InvalidParserFailure(_)));
+        let outcome = ansi_parser_inner_csi_finished_decsasd(b"2", &mut out);
+        assert!(matches!(outcome, ParserOutcome::InvalidParserFailure(_)));
+        assert!(out.is_empty());
+    }
+}
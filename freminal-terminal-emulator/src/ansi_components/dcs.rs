@@ -111,15 +111,33 @@ impl DcsParser {
         self.seq_trace.as_str()
     }
 
+    /// Returns `true` when the accumulated sequence is exactly the tmux
+    /// control-mode handshake (`DCS 1000 p`).
+    #[must_use]
+    pub fn is_tmux_control_handshake(&self) -> bool {
+        self.sequence == b"P1000p"
+    }
+
     /// Push a byte into the DCS parser and return the parser outcome.
     ///
     /// Accumulates bytes until a String Terminator is detected, at which
     /// point it emits `TerminalOutput::DeviceControlString` and returns
     /// `ParserOutcome::Finished`.
+    ///
+    /// The tmux control-mode handshake (`DCS 1000 p`) finishes early with
+    /// `TerminalOutput::TmuxControlModeStart`: the string stays open for the
+    /// whole session, so the caller hands the rest of the stream to
+    /// [`TmuxControlParser`](super::tmux_control::TmuxControlParser).
     pub fn dcs_parser_inner(&mut self, b: u8, output: &mut Vec<TerminalOutput>) -> ParserOutcome {
         self.append_trace(b);
         self.sequence.push(b);
 
+        if self.is_tmux_control_handshake() {
+            self.sequence.clear();
+            output.push(TerminalOutput::TmuxControlModeStart);
+            return ParserOutcome::Finished;
+        }
+
         if self.contains_string_terminator() {
             self.seq_trace.trim_control_tail();
             output.push(TerminalOutput::DeviceControlString(std::mem::take(
@@ -214,6 +232,21 @@ mod tests {
         assert!(!parser.contains_string_terminator());
     }
 
+    #[test]
+    fn tmux_control_handshake_finishes_early() {
+        let mut parser = DcsParser::new();
+        let mut output = Vec::new();
+        for &b in b"100" {
+            let result = parser.dcs_parser_inner(b, &mut output);
+            assert!(matches!(result, ParserOutcome::Continue));
+        }
+        let result = parser.dcs_parser_inner(b'0', &mut output);
+        assert!(matches!(result, ParserOutcome::Continue));
+        let result = parser.dcs_parser_inner(b'p', &mut output);
+        assert!(matches!(result, ParserOutcome::Finished));
+        assert_eq!(output, vec![TerminalOutput::TmuxControlModeStart]);
+    }
+
     #[test]
     fn tmux_passthrough_not_false_terminated_by_doubled_esc() {
         // A tmux passthrough that ends with \x1b\x1b\ should NOT be treated as ST.
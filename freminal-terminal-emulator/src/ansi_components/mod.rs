@@ -14,4 +14,5 @@ pub mod osc_notify;
 pub mod osc_palette;
 pub mod osc_shell_info;
 pub mod standard;
+pub mod tmux_control;
 pub mod tracer;
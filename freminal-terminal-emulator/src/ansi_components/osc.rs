@@ -6,9 +6,10 @@
 use crate::ansi::{ParserOutcome, parse_param_as};
 use crate::ansi_components::tracer::{SequenceTraceable, SequenceTracer};
 use crate::error::AnsiParseError;
-use freminal_common::buffer_states::ftcs::{is_known_ftcs_marker, parse_ftcs_params};
+use freminal_common::buffer_states::ftcs::parse_ftcs_params;
+use freminal_common::buffer_states::ftcs_compat::{parse_osc633, parse_plain_ftcs_params};
 use freminal_common::buffer_states::osc::{
-    AnsiOscInternalType, AnsiOscToken, AnsiOscType, OscTarget, UrlResponse,
+    AnsiOscInternalType, AnsiOscToken, AnsiOscType, DynamicColor, OscTarget, UrlResponse,
 };
 use freminal_common::buffer_states::pointer_shape::PointerShape;
 use freminal_common::buffer_states::terminal_output::TerminalOutput;
@@ -16,7 +17,10 @@ use freminal_common::buffer_states::terminal_output::TerminalOutput;
 use super::osc_clipboard::handle_osc_clipboard;
 use super::osc_iterm2::handle_osc_iterm2;
 use super::osc_notify::{handle_osc_notify_9, handle_osc_notify_99, handle_osc_notify_777};
-use super::osc_palette::{handle_osc_palette_color, handle_osc_reset_palette};
+use super::osc_palette::{
+    handle_osc_palette_color, handle_osc_reset_palette, handle_osc_reset_special_color,
+    handle_osc_special_color,
+};
 use super::osc_shell_info::handle_osc_shell_info;
 
 #[derive(Eq, PartialEq, Debug)]
@@ -193,6 +197,28 @@ impl AnsiOscParser {
     }
 }
 
+/// Parse an OSC 633 (VS Code shell integration) sequence.
+///
+/// Parsed from the raw bytes because the `E` command line and `P` values
+/// are escaped by the shell and decoded by [`parse_osc633`].
+fn handle_osc_vscode(
+    raw_params: &[u8],
+    seq_trace: &SequenceTracer,
+    output: &mut Vec<TerminalOutput>,
+) {
+    // raw_params looks like: b"633;E;ls -la;nonce"
+    let raw = String::from_utf8_lossy(raw_params);
+    let body = raw.split_once(';').map_or("", |(_, body)| body);
+    if let Some(marker) = parse_osc633(body) {
+        output.push(TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(marker)));
+    } else {
+        tracing::warn!(
+            "OSC 633: unrecognised sub-command (ignored); raw sequence: \"{}\"",
+            seq_trace.as_escaped()
+        );
+    }
+}
+
 /// Extract the pointer-shape name from OSC 22 parameters and emit the
 /// corresponding terminal output.
 ///
@@ -267,29 +293,29 @@ fn dispatch_osc_target(
 
             if let Some(marker) = parse_ftcs_params(&ftcs_str_refs) {
                 output.push(TerminalOutput::OscResponse(AnsiOscType::Ftcs(marker)));
-            } else if is_known_ftcs_marker(&ftcs_str_refs) {
-                // Known FTCS marker (A/B/C/D/P) that we recognise but did not
-                // act on — a foreign emitter sent it without the `freminal=1`
-                // tag (e.g. Apple Terminal's `133;A;cl=m;aid=$$`, or
-                // Starship/oh-my-zsh/VTE). Freminal uses its own
-                // `freminal=1`-tagged FTCS variant (see shell-integration/) and
-                // deliberately ignores foreign duplicates to avoid double
-                // command blocks. This is expected and benign, so it is
-                // silently dropped WITHOUT a log by design.
+            } else if let Some(marker) = parse_plain_ftcs_params(&ftcs_str_refs) {
+                // A marker without the `freminal=1` tag, from another shell
+                // integration (Starship, iTerm2, kitty, a remote host).  The
+                // handler pairs these by order and lets freminal's own
+                // markers win when both are active.
+                output.push(TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(marker)));
             } else {
                 // Unknown or malformed OSC 133: the marker letter is not one we
                 // recognise (e.g. `Z`, or a future FTCS addition like `E`), or
-                // the parameter list was empty. Unlike a known-foreign marker,
-                // this is a genuine gap — the OSC 133 surface may have grown a
-                // variant we do not handle, or a program sent something
-                // malformed. Log it at warn with the full raw sequence so the
-                // unhandled surface can be audited.
+                // the parameter list was empty.  This is a genuine gap — the
+                // OSC 133 surface may have grown a variant we do not handle,
+                // or a program sent something malformed.  Log it at warn with
+                // the full raw sequence so the unhandled surface can be
+                // audited.
                 tracing::warn!(
                     "OSC 133: unrecognised or malformed FTCS marker (not a known A/B/C/D/P); raw sequence: \"{}\"",
                     seq_trace.as_escaped()
                 );
             }
         }
+        OscTarget::VsCode => {
+            handle_osc_vscode(raw_params, seq_trace, output);
+        }
         OscTarget::Clipboard => {
             handle_osc_clipboard(&params, seq_trace, output);
         }
@@ -299,6 +325,66 @@ fn dispatch_osc_target(
         OscTarget::ResetPaletteColor => {
             handle_osc_reset_palette(&params, output);
         }
+        OscTarget::SpecialColor => {
+            handle_osc_special_color(&params, seq_trace, output);
+        }
+        OscTarget::ResetSpecialColor => {
+            handle_osc_reset_special_color(&params, output);
+        }
+        // OSC 13/14/17/19 — mouse pointer and highlight colors, and their
+        // OSC 113/114/117/119 resets.
+        OscTarget::MouseForeground => {
+            output.push(TerminalOutput::OscResponse(
+                AnsiOscType::RequestColorQueryDynamic(
+                    DynamicColor::MouseForeground,
+                    osc_internal_type,
+                ),
+            ));
+        }
+        OscTarget::MouseBackground => {
+            output.push(TerminalOutput::OscResponse(
+                AnsiOscType::RequestColorQueryDynamic(
+                    DynamicColor::MouseBackground,
+                    osc_internal_type,
+                ),
+            ));
+        }
+        OscTarget::HighlightBackground => {
+            output.push(TerminalOutput::OscResponse(
+                AnsiOscType::RequestColorQueryDynamic(
+                    DynamicColor::HighlightBackground,
+                    osc_internal_type,
+                ),
+            ));
+        }
+        OscTarget::HighlightForeground => {
+            output.push(TerminalOutput::OscResponse(
+                AnsiOscType::RequestColorQueryDynamic(
+                    DynamicColor::HighlightForeground,
+                    osc_internal_type,
+                ),
+            ));
+        }
+        OscTarget::ResetMouseForeground => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ResetDynamicColor(
+                DynamicColor::MouseForeground,
+            )));
+        }
+        OscTarget::ResetMouseBackground => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ResetDynamicColor(
+                DynamicColor::MouseBackground,
+            )));
+        }
+        OscTarget::ResetHighlightBackground => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ResetDynamicColor(
+                DynamicColor::HighlightBackground,
+            )));
+        }
+        OscTarget::ResetHighlightForeground => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ResetDynamicColor(
+                DynamicColor::HighlightForeground,
+            )));
+        }
         OscTarget::RemoteHost => {
             output.push(TerminalOutput::OscResponse(AnsiOscType::RemoteHost(
                 osc_internal_type.to_string(),
@@ -344,15 +430,11 @@ fn dispatch_osc_target(
         }
         // Known-but-unimplemented OSC targets.  These are recognised
         // sequences sent by common programs (vim/neovim, zsh, tmux) that
-        // Freminal cannot meaningfully act on (X11 mouse colors, Tektronix
-        // graphics, color-scheme notifications).  Logged at warn with the
-        // full raw sequence so the unhandled surface can be audited.
-        OscTarget::MouseForeground
-        | OscTarget::MouseBackground
-        | OscTarget::TekForeground
+        // Freminal cannot meaningfully act on (Tektronix graphics,
+        // color-scheme notifications).  Logged at warn with the full raw
+        // sequence so the unhandled surface can be audited.
+        OscTarget::TekForeground
         | OscTarget::TekBackground
-        | OscTarget::HighlightBackground
-        | OscTarget::HighlightForeground
         | OscTarget::ColorSchemeNotification => {
             tracing::warn!(
                 "Recognised but unimplemented OSC (silently consumed): target={osc_target:?}; raw sequence: \"{}\"",
@@ -405,7 +487,8 @@ fn extract_param(idx: usize, params: &[Option<AnsiOscToken>]) -> Option<AnsiOscT
 mod tests {
     use super::{AnsiOscParser, AnsiOscParserState};
     use crate::ansi::ParserOutcome;
-    use freminal_common::buffer_states::osc::AnsiOscType;
+    use freminal_common::buffer_states::ftcs_compat::CompatMarker;
+    use freminal_common::buffer_states::osc::{AnsiOscInternalType, AnsiOscType, DynamicColor};
     use freminal_common::buffer_states::pointer_shape::PointerShape;
     use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
@@ -584,23 +667,56 @@ mod tests {
     }
 
     // ------------------------------------------------------------------
-    // Known-but-unimplemented targets (silently consumed)
+    // OSC 13/14/17/19 — dynamic colors, and their 113/114/117/119 resets
     // ------------------------------------------------------------------
 
     #[test]
-    fn osc13_mouse_foreground_silently_consumed() {
-        // OSC 13 — MouseForeground — known but unimplemented
-        let output = feed_osc(b"13;?\x07");
-        assert!(output.is_empty());
+    fn osc_dynamic_colors_dispatched_correctly() {
+        for (payload, color) in [
+            (&b"13;?\x07"[..], DynamicColor::MouseForeground),
+            (b"14;?\x07", DynamicColor::MouseBackground),
+            (b"17;?\x07", DynamicColor::HighlightBackground),
+            (b"19;?\x07", DynamicColor::HighlightForeground),
+        ] {
+            assert_eq!(
+                feed_osc(payload),
+                vec![TerminalOutput::OscResponse(
+                    AnsiOscType::RequestColorQueryDynamic(color, AnsiOscInternalType::Query)
+                )]
+            );
+        }
+        assert_eq!(
+            feed_osc(b"17;#102030\x07"),
+            vec![TerminalOutput::OscResponse(
+                AnsiOscType::RequestColorQueryDynamic(
+                    DynamicColor::HighlightBackground,
+                    AnsiOscInternalType::String("#102030".to_string())
+                )
+            )]
+        );
     }
 
     #[test]
-    fn osc14_mouse_background_silently_consumed() {
-        // OSC 14 — MouseBackground — known but unimplemented
-        let output = feed_osc(b"14;?\x07");
-        assert!(output.is_empty());
+    fn osc_dynamic_color_resets_dispatched_correctly() {
+        for (payload, color) in [
+            (&b"113\x07"[..], DynamicColor::MouseForeground),
+            (b"114\x07", DynamicColor::MouseBackground),
+            (b"117\x07", DynamicColor::HighlightBackground),
+            (b"119\x07", DynamicColor::HighlightForeground),
+        ] {
+            assert_eq!(
+                feed_osc(payload),
+                vec![TerminalOutput::OscResponse(AnsiOscType::ResetDynamicColor(
+                    color
+                ))]
+            );
+        }
     }
 
+    // ------------------------------------------------------------------
+    // Known-but-unimplemented targets (silently consumed)
+    // ------------------------------------------------------------------
+
     #[test]
     fn osc66_color_scheme_notification_silently_consumed() {
         // OSC 66 — ColorSchemeNotification — known but unimplemented
@@ -750,22 +866,27 @@ mod tests {
     }
 
     #[test]
-    fn osc133_ftcs_plain_marker_silently_consumed() {
-        // OSC 133 ; A BEL — plain marker without freminal=1 is silently dropped
+    fn osc133_ftcs_plain_marker_is_compat() {
+        // OSC 133 ; A BEL — plain marker without freminal=1 goes to the
+        // compatibility pairing
         let output = feed_osc(b"133;A\x07");
-        assert!(
-            output.is_empty(),
-            "plain marker without freminal=1 must produce no output"
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(
+                CompatMarker::PromptStart
+            ))]
         );
     }
 
     #[test]
-    fn osc133_ftcs_foreign_marker_silently_consumed() {
-        // OSC 133 ; A ; aid=12345 BEL — WezTerm-style marker is silently dropped
+    fn osc133_ftcs_foreign_marker_is_compat() {
+        // OSC 133 ; A ; aid=12345 BEL — WezTerm-style marker
         let output = feed_osc(b"133;A;aid=12345\x07");
-        assert!(
-            output.is_empty(),
-            "foreign marker without freminal=1 must produce no output"
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(
+                CompatMarker::PromptStart
+            ))]
         );
     }
 
@@ -780,25 +901,49 @@ mod tests {
     }
 
     #[test]
-    fn osc133_known_foreign_marker_consumed_without_output() {
-        // Broaden coverage beyond `A` (see `osc133_ftcs_foreign_marker_...`):
-        // the `freminal=1`-gated markers B/C/D, when sent WITHOUT `freminal=1`,
-        // are foreign duplicates — recognised, deliberately ignored, no output
-        // (and, by design, no unhandled-log). `P` is excluded here: it is
-        // intentionally freminal-independent and DOES emit output.
-        for seq in [
-            b"133;B\x07".as_slice(),   // prompt end / command input start
-            b"133;C\x07".as_slice(),   // command output start
-            b"133;D;1\x07".as_slice(), // command finished, exit code 1
+    fn osc133_foreign_markers_are_compat() {
+        // B/C/D without `freminal=1` are parsed the same way as `A`.  `P` is
+        // excluded: the freminal parser accepts it from any emitter.
+        for (seq, marker) in [
+            (b"133;B\x07".as_slice(), CompatMarker::CommandStart),
+            (b"133;C\x07".as_slice(), CompatMarker::OutputStart),
+            (
+                b"133;D;1\x07".as_slice(),
+                CompatMarker::CommandFinished { exit_code: Some(1) },
+            ),
         ] {
             let output = feed_osc(seq);
-            assert!(
-                output.is_empty(),
-                "known foreign marker {seq:?} must produce no output"
+            assert_eq!(
+                output,
+                vec![TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(marker))],
+                "{seq:?}"
             );
         }
     }
 
+    #[test]
+    fn osc633_markers_are_compat() {
+        let output = feed_osc(b"633;E;ls\\x3b pwd;nonce\x07");
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(
+                CompatMarker::CommandLine("ls; pwd".to_owned())
+            ))]
+        );
+        let output = feed_osc(b"633;D;0\x07");
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::FtcsCompat(
+                CompatMarker::CommandFinished { exit_code: Some(0) }
+            ))]
+        );
+    }
+
+    #[test]
+    fn osc633_unknown_sub_command_is_consumed() {
+        assert!(feed_osc(b"633;Z\x07").is_empty());
+    }
+
     // ── Lines 272-276: OSC 7 (RemoteHost) ───────────────────────────────────
     #[test]
     fn osc7_remote_host() {
@@ -5,6 +5,7 @@
 
 use crate::ansi_components::tracer::SequenceTracer;
 use freminal_common::buffer_states::osc::{AnsiOscType, ITerm2InlineImageData, ImageDimension};
+use freminal_common::buffer_states::osc_iterm2::{ITerm2ParseError, parse_iterm2_command};
 use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
 /// Handle OSC 1337 (iTerm2 extensions).
@@ -16,6 +17,9 @@ use freminal_common::buffer_states::terminal_output::TerminalOutput;
 /// 1337 ; File = [key=value[;key=value]...] : <base64 data>
 /// ```
 ///
+/// The text commands (`SetUserVar=`, `CurrentDir=`, `SetMark`, …) are
+/// parsed by [`parse_iterm2_command`].
+///
 /// `raw_params` is the full, un-split OSC parameter bytes (before `;` splitting).
 /// We parse from the raw bytes because the `;` delimiter inside the `File=` args
 /// must be handled together with the `:` that separates args from the base64 payload.
@@ -65,6 +69,23 @@ pub(super) fn handle_osc_iterm2(
         return;
     }
 
+    // The text commands are parsed from the unsplit bytes too, so a
+    // `CurrentDir=` path may contain `;`.
+    match std::str::from_utf8(rest).map(parse_iterm2_command) {
+        Ok(Ok(cmd)) => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ITerm2(cmd)));
+            return;
+        }
+        Ok(Err(ITerm2ParseError::InvalidArgument(arg))) => {
+            tracing::warn!(
+                "OSC 1337: invalid argument {arg:?} (ignored): recent='{}'",
+                seq_trace.as_str()
+            );
+            return;
+        }
+        Ok(Err(ITerm2ParseError::Unsupported)) | Err(_) => {}
+    }
+
     // Not a recognised sub-command — silently consume, like xterm/VTE.
     tracing::warn!(
         "OSC 1337: unrecognised sub-command; raw sequence: \"{}\"",
@@ -252,6 +273,7 @@ mod tests {
     use super::super::osc::AnsiOscParser;
     use super::super::tracer::SequenceTracer;
     use freminal_common::buffer_states::osc::{AnsiOscType, ImageDimension};
+    use freminal_common::buffer_states::osc_iterm2::ITerm2Command;
     use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
     fn feed_osc(payload: &[u8]) -> Vec<TerminalOutput> {
@@ -345,9 +367,9 @@ mod tests {
     }
 
     #[test]
-    fn osc1337_non_file_subcommand_returns_unknown() {
-        // OSC 1337 ; SetUserVar=foo=bar BEL
-        let mut payload = b"1337;SetUserVar=foo=bar\x07".to_vec();
+    fn osc1337_unsupported_subcommand_returns_unknown() {
+        // OSC 1337 ; StealFocus BEL
+        let mut payload = b"1337;StealFocus\x07".to_vec();
         let output = feed_osc(&payload);
         assert_eq!(output.len(), 1);
         assert!(matches!(
@@ -356,7 +378,7 @@ mod tests {
         ));
 
         // Also test with ST terminator
-        payload = b"1337;SetUserVar=foo=bar\x1b\\".to_vec();
+        payload = b"1337;StealFocus\x1b\\".to_vec();
         let output = feed_osc(&payload);
         assert_eq!(output.len(), 1);
         assert!(matches!(
@@ -365,6 +387,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn osc1337_text_commands_are_parsed() {
+        let output = feed_osc(b"1337;SetUserVar=host=ZGIx\x07");
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::ITerm2(
+                ITerm2Command::SetUserVar {
+                    name: "host".to_owned(),
+                    value: "db1".to_owned(),
+                }
+            ))]
+        );
+
+        let output = feed_osc(b"1337;CurrentDir=/tmp/a;b\x1b\\");
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::ITerm2(
+                ITerm2Command::CurrentDir("/tmp/a;b".to_owned())
+            ))]
+        );
+
+        let output = feed_osc(b"1337;SetMark\x07");
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::ITerm2(
+                ITerm2Command::SetMark
+            ))]
+        );
+    }
+
+    #[test]
+    fn osc1337_invalid_argument_no_output() {
+        assert!(feed_osc(b"1337;RequestAttention=maybe\x07").is_empty());
+        assert!(feed_osc(b"1337;SetUserVar=novalue\x07").is_empty());
+    }
+
     #[test]
     fn osc1337_missing_colon_no_output() {
         // File= args without ':' separator before base64 data
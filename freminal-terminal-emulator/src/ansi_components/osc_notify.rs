@@ -8,7 +8,9 @@
 //! OSC 9 and OSC 777 are one-way, fire-and-forget notification requests. They
 //! produce an [`AnsiOscType::Notify`] which the GUI routes to an in-app
 //! toast and/or the system notification daemon per the `[notifications]`
-//! config.
+//! config.  An OSC 9 body that starts with a `ConEmu` sub-command number
+//! (`9;4;1;50` progress and friends) is not a notification; it produces an
+//! [`AnsiOscType::ConEmu`] instead.
 //!
 //! OSC 99 is the kitty stateful notification protocol.  It produces an
 //! [`AnsiOscType::Notify99`] carrying a fully-parsed
@@ -20,6 +22,7 @@
 //!
 //! ```text
 //! OSC 9 ; <body>                                        ST   (iTerm2 / WezTerm)
+//! OSC 9 ; <1-12> ; <args>                               ST   (ConEmu)
 //! OSC 777 ; notify ; <title> ; <body>                   ST   (urxvt)
 //! OSC 99  ; <colon-sep key=value metadata> ; <payload>  ST   (kitty)
 //! ```
@@ -34,14 +37,16 @@
 
 use crate::ansi_components::tracer::SequenceTracer;
 use freminal_common::buffer_states::osc::{AnsiOscType, OscNotifySource};
+use freminal_common::buffer_states::osc_conemu::{ConEmuParseError, parse_conemu};
 use freminal_common::buffer_states::osc_notify_99::parse_osc_99;
 use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
-/// Handle OSC 9 (`iTerm2` / `WezTerm` notification).
+/// Handle OSC 9 (`iTerm2` / `WezTerm` notification, or a `ConEmu`
+/// sub-command).
 ///
 /// The entire payload after the leading `9;` is the notification body;
 /// there is no separate title. An empty body is silently consumed (nothing
-/// useful to display).
+/// useful to display), as is a malformed `ConEmu` sub-command.
 pub(super) fn handle_osc_notify_9(
     raw_params: &[u8],
     seq_trace: &SequenceTracer,
@@ -70,6 +75,21 @@ pub(super) fn handle_osc_notify_9(
         return;
     }
 
+    match parse_conemu(&body) {
+        Ok(cmd) => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ConEmu(cmd)));
+            return;
+        }
+        Err(ConEmuParseError::NotConEmu) => {}
+        Err(e) => {
+            tracing::debug!(
+                "OSC 9: ConEmu sub-command {e} (ignored): recent='{}'",
+                seq_trace.as_str()
+            );
+            return;
+        }
+    }
+
     output.push(TerminalOutput::OscResponse(AnsiOscType::Notify {
         source: OscNotifySource::Osc9,
         title: None,
@@ -226,6 +246,7 @@ mod tests {
     use super::super::osc::AnsiOscParser;
     use super::super::tracer::SequenceTracer;
     use freminal_common::buffer_states::osc::{AnsiOscType, OscNotifySource};
+    use freminal_common::buffer_states::osc_conemu::{ConEmuCommand, ProgressState};
     use freminal_common::buffer_states::osc_notify_99::{Osc99Command, Osc99PayloadType};
     use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
@@ -298,6 +319,34 @@ mod tests {
         assert!(output.is_empty(), "got: {output:?}");
     }
 
+    #[test]
+    fn osc9_conemu_progress_is_not_a_notification() {
+        let output = feed_osc(b"9;4;1;42\x1b\\");
+        assert_eq!(
+            output,
+            vec![TerminalOutput::OscResponse(AnsiOscType::ConEmu(
+                ConEmuCommand::SetProgress {
+                    state: ProgressState::Normal,
+                    percent: Some(42),
+                }
+            ))]
+        );
+    }
+
+    #[test]
+    fn osc9_malformed_conemu_no_output() {
+        let output = feed_osc(b"9;4;9\x07");
+        assert!(output.is_empty(), "got: {output:?}");
+    }
+
+    #[test]
+    fn osc9_numeric_text_stays_a_notification() {
+        let output = feed_osc(b"9;3 tests failed\x07");
+        let (source, _, body) = expect_notify(&output);
+        assert_eq!(source, OscNotifySource::Osc9);
+        assert_eq!(body, "3 tests failed");
+    }
+
     // ── OSC 777 ──────────────────────────────────────────────────────────
 
     #[test]
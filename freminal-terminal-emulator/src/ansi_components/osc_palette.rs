@@ -6,7 +6,7 @@
 use crate::ansi_components::tracer::SequenceTracer;
 use freminal_common::buffer_states::osc::{AnsiOscToken, AnsiOscType};
 use freminal_common::buffer_states::terminal_output::TerminalOutput;
-use freminal_common::colors::parse_color_spec;
+use freminal_common::colors::{SpecialColor, parse_color_spec};
 
 /// Handle OSC 4 (palette color set/query).
 ///
@@ -108,12 +108,85 @@ pub(super) fn handle_osc_reset_palette(
     }
 }
 
+/// Read an OSC 5 / OSC 105 special-color index.
+fn special_color_index(token: &AnsiOscToken, osc: u16) -> Option<SpecialColor> {
+    let index = match token {
+        AnsiOscToken::OscValue(v) => Some(*v),
+        AnsiOscToken::String(s) => s.parse::<u16>().ok(),
+    };
+    let color = index.and_then(SpecialColor::from_index);
+    if color.is_none() {
+        tracing::warn!("OSC {osc}: unsupported special color index: {token:?}");
+    }
+    color
+}
+
+/// Handle OSC 5 (special color set/query).
+///
+/// Format: `OSC 5 ; c ; spec ST`, where `c` is 0 (bold), 1 (underline),
+/// 2 (blink) or 3 (reverse), and `spec` is `?` or a color as for OSC 4.
+pub(super) fn handle_osc_special_color(
+    params: &[Option<AnsiOscToken>],
+    seq_trace: &SequenceTracer,
+    output: &mut Vec<TerminalOutput>,
+) {
+    let Some(Some(token)) = params.get(1) else {
+        tracing::warn!("OSC 5: missing index: recent='{}'", seq_trace.as_str());
+        return;
+    };
+    let Some(color) = special_color_index(token, 5) else {
+        return;
+    };
+
+    let spec = if let Some(Some(AnsiOscToken::String(s))) = params.get(2) {
+        s.as_str()
+    } else {
+        tracing::warn!("OSC 5: missing color spec: recent='{}'", seq_trace.as_str());
+        return;
+    };
+
+    if spec == "?" {
+        output.push(TerminalOutput::OscResponse(AnsiOscType::QuerySpecialColor(
+            color,
+        )));
+    } else if let Some((r, g, b)) = parse_color_spec(spec) {
+        output.push(TerminalOutput::OscResponse(AnsiOscType::SetSpecialColor(
+            color, r, g, b,
+        )));
+    } else {
+        tracing::warn!("OSC 5: invalid color spec: {spec}");
+    }
+}
+
+/// Handle OSC 105 (reset special color).
+///
+/// Format: `OSC 105 ST` (reset all) or `OSC 105 ; c ST` (reset one).
+pub(super) fn handle_osc_reset_special_color(
+    params: &[Option<AnsiOscToken>],
+    output: &mut Vec<TerminalOutput>,
+) {
+    match params.get(1) {
+        None | Some(None) => {
+            output.push(TerminalOutput::OscResponse(AnsiOscType::ResetSpecialColor(
+                None,
+            )));
+        }
+        Some(Some(token)) => {
+            if let Some(color) = special_color_index(token, 105) {
+                output.push(TerminalOutput::OscResponse(AnsiOscType::ResetSpecialColor(
+                    Some(color),
+                )));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::osc::AnsiOscParser;
     use freminal_common::buffer_states::osc::AnsiOscType;
     use freminal_common::buffer_states::terminal_output::TerminalOutput;
-    use freminal_common::colors::{parse_color_spec, scale_hex_channel};
+    use freminal_common::colors::{SpecialColor, parse_color_spec, scale_hex_channel};
 
     fn feed_osc(payload: &[u8]) -> Vec<TerminalOutput> {
         let mut parser = AnsiOscParser::new();
@@ -356,4 +429,51 @@ mod tests {
         let output = feed_osc(payload);
         assert!(output.is_empty());
     }
+
+    // ------------------------------------------------------------------
+    // OSC 5 / OSC 105 parser integration tests
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn osc5_set_and_query_special_color() {
+        assert_eq!(
+            feed_osc(b"5;0;#ff0080\x07"),
+            vec![TerminalOutput::OscResponse(AnsiOscType::SetSpecialColor(
+                SpecialColor::Bold,
+                0xff,
+                0x00,
+                0x80
+            ))]
+        );
+        assert_eq!(
+            feed_osc(b"5;3;?\x1b\\"),
+            vec![TerminalOutput::OscResponse(AnsiOscType::QuerySpecialColor(
+                SpecialColor::Reverse
+            ))]
+        );
+    }
+
+    #[test]
+    fn osc5_unsupported_index_or_bad_spec_no_output() {
+        assert!(feed_osc(b"5;4;#ffffff\x07").is_empty());
+        assert!(feed_osc(b"5;1;notacolor\x07").is_empty());
+        assert!(feed_osc(b"5;1\x07").is_empty());
+    }
+
+    #[test]
+    fn osc105_reset_all_and_single() {
+        assert_eq!(
+            feed_osc(b"105\x07"),
+            vec![TerminalOutput::OscResponse(AnsiOscType::ResetSpecialColor(
+                None
+            ))]
+        );
+        assert_eq!(
+            feed_osc(b"105;2\x07"),
+            vec![TerminalOutput::OscResponse(AnsiOscType::ResetSpecialColor(
+                Some(SpecialColor::Blink)
+            ))]
+        );
+        assert!(feed_osc(b"105;9\x07").is_empty());
+    }
 }
@@ -3,7 +3,7 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
-use freminal_common::buffer_states::line_draw::DecSpecialGraphics;
+use freminal_common::buffer_states::charset::{Charset, GSet};
 use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
 use crate::ansi::ParserOutcome;
@@ -94,7 +94,26 @@ impl StandardParser {
                 ParserOutcome::Invalid("Invalid intermediate byte".to_string())
             }
             StandardParserState::Params => {
-                if is_standard_param(b) {
+                // SCS designators may carry one more intermediate (`%5`) and
+                // end in any final byte; the dispatch decides which it knows.
+                if self
+                    .intermediates
+                    .first()
+                    .is_some_and(|&i| scs_target(i).is_some())
+                {
+                    if (0x20..=0x2f).contains(&b) && self.intermediates.len() == 1 {
+                        self.intermediates.push(b);
+                        return ParserOutcome::Continue;
+                    }
+                    if (0x30..=0x7e).contains(&b) {
+                        self.params.push(b);
+                        self.state = StandardParserState::Finished;
+
+                        self.seq_trace.trim_control_tail();
+
+                        return ParserOutcome::Finished;
+                    }
+                } else if is_standard_param(b) {
                     self.params.push(b);
                     self.state = StandardParserState::Finished;
 
@@ -205,112 +224,12 @@ impl StandardParser {
                         }
                     }
                 }
-                Some(b'(') => {
-                    let value = self.params.first();
-
-                    match value {
-                        None => ParserOutcome::Invalid("No params".to_string()),
-                        Some(value) => {
-                            let value = *value as char;
-
-                            match value {
-                                '0' => output.push(TerminalOutput::DecSpecialGraphics(
-                                    DecSpecialGraphics::Replace,
-                                )),
-                                'B' => output.push(TerminalOutput::DecSpecialGraphics(
-                                    DecSpecialGraphics::DontReplace,
-                                )),
-                                'C' => output.push(TerminalOutput::CharsetG0),
-                                _ => {
-                                    output.push(TerminalOutput::Invalid);
-                                    return ParserOutcome::Invalid(
-                                        "Invalid param value".to_string(),
-                                    );
-                                }
-                            }
-                            ParserOutcome::Finished
-                        }
-                    }
-                }
-                Some(b')') => {
-                    let value = self.params.first();
-
-                    match value {
-                        None => ParserOutcome::Invalid("No params".to_string()),
-                        Some(value) => {
-                            match *value {
-                                b'0' | b'B' | b'C' => {
-                                    // G1 designation: '0' = DEC Special, 'B' = US-ASCII,
-                                    // 'C' = Finnish NRC.  Freminal uses a simplified
-                                    // single-slot charset model — G1 designations are
-                                    // silently accepted but do not change state.
-                                    output.push(TerminalOutput::CharsetG1);
-                                }
-                                _ => {
-                                    output.push(TerminalOutput::Invalid);
-                                    return ParserOutcome::Invalid(
-                                        "Invalid param value".to_string(),
-                                    );
-                                }
-                            }
-
-                            ParserOutcome::Finished
-                        }
-                    }
-                }
-                Some(b'*') => {
-                    let value = self.params.first();
-
-                    match value {
-                        None => ParserOutcome::Invalid("No params".to_string()),
-                        Some(value) => {
-                            if *value == b'C' {
-                                output.push(TerminalOutput::CharsetG2);
-                            } else {
-                                output.push(TerminalOutput::Invalid);
-                                return ParserOutcome::Invalid("Invalid param value".to_string());
-                            }
-                            ParserOutcome::Finished
-                        }
+                Some(&value) => {
+                    if let Some((set, is_96)) = scs_target(value) {
+                        return self.designate_charset(set, is_96, output);
                     }
-                }
-                Some(b'+') => {
-                    let value = self.params.first();
 
-                    match value {
-                        None => ParserOutcome::Invalid("No params".to_string()),
-                        Some(value) => {
-                            match value {
-                                b'0' => output.push(TerminalOutput::DecSpecialGraphics(
-                                    DecSpecialGraphics::Replace,
-                                )),
-                                b'A' => output.push(TerminalOutput::CharsetUK),
-                                b'B' => output.push(TerminalOutput::CharsetUSASCII),
-                                b'4' => output.push(TerminalOutput::CharsetDutch),
-                                b'5' | b'C' => output.push(TerminalOutput::CharsetFinnish),
-                                b'R' => output.push(TerminalOutput::CharsetFrench),
-                                b'Q' => output.push(TerminalOutput::CharsetFrenchCanadian),
-                                b'K' => output.push(TerminalOutput::CharsetGerman),
-                                b'Y' => output.push(TerminalOutput::CharsetItalian),
-                                b'E' | b'6' => output.push(TerminalOutput::CharsetNorwegianDanish),
-                                b'Z' => output.push(TerminalOutput::CharsetSpanish),
-                                b'H' | b'7' => output.push(TerminalOutput::CharsetSwedish),
-                                b'=' => output.push(TerminalOutput::CharsetSwiss),
-                                _ => {
-                                    output.push(TerminalOutput::Invalid);
-                                    return ParserOutcome::Invalid(
-                                        "Invalid param value".to_string(),
-                                    );
-                                }
-                            }
-
-                            ParserOutcome::Finished
-                        }
-                    }
-                }
-                Some(value) => {
-                    let value = *value as char;
-                    match value {
+                    match value as char {
                         '7' => output.push(TerminalOutput::SaveCursor),
                         '8' => output.push(TerminalOutput::RestoreCursor),
                         '=' => output.push(TerminalOutput::ApplicationKeypadMode),
@@ -319,6 +238,8 @@ impl StandardParser {
                         'c' => output.push(TerminalOutput::ResetDevice),
                         'l' => output.push(TerminalOutput::MemoryLock),
                         'm' => output.push(TerminalOutput::MemoryUnlock),
+                        'N' => output.push(TerminalOutput::SingleShiftG2),
+                        'O' => output.push(TerminalOutput::SingleShiftG3),
                         'n' => output.push(TerminalOutput::CharsetG2AsGL),
                         'o' => output.push(TerminalOutput::CharsetG3AsGL),
                         '|' => output.push(TerminalOutput::CharsetG3AsGR),
@@ -345,12 +266,53 @@ impl StandardParser {
             _ => ParserOutcome::Continue,
         }
     }
+
+    /// SCS: designate the set named by the intermediates after the first
+    /// and the final byte.
+    fn designate_charset(
+        &self,
+        set: GSet,
+        is_96: bool,
+        output: &mut Vec<TerminalOutput>,
+    ) -> ParserOutcome {
+        if self.params.is_empty() {
+            return ParserOutcome::Invalid("No params".to_string());
+        }
+
+        let designator: Vec<u8> = self.intermediates[1..]
+            .iter()
+            .chain(&self.params)
+            .copied()
+            .collect();
+        let Some(charset) = Charset::from_designator(&designator, is_96) else {
+            output.push(TerminalOutput::Invalid);
+            return ParserOutcome::Invalid("Invalid param value".to_string());
+        };
+
+        output.push(TerminalOutput::DesignateCharset { set, charset });
+        ParserOutcome::Finished
+    }
+}
+
+/// The G set an SCS intermediate designates into, and whether it names a
+/// 96-character set.
+const fn scs_target(b: u8) -> Option<(GSet, bool)> {
+    match b {
+        b'(' => Some((GSet::G0, false)),
+        b')' => Some((GSet::G1, false)),
+        b'*' => Some((GSet::G2, false)),
+        b'+' => Some((GSet::G3, false)),
+        b'-' => Some((GSet::G1, true)),
+        b'.' => Some((GSet::G2, true)),
+        b'/' => Some((GSet::G3, true)),
+        _ => None,
+    }
 }
 
 #[must_use]
 pub const fn is_standard_intermediate_final(b: u8) -> bool {
-    // 7 8 = > F H c l m n o | } ~ are final and we want to enter the finished state
-    // H (0x48) is HTS — Horizontal Tab Set
+    // 7 8 = > F H N O c l m n o | } ~ are final and we want to enter the finished state
+    // H (0x48) is HTS — Horizontal Tab Set; N and O are SS2 and SS3
 
     matches!(
         b,
@@ -358,6 +320,8 @@ pub const fn is_standard_intermediate_final(b: u8) -> bool {
             | 0x3e
             | 0x46
             | 0x48
+            | 0x4e
+            | 0x4f
             | 0x63
             | 0x6c
             | 0x6d
@@ -377,9 +341,12 @@ pub const fn is_standard_intermediate_final(b: u8) -> bool {
 
 #[must_use]
 pub const fn is_standard_intermediate_continue(b: u8) -> bool {
-    // space # % ( ) * + are states where we want to continue and get a Params
+    // space # % ( ) * + - . / are states where we want to continue and get a Params
 
-    matches!(b, 0x20 | 0x23 | 0x25 | 0x28 | 0x29 | 0x2a | 0x2b)
+    matches!(
+        b,
+        0x20 | 0x23 | 0x25 | 0x28 | 0x29 | 0x2a | 0x2b | 0x2d | 0x2e | 0x2f
+    )
 }
 
 #[must_use]
@@ -418,7 +385,7 @@ pub const fn is_standard_param(b: u8) -> bool {
 mod tests {
     use super::StandardParser;
     use crate::ansi::ParserOutcome;
-    use freminal_common::buffer_states::line_draw::DecSpecialGraphics;
+    use freminal_common::buffer_states::charset::{Charset, GSet};
     use freminal_common::buffer_states::terminal_output::TerminalOutput;
 
     /// Feed a two-byte standard escape sequence through the parser.
@@ -561,222 +528,156 @@ mod tests {
     }
 
     // ------------------------------------------------------------------
-    // ESC ( — G0 charset
+    // SCS — ESC ( ) * + designate 94-character sets into G0–G3
     // ------------------------------------------------------------------
 
+    /// Feed the bytes after `ESC` one at a time.
+    fn feed_bytes(bytes: &[u8]) -> (Vec<TerminalOutput>, ParserOutcome) {
+        let mut parser = StandardParser::new();
+        let mut output = Vec::new();
+        let mut result = ParserOutcome::Continue;
+        for &b in bytes {
+            result = parser.standard_parser_inner(b, &mut output);
+        }
+        (output, result)
+    }
+
+    fn designated(set: GSet, charset: Charset) -> Vec<TerminalOutput> {
+        vec![TerminalOutput::DesignateCharset { set, charset }]
+    }
+
     #[test]
-    fn esc_paren_0_dec_special_graphics_replace() {
+    fn esc_paren_0_designates_g0_dec_special_graphics() {
         let (output, result) = feed_standard(b'(', b'0');
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(
-            output,
-            vec![TerminalOutput::DecSpecialGraphics(
-                DecSpecialGraphics::Replace
-            )]
-        );
+        assert_eq!(output, designated(GSet::G0, Charset::DecSpecialGraphics));
     }
 
     #[test]
-    fn esc_paren_b_dec_special_graphics_dont_replace() {
+    fn esc_paren_b_designates_g0_ascii() {
         let (output, result) = feed_standard(b'(', b'B');
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(
-            output,
-            vec![TerminalOutput::DecSpecialGraphics(
-                DecSpecialGraphics::DontReplace
-            )]
-        );
+        assert_eq!(output, designated(GSet::G0, Charset::Ascii));
     }
 
     #[test]
-    fn esc_paren_c_charset_g0() {
+    fn esc_paren_c_designates_g0_finnish() {
         let (output, result) = feed_standard(b'(', b'C');
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetG0]);
+        assert_eq!(output, designated(GSet::G0, Charset::Finnish));
     }
 
     #[test]
     fn esc_paren_invalid_param() {
-        let (output, result) = feed_standard(b'(', b'Z');
+        let (output, result) = feed_standard(b'(', b'W');
         assert!(matches!(result, ParserOutcome::Invalid(_)));
         assert!(output.contains(&TerminalOutput::Invalid));
     }
 
-    // ------------------------------------------------------------------
-    // ESC ) — G1 charset designators
-    // ------------------------------------------------------------------
-
     #[test]
-    fn esc_close_paren_0_charset_g1() {
+    fn esc_close_paren_0_designates_g1() {
         let (output, result) = feed_standard(b')', b'0');
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetG1]);
-    }
-
-    #[test]
-    fn esc_close_paren_b_charset_g1() {
-        let (output, result) = feed_standard(b')', b'B');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetG1]);
-    }
-
-    #[test]
-    fn esc_close_paren_c_charset_g1() {
-        let (output, result) = feed_standard(b')', b'C');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetG1]);
+        assert_eq!(output, designated(GSet::G1, Charset::DecSpecialGraphics));
     }
 
     #[test]
     fn esc_close_paren_invalid_param() {
-        let (output, result) = feed_standard(b')', b'Z');
+        let (output, result) = feed_standard(b')', b'W');
         assert!(matches!(result, ParserOutcome::Invalid(_)));
         assert!(output.contains(&TerminalOutput::Invalid));
     }
 
-    // ------------------------------------------------------------------
-    // ESC * — G2 charset
-    // ------------------------------------------------------------------
-
     #[test]
-    fn esc_star_c_charset_g2() {
-        let (output, result) = feed_standard(b'*', b'C');
+    fn esc_star_lt_designates_g2_dec_supplemental() {
+        let (output, result) = feed_standard(b'*', b'<');
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetG2]);
+        assert_eq!(output, designated(GSet::G2, Charset::DecSupplemental));
     }
 
     #[test]
     fn esc_star_invalid_param() {
-        let (output, result) = feed_standard(b'*', b'Z');
+        let (output, result) = feed_standard(b'*', b'W');
         assert!(matches!(result, ParserOutcome::Invalid(_)));
         assert!(output.contains(&TerminalOutput::Invalid));
     }
 
-    // ------------------------------------------------------------------
-    // ESC + — national character set designations
-    // ------------------------------------------------------------------
-
-    #[test]
-    fn esc_plus_0_dec_special_graphics() {
-        let (output, result) = feed_standard(b'+', b'0');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(
-            output,
-            vec![TerminalOutput::DecSpecialGraphics(
-                DecSpecialGraphics::Replace
-            )]
-        );
-    }
-
-    #[test]
-    fn esc_plus_a_charset_uk() {
-        let (output, result) = feed_standard(b'+', b'A');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetUK]);
-    }
-
-    #[test]
-    fn esc_plus_b_charset_us_ascii() {
-        let (output, result) = feed_standard(b'+', b'B');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetUSASCII]);
-    }
-
-    #[test]
-    fn esc_plus_4_charset_dutch() {
-        let (output, result) = feed_standard(b'+', b'4');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetDutch]);
-    }
-
     #[test]
-    fn esc_plus_5_charset_finnish() {
-        let (output, result) = feed_standard(b'+', b'5');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetFinnish]);
-    }
-
-    #[test]
-    fn esc_plus_r_charset_french() {
-        let (output, result) = feed_standard(b'+', b'R');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetFrench]);
-    }
-
-    #[test]
-    fn esc_plus_q_charset_french_canadian() {
-        let (output, result) = feed_standard(b'+', b'Q');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetFrenchCanadian]);
-    }
-
-    #[test]
-    fn esc_plus_k_charset_german() {
-        let (output, result) = feed_standard(b'+', b'K');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetGerman]);
-    }
-
-    #[test]
-    fn esc_plus_y_charset_italian() {
-        let (output, result) = feed_standard(b'+', b'Y');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetItalian]);
+    fn esc_plus_gt_designates_g3_dec_technical() {
+        let (output, result) = feed_standard(b'+', b'>');
+        assert!(matches!(result, ParserOutcome::Finished));
+        assert_eq!(output, designated(GSet::G3, Charset::DecTechnical));
+    }
+
+    #[test]
+    fn national_replacement_set_designators() {
+        for (param, charset) in [
+            (b'A', Charset::British),
+            (b'4', Charset::Dutch),
+            (b'5', Charset::Finnish),
+            (b'R', Charset::French),
+            (b'f', Charset::French),
+            (b'Q', Charset::FrenchCanadian),
+            (b'9', Charset::FrenchCanadian),
+            (b'K', Charset::German),
+            (b'Y', Charset::Italian),
+            (b'E', Charset::NorwegianDanish),
+            (b'6', Charset::NorwegianDanish),
+            (b'`', Charset::NorwegianDanish),
+            (b'Z', Charset::Spanish),
+            (b'H', Charset::Swedish),
+            (b'7', Charset::Swedish),
+            (b'=', Charset::Swiss),
+        ] {
+            let (output, result) = feed_standard(b'+', param);
+            assert!(matches!(result, ParserOutcome::Finished));
+            assert_eq!(output, designated(GSet::G3, charset), "{}", param as char);
+        }
     }
 
     #[test]
-    fn esc_plus_e_charset_norwegian_danish() {
-        let (output, result) = feed_standard(b'+', b'E');
+    fn two_byte_designators() {
+        let (output, result) = feed_bytes(b"(%5");
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetNorwegianDanish]);
-    }
+        assert_eq!(output, designated(GSet::G0, Charset::DecSupplemental));
 
-    #[test]
-    fn esc_plus_z_charset_spanish() {
-        let (output, result) = feed_standard(b'+', b'Z');
+        let (output, result) = feed_bytes(b")%6");
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetSpanish]);
-    }
+        assert_eq!(output, designated(GSet::G1, Charset::Portuguese));
 
-    #[test]
-    fn esc_plus_h_charset_swedish() {
-        let (output, result) = feed_standard(b'+', b'H');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetSwedish]);
+        let (output, result) = feed_bytes(b"(%9");
+        assert!(matches!(result, ParserOutcome::Invalid(_)));
+        assert!(output.contains(&TerminalOutput::Invalid));
     }
 
     #[test]
-    fn esc_plus_eq_charset_swiss() {
-        let (output, result) = feed_standard(b'+', b'=');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetSwiss]);
+    fn only_one_extra_intermediate() {
+        let (_, result) = feed_bytes(b"(%%");
+        assert!(matches!(result, ParserOutcome::Invalid(_)));
     }
 
-    #[test]
-    fn esc_plus_c_charset_finnish_alternate() {
-        let (output, result) = feed_standard(b'+', b'C');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetFinnish]);
-    }
+    // ------------------------------------------------------------------
+    // SCS — ESC - . / designate 96-character sets into G1–G3
+    // ------------------------------------------------------------------
 
     #[test]
-    fn esc_plus_6_charset_norwegian_danish_alternate() {
-        let (output, result) = feed_standard(b'+', b'6');
+    fn esc_minus_a_designates_g1_latin1() {
+        let (output, result) = feed_standard(b'-', b'A');
         assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetNorwegianDanish]);
+        assert_eq!(output, designated(GSet::G1, Charset::Latin1Supplemental));
     }
 
     #[test]
-    fn esc_plus_7_charset_swedish_alternate() {
-        let (output, result) = feed_standard(b'+', b'7');
-        assert!(matches!(result, ParserOutcome::Finished));
-        assert_eq!(output, vec![TerminalOutput::CharsetSwedish]);
+    fn esc_dot_and_slash_designate_g2_and_g3() {
+        let (output, _) = feed_standard(b'.', b'A');
+        assert_eq!(output, designated(GSet::G2, Charset::Latin1Supplemental));
+        let (output, _) = feed_standard(b'/', b'A');
+        assert_eq!(output, designated(GSet::G3, Charset::Latin1Supplemental));
     }
 
     #[test]
-    fn esc_plus_invalid_param() {
-        // b'F' passes is_standard_param() but is not valid for the '+' dispatch.
-        let (output, result) = feed_standard(b'+', b'F');
+    fn ninety_six_set_rejects_94_designators() {
+        let (output, result) = feed_standard(b'-', b'0');
         assert!(matches!(result, ParserOutcome::Invalid(_)));
         assert!(output.contains(&TerminalOutput::Invalid));
     }
@@ -902,6 +803,20 @@ mod tests {
         assert_eq!(output, vec![TerminalOutput::ResetDevice]);
     }
 
+    #[test]
+    fn esc_upper_n_single_shift_g2() {
+        let (output, result) = feed_standard_final(b'N');
+        assert!(matches!(result, ParserOutcome::Finished));
+        assert_eq!(output, vec![TerminalOutput::SingleShiftG2]);
+    }
+
+    #[test]
+    fn esc_upper_o_single_shift_g3() {
+        let (output, result) = feed_standard_final(b'O');
+        assert!(matches!(result, ParserOutcome::Finished));
+        assert_eq!(output, vec![TerminalOutput::SingleShiftG3]);
+    }
+
     #[test]
     fn esc_lower_n_charset_g2_as_gl() {
         let (output, result) = feed_standard_final(b'n');
@@ -0,0 +1,209 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+use freminal_common::buffer_states::terminal_output::TerminalOutput;
+
+use crate::ansi::ParserOutcome;
+use crate::ansi_components::tracer::{SequenceTraceable, SequenceTracer};
+
+/// Longest protocol line kept, in bytes.  tmux sends `%output` in chunks far
+/// below this; a line that grows past it is dropped rather than buffered
+/// without bound.
+const MAX_LINE_LEN: usize = 1024 * 1024;
+
+/// Parser for the body of a tmux control-mode stream.
+///
+/// `tmux -CC` opens a `DCS 1000 p` string and then keeps it open for the
+/// whole session: everything until the closing ST (`ESC \`) is a series of
+/// newline-terminated protocol lines.  Buffering that as one DCS payload
+/// would hold the entire session in memory and deliver nothing until tmux
+/// exits, so once [`DcsParser`](super::dcs::DcsParser) sees the handshake
+/// the top-level parser switches here and every complete line is emitted as
+/// [`TerminalOutput::TmuxControlLine`] as soon as it arrives.
+///
+/// tmux escapes every non-printable byte in `%output` payloads as octal, so
+/// an `ESC` in the stream can only be the start of the terminating ST.
+#[derive(Eq, PartialEq, Debug)]
+pub struct TmuxControlParser {
+    /// Bytes of the current, not yet terminated line.
+    line: Vec<u8>,
+
+    /// The current line passed [`MAX_LINE_LEN`]; its bytes are discarded
+    /// up to the next newline.
+    overlong: bool,
+
+    /// An `ESC` was seen and the next byte decides whether it is ST.
+    pending_esc: bool,
+
+    // Internal trace of recent bytes for diagnostics.
+    seq_trace: SequenceTracer,
+}
+
+impl Default for TmuxControlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequenceTraceable for TmuxControlParser {
+    #[inline]
+    fn seq_tracer(&mut self) -> &mut SequenceTracer {
+        &mut self.seq_trace
+    }
+    #[inline]
+    fn seq_tracer_ref(&self) -> &SequenceTracer {
+        &self.seq_trace
+    }
+}
+
+impl TmuxControlParser {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            line: Vec::new(),
+            overlong: false,
+            pending_esc: false,
+            seq_trace: SequenceTracer::new(),
+        }
+    }
+
+    /// Expose current sequence trace for testing and diagnostics.
+    #[must_use]
+    pub fn trace_str(&self) -> String {
+        self.seq_trace.as_str()
+    }
+
+    /// Push a byte of the control-mode stream.
+    ///
+    /// Emits one `TmuxControlLine` per `\n` (a trailing `\r` is dropped) and
+    /// returns `ParserOutcome::Finished` with `TmuxControlModeEnd` once the
+    /// closing ST arrives.  A partial line still buffered at that point is
+    /// flushed first.  A line longer than [`MAX_LINE_LEN`] is dropped.
+    pub fn tmux_control_parser_inner(
+        &mut self,
+        b: u8,
+        output: &mut Vec<TerminalOutput>,
+    ) -> ParserOutcome {
+        self.append_trace(b);
+
+        if self.pending_esc {
+            self.pending_esc = false;
+            if b == b'\\' {
+                if !self.line.is_empty() && !self.overlong {
+                    output.push(TerminalOutput::TmuxControlLine(std::mem::take(
+                        &mut self.line,
+                    )));
+                }
+                output.push(TerminalOutput::TmuxControlModeEnd);
+                return ParserOutcome::Finished;
+            }
+            // Not an ST: keep the ESC as line content.
+            self.push_line_byte(0x1b);
+        }
+
+        match b {
+            0x1b => self.pending_esc = true,
+            b'\n' if self.overlong => self.overlong = false,
+            b'\n' => {
+                if self.line.last() == Some(&b'\r') {
+                    self.line.pop();
+                }
+                output.push(TerminalOutput::TmuxControlLine(std::mem::take(
+                    &mut self.line,
+                )));
+            }
+            _ => self.push_line_byte(b),
+        }
+
+        ParserOutcome::Continue
+    }
+
+    /// Append `b` to the current line, dropping the line once it passes
+    /// [`MAX_LINE_LEN`].
+    fn push_line_byte(&mut self, b: u8) {
+        if self.overlong {
+            return;
+        }
+        if self.line.len() >= MAX_LINE_LEN {
+            tracing::warn!("tmux control mode: dropping a line longer than {MAX_LINE_LEN} bytes");
+            self.line = Vec::new();
+            self.overlong = true;
+            return;
+        }
+        self.line.push(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_LINE_LEN, TmuxControlParser};
+    use crate::ansi::ParserOutcome;
+    use freminal_common::buffer_states::terminal_output::TerminalOutput;
+
+    fn feed(parser: &mut TmuxControlParser, bytes: &[u8]) -> (Vec<TerminalOutput>, bool) {
+        let mut output = Vec::new();
+        let mut finished = false;
+        for &b in bytes {
+            if parser.tmux_control_parser_inner(b, &mut output) == ParserOutcome::Finished {
+                finished = true;
+            }
+        }
+        (output, finished)
+    }
+
+    #[test]
+    fn emits_one_output_per_line() {
+        let mut parser = TmuxControlParser::new();
+        let (out, finished) = feed(&mut parser, b"%begin 1 2 0\r\n%end 1 2 0\n%out");
+        assert!(!finished);
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::TmuxControlLine(b"%begin 1 2 0".to_vec()),
+                TerminalOutput::TmuxControlLine(b"%end 1 2 0".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn st_flushes_partial_line_and_ends() {
+        let mut parser = TmuxControlParser::new();
+        let (out, finished) = feed(&mut parser, b"%exit\n%tail\x1b\\");
+        assert!(finished);
+        assert_eq!(
+            out,
+            vec![
+                TerminalOutput::TmuxControlLine(b"%exit".to_vec()),
+                TerminalOutput::TmuxControlLine(b"%tail".to_vec()),
+                TerminalOutput::TmuxControlModeEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn esc_not_followed_by_backslash_is_content() {
+        let mut parser = TmuxControlParser::new();
+        let (out, finished) = feed(&mut parser, b"a\x1bb\n");
+        assert!(!finished);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::TmuxControlLine(b"a\x1bb".to_vec())]
+        );
+    }
+
+    #[test]
+    fn overlong_line_is_dropped() {
+        let mut parser = TmuxControlParser::new();
+        let mut bytes = vec![b'x'; MAX_LINE_LEN + 10];
+        bytes.extend_from_slice(b"\n%exit\n");
+        let (out, finished) = feed(&mut parser, &bytes);
+        assert!(!finished);
+        assert_eq!(
+            out,
+            vec![TerminalOutput::TmuxControlLine(b"%exit".to_vec())]
+        );
+        assert!(parser.line.capacity() <= MAX_LINE_LEN);
+    }
+}
@@ -0,0 +1,508 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Conversion between FREC recordings and [asciicast v2] files.
+//!
+//! asciicast describes a single terminal, so export picks one pane and
+//! writes its `PtyOutput`, `PtyInput` and `PaneResize` events as `"o"`,
+//! `"i"` and `"r"` events.  Import goes the other way, producing a
+//! one-window, one-tab, one-pane recording ready for [`write_recording`]
+//! that `freminal --play` and `freminal rec` handle like any other.
+//!
+//! asciicast event data is text, so bytes that are not valid UTF-8 are
+//! replaced with U+FFFD on export.  A multi-byte character split across two
+//! PTY reads is carried over to the next event rather than replaced.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+//! [`write_recording`]: crate::recording::write_recording
+
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+use freminal_common::config::ScrollbackConfig;
+use freminal_common::terminal_size::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use serde_json::Value;
+
+use crate::playback::topology_after;
+use crate::recording::{
+    EventPayload, PaneNodeSnapshot, PaneTreeSnapshot, ParsedRecording, RecordingEvent,
+    RecordingMetadata, TabSnapshot, TopologySnapshot, WindowSnapshot,
+};
+
+/// Pane ID given to the single pane of an imported recording.
+pub const IMPORTED_PANE_ID: u32 = 0;
+
+/// Errors that can occur while converting to or from asciicast.
+#[derive(Debug, thiserror::Error)]
+pub enum AsciicastError {
+    /// I/O error.
+    #[error("asciicast I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// JSON encoding or decoding error.
+    #[error("asciicast JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The requested pane never appears in the recording.
+    #[error("pane {0} does not appear in the recording")]
+    UnknownPane(u32),
+    /// The header line is missing or is not an asciicast v2 header.
+    #[error("invalid asciicast header: {0}")]
+    InvalidHeader(String),
+    /// An event line is malformed.
+    #[error("invalid asciicast event on line {line}: {reason}")]
+    InvalidEvent {
+        /// 1-based line number in the file.
+        line: usize,
+        /// What was wrong with it.
+        reason: String,
+    },
+}
+
+/// Write pane `pane_id` of `recording` as an asciicast v2 file.
+///
+/// The cast starts when the pane does: at zero for a pane in the initial
+/// topology, otherwise at the pane's first event.  The header carries the
+/// pane's size, title and shell at that point.
+///
+/// # Errors
+///
+/// Returns [`AsciicastError::UnknownPane`] if `pane_id` is neither in the
+/// initial topology nor named by any event, or an I/O or JSON error if
+/// writing fails.
+pub fn export_asciicast<W: Write>(
+    recording: &ParsedRecording,
+    pane_id: u32,
+    mut writer: W,
+) -> Result<(), AsciicastError> {
+    let (start, start_us, topology) =
+        if tree_leaf(&recording.metadata.initial_topology, pane_id).is_some() {
+            (0, 0, recording.metadata.initial_topology.clone())
+        } else {
+            let first = recording
+                .events
+                .iter()
+                .position(|event| event.payload.involves_pane(pane_id))
+                .ok_or(AsciicastError::UnknownPane(pane_id))?;
+            (
+                first,
+                recording.events[first].timestamp_us,
+                topology_after(recording, first + 1),
+            )
+        };
+
+    let mut header = serde_json::json!({
+        "version": 2,
+        "width": u32::from(DEFAULT_WIDTH),
+        "height": u32::from(DEFAULT_HEIGHT),
+        "timestamp": recording.metadata.created_at.saturating_add(start_us / 1_000_000),
+        "env": { "TERM": recording.metadata.term },
+    });
+    if let Some(PaneNodeSnapshot::Leaf {
+        cols,
+        rows,
+        shell,
+        title,
+        ..
+    }) = tree_leaf(&topology, pane_id)
+    {
+        header["width"] = (*cols).into();
+        header["height"] = (*rows).into();
+        if !title.is_empty() {
+            header["title"] = title.as_str().into();
+        }
+        if let Some(shell) = shell {
+            header["env"]["SHELL"] = shell.as_str().into();
+        }
+    }
+    serde_json::to_writer(&mut writer, &header)?;
+    writer.write_all(b"\n")?;
+
+    let mut output = Utf8Carry::default();
+    let mut input = Utf8Carry::default();
+    let mut last_seconds = 0.0;
+    for event in &recording.events[start..] {
+        let seconds = micros_to_seconds(event.timestamp_us.saturating_sub(start_us));
+        let (code, data) = match &event.payload {
+            EventPayload::PtyOutput { pane_id: id, data } if *id == pane_id => {
+                ("o", output.decode(data))
+            }
+            EventPayload::PtyInput { pane_id: id, data } if *id == pane_id => {
+                ("i", input.decode(data))
+            }
+            EventPayload::PaneResize {
+                pane_id: id,
+                cols,
+                rows,
+            } if *id == pane_id => ("r", format!("{cols}x{rows}")),
+            _ => continue,
+        };
+        last_seconds = seconds;
+        if !data.is_empty() {
+            write_event(&mut writer, seconds, code, &data)?;
+        }
+    }
+    for (code, carry) in [("o", output), ("i", input)] {
+        if !carry.pending.is_empty() {
+            let data = String::from_utf8_lossy(&carry.pending);
+            write_event(&mut writer, last_seconds, code, &data)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read an asciicast v2 file into a single-pane recording.
+///
+/// `"o"`, `"i"` and `"r"` events become `PtyOutput`, `PtyInput` and
+/// `PaneResize` events for [`IMPORTED_PANE_ID`]; markers and unknown event
+/// codes are skipped.  Timestamps that go backwards are clamped so the
+/// result stays ordered.
+///
+/// # Errors
+///
+/// Returns [`AsciicastError::InvalidHeader`] if the first line is not an
+/// asciicast v2 header, [`AsciicastError::InvalidEvent`] for a malformed
+/// event line, or an I/O or JSON error.
+pub fn import_asciicast<R: BufRead>(
+    reader: R,
+) -> Result<(RecordingMetadata, Vec<RecordingEvent>), AsciicastError> {
+    let mut lines = reader.lines().enumerate();
+    let header = loop {
+        match lines.next() {
+            Some((_, line)) => {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    break serde_json::from_str::<Value>(&line)?;
+                }
+            }
+            None => return Err(AsciicastError::InvalidHeader("empty file".to_owned())),
+        }
+    };
+    let metadata = header_metadata(&header)?;
+
+    let mut events = Vec::new();
+    let mut last_us = 0;
+    for (index, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |reason: &str| AsciicastError::InvalidEvent {
+            line: index + 1,
+            reason: reason.to_owned(),
+        };
+        let value: Value = serde_json::from_str(&line)?;
+        let (Some(time), Some(code), Some(data)) = (
+            value.get(0).and_then(Value::as_f64),
+            value.get(1).and_then(Value::as_str),
+            value.get(2).and_then(Value::as_str),
+        ) else {
+            return Err(invalid("expected [time, code, data]"));
+        };
+        let timestamp_us = seconds_to_micros(time)
+            .ok_or_else(|| invalid("time is not a non-negative number of seconds"))?
+            .max(last_us);
+        let payload = match code {
+            "o" => EventPayload::PtyOutput {
+                pane_id: IMPORTED_PANE_ID,
+                data: data.as_bytes().to_vec(),
+            },
+            "i" => EventPayload::PtyInput {
+                pane_id: IMPORTED_PANE_ID,
+                data: data.as_bytes().to_vec(),
+            },
+            "r" => {
+                let (cols, rows) =
+                    parse_size(data).ok_or_else(|| invalid("resize is not COLSxROWS"))?;
+                EventPayload::PaneResize {
+                    pane_id: IMPORTED_PANE_ID,
+                    cols,
+                    rows,
+                }
+            }
+            _ => continue,
+        };
+        last_us = timestamp_us;
+        events.push(RecordingEvent {
+            timestamp_us,
+            payload,
+        });
+    }
+    Ok((metadata, events))
+}
+
+/// Build the recording metadata for an imported cast from its header.
+fn header_metadata(header: &Value) -> Result<RecordingMetadata, AsciicastError> {
+    if header.get("version").and_then(Value::as_u64) != Some(2) {
+        return Err(AsciicastError::InvalidHeader(
+            "only asciicast version 2 is supported".to_owned(),
+        ));
+    }
+    let dimension = |key: &str| {
+        header
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| AsciicastError::InvalidHeader(format!("missing or invalid {key}")))
+    };
+    let (cols, rows) = (dimension("width")?, dimension("height")?);
+    let env = |key: &str| {
+        header
+            .get("env")
+            .and_then(|env| env.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+    };
+
+    let pane = PaneNodeSnapshot::Leaf {
+        pane_id: IMPORTED_PANE_ID,
+        cols,
+        rows,
+        cwd: None,
+        shell: env("SHELL"),
+        title: header
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned(),
+    };
+    Ok(RecordingMetadata {
+        freminal_version: env!("CARGO_PKG_VERSION").to_owned(),
+        created_at: header.get("timestamp").and_then(Value::as_u64).unwrap_or(0),
+        term: env("TERM").unwrap_or_else(|| "xterm-256color".to_owned()),
+        initial_topology: TopologySnapshot {
+            windows: vec![WindowSnapshot {
+                window_id: 0,
+                position: None,
+                size: (0, 0),
+                tabs: vec![TabSnapshot {
+                    tab_id: 0,
+                    window_id: 0,
+                    pane_tree: PaneTreeSnapshot { node: pane },
+                    active_pane: IMPORTED_PANE_ID,
+                    zoomed_pane: None,
+                }],
+                active_tab: 0,
+            }],
+        },
+        scrollback_limit: u32::try_from(ScrollbackConfig::default().limit).unwrap_or(u32::MAX),
+    })
+}
+
+/// Write one `[time, code, data]` event line.
+fn write_event<W: Write>(
+    writer: &mut W,
+    seconds: f64,
+    code: &str,
+    data: &str,
+) -> Result<(), AsciicastError> {
+    serde_json::to_writer(&mut *writer, &(seconds, code, data))?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// The leaf for `pane_id` anywhere in `topology`.
+fn tree_leaf(topology: &TopologySnapshot, pane_id: u32) -> Option<&PaneNodeSnapshot> {
+    fn walk(node: &PaneNodeSnapshot, pane_id: u32) -> Option<&PaneNodeSnapshot> {
+        match node {
+            PaneNodeSnapshot::Leaf { pane_id: id, .. } => (*id == pane_id).then_some(node),
+            PaneNodeSnapshot::Split { first, second, .. } => {
+                walk(first, pane_id).or_else(|| walk(second, pane_id))
+            }
+        }
+    }
+
+    topology
+        .windows
+        .iter()
+        .flat_map(|window| &window.tabs)
+        .find_map(|tab| walk(&tab.pane_tree.node, pane_id))
+}
+
+/// Parse an asciicast resize payload (`"132x50"`).
+fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let (cols, rows) = size.split_once('x')?;
+    Some((cols.parse().ok()?, rows.parse().ok()?))
+}
+
+const fn micros_to_seconds(us: u64) -> f64 {
+    Duration::from_micros(us).as_secs_f64()
+}
+
+/// Seconds to the nearest microsecond, or `None` if negative or not finite.
+fn seconds_to_micros(seconds: f64) -> Option<u64> {
+    let nanos = Duration::try_from_secs_f64(seconds).ok()?.as_nanos();
+    u64::try_from(nanos.saturating_add(500) / 1_000).ok()
+}
+
+/// Decodes a byte stream to text, holding back a trailing partial UTF-8
+/// character until the rest of it arrives.
+#[derive(Default)]
+struct Utf8Carry {
+    pending: Vec<u8>,
+}
+
+impl Utf8Carry {
+    fn decode(&mut self, data: &[u8]) -> String {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(data);
+        let complete = bytes.len() - incomplete_tail(&bytes);
+        self.pending = bytes.split_off(complete);
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+/// Length of a UTF-8 sequence that was started but not finished at the
+/// end of `bytes`, or 0.
+fn incomplete_tail(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(3) {
+        let byte = bytes[bytes.len() - back];
+        if byte & 0xC0 == 0x80 {
+            continue;
+        }
+        let needed = match byte {
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return 0,
+        };
+        return if needed > back { back } else { 0 };
+    }
+    0
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::recording::{parse_recording_from_bytes, write_recording};
+
+    const CAST: &str = r#"{"version": 2, "width": 80, "height": 24, "timestamp": 1700000000, "title": "demo", "env": {"SHELL": "/bin/zsh", "TERM": "xterm-256color"}}
+[0.5, "o", "$ "]
+[1.25, "i", "ls\r"]
+[1.3, "o", "ls\r\n\u001b[1mCargo.toml\u001b[0m\r\n"]
+[2.0, "m", "chapter"]
+[2.000001, "r", "132x50"]
+[3.75, "o", "$ "]
+"#;
+
+    #[test]
+    fn import_builds_single_pane_recording() {
+        let (metadata, events) = import_asciicast(CAST.as_bytes()).unwrap();
+        assert_eq!(metadata.created_at, 1_700_000_000);
+        assert_eq!(metadata.term, "xterm-256color");
+        let tab = &metadata.initial_topology.windows[0].tabs[0];
+        assert_eq!(
+            tab.pane_tree.node,
+            PaneNodeSnapshot::Leaf {
+                pane_id: IMPORTED_PANE_ID,
+                cols: 80,
+                rows: 24,
+                cwd: None,
+                shell: Some("/bin/zsh".to_owned()),
+                title: "demo".to_owned(),
+            }
+        );
+        assert_eq!(events.len(), 5, "the marker is skipped");
+        assert_eq!(events[1].timestamp_us, 1_250_000);
+        assert_eq!(
+            events[3].payload,
+            EventPayload::PaneResize {
+                pane_id: IMPORTED_PANE_ID,
+                cols: 132,
+                rows: 50,
+            }
+        );
+        assert_eq!(events[3].timestamp_us, 2_000_001);
+    }
+
+    #[test]
+    fn cast_round_trips_through_frec() {
+        let (metadata, events) = import_asciicast(CAST.as_bytes()).unwrap();
+        let mut frec = std::io::Cursor::new(Vec::new());
+        write_recording(&mut frec, &metadata, &events).unwrap();
+        let recording = parse_recording_from_bytes(frec.get_ref()).unwrap();
+
+        let mut cast = Vec::new();
+        export_asciicast(&recording, IMPORTED_PANE_ID, &mut cast).unwrap();
+        let (metadata_again, events_again) = import_asciicast(cast.as_slice()).unwrap();
+        assert_eq!(metadata_again.initial_topology, metadata.initial_topology);
+        assert_eq!(metadata_again.created_at, metadata.created_at);
+        assert_eq!(events_again, events);
+    }
+
+    #[test]
+    fn export_starts_at_pane_creation_and_joins_split_characters() {
+        let metadata = RecordingMetadata {
+            freminal_version: "0.7.0".to_owned(),
+            created_at: 1_700_000_000,
+            term: "xterm-256color".to_owned(),
+            initial_topology: TopologySnapshot { windows: vec![] },
+            scrollback_limit: 1_000,
+        };
+        let event = |timestamp_us, payload| RecordingEvent {
+            timestamp_us,
+            payload,
+        };
+        let events = vec![
+            event(
+                1_000_000,
+                EventPayload::TabCreate {
+                    window_id: 0,
+                    tab_id: 0,
+                    pane_id: 4,
+                    cols: 100,
+                    rows: 30,
+                },
+            ),
+            event(
+                1_500_000,
+                EventPayload::PtyOutput {
+                    pane_id: 4,
+                    data: b"caf\xC3".to_vec(),
+                },
+            ),
+            event(
+                2_000_000,
+                EventPayload::PtyOutput {
+                    pane_id: 4,
+                    data: b"\xA9\r\n".to_vec(),
+                },
+            ),
+        ];
+        let mut frec = std::io::Cursor::new(Vec::new());
+        write_recording(&mut frec, &metadata, &events).unwrap();
+        let recording = parse_recording_from_bytes(frec.get_ref()).unwrap();
+
+        let mut cast = Vec::new();
+        export_asciicast(&recording, 4, &mut cast).unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        let header: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(
+            (header["width"].as_u64(), header["height"].as_u64()),
+            (Some(100), Some(30))
+        );
+        assert_eq!(header["timestamp"].as_u64(), Some(1_700_000_001));
+        assert_eq!(&lines[1..], [r#"[0.5,"o","caf"]"#, r#"[1.0,"o","é\r\n"]"#]);
+
+        assert!(matches!(
+            export_asciicast(&recording, 9, Vec::new()),
+            Err(AsciicastError::UnknownPane(9))
+        ));
+    }
+
+    #[test]
+    fn import_rejects_other_versions_and_bad_events() {
+        assert!(matches!(
+            import_asciicast(r#"{"version": 1, "width": 80, "height": 24}"#.as_bytes()),
+            Err(AsciicastError::InvalidHeader(_))
+        ));
+        let bad = "{\"version\": 2, \"width\": 80, \"height\": 24}\n[-1, \"o\", \"x\"]\n";
+        assert!(matches!(
+            import_asciicast(bad.as_bytes()),
+            Err(AsciicastError::InvalidEvent { line: 2, .. })
+        ));
+    }
+}
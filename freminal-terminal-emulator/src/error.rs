@@ -57,6 +57,7 @@ pub enum InterfaceError {
     #[error("failed to send to PTY write channel: {0}")]
     PtySendFailed(String),
     /// PTY initialization failed while constructing a new emulator.
+    #[cfg(feature = "pty")]
     #[error("failed to initialize PTY")]
     PtyInit(#[from] crate::io::pty::PtyInitError),
 }
@@ -69,6 +70,23 @@ pub enum InternalStateError {
     PtySendFailed(String),
 }
 
+/// Errors produced by the tmux control-mode client in
+/// [`crate::tmux_control`].
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum TmuxControlError {
+    /// A `window_layout` string did not match tmux's layout grammar.
+    #[error("invalid tmux layout {layout:?} at byte {position}")]
+    InvalidLayout {
+        /// The layout string as received.
+        layout: String,
+        /// Byte offset where parsing failed.
+        position: usize,
+    },
+    /// Failed to send a command to the gateway PTY.
+    #[error("failed to send tmux command: {0}")]
+    SendFailed(String),
+}
+
 #[derive(Debug, Error, Eq, PartialEq, Clone)]
 #[error(transparent)]
 pub enum ParserFailures {
@@ -140,4 +158,52 @@ pub enum ParserFailures {
     UnhandledCBTCommand(String),
     #[error("Invalid repeat character (REP) sequence: {0}")]
     UnhandledREPCommand(String),
+    #[error("Invalid copy rectangular area (DECCRA) sequence: {0}")]
+    UnhandledDECCRACommand(String),
+    #[error("Invalid fill rectangular area (DECFRA) sequence: {0}")]
+    UnhandledDECFRACommand(String),
+    #[error("Invalid erase rectangular area (DECERA) sequence: {0}")]
+    UnhandledDECERACommand(String),
+    #[error("Invalid selective erase rectangular area (DECSERA) sequence: {0}")]
+    UnhandledDECSERACommand(String),
+    #[error("Invalid change attributes in rectangular area (DECCARA) sequence: {0}")]
+    UnhandledDECCARACommand(String),
+    #[error("Invalid reverse attributes in rectangular area (DECRARA) sequence: {0}")]
+    UnhandledDECRARACommand(String),
+    #[error("Invalid select attribute change extent (DECSACE) sequence: {0}")]
+    UnhandledDECSACECommand(String),
+    #[error("Invalid request presentation state report (DECRQPSR) sequence: {0}")]
+    UnhandledDECRQPSRCommand(String),
+    #[error("Invalid select status line type (DECSSDT) sequence: {0}")]
+    UnhandledDECSSDTCommand(String),
+    #[error("Invalid select active status display (DECSASD) sequence: {0}")]
+    UnhandledDECSASDCommand(String),
+    #[error("Invalid insert column (DECIC) sequence: {0}")]
+    UnhandledDECICCommand(String),
+    #[error("Invalid delete column (DECDC) sequence: {0}")]
+    UnhandledDECDCCommand(String),
+    #[error("Invalid enable locator reporting (DECELR) sequence: {0}")]
+    UnhandledDECELRCommand(String),
+    #[error("Invalid select locator events (DECSLE) sequence: {0}")]
+    UnhandledDECSLECommand(String),
+    #[error("Invalid enable filter rectangle (DECEFR) sequence: {0}")]
+    UnhandledDECEFRCommand(String),
+    #[error("Invalid request checksum of rectangular area (DECRQCRA) sequence: {0}")]
+    UnhandledDECRQCRACommand(String),
+    #[error("Invalid select checksum extension (XTCHECKSUM) sequence: {0}")]
+    UnhandledXTCHECKSUMCommand(String),
+    #[error("Invalid select character protection attribute (DECSCA) sequence: {0}")]
+    UnhandledDECSCACommand(String),
+    #[error("Invalid selective erase in display (DECSED) sequence: {0}")]
+    UnhandledDECSEDCommand(String),
+    #[error("Invalid selective erase in line (DECSEL) sequence: {0}")]
+    UnhandledDECSELCommand(String),
+    #[error("Invalid push SGR attributes (XTPUSHSGR) sequence: {0}")]
+    UnhandledXTPUSHSGRCommand(String),
+    #[error("Invalid report SGR attributes (XTREPORTSGR) sequence: {0}")]
+    UnhandledXTREPORTSGRCommand(String),
+    #[error("Invalid push colors (XTPUSHCOLORS) sequence: {0}")]
+    UnhandledXTPUSHCOLORSCommand(String),
+    #[error("Invalid pop colors (XTPOPCOLORS) sequence: {0}")]
+    UnhandledXTPOPCOLORSCommand(String),
 }
@@ -0,0 +1,542 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Always-on in-memory recording ("flight recorder").
+//!
+//! [`start_flight_recorder`] returns a [`FlightRecorder`] whose
+//! [`RecordingHandle`] is fed exactly like a file recording, but whose events
+//! go into a ring that only keeps the most recent stretch of the session:
+//! at most [`FlightRecorderLimits::max_duration`] of events and at most
+//! [`FlightRecorderLimits::max_bytes`] of payload, whichever is smaller.
+//!
+//! Events falling off the front of the ring are applied to a topology
+//! snapshot, so the ring always knows the window/tab/pane tree as it stood
+//! at its first retained event.  [`FlightRecorder::dump`] writes that tree
+//! as the header's initial topology, followed by the retained events
+//! rebased to start at zero, through [`write_recording`] — so a dump is an
+//! ordinary FREC v2 file with its own seek index and footer.
+//!
+//! Each window keeps its own ring in [`WindowFlightRecorders`], so a busy
+//! window cannot push a quiet one's history out, and a dump only carries
+//! the window it was taken from.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::playback::{advance_topology, leaf_sizes};
+use crate::recording::{
+    EventPayload, RecordingError, RecordingEvent, RecordingHandle, RecordingMetadata,
+    TopologySnapshot, write_recording,
+};
+
+/// How much of the session a [`FlightRecorder`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlightRecorderLimits {
+    /// Span between the oldest and newest retained event.
+    pub max_duration: Duration,
+    /// Approximate memory held by retained events, in bytes.
+    pub max_bytes: usize,
+}
+
+/// A running flight recorder.
+///
+/// Dropping it (and every clone of [`Self::handle`]) stops the thread that
+/// fills the ring.
+pub struct FlightRecorder {
+    handle: RecordingHandle,
+    ring: Arc<Mutex<FlightRing>>,
+    metadata: RecordingMetadata,
+}
+
+/// Start a flight recorder.
+///
+/// `metadata` describes the session at this instant; its
+/// `initial_topology` is the starting point the ring tracks from.  Events
+/// are queued on a channel bounded to `channel_capacity` and moved into the
+/// ring by a dedicated thread, so emitting never blocks.
+///
+/// # Errors
+///
+/// Returns an error if the ring thread cannot be spawned.
+pub fn start_flight_recorder(
+    metadata: RecordingMetadata,
+    limits: FlightRecorderLimits,
+    channel_capacity: usize,
+) -> Result<FlightRecorder, RecordingError> {
+    let (tx, rx) = crossbeam_channel::bounded::<RecordingEvent>(channel_capacity);
+    let ring = Arc::new(Mutex::new(FlightRing::new(
+        metadata.initial_topology.clone(),
+        limits,
+    )));
+
+    let thread_ring = Arc::clone(&ring);
+    std::thread::Builder::new()
+        .name("freminal-flight-recorder".to_string())
+        .spawn(move || {
+            // Exits once every handle clone is dropped.
+            while let Ok(event) = rx.recv() {
+                let mut ring = thread_ring.lock().unwrap_or_else(PoisonError::into_inner);
+                ring.push(event);
+                // Take whatever else is queued under the same lock.
+                for event in rx.try_iter() {
+                    ring.push(event);
+                }
+            }
+        })
+        .map_err(|e| RecordingError::Io(std::io::Error::other(e)))?;
+
+    Ok(FlightRecorder {
+        handle: RecordingHandle::new(tx),
+        ring,
+        metadata,
+    })
+}
+
+impl FlightRecorder {
+    /// The handle to emit events into the ring.
+    #[must_use]
+    pub const fn handle(&self) -> &RecordingHandle {
+        &self.handle
+    }
+
+    /// The limits the ring was started with.
+    #[must_use]
+    pub fn limits(&self) -> FlightRecorderLimits {
+        self.lock().limits
+    }
+
+    /// The retained events as a recording of their own, ready for
+    /// [`write_recording`].
+    ///
+    /// The first retained event is at zero, the initial topology is the
+    /// tree as it stood at that event, and `created_at` is moved forward to
+    /// match.
+    #[must_use]
+    pub fn snapshot(&self) -> (RecordingMetadata, Vec<RecordingEvent>) {
+        let (start_us, topology, events) = {
+            let ring = self.lock();
+            let start_us = ring.events.front().map_or(0, |event| event.timestamp_us);
+            let events: Vec<RecordingEvent> = ring
+                .events
+                .iter()
+                .map(|event| RecordingEvent {
+                    timestamp_us: event.timestamp_us - start_us,
+                    payload: event.payload.clone(),
+                })
+                .collect();
+            (start_us, ring.topology.clone(), events)
+        };
+        let metadata = RecordingMetadata {
+            created_at: self
+                .metadata
+                .created_at
+                .saturating_add(start_us / 1_000_000),
+            initial_topology: topology,
+            ..self.metadata.clone()
+        };
+        (metadata, events)
+    }
+
+    /// Write the retained events to a new FREC file at `path` and return
+    /// how many were written.
+    ///
+    /// The ring keeps running; dumping again later writes the then-current
+    /// window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn dump(&self, path: &Path) -> Result<usize, RecordingError> {
+        let (metadata, events) = self.snapshot();
+        let file = std::fs::File::create(path)?;
+        write_recording(BufWriter::new(file), &metadata, &events)?;
+        Ok(events.len())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FlightRing> {
+        self.ring.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// One [`FlightRecorder`] per window, keyed by recording window ID.
+#[derive(Default)]
+pub struct WindowFlightRecorders {
+    recorders: BTreeMap<u32, FlightRecorder>,
+}
+
+impl WindowFlightRecorders {
+    /// Start a ring for `window_id`, replacing (and discarding) any ring the
+    /// window already had.
+    ///
+    /// `metadata` may describe the whole session; only `window_id`'s part of
+    /// its `initial_topology` is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ring thread cannot be spawned.  The window's
+    /// previous ring, if any, is gone either way.
+    pub fn open(
+        &mut self,
+        window_id: u32,
+        mut metadata: RecordingMetadata,
+        limits: FlightRecorderLimits,
+        channel_capacity: usize,
+    ) -> Result<&FlightRecorder, RecordingError> {
+        self.recorders.remove(&window_id);
+        metadata
+            .initial_topology
+            .windows
+            .retain(|window| window.window_id == window_id);
+        let recorder = start_flight_recorder(metadata, limits, channel_capacity)?;
+        Ok(self.recorders.entry(window_id).or_insert(recorder))
+    }
+
+    /// Stop and drop `window_id`'s ring.
+    pub fn close(&mut self, window_id: u32) {
+        self.recorders.remove(&window_id);
+    }
+
+    /// Stop and drop every ring.
+    pub fn clear(&mut self) {
+        self.recorders.clear();
+    }
+
+    /// The ring of `window_id`, if it has one.
+    #[must_use]
+    pub fn get(&self, window_id: u32) -> Option<&FlightRecorder> {
+        self.recorders.get(&window_id)
+    }
+
+    /// The limits the rings run with, or `None` when no window has one.
+    #[must_use]
+    pub fn limits(&self) -> Option<FlightRecorderLimits> {
+        self.recorders.values().next().map(FlightRecorder::limits)
+    }
+
+    /// `true` when no window has a ring.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.recorders.is_empty()
+    }
+}
+
+/// The retained events plus the topology at the first of them.
+struct FlightRing {
+    limits: FlightRecorderLimits,
+    /// Tree as it stood just before `events.front()`.
+    topology: TopologySnapshot,
+    /// Size of each pane when it appeared, for [`advance_topology`].
+    initial_sizes: BTreeMap<u32, (u32, u32)>,
+    events: VecDeque<RecordingEvent>,
+    bytes: usize,
+}
+
+impl FlightRing {
+    fn new(topology: TopologySnapshot, limits: FlightRecorderLimits) -> Self {
+        Self {
+            limits,
+            initial_sizes: leaf_sizes(&topology),
+            topology,
+            events: VecDeque::new(),
+            bytes: 0,
+        }
+    }
+
+    fn push(&mut self, event: RecordingEvent) {
+        self.bytes += event_bytes(&event.payload);
+        let newest_us = event.timestamp_us;
+        self.events.push_back(event);
+
+        let max_us = u64::try_from(self.limits.max_duration.as_micros()).unwrap_or(u64::MAX);
+        while let Some(oldest) = self.events.front() {
+            if self.bytes <= self.limits.max_bytes
+                && newest_us.saturating_sub(oldest.timestamp_us) <= max_us
+            {
+                break;
+            }
+            if let Some(evicted) = self.events.pop_front() {
+                self.bytes -= event_bytes(&evicted.payload);
+                advance_topology(
+                    &mut self.topology,
+                    &mut self.initial_sizes,
+                    &evicted.payload,
+                );
+            }
+        }
+    }
+}
+
+/// Approximate memory held by one event: the event itself plus its heap
+/// data.
+const fn event_bytes(payload: &EventPayload) -> usize {
+    let heap = match payload {
+        EventPayload::PtyOutput { data, .. }
+        | EventPayload::PtyInput { data, .. }
+        | EventPayload::ClipboardPaste { data, .. } => data.len(),
+        EventPayload::KeyboardInput {
+            key_name, encoded, ..
+        } => key_name.len() + encoded.len(),
+        EventPayload::ThemeChange { theme_name } => theme_name.len(),
+        _ => 0,
+    };
+    std::mem::size_of::<RecordingEvent>() + heap
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::recording::{
+        PaneNodeSnapshot, PaneTreeSnapshot, RecordingSplitDirection, TabSnapshot, WindowSnapshot,
+        parse_recording,
+    };
+
+    fn one_pane_topology() -> TopologySnapshot {
+        TopologySnapshot {
+            windows: vec![WindowSnapshot {
+                window_id: 0,
+                position: None,
+                size: (800, 600),
+                tabs: vec![TabSnapshot {
+                    tab_id: 0,
+                    window_id: 0,
+                    pane_tree: PaneTreeSnapshot {
+                        node: PaneNodeSnapshot::Leaf {
+                            pane_id: 1,
+                            cols: 80,
+                            rows: 24,
+                            cwd: None,
+                            shell: None,
+                            title: String::new(),
+                        },
+                    },
+                    active_pane: 1,
+                    zoomed_pane: None,
+                }],
+                active_tab: 0,
+            }],
+        }
+    }
+
+    fn output(timestamp_us: u64, pane_id: u32, len: usize) -> RecordingEvent {
+        RecordingEvent {
+            timestamp_us,
+            payload: EventPayload::PtyOutput {
+                pane_id,
+                data: vec![b'x'; len],
+            },
+        }
+    }
+
+    fn ring(max_duration: Duration, max_bytes: usize) -> FlightRing {
+        FlightRing::new(
+            one_pane_topology(),
+            FlightRecorderLimits {
+                max_duration,
+                max_bytes,
+            },
+        )
+    }
+
+    #[test]
+    fn ring_drops_events_older_than_max_duration() {
+        let mut ring = ring(Duration::from_secs(2), usize::MAX);
+        for i in 0..10 {
+            ring.push(output(i * 500_000, 1, 1));
+        }
+        let kept: Vec<u64> = ring.events.iter().map(|e| e.timestamp_us).collect();
+        assert_eq!(
+            kept,
+            [2_500_000, 3_000_000, 3_500_000, 4_000_000, 4_500_000]
+        );
+    }
+
+    #[test]
+    fn ring_stays_under_max_bytes() {
+        let per_event = event_bytes(&output(0, 1, 1_000).payload);
+        let mut ring = ring(Duration::from_hours(1), per_event * 3);
+        for i in 0..100 {
+            ring.push(output(i, 1, 1_000));
+            assert!(ring.bytes <= per_event * 3);
+        }
+        assert_eq!(ring.events.len(), 3);
+        assert_eq!(ring.bytes, per_event * 3);
+    }
+
+    #[test]
+    fn evicted_topology_changes_move_into_the_base_snapshot() {
+        let mut ring = ring(Duration::from_secs(1), usize::MAX);
+        ring.push(RecordingEvent {
+            timestamp_us: 0,
+            payload: EventPayload::PaneSplit {
+                window_id: 0,
+                parent_pane: 1,
+                new_pane: 2,
+                direction: RecordingSplitDirection::Horizontal,
+                ratio: 0.5,
+                cols: 40,
+                rows: 24,
+            },
+        });
+        ring.push(output(5_000_000, 2, 4));
+
+        assert_eq!(ring.events.len(), 1);
+        let node = &ring.topology.windows[0].tabs[0].pane_tree.node;
+        assert_eq!(node.pane_ids(), [1, 2]);
+    }
+
+    #[test]
+    fn dump_writes_a_rebased_recording_with_seek_index() {
+        let metadata = RecordingMetadata {
+            freminal_version: "0.7.0".to_string(),
+            created_at: 1_700_000_000,
+            term: "xterm-256color".to_string(),
+            initial_topology: one_pane_topology(),
+            scrollback_limit: 1_000,
+        };
+        let recorder = start_flight_recorder(
+            metadata,
+            FlightRecorderLimits {
+                max_duration: Duration::from_secs(3),
+                max_bytes: usize::MAX,
+            },
+            64,
+        )
+        .unwrap();
+        {
+            let mut ring = recorder.lock();
+            for i in 0..20 {
+                ring.push(output(i * 500_000, 1, 8));
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flight.frec");
+        assert_eq!(recorder.dump(&path).unwrap(), 7);
+
+        let parsed = parse_recording(&path).unwrap();
+        assert_eq!(parsed.events.len(), 7);
+        assert_eq!(parsed.events[0].timestamp_us, 0);
+        assert_eq!(parsed.total_duration_us, 3_000_000);
+        assert_eq!(parsed.metadata.created_at, 1_700_000_006);
+        assert_eq!(parsed.metadata.initial_topology, one_pane_topology());
+        assert_eq!(parsed.seek_index.len(), 4);
+        assert_eq!(parsed.events_until(1_000_000), 3);
+    }
+
+    #[test]
+    fn emitted_events_reach_the_ring() {
+        let recorder = start_flight_recorder(
+            RecordingMetadata {
+                freminal_version: "0.7.0".to_string(),
+                created_at: 0,
+                term: "xterm-256color".to_string(),
+                initial_topology: TopologySnapshot { windows: vec![] },
+                scrollback_limit: 1_000,
+            },
+            FlightRecorderLimits {
+                max_duration: Duration::from_mins(1),
+                max_bytes: usize::MAX,
+            },
+            64,
+        )
+        .unwrap();
+        recorder.handle().emit(EventPayload::BellEvent {
+            pane_id: 1,
+            bell_type: 0,
+        });
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while recorder.snapshot().1.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(recorder.snapshot().1.len(), 1);
+    }
+
+    #[test]
+    fn each_window_keeps_its_own_history() {
+        let mut topology = one_pane_topology();
+        let mut second = topology.windows[0].clone();
+        second.window_id = 1;
+        second.tabs[0].window_id = 1;
+        second.tabs[0].pane_tree.node = PaneNodeSnapshot::Leaf {
+            pane_id: 2,
+            cols: 80,
+            rows: 24,
+            cwd: None,
+            shell: None,
+            title: String::new(),
+        };
+        second.tabs[0].active_pane = 2;
+        topology.windows.push(second);
+        let metadata = RecordingMetadata {
+            freminal_version: "0.7.0".to_string(),
+            created_at: 0,
+            term: "xterm-256color".to_string(),
+            initial_topology: topology,
+            scrollback_limit: 1_000,
+        };
+        let per_event = event_bytes(&output(0, 1, 100).payload);
+        let limits = FlightRecorderLimits {
+            max_duration: Duration::from_hours(1),
+            max_bytes: per_event * 4,
+        };
+
+        let mut recorders = WindowFlightRecorders::default();
+        recorders.open(0, metadata.clone(), limits, 64).unwrap();
+        recorders.open(1, metadata, limits, 64).unwrap();
+
+        // A quiet window 1 next to a busy window 0.
+        recorders
+            .get(1)
+            .unwrap()
+            .handle()
+            .emit(EventPayload::PtyOutput {
+                pane_id: 2,
+                data: vec![b'y'; 100],
+            });
+        for _ in 0..50 {
+            recorders
+                .get(0)
+                .unwrap()
+                .handle()
+                .emit(EventPayload::PtyOutput {
+                    pane_id: 1,
+                    data: vec![b'x'; 100],
+                });
+        }
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let events = |id: u32| recorders.get(id).unwrap().snapshot().1;
+        while (events(0).len() < 4 || events(1).is_empty()) && std::time::Instant::now() < deadline
+        {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let (busy_meta, busy) = recorders.get(0).unwrap().snapshot();
+        assert_eq!(busy.len(), 4);
+        assert!(
+            busy.iter()
+                .all(|e| matches!(e.payload, EventPayload::PtyOutput { pane_id: 1, .. }))
+        );
+        assert_eq!(busy_meta.initial_topology.windows.len(), 1);
+        assert_eq!(busy_meta.initial_topology.windows[0].window_id, 0);
+
+        let (quiet_meta, quiet) = recorders.get(1).unwrap().snapshot();
+        assert_eq!(quiet.len(), 1);
+        assert!(matches!(
+            quiet[0].payload,
+            EventPayload::PtyOutput { pane_id: 2, .. }
+        ));
+        assert_eq!(quiet_meta.initial_topology.windows.len(), 1);
+        assert_eq!(quiet_meta.initial_topology.windows[0].window_id, 1);
+
+        recorders.close(0);
+        assert!(recorders.get(0).is_none());
+        assert_eq!(recorders.get(1).unwrap().snapshot().1.len(), 1);
+    }
+}
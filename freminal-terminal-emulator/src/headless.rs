@@ -0,0 +1,440 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Windowless terminals for rendering a screen without a GUI.
+//!
+//! [`HeadlessTerminal`] wraps a [`TerminalEmulator::new_headless`] emulator
+//! at a fixed size and theme. The `run_*` functions feed it from a program
+//! on a real PTY (with the `pty` feature), from a byte stream, or from one
+//! pane of a FREC recording, and stop when the input ends, a timeout passes,
+//! or the screen text matches a regex. [`crate::screen_export`] turns the final snapshot into
+//! text, ANSI, HTML, or SVG.
+
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Receiver, select};
+#[cfg(feature = "pty")]
+use freminal_common::pty_write::FreminalTerminalSize;
+use freminal_common::pty_write::PtyWrite;
+use freminal_common::terminal_size::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use freminal_common::themes::ThemePalette;
+use regex::Regex;
+
+use crate::interface::TerminalEmulator;
+#[cfg(feature = "pty")]
+use crate::io::PtyRead;
+#[cfg(feature = "pty")]
+use crate::io::pty::{PtyInitError, PtySpawnConfig, run_terminal};
+use crate::playback::leaf_sizes;
+use crate::recording::{EventPayload, ParsedRecording};
+use crate::screen_export;
+use crate::snapshot::TerminalSnapshot;
+
+/// How long to keep reading after a program exits, to collect output still
+/// buffered in the PTY.
+#[cfg(feature = "pty")]
+const EXIT_DRAIN: Duration = Duration::from_millis(100);
+
+/// Bytes read from a stream at a time.
+const STREAM_CHUNK: usize = 4096;
+
+/// Errors from driving a [`HeadlessTerminal`].
+#[derive(Debug, thiserror::Error)]
+pub enum HeadlessError {
+    /// The program could not be started on a PTY.
+    #[cfg(feature = "pty")]
+    #[error("failed to start program: {0}")]
+    Spawn(#[from] PtyInitError),
+    /// Reading the input stream failed.
+    #[error("failed to read input: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Why a `run_*` function stopped feeding the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The program exited.
+    Exited,
+    /// The stream or recording ran out.
+    EndOfInput,
+    /// The timeout passed first.
+    TimedOut,
+    /// The screen text matched the `until` regex.
+    Matched,
+}
+
+/// When to stop feeding a [`HeadlessTerminal`] before its input ends.
+#[derive(Debug, Clone, Default)]
+pub struct StopConditions {
+    /// Stop after this long. For recordings this is measured on the
+    /// recording's clock rather than the wall clock.
+    pub timeout: Option<Duration>,
+    /// Stop as soon as [`HeadlessTerminal::screen_text`] matches.
+    pub until: Option<Regex>,
+}
+
+/// A terminal emulator with no window and no PTY.
+pub struct HeadlessTerminal {
+    emulator: TerminalEmulator,
+    responses: Receiver<PtyWrite>,
+}
+
+impl HeadlessTerminal {
+    /// Create a `cols` x `rows` terminal drawing with `theme`.
+    #[must_use]
+    pub fn new(cols: usize, rows: usize, theme: &'static ThemePalette) -> Self {
+        let (mut emulator, responses) = TerminalEmulator::new_headless(None);
+        emulator.internal.handler.set_theme(theme);
+        emulator.internal.set_win_size(cols, rows, 0, 0);
+        Self {
+            emulator,
+            responses,
+        }
+    }
+
+    /// Process output from the program.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.emulator.handle_incoming_data(data);
+    }
+
+    /// Change the terminal size.
+    pub fn resize(&mut self, cols: usize, rows: usize) {
+        self.emulator.internal.set_win_size(cols, rows, 0, 0);
+    }
+
+    /// The terminal size as `(cols, rows)`.
+    #[must_use]
+    pub const fn size(&mut self) -> (usize, usize) {
+        self.emulator.internal.win_size()
+    }
+
+    /// Bytes the terminal wants to send back to the program (replies to
+    /// device-attribute and cursor-position queries, and so on) since the
+    /// last call.
+    #[must_use]
+    pub fn take_responses(&self) -> Vec<u8> {
+        self.responses
+            .try_iter()
+            .filter_map(|write| match write {
+                PtyWrite::Write(bytes) => Some(bytes),
+                PtyWrite::Resize(_) => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The current screen.
+    pub fn snapshot(&mut self) -> TerminalSnapshot {
+        self.emulator.build_snapshot()
+    }
+
+    /// The current screen as plain text (see [`screen_export::to_text`]).
+    pub fn screen_text(&mut self) -> String {
+        screen_export::to_text(&self.snapshot())
+    }
+
+    fn matches(&mut self, stop: &StopConditions) -> bool {
+        stop.until
+            .as_ref()
+            .is_some_and(|until| until.is_match(&self.screen_text()))
+    }
+}
+
+/// Run `program` with `args` on a PTY the size of `term` and feed it its
+/// output until it exits or `stop` triggers.
+///
+/// The program gets `TERM=xterm-256color`, like a pane in a window, and its
+/// queries are answered. It is sent `SIGHUP` (the PTY is closed) when this
+/// returns while it is still running.
+///
+/// # Errors
+///
+/// Returns [`HeadlessError::Spawn`] when the PTY cannot be opened or the
+/// program cannot be started.
+#[cfg(feature = "pty")]
+pub fn run_command(
+    term: &mut HeadlessTerminal,
+    program: &str,
+    args: &[String],
+    stop: &StopConditions,
+) -> Result<StopReason, HeadlessError> {
+    let (cols, rows) = term.size();
+    let (write_tx, write_rx) = crossbeam_channel::unbounded::<PtyWrite>();
+    let (read_tx, read_rx) = crossbeam_channel::unbounded::<PtyRead>();
+    let pty = run_terminal(
+        write_rx,
+        read_tx,
+        PtySpawnConfig {
+            command: Some((program.to_owned(), args.to_vec())),
+            shell: None,
+            cwd: None,
+            extra_env: None,
+            set_term_program: true,
+        },
+        None,
+        &FreminalTerminalSize {
+            width: cols,
+            height: rows,
+            pixel_width: 0,
+            pixel_height: 0,
+        },
+        0,
+    )?;
+
+    let deadline = stop.timeout.map(|timeout| Instant::now() + timeout);
+    let mut exited = false;
+    loop {
+        let timer = deadline.map_or_else(crossbeam_channel::never, crossbeam_channel::at);
+        // Once the program has exited, only wait until its remaining output
+        // has been quiet for a moment.
+        let (exit, drain) = if exited {
+            (
+                crossbeam_channel::never(),
+                crossbeam_channel::after(EXIT_DRAIN),
+            )
+        } else {
+            (pty.child_exit_rx.clone(), crossbeam_channel::never())
+        };
+        select! {
+            recv(read_rx) -> read => match read {
+                Ok(read) => {
+                    term.feed(&read.buf);
+                    let responses = term.take_responses();
+                    if !responses.is_empty() {
+                        let _ = write_tx.send(PtyWrite::Write(responses));
+                    }
+                    if term.matches(stop) {
+                        return Ok(StopReason::Matched);
+                    }
+                }
+                Err(_) => return Ok(StopReason::Exited),
+            },
+            recv(exit) -> _ => exited = true,
+            recv(drain) -> _ => return Ok(StopReason::Exited),
+            recv(timer) -> _ => return Ok(StopReason::TimedOut),
+        }
+    }
+}
+
+/// Feed `term` from `reader` until it reaches end of file or `stop`
+/// triggers.
+///
+/// The reader runs on its own thread so a timeout also covers a stream that
+/// stops producing data without closing (a pipe from a program that hangs).
+///
+/// # Errors
+///
+/// Returns [`HeadlessError::Io`] when reading fails.
+pub fn run_stream<R: Read + Send + 'static>(
+    term: &mut HeadlessTerminal,
+    mut reader: R,
+    stop: &StopConditions,
+) -> Result<StopReason, HeadlessError> {
+    let (tx, rx) = crossbeam_channel::unbounded::<std::io::Result<Vec<u8>>>();
+    std::thread::Builder::new()
+        .name(String::from("freminal-render-read"))
+        .spawn(move || {
+            let mut buf = vec![0; STREAM_CHUNK];
+            loop {
+                let chunk = match reader.read(&mut buf) {
+                    Ok(0) => return,
+                    Ok(n) => Ok(buf[..n].to_vec()),
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(e) => Err(e),
+                };
+                let failed = chunk.is_err();
+                if tx.send(chunk).is_err() || failed {
+                    return;
+                }
+            }
+        })?;
+
+    let deadline = stop.timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        let timer = deadline.map_or_else(crossbeam_channel::never, crossbeam_channel::at);
+        select! {
+            recv(rx) -> chunk => match chunk {
+                Ok(chunk) => {
+                    term.feed(&chunk?);
+                    // Nobody reads replies to queries in a stream.
+                    let _ = term.take_responses();
+                    if term.matches(stop) {
+                        return Ok(StopReason::Matched);
+                    }
+                }
+                Err(_) => return Ok(StopReason::EndOfInput),
+            },
+            recv(timer) -> _ => return Ok(StopReason::TimedOut),
+        }
+    }
+}
+
+/// Feed `term` the output `pane_id` produced in `recording`, until the end
+/// of the recording or `stop` triggers.
+///
+/// With `follow_resizes`, `term` starts at the pane's recorded size and
+/// follows its `PaneResize` events; otherwise it keeps its own size.
+#[must_use]
+pub fn run_recording(
+    term: &mut HeadlessTerminal,
+    recording: &ParsedRecording,
+    pane_id: u32,
+    follow_resizes: bool,
+    stop: &StopConditions,
+) -> StopReason {
+    if follow_resizes {
+        let (cols, rows) = leaf_sizes(&recording.metadata.initial_topology)
+            .get(&pane_id)
+            .copied()
+            .unwrap_or_else(|| (u32::from(DEFAULT_WIDTH), u32::from(DEFAULT_HEIGHT)));
+        term.resize(cols as usize, rows as usize);
+    }
+    let timeout_us = stop
+        .timeout
+        .map(|timeout| u64::try_from(timeout.as_micros()).unwrap_or(u64::MAX));
+
+    for event in &recording.events {
+        if timeout_us.is_some_and(|timeout_us| event.timestamp_us > timeout_us) {
+            return StopReason::TimedOut;
+        }
+        match &event.payload {
+            EventPayload::PtyOutput { pane_id: id, data } if *id == pane_id => {
+                term.feed(data);
+                let _ = term.take_responses();
+                if term.matches(stop) {
+                    return StopReason::Matched;
+                }
+            }
+            EventPayload::PaneResize {
+                pane_id: id,
+                cols,
+                rows,
+            } if follow_resizes && *id == pane_id => {
+                term.resize(*cols as usize, *rows as usize);
+            }
+            _ => {}
+        }
+    }
+    StopReason::EndOfInput
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::recording::{RecordingEvent, RecordingMetadata, TopologySnapshot};
+    use freminal_common::themes::DEFAULT_THEME;
+
+    fn output(timestamp_us: u64, data: &[u8]) -> RecordingEvent {
+        RecordingEvent {
+            timestamp_us,
+            payload: EventPayload::PtyOutput {
+                pane_id: 1,
+                data: data.to_vec(),
+            },
+        }
+    }
+
+    fn recording(events: Vec<RecordingEvent>) -> ParsedRecording {
+        ParsedRecording {
+            metadata: RecordingMetadata {
+                freminal_version: "0.0.0".to_string(),
+                created_at: 0,
+                term: "xterm-256color".to_string(),
+                initial_topology: TopologySnapshot { windows: vec![] },
+                scrollback_limit: 1000,
+            },
+            total_duration_us: events.last().map_or(0, |e| e.timestamp_us),
+            total_events: events.len() as u64,
+            events,
+            event_offsets: Vec::new(),
+            seek_index: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn stream_runs_to_end_of_input() {
+        let mut term = HeadlessTerminal::new(10, 2, DEFAULT_THEME);
+        let reason = run_stream(
+            &mut term,
+            std::io::Cursor::new(b"one\r\ntwo".to_vec()),
+            &StopConditions::default(),
+        )
+        .unwrap();
+        assert_eq!(reason, StopReason::EndOfInput);
+        assert_eq!(term.screen_text(), "one\ntwo\n");
+    }
+
+    #[test]
+    fn recording_stops_at_match_and_timeout() {
+        let recording = recording(vec![
+            output(0, b"booting\r\n"),
+            output(1_000, b"ready\r\n"),
+            output(2_000, b"late"),
+        ]);
+
+        let mut term = HeadlessTerminal::new(10, 3, DEFAULT_THEME);
+        let stop = StopConditions {
+            until: Some(Regex::new("(?m)^ready$").unwrap()),
+            ..StopConditions::default()
+        };
+        assert_eq!(
+            run_recording(&mut term, &recording, 1, false, &stop),
+            StopReason::Matched
+        );
+        assert_eq!(term.screen_text(), "booting\nready\n\n");
+
+        let mut term = HeadlessTerminal::new(10, 3, DEFAULT_THEME);
+        let stop = StopConditions {
+            timeout: Some(Duration::from_micros(500)),
+            ..StopConditions::default()
+        };
+        assert_eq!(
+            run_recording(&mut term, &recording, 1, false, &stop),
+            StopReason::TimedOut
+        );
+        assert_eq!(term.screen_text(), "booting\n\n\n");
+    }
+
+    #[test]
+    fn recording_follows_pane_resizes() {
+        let recording = recording(vec![
+            RecordingEvent {
+                timestamp_us: 0,
+                payload: EventPayload::PaneResize {
+                    pane_id: 1,
+                    cols: 4,
+                    rows: 1,
+                },
+            },
+            output(10, b"abcdef"),
+        ]);
+        let mut term = HeadlessTerminal::new(80, 24, DEFAULT_THEME);
+        let reason = run_recording(&mut term, &recording, 1, true, &StopConditions::default());
+        assert_eq!(reason, StopReason::EndOfInput);
+        assert_eq!(term.size(), (4, 1));
+        assert_eq!(term.screen_text(), "ef\n");
+    }
+
+    #[cfg(all(unix, feature = "pty"))]
+    #[test]
+    fn command_output_is_captured_on_exit() {
+        let mut term = HeadlessTerminal::new(20, 2, DEFAULT_THEME);
+        let stop = StopConditions {
+            timeout: Some(Duration::from_secs(10)),
+            ..StopConditions::default()
+        };
+        let reason = run_command(
+            &mut term,
+            "printf",
+            &[String::from("\\033[1mhi\\033[0m")],
+            &stop,
+        )
+        .unwrap();
+        assert_eq!(reason, StopReason::Exited);
+        assert_eq!(term.screen_text(), "hi\n\n");
+    }
+}
@@ -4,6 +4,7 @@
 // https://opensource.org/licenses/MIT.
 
 use std::collections::HashMap;
+#[cfg(feature = "pty")]
 use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -51,11 +52,14 @@ pub use crate::input::{
 use conv2::ValueFrom;
 
 use crate::error::InterfaceError;
-use crate::io::{FreminalPtyInputOutput, PtySpawnConfig};
-use crate::io::{FreminalTerminalSize, PtyRead, PtyWrite};
-use crate::snapshot::TerminalSnapshot;
+#[cfg(feature = "pty")]
+use crate::io::{FreminalPtyInputOutput, PtyRead, PtySpawnConfig};
+use crate::io::{FreminalTerminalSize, PtyWrite};
+use crate::snapshot::{StatusLineSnapshot, TerminalSnapshot};
 use crate::state::{TerminalSections, internal::TerminalState};
-use crossbeam_channel::{Receiver, unbounded};
+use crossbeam_channel::Receiver;
+#[cfg(feature = "pty")]
+use crossbeam_channel::unbounded;
 use freminal_buffer::image_store::{ImagePlacement, InlineImage};
 
 use freminal_common::buffer_states::command_block::CommandBlock;
@@ -66,7 +70,9 @@ use freminal_common::buffer_states::modes::{
     mouse::MouseEncoding, mouse::MouseTrack, rl_bracket::RlBracket,
 };
 
-use freminal_common::{args::Args, buffer_states::tchar::TChar, send_or_log};
+#[cfg(feature = "pty")]
+use freminal_common::args::Args;
+use freminal_common::{buffer_states::tchar::TChar, send_or_log};
 
 /// Mode-related fields extracted from the emulator state for a snapshot.
 ///
@@ -75,6 +81,7 @@ struct SnapshotModeFields {
     bracketed_paste: RlBracket,
     mouse_tracking: MouseTrack,
     mouse_encoding: MouseEncoding,
+    locator_enabled: bool,
     repeat_keys: Decarm,
     cursor_key_app_mode: Decckm,
     keypad_app_mode: KeypadMode,
@@ -199,6 +206,7 @@ pub struct TerminalEmulator {
     pub internal: TerminalState,
     /// PTY I/O layer (holds the terminfo `TempDir` and child-exit receiver).
     /// `None` in headless/benchmark mode where no PTY is started.
+    #[cfg(feature = "pty")]
     pty_io: Option<FreminalPtyInputOutput>,
     write_tx: crossbeam_channel::Sender<PtyWrite>,
     /// Cached flat representation of the visible window from the last
@@ -264,6 +272,9 @@ pub struct TerminalEmulator {
     /// snapshot.  When these change (e.g. after a pane resize), the cached
     /// snapshot must be invalidated — it was built for a different grid size.
     previous_term_size: (usize, usize),
+    /// The status line published in the previous snapshot.  Reused while the
+    /// line is unchanged so the GUI sees the same `Arc` and skips a rebuild.
+    previous_status_line: Option<Arc<StatusLineSnapshot>>,
     /// The instant at which `SynchronizedUpdates::DontDraw` was first observed
     /// during `build_snapshot`.  Used to enforce the 200 ms auto-resume timeout:
     /// if `DontDraw` is still active when this deadline passes, the mode is
@@ -297,6 +308,7 @@ impl TerminalEmulator {
 
         Self {
             internal: TerminalState::default(),
+            #[cfg(feature = "pty")]
             pty_io: None,
             write_tx,
             previous_visible_snap: None,
@@ -307,6 +319,7 @@ impl TerminalEmulator {
             previous_effective_scroll_offset: 0,
             previous_effective_extra_rows: 0,
             previous_term_size: (0, 0),
+            previous_status_line: None,
             dont_draw_entered_at: None,
             deferred_changes: DeferredChangeFlags::default(),
         }
@@ -328,6 +341,7 @@ impl TerminalEmulator {
 
         let emulator = Self {
             internal: TerminalState::new(write_tx.clone(), scrollback_limit),
+            #[cfg(feature = "pty")]
             pty_io: None,
             write_tx,
             previous_visible_snap: None,
@@ -338,6 +352,7 @@ impl TerminalEmulator {
             previous_effective_scroll_offset: 0,
             previous_effective_extra_rows: 0,
             previous_term_size: (0, 0),
+            previous_status_line: None,
             dont_draw_entered_at: None,
             deferred_changes: DeferredChangeFlags::default(),
         };
@@ -353,6 +368,7 @@ impl TerminalEmulator {
     ///
     // 72.6: 8th parameter is the shell-integration TERM_PROGRAM flag,
     // derived from the GUI's config and forwarded straight to PtySpawnConfig.
+    #[cfg(feature = "pty")]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         args: &Args,
@@ -420,6 +436,7 @@ impl TerminalEmulator {
             previous_effective_scroll_offset: 0,
             previous_effective_extra_rows: 0,
             previous_term_size: (0, 0),
+            previous_status_line: None,
             dont_draw_entered_at: None,
             deferred_changes: DeferredChangeFlags::default(),
         };
@@ -445,6 +462,7 @@ impl TerminalEmulator {
     /// Used by `main.rs` to add a third arm to the `select!` loop so the
     /// consumer thread can detect child exit on platforms (Windows) where the
     /// PTY read pipe does not close when the child exits.
+    #[cfg(feature = "pty")]
     #[must_use]
     pub fn child_exit_rx(&self) -> Option<crossbeam_channel::Receiver<()>> {
         self.pty_io.as_ref().map(|io| io.child_exit_rx.clone())
@@ -648,6 +666,7 @@ impl TerminalEmulator {
     ///
     /// Returns `None` in headless / benchmark / playback mode where there is
     /// no real PTY.
+    #[cfg(feature = "pty")]
     #[must_use]
     pub fn echo_off_atomic(&self) -> Option<std::sync::Arc<std::sync::atomic::AtomicBool>> {
         self.pty_io
@@ -663,6 +682,7 @@ impl TerminalEmulator {
     /// than an error.
     ///
     /// Returns `None` in headless / benchmark mode where there is no real PTY.
+    #[cfg(feature = "pty")]
     #[must_use]
     pub fn reader_shutdown_atomic(&self) -> Option<std::sync::Arc<std::sync::atomic::AtomicBool>> {
         self.pty_io
@@ -674,6 +694,7 @@ impl TerminalEmulator {
     ///
     /// Used by the GUI layer for CWD discovery (via a platform-specific path readback) when saving layouts and recording snapshots.
     /// Returns `None` on headless terminals or platforms where the PID is unavailable.
+    #[cfg(feature = "pty")]
     #[must_use]
     pub fn child_pid(&self) -> Option<u32> {
         self.pty_io.as_ref().and_then(|io| io.child_pid)
@@ -869,6 +890,8 @@ impl TerminalEmulator {
         let (images, visible_image_placements) =
             self.collect_visible_images(scroll_offset, extra_rows);
 
+        let status_line = self.collect_status_line();
+
         // ── Per-row line-width attributes (DECDWL / DECDHL) ──────────────────
         let visible_line_widths = Arc::new(
             self.internal
@@ -901,6 +924,7 @@ impl TerminalEmulator {
             bracketed_paste: mode_fields.bracketed_paste,
             mouse_tracking: mode_fields.mouse_tracking,
             mouse_encoding: mode_fields.mouse_encoding,
+            locator_enabled: mode_fields.locator_enabled,
             repeat_keys: mode_fields.repeat_keys,
             cursor_key_app_mode: mode_fields.cursor_key_app_mode,
             keypad_app_mode: mode_fields.keypad_app_mode,
@@ -915,17 +939,43 @@ impl TerminalEmulator {
             shell_histfile,
             ftcs_state,
             last_exit_code,
+            progress: self.internal.handler.progress(),
+            user_vars: self.internal.handler.user_vars(),
+            badge: self.internal.handler.badge(),
             prompt_rows,
             command_blocks,
             theme,
             images,
             visible_image_placements,
             visible_line_widths,
+            status_line,
+            soft_font: self.internal.handler.soft_font(),
             cursor_color_override: self.internal.handler.cursor_color_override(),
+            highlight_bg_override: self.internal.handler.highlight_bg_override(),
+            highlight_fg_override: self.internal.handler.highlight_fg_override(),
+            special_colors: self.internal.handler.special_colors(),
             pointer_shape: self.internal.handler.pointer_shape(),
         }
     }
 
+    /// The status line for the snapshot, reusing the previous `Arc` when the
+    /// line has not changed.
+    fn collect_status_line(&mut self) -> Option<Arc<StatusLineSnapshot>> {
+        let Some((chars, tags)) = self.internal.handler.status_line_contents() else {
+            self.previous_status_line = None;
+            return None;
+        };
+        let line = StatusLineSnapshot { chars, tags };
+        if let Some(previous) = &self.previous_status_line
+            && **previous == line
+        {
+            return Some(Arc::clone(previous));
+        }
+        let line = Arc::new(line);
+        self.previous_status_line = Some(Arc::clone(&line));
+        Some(line)
+    }
+
     /// Flatten the visible rows into
     /// `(chars, tags, row_offsets, url_tag_indices, content_changed)`, using the
     /// snapshot-level cache to avoid work when no visible row is dirty.
@@ -1033,6 +1083,7 @@ impl TerminalEmulator {
             bracketed_paste: self.internal.modes.bracketed_paste.clone(),
             mouse_tracking: self.internal.modes.mouse_tracking.clone(),
             mouse_encoding: self.internal.modes.mouse_encoding.clone(),
+            locator_enabled: self.internal.handler.locator_enabled(),
             repeat_keys: self.internal.modes.repeat_keys,
             cursor_key_app_mode: self.internal.cursor_key_mode(),
             keypad_app_mode: self.internal.modes.keypad_mode,
@@ -1295,6 +1346,26 @@ mod tests {
         );
     }
 
+    // ── build_snapshot: status line ──────────────────────────────────────────
+
+    #[test]
+    fn build_snapshot_reuses_unchanged_status_line() {
+        let (mut emu, _rx) = TerminalEmulator::new_headless(None);
+        assert!(emu.build_snapshot().status_line.is_none());
+
+        emu.handle_incoming_data(b"\x1b[2$~\x1b[1$}status\x1b[0$}");
+        let snap1 = emu.build_snapshot();
+        let snap2 = emu.build_snapshot();
+        let (Some(line1), Some(line2)) = (&snap1.status_line, &snap2.status_line) else {
+            panic!("expected a status line in both snapshots");
+        };
+        assert!(Arc::ptr_eq(line1, line2));
+
+        emu.handle_incoming_data(b"\x1b[1$}!\x1b[0$}");
+        let snap3 = emu.build_snapshot();
+        assert!(!Arc::ptr_eq(line1, snap3.status_line.as_ref().unwrap()));
+    }
+
     // ── set_requested_scroll_offset / reset_scroll_offset ───────────────────────────
 
     #[test]
@@ -1425,6 +1496,7 @@ mod tests {
 
     // ── echo_off_atomic ────────────────────────────────────────────────────────
 
+    #[cfg(feature = "pty")]
     #[test]
     fn echo_off_atomic_headless_returns_none() {
         let emu = make_headless();
@@ -1436,6 +1508,7 @@ mod tests {
 
     // ── child_exit_rx ────────────────────────────────────────────────────────
 
+    #[cfg(feature = "pty")]
     #[test]
     fn child_exit_rx_headless_returns_none() {
         let emu = make_headless();
@@ -1905,28 +1978,32 @@ mod tests {
     }
 
     #[test]
-    fn foreign_osc_133_markers_produce_no_command_blocks() {
+    fn foreign_osc_133_markers_produce_command_blocks() {
         let (mut emu, _rx) = TerminalEmulator::new_headless(None);
         // WezTerm-style: A;cl=m;aid=12345, D;0;aid=12345 — no freminal=1
         emu.handle_incoming_data(b"\x1b]133;A;cl=m;aid=12345\x07");
         emu.handle_incoming_data(b"\x1b]133;D;0;aid=12345\x07");
         let snap = emu.build_snapshot();
-        assert!(
-            snap.command_blocks.is_empty(),
-            "foreign FTCS markers (no freminal=1) must not produce command blocks"
+        assert_eq!(
+            snap.command_blocks.len(),
+            1,
+            "foreign FTCS markers (no freminal=1) are paired into a command block"
         );
+        assert_eq!(snap.command_blocks[0].exit_code, Some(0));
     }
 
     #[test]
-    fn plain_osc_133_markers_without_freminal_tag_produce_no_blocks() {
+    fn plain_osc_133_markers_alongside_tagged_ones_add_no_blocks() {
         let (mut emu, _rx) = TerminalEmulator::new_headless(None);
-        // Plain markers (e.g. older freminal recordings, hand-written test fixtures)
-        emu.handle_incoming_data(b"\x1b]133;A\x07");
-        emu.handle_incoming_data(b"\x1b]133;D;0\x07");
+        // A second integration marking the same prompt as freminal's scripts.
+        emu.handle_incoming_data(b"\x1b]133;A;freminal=1;fid=t1\x07\x1b]133;A\x07");
+        emu.handle_incoming_data(b"\x1b]133;D;0;freminal=1;fid=t1\x07\x1b]133;D;0\x07");
         let snap = emu.build_snapshot();
-        assert!(
-            snap.command_blocks.is_empty(),
-            "plain FTCS markers without freminal=1 must not produce command blocks"
+        assert_eq!(
+            snap.command_blocks.len(),
+            1,
+            "plain FTCS markers inside a tagged prompt must not produce command blocks"
         );
+        assert_eq!(snap.command_blocks[0].fid, "t1");
     }
 }
@@ -3,13 +3,16 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
+#[cfg(feature = "pty")]
 pub mod pty;
+#[cfg(feature = "pty")]
 pub use pty::{FreminalPtyInputOutput, PtySpawnConfig};
 
 // Re-export the shared PTY I/O types from freminal-common so that all crates
 // can use the same definitions without creating a circular dependency.
 pub use freminal_common::pty_write::{FreminalTerminalSize, PtyWrite};
 
+use freminal_common::buffer_states::locator::LocatorInput;
 use freminal_common::config::ThemeMode;
 use freminal_common::themes::ThemePalette;
 
@@ -34,6 +37,12 @@ pub enum InputEvent {
     Resize(usize, usize, usize, usize),
     /// Window focus gained (`true`) or lost (`false`).
     FocusChange(bool),
+    /// Pointer input for DEC locator reporting, sent only while the
+    /// snapshot says it is on.
+    ///
+    /// The PTY thread passes it to `TerminalHandler::handle_locator_input`,
+    /// which answers with any DECLRP report it triggers.
+    Locator(LocatorInput),
     /// Desired scroll window: scroll offset (rows from the bottom, 0 = live
     /// view) plus extra rows to flatten above the visible window.
     ///
@@ -81,6 +90,12 @@ pub enum InputEvent {
     /// `Buffer::set_auto_detect_urls`, which invalidates the row flatten
     /// cache so subsequent snapshots carry the updated detection state.
     AutoDetectUrls(bool),
+    /// Allow or refuse DECRQCRA checksum reports.
+    ///
+    /// Sent by the Settings Modal when the user toggles
+    /// `[security] allow_checksum_reports`.  The PTY thread forwards this to
+    /// `TerminalHandler::set_allow_checksum_reports`.
+    AllowChecksumReports(bool),
     ExtractSelection {
         start_row: usize,
         start_col: usize,
@@ -91,6 +106,18 @@ pub enum InputEvent {
         /// `end_col` (the same column range on each row).
         is_block: bool,
     },
+    /// Extract full-width rows `start_row..=end_row` and send the text on
+    /// `reply` rather than the shared clipboard channel.
+    ///
+    /// Used by remote-control `get-text`: the socket connection thread waits
+    /// on the other end of `reply`, so the GUI thread never blocks on the
+    /// PTY thread.
+    ExtractText {
+        start_row: usize,
+        end_row: usize,
+        end_col: usize,
+        reply: crossbeam_channel::Sender<String>,
+    },
     /// Erase the scrollback buffer, leaving the visible display intact.
     ///
     /// Triggered by the `ClearScrollback` `KeyAction`. The PTY thread calls
@@ -108,6 +135,13 @@ pub enum InputEvent {
     /// overwrite: a program's own subsequent DECSCUSR / `XTCBlink` request
     /// still takes over normally afterward.
     CursorConfigChange(freminal_common::cursor::CursorVisualStyle),
+    /// Recorded PTY output replayed by the `--play` viewer.
+    ///
+    /// The PTY thread feeds these bytes to the emulator exactly as if they had
+    /// been read from a child PTY. Playback panes have no child; the output
+    /// travels on the input channel (not `pty_read_rx`) so it stays strictly
+    /// ordered with the recording's `Resize` events.
+    PlaybackOutput(Vec<u8>),
 }
 
 /// Commands sent from the PTY processing thread to the GUI thread.
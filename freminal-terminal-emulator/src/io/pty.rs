@@ -518,6 +518,12 @@ pub fn run_terminal(
     }
     cmd.env("__CFBundleIdentifier", "io.github.fredclausen.freminal");
 
+    // Point `freminal msg` run inside this pane at the instance that owns it
+    // (only set when `[security] allow_remote_control` bound a socket).
+    if let Some(socket) = freminal_common::remote_control::listening_socket() {
+        cmd.env(freminal_common::remote_control::LISTEN_ON_ENV, socket);
+    }
+
     // NOTE: Some programs (e.g. ohmyposh, zsh) require LANG to be set.  On
     // many Linux installs LANG is unset by default, so we synthesise it here.
     //
@@ -50,15 +50,22 @@
 
 pub mod ansi;
 pub mod ansi_components;
+pub mod asciicast;
 pub mod error;
+pub mod flight_recorder;
+pub mod headless;
 
 pub mod input;
 pub mod interface;
 pub mod io;
+pub mod playback;
 pub mod recording;
+pub mod redaction;
+pub mod screen_export;
 pub mod snapshot;
 pub mod state;
 pub mod terminal_handler;
+pub mod tmux_control;
 
 #[macro_use]
 extern crate tracing;
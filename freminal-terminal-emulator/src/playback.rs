@@ -0,0 +1,1110 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! FREC v2 playback timeline.
+//!
+//! [`Playback`] walks a [`ParsedRecording`] to any point in time, keeping the
+//! recorded window/tab/pane tree ([`TopologySnapshot`]) current and returning
+//! the PTY output and resizes each pane needs to reach that point.  It owns
+//! no emulators: the caller feeds the returned [`PlaybackStep`]s into one
+//! headless [`TerminalEmulator`](crate::interface::TerminalEmulator) per
+//! recorded pane.
+//!
+//! Terminal state cannot be rewound, so going backwards (or any change to
+//! the shape of the tree) asks the caller to rebuild its panes and replays
+//! everything from the start of the recording.  Going forwards only returns
+//! the events in between.
+//!
+//! [`PlaybackClock`] maps wall-clock time to a recording position for
+//! play/pause and speed control.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use freminal_common::terminal_size::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+
+use crate::recording::{
+    EventPayload, PaneNodeSnapshot, PaneTreeSnapshot, ParsedRecording, TabSnapshot,
+    TopologySnapshot, WindowSnapshot,
+};
+
+/// Something a pane's emulator must be fed, in recording order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaybackStep {
+    /// PTY output for a pane.
+    Output {
+        /// Recording-local pane ID.
+        pane_id: u32,
+        /// Raw output bytes.
+        data: Vec<u8>,
+    },
+    /// A pane changed size.
+    Resize {
+        /// Recording-local pane ID.
+        pane_id: u32,
+        /// New column count.
+        cols: u32,
+        /// New row count.
+        rows: u32,
+    },
+}
+
+/// What changed when the playback position moved.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PlaybackUpdate {
+    /// Discard every pane and rebuild them from [`Playback::topology`].
+    ///
+    /// Set when panes, tabs or windows appeared or went away, or when the
+    /// position moved backwards.  `steps` then replays the whole recording
+    /// up to the new position.
+    pub rebuild: bool,
+
+    /// The active tab, active pane or zoomed pane changed.
+    pub focus_changed: bool,
+
+    /// Output and resizes to apply, in order.
+    pub steps: Vec<PlaybackStep>,
+}
+
+/// A recording being played back.
+#[derive(Debug, Clone)]
+pub struct Playback {
+    recording: ParsedRecording,
+
+    /// The tree as of `applied` events.
+    topology: TopologySnapshot,
+
+    /// Size of each pane when it first appeared, the size its replayed
+    /// output starts at.
+    initial_sizes: BTreeMap<u32, (u32, u32)>,
+
+    /// Number of events applied to `topology`.
+    applied: usize,
+
+    /// Current position in microseconds.
+    position_us: u64,
+}
+
+impl Playback {
+    /// Start playback of `recording` at its beginning.
+    #[must_use]
+    pub fn new(recording: ParsedRecording) -> Self {
+        let topology = recording.metadata.initial_topology.clone();
+        let initial_sizes = leaf_sizes(&topology);
+        Self {
+            recording,
+            topology,
+            initial_sizes,
+            applied: 0,
+            position_us: 0,
+        }
+    }
+
+    /// The recording being played.
+    #[must_use]
+    pub const fn recording(&self) -> &ParsedRecording {
+        &self.recording
+    }
+
+    /// The window/tab/pane tree at the current position.
+    #[must_use]
+    pub const fn topology(&self) -> &TopologySnapshot {
+        &self.topology
+    }
+
+    /// Current position in microseconds.
+    #[must_use]
+    pub const fn position_us(&self) -> u64 {
+        self.position_us
+    }
+
+    /// Total length of the recording in microseconds.
+    #[must_use]
+    pub fn duration_us(&self) -> u64 {
+        self.recording.total_duration_us.max(
+            self.recording
+                .events
+                .last()
+                .map_or(0, |event| event.timestamp_us),
+        )
+    }
+
+    /// Move to `position_us`, applying (or un-applying) every event in
+    /// between.
+    pub fn seek(&mut self, position_us: u64) -> PlaybackUpdate {
+        let position_us = position_us.min(self.duration_us());
+        let target = self.recording.events_until(position_us);
+        let mut update = PlaybackUpdate::default();
+
+        if target < self.applied {
+            self.topology = self.recording.metadata.initial_topology.clone();
+            self.initial_sizes = leaf_sizes(&self.topology);
+            self.applied = 0;
+            update.rebuild = true;
+        }
+
+        for index in self.applied..target {
+            let payload = &self.recording.events[index].payload;
+            apply_event(
+                &mut self.topology,
+                &mut self.initial_sizes,
+                payload,
+                &mut update,
+            );
+        }
+        self.applied = target;
+        self.position_us = position_us;
+
+        if update.rebuild {
+            update.focus_changed = true;
+            update.steps = self.replay();
+        }
+        update
+    }
+
+    /// Every step needed to bring freshly created panes for the current
+    /// topology up to the current position.
+    ///
+    /// Each pane starts at the size it first appeared with.  Consecutive
+    /// output for the same pane is merged into one step and resizes that do
+    /// not change the size are dropped.
+    #[must_use]
+    pub fn replay(&self) -> Vec<PlaybackStep> {
+        let mut sizes: BTreeMap<u32, (u32, u32)> = leaf_sizes(&self.topology)
+            .into_keys()
+            .map(|pane_id| {
+                let size = self
+                    .initial_sizes
+                    .get(&pane_id)
+                    .copied()
+                    .unwrap_or_else(default_size);
+                (pane_id, size)
+            })
+            .collect();
+        let mut steps: Vec<PlaybackStep> = sizes
+            .iter()
+            .map(|(&pane_id, &(cols, rows))| PlaybackStep::Resize {
+                pane_id,
+                cols,
+                rows,
+            })
+            .collect();
+
+        for event in &self.recording.events[..self.applied] {
+            match &event.payload {
+                EventPayload::PtyOutput { pane_id, data } if sizes.contains_key(pane_id) => {
+                    if let Some(PlaybackStep::Output {
+                        pane_id: last,
+                        data: merged,
+                    }) = steps.last_mut()
+                        && last == pane_id
+                    {
+                        merged.extend_from_slice(data);
+                    } else {
+                        steps.push(PlaybackStep::Output {
+                            pane_id: *pane_id,
+                            data: data.clone(),
+                        });
+                    }
+                }
+                EventPayload::PaneResize {
+                    pane_id,
+                    cols,
+                    rows,
+                } => {
+                    if let Some(size) = sizes.get_mut(pane_id)
+                        && *size != (*cols, *rows)
+                    {
+                        *size = (*cols, *rows);
+                        steps.push(PlaybackStep::Resize {
+                            pane_id: *pane_id,
+                            cols: *cols,
+                            rows: *rows,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        steps
+    }
+}
+
+/// Apply one event to `topology`, recording what the caller must do in
+/// `update`.  Steps are not collected once a rebuild is pending; the rebuild
+/// replays them.
+// One arm per event type; splitting the match would only scatter it.
+#[allow(clippy::too_many_lines)]
+fn apply_event(
+    topology: &mut TopologySnapshot,
+    initial_sizes: &mut BTreeMap<u32, (u32, u32)>,
+    payload: &EventPayload,
+    update: &mut PlaybackUpdate,
+) {
+    match payload {
+        EventPayload::PtyOutput { pane_id, data } => {
+            if find_leaf(topology, *pane_id).is_none() {
+                add_implicit_pane(topology, initial_sizes, *pane_id, default_size());
+                update.rebuild = true;
+            }
+            if !update.rebuild {
+                update.steps.push(PlaybackStep::Output {
+                    pane_id: *pane_id,
+                    data: data.clone(),
+                });
+            }
+        }
+        EventPayload::PaneResize {
+            pane_id,
+            cols,
+            rows,
+        } => {
+            if let Some((leaf_cols, leaf_rows)) = find_leaf(topology, *pane_id) {
+                *leaf_cols = *cols;
+                *leaf_rows = *rows;
+            } else {
+                add_implicit_pane(topology, initial_sizes, *pane_id, (*cols, *rows));
+                update.rebuild = true;
+            }
+            if !update.rebuild {
+                update.steps.push(PlaybackStep::Resize {
+                    pane_id: *pane_id,
+                    cols: *cols,
+                    rows: *rows,
+                });
+            }
+        }
+        EventPayload::TabCreate {
+            window_id,
+            tab_id,
+            pane_id,
+            cols,
+            rows,
+        } => {
+            let window = window_mut(topology, *window_id);
+            window
+                .tabs
+                .push(single_pane_tab(*window_id, *tab_id, *pane_id, *cols, *rows));
+            window.active_tab = *tab_id;
+            initial_sizes.insert(*pane_id, (*cols, *rows));
+            update.rebuild = true;
+        }
+        EventPayload::TabClose { window_id, tab_id } => {
+            if let Some(window) = topology
+                .windows
+                .iter_mut()
+                .find(|w| w.window_id == *window_id)
+            {
+                window.tabs.retain(|tab| tab.tab_id != *tab_id);
+                if window.active_tab == *tab_id {
+                    window.active_tab = window.tabs.first().map_or(0, |tab| tab.tab_id);
+                }
+                update.rebuild = true;
+            }
+        }
+        EventPayload::PaneSplit {
+            window_id,
+            parent_pane,
+            new_pane,
+            direction,
+            ratio,
+            cols,
+            rows,
+        } => {
+            let leaf = PaneNodeSnapshot::Leaf {
+                pane_id: *new_pane,
+                cols: *cols,
+                rows: *rows,
+                cwd: None,
+                shell: None,
+                title: String::new(),
+            };
+            let split = topology
+                .windows
+                .iter_mut()
+                .flat_map(|window| window.tabs.iter_mut())
+                .find(|tab| tab.pane_tree.node.contains(*parent_pane));
+            if let Some(tab) = split {
+                let node = std::mem::replace(&mut tab.pane_tree.node, placeholder_leaf());
+                tab.pane_tree.node = split_leaf(node, *parent_pane, *direction, *ratio, leaf);
+                tab.active_pane = *new_pane;
+            } else {
+                let window = window_mut(topology, *window_id);
+                let tab_id = next_tab_id(window);
+                window
+                    .tabs
+                    .push(single_pane_tab(*window_id, tab_id, *new_pane, *cols, *rows));
+            }
+            initial_sizes.insert(*new_pane, (*cols, *rows));
+            update.rebuild = true;
+        }
+        EventPayload::PaneClose { pane_id } => {
+            if close_pane(topology, *pane_id) {
+                update.rebuild = true;
+            }
+        }
+        EventPayload::FocusChange {
+            tab_id, pane_id, ..
+        } => {
+            if let Some(tab) = tab_mut(topology, *tab_id) {
+                tab.active_pane = *pane_id;
+                update.focus_changed = true;
+            }
+        }
+        EventPayload::ZoomToggle {
+            tab_id,
+            pane_id,
+            zoomed,
+            ..
+        } => {
+            if let Some(tab) = tab_mut(topology, *tab_id) {
+                tab.zoomed_pane = zoomed.then_some(*pane_id);
+                update.focus_changed = true;
+            }
+        }
+        EventPayload::TabSwitch { window_id, tab_id } => {
+            if let Some(window) = topology
+                .windows
+                .iter_mut()
+                .find(|w| w.window_id == *window_id)
+            {
+                window.active_tab = *tab_id;
+                update.focus_changed = true;
+            }
+        }
+        EventPayload::WindowCreate {
+            window_id,
+            width_px,
+            height_px,
+            x,
+            y,
+        } => {
+            if !topology.windows.iter().any(|w| w.window_id == *window_id) {
+                topology.windows.push(WindowSnapshot {
+                    window_id: *window_id,
+                    position: Some((*x, *y)),
+                    size: (*width_px, *height_px),
+                    tabs: Vec::new(),
+                    active_tab: 0,
+                });
+                update.rebuild = true;
+            }
+        }
+        EventPayload::WindowClose { window_id } => {
+            let before = topology.windows.len();
+            topology.windows.retain(|w| w.window_id != *window_id);
+            if topology.windows.len() != before {
+                update.rebuild = true;
+            }
+        }
+        EventPayload::WindowResize {
+            window_id,
+            width_px,
+            height_px,
+        } => {
+            if let Some(window) = topology
+                .windows
+                .iter_mut()
+                .find(|w| w.window_id == *window_id)
+            {
+                window.size = (*width_px, *height_px);
+            }
+        }
+        EventPayload::WindowMove { window_id, x, y } => {
+            if let Some(window) = topology
+                .windows
+                .iter_mut()
+                .find(|w| w.window_id == *window_id)
+            {
+                window.position = Some((*x, *y));
+            }
+        }
+        EventPayload::PtyInput { .. }
+        | EventPayload::ThemeChange { .. }
+        | EventPayload::KeyboardInput { .. }
+        | EventPayload::MouseMove { .. }
+        | EventPayload::MouseButton { .. }
+        | EventPayload::MouseScroll { .. }
+        | EventPayload::WindowFocus { .. }
+        | EventPayload::ClipboardPaste { .. }
+        | EventPayload::BellEvent { .. }
+        | EventPayload::SelectionEvent { .. }
+        | EventPayload::Redaction { .. } => {}
+    }
+}
+
+/// The window/tab/pane tree of `recording` after its first `count` events.
+#[must_use]
+pub fn topology_after(recording: &ParsedRecording, count: usize) -> TopologySnapshot {
+    let mut topology = recording.metadata.initial_topology.clone();
+    let mut initial_sizes = leaf_sizes(&topology);
+    for event in recording.events.iter().take(count) {
+        advance_topology(&mut topology, &mut initial_sizes, &event.payload);
+    }
+    topology
+}
+
+/// Apply one event's effect on the window/tab/pane tree to `topology`.
+///
+/// `initial_sizes` carries the size each pane had when it appeared; start
+/// it from [`leaf_sizes`] of the starting topology.
+pub(crate) fn advance_topology(
+    topology: &mut TopologySnapshot,
+    initial_sizes: &mut BTreeMap<u32, (u32, u32)>,
+    payload: &EventPayload,
+) {
+    let mut update = PlaybackUpdate {
+        // Nothing is fed, so skip collecting steps.
+        rebuild: true,
+        ..PlaybackUpdate::default()
+    };
+    apply_event(topology, initial_sizes, payload, &mut update);
+}
+
+/// Size used for a pane that produced output before its size was recorded.
+fn default_size() -> (u32, u32) {
+    (u32::from(DEFAULT_WIDTH), u32::from(DEFAULT_HEIGHT))
+}
+
+/// Size of every leaf in `topology`, by pane ID.
+pub(crate) fn leaf_sizes(topology: &TopologySnapshot) -> BTreeMap<u32, (u32, u32)> {
+    fn walk(node: &PaneNodeSnapshot, sizes: &mut BTreeMap<u32, (u32, u32)>) {
+        match node {
+            PaneNodeSnapshot::Leaf {
+                pane_id,
+                cols,
+                rows,
+                ..
+            } => {
+                sizes.insert(*pane_id, (*cols, *rows));
+            }
+            PaneNodeSnapshot::Split { first, second, .. } => {
+                walk(first, sizes);
+                walk(second, sizes);
+            }
+        }
+    }
+
+    let mut sizes = BTreeMap::new();
+    for tab in topology.windows.iter().flat_map(|w| &w.tabs) {
+        walk(&tab.pane_tree.node, &mut sizes);
+    }
+    sizes
+}
+
+/// The size fields of leaf `pane_id`, if it is in the tree.
+fn find_leaf(topology: &mut TopologySnapshot, pane_id: u32) -> Option<(&mut u32, &mut u32)> {
+    fn walk(node: &mut PaneNodeSnapshot, id: u32) -> Option<(&mut u32, &mut u32)> {
+        match node {
+            PaneNodeSnapshot::Leaf {
+                pane_id,
+                cols,
+                rows,
+                ..
+            } => (*pane_id == id).then_some((cols, rows)),
+            PaneNodeSnapshot::Split { first, second, .. } => {
+                walk(first, id).or_else(|| walk(second, id))
+            }
+        }
+    }
+
+    topology
+        .windows
+        .iter_mut()
+        .flat_map(|w| w.tabs.iter_mut())
+        .find_map(|tab| walk(&mut tab.pane_tree.node, pane_id))
+}
+
+/// The tab with `tab_id`, in any window.
+fn tab_mut(topology: &mut TopologySnapshot, tab_id: u32) -> Option<&mut TabSnapshot> {
+    topology
+        .windows
+        .iter_mut()
+        .flat_map(|w| w.tabs.iter_mut())
+        .find(|tab| tab.tab_id == tab_id)
+}
+
+/// The window with `window_id`, created empty if the recording never
+/// announced it.
+fn window_mut(topology: &mut TopologySnapshot, window_id: u32) -> &mut WindowSnapshot {
+    let index = topology
+        .windows
+        .iter()
+        .position(|w| w.window_id == window_id)
+        .unwrap_or_else(|| {
+            topology.windows.push(WindowSnapshot {
+                window_id,
+                position: None,
+                size: (0, 0),
+                tabs: Vec::new(),
+                active_tab: 0,
+            });
+            topology.windows.len() - 1
+        });
+    &mut topology.windows[index]
+}
+
+/// A tab ID not yet used in `window`.
+fn next_tab_id(window: &WindowSnapshot) -> u32 {
+    window
+        .tabs
+        .iter()
+        .map(|tab| tab.tab_id.saturating_add(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// A tab holding a single pane.
+const fn single_pane_tab(
+    window_id: u32,
+    tab_id: u32,
+    pane_id: u32,
+    cols: u32,
+    rows: u32,
+) -> TabSnapshot {
+    TabSnapshot {
+        tab_id,
+        window_id,
+        pane_tree: PaneTreeSnapshot {
+            node: PaneNodeSnapshot::Leaf {
+                pane_id,
+                cols,
+                rows,
+                cwd: None,
+                shell: None,
+                title: String::new(),
+            },
+        },
+        active_pane: pane_id,
+        zoomed_pane: None,
+    }
+}
+
+/// Give a pane that appears in the event stream without a topology event
+/// (recordings started with `--recording-path` carry an empty initial
+/// topology) a tab of its own in the most recently created window.
+fn add_implicit_pane(
+    topology: &mut TopologySnapshot,
+    initial_sizes: &mut BTreeMap<u32, (u32, u32)>,
+    pane_id: u32,
+    (cols, rows): (u32, u32),
+) {
+    let window_id = topology.windows.last().map_or(0, |w| w.window_id);
+    let window = window_mut(topology, window_id);
+    let tab_id = next_tab_id(window);
+    window
+        .tabs
+        .push(single_pane_tab(window_id, tab_id, pane_id, cols, rows));
+    if window.tabs.len() == 1 {
+        window.active_tab = tab_id;
+    }
+    initial_sizes.insert(pane_id, (cols, rows));
+}
+
+/// Stand-in used while a tree is moved out for rebuilding.
+const fn placeholder_leaf() -> PaneNodeSnapshot {
+    PaneNodeSnapshot::Leaf {
+        pane_id: u32::MAX,
+        cols: 0,
+        rows: 0,
+        cwd: None,
+        shell: None,
+        title: String::new(),
+    }
+}
+
+/// Replace leaf `target` with a split of it and `new_leaf`.
+fn split_leaf(
+    node: PaneNodeSnapshot,
+    target: u32,
+    direction: crate::recording::RecordingSplitDirection,
+    ratio: f32,
+    new_leaf: PaneNodeSnapshot,
+) -> PaneNodeSnapshot {
+    match node {
+        PaneNodeSnapshot::Leaf { pane_id, .. } if pane_id == target => PaneNodeSnapshot::Split {
+            direction,
+            ratio,
+            first: Box::new(node),
+            second: Box::new(new_leaf),
+        },
+        PaneNodeSnapshot::Split {
+            direction: d,
+            ratio: r,
+            first,
+            second,
+        } => {
+            let (first, second) = if first.contains(target) {
+                (
+                    split_leaf(*first, target, direction, ratio, new_leaf),
+                    *second,
+                )
+            } else {
+                (
+                    *first,
+                    split_leaf(*second, target, direction, ratio, new_leaf),
+                )
+            };
+            PaneNodeSnapshot::Split {
+                direction: d,
+                ratio: r,
+                first: Box::new(first),
+                second: Box::new(second),
+            }
+        }
+        leaf @ PaneNodeSnapshot::Leaf { .. } => leaf,
+    }
+}
+
+/// `node` without leaf `target`, its parent split collapsed into the
+/// sibling.  `None` when `node` is that leaf.
+fn without_leaf(node: PaneNodeSnapshot, target: u32) -> Option<PaneNodeSnapshot> {
+    match node {
+        PaneNodeSnapshot::Leaf { pane_id, .. } if pane_id == target => None,
+        PaneNodeSnapshot::Split {
+            direction,
+            ratio,
+            first,
+            second,
+        } => match (without_leaf(*first, target), without_leaf(*second, target)) {
+            (Some(first), Some(second)) => Some(PaneNodeSnapshot::Split {
+                direction,
+                ratio,
+                first: Box::new(first),
+                second: Box::new(second),
+            }),
+            (Some(only), None) | (None, Some(only)) => Some(only),
+            (None, None) => None,
+        },
+        leaf @ PaneNodeSnapshot::Leaf { .. } => Some(leaf),
+    }
+}
+
+/// Remove pane `pane_id`, and its tab if it was the last pane there.
+/// Returns whether the pane was found.
+fn close_pane(topology: &mut TopologySnapshot, pane_id: u32) -> bool {
+    for window in &mut topology.windows {
+        let Some(index) = window
+            .tabs
+            .iter()
+            .position(|tab| tab.pane_tree.node.contains(pane_id))
+        else {
+            continue;
+        };
+        let tab = &mut window.tabs[index];
+        let node = std::mem::replace(&mut tab.pane_tree.node, placeholder_leaf());
+        if let Some(node) = without_leaf(node, pane_id) {
+            if tab.active_pane == pane_id {
+                tab.active_pane = node.first_pane_id();
+            }
+            if tab.zoomed_pane == Some(pane_id) {
+                tab.zoomed_pane = None;
+            }
+            tab.pane_tree.node = node;
+        } else {
+            let tab_id = tab.tab_id;
+            window.tabs.remove(index);
+            if window.active_tab == tab_id {
+                window.active_tab = window.tabs.first().map_or(0, |tab| tab.tab_id);
+            }
+        }
+        return true;
+    }
+    false
+}
+
+/// Maps wall-clock time to a position in a recording for play/pause and
+/// speed control.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackClock {
+    /// Length of the recording in microseconds.
+    duration_us: u64,
+
+    /// Recording position at `anchor`.
+    anchor_us: u64,
+
+    /// The instant playback was at `anchor_us`; `None` while paused.
+    anchor: Option<Instant>,
+
+    /// Playback speed multiplier.
+    speed: f64,
+}
+
+impl PlaybackClock {
+    /// A paused clock at the start of a recording `duration_us` long,
+    /// running at normal speed.
+    #[must_use]
+    pub const fn new(duration_us: u64) -> Self {
+        Self {
+            duration_us,
+            anchor_us: 0,
+            anchor: None,
+            speed: 1.0,
+        }
+    }
+
+    /// Recording position at `now`, in microseconds.
+    #[must_use]
+    pub fn position_us(&self, now: Instant) -> u64 {
+        let Some(anchor) = self.anchor else {
+            return self.anchor_us;
+        };
+        let elapsed = now.saturating_duration_since(anchor).as_secs_f64() * self.speed;
+        let elapsed_us = Duration::try_from_secs_f64(elapsed).map_or(u64::MAX, |d| {
+            u64::try_from(d.as_micros()).unwrap_or(u64::MAX)
+        });
+        self.anchor_us
+            .saturating_add(elapsed_us)
+            .min(self.duration_us)
+    }
+
+    /// Whether the clock is running.
+    #[must_use]
+    pub const fn is_playing(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Whether the position has reached the end of the recording.
+    #[must_use]
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.position_us(now) >= self.duration_us
+    }
+
+    /// Current speed multiplier.
+    #[must_use]
+    pub const fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Start (or keep) running.  Playing from the end starts over.
+    pub const fn play(&mut self, now: Instant) {
+        if self.is_playing() {
+            return;
+        }
+        if self.anchor_us >= self.duration_us {
+            self.anchor_us = 0;
+        }
+        self.anchor = Some(now);
+    }
+
+    /// Stop at the current position.
+    pub fn pause(&mut self, now: Instant) {
+        self.anchor_us = self.position_us(now);
+        self.anchor = None;
+    }
+
+    /// Jump to `position_us`, keeping the play/pause state.
+    pub fn seek(&mut self, now: Instant, position_us: u64) {
+        self.anchor_us = position_us.min(self.duration_us);
+        if self.is_playing() {
+            self.anchor = Some(now);
+        }
+    }
+
+    /// Change the speed multiplier from `now` on.
+    pub fn set_speed(&mut self, now: Instant, speed: f64) {
+        self.anchor_us = self.position_us(now);
+        if self.is_playing() {
+            self.anchor = Some(now);
+        }
+        self.speed = speed;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::interface::TerminalEmulator;
+    use crate::recording::{RecordingEvent, RecordingMetadata, RecordingSplitDirection};
+
+    fn event(timestamp_us: u64, payload: EventPayload) -> RecordingEvent {
+        RecordingEvent {
+            timestamp_us,
+            payload,
+        }
+    }
+
+    fn output(timestamp_us: u64, pane_id: u32, data: &[u8]) -> RecordingEvent {
+        event(
+            timestamp_us,
+            EventPayload::PtyOutput {
+                pane_id,
+                data: data.to_vec(),
+            },
+        )
+    }
+
+    /// A recording with the given events and an empty initial topology, as
+    /// `--recording-path` writes it.  One seek entry per event.
+    fn recording(events: Vec<RecordingEvent>) -> ParsedRecording {
+        let event_offsets: Vec<u64> = (0..events.len() as u64).map(|i| i * 100).collect();
+        let seek_index = events
+            .iter()
+            .zip(&event_offsets)
+            .map(|(event, &file_offset)| crate::recording::SeekIndexEntry {
+                timestamp_us: event.timestamp_us,
+                file_offset,
+            })
+            .collect();
+        ParsedRecording {
+            metadata: RecordingMetadata {
+                freminal_version: "0.0.0".to_string(),
+                created_at: 0,
+                term: "xterm-256color".to_string(),
+                initial_topology: TopologySnapshot { windows: vec![] },
+                scrollback_limit: 1000,
+            },
+            total_duration_us: events.last().map_or(0, |e| e.timestamp_us),
+            total_events: events.len() as u64,
+            events,
+            event_offsets,
+            seek_index,
+        }
+    }
+
+    fn pane_ids(playback: &Playback) -> Vec<Vec<u32>> {
+        playback
+            .topology()
+            .windows
+            .iter()
+            .flat_map(|w| &w.tabs)
+            .map(|tab| tab.pane_tree.node.pane_ids())
+            .collect()
+    }
+
+    /// Feed `steps` for `pane` into a fresh headless emulator and return its
+    /// first screen line.
+    fn render(steps: &[PlaybackStep], pane: u32) -> String {
+        let (mut emu, _rx) = TerminalEmulator::new_headless(None);
+        for step in steps {
+            match step {
+                PlaybackStep::Output { pane_id, data } if *pane_id == pane => {
+                    emu.handle_incoming_data(data);
+                }
+                PlaybackStep::Resize {
+                    pane_id,
+                    cols,
+                    rows,
+                } if *pane_id == pane => {
+                    emu.internal
+                        .set_win_size(*cols as usize, *rows as usize, 0, 0);
+                }
+                _ => {}
+            }
+        }
+        let snap = emu.build_snapshot();
+        snap.visible_chars
+            .iter()
+            .map(ToString::to_string)
+            .collect::<String>()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim_end()
+            .to_string()
+    }
+
+    #[test]
+    fn unknown_pane_gets_an_implicit_tab() {
+        let mut playback = Playback::new(recording(vec![
+            output(10, 7, b"hi"),
+            output(20, 9, b"there"),
+        ]));
+        let update = playback.seek(10);
+        assert!(update.rebuild);
+        assert_eq!(pane_ids(&playback), vec![vec![7]]);
+        assert_eq!(playback.topology().windows[0].window_id, 0);
+
+        let update = playback.seek(20);
+        assert!(update.rebuild);
+        assert_eq!(pane_ids(&playback), vec![vec![7], vec![9]]);
+    }
+
+    #[test]
+    fn forward_seek_returns_only_new_steps() {
+        let mut playback = Playback::new(recording(vec![
+            output(0, 1, b"a"),
+            output(1_000, 1, b"b"),
+            event(
+                2_000,
+                EventPayload::PaneResize {
+                    pane_id: 1,
+                    cols: 40,
+                    rows: 10,
+                },
+            ),
+            output(3_000, 1, b"c"),
+        ]));
+        playback.seek(0);
+
+        let update = playback.seek(2_500);
+        assert!(!update.rebuild);
+        assert_eq!(
+            update.steps,
+            vec![
+                PlaybackStep::Output {
+                    pane_id: 1,
+                    data: b"b".to_vec()
+                },
+                PlaybackStep::Resize {
+                    pane_id: 1,
+                    cols: 40,
+                    rows: 10
+                },
+            ]
+        );
+        assert_eq!(playback.position_us(), 2_500);
+    }
+
+    #[test]
+    fn backward_seek_rebuilds_and_replays_from_start() {
+        let mut playback = Playback::new(recording(vec![
+            output(0, 1, b"one "),
+            output(1_000, 1, b"two "),
+            output(2_000, 1, b"three"),
+        ]));
+        playback.seek(2_000);
+
+        let update = playback.seek(1_000);
+        assert!(update.rebuild);
+        assert!(update.focus_changed);
+        assert_eq!(render(&update.steps, 1), "one two");
+    }
+
+    #[test]
+    fn replay_merges_output_and_starts_at_initial_size() {
+        let mut playback = Playback::new(recording(vec![
+            event(
+                0,
+                EventPayload::PaneResize {
+                    pane_id: 3,
+                    cols: 20,
+                    rows: 5,
+                },
+            ),
+            output(10, 3, b"ab"),
+            output(20, 3, b"cd"),
+            event(
+                30,
+                EventPayload::PaneResize {
+                    pane_id: 3,
+                    cols: 30,
+                    rows: 6,
+                },
+            ),
+        ]));
+        let update = playback.seek(30);
+        assert_eq!(
+            update.steps,
+            vec![
+                PlaybackStep::Resize {
+                    pane_id: 3,
+                    cols: 20,
+                    rows: 5
+                },
+                PlaybackStep::Output {
+                    pane_id: 3,
+                    data: b"abcd".to_vec()
+                },
+                PlaybackStep::Resize {
+                    pane_id: 3,
+                    cols: 30,
+                    rows: 6
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_and_close_follow_the_recording() {
+        let mut playback = Playback::new(recording(vec![
+            event(
+                0,
+                EventPayload::TabCreate {
+                    window_id: 0,
+                    tab_id: 0,
+                    pane_id: 1,
+                    cols: 80,
+                    rows: 24,
+                },
+            ),
+            event(
+                10,
+                EventPayload::PaneSplit {
+                    window_id: 0,
+                    parent_pane: 1,
+                    new_pane: 2,
+                    direction: RecordingSplitDirection::Horizontal,
+                    ratio: 0.5,
+                    cols: 40,
+                    rows: 24,
+                },
+            ),
+            event(
+                20,
+                EventPayload::FocusChange {
+                    window_id: 0,
+                    tab_id: 0,
+                    pane_id: 1,
+                },
+            ),
+            event(30, EventPayload::PaneClose { pane_id: 1 }),
+        ]));
+
+        playback.seek(10);
+        assert_eq!(pane_ids(&playback), vec![vec![1, 2]]);
+        assert_eq!(playback.topology().windows[0].tabs[0].active_pane, 2);
+
+        let update = playback.seek(20);
+        assert!(!update.rebuild);
+        assert!(update.focus_changed);
+        assert_eq!(playback.topology().windows[0].tabs[0].active_pane, 1);
+
+        let update = playback.seek(30);
+        assert!(update.rebuild);
+        assert_eq!(pane_ids(&playback), vec![vec![2]]);
+        assert_eq!(playback.topology().windows[0].tabs[0].active_pane, 2);
+    }
+
+    #[test]
+    fn closed_panes_are_left_out_of_replay() {
+        let mut playback = Playback::new(recording(vec![
+            output(0, 1, b"a"),
+            output(10, 2, b"b"),
+            event(20, EventPayload::PaneClose { pane_id: 1 }),
+        ]));
+        let update = playback.seek(20);
+        assert!(
+            update
+                .steps
+                .iter()
+                .all(|step| !matches!(step, PlaybackStep::Output { pane_id: 1, .. }))
+        );
+        assert_eq!(pane_ids(&playback), vec![vec![2]]);
+    }
+
+    #[test]
+    fn clock_plays_pauses_and_scales() {
+        let start = Instant::now();
+        let mut clock = PlaybackClock::new(10_000_000);
+        assert_eq!(clock.position_us(start + Duration::from_secs(1)), 0);
+
+        clock.play(start);
+        assert_eq!(clock.position_us(start + Duration::from_secs(1)), 1_000_000);
+
+        clock.set_speed(start + Duration::from_secs(1), 2.0);
+        assert_eq!(clock.position_us(start + Duration::from_secs(2)), 3_000_000);
+
+        clock.pause(start + Duration::from_secs(2));
+        assert!(!clock.is_playing());
+        assert_eq!(clock.position_us(start + Duration::from_secs(5)), 3_000_000);
+
+        clock.seek(start, 20_000_000);
+        assert!(clock.is_finished(start));
+        clock.play(start);
+        assert_eq!(clock.position_us(start), 0);
+    }
+}
@@ -18,12 +18,17 @@
 //! Metadata and topology snapshots use `MessagePack` via `rmp_serde` for compact, self-describing
 //! serialization. Event records use a fixed binary header (timestamp + type + length) with
 //! `MessagePack` payloads. `MessagePack` was chosen over bincode for forward-compatible decoding
-//! (field names preserved) and easy consumption from Python (the `msgpack` package) in
-//! the `sequence_decoder` script.
+//! (field names preserved) and easy consumption from other languages (e.g. Python's `msgpack`
+//! package).
+//!
+//! `freminal rec` (`info`, `cat`, `extract`, `events`) is built on [`parse_recording`] and
+//! [`write_recording`], so the tooling shares this module's format logic.
 
 use conv2::ValueFrom;
 use serde::{Deserialize, Serialize};
 
+use crate::redaction::Redactor;
+
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
@@ -147,6 +152,39 @@ pub enum PaneNodeSnapshot {
     },
 }
 
+impl PaneNodeSnapshot {
+    /// ID of the leftmost/topmost leaf.
+    #[must_use]
+    pub fn first_pane_id(&self) -> u32 {
+        match self {
+            Self::Leaf { pane_id, .. } => *pane_id,
+            Self::Split { first, .. } => first.first_pane_id(),
+        }
+    }
+
+    /// IDs of every leaf, left to right / top to bottom.
+    #[must_use]
+    pub fn pane_ids(&self) -> Vec<u32> {
+        match self {
+            Self::Leaf { pane_id, .. } => vec![*pane_id],
+            Self::Split { first, second, .. } => {
+                let mut ids = first.pane_ids();
+                ids.extend(second.pane_ids());
+                ids
+            }
+        }
+    }
+
+    /// Whether a leaf with `id` is in this tree.
+    #[must_use]
+    pub fn contains(&self, id: u32) -> bool {
+        match self {
+            Self::Leaf { pane_id, .. } => *pane_id == id,
+            Self::Split { first, second, .. } => first.contains(id) || second.contains(id),
+        }
+    }
+}
+
 /// Split direction for recording format (independent of GUI `SplitDirection` to keep the
 /// recording format stable across GUI refactors).
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -211,6 +249,8 @@ pub enum EventType {
     SelectionEvent = 0x16,
     /// Window moved.
     WindowMove = 0x17,
+    /// Bytes were withheld or masked by the redaction layer.
+    Redaction = 0x18,
 }
 
 impl EventType {
@@ -241,6 +281,7 @@ impl EventType {
             0x15 => Some(Self::BellEvent),
             0x16 => Some(Self::SelectionEvent),
             0x17 => Some(Self::WindowMove),
+            0x18 => Some(Self::Redaction),
             _ => None,
         }
     }
@@ -250,6 +291,73 @@ impl EventType {
     pub const fn to_u8(self) -> u8 {
         self as u8
     }
+
+    /// Every event type, in discriminant order.
+    pub const ALL: [Self; 24] = [
+        Self::PtyOutput,
+        Self::PtyInput,
+        Self::PaneResize,
+        Self::WindowResize,
+        Self::TabCreate,
+        Self::TabClose,
+        Self::PaneSplit,
+        Self::PaneClose,
+        Self::FocusChange,
+        Self::ZoomToggle,
+        Self::TabSwitch,
+        Self::ThemeChange,
+        Self::KeyboardInput,
+        Self::MouseMove,
+        Self::MouseButton,
+        Self::MouseScroll,
+        Self::WindowCreate,
+        Self::WindowClose,
+        Self::WindowFocus,
+        Self::ClipboardPaste,
+        Self::BellEvent,
+        Self::SelectionEvent,
+        Self::WindowMove,
+        Self::Redaction,
+    ];
+
+    /// The variant name, as shown by `freminal rec`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::PtyOutput => "PtyOutput",
+            Self::PtyInput => "PtyInput",
+            Self::PaneResize => "PaneResize",
+            Self::WindowResize => "WindowResize",
+            Self::TabCreate => "TabCreate",
+            Self::TabClose => "TabClose",
+            Self::PaneSplit => "PaneSplit",
+            Self::PaneClose => "PaneClose",
+            Self::FocusChange => "FocusChange",
+            Self::ZoomToggle => "ZoomToggle",
+            Self::TabSwitch => "TabSwitch",
+            Self::ThemeChange => "ThemeChange",
+            Self::KeyboardInput => "KeyboardInput",
+            Self::MouseMove => "MouseMove",
+            Self::MouseButton => "MouseButton",
+            Self::MouseScroll => "MouseScroll",
+            Self::WindowCreate => "WindowCreate",
+            Self::WindowClose => "WindowClose",
+            Self::WindowFocus => "WindowFocus",
+            Self::ClipboardPaste => "ClipboardPaste",
+            Self::BellEvent => "BellEvent",
+            Self::SelectionEvent => "SelectionEvent",
+            Self::WindowMove => "WindowMove",
+            Self::Redaction => "Redaction",
+        }
+    }
+
+    /// Look up an event type by [`name`](Self::name), ignoring ASCII case.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|ty| ty.name().eq_ignore_ascii_case(name))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -489,6 +597,16 @@ pub enum EventPayload {
         /// New Y position.
         y: i32,
     },
+    /// Marker left by the redaction layer in place of what it removed.
+    Redaction {
+        /// Pane whose data was redacted.
+        pane_id: u32,
+        /// Why (0=input withheld while echo was off, 1=bytes matching a
+        /// redaction pattern masked).
+        reason: u8,
+        /// Number of bytes withheld or masked.
+        byte_count: u32,
+    },
 }
 
 impl EventPayload {
@@ -519,6 +637,43 @@ impl EventPayload {
             Self::BellEvent { .. } => EventType::BellEvent,
             Self::SelectionEvent { .. } => EventType::SelectionEvent,
             Self::WindowMove { .. } => EventType::WindowMove,
+            Self::Redaction { .. } => EventType::Redaction,
+        }
+    }
+
+    /// Whether this event is about pane `id` (for a split, either the pane
+    /// split or the new pane).
+    #[must_use]
+    pub const fn involves_pane(&self, id: u32) -> bool {
+        match self {
+            Self::PtyOutput { pane_id, .. }
+            | Self::PtyInput { pane_id, .. }
+            | Self::PaneResize { pane_id, .. }
+            | Self::TabCreate { pane_id, .. }
+            | Self::PaneClose { pane_id }
+            | Self::FocusChange { pane_id, .. }
+            | Self::ZoomToggle { pane_id, .. }
+            | Self::KeyboardInput { pane_id, .. }
+            | Self::MouseMove { pane_id, .. }
+            | Self::MouseButton { pane_id, .. }
+            | Self::MouseScroll { pane_id, .. }
+            | Self::ClipboardPaste { pane_id, .. }
+            | Self::BellEvent { pane_id, .. }
+            | Self::SelectionEvent { pane_id, .. }
+            | Self::Redaction { pane_id, .. } => *pane_id == id,
+            Self::PaneSplit {
+                parent_pane,
+                new_pane,
+                ..
+            } => *parent_pane == id || *new_pane == id,
+            Self::WindowResize { .. }
+            | Self::TabClose { .. }
+            | Self::TabSwitch { .. }
+            | Self::ThemeChange { .. }
+            | Self::WindowCreate { .. }
+            | Self::WindowClose { .. }
+            | Self::WindowFocus { .. }
+            | Self::WindowMove { .. } => false,
         }
     }
 }
@@ -563,18 +718,51 @@ pub struct RecordingContext<'a> {
     pub window_id: u32,
     /// Recording-local pane identifier.
     pub pane_id: u32,
+    /// Whether the pane's PTY had echo off when the context was built, so
+    /// keyboard and paste events can be withheld (see
+    /// [`RecordingHandle::emit_input`]).
+    pub echo_off: bool,
 }
 
 /// Handle for sending recording events from any thread.
 ///
 /// Cheaply cloneable. Dropping all clones signals the writer thread to finalize
 /// and flush.
+///
+/// A handle may feed several sinks at once (see [`Self::fan_out`]), e.g. a
+/// file recording and the flight recorder; each sink timestamps events
+/// against its own start time.
+///
+/// A handle may also carry a [`Redactor`] (see [`Self::with_redactor`]),
+/// applied to every emitted event before it reaches any sink.
 #[derive(Clone)]
 pub struct RecordingHandle {
+    sinks: std::sync::Arc<[RecordingSink]>,
+    redactor: Option<std::sync::Arc<Redactor>>,
+}
+
+/// One destination of a [`RecordingHandle`].
+#[derive(Clone)]
+struct RecordingSink {
     tx: crossbeam_channel::Sender<RecordingEvent>,
     start: std::time::Instant,
 }
 
+impl RecordingSink {
+    fn timestamp_us(&self) -> u64 {
+        let elapsed = self.start.elapsed();
+        // Truncation is acceptable: u64 microseconds covers ~584,942 years.
+        #[allow(clippy::cast_possible_truncation)]
+        let us = elapsed.as_micros() as u64;
+        us
+    }
+
+    fn send(&self, event: RecordingEvent) {
+        // Best-effort: never block production threads.
+        let _: Result<(), _> = self.tx.try_send(event);
+    }
+}
+
 /// Shared, hot-swappable recording handle.
 ///
 /// Wraps `Option<RecordingHandle>` in an [`arc_swap::ArcSwapOption`] so the
@@ -596,52 +784,134 @@ pub fn empty_recording_swap() -> RecordingSwap {
 }
 
 impl RecordingHandle {
+    /// A handle feeding the single channel `tx`, timed from now.
+    pub(crate) fn new(tx: crossbeam_channel::Sender<RecordingEvent>) -> Self {
+        Self {
+            sinks: std::sync::Arc::new([RecordingSink {
+                tx,
+                start: std::time::Instant::now(),
+            }]),
+            redactor: None,
+        }
+    }
+
+    /// This handle with `redactor` applied to everything it emits
+    /// (`None` turns redaction off).
+    #[must_use]
+    pub fn with_redactor(self, redactor: Option<std::sync::Arc<Redactor>>) -> Self {
+        Self { redactor, ..self }
+    }
+
+    /// A handle that sends every event to all of `handles`' sinks, or
+    /// `None` if there are none.
+    ///
+    /// The result has no redactor; attach one with [`Self::with_redactor`].
+    pub fn fan_out<'a>(handles: impl IntoIterator<Item = &'a Self>) -> Option<Self> {
+        let sinks: Vec<RecordingSink> = handles
+            .into_iter()
+            .flat_map(|handle| handle.sinks.iter().cloned())
+            .collect();
+        (!sinks.is_empty()).then(|| Self {
+            sinks: sinks.into(),
+            redactor: None,
+        })
+    }
+
     /// Send an event to the recording writer thread.
     ///
     /// Events are silently dropped on a full channel to avoid
     /// blocking the PTY/GUI threads (uses `try_send`).
     pub fn send(&self, event: RecordingEvent) {
-        // Best-effort: never block production threads.
-        let _: Result<(), _> = self.tx.try_send(event);
+        if let Some((last, rest)) = self.sinks.split_last() {
+            for sink in rest {
+                sink.send(event.clone());
+            }
+            last.send(event);
+        }
     }
 
-    /// Compute the current timestamp in microseconds since recording start.
+    /// Compute the current timestamp in microseconds since recording start
+    /// (of the first sink, when there are several).
     #[must_use]
     pub fn timestamp_us(&self) -> u64 {
-        let elapsed = self.start.elapsed();
-        // Truncation is acceptable: u64 microseconds covers ~584,942 years.
-        #[allow(clippy::cast_possible_truncation)]
-        let us = elapsed.as_micros() as u64;
-        us
+        self.sinks.first().map_or(0, RecordingSink::timestamp_us)
     }
 
     /// Build and send an event with the current timestamp.
+    ///
+    /// Use [`Self::emit_input`] for input sent to a pane, so it can be
+    /// withheld while the pane has echo off.
     pub fn emit(&self, payload: EventPayload) {
-        self.send(RecordingEvent {
-            timestamp_us: self.timestamp_us(),
-            payload,
-        });
+        self.emit_input(payload, false);
+    }
+
+    /// [`Self::emit`] for an event whose pane had echo off (`echo_off`) or
+    /// on at the time.
+    ///
+    /// With a redactor attached, input sent while echo was off is replaced
+    /// by a [`EventPayload::Redaction`] marker and bytes matching a
+    /// redaction pattern are masked, with a marker sent first.
+    pub fn emit_input(&self, payload: EventPayload, echo_off: bool) {
+        let Some(redactor) = self.redactor.as_deref() else {
+            self.emit_unredacted(payload);
+            return;
+        };
+        let redacted = redactor.redact(payload, echo_off);
+        if let Some(marker) = redacted.marker {
+            self.emit_unredacted(marker);
+        }
+        if let Some(payload) = redacted.payload {
+            self.emit_unredacted(payload);
+        }
+    }
+
+    fn emit_unredacted(&self, payload: EventPayload) {
+        if let Some((last, rest)) = self.sinks.split_last() {
+            for sink in rest {
+                sink.send(RecordingEvent {
+                    timestamp_us: sink.timestamp_us(),
+                    payload: payload.clone(),
+                });
+            }
+            last.send(RecordingEvent {
+                timestamp_us: last.timestamp_us(),
+                payload,
+            });
+        }
     }
 }
 
-/// Dedicated writer thread state. Not public — created via [`start_recording`].
-struct WriterThread {
-    writer: std::io::BufWriter<std::fs::File>,
-    rx: crossbeam_channel::Receiver<RecordingEvent>,
+/// Serializes one FREC v2 file: header, events, then seek index and footer.
+struct FrecWriter<W> {
+    writer: W,
     seek_entries: Vec<SeekIndexEntry>,
     last_seek_timestamp_us: u64,
     events_written: u64,
     last_timestamp_us: u64,
 }
 
+/// Dedicated writer thread state. Not public — created via [`start_recording`].
+struct WriterThread {
+    frec: FrecWriter<std::io::BufWriter<std::fs::File>>,
+    rx: crossbeam_channel::Receiver<RecordingEvent>,
+}
+
 /// Seek index interval: one entry per second of recording time.
 const SEEK_INDEX_INTERVAL_US: u64 = 1_000_000;
 
-impl WriterThread {
+impl<W: std::io::Write + std::io::Seek> FrecWriter<W> {
+    const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            seek_entries: Vec::new(),
+            last_seek_timestamp_us: 0,
+            events_written: 0,
+            last_timestamp_us: 0,
+        }
+    }
+
     /// Write the fixed file header and serialized metadata.
     fn write_header(&mut self, metadata: &RecordingMetadata) -> Result<(), RecordingError> {
-        use std::io::Write;
-
         // Magic
         self.writer.write_all(FREC_MAGIC)?;
         // Version
@@ -659,8 +929,6 @@ impl WriterThread {
 
     /// Write a single event record and update seek index.
     fn write_event(&mut self, event: &RecordingEvent) -> Result<(), RecordingError> {
-        use std::io::{Seek, Write};
-
         // Record file offset for seek index (before writing this event).
         let file_offset = self.writer.stream_position()?;
 
@@ -695,8 +963,6 @@ impl WriterThread {
 
     /// Write the seek index and footer, finalizing the file.
     fn finalize(&mut self) -> Result<(), RecordingError> {
-        use std::io::{Seek, Write};
-
         // Record offset of seek index.
         let seek_index_offset = self.writer.stream_position()?;
 
@@ -718,10 +984,12 @@ impl WriterThread {
         self.writer.flush()?;
         Ok(())
     }
+}
 
+impl WriterThread {
     /// Run the writer loop: drain events from channel, write, finalize on close.
     fn run(mut self, metadata: &RecordingMetadata) {
-        if let Err(e) = self.write_header(metadata) {
+        if let Err(e) = self.frec.write_header(metadata) {
             error!("FREC recording: failed to write header: {e}");
             return;
         }
@@ -731,12 +999,12 @@ impl WriterThread {
 
         // Drain events until all senders are dropped.
         for event in &rx {
-            if let Err(e) = self.write_event(&event) {
+            if let Err(e) = self.frec.write_event(&event) {
                 error!("FREC recording: failed to write event: {e}");
             }
         }
 
-        if let Err(e) = self.finalize() {
+        if let Err(e) = self.frec.finalize() {
             error!("FREC recording: failed to finalize: {e}");
         }
     }
@@ -797,12 +1065,8 @@ pub fn start_recording(
     let (tx, rx) = crossbeam_channel::bounded(channel_capacity);
 
     let thread = WriterThread {
-        writer,
+        frec: FrecWriter::new(writer),
         rx,
-        seek_entries: Vec::new(),
-        last_seek_timestamp_us: 0,
-        events_written: 0,
-        last_timestamp_us: 0,
     };
 
     let join_handle = std::thread::Builder::new()
@@ -811,16 +1075,35 @@ pub fn start_recording(
         .map_err(|e| RecordingError::Io(std::io::Error::other(e)))?;
 
     Ok((
-        RecordingHandle {
-            tx,
-            start: std::time::Instant::now(),
-        },
+        RecordingHandle::new(tx),
         RecordingJoinHandle {
             inner: Some(join_handle),
         },
     ))
 }
 
+/// Write a complete FREC v2 file holding `events` to `writer`.
+///
+/// Produces exactly what a live recording of the same events would: the
+/// seek index is built from the event timestamps and the footer's duration
+/// is the last event's timestamp.  `events` must be in timestamp order.
+///
+/// # Errors
+///
+/// Returns an error if writing or serializing fails.
+pub fn write_recording<W: std::io::Write + std::io::Seek>(
+    writer: W,
+    metadata: &RecordingMetadata,
+    events: &[RecordingEvent],
+) -> Result<(), RecordingError> {
+    let mut frec = FrecWriter::new(writer);
+    frec.write_header(metadata)?;
+    for event in events {
+        frec.write_event(event)?;
+    }
+    frec.finalize()
+}
+
 // ---------------------------------------------------------------------------
 // Reader / Parser
 // ---------------------------------------------------------------------------
@@ -832,6 +1115,9 @@ pub struct ParsedRecording {
     pub metadata: RecordingMetadata,
     /// All events in order.
     pub events: Vec<RecordingEvent>,
+    /// Byte offset from start of file to each event record, parallel to
+    /// `events`.  Maps [`SeekIndexEntry::file_offset`] back to an event index.
+    pub event_offsets: Vec<u64>,
     /// Seek index entries.
     pub seek_index: Vec<SeekIndexEntry>,
     /// Total recording duration in microseconds (from footer).
@@ -921,6 +1207,7 @@ pub fn parse_recording_from_bytes(data: &[u8]) -> Result<ParsedRecording, ParseE
 
     // --- Events ---
     let mut events = Vec::new();
+    let mut event_offsets = Vec::new();
     let mut event_pos = pos;
     while event_pos < seek_index_offset {
         if event_pos + EVENT_HEADER_SIZE > data.len() {
@@ -944,6 +1231,7 @@ pub fn parse_recording_from_bytes(data: &[u8]) -> Result<ParsedRecording, ParseE
             timestamp_us,
             payload,
         });
+        event_offsets.push(u64::value_from(event_pos - EVENT_HEADER_SIZE).unwrap_or(u64::MAX));
         event_pos += payload_len;
     }
 
@@ -976,12 +1264,69 @@ pub fn parse_recording_from_bytes(data: &[u8]) -> Result<ParsedRecording, ParseE
     Ok(ParsedRecording {
         metadata,
         events,
+        event_offsets,
         seek_index,
         total_duration_us,
         total_events,
     })
 }
 
+impl ParsedRecording {
+    /// Number of events with a timestamp at or before `timestamp_us`.
+    ///
+    /// Jumps to the last seek-index entry at or before `timestamp_us` and
+    /// scans forward from there, so the scan covers at most one seek
+    /// interval of events however long the recording is.
+    #[must_use]
+    pub fn events_until(&self, timestamp_us: u64) -> usize {
+        let entries = self
+            .seek_index
+            .partition_point(|entry| entry.timestamp_us <= timestamp_us);
+        let start = entries
+            .checked_sub(1)
+            .and_then(|i| self.seek_index.get(i))
+            .and_then(|entry| self.event_offsets.binary_search(&entry.file_offset).ok())
+            .unwrap_or(0);
+        start
+            + self.events[start..]
+                .iter()
+                .take_while(|event| event.timestamp_us <= timestamp_us)
+                .count()
+    }
+
+    /// The events from `start_us` to `end_us` (inclusive) as a recording of
+    /// their own, ready for [`write_recording`].
+    ///
+    /// Timestamps are rebased so the slice starts at zero, and the initial
+    /// topology is the window/tab/pane tree as it stood at `start_us`.
+    /// Output from before `start_us` is not carried over, so panes start
+    /// blank.
+    #[must_use]
+    pub fn slice(&self, start_us: u64, end_us: u64) -> (RecordingMetadata, Vec<RecordingEvent>) {
+        let first = self
+            .events
+            .partition_point(|event| event.timestamp_us < start_us);
+        let last = self.events_until(end_us).max(first);
+
+        let metadata = RecordingMetadata {
+            created_at: self
+                .metadata
+                .created_at
+                .saturating_add(start_us / 1_000_000),
+            initial_topology: crate::playback::topology_after(self, first),
+            ..self.metadata.clone()
+        };
+        let events = self.events[first..last]
+            .iter()
+            .map(|event| RecordingEvent {
+                timestamp_us: event.timestamp_us - start_us,
+                payload: event.payload.clone(),
+            })
+            .collect();
+        (metadata, events)
+    }
+}
+
 /// Read a little-endian `u32` from a byte slice at the given offset.
 const fn read_u32_le(data: &[u8], offset: usize) -> u32 {
     let bytes: [u8; 4] = [
@@ -1304,6 +1649,14 @@ mod tests {
                     y: 300,
                 },
             },
+            RecordingEvent {
+                timestamp_us: 2400,
+                payload: EventPayload::Redaction {
+                    pane_id: 0,
+                    reason: 1,
+                    byte_count: 20,
+                },
+            },
         ];
 
         for event in &events {
@@ -1313,7 +1666,7 @@ mod tests {
 
     #[test]
     fn event_type_from_u8_round_trip() {
-        for id in 0x01..=0x17u8 {
+        for id in 0x01..=0x18u8 {
             let et = EventType::from_u8(id).unwrap_or_else(|| panic!("unknown event type {id:#x}"));
             assert_eq!(et.to_u8(), id);
         }
@@ -1322,7 +1675,7 @@ mod tests {
     #[test]
     fn event_type_from_u8_unknown() {
         assert_eq!(EventType::from_u8(0x00), None);
-        assert_eq!(EventType::from_u8(0x18), None);
+        assert_eq!(EventType::from_u8(0x19), None);
         assert_eq!(EventType::from_u8(0xFF), None);
     }
 
@@ -1514,6 +1867,14 @@ mod tests {
                 },
                 EventType::WindowMove,
             ),
+            (
+                EventPayload::Redaction {
+                    pane_id: 0,
+                    reason: 0,
+                    byte_count: 0,
+                },
+                EventType::Redaction,
+            ),
         ];
 
         for (payload, expected) in &cases {
@@ -1664,6 +2025,145 @@ mod tests {
         assert!(parsed.seek_index[3].timestamp_us >= 3_000_000);
     }
 
+    #[test]
+    fn events_until_uses_seek_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.frec");
+        let (handle, mut join) = start_recording(&path, test_metadata(), 256).unwrap();
+        for i in 0..35 {
+            handle.send(RecordingEvent {
+                timestamp_us: i * 100_000,
+                payload: EventPayload::PtyOutput {
+                    pane_id: 0,
+                    data: vec![b'A'],
+                },
+            });
+        }
+        drop(handle);
+        join.join();
+
+        let parsed = read_frec_file(&path);
+        assert_eq!(parsed.event_offsets.len(), parsed.events.len());
+        for entry in &parsed.seek_index {
+            assert!(parsed.event_offsets.contains(&entry.file_offset));
+        }
+
+        assert_eq!(parsed.events_until(0), 1);
+        assert_eq!(parsed.events_until(99_999), 1);
+        assert_eq!(parsed.events_until(1_000_000), 11);
+        assert_eq!(parsed.events_until(2_450_000), 25);
+        assert_eq!(parsed.events_until(u64::MAX), 35);
+    }
+
+    #[test]
+    fn slice_rebases_and_rebuilds_seek_index() {
+        let mut events: Vec<RecordingEvent> = (0..30)
+            .map(|i| RecordingEvent {
+                timestamp_us: i * 200_000,
+                payload: EventPayload::PtyOutput {
+                    pane_id: 1,
+                    data: vec![b'a'],
+                },
+            })
+            .collect();
+        events.insert(
+            5,
+            RecordingEvent {
+                timestamp_us: 900_000,
+                payload: EventPayload::PaneResize {
+                    pane_id: 1,
+                    cols: 132,
+                    rows: 50,
+                },
+            },
+        );
+
+        let mut original = std::io::Cursor::new(Vec::new());
+        write_recording(&mut original, &test_metadata(), &events).unwrap();
+        let parsed = parse_recording_from_bytes(original.get_ref()).unwrap();
+        assert_eq!(parsed.events, events);
+        assert_eq!(parsed.total_duration_us, 5_800_000);
+
+        let (metadata, sliced) = parsed.slice(2_000_000, 4_000_000);
+        assert_eq!(sliced.len(), 11);
+        assert_eq!(sliced[0].timestamp_us, 0);
+        assert_eq!(sliced[10].timestamp_us, 2_000_000);
+        assert_eq!(metadata.created_at, 1_700_000_002);
+        let PaneNodeSnapshot::Leaf { pane_id, cols, .. } =
+            &metadata.initial_topology.windows[0].tabs[0].pane_tree.node
+        else {
+            panic!("expected a single pane");
+        };
+        assert_eq!((*pane_id, *cols), (1, 132));
+
+        let mut out = std::io::Cursor::new(Vec::new());
+        write_recording(&mut out, &metadata, &sliced).unwrap();
+        let reparsed = parse_recording_from_bytes(out.get_ref()).unwrap();
+        assert_eq!(reparsed.events, sliced);
+        assert_eq!(reparsed.metadata, metadata);
+        assert_eq!(reparsed.total_events, 11);
+        assert_eq!(reparsed.total_duration_us, 2_000_000);
+        assert_eq!(reparsed.seek_index.len(), 3);
+        assert_eq!(reparsed.events_until(1_000_000), 6);
+    }
+
+    #[test]
+    fn fan_out_handle_feeds_every_sink() {
+        let (tx_a, rx_a) = crossbeam_channel::unbounded();
+        let (tx_b, rx_b) = crossbeam_channel::unbounded();
+        let (a, b) = (RecordingHandle::new(tx_a), RecordingHandle::new(tx_b));
+        assert!(RecordingHandle::fan_out([]).is_none());
+
+        let both = RecordingHandle::fan_out([&a, &b]).unwrap();
+        both.emit(EventPayload::PaneClose { pane_id: 3 });
+        for rx in [&rx_a, &rx_b] {
+            let event = rx.try_recv().unwrap();
+            assert_eq!(event.payload, EventPayload::PaneClose { pane_id: 3 });
+        }
+
+        // The combined handle keeps both channels open.
+        drop((a, b));
+        assert!(matches!(
+            rx_a.try_recv(),
+            Err(crossbeam_channel::TryRecvError::Empty)
+        ));
+        drop(both);
+        assert!(matches!(
+            rx_b.try_recv(),
+            Err(crossbeam_channel::TryRecvError::Disconnected)
+        ));
+    }
+
+    #[test]
+    fn event_type_names_round_trip() {
+        for ty in EventType::ALL {
+            assert_eq!(EventType::from_name(ty.name()), Some(ty));
+            assert_eq!(EventType::from_u8(ty.to_u8()), Some(ty));
+        }
+        assert_eq!(
+            EventType::from_name("ptyoutput"),
+            Some(EventType::PtyOutput)
+        );
+        assert_eq!(EventType::from_name("Nope"), None);
+    }
+
+    #[test]
+    fn involves_pane_matches_both_sides_of_a_split() {
+        let split = EventPayload::PaneSplit {
+            window_id: 0,
+            parent_pane: 1,
+            new_pane: 2,
+            direction: RecordingSplitDirection::Vertical,
+            ratio: 0.5,
+            cols: 80,
+            rows: 12,
+        };
+        assert!(split.involves_pane(1));
+        assert!(split.involves_pane(2));
+        assert!(!split.involves_pane(3));
+        assert!(!EventPayload::WindowClose { window_id: 1 }.involves_pane(1));
+    }
+
     #[test]
     fn writer_event_ordering_preserved() {
         let dir = tempfile::tempdir().unwrap();
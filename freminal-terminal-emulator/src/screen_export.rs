@@ -0,0 +1,558 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Dump the visible screen of a [`TerminalSnapshot`] as plain text, ANSI,
+//! HTML, or SVG.
+//!
+//! Every format covers exactly `term_height` rows. Plain text and ANSI drop
+//! trailing blank cells on each row; HTML and SVG keep the full grid so the
+//! image has the terminal's size. Colors are resolved against the snapshot's
+//! theme the same way the GPU renderer does: reverse video and DECSCNM swap
+//! foreground and background, and faint text is blended halfway into its
+//! background.
+//!
+//! Blinking text is drawn in its visible phase, and inline images and the
+//! cursor are not drawn.
+
+use std::fmt::Write as _;
+
+use freminal_common::buffer_states::{
+    fonts::{BlinkState, FontDecorations, FontWeight, UnderlineStyle},
+    format_tag::FormatTag,
+    tchar::TChar,
+};
+use freminal_common::colors::TerminalColor;
+use freminal_common::themes::ThemePalette;
+
+use crate::snapshot::TerminalSnapshot;
+
+/// Width of one cell in an SVG dump, in pixels.
+pub const SVG_CELL_WIDTH: usize = 9;
+
+/// Height of one cell in an SVG dump, in pixels.
+pub const SVG_CELL_HEIGHT: usize = 18;
+
+/// Font size used for SVG text. A monospace glyph is about 0.6em wide, so
+/// this fills [`SVG_CELL_WIDTH`].
+const SVG_FONT_SIZE: usize = 15;
+
+/// Distance from the top of a cell to the text baseline in an SVG dump.
+const SVG_BASELINE: usize = 14;
+
+/// Consecutive cells of one row that share a format.
+struct Run<'a> {
+    text: String,
+    /// Number of columns the run covers (wide characters count twice).
+    cells: usize,
+    tag: &'a FormatTag,
+}
+
+impl Run<'_> {
+    fn is_blank(&self) -> bool {
+        self.text.bytes().all(|b| b == b' ')
+    }
+}
+
+/// Resolve a color to RGB against `theme`, the way the renderer does.
+#[must_use]
+pub fn color_rgb(color: TerminalColor, theme: &ThemePalette) -> (u8, u8, u8) {
+    match color {
+        TerminalColor::Default
+        | TerminalColor::DefaultUnderlineColor
+        | TerminalColor::DefaultCursorColor => theme.foreground,
+        TerminalColor::DefaultBackground => theme.background,
+        TerminalColor::Black => theme.ansi[0],
+        TerminalColor::Red => theme.ansi[1],
+        TerminalColor::Green => theme.ansi[2],
+        TerminalColor::Yellow => theme.ansi[3],
+        TerminalColor::Blue => theme.ansi[4],
+        TerminalColor::Magenta => theme.ansi[5],
+        TerminalColor::Cyan => theme.ansi[6],
+        TerminalColor::White => theme.ansi[7],
+        TerminalColor::BrightBlack => theme.ansi[8],
+        TerminalColor::BrightRed => theme.ansi[9],
+        TerminalColor::BrightGreen => theme.ansi[10],
+        TerminalColor::BrightYellow => theme.ansi[11],
+        TerminalColor::BrightBlue => theme.ansi[12],
+        TerminalColor::BrightMagenta => theme.ansi[13],
+        TerminalColor::BrightCyan => theme.ansi[14],
+        TerminalColor::BrightWhite => theme.ansi[15],
+        TerminalColor::Custom(r, g, b) => (r, g, b),
+        TerminalColor::PaletteIndex(_) => color_rgb(color.resolve_palette_default(theme), theme),
+    }
+}
+
+/// The resolved foreground and background of a cell with format `tag`.
+#[must_use]
+pub fn cell_rgb(
+    tag: &FormatTag,
+    reverse_screen: bool,
+    theme: &ThemePalette,
+) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let (fg, bg) = if reverse_screen {
+        (tag.colors.background_color(), tag.colors.color())
+    } else {
+        (tag.colors.color(), tag.colors.background_color())
+    };
+    let bg = color_rgb(bg, theme);
+    let mut fg = color_rgb(fg, theme);
+    if tag.font_decorations.contains(FontDecorations::Faint) {
+        fg = blend(fg, bg);
+    }
+    (fg, bg)
+}
+
+/// The colors of the blank screen: what unwritten cells look like.
+fn screen_rgb(snap: &TerminalSnapshot) -> ((u8, u8, u8), (u8, u8, u8)) {
+    cell_rgb(&FormatTag::default(), !snap.is_normal_display, snap.theme)
+}
+
+const fn blend(a: (u8, u8, u8), b: (u8, u8, u8)) -> (u8, u8, u8) {
+    const fn mid(x: u8, y: u8) -> u8 {
+        x / 2 + y / 2 + (x & y & 1)
+    }
+    (mid(a.0, b.0), mid(a.1, b.1), mid(a.2, b.2))
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Split the visible screen into rows of formatted runs.
+fn screen_rows(snap: &TerminalSnapshot) -> Vec<Vec<Run<'_>>> {
+    static DEFAULT_TAG: std::sync::LazyLock<FormatTag> =
+        std::sync::LazyLock::new(FormatTag::default);
+
+    let tags = &snap.visible_tags;
+    let mut rows: Vec<Vec<Run<'_>>> = vec![Vec::new()];
+    let mut tag_index = 0;
+    for (index, ch) in snap.visible_chars.iter().enumerate() {
+        if *ch == TChar::NewLine {
+            rows.push(Vec::new());
+            continue;
+        }
+        while tags.get(tag_index).is_some_and(|tag| tag.end <= index) {
+            tag_index += 1;
+        }
+        let tag = tags
+            .get(tag_index)
+            .filter(|tag| tag.start <= index)
+            .unwrap_or(&DEFAULT_TAG);
+        let Some(row) = rows.last_mut() else {
+            continue;
+        };
+        let text = std::str::from_utf8(ch.as_bytes()).unwrap_or("\u{fffd}");
+        let cells = ch.display_width().max(1);
+        match row.last_mut() {
+            Some(run) if std::ptr::eq(run.tag, tag) => {
+                run.text.push_str(text);
+                run.cells += cells;
+            }
+            _ => row.push(Run {
+                text: text.to_owned(),
+                cells,
+                tag,
+            }),
+        }
+    }
+
+    let mut rows: Vec<_> = rows
+        .into_iter()
+        .skip(snap.window_extra_rows)
+        .take(snap.term_height)
+        .collect();
+    rows.resize_with(snap.term_height, Vec::new);
+    rows
+}
+
+/// Whether blank cells of `run` still show something: a background other
+/// than the screen's, or a line drawn through them.
+fn blanks_are_visible(run: &Run<'_>, snap: &TerminalSnapshot) -> bool {
+    let decorations = run.tag.font_decorations;
+    cell_rgb(run.tag, !snap.is_normal_display, snap.theme).1 != screen_rgb(snap).1
+        || decorations.underline_style().is_active()
+        || decorations.contains(FontDecorations::Strikethrough)
+}
+
+/// Drop trailing runs that draw nothing: spaces on the screen background.
+fn trim_row<'a, 'b>(row: &'b [Run<'a>], snap: &TerminalSnapshot) -> &'b [Run<'a>] {
+    let visible = row
+        .iter()
+        .rposition(|run| !run.is_blank() || blanks_are_visible(run, snap))
+        .map_or(0, |last| last + 1);
+    &row[..visible]
+}
+
+/// The screen as plain text, one line per row with trailing spaces removed.
+#[must_use]
+pub fn to_text(snap: &TerminalSnapshot) -> String {
+    let mut out = String::new();
+    for row in screen_rows(snap) {
+        let line: String = row.iter().map(|run| run.text.as_str()).collect();
+        out.push_str(line.trim_end_matches(' '));
+        out.push('\n');
+    }
+    out
+}
+
+/// The screen as text with SGR sequences (and OSC 8 hyperlinks) that
+/// reproduce its formatting when written to a terminal.
+///
+/// Colors are written as the program set them (named, 256-color, or RGB)
+/// rather than resolved against the theme.
+#[must_use]
+pub fn to_ansi(snap: &TerminalSnapshot) -> String {
+    let mut out = String::new();
+    for row in screen_rows(snap) {
+        let mut url: Option<&str> = None;
+        let row = trim_row(&row, snap);
+        for (index, run) in row.iter().enumerate() {
+            let run_url = run.tag.url.as_deref().map(|u| u.url.as_str());
+            if run_url != url {
+                let _ = write!(out, "\x1b]8;;{}\x1b\\", run_url.unwrap_or_default());
+                url = run_url;
+            }
+            let text = if index + 1 == row.len() && !blanks_are_visible(run, snap) {
+                run.text.trim_end_matches(' ')
+            } else {
+                &run.text
+            };
+            let _ = write!(out, "\x1b[{}m{text}", sgr_params(run.tag));
+        }
+        if url.is_some() {
+            out.push_str("\x1b]8;;\x1b\\");
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// SGR parameters that select `tag`'s format from a reset state.
+fn sgr_params(tag: &FormatTag) -> String {
+    let mut params = vec![String::from("0")];
+    if tag.font_weight == FontWeight::Bold {
+        params.push("1".into());
+    }
+    let decorations = tag.font_decorations;
+    if decorations.contains(FontDecorations::Faint) {
+        params.push("2".into());
+    }
+    if decorations.contains(FontDecorations::Italic) {
+        params.push("3".into());
+    }
+    match decorations.underline_style() {
+        UnderlineStyle::None => {}
+        UnderlineStyle::Single => params.push("4".into()),
+        UnderlineStyle::Double => params.push("4:2".into()),
+        UnderlineStyle::Curly => params.push("4:3".into()),
+        UnderlineStyle::Dotted => params.push("4:4".into()),
+        UnderlineStyle::Dashed => params.push("4:5".into()),
+    }
+    match tag.blink {
+        BlinkState::None => {}
+        BlinkState::Slow => params.push("5".into()),
+        BlinkState::Fast => params.push("6".into()),
+    }
+    if tag.colors.reverse_video == freminal_common::buffer_states::cursor::ReverseVideo::On {
+        params.push("7".into());
+    }
+    if decorations.contains(FontDecorations::Strikethrough) {
+        params.push("9".into());
+    }
+    params.extend(sgr_color(tag.colors.color, 30, 90, "38"));
+    params.extend(sgr_color(tag.colors.background_color, 40, 100, "48"));
+    params.extend(sgr_color(tag.colors.underline_color, 0, 0, "58"));
+    params.join(";")
+}
+
+/// The SGR parameter for `color` in one slot: `base` + n for the normal
+/// colors, `bright_base` + n for the bright ones, and `extended` (`38`,
+/// `48`, `58`) for indexed and RGB colors. Slots without a short form
+/// (underline) pass `0` for both bases.
+fn sgr_color(color: TerminalColor, base: u8, bright_base: u8, extended: &str) -> Option<String> {
+    let named = |index: u8| {
+        if base == 0 {
+            format!("{extended};5;{index}")
+        } else if index < 8 {
+            (base + index).to_string()
+        } else {
+            (bright_base + index - 8).to_string()
+        }
+    };
+    let param = match color {
+        TerminalColor::Default
+        | TerminalColor::DefaultBackground
+        | TerminalColor::DefaultUnderlineColor
+        | TerminalColor::DefaultCursorColor => return None,
+        TerminalColor::Black => named(0),
+        TerminalColor::Red => named(1),
+        TerminalColor::Green => named(2),
+        TerminalColor::Yellow => named(3),
+        TerminalColor::Blue => named(4),
+        TerminalColor::Magenta => named(5),
+        TerminalColor::Cyan => named(6),
+        TerminalColor::White => named(7),
+        TerminalColor::BrightBlack => named(8),
+        TerminalColor::BrightRed => named(9),
+        TerminalColor::BrightGreen => named(10),
+        TerminalColor::BrightYellow => named(11),
+        TerminalColor::BrightBlue => named(12),
+        TerminalColor::BrightMagenta => named(13),
+        TerminalColor::BrightCyan => named(14),
+        TerminalColor::BrightWhite => named(15),
+        TerminalColor::PaletteIndex(index) => format!("{extended};5;{index}"),
+        TerminalColor::Custom(r, g, b) => format!("{extended};2;{r};{g};{b}"),
+    };
+    Some(param)
+}
+
+/// CSS declarations for `tag`, leaving out what matches the screen colors.
+fn css_style(tag: &FormatTag, snap: &TerminalSnapshot) -> String {
+    let (text_color, canvas) = screen_rgb(snap);
+    let (fg, bg) = cell_rgb(tag, !snap.is_normal_display, snap.theme);
+    let mut style = String::new();
+    if fg != text_color {
+        let _ = write!(style, "color:{};", hex(fg));
+    }
+    if bg != canvas {
+        let _ = write!(style, "background-color:{};", hex(bg));
+    }
+    if tag.font_weight == FontWeight::Bold {
+        style.push_str("font-weight:bold;");
+    }
+    if tag.font_decorations.contains(FontDecorations::Italic) {
+        style.push_str("font-style:italic;");
+    }
+    style.push_str(&text_decoration(tag, snap, ":", ";"));
+    style
+}
+
+/// `text-decoration` declarations (CSS) or attributes (SVG) for `tag`.
+fn text_decoration(tag: &FormatTag, snap: &TerminalSnapshot, assign: &str, end: &str) -> String {
+    let decorations = tag.font_decorations;
+    let underline = decorations.underline_style();
+    let mut lines = Vec::new();
+    if underline.is_active() {
+        lines.push("underline");
+    }
+    if decorations.contains(FontDecorations::Strikethrough) {
+        lines.push("line-through");
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("text-decoration-line{assign}{}{end}", lines.join(" "));
+    let line_style = match underline {
+        UnderlineStyle::Double => Some("double"),
+        UnderlineStyle::Curly => Some("wavy"),
+        UnderlineStyle::Dotted => Some("dotted"),
+        UnderlineStyle::Dashed => Some("dashed"),
+        UnderlineStyle::None | UnderlineStyle::Single => None,
+    };
+    if let Some(line_style) = line_style {
+        let _ = write!(out, "text-decoration-style{assign}{line_style}{end}");
+    }
+    if underline.is_active() && tag.colors.underline_color != TerminalColor::DefaultUnderlineColor {
+        let color = color_rgb(tag.colors.underline_color(), snap.theme);
+        let _ = write!(out, "text-decoration-color{assign}{}{end}", hex(color));
+    }
+    out
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// The screen as a self-contained HTML `<pre>` block with inline styles.
+#[must_use]
+pub fn to_html(snap: &TerminalSnapshot) -> String {
+    let (fg, bg) = screen_rgb(snap);
+    let mut out = format!(
+        "<pre class=\"freminal-screen\" style=\"margin:0;padding:0.5em;\
+         font-family:monospace;line-height:1.2;color:{};background-color:{}\">",
+        hex(fg),
+        hex(bg)
+    );
+    for (index, row) in screen_rows(snap).iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        for run in row {
+            let text = escape_xml(&run.text);
+            let style = css_style(run.tag, snap);
+            let span = if style.is_empty() {
+                text
+            } else {
+                format!("<span style=\"{style}\">{text}</span>")
+            };
+            match &run.tag.url {
+                Some(url) => {
+                    let _ = write!(out, "<a href=\"{}\">{span}</a>", escape_xml(&url.url));
+                }
+                None => out.push_str(&span),
+            }
+        }
+    }
+    out.push_str("</pre>\n");
+    out
+}
+
+/// The screen as an SVG image of `term_width` x `term_height` cells, each
+/// [`SVG_CELL_WIDTH`] x [`SVG_CELL_HEIGHT`] pixels.
+#[must_use]
+pub fn to_svg(snap: &TerminalSnapshot) -> String {
+    let (text_color, canvas) = screen_rgb(snap);
+    let width = snap.term_width * SVG_CELL_WIDTH;
+    let height = snap.term_height * SVG_CELL_HEIGHT;
+    let mut backgrounds = String::new();
+    let mut texts = String::new();
+
+    for (row_index, row) in screen_rows(snap).iter().enumerate() {
+        let top = row_index * SVG_CELL_HEIGHT;
+        let mut col = 0;
+        for run in row {
+            let x = col * SVG_CELL_WIDTH;
+            let run_width = run.cells * SVG_CELL_WIDTH;
+            col += run.cells;
+            let (fg, bg) = cell_rgb(run.tag, !snap.is_normal_display, snap.theme);
+            if bg != canvas {
+                let _ = writeln!(
+                    backgrounds,
+                    "<rect x=\"{x}\" y=\"{top}\" width=\"{run_width}\" height=\"{SVG_CELL_HEIGHT}\" fill=\"{}\"/>",
+                    hex(bg)
+                );
+            }
+            if run.is_blank() && run.tag.font_decorations.is_empty() {
+                continue;
+            }
+
+            let mut attrs = format!(
+                "x=\"{x}\" y=\"{}\" textLength=\"{run_width}\" lengthAdjust=\"spacingAndGlyphs\"",
+                top + SVG_BASELINE
+            );
+            if fg != text_color {
+                let _ = write!(attrs, " fill=\"{}\"", hex(fg));
+            }
+            if run.tag.font_weight == FontWeight::Bold {
+                attrs.push_str(" font-weight=\"bold\"");
+            }
+            if run.tag.font_decorations.contains(FontDecorations::Italic) {
+                attrs.push_str(" font-style=\"italic\"");
+            }
+            let decoration = text_decoration(run.tag, snap, ":", ";");
+            if !decoration.is_empty() {
+                let _ = write!(attrs, " style=\"{decoration}\"");
+            }
+            let text = format!("<text {attrs}>{}</text>", escape_xml(&run.text));
+            match &run.tag.url {
+                Some(url) => {
+                    let _ = writeln!(texts, "<a href=\"{}\">{text}</a>", escape_xml(&url.url));
+                }
+                None => {
+                    let _ = writeln!(texts, "{text}");
+                }
+            }
+        }
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n\
+         {backgrounds}\
+         <g font-family=\"monospace\" font-size=\"{SVG_FONT_SIZE}\" fill=\"{}\" xml:space=\"preserve\">\n\
+         {texts}\
+         </g>\n\
+         </svg>\n",
+        hex(canvas),
+        hex(text_color)
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::interface::TerminalEmulator;
+
+    fn screen(cols: usize, rows: usize, data: &[u8]) -> TerminalSnapshot {
+        let (mut emu, _rx) = TerminalEmulator::new_headless(None);
+        emu.internal.set_win_size(cols, rows, 0, 0);
+        emu.handle_incoming_data(data);
+        emu.build_snapshot()
+    }
+
+    #[test]
+    fn text_has_one_trimmed_line_per_row() {
+        let snap = screen(10, 3, b"hello\r\n  world");
+        assert_eq!(to_text(&snap), "hello\n  world\n\n");
+    }
+
+    #[test]
+    fn ansi_preserves_sgr() {
+        let snap = screen(20, 1, b"a\x1b[1;31mb\x1b[0;48;2;1;2;3mc");
+        assert_eq!(
+            to_ansi(&snap),
+            "\x1b[0ma\x1b[0;1;31mb\x1b[0;48;2;1;2;3mc\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn ansi_keeps_trailing_colored_blanks() {
+        let snap = screen(10, 1, b"x\x1b[44m  \x1b[0m   ");
+        assert_eq!(to_ansi(&snap), "\x1b[0mx\x1b[0;44m  \x1b[0m\n");
+    }
+
+    #[test]
+    fn html_resolves_colors_against_the_theme() {
+        let snap = screen(10, 1, b"\x1b[31m<b>");
+        let red = hex(snap.theme.ansi[1]);
+        let html = to_html(&snap);
+        assert!(html.starts_with("<pre class=\"freminal-screen\""));
+        assert!(html.contains(&format!("<span style=\"color:{red};\">&lt;b&gt;</span>")));
+    }
+
+    #[test]
+    fn reverse_video_swaps_resolved_colors() {
+        let snap = screen(10, 1, b"\x1b[7mx");
+        let tag = snap.visible_tags.first().unwrap();
+        let (fg, bg) = cell_rgb(tag, false, snap.theme);
+        assert_eq!(fg, snap.theme.ansi[0]);
+        assert_eq!(bg, snap.theme.ansi[7]);
+    }
+
+    #[test]
+    fn svg_covers_the_whole_grid() {
+        let snap = screen(4, 2, b"\x1b[42mok");
+        let svg = to_svg(&snap);
+        let green = hex(snap.theme.ansi[2]);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"36\" height=\"36\"")
+        );
+        assert!(svg.contains(&format!(
+            "<rect x=\"0\" y=\"0\" width=\"18\" height=\"18\" fill=\"{green}\"/>"
+        )));
+        assert!(svg.contains(">ok</text>"));
+    }
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        let snap = screen(6, 1, "\u{4e2d}x".as_bytes());
+        assert_eq!(to_text(&snap), "\u{4e2d}x\n");
+        let svg = to_svg(&snap);
+        assert!(svg.contains("textLength=\"27\""));
+    }
+}
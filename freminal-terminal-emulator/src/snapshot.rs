@@ -11,7 +11,11 @@
 //! thread loads the snapshot with a single atomic pointer load — no lock, no
 //! blocking.
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use freminal_buffer::image_store::{ImagePlacement, InlineImage};
 use freminal_common::{
@@ -31,13 +35,24 @@ use freminal_common::{
             mouse::{MouseEncoding, MouseTrack},
             rl_bracket::RlBracket,
         },
+        osc_conemu::TaskProgress,
         pointer_shape::PointerShape,
+        soft_font::SoftFont,
         tchar::TChar,
     },
+    colors::SpecialColors,
     cursor::CursorVisualStyle,
     themes::ThemePalette,
 };
 
+/// The status line drawn below the main display (DECSSDT), flattened the
+/// same way as `visible_chars` / `visible_tags` for its single row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusLineSnapshot {
+    pub chars: Vec<TChar>,
+    pub tags: Vec<FormatTag>,
+}
+
 /// A point-in-time snapshot of the terminal state, ready for the GUI to render.
 ///
 /// All expensive work (flattening rows → `Vec<TChar>` / `Vec<FormatTag>`) is
@@ -196,6 +211,12 @@ pub struct TerminalSnapshot {
     /// Defaults to `X11` when no encoding mode has been explicitly set.
     pub mouse_encoding: MouseEncoding,
 
+    /// Whether DEC locator reporting (DECELR) is on.
+    ///
+    /// While it is, the GUI sends pointer input to the emulator instead of
+    /// encoding xterm mouse reports.
+    pub locator_enabled: bool,
+
     /// Whether the terminal should repeat key-press events while a key is held.
     pub repeat_keys: Decarm,
 
@@ -286,6 +307,17 @@ pub struct TerminalSnapshot {
     /// The GUI can use this to display command success/failure indicators.
     pub last_exit_code: Option<i32>,
 
+    /// Task progress from `OSC 9 ; 4`, shown on the pane's tab.
+    pub progress: Option<TaskProgress>,
+
+    /// User variables set by `iTerm2`'s `OSC 1337 ; SetUserVar=`, for the
+    /// `{user.<name>}` tab-title template tokens.
+    pub user_vars: Arc<BTreeMap<String, String>>,
+
+    /// The badge set by `OSC 1337 ; SetBadgeFormat=`, already expanded,
+    /// drawn over the top-right corner of the pane.
+    pub badge: Option<Arc<str>>,
+
     /// Absolute buffer row indices where OSC 133 prompt-start markers fired.
     ///
     /// Used by the GUI for command-boundary jumping (Ctrl+Shift+Up/Down).
@@ -317,6 +349,24 @@ pub struct TerminalSnapshot {
     /// the theme's `cursor` field.
     pub cursor_color_override: Option<(u8, u8, u8)>,
 
+    /// Dynamic highlight (selection) background override (set via OSC 17;
+    /// reset via OSC 117).
+    ///
+    /// When `Some`, the selection should be drawn in this color instead of
+    /// the theme's `selection_bg` field.
+    pub highlight_bg_override: Option<(u8, u8, u8)>,
+
+    /// Dynamic highlight (selection) foreground override (set via OSC 19;
+    /// reset via OSC 119).
+    ///
+    /// When `Some`, selected text should be drawn in this color instead of
+    /// the theme's `selection_fg` field.
+    pub highlight_fg_override: Option<(u8, u8, u8)>,
+
+    /// Special colors for bold, underlined, blinking and reverse text in the
+    /// default foreground (set via OSC 5; reset via OSC 105).
+    pub special_colors: SpecialColors,
+
     /// Pointer (mouse cursor) shape requested by the application via OSC 22.
     ///
     /// The GUI maps this to `egui::CursorIcon` during the render pass.
@@ -348,6 +398,19 @@ pub struct TerminalSnapshot {
     /// this to apply 2× horizontal scaling for DECDWL rows and 2× scaling in
     /// both dimensions (with top/bottom clipping) for DECDHL rows.
     pub visible_line_widths: Arc<Vec<freminal_buffer::row::LineWidth>>,
+
+    /// The status line selected by DECSSDT, if any.
+    ///
+    /// It is not part of `term_height`: the GUI gives it a row of its own
+    /// below the grid.  The `Arc` is reused while the line is unchanged, so
+    /// `Arc::ptr_eq` tells the GUI whether to redraw it.
+    pub status_line: Option<Arc<StatusLineSnapshot>>,
+
+    /// The soft character sets loaded by DECDLD.
+    ///
+    /// Cells written through a soft set hold private-use characters that the
+    /// renderer draws from here.  The `Arc` only changes with a load.
+    pub soft_font: Arc<SoftFont>,
 }
 
 impl TerminalSnapshot {
@@ -380,6 +443,7 @@ impl TerminalSnapshot {
             bracketed_paste: RlBracket::default(),
             mouse_tracking: MouseTrack::default(),
             mouse_encoding: MouseEncoding::default(),
+            locator_enabled: false,
             repeat_keys: Decarm::RepeatKey,
             cursor_key_app_mode: Decckm::Ansi,
             keypad_app_mode: KeypadMode::Numeric,
@@ -394,13 +458,21 @@ impl TerminalSnapshot {
             shell_histfile: None,
             ftcs_state: FtcsState::default(),
             last_exit_code: None,
+            progress: None,
+            user_vars: Arc::default(),
+            badge: None,
             prompt_rows: Arc::from([]),
             command_blocks: Arc::from(Vec::<CommandBlock>::new()),
             theme: &freminal_common::themes::CATPPUCCIN_MOCHA,
             images: Arc::new(HashMap::new()),
             visible_image_placements: Arc::new(Vec::new()),
             visible_line_widths: Arc::new(Vec::new()),
+            status_line: None,
+            soft_font: Arc::default(),
             cursor_color_override: None,
+            highlight_bg_override: None,
+            highlight_fg_override: None,
+            special_colors: SpecialColors::default(),
             pointer_shape: PointerShape::Default,
         }
     }
@@ -0,0 +1,193 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! xterm's palette stack for [`TerminalHandler`].
+//!
+//! XTPUSHCOLORS saves the 256-color palette (OSC 4) and the dynamic
+//! foreground, background and cursor colors (OSC 10/11/12); XTPOPCOLORS
+//! restores them, so an application that changes the theme can put ours
+//! back exactly when it exits.  XTREPORTCOLORS reports the stack depth.
+//!
+//! - [`TerminalHandler::handle_push_colors`]
+//! - [`TerminalHandler::handle_pop_colors`]
+//! - [`TerminalHandler::handle_report_colors`]
+
+use freminal_common::colors::ColorPalette;
+
+use super::TerminalHandler;
+
+/// How many palettes XTPUSHCOLORS can save, as in xterm.
+pub(super) const COLOR_STACK_LIMIT: usize = 10;
+
+/// The colors one XTPUSHCOLORS saves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SavedColors {
+    palette: ColorPalette,
+    fg_color_override: Option<(u8, u8, u8)>,
+    bg_color_override: Option<(u8, u8, u8)>,
+    cursor_color_override: Option<(u8, u8, u8)>,
+}
+
+/// The palette stack.
+///
+/// Slots are numbered 1 to [`COLOR_STACK_LIMIT`], as XTPUSHCOLORS and
+/// XTPOPCOLORS address them; the first `depth` slots are the stack.
+#[derive(Debug, Default)]
+pub(super) struct ColorStack {
+    slots: [Option<SavedColors>; COLOR_STACK_LIMIT],
+    depth: usize,
+}
+
+impl ColorStack {
+    /// Index into `slots` for the slot number `n`, if it is in range.
+    fn index(n: usize) -> Option<usize> {
+        (1..=COLOR_STACK_LIMIT).contains(&n).then(|| n - 1)
+    }
+
+    /// The number of slots holding a palette.
+    fn stored(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+}
+
+impl TerminalHandler {
+    fn saved_colors(&self) -> SavedColors {
+        SavedColors {
+            palette: self.palette.clone(),
+            fg_color_override: self.fg_color_override,
+            bg_color_override: self.bg_color_override,
+            cursor_color_override: self.cursor_color_override,
+        }
+    }
+
+    fn restore_colors(&mut self, saved: SavedColors) {
+        self.palette = saved.palette;
+        self.fg_color_override = saved.fg_color_override;
+        self.bg_color_override = saved.bg_color_override;
+        self.cursor_color_override = saved.cursor_color_override;
+    }
+
+    /// Handle XTPUSHCOLORS — push the colors onto the stack, or with a
+    /// slot number store them there without pushing.
+    ///
+    /// Pushes beyond [`COLOR_STACK_LIMIT`] are ignored.
+    pub fn handle_push_colors(&mut self, slot: Option<usize>) {
+        let index = match slot {
+            None if self.color_stack.depth < COLOR_STACK_LIMIT => {
+                self.color_stack.depth += 1;
+                self.color_stack.depth - 1
+            }
+            None => return,
+            Some(n) => {
+                let Some(index) = ColorStack::index(n) else {
+                    tracing::warn!("XTPUSHCOLORS: slot {n} out of range (ignored)");
+                    return;
+                };
+                index
+            }
+        };
+        self.color_stack.slots[index] = Some(self.saved_colors());
+    }
+
+    /// Handle XTPOPCOLORS — pop the colors off the stack, or with a slot
+    /// number restore that slot without popping.  A no-op when the stack
+    /// or the slot is empty.
+    pub fn handle_pop_colors(&mut self, slot: Option<usize>) {
+        let saved = match slot {
+            None if self.color_stack.depth > 0 => {
+                self.color_stack.depth -= 1;
+                self.color_stack.slots[self.color_stack.depth].take()
+            }
+            None => None,
+            Some(n) => {
+                let Some(index) = ColorStack::index(n) else {
+                    tracing::warn!("XTPOPCOLORS: slot {n} out of range (ignored)");
+                    return;
+                };
+                self.color_stack.slots[index].clone()
+            }
+        };
+        if let Some(saved) = saved {
+            self.restore_colors(saved);
+        }
+    }
+
+    /// Handle XTREPORTCOLORS — reply `CSI Ps ; Ps # Q` with the stack
+    /// depth and the number of saved palettes.
+    pub fn handle_report_colors(&mut self) {
+        let depth = self.color_stack.depth;
+        let stored = self.color_stack.stored();
+        self.write_csi_response(&format!("{depth};{stored}#Q"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handler() -> TerminalHandler {
+        TerminalHandler::new(80, 24)
+    }
+
+    #[test]
+    fn pop_restores_the_pushed_colors() {
+        let mut handler = handler();
+        handler.palette.set(1, 10, 20, 30);
+        handler.fg_color_override = Some((1, 2, 3));
+        handler.handle_push_colors(None);
+
+        handler.palette.set(1, 200, 200, 200);
+        handler.palette.set(2, 5, 5, 5);
+        handler.fg_color_override = None;
+        handler.bg_color_override = Some((4, 5, 6));
+        handler.cursor_color_override = Some((7, 8, 9));
+        handler.handle_pop_colors(None);
+
+        let mut expected = ColorPalette::default();
+        expected.set(1, 10, 20, 30);
+        assert_eq!(handler.palette, expected);
+        assert_eq!(handler.fg_color_override, Some((1, 2, 3)));
+        assert_eq!(handler.bg_color_override, None);
+        assert_eq!(handler.cursor_color_override, None);
+        assert_eq!(handler.color_stack.depth, 0);
+    }
+
+    #[test]
+    fn slots_are_read_without_popping() {
+        let mut handler = handler();
+        handler.palette.set(3, 1, 1, 1);
+        handler.handle_push_colors(Some(4));
+        assert_eq!(handler.color_stack.depth, 0);
+
+        handler.palette.reset_all();
+        handler.handle_pop_colors(Some(4));
+        assert_eq!(handler.palette.rgb(3, handler.theme()), (1, 1, 1));
+        handler.palette.reset_all();
+        handler.handle_pop_colors(Some(4));
+        assert_eq!(handler.palette.rgb(3, handler.theme()), (1, 1, 1));
+    }
+
+    #[test]
+    fn stack_is_bounded() {
+        let mut handler = handler();
+        for _ in 0..=COLOR_STACK_LIMIT {
+            handler.handle_push_colors(None);
+        }
+        assert_eq!(handler.color_stack.depth, COLOR_STACK_LIMIT);
+
+        handler.handle_push_colors(Some(COLOR_STACK_LIMIT + 1));
+        handler.handle_pop_colors(Some(0));
+        assert_eq!(handler.color_stack.stored(), COLOR_STACK_LIMIT);
+    }
+
+    #[test]
+    fn empty_pop_changes_nothing() {
+        let mut handler = handler();
+        handler.fg_color_override = Some((1, 2, 3));
+        handler.handle_pop_colors(None);
+        handler.handle_pop_colors(Some(2));
+        assert_eq!(handler.fg_color_override, Some((1, 2, 3)));
+    }
+}
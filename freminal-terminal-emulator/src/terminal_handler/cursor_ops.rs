@@ -90,14 +90,14 @@ impl TerminalHandler {
     /// Handle DECSC — save the current cursor position, SGR state, and character set.
     pub fn handle_save_cursor(&mut self) {
         self.buffer.save_cursor();
-        self.saved_character_replace = Some(self.character_replace.clone());
+        self.saved_charsets = Some(self.charsets);
     }
 
     /// Handle DECRC — restore the cursor position, SGR state, and character set saved by the most recent DECSC.
     pub fn handle_restore_cursor(&mut self) {
         self.buffer.restore_cursor();
-        if let Some(saved) = &self.saved_character_replace {
-            self.character_replace = saved.clone();
+        if let Some(saved) = self.saved_charsets {
+            self.charsets = saved;
         }
     }
 
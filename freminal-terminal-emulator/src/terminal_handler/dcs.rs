@@ -10,17 +10,34 @@
 //! - [`TerminalHandler::handle_device_control_string`] — main entry point
 //! - DECRQSS (`$ q`) — Request Selection or Setting
 //! - XTGETTCAP (`+ q`) — xterm termcap/terminfo capability query
+//! - DECRSPS (`Ps $ t`) — restore the DECCIR or DECTABSR presentation state
+//! - DECDLD (`Pn;… {`) — load a soft character set
 //! - tmux DCS passthrough (`tmux;`) — un-doubles ESC bytes and dispatches the
 //!   inner escape sequence to the appropriate handler
 //! - CSI direct dispatch for tmux passthrough ordering correctness
+//! - tmux control mode (`DCS 1000 p`) — the handshake arrives as its own
+//!   `TerminalOutput` (the string stays open for the whole session) and each
+//!   protocol line is handed to [`crate::tmux_control::TmuxControlSession`]
+
+use std::sync::Arc;
 
 use conv2::ValueFrom;
-use freminal_common::{buffer_states::modes::s8c1t::S8c1t, cursor::CursorVisualStyle};
+use freminal_common::{
+    buffer_states::{
+        charset::{Charset, GSet},
+        modes::{decom::Decom, s8c1t::S8c1t},
+        presentation_state::{CursorInformation, parse_tab_stops},
+        rectangle::RectAttributes,
+        soft_font::{SoftFont, SoftFontLoad},
+    },
+    cursor::CursorVisualStyle,
+};
 
 use super::TerminalHandler;
 use crate::ansi_components::csi_commands::ed::EraseDisplayMode;
 use crate::ansi_components::csi_commands::el::EraseLineMode;
 use crate::ansi_components::tracer::escape_sequence_for_log;
+use crate::tmux_control::{TmuxControlSession, TmuxEvent};
 
 impl TerminalHandler {
     /// Handle a DCS (Device Control String) sequence.
@@ -31,6 +48,8 @@ impl TerminalHandler {
     ///
     /// - **DECRQSS** (`$ q <Pt> ST`): Request Selection or Setting.
     /// - **XTGETTCAP** (`+ q <hex> ST`): xterm termcap/terminfo query.
+    /// - **DECRSPS** (`Ps $ t <report> ST`): restore a presentation state.
+    /// - **DECDLD** (`Pn;… { Dscs <sixels> ST`): load a soft character set.
     /// - **tmux passthrough** (`tmux; <inner> ST`): un-doubles ESC bytes and
     ///   dispatches the inner escape sequence to the appropriate handler.
     ///
@@ -44,6 +63,12 @@ impl TerminalHandler {
             self.handle_decrqss(pt);
         } else if let Some(hex_payload) = inner.strip_prefix(b"+q") {
             self.handle_xtgettcap(hex_payload);
+        } else if let Some(report) = inner.strip_prefix(b"1$t") {
+            self.handle_restore_cursor_information(report);
+        } else if let Some(report) = inner.strip_prefix(b"2$t") {
+            self.handle_restore_tab_stops(report);
+        } else if Self::is_decdld_sequence(inner) {
+            self.handle_decdld(inner);
         } else if Self::is_sixel_sequence(inner) {
             self.handle_sixel(inner);
         } else if let Some(payload) = inner.strip_prefix(b"tmux;") {
@@ -56,6 +81,138 @@ impl TerminalHandler {
         }
     }
 
+    /// Handle DECRSPS 1 — restore the cursor position, rendition, DECSCA,
+    /// DECOM, pending wrap and G0 character set from a DECCIR report.
+    ///
+    /// A malformed report is ignored as a whole.
+    fn handle_restore_cursor_information(&mut self, report: &[u8]) {
+        let Some(info) = std::str::from_utf8(report)
+            .ok()
+            .and_then(CursorInformation::parse)
+        else {
+            tracing::warn!(
+                "DECRSPS: malformed cursor information report: {}",
+                String::from_utf8_lossy(report)
+            );
+            return;
+        };
+
+        // DECOM first: changing it homes the cursor.
+        self.buffer.set_decom(if info.origin_mode {
+            Decom::OriginMode
+        } else {
+            Decom::NormalCursor
+        });
+
+        let mut format = self.current_format.clone();
+        let mut off = RectAttributes::ALL;
+        off.remove(info.rendition);
+        off.apply_to(&mut format, false);
+        info.rendition.apply_to(&mut format, true);
+        format.set_protected(info.protected);
+        self.set_format(format);
+
+        for (set, (designator, is_96)) in GSet::ALL.into_iter().zip(&info.charsets) {
+            if let Some(charset) = Charset::from_designator(designator.as_bytes(), *is_96) {
+                self.charsets.designate(set, charset);
+            }
+        }
+        if let (Some(gl), Some(gr)) = (GSet::from_index(info.gl), GSet::from_index(info.gr)) {
+            self.charsets.invoke_gl(gl);
+            self.charsets.invoke_gr(gr);
+        }
+        self.charsets.set_single_shift(if info.single_shift_2 {
+            Some(GSet::G2)
+        } else if info.single_shift_3 {
+            Some(GSet::G3)
+        } else {
+            None
+        });
+
+        self.buffer.set_cursor_screen_pos(
+            info.col.saturating_sub(1),
+            info.row.saturating_sub(1),
+            info.wrap_pending,
+        );
+    }
+
+    /// Handle DECRSPS 2 — replace the tab stops with those of a DECTABSR
+    /// report.  A malformed report is ignored as a whole.
+    fn handle_restore_tab_stops(&mut self, report: &[u8]) {
+        let Some(columns) = std::str::from_utf8(report).ok().and_then(parse_tab_stops) else {
+            tracing::warn!(
+                "DECRSPS: malformed tab stop report: {}",
+                String::from_utf8_lossy(report)
+            );
+            return;
+        };
+        let columns: Vec<usize> = columns.into_iter().map(|col| col - 1).collect();
+        self.buffer.set_tab_stop_columns(&columns);
+    }
+
+    /// Whether a DCS payload is a DECDLD: numeric params, then `{`.
+    fn is_decdld_sequence(inner: &[u8]) -> bool {
+        inner.iter().position(|&b| b == b'{').is_some_and(|brace| {
+            inner[..brace]
+                .iter()
+                .all(|&b| b.is_ascii_digit() || b == b';')
+        })
+    }
+
+    /// Handle DECDLD — load glyphs into a soft character set.
+    ///
+    /// A malformed load is ignored as a whole.
+    fn handle_decdld(&mut self, inner: &[u8]) {
+        let Some(load) = SoftFontLoad::parse(inner) else {
+            tracing::warn!(
+                "DECDLD: malformed soft font: {}",
+                String::from_utf8_lossy(inner)
+            );
+            return;
+        };
+        Arc::make_mut(&mut self.soft_font).load(load);
+    }
+
+    /// The soft character sets loaded by DECDLD.
+    #[must_use]
+    pub fn soft_font(&self) -> Arc<SoftFont> {
+        Arc::clone(&self.soft_font)
+    }
+
+    /// Handle the tmux control-mode handshake (`DCS 1000 p`).
+    ///
+    /// Starts a [`TmuxControlSession`] on this pane's PTY write channel (which
+    /// queues the initial `list-windows`) and tells the GUI the pane is now a
+    /// gateway.  Without a write channel (e.g. playback) there is nobody to
+    /// answer tmux, so the stream is ignored.
+    pub(super) fn handle_tmux_control_start(&mut self) {
+        let Some(write_tx) = self.write_tx.clone() else {
+            tracing::warn!("tmux control mode started without a PTY write channel; ignoring");
+            return;
+        };
+        tracing::info!("tmux control mode started");
+        let session = TmuxControlSession::start(write_tx);
+        self.pending_tmux_events.push(TmuxEvent::Started {
+            commands: session.commands().clone(),
+        });
+        self.tmux_control = Some(session);
+    }
+
+    /// Handle one line of an active tmux control-mode stream.
+    pub(super) fn handle_tmux_control_line(&mut self, line: &[u8]) {
+        if let Some(session) = &mut self.tmux_control {
+            session.handle_line(line, &mut self.pending_tmux_events);
+        }
+    }
+
+    /// Handle the ST that ends a tmux control-mode stream.
+    pub(super) fn handle_tmux_control_end(&mut self) {
+        if self.tmux_control.take().is_some() {
+            tracing::info!("tmux control mode ended");
+            self.pending_tmux_events.push(TmuxEvent::Ended);
+        }
+    }
+
     /// Strip the DCS envelope: leading `P` byte and trailing `ESC \` (if present).
     pub(super) fn strip_dcs_envelope(dcs: &[u8]) -> &[u8] {
         let start = usize::from(dcs.first() == Some(&b'P'));
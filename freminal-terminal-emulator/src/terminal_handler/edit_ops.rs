@@ -5,8 +5,11 @@
 
 //! Edit and erase dispatch methods for [`TerminalHandler`].
 //!
-//! Covers erase-in-display, erase-in-line, insert/delete lines,
-//! erase/delete characters, insert spaces, and repeat-character.
+//! Covers erase-in-display, erase-in-line, their selective forms and
+//! DECSCA, insert/delete lines, erase/delete characters, insert spaces,
+//! repeat-character, and the VT420 rectangular area operations.
+
+use freminal_common::buffer_states::rectangle::{AttributeChange, RectAttributes, Rectangle};
 
 use super::TerminalHandler;
 use crate::ansi_components::csi_commands::ed::EraseDisplayMode;
@@ -32,6 +35,39 @@ impl TerminalHandler {
         }
     }
 
+    /// Handle DECSED — erase in display, sparing DECSCA-protected characters.
+    ///
+    /// There is no selective form of ED 3, so `AllWithScrollback` erases the
+    /// display only.
+    pub fn handle_selective_erase_in_display(&mut self, mode: EraseDisplayMode) {
+        match mode {
+            EraseDisplayMode::CursorToEnd => self.buffer.selective_erase_to_end_of_display(),
+            EraseDisplayMode::StartToCursor => {
+                self.buffer.selective_erase_to_beginning_of_display();
+            }
+            EraseDisplayMode::All | EraseDisplayMode::AllWithScrollback => {
+                self.buffer.selective_erase_display();
+            }
+        }
+    }
+
+    /// Handle DECSEL — erase in line, sparing DECSCA-protected characters.
+    pub fn handle_selective_erase_in_line(&mut self, mode: EraseLineMode) {
+        match mode {
+            EraseLineMode::CursorToEnd => self.buffer.selective_erase_line_to_end(),
+            EraseLineMode::StartToCursor => self.buffer.selective_erase_line_to_beginning(),
+            EraseLineMode::All => self.buffer.selective_erase_line(),
+        }
+    }
+
+    /// Handle DECSCA — mark the characters written from now on as protected
+    /// (or not) against the selective erases.
+    pub fn handle_set_character_protection(&mut self, protected: bool) {
+        let mut format = self.current_format.clone();
+        format.set_protected(protected);
+        self.set_format(format);
+    }
+
     /// Handle IL — insert `n` blank lines at the cursor row, pushing existing lines down (Insert Lines).
     pub fn handle_insert_lines(&mut self, n: usize) {
         self.buffer.insert_lines(n);
@@ -57,6 +93,16 @@ impl TerminalHandler {
         self.buffer.insert_spaces(n);
     }
 
+    /// Handle DECIC (Insert Column) — insert `n` blank columns at the cursor column, within the scrolling region and margins.
+    pub fn handle_insert_columns(&mut self, n: usize) {
+        self.buffer.insert_columns(n);
+    }
+
+    /// Handle DECDC (Delete Column) — delete `n` columns at the cursor column, within the scrolling region and margins.
+    pub fn handle_delete_columns(&mut self, n: usize) {
+        self.buffer.delete_columns(n);
+    }
+
     /// Handle REP (CSI Ps b) — repeat the last graphic character Ps times.
     pub(super) fn handle_repeat_character(&mut self, count: usize) {
         if let Some(ref ch) = self.last_graphic_char {
@@ -64,4 +110,36 @@ impl TerminalHandler {
             self.buffer.insert_text(&repeated);
         }
     }
+
+    /// Handle DECCRA — copy a rectangle to (`dest_top`, `dest_left`).
+    pub fn handle_copy_rectangle(&mut self, source: Rectangle, dest_top: usize, dest_left: usize) {
+        self.buffer.copy_rectangle(source, dest_top, dest_left);
+    }
+
+    /// Handle DECFRA — fill a rectangle with `ch` in the current attributes.
+    pub fn handle_fill_rectangle(&mut self, area: Rectangle, ch: char) {
+        self.buffer.fill_rectangle(area, ch);
+    }
+
+    /// Handle DECERA — erase a rectangle.
+    pub fn handle_erase_rectangle(&mut self, area: Rectangle) {
+        self.buffer.erase_rectangle(area);
+    }
+
+    /// Handle DECSERA — erase the characters of a rectangle, keeping attributes.
+    pub fn handle_selective_erase_rectangle(&mut self, area: Rectangle) {
+        self.buffer.selective_erase_rectangle(area);
+    }
+
+    /// Handle DECCARA — change attributes in the area selected by DECSACE.
+    pub fn handle_change_rectangle_attributes(&mut self, area: Rectangle, change: AttributeChange) {
+        self.buffer
+            .change_rectangle_attributes(area, change, self.attribute_change_extent);
+    }
+
+    /// Handle DECRARA — reverse attributes in the area selected by DECSACE.
+    pub fn handle_reverse_rectangle_attributes(&mut self, area: Rectangle, toggle: RectAttributes) {
+        self.buffer
+            .reverse_rectangle_attributes(area, toggle, self.attribute_change_extent);
+    }
 }
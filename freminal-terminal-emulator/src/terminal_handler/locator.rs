@@ -0,0 +1,60 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DEC locator reporting (DECELR / DECSLE / DECEFR / DECRQLP) for
+//! [`TerminalHandler`].
+//!
+//! The GUI sends pointer input through [`TerminalHandler::handle_locator_input`]
+//! while reporting is on; every DECLRP report goes back through the PTY.
+
+use freminal_common::buffer_states::locator::{
+    LocatorFilter, LocatorInput, LocatorReporting, LocatorUnits,
+};
+
+use super::TerminalHandler;
+
+impl TerminalHandler {
+    /// Handle DECELR — turn locator reporting on or off.
+    pub(super) const fn handle_enable_locator_reporting(
+        &mut self,
+        reporting: LocatorReporting,
+        units: LocatorUnits,
+    ) {
+        self.locator.enable(reporting, units);
+    }
+
+    /// Handle DECSLE — select the button transitions that are reported.
+    pub(super) fn handle_select_locator_events(&mut self, events: &[usize]) {
+        self.locator.select_events(events);
+    }
+
+    /// Handle DECEFR — arm a filter rectangle, reporting at once if the
+    /// pointer is already outside it.
+    pub(super) fn handle_set_locator_filter(&mut self, filter: LocatorFilter) {
+        if let Some(report) = self.locator.set_filter(filter) {
+            self.write_csi_response(&report);
+        }
+    }
+
+    /// Handle DECRQLP — report the locator position.
+    pub(super) fn handle_request_locator_position(&mut self) {
+        if let Some(report) = self.locator.request_position() {
+            self.write_csi_response(&report);
+        }
+    }
+
+    /// Record pointer input from the GUI and send any report it triggers.
+    pub fn handle_locator_input(&mut self, input: LocatorInput) {
+        if let Some(report) = self.locator.input(input) {
+            self.write_csi_response(&report);
+        }
+    }
+
+    /// Whether DECELR has locator reporting on.
+    #[must_use]
+    pub const fn locator_enabled(&self) -> bool {
+        self.locator.is_enabled()
+    }
+}
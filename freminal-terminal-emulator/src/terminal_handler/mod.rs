@@ -9,12 +9,14 @@ use conv2::ValueFrom;
 use crossbeam_channel::Sender;
 use freminal_common::{
     buffer_states::{
+        charset::{Charset, CharsetState, GSet},
         command_block::CommandBlock,
         cursor::CursorPos,
         format_tag::FormatTag,
         ftcs::FtcsState,
         kitty_graphics::KittyControlData,
         line_draw::DecSpecialGraphics,
+        locator::LocatorState,
         mode::{Mode, SetMode},
         modes::ReportMode,
         modes::allow_alt_screen::AllowAltScreen,
@@ -40,7 +42,12 @@ use freminal_common::{
         modes::xtcblink::XtCBlink,
         modes::xtextscrn::{AltScreen47, SaveCursor1048, XtExtscrn},
         osc::ITerm2InlineImageData,
+        osc_conemu::TaskProgress,
         pointer_shape::PointerShape,
+        rectangle::{AttributeChangeExtent, ChecksumExtension},
+        sgr_stack::SgrStackAttributes,
+        soft_font::SoftFont,
+        status_line::{StatusDisplay, StatusLineType},
         tchar::TChar,
         terminal_output::{TabClearMode, TerminalOutput},
         terminal_sections::TerminalSections,
@@ -50,18 +57,21 @@ use freminal_common::{
         },
         window_manipulation::WindowManipulation,
     },
-    colors::{ColorPalette, TerminalColor},
+    colors::{ColorPalette, SpecialColors, TerminalColor},
     cursor::CursorVisualStyle,
     pty_write::PtyWrite,
     themes::ThemePalette,
 };
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use freminal_buffer::buffer::Buffer;
 use freminal_buffer::image_store::{ImagePlacement, ImageProtocol};
 
+mod color_stack;
+use color_stack::ColorStack;
 mod cursor_ops;
 mod dcs;
 mod edit_ops;
@@ -69,6 +79,7 @@ mod graphics_iterm2;
 mod graphics_kitty;
 use graphics_kitty::signed_cell_offset;
 mod graphics_sixel;
+mod locator;
 mod notify_99;
 mod osc;
 mod osc_colors;
@@ -77,6 +88,7 @@ mod reports;
 mod scroll_ops;
 mod sgr;
 mod shell_integration;
+mod status_line;
 mod window_ops;
 
 /// In-progress state for an iTerm2 multipart file transfer.
@@ -184,14 +196,14 @@ pub struct TerminalHandler {
     show_cursor: Dectcem,
     /// The current cursor shape and blink state.
     cursor_visual_style: CursorVisualStyle,
-    /// Whether DEC Special Graphics character remapping is active.
-    character_replace: DecSpecialGraphics,
-    /// Saved `character_replace` state from the most recent DECSC.
-    ///
-    /// The VT100 spec requires DECSC to save the character set designators
-    /// (G0/G1) and GL invocation.  Freminal uses a simplified single-flag
-    /// model, so we save just `character_replace` here.
-    saved_character_replace: Option<DecSpecialGraphics>,
+    /// The G0–G3 designations, the sets invoked into GL and GR, and any
+    /// pending single shift.
+    charsets: CharsetState,
+    /// The charset state saved by the most recent DECSC.
+    saved_charsets: Option<CharsetState>,
+    /// The soft character sets loaded by DECDLD.  Shared with snapshots, so
+    /// a load replaces the `Arc` rather than mutating it.
+    soft_font: Arc<SoftFont>,
     /// Optional channel for writing responses back to the PTY.
     write_tx: Option<Sender<PtyWrite>>,
     /// Queued window-manipulation commands waiting to be consumed by the GUI.
@@ -204,6 +216,14 @@ pub struct TerminalHandler {
     /// the corresponding pane's `recent_commands` and to the notification
     /// system (Task 76).
     pending_command_events: Vec<CommandBlock>,
+    /// tmux control-mode state while this pane is a `tmux -CC` gateway.
+    ///
+    /// Set by the `DCS 1000 p` handshake and cleared by the closing ST; see
+    /// [`Self::handle_tmux_control_start`].
+    tmux_control: Option<crate::tmux_control::TmuxControlSession>,
+    /// tmux control-mode events queued for the GUI to drain via
+    /// [`Self::drain_tmux_events`].
+    pending_tmux_events: Vec<crate::tmux_control::TmuxEvent>,
     /// Last graphic character written (for REP — CSI b).
     last_graphic_char: Option<TChar>,
     /// Current working directory reported by the shell via OSC 7.
@@ -217,8 +237,23 @@ pub struct TerminalHandler {
     shell_histfile: Option<PathBuf>,
     /// Current FTCS (OSC 133) shell integration state.
     ftcs_state: FtcsState,
+    /// Pairing of shell-integration markers that carry no `fid` (plain
+    /// OSC 133 and OSC 633).
+    ftcs_compat: shell_integration::CompatPairing,
     /// Exit code from the most recent `OSC 133 ; D [; exitcode]` marker.
     last_exit_code: Option<i32>,
+    /// Task progress set by `ConEmu`'s `OSC 9 ; 4`.  Cleared by `9;4;0` or
+    /// when the command that set it finishes (`OSC 133 ; D`).
+    progress: Option<TaskProgress>,
+    /// User variables set by `iTerm2`'s `OSC 1337 ; SetUserVar=`.  Shared
+    /// with snapshots, so a change replaces the map rather than mutating it.
+    user_vars: Arc<BTreeMap<String, String>>,
+    /// The badge format set by `OSC 1337 ; SetBadgeFormat=`, unexpanded.
+    badge_format: Option<String>,
+    /// The badge format expanded against `user_vars`, refreshed whenever
+    /// either changes.  `None` when there is no badge or it expands to
+    /// nothing.
+    badge: Option<Arc<str>>,
     /// Mutable 256-color palette with optional per-index overrides.
     palette: ColorPalette,
     /// Whether DECCOLM (132-column mode switching) is allowed.
@@ -235,6 +270,27 @@ pub struct TerminalHandler {
     /// (80-column reset) restores the actual GUI window width rather than
     /// hardcoding 80.  `None` means DECCOLM has not changed the width.
     pre_deccolm_width: Option<usize>,
+    /// Which cells DECCARA / DECRARA change, selected by DECSACE.
+    attribute_change_extent: AttributeChangeExtent,
+    /// How DECRQCRA computes its checksum, selected by XTCHECKSUM.
+    checksum_extension: ChecksumExtension,
+    /// Whether DECRQCRA is answered.  Off by default because the checksum
+    /// lets an application read back what is on the screen; set from
+    /// `[security] allow_checksum_reports`.
+    allow_checksum_reports: bool,
+    /// Renditions saved by XTPUSHSGR, each with the attributes XTPOPSGR
+    /// restores from it.
+    sgr_stack: Vec<(SgrStackAttributes, FormatTag)>,
+    /// Palettes and dynamic colors saved by XTPUSHCOLORS.
+    color_stack: ColorStack,
+    /// The status line selected by DECSSDT.
+    status_line_type: StatusLineType,
+    /// Where DECSASD sends output.
+    status_display: StatusDisplay,
+    /// The one-row host-writable status line, drawn below the main display.
+    status_buffer: Buffer,
+    /// DEC locator settings and the last pointer state the GUI sent.
+    locator: LocatorState,
     /// Active color theme for default palette lookups.
     theme: &'static ThemePalette,
     /// Dynamic foreground color override (set via OSC 10; reset via OSC 110).
@@ -252,6 +308,27 @@ pub struct TerminalHandler {
     /// When `Some`, the cursor is rendered in this color instead of the
     /// theme's `cursor` field.
     cursor_color_override: Option<(u8, u8, u8)>,
+    /// Dynamic mouse pointer foreground color (set via OSC 13; reset via
+    /// OSC 113).  Only reported: the OS draws the pointer.
+    mouse_fg_override: Option<(u8, u8, u8)>,
+    /// Dynamic mouse pointer background color (set via OSC 14; reset via
+    /// OSC 114).  Only reported: the OS draws the pointer.
+    mouse_bg_override: Option<(u8, u8, u8)>,
+    /// Dynamic highlight background color (set via OSC 17; reset via
+    /// OSC 117).
+    ///
+    /// When `Some`, the selection is drawn in this color instead of the
+    /// theme's `selection_bg` field.
+    highlight_bg_override: Option<(u8, u8, u8)>,
+    /// Dynamic highlight foreground color (set via OSC 19; reset via
+    /// OSC 119).
+    ///
+    /// When `Some`, selected text is drawn in this color instead of the
+    /// theme's `selection_fg` field.
+    highlight_fg_override: Option<(u8, u8, u8)>,
+    /// Special colors for bold, underlined, blinking and reverse text (set
+    /// via OSC 5; reset via OSC 105).
+    special_colors: SpecialColors,
     /// Pointer (mouse cursor) shape requested via OSC 22.
     ///
     /// Defaults to `PointerShape::Default` (OS default arrow).
@@ -341,8 +418,9 @@ pub struct TerminalHandler {
     /// palette (`?1070 l`).
     private_color_registers: PrivateColorRegisters,
     /// Whether DECNRCM (National Replacement Character Set Mode, `?42`) is
-    /// active. When `NrcEnabled`, character set designations map specific ASCII
-    /// positions to national characters. Default is `NrcDisabled`.
+    /// active. Only when `NrcEnabled` do national replacement sets designated
+    /// into G0–G3 map ASCII positions to national characters. Default is
+    /// `NrcDisabled`.
     nrc_mode: Decnrcm,
     /// Whether reverse-wraparound (`?45`) is active.
     ///
@@ -410,24 +488,46 @@ impl TerminalHandler {
             current_format: FormatTag::default(),
             show_cursor: Dectcem::default(),
             cursor_visual_style: CursorVisualStyle::default(),
-            character_replace: DecSpecialGraphics::default(),
-            saved_character_replace: None,
+            charsets: CharsetState::default(),
+            saved_charsets: None,
+            soft_font: Arc::default(),
             write_tx: None,
             window_commands: Vec::new(),
             pending_command_events: Vec::new(),
+            tmux_control: None,
+            pending_tmux_events: Vec::new(),
             last_graphic_char: None,
             current_working_directory: None,
             shell_histfile: None,
             ftcs_state: FtcsState::default(),
+            ftcs_compat: shell_integration::CompatPairing::default(),
             last_exit_code: None,
+            progress: None,
+            user_vars: Arc::default(),
+            badge_format: None,
+            badge: None,
             palette: ColorPalette::default(),
             allow_column_mode_switch: AllowColumnModeSwitch::AllowColumnModeSwitch,
             allow_alt_screen: AllowAltScreen::Allow,
             pre_deccolm_width: None,
+            attribute_change_extent: AttributeChangeExtent::default(),
+            checksum_extension: ChecksumExtension::default(),
+            allow_checksum_reports: false,
+            sgr_stack: Vec::new(),
+            color_stack: ColorStack::default(),
+            status_line_type: StatusLineType::default(),
+            status_display: StatusDisplay::default(),
+            status_buffer: Self::new_status_buffer(width),
+            locator: LocatorState::default(),
             theme: &freminal_common::themes::CATPPUCCIN_MOCHA,
             fg_color_override: None,
             bg_color_override: None,
             cursor_color_override: None,
+            mouse_fg_override: None,
+            mouse_bg_override: None,
+            highlight_bg_override: None,
+            highlight_fg_override: None,
+            special_colors: SpecialColors::default(),
             pointer_shape: PointerShape::Default,
             multipart_state: None,
             kitty_state: None,
@@ -475,6 +575,17 @@ impl TerminalHandler {
         self.theme = theme;
     }
 
+    /// Whether DECRQCRA checksum reports are answered.
+    #[must_use]
+    pub const fn allow_checksum_reports(&self) -> bool {
+        self.allow_checksum_reports
+    }
+
+    /// Allow or refuse DECRQCRA checksum reports.
+    pub const fn set_allow_checksum_reports(&mut self, allow: bool) {
+        self.allow_checksum_reports = allow;
+    }
+
     /// Get the current S8C1T mode.
     #[must_use]
     pub const fn s8c1t_mode(&self) -> &S8c1t {
@@ -494,6 +605,30 @@ impl TerminalHandler {
         self.cursor_color_override
     }
 
+    /// Get the dynamic highlight (selection) background color override (set
+    /// via OSC 17).
+    ///
+    /// Returns `None` when the theme default should be used.
+    #[must_use]
+    pub const fn highlight_bg_override(&self) -> Option<(u8, u8, u8)> {
+        self.highlight_bg_override
+    }
+
+    /// Get the dynamic highlight (selection) foreground color override (set
+    /// via OSC 19).
+    ///
+    /// Returns `None` when the theme default should be used.
+    #[must_use]
+    pub const fn highlight_fg_override(&self) -> Option<(u8, u8, u8)> {
+        self.highlight_fg_override
+    }
+
+    /// Get the special colors set via OSC 5.
+    #[must_use]
+    pub const fn special_colors(&self) -> SpecialColors {
+        self.special_colors
+    }
+
     /// Get the current pointer (mouse cursor) shape requested via OSC 22.
     ///
     /// Returns `PointerShape::Default` when no override is active.
@@ -522,21 +657,40 @@ impl TerminalHandler {
         self.current_format = FormatTag::default();
         self.show_cursor = Dectcem::default();
         self.cursor_visual_style = CursorVisualStyle::default();
-        self.character_replace = DecSpecialGraphics::default();
-        self.saved_character_replace = None;
+        self.charsets = CharsetState::default();
+        self.saved_charsets = None;
+        self.soft_font = Arc::default();
         self.window_commands.clear();
         self.pending_command_events.clear();
         self.last_graphic_char = None;
         self.current_working_directory = None;
         self.shell_histfile = None;
         self.ftcs_state = FtcsState::default();
+        self.ftcs_compat = shell_integration::CompatPairing::default();
         self.last_exit_code = None;
+        self.progress = None;
+        self.user_vars = Arc::default();
+        self.badge_format = None;
+        self.badge = None;
         self.palette.reset_all();
         self.fg_color_override = None;
         self.bg_color_override = None;
         self.cursor_color_override = None;
+        self.mouse_fg_override = None;
+        self.mouse_bg_override = None;
+        self.highlight_bg_override = None;
+        self.highlight_fg_override = None;
+        self.special_colors.reset_all();
         self.pointer_shape = PointerShape::Default;
         self.allow_column_mode_switch = AllowColumnModeSwitch::AllowColumnModeSwitch;
+        self.attribute_change_extent = AttributeChangeExtent::default();
+        self.checksum_extension = ChecksumExtension::default();
+        self.sgr_stack.clear();
+        self.color_stack = ColorStack::default();
+        self.status_line_type = StatusLineType::default();
+        self.status_display = StatusDisplay::default();
+        self.status_buffer = Self::new_status_buffer(self.buffer.terminal_width());
+        self.locator = LocatorState::default();
         self.virtual_placements.clear();
         self.real_placements.clear();
         self.prev_placeholder = None;
@@ -571,8 +725,8 @@ impl TerminalHandler {
     }
 
     /// Handle raw data bytes - convert to `TChar` and insert.
-    /// When DEC Special Graphics mode is active, bytes 0x5F–0x7E are remapped
-    /// to their Unicode box-drawing equivalents before conversion.
+    /// Bytes 0x20–0x7F are first mapped through the character set invoked
+    /// into GL (or the one a pending SS2/SS3 selected).
     ///
     /// If any grapheme cluster begins with U+10EEEE (the Kitty Unicode
     /// placeholder character), it is intercepted and converted into an image
@@ -582,7 +736,7 @@ impl TerminalHandler {
             return;
         }
 
-        let remapped: Cow<[u8]> = apply_dec_special(data, &self.character_replace);
+        let remapped: Cow<[u8]> = self.charsets.translate(data, self.nrc_mode);
         let Ok(text) = TChar::from_vec(&remapped) else {
             return;
         };
@@ -865,6 +1019,25 @@ impl TerminalHandler {
         self.last_exit_code
     }
 
+    /// Return the task progress set by `OSC 9 ; 4`, if any.
+    #[must_use]
+    pub const fn progress(&self) -> Option<TaskProgress> {
+        self.progress
+    }
+
+    /// Return the user variables set by `OSC 1337 ; SetUserVar=`.
+    #[must_use]
+    pub fn user_vars(&self) -> Arc<BTreeMap<String, String>> {
+        Arc::clone(&self.user_vars)
+    }
+
+    /// Return the badge set by `OSC 1337 ; SetBadgeFormat=`, with its user
+    /// variables expanded, if any.
+    #[must_use]
+    pub fn badge(&self) -> Option<Arc<str>> {
+        self.badge.clone()
+    }
+
     /// Drain and return all pending command-finished events.
     ///
     /// Called by the PTY loop after each batch of incoming data is processed.
@@ -874,6 +1047,21 @@ impl TerminalHandler {
         std::mem::take(&mut self.pending_command_events)
     }
 
+    /// Drain and return all pending tmux control-mode events.
+    ///
+    /// Called by the PTY loop after each batch of incoming data is processed,
+    /// alongside [`Self::drain_command_events`].  Returns events oldest-first.
+    #[must_use]
+    pub fn drain_tmux_events(&mut self) -> Vec<crate::tmux_control::TmuxEvent> {
+        std::mem::take(&mut self.pending_tmux_events)
+    }
+
+    /// Whether this pane is currently a tmux control-mode gateway.
+    #[must_use]
+    pub const fn in_tmux_control_mode(&self) -> bool {
+        self.tmux_control.is_some()
+    }
+
     /// Return the current xterm `modifyOtherKeys` level (0, 1, or 2).
     #[must_use]
     pub const fn modify_other_keys_level(&self) -> u8 {
@@ -1171,7 +1359,11 @@ impl TerminalHandler {
     /// It dispatches each `TerminalOutput` variant to the appropriate handler method.
     pub fn process_outputs(&mut self, outputs: &[TerminalOutput]) {
         for output in outputs {
-            self.process_output(output);
+            if self.targets_status_line(output) {
+                self.process_output_on_status_line(output);
+            } else {
+                self.process_output(output);
+            }
         }
     }
 
@@ -1242,6 +1434,12 @@ impl TerminalHandler {
             TerminalOutput::InsertSpaces(n) => {
                 self.handle_insert_spaces(*n);
             }
+            TerminalOutput::InsertColumns(n) => {
+                self.handle_insert_columns(*n);
+            }
+            TerminalOutput::DeleteColumns(n) => {
+                self.handle_delete_columns(*n);
+            }
             TerminalOutput::Index => {
                 self.handle_index();
             }
@@ -1303,9 +1501,100 @@ impl TerminalHandler {
             TerminalOutput::Erase(n) => {
                 self.handle_erase_chars(*n);
             }
+            TerminalOutput::CopyRectangle {
+                source,
+                dest_top,
+                dest_left,
+            } => {
+                self.handle_copy_rectangle(*source, *dest_top, *dest_left);
+            }
+            TerminalOutput::FillRectangle { ch, area } => {
+                self.handle_fill_rectangle(*area, *ch);
+            }
+            TerminalOutput::EraseRectangle(area) => {
+                self.handle_erase_rectangle(*area);
+            }
+            TerminalOutput::SelectiveEraseRectangle(area) => {
+                self.handle_selective_erase_rectangle(*area);
+            }
+            TerminalOutput::ChangeRectangleAttributes { area, change } => {
+                self.handle_change_rectangle_attributes(*area, *change);
+            }
+            TerminalOutput::ReverseRectangleAttributes { area, toggle } => {
+                self.handle_reverse_rectangle_attributes(*area, *toggle);
+            }
+            TerminalOutput::SelectAttributeChangeExtent(extent) => {
+                self.attribute_change_extent = *extent;
+            }
+            TerminalOutput::RequestRectangleChecksum { id, area } => {
+                self.handle_request_rectangle_checksum(*id, *area);
+            }
+            TerminalOutput::SelectChecksumExtension(ext) => {
+                self.checksum_extension = *ext;
+            }
+            TerminalOutput::RequestPresentationState(report) => {
+                self.handle_request_presentation_state(*report);
+            }
+            TerminalOutput::SelectStatusLineType(kind) => {
+                self.handle_select_status_line_type(*kind);
+            }
+            TerminalOutput::SelectActiveStatusDisplay(display) => {
+                self.handle_select_active_status_display(*display);
+            }
+            TerminalOutput::EnableLocatorReporting { reporting, units } => {
+                self.handle_enable_locator_reporting(*reporting, *units);
+            }
+            TerminalOutput::SelectLocatorEvents(events) => {
+                self.handle_select_locator_events(events);
+            }
+            TerminalOutput::SetLocatorFilter(filter) => {
+                self.handle_set_locator_filter(*filter);
+            }
+            TerminalOutput::RequestLocatorPosition => {
+                self.handle_request_locator_position();
+            }
+            TerminalOutput::SetCharacterProtection(protected) => {
+                self.handle_set_character_protection(*protected);
+            }
+            TerminalOutput::SelectiveClearDisplayForwards => {
+                self.handle_selective_erase_in_display(EraseDisplayMode::CursorToEnd);
+            }
+            TerminalOutput::SelectiveClearDisplayBackwards => {
+                self.handle_selective_erase_in_display(EraseDisplayMode::StartToCursor);
+            }
+            TerminalOutput::SelectiveClearDisplay => {
+                self.handle_selective_erase_in_display(EraseDisplayMode::All);
+            }
+            TerminalOutput::SelectiveClearLineForwards => {
+                self.handle_selective_erase_in_line(EraseLineMode::CursorToEnd);
+            }
+            TerminalOutput::SelectiveClearLineBackwards => {
+                self.handle_selective_erase_in_line(EraseLineMode::StartToCursor);
+            }
+            TerminalOutput::SelectiveClearLine => {
+                self.handle_selective_erase_in_line(EraseLineMode::All);
+            }
             TerminalOutput::Sgr(sgr) => {
                 self.handle_sgr(sgr);
             }
+            TerminalOutput::PushSgr(attrs) => {
+                self.handle_push_sgr(*attrs);
+            }
+            TerminalOutput::PopSgr => {
+                self.handle_pop_sgr();
+            }
+            TerminalOutput::ReportSgr(area) => {
+                self.handle_report_sgr(*area);
+            }
+            TerminalOutput::PushColors(slot) => {
+                self.handle_push_colors(*slot);
+            }
+            TerminalOutput::PopColors(slot) => {
+                self.handle_pop_colors(*slot);
+            }
+            TerminalOutput::ReportColors => {
+                self.handle_report_colors();
+            }
             TerminalOutput::Mode(mode) => match mode {
                 Mode::XtExtscrn(XtExtscrn::Alternate)
                 | Mode::AltScreen47(AltScreen47::Alternate)
@@ -1671,7 +1960,11 @@ impl TerminalHandler {
                 self.handle_device_status_report();
             }
             TerminalOutput::DecSpecialGraphics(dsg) => {
-                self.character_replace = dsg.clone();
+                let charset = match dsg {
+                    DecSpecialGraphics::Replace => Charset::DecSpecialGraphics,
+                    DecSpecialGraphics::DontReplace => Charset::Ascii,
+                };
+                self.charsets.designate(GSet::G0, charset);
             }
             TerminalOutput::CursorVisualStyle(style) => {
                 self.cursor_visual_style = style.clone();
@@ -1716,34 +2009,22 @@ impl TerminalHandler {
             TerminalOutput::ScreenAlignmentTest => {
                 self.buffer.screen_alignment_test();
             }
+            TerminalOutput::DesignateCharset { set, charset } => {
+                self.charsets.designate(*set, *charset);
+            }
+            TerminalOutput::CharsetG0AsGL => self.charsets.invoke_gl(GSet::G0),
+            TerminalOutput::CharsetG1AsGL => self.charsets.invoke_gl(GSet::G1),
+            TerminalOutput::CharsetG2AsGL => self.charsets.invoke_gl(GSet::G2),
+            TerminalOutput::CharsetG3AsGL => self.charsets.invoke_gl(GSet::G3),
+            TerminalOutput::CharsetG1AsGR => self.charsets.invoke_gr(GSet::G1),
+            TerminalOutput::CharsetG2AsGR => self.charsets.invoke_gr(GSet::G2),
+            TerminalOutput::CharsetG3AsGR => self.charsets.invoke_gr(GSet::G3),
+            TerminalOutput::SingleShiftG2 => self.charsets.set_single_shift(Some(GSet::G2)),
+            TerminalOutput::SingleShiftG3 => self.charsets.set_single_shift(Some(GSet::G3)),
             TerminalOutput::CharsetDefault
             | TerminalOutput::CharsetUTF8
-            | TerminalOutput::CharsetG0
-            | TerminalOutput::CharsetG1
-            | TerminalOutput::CharsetG1AsGR
-            | TerminalOutput::CharsetG2
-            | TerminalOutput::CharsetG2AsGR
-            | TerminalOutput::CharsetG2AsGL
-            | TerminalOutput::CharsetG3
-            | TerminalOutput::CharsetG3AsGR
-            | TerminalOutput::CharsetG3AsGL
-            | TerminalOutput::DecSpecial
-            | TerminalOutput::CharsetUK
-            | TerminalOutput::CharsetUS
-            | TerminalOutput::CharsetUSASCII
-            | TerminalOutput::CharsetDutch
-            | TerminalOutput::CharsetFinnish
-            | TerminalOutput::CharsetFrench
-            | TerminalOutput::CharsetFrenchCanadian
-            | TerminalOutput::CharsetGerman
-            | TerminalOutput::CharsetItalian
-            | TerminalOutput::CharsetNorwegianDanish
-            | TerminalOutput::CharsetSpanish
-            | TerminalOutput::CharsetSwedish
-            | TerminalOutput::CharsetSwiss => {
-                tracing::warn!(
-                    "Charset/line-drawing designation not yet implemented (ignored): {output}"
-                );
+            | TerminalOutput::DecSpecial => {
+                tracing::warn!("Charset selection not yet implemented (ignored): {output}");
             }
             TerminalOutput::SaveCursor => {
                 self.handle_save_cursor();
@@ -1766,6 +2047,9 @@ impl TerminalHandler {
             TerminalOutput::DeviceControlString(dcs) => {
                 self.handle_device_control_string(dcs);
             }
+            TerminalOutput::TmuxControlModeStart => self.handle_tmux_control_start(),
+            TerminalOutput::TmuxControlLine(line) => self.handle_tmux_control_line(line),
+            TerminalOutput::TmuxControlModeEnd => self.handle_tmux_control_end(),
             TerminalOutput::ApplicationProgramCommand(apc) => {
                 self.handle_application_program_command(apc);
             }
@@ -1843,64 +2127,6 @@ impl TerminalHandler {
     }
 }
 
-/// Remap bytes in `data` according to the DEC Special Graphics character set.
-///
-/// When `mode` is `DecSpecialGraphics::Replace`, bytes in the range `0x5F–0x7E`
-/// are expanded to their Unicode UTF-8 equivalents (box-drawing, Greek, etc.).
-/// All other bytes are passed through unchanged. When `mode` is `DontReplace`
-/// the slice is copied verbatim.
-///
-/// Reference: <https://en.wikipedia.org/wiki/DEC_Special_Graphics>
-///
-/// Returns `Cow::Borrowed(data)` when no remapping is needed (`DontReplace` mode),
-/// avoiding any heap allocation in the overwhelmingly common case.
-fn apply_dec_special<'a>(data: &'a [u8], mode: &DecSpecialGraphics) -> Cow<'a, [u8]> {
-    match mode {
-        DecSpecialGraphics::DontReplace => Cow::Borrowed(data),
-        DecSpecialGraphics::Replace => {
-            let mut out = Vec::with_capacity(data.len() * 3);
-            for &b in data {
-                match b {
-                    0x5f => out.extend_from_slice("\u{00A0}".as_bytes()), // NO-BREAK SPACE
-                    0x60 => out.extend_from_slice("\u{25C6}".as_bytes()), // BLACK DIAMOND
-                    0x61 => out.extend_from_slice("\u{2592}".as_bytes()), // MEDIUM SHADE
-                    0x62 => out.extend_from_slice("\u{2409}".as_bytes()), // SYMBOL FOR HT
-                    0x63 => out.extend_from_slice("\u{240C}".as_bytes()), // SYMBOL FOR FF
-                    0x64 => out.extend_from_slice("\u{240D}".as_bytes()), // SYMBOL FOR CR
-                    0x65 => out.extend_from_slice("\u{240A}".as_bytes()), // SYMBOL FOR LF
-                    0x66 => out.extend_from_slice("\u{00B0}".as_bytes()), // DEGREE SIGN
-                    0x67 => out.extend_from_slice("\u{00B1}".as_bytes()), // PLUS-MINUS SIGN
-                    0x68 => out.extend_from_slice("\u{2424}".as_bytes()), // SYMBOL FOR NEWLINE
-                    0x69 => out.extend_from_slice("\u{240B}".as_bytes()), // SYMBOL FOR VT
-                    0x6a => out.extend_from_slice("\u{2518}".as_bytes()), // BOX LIGHT UP AND LEFT
-                    0x6b => out.extend_from_slice("\u{2510}".as_bytes()), // BOX LIGHT DOWN AND LEFT
-                    0x6c => out.extend_from_slice("\u{250C}".as_bytes()), // BOX LIGHT DOWN AND RIGHT
-                    0x6d => out.extend_from_slice("\u{2514}".as_bytes()), // BOX LIGHT UP AND RIGHT
-                    0x6e => out.extend_from_slice("\u{253C}".as_bytes()), // BOX LIGHT VERTICAL AND HORIZONTAL
-                    0x6f => out.extend_from_slice("\u{23BA}".as_bytes()), // HORIZONTAL SCAN LINE-1
-                    0x70 => out.extend_from_slice("\u{23BB}".as_bytes()), // HORIZONTAL SCAN LINE-3
-                    0x71 => out.extend_from_slice("\u{2500}".as_bytes()), // BOX LIGHT HORIZONTAL
-                    0x72 => out.extend_from_slice("\u{23BC}".as_bytes()), // HORIZONTAL SCAN LINE-7
-                    0x73 => out.extend_from_slice("\u{23BD}".as_bytes()), // HORIZONTAL SCAN LINE-9
-                    0x74 => out.extend_from_slice("\u{251C}".as_bytes()), // BOX LIGHT VERTICAL AND RIGHT
-                    0x75 => out.extend_from_slice("\u{2524}".as_bytes()), // BOX LIGHT VERTICAL AND LEFT
-                    0x76 => out.extend_from_slice("\u{2534}".as_bytes()), // BOX LIGHT UP AND HORIZONTAL
-                    0x77 => out.extend_from_slice("\u{252C}".as_bytes()), // BOX LIGHT DOWN AND HORIZONTAL
-                    0x78 => out.extend_from_slice("\u{2502}".as_bytes()), // BOX LIGHT VERTICAL
-                    0x79 => out.extend_from_slice("\u{2264}".as_bytes()), // LESS-THAN OR EQUAL TO
-                    0x7a => out.extend_from_slice("\u{2265}".as_bytes()), // GREATER-THAN OR EQUAL TO
-                    0x7b => out.extend_from_slice("\u{03C0}".as_bytes()), // GREEK SMALL LETTER PI
-                    0x7c => out.extend_from_slice("\u{2260}".as_bytes()), // NOT EQUAL TO
-                    0x7d => out.extend_from_slice("\u{00A3}".as_bytes()), // POUND SIGN
-                    0x7e => out.extend_from_slice("\u{00B7}".as_bytes()), // MIDDLE DOT
-                    _ => out.push(b),
-                }
-            }
-            Cow::Owned(out)
-        }
-    }
-}
-
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -3832,38 +4058,42 @@ mod tests {
     }
 
     #[test]
-    fn process_charset_variants_are_noops() {
+    fn process_charset_selections_are_noops() {
         let mut handler = TerminalHandler::new(80, 24);
-        let charsets = [
+        handler.process_outputs(&[
             TerminalOutput::CharsetDefault,
             TerminalOutput::CharsetUTF8,
-            TerminalOutput::CharsetG0,
-            TerminalOutput::CharsetG1,
-            TerminalOutput::CharsetG1AsGR,
-            TerminalOutput::CharsetG2,
-            TerminalOutput::CharsetG2AsGR,
-            TerminalOutput::CharsetG2AsGL,
-            TerminalOutput::CharsetG3,
-            TerminalOutput::CharsetG3AsGR,
-            TerminalOutput::CharsetG3AsGL,
             TerminalOutput::DecSpecial,
-            TerminalOutput::CharsetUK,
-            TerminalOutput::CharsetUS,
-            TerminalOutput::CharsetUSASCII,
-            TerminalOutput::CharsetDutch,
-            TerminalOutput::CharsetFinnish,
-            TerminalOutput::CharsetFrench,
-            TerminalOutput::CharsetFrenchCanadian,
-            TerminalOutput::CharsetGerman,
-            TerminalOutput::CharsetItalian,
-            TerminalOutput::CharsetNorwegianDanish,
-            TerminalOutput::CharsetSpanish,
-            TerminalOutput::CharsetSwedish,
-            TerminalOutput::CharsetSwiss,
-        ];
-        for cs in &charsets {
-            handler.process_outputs(std::slice::from_ref(cs));
-        }
+        ]);
+        handler.handle_data(b"q");
+        assert_eq!(handler.buffer().cursor().pos.x, 1);
+    }
+
+    #[test]
+    fn process_charset_designation_and_invocation() {
+        let mut handler = TerminalHandler::new(80, 24);
+        handler.process_outputs(&[
+            TerminalOutput::DesignateCharset {
+                set: GSet::G1,
+                charset: Charset::DecSpecialGraphics,
+            },
+            TerminalOutput::DesignateCharset {
+                set: GSet::G2,
+                charset: Charset::German,
+            },
+            TerminalOutput::CharsetG1AsGL,
+            TerminalOutput::CharsetG2AsGR,
+            TerminalOutput::SingleShiftG2,
+        ]);
+        assert_eq!(handler.charsets.gl(), GSet::G1);
+        assert_eq!(handler.charsets.gr(), GSet::G2);
+        assert_eq!(handler.charsets.single_shift(), Some(GSet::G2));
+
+        handler.handle_data(b"{q");
+        assert_eq!(handler.charsets.single_shift(), None);
+
+        handler.process_outputs(&[TerminalOutput::CharsetG0AsGL]);
+        assert_eq!(handler.charsets.gl(), GSet::G0);
     }
 
     #[test]
@@ -4282,35 +4512,6 @@ mod tests {
         assert_eq!(*handler.current_format(), FormatTag::default());
     }
 
-    // ------------------------------------------------------------------
-    // `apply_dec_special` standalone function
-    // ------------------------------------------------------------------
-
-    #[test]
-    fn apply_dec_special_dont_replace() {
-        let data = b"hello";
-        let result = apply_dec_special(data, &DecSpecialGraphics::DontReplace);
-        assert!(matches!(result, Cow::Borrowed(_)));
-        assert_eq!(&*result, data);
-    }
-
-    #[test]
-    fn apply_dec_special_replace_box_drawing() {
-        // 'q' (0x71) maps to U+2500 HORIZONTAL LINE (─)
-        let data = b"q";
-        let result = apply_dec_special(data, &DecSpecialGraphics::Replace);
-        // The result should be UTF-8 bytes for ─ (U+2500 = 0xE2 0x94 0x80)
-        assert_ne!(&*result, data, "Should have been remapped");
-    }
-
-    #[test]
-    fn apply_dec_special_replace_non_mappable_byte() {
-        // Bytes outside 0x5F-0x7E are passed through
-        let data = b"ABC";
-        let result = apply_dec_special(data, &DecSpecialGraphics::Replace);
-        assert_eq!(&*result, data, "Non-mappable bytes should pass through");
-    }
-
     // ------------------------------------------------------------------
     // Coverage gap tests: terminal_handler/mod.rs
     // ------------------------------------------------------------------
@@ -15,6 +15,8 @@ use freminal_common::buffer_states::{
     ftcs::{FtcsMarker, FtcsState},
     kitty_graphics::{KittyParseError, parse_kitty_graphics},
     osc::{AnsiOscType, UrlResponse},
+    osc_conemu::{ConEmuCommand, TaskProgress},
+    osc_iterm2::{ITerm2Command, expand_badge},
     url::Url,
     window_manipulation::{NotificationKind, WindowManipulation},
 };
@@ -23,6 +25,13 @@ use crate::ansi_components::tracer::escape_sequence_for_log;
 
 use super::{TerminalHandler, notify_99, shell_integration};
 
+/// How many `iTerm2` user variables a pane keeps; setting a new one past
+/// this is ignored.
+const USER_VAR_LIMIT: usize = 256;
+
+/// Longest `iTerm2` user variable value accepted, in bytes.
+const USER_VAR_VALUE_LIMIT: usize = 4096;
+
 impl TerminalHandler {
     /// Handle an APC (Application Program Command) sequence.
     ///
@@ -81,6 +90,12 @@ impl TerminalHandler {
                 self.handle_osc_fg_bg_color(osc);
             }
 
+            // OSC 13/14/17/19 mouse pointer and highlight colors, and their
+            // OSC 113/114/117/119 resets.
+            AnsiOscType::RequestColorQueryDynamic(..) | AnsiOscType::ResetDynamicColor(_) => {
+                self.handle_osc_dynamic_color(osc);
+            }
+
             // Remote host / CWD: OSC 7 ; file://hostname/path ST
             AnsiOscType::RemoteHost(value) => {
                 self.current_working_directory = shell_integration::parse_osc7_uri(value);
@@ -97,6 +112,9 @@ impl TerminalHandler {
             AnsiOscType::Ftcs(marker) => {
                 self.handle_osc_ftcs(marker);
             }
+            AnsiOscType::FtcsCompat(marker) => {
+                self.handle_ftcs_compat(marker);
+            }
             AnsiOscType::ITerm2FileInline(data) => {
                 self.handle_iterm2_inline_image(data);
             }
@@ -109,6 +127,9 @@ impl TerminalHandler {
             AnsiOscType::ITerm2FileEnd => {
                 self.handle_iterm2_file_end();
             }
+            AnsiOscType::ITerm2(cmd) => {
+                self.handle_iterm2(cmd);
+            }
             AnsiOscType::ITerm2Unknown => {
                 tracing::warn!("OSC 1337: unrecognised sub-command (ignored)");
             }
@@ -146,6 +167,32 @@ impl TerminalHandler {
                 self.palette.reset_all();
             }
 
+            // Special colors: OSC 5 (set/query) and OSC 105 (reset).  An
+            // unset special color is the text foreground.
+            AnsiOscType::SetSpecialColor(color, r, g, b) => {
+                self.special_colors.set(*color, (*r, *g, *b));
+            }
+            AnsiOscType::QuerySpecialColor(color) => {
+                let (r, g, b) = self
+                    .special_colors
+                    .get(*color)
+                    .unwrap_or_else(|| self.fg_color_override.unwrap_or(self.theme.foreground));
+                let body = format!(
+                    "5;{};rgb:{:04x}/{:04x}/{:04x}",
+                    color.index(),
+                    u16::from(r) * 257,
+                    u16::from(g) * 257,
+                    u16::from(b) * 257,
+                );
+                self.write_osc_response(&body);
+            }
+            AnsiOscType::ResetSpecialColor(Some(color)) => {
+                self.special_colors.reset(*color);
+            }
+            AnsiOscType::ResetSpecialColor(None) => {
+                self.special_colors.reset_all();
+            }
+
             // OSC 22 — set pointer (mouse cursor) shape.
             AnsiOscType::SetPointerShape(shape) => {
                 self.pointer_shape = *shape;
@@ -187,10 +234,113 @@ impl TerminalHandler {
                 }
             }
 
+            AnsiOscType::ConEmu(cmd) => {
+                self.handle_conemu(cmd);
+            }
+
             AnsiOscType::NoOp => {}
         }
     }
 
+    /// Handle a `ConEmu` OSC 9 sub-command.
+    ///
+    /// Progress (`9;4`) and the working directory (`9;9`) are tracked; the
+    /// sub-commands that drive the `ConEmu` GUI itself are ignored.
+    fn handle_conemu(&mut self, cmd: &ConEmuCommand) {
+        match cmd {
+            ConEmuCommand::SetProgress { state, percent } => {
+                let previous = self.progress.map_or(0, |p| p.percent);
+                self.progress = Some(TaskProgress {
+                    state: *state,
+                    percent: percent.unwrap_or(previous),
+                });
+            }
+            ConEmuCommand::ClearProgress => {
+                self.progress = None;
+            }
+            ConEmuCommand::WorkingDirectory(cwd) if !cwd.is_empty() => {
+                tracing::debug!("OSC 9;9: CWD set to {cwd:?}");
+                self.current_working_directory = Some(cwd.clone());
+            }
+            _ => {
+                tracing::debug!("OSC 9: ConEmu sub-command ignored: {cmd}");
+            }
+        }
+    }
+
+    /// Handle an `iTerm2` `OSC 1337` text command.
+    ///
+    /// A mark is recorded with the prompt rows, so the command-boundary
+    /// jump stops at it too.  The cell size is reported in pixels with a
+    /// scale of 1, which `iTerm2` clients multiply back out.  At most
+    /// [`USER_VAR_LIMIT`] user variables are kept, each no longer than
+    /// [`USER_VAR_VALUE_LIMIT`] bytes.
+    fn handle_iterm2(&mut self, cmd: &ITerm2Command) {
+        match cmd {
+            ITerm2Command::SetUserVar { name, value } => {
+                if value.len() > USER_VAR_VALUE_LIMIT {
+                    tracing::warn!(
+                        "OSC 1337: ignoring SetUserVar {name:?}: value is {} bytes, limit is {USER_VAR_VALUE_LIMIT}",
+                        value.len()
+                    );
+                    return;
+                }
+                if !value.is_empty()
+                    && self.user_vars.len() >= USER_VAR_LIMIT
+                    && !self.user_vars.contains_key(name)
+                {
+                    tracing::warn!(
+                        "OSC 1337: ignoring SetUserVar {name:?}: already {USER_VAR_LIMIT} variables"
+                    );
+                    return;
+                }
+                let vars = Arc::make_mut(&mut self.user_vars);
+                if value.is_empty() {
+                    vars.remove(name);
+                } else {
+                    vars.insert(name.clone(), value.clone());
+                }
+                self.refresh_badge();
+            }
+            ITerm2Command::CurrentDir(path) if !path.is_empty() => {
+                tracing::debug!("OSC 1337: CWD set to {path:?}");
+                self.current_working_directory = Some(path.clone());
+            }
+            ITerm2Command::SetMark if !self.buffer.is_alternate_screen() => {
+                self.buffer.mark_prompt_row();
+            }
+            ITerm2Command::RequestAttention(request) => {
+                self.window_commands
+                    .push(WindowManipulation::RequestAttention(*request));
+            }
+            ITerm2Command::ReportCellSize => {
+                let w = self.cell_pixel_width;
+                let h = self.cell_pixel_height;
+                self.write_osc_response(&format!("1337;ReportCellSize={h}.0;{w}.0;1.0"));
+            }
+            ITerm2Command::SetBadgeFormat(format) => {
+                self.badge_format = (!format.is_empty()).then(|| format.clone());
+                self.refresh_badge();
+            }
+            ITerm2Command::ClearScrollback => {
+                self.buffer.erase_scrollback();
+            }
+            ITerm2Command::CurrentDir(_) | ITerm2Command::SetMark => {
+                tracing::debug!("OSC 1337: {cmd} ignored");
+            }
+        }
+    }
+
+    /// Re-expand the badge format after it or a user variable changed.
+    fn refresh_badge(&mut self) {
+        self.badge = self
+            .badge_format
+            .as_deref()
+            .map(|format| expand_badge(format, &self.user_vars))
+            .filter(|badge| !badge.is_empty())
+            .map(Arc::from);
+    }
+
     /// Handle an OSC 133 (FTCS) shell integration marker.
     ///
     /// The `A`/`B`/`C`/`D` markers reach this function only when they carry
@@ -201,11 +351,16 @@ impl TerminalHandler {
     /// (accepted from any emitter, no `freminal=1`/`fid` required), so it
     /// *does* reach this function — where it is handled as an informational
     /// no-op (see the `PromptProperty` arm below).
+    ///
+    /// Untagged markers go to [`Self::handle_ftcs_compat`] instead, which
+    /// gives way to these: an `A` or `D` here closes any block it left open.
     pub(super) fn handle_osc_ftcs(&mut self, marker: &FtcsMarker) {
         tracing::debug!("OSC 133 FTCS marker: {marker}");
         match marker {
             FtcsMarker::PromptStart { fid } => {
+                self.close_compat_block();
                 self.ftcs_state = FtcsState::InPrompt;
+                self.ftcs_compat.set_native(FtcsState::InPrompt);
                 // mark_prompt_row() powers PrevCommand/NextCommand navigation
                 // and must stay. start_command_block() is a sibling that
                 // opens the new CommandBlock storage introduced in 72.2/72.3.
@@ -215,15 +370,21 @@ impl TerminalHandler {
             }
             FtcsMarker::CommandStart { fid } => {
                 self.ftcs_state = FtcsState::InCommand;
+                self.ftcs_compat.set_native(FtcsState::InCommand);
                 self.buffer.mark_command_start_row(fid);
             }
             FtcsMarker::OutputStart { fid } => {
                 self.ftcs_state = FtcsState::InOutput;
+                self.ftcs_compat.set_native(FtcsState::InOutput);
                 self.buffer.mark_output_start_row(fid);
             }
             FtcsMarker::CommandFinished { exit_code, fid } => {
+                self.close_compat_block();
                 self.last_exit_code = *exit_code;
                 self.ftcs_state = FtcsState::None;
+                self.ftcs_compat.set_native(FtcsState::None);
+                // Progress belongs to the command that reported it.
+                self.progress = None;
                 if let Some(block) = self.buffer.finish_command_block(*exit_code, fid) {
                     self.pending_command_events.push(block);
                 }
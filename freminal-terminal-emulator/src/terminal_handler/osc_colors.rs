@@ -11,9 +11,12 @@
 //!
 //! - [`TerminalHandler::handle_osc_fg_bg_color`] — main entry point for
 //!   OSC 10/11/12/110/111/112 color query, set, and reset sequences.
+//! - [`TerminalHandler::handle_osc_dynamic_color`] — the same for the
+//!   mouse pointer and highlight colors, OSC 13/14/17/19 and
+//!   113/114/117/119.
 
 use freminal_common::{
-    buffer_states::osc::{AnsiOscInternalType, AnsiOscType},
+    buffer_states::osc::{AnsiOscInternalType, AnsiOscType, DynamicColor},
     colors::parse_color_spec,
 };
 
@@ -89,13 +92,76 @@ impl TerminalHandler {
             _ => {}
         }
     }
+
+    /// Handle OSC 13/14/17/19 mouse pointer and highlight color query and
+    /// set, and the OSC 113/114/117/119 resets.
+    ///
+    /// Queries are answered like OSC 10/11: with the override, or else the
+    /// theme default — `selection_bg` / `selection_fg` for the highlight,
+    /// and the foreground / background for the pointer.
+    pub(super) fn handle_osc_dynamic_color(&mut self, osc: &AnsiOscType) {
+        match osc {
+            AnsiOscType::RequestColorQueryDynamic(color, AnsiOscInternalType::Query) => {
+                let (r, g, b) = self
+                    .dynamic_color_override(*color)
+                    .unwrap_or_else(|| self.dynamic_color_default(*color));
+                let number = color.osc_number();
+                self.write_osc_response(&format!("{number};rgb:{r:02x}/{g:02x}/{b:02x}"));
+            }
+            AnsiOscType::RequestColorQueryDynamic(color, AnsiOscInternalType::String(spec)) => {
+                if let Some(rgb) = parse_color_spec(spec) {
+                    *self.dynamic_color_override_mut(*color) = Some(rgb);
+                } else {
+                    tracing::warn!(
+                        "OSC {}: unrecognised color spec: {spec:?}",
+                        color.osc_number()
+                    );
+                }
+            }
+            AnsiOscType::ResetDynamicColor(color) => {
+                *self.dynamic_color_override_mut(*color) = None;
+            }
+            // Unknown internal-type variants and unreachable arms — silently ignore.
+            _ => {}
+        }
+    }
+
+    const fn dynamic_color_override(&self, color: DynamicColor) -> Option<(u8, u8, u8)> {
+        match color {
+            DynamicColor::MouseForeground => self.mouse_fg_override,
+            DynamicColor::MouseBackground => self.mouse_bg_override,
+            DynamicColor::HighlightBackground => self.highlight_bg_override,
+            DynamicColor::HighlightForeground => self.highlight_fg_override,
+        }
+    }
+
+    const fn dynamic_color_override_mut(
+        &mut self,
+        color: DynamicColor,
+    ) -> &mut Option<(u8, u8, u8)> {
+        match color {
+            DynamicColor::MouseForeground => &mut self.mouse_fg_override,
+            DynamicColor::MouseBackground => &mut self.mouse_bg_override,
+            DynamicColor::HighlightBackground => &mut self.highlight_bg_override,
+            DynamicColor::HighlightForeground => &mut self.highlight_fg_override,
+        }
+    }
+
+    const fn dynamic_color_default(&self, color: DynamicColor) -> (u8, u8, u8) {
+        match color {
+            DynamicColor::MouseForeground => self.theme.foreground,
+            DynamicColor::MouseBackground => self.theme.background,
+            DynamicColor::HighlightBackground => self.theme.selection_bg,
+            DynamicColor::HighlightForeground => self.theme.selection_fg,
+        }
+    }
 }
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use freminal_common::{
-        buffer_states::osc::{AnsiOscInternalType, AnsiOscType},
+        buffer_states::osc::{AnsiOscInternalType, AnsiOscType, DynamicColor},
         pty_write::PtyWrite,
     };
 
@@ -443,4 +509,95 @@ mod tests {
         handler.handle_osc_fg_bg_color(&AnsiOscType::ResetCursorColor);
         assert_eq!(handler.cursor_color_override(), None);
     }
+
+    // ------------------------------------------------------------------
+    // OSC 13/14/17/19 — mouse pointer and highlight colors
+    // ------------------------------------------------------------------
+
+    fn query_dynamic(
+        handler: &mut TerminalHandler,
+        rx: &crossbeam_channel::Receiver<PtyWrite>,
+        color: DynamicColor,
+    ) -> String {
+        handler.handle_osc_dynamic_color(&AnsiOscType::RequestColorQueryDynamic(
+            color,
+            AnsiOscInternalType::Query,
+        ));
+        let Ok(PtyWrite::Write(bytes)) = rx.try_recv() else {
+            panic!("expected PtyWrite::Write response");
+        };
+        String::from_utf8(bytes).expect("response must be valid UTF-8")
+    }
+
+    #[test]
+    fn dynamic_color_queries_return_theme_defaults() {
+        let mut handler = TerminalHandler::new(80, 24);
+        let (tx, rx) = crossbeam_channel::unbounded::<PtyWrite>();
+        handler.set_write_tx(tx);
+        let theme = *handler.theme();
+
+        for (color, (r, g, b)) in [
+            (DynamicColor::MouseForeground, theme.foreground),
+            (DynamicColor::MouseBackground, theme.background),
+            (DynamicColor::HighlightBackground, theme.selection_bg),
+            (DynamicColor::HighlightForeground, theme.selection_fg),
+        ] {
+            let number = color.osc_number();
+            assert!(
+                query_dynamic(&mut handler, &rx, color)
+                    .contains(&format!("{number};rgb:{r:02x}/{g:02x}/{b:02x}")),
+                "{color:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn highlight_color_set_query_reset() {
+        let mut handler = TerminalHandler::new(80, 24);
+        let (tx, rx) = crossbeam_channel::unbounded::<PtyWrite>();
+        handler.set_write_tx(tx);
+
+        handler.handle_osc_dynamic_color(&AnsiOscType::RequestColorQueryDynamic(
+            DynamicColor::HighlightBackground,
+            AnsiOscInternalType::String("#102030".to_string()),
+        ));
+        handler.handle_osc_dynamic_color(&AnsiOscType::RequestColorQueryDynamic(
+            DynamicColor::HighlightForeground,
+            AnsiOscInternalType::String("rgb:ff/ee/dd".to_string()),
+        ));
+        assert_eq!(handler.highlight_bg_override(), Some((0x10, 0x20, 0x30)));
+        assert_eq!(handler.highlight_fg_override(), Some((0xff, 0xee, 0xdd)));
+        assert!(
+            query_dynamic(&mut handler, &rx, DynamicColor::HighlightBackground)
+                .contains("17;rgb:10/20/30")
+        );
+
+        handler.handle_osc_dynamic_color(&AnsiOscType::ResetDynamicColor(
+            DynamicColor::HighlightBackground,
+        ));
+        assert_eq!(handler.highlight_bg_override(), None);
+        assert_eq!(handler.highlight_fg_override(), Some((0xff, 0xee, 0xdd)));
+    }
+
+    #[test]
+    fn mouse_color_set_is_reported_and_cleared_by_full_reset() {
+        let mut handler = TerminalHandler::new(80, 24);
+        let (tx, rx) = crossbeam_channel::unbounded::<PtyWrite>();
+        handler.set_write_tx(tx);
+
+        handler.handle_osc_dynamic_color(&AnsiOscType::RequestColorQueryDynamic(
+            DynamicColor::MouseForeground,
+            AnsiOscInternalType::String("#abcdef".to_string()),
+        ));
+        assert!(
+            query_dynamic(&mut handler, &rx, DynamicColor::MouseForeground)
+                .contains("13;rgb:ab/cd/ef")
+        );
+
+        handler.full_reset();
+        assert!(
+            !query_dynamic(&mut handler, &rx, DynamicColor::MouseForeground)
+                .contains("13;rgb:ab/cd/ef")
+        );
+    }
 }
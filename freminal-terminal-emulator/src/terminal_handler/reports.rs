@@ -5,10 +5,15 @@
 
 //! Terminal report and query response methods for [`TerminalHandler`].
 //!
-//! Contains DA1/DA2/DA3, DECREQTPARM, DSR, color theme report, and
-//! device name/version responses.
+//! Contains DA1/DA2/DA3, DECREQTPARM, DSR, color theme report, DECRQCRA
+//! checksum, DECRQPSR presentation state, and device name/version responses.
 
-use freminal_common::buffer_states::modes::decanm::Decanm;
+use freminal_common::buffer_states::{
+    charset::GSet,
+    modes::{decanm::Decanm, decom::Decom},
+    presentation_state::{CursorInformation, PresentationStateReport, tab_stops_report},
+    rectangle::{RectAttributes, Rectangle},
+};
 
 use super::TerminalHandler;
 
@@ -48,6 +53,67 @@ impl TerminalHandler {
         self.write_csi_response(&format!("{code};1;1;120;120;1;0x"));
     }
 
+    /// Handle DECRQCRA — Request Checksum of Rectangular Area.
+    ///
+    /// Responds with `DCS Pi ! ~ xxxx ST`, `xxxx` being the 16-bit checksum
+    /// in upper-case hex.  Ignored unless checksum reports are allowed.
+    pub fn handle_request_rectangle_checksum(&mut self, id: usize, area: Rectangle) {
+        if !self.allow_checksum_reports {
+            tracing::debug!(
+                "DECRQCRA ignored: checksum reports are disabled ([security] allow_checksum_reports)"
+            );
+            return;
+        }
+
+        let checksum = self
+            .buffer
+            .rectangle_checksum(area, self.checksum_extension);
+        self.write_dcs_response(&format!("{id}!~{checksum:04X}"));
+    }
+
+    /// Handle DECRQPSR — Request Presentation State Report.
+    ///
+    /// Responds with `DCS 1 $ u <DECCIR> ST` or `DCS 2 $ u <DECTABSR> ST`.
+    pub fn handle_request_presentation_state(&mut self, report: PresentationStateReport) {
+        let body = match report {
+            PresentationStateReport::CursorInformation => self.cursor_information().to_report(),
+            PresentationStateReport::TabStops => {
+                let columns: Vec<usize> = self
+                    .buffer
+                    .tab_stop_columns()
+                    .into_iter()
+                    .map(|col| col + 1)
+                    .collect();
+                tab_stops_report(&columns)
+            }
+        };
+        self.write_dcs_response(&format!("{}$u{body}", report.ps()));
+    }
+
+    /// The current cursor state as DECCIR describes it.
+    fn cursor_information(&self) -> CursorInformation {
+        let pos = self.buffer.cursor_screen_pos();
+        let last_col = self.buffer.terminal_width().saturating_sub(1);
+        let charsets = &self.charsets;
+        CursorInformation {
+            row: pos.y + 1,
+            col: pos.x.min(last_col) + 1,
+            rendition: RectAttributes::of(&self.current_format),
+            protected: self.current_format.is_protected(),
+            origin_mode: self.buffer.is_decom_enabled() == Decom::OriginMode,
+            single_shift_2: charsets.single_shift() == Some(GSet::G2),
+            single_shift_3: charsets.single_shift() == Some(GSet::G3),
+            wrap_pending: self.buffer.is_wrap_pending(),
+            gl: charsets.gl().index(),
+            gr: charsets.gr().index(),
+            charsets: GSet::ALL.map(|set| {
+                let charset = charsets.designation(set);
+                (charset.designator().to_string(), charset.is_96())
+            }),
+            ..CursorInformation::default()
+        }
+    }
+
     /// Handle `RequestDeviceNameAndVersion` — respond with Freminal's name and version.
     ///
     /// Responds with `DCS >|XTerm(Freminal <version>) ST` (7-bit) or the 8-bit
@@ -97,6 +163,10 @@ impl TerminalHandler {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use crate::terminal_handler::TerminalHandler;
+    use freminal_common::buffer_states::{
+        rectangle::{ChecksumExtension, Rectangle},
+        terminal_output::TerminalOutput,
+    };
     use freminal_common::pty_write::PtyWrite;
 
     fn recv_pty_response(rx: &crossbeam_channel::Receiver<PtyWrite>) -> String {
@@ -130,4 +200,39 @@ mod tests {
             "XTVERSION response must keep the tmux-compatible XTerm( prefix"
         );
     }
+
+    fn checksum_handler() -> (TerminalHandler, crossbeam_channel::Receiver<PtyWrite>) {
+        let mut handler = TerminalHandler::new(80, 24);
+        let (tx, rx) = crossbeam_channel::unbounded::<PtyWrite>();
+        handler.set_write_tx(tx);
+        handler.handle_data(b"AB");
+        (handler, rx)
+    }
+
+    #[test]
+    fn checksum_report_is_ignored_unless_allowed() {
+        let (mut handler, rx) = checksum_handler();
+        handler.handle_request_rectangle_checksum(1, Rectangle::default());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn checksum_report_negates_character_sum() {
+        let (mut handler, rx) = checksum_handler();
+        handler.set_allow_checksum_reports(true);
+        handler.handle_request_rectangle_checksum(3, Rectangle::default());
+        // 'A' + 'B' = 0x83, negated to 16 bits.
+        assert_eq!(recv_pty_response(&rx), "\x1bP3!~FF7D\x1b\\");
+    }
+
+    #[test]
+    fn checksum_extension_selects_positive_sum() {
+        let (mut handler, rx) = checksum_handler();
+        handler.set_allow_checksum_reports(true);
+        handler.process_outputs(&[TerminalOutput::SelectChecksumExtension(
+            ChecksumExtension::from_param(1),
+        )]);
+        handler.handle_request_rectangle_checksum(0, Rectangle::default());
+        assert_eq!(recv_pty_response(&rx), "\x1bP0!~0083\x1b\\");
+    }
 }
@@ -179,6 +179,7 @@ impl TerminalHandler {
         // scroll_offset is owned by ViewState on the GUI side; the PTY thread
         // always passes 0 when resizing.
         let _new_offset = self.buffer.set_size(width, height, 0);
+        let _ = self.status_buffer.set_size(width, 1, 0);
 
         if self.in_band_resize_enabled == InBandResizeMode::Set
             && (old_width != width || old_height != height)
@@ -14,14 +14,19 @@
 //!   to [`apply_sgr`].
 //! - [`TerminalHandler::build_sgr_response`] — serialises the current
 //!   [`FormatTag`] state back to an SGR parameter string (used by DECRQSS).
+//! - [`TerminalHandler::handle_push_sgr`] / [`TerminalHandler::handle_pop_sgr`] /
+//!   [`TerminalHandler::handle_report_sgr`] — xterm's SGR stack and
+//!   XTREPORTSGR.
 //! - [`append_color_sgr`] / [`append_underline_color_sgr`] — helper free
 //!   functions used by [`TerminalHandler::build_sgr_response`].
 
 use freminal_common::{
     buffer_states::{
-        cursor::ReverseVideo,
+        cursor::{ReverseVideo, StateColors},
         fonts::{BlinkState, FontDecorations, FontWeight, UnderlineStyle},
         format_tag::FormatTag,
+        rectangle::Rectangle,
+        sgr_stack::SgrStackAttributes,
     },
     colors::TerminalColor,
     sgr::SelectGraphicRendition,
@@ -29,6 +34,9 @@ use freminal_common::{
 
 use super::TerminalHandler;
 
+/// How many renditions XTPUSHSGR can save, as in xterm.
+pub(super) const SGR_STACK_LIMIT: usize = 10;
+
 impl TerminalHandler {
     /// Handle SGR (Select Graphic Rendition) — update `current_format` and propagate to buffer.
     pub fn handle_sgr(&mut self, sgr: &SelectGraphicRendition) {
@@ -55,13 +63,55 @@ impl TerminalHandler {
         self.buffer.set_format(self.current_format.clone());
     }
 
+    /// Handle XTPUSHSGR — save the current rendition, remembering which
+    /// attributes XTPOPSGR should restore.
+    ///
+    /// Like xterm, the stack holds [`SGR_STACK_LIMIT`] entries; pushes
+    /// beyond that are ignored.
+    pub fn handle_push_sgr(&mut self, attrs: SgrStackAttributes) {
+        if self.sgr_stack.len() < SGR_STACK_LIMIT {
+            self.sgr_stack.push((attrs, self.current_format.clone()));
+        }
+    }
+
+    /// Handle XTPOPSGR — restore the attributes saved by the last
+    /// XTPUSHSGR.  A no-op when the stack is empty.
+    pub fn handle_pop_sgr(&mut self) {
+        if let Some((attrs, saved)) = self.sgr_stack.pop() {
+            attrs.restore(&mut self.current_format, &saved);
+            self.buffer.set_format(self.current_format.clone());
+        }
+    }
+
+    /// Handle XTREPORTSGR — reply `CSI Ps ; ... m` with the rendition every
+    /// cell in `area` shares.
+    pub fn handle_report_sgr(&mut self, area: Rectangle) {
+        let tags = self.buffer.rectangle_tags(area);
+        let mut tags = tags.iter();
+        let shared = tags.next().map_or_else(FormatTag::default, |first| {
+            tags.fold(first.clone(), |mut shared, tag| {
+                keep_shared_rendition(&mut shared, tag);
+                shared
+            })
+        });
+        let sgr = Self::sgr_parameters(&shared, true);
+        self.write_csi_response(&format!("{sgr}m"));
+    }
+
     /// Build the SGR parameter string for the current format state.
+    pub(super) fn build_sgr_response(&self) -> String {
+        Self::sgr_parameters(self.current_format(), false)
+    }
+
+    /// Build the SGR parameter string that recreates `fmt`.
     ///
     /// Returns a string like `0;1;4;38;2;255;0;0` representing the active SGR
     /// attributes.  The leading `0` (reset) is always included; individual
     /// attributes are appended only when they differ from the default.
-    pub(super) fn build_sgr_response(&self) -> String {
-        let fmt = self.current_format();
+    ///
+    /// Blink (`5` / `6`) is listed only when `include_blink` is set: the
+    /// XTREPORTSGR reply carries it, the DECRQSS reply does not.
+    fn sgr_parameters(fmt: &FormatTag, include_blink: bool) -> String {
         let mut parts: Vec<String> = vec!["0".to_string()];
 
         // Font weight
@@ -86,6 +136,15 @@ impl TerminalHandler {
             }
         }
 
+        // Blink
+        if include_blink {
+            match fmt.blink {
+                BlinkState::None => {}
+                BlinkState::Slow => parts.push("5".to_string()),
+                BlinkState::Fast => parts.push("6".to_string()),
+            }
+        }
+
         // Reverse video
         if fmt.colors.reverse_video == ReverseVideo::On {
             parts.push("7".to_string());
@@ -168,6 +227,44 @@ impl TerminalHandler {
     }
 }
 
+/// Reduce `shared` to the rendition it has in common with `tag`: an
+/// attribute or color survives only if both carry the same value.
+fn keep_shared_rendition(shared: &mut FormatTag, tag: &FormatTag) {
+    if shared.font_weight != tag.font_weight {
+        shared.font_weight = FontWeight::Normal;
+    }
+    for decoration in [
+        FontDecorations::Italic,
+        FontDecorations::Faint,
+        FontDecorations::Strikethrough,
+    ] {
+        if !tag.font_decorations.contains(decoration) {
+            shared.font_decorations.remove(decoration);
+        }
+    }
+    if shared.font_decorations.underline_style() != tag.font_decorations.underline_style() {
+        shared.font_decorations.remove(FontDecorations::Underline);
+    }
+    if shared.blink != tag.blink {
+        shared.blink = BlinkState::None;
+    }
+
+    let default = StateColors::default();
+    let colors = &mut shared.colors;
+    if colors.color != tag.colors.color {
+        colors.color = default.color;
+    }
+    if colors.background_color != tag.colors.background_color {
+        colors.background_color = default.background_color;
+    }
+    if colors.underline_color != tag.colors.underline_color {
+        colors.underline_color = default.underline_color;
+    }
+    if colors.reverse_video != tag.colors.reverse_video {
+        colors.reverse_video = ReverseVideo::Off;
+    }
+}
+
 /// Apply a single `SelectGraphicRendition` value to a `FormatTag`, mutating it in-place.
 ///
 /// This is the central mapping between the parser's SGR enum and the buffer's format
@@ -177,9 +274,12 @@ impl TerminalHandler {
 #[allow(clippy::too_many_lines)]
 pub(super) fn apply_sgr(tag: &mut FormatTag, sgr: &SelectGraphicRendition) {
     match sgr {
-        // Reset: restore every field to its default value
+        // Reset: restore every field to its default value, except the
+        // DECSCA protection, which SGR does not own
         SelectGraphicRendition::Reset => {
+            let protected = tag.is_protected();
             *tag = FormatTag::default();
+            tag.set_protected(protected);
         }
 
         // Font weight
@@ -1013,4 +1113,81 @@ mod tests {
             );
         }
     }
+
+    // ------------------------------------------------------------------
+    // XTPUSHSGR / XTPOPSGR
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn pop_restores_pushed_rendition() {
+        let mut handler = TerminalHandler::new(80, 24);
+        handler.handle_sgr(&SelectGraphicRendition::Bold);
+        handler.handle_push_sgr(SgrStackAttributes::ALL);
+        handler.handle_sgr(&SelectGraphicRendition::Reset);
+        handler.handle_sgr(&SelectGraphicRendition::Foreground(TerminalColor::Red));
+
+        handler.handle_pop_sgr();
+
+        assert_eq!(handler.current_format().font_weight, FontWeight::Bold);
+        assert_eq!(
+            handler.current_format().colors.color,
+            TerminalColor::Default
+        );
+    }
+
+    #[test]
+    fn pop_restores_only_selected_attributes() {
+        let mut handler = TerminalHandler::new(80, 24);
+        handler.handle_push_sgr(SgrStackAttributes::FOREGROUND);
+        handler.handle_sgr(&SelectGraphicRendition::Bold);
+        handler.handle_sgr(&SelectGraphicRendition::Foreground(TerminalColor::Red));
+
+        handler.handle_pop_sgr();
+
+        assert_eq!(handler.current_format().font_weight, FontWeight::Bold);
+        assert_eq!(
+            handler.current_format().colors.color,
+            TerminalColor::Default
+        );
+    }
+
+    #[test]
+    fn pop_on_empty_stack_is_a_no_op() {
+        let mut handler = TerminalHandler::new(80, 24);
+        handler.handle_sgr(&SelectGraphicRendition::Italic);
+        handler.handle_pop_sgr();
+        assert!(
+            handler
+                .current_format()
+                .font_decorations
+                .contains(FontDecorations::Italic)
+        );
+    }
+
+    #[test]
+    fn push_beyond_limit_is_ignored() {
+        let mut handler = TerminalHandler::new(80, 24);
+        for _ in 0..=SGR_STACK_LIMIT {
+            handler.handle_push_sgr(SgrStackAttributes::ALL);
+        }
+        assert_eq!(handler.sgr_stack.len(), SGR_STACK_LIMIT);
+    }
+
+    #[test]
+    fn shared_rendition_keeps_common_attributes_only() {
+        let mut shared = FormatTag {
+            font_weight: FontWeight::Bold,
+            ..FormatTag::default()
+        };
+        shared.colors.set_color(TerminalColor::Red);
+        shared.font_decorations.insert(FontDecorations::Italic);
+        let mut other = shared.clone();
+        other.colors.set_color(TerminalColor::Blue);
+
+        keep_shared_rendition(&mut shared, &other);
+
+        assert_eq!(shared.font_weight, FontWeight::Bold);
+        assert!(shared.font_decorations.contains(FontDecorations::Italic));
+        assert_eq!(shared.colors.color, TerminalColor::Default);
+    }
 }
@@ -11,6 +11,188 @@
 //! - [`parse_osc7_uri`] — parse `file://hostname/path` URIs emitted by OSC 7
 //! - [`percent_decode`] — URL percent-decode a string
 //! - [`hex_val`] — convert a single ASCII hex digit to its numeric value
+//!
+//! It also holds the compatibility mode that builds command blocks from
+//! markers without freminal's `fid` — plain `OSC 133 A/B/C/D` from other
+//! integrations, and VS Code's `OSC 633` — by pairing them in order:
+//!
+//! - `A` opens a block.  An `A` while a block is still open closes that
+//!   block first: it is finished without an exit code if its command ran
+//!   (`C` was seen), and dropped otherwise (a redrawn prompt).
+//! - `B` and `C` advance the open block; repeats are ignored.  Without an
+//!   open block they open one at the cursor, as if `A` had been sent there.
+//! - `D` finishes the open block; a `D` with no open block is ignored.
+//! - `633 E` records the command line on the open block, `633 P ; Cwd=`
+//!   sets the working directory.
+//!
+//! Freminal's own markers win.  Plain markers are ignored between a tagged
+//! `A` and `C` (another integration running in the same prompt), and a
+//! tagged `A` or `D` closes any open plain block.  During a tagged
+//! command's output the plain markers are kept: they come from a nested
+//! shell, such as one on a remote host.
+
+use freminal_common::buffer_states::{ftcs::FtcsState, ftcs_compat::CompatMarker};
+
+use super::TerminalHandler;
+
+/// Pairing state for shell-integration markers that carry no `fid`.
+#[derive(Debug, Default)]
+pub(super) struct CompatPairing {
+    /// The block opened from plain markers that has not finished yet.
+    open: Option<OpenBlock>,
+    /// Where freminal's own (tagged) markers are.
+    native: FtcsState,
+    /// Counter for the `fid`s given to plain blocks.
+    next_fid: u64,
+}
+
+/// A command block opened from plain markers.
+#[derive(Debug)]
+struct OpenBlock {
+    /// The `fid` it was given, which tagged markers never use.
+    fid: String,
+    /// The last marker applied to it.
+    phase: FtcsState,
+}
+
+impl CompatPairing {
+    /// Record the region of freminal's own markers.
+    pub(super) const fn set_native(&mut self, state: FtcsState) {
+        self.native = state;
+    }
+
+    fn next_fid(&mut self) -> String {
+        self.next_fid += 1;
+        format!("compat:{}", self.next_fid)
+    }
+}
+
+impl TerminalHandler {
+    /// Handle a plain `OSC 133` or an `OSC 633` marker.
+    pub(super) fn handle_ftcs_compat(&mut self, marker: &CompatMarker) {
+        tracing::debug!("shell integration marker without fid: {marker}");
+        if matches!(
+            self.ftcs_compat.native,
+            FtcsState::InPrompt | FtcsState::InCommand
+        ) {
+            // Another integration is marking the prompt freminal's scripts
+            // already mark.
+            return;
+        }
+
+        match marker {
+            CompatMarker::PromptStart => {
+                self.close_compat_block();
+                self.open_compat_block();
+            }
+            // Repeats, and B or C guessed inside a tagged command's output,
+            // fall through to the empty arms.
+            CompatMarker::CommandStart => match self.compat_phase() {
+                Some(FtcsState::InPrompt) => {
+                    self.advance_compat_block(FtcsState::InCommand);
+                }
+                None if self.ftcs_compat.native == FtcsState::None => {
+                    self.open_compat_block();
+                    self.advance_compat_block(FtcsState::InCommand);
+                }
+                _ => {}
+            },
+            CompatMarker::OutputStart => match self.compat_phase() {
+                Some(FtcsState::InPrompt | FtcsState::InCommand) => {
+                    self.advance_compat_block(FtcsState::InOutput);
+                }
+                None if self.ftcs_compat.native == FtcsState::None => {
+                    self.open_compat_block();
+                    self.advance_compat_block(FtcsState::InOutput);
+                }
+                _ => {}
+            },
+            CompatMarker::CommandFinished { exit_code } => {
+                self.finish_compat_block(*exit_code);
+            }
+            CompatMarker::CommandLine(command_line) => {
+                if let Some(open) = &self.ftcs_compat.open
+                    && !command_line.is_empty()
+                {
+                    self.buffer
+                        .set_command_line(&open.fid, command_line.clone());
+                }
+            }
+            CompatMarker::Property { key, value } if key == "Cwd" && !value.is_empty() => {
+                tracing::debug!("OSC 633 P: CWD set to {value:?}");
+                self.current_working_directory = Some(value.clone());
+            }
+            CompatMarker::Property { key, .. } => {
+                tracing::debug!("OSC 633 P: property {key} ignored");
+            }
+        }
+    }
+
+    /// Close the open plain block, whose `D` never came.
+    pub(super) fn close_compat_block(&mut self) {
+        let Some(open) = self.ftcs_compat.open.take() else {
+            return;
+        };
+        if open.phase == FtcsState::InOutput {
+            let _block = self.buffer.finish_command_block(None, &open.fid);
+        } else {
+            self.buffer.discard_command_block(&open.fid);
+        }
+    }
+
+    fn compat_phase(&self) -> Option<FtcsState> {
+        self.ftcs_compat.open.as_ref().map(|open| open.phase)
+    }
+
+    /// Open a plain block at the cursor row.
+    fn open_compat_block(&mut self) {
+        let fid = self.ftcs_compat.next_fid();
+        self.buffer.mark_prompt_row();
+        let cwd = self.current_working_directory().map(str::to_owned);
+        let _id = self.buffer.start_command_block(cwd, fid.clone());
+        self.ftcs_compat.open = Some(OpenBlock {
+            fid,
+            phase: FtcsState::InPrompt,
+        });
+        self.track_compat_phase(FtcsState::InPrompt);
+    }
+
+    /// Mark the open block's command or output start at the cursor row.
+    fn advance_compat_block(&mut self, phase: FtcsState) {
+        let Some(open) = self.ftcs_compat.open.as_mut() else {
+            return;
+        };
+        open.phase = phase;
+        if phase == FtcsState::InCommand {
+            self.buffer.mark_command_start_row(&open.fid);
+        } else {
+            self.buffer.mark_output_start_row(&open.fid);
+        }
+        self.track_compat_phase(phase);
+    }
+
+    fn finish_compat_block(&mut self, exit_code: Option<i32>) {
+        let Some(open) = self.ftcs_compat.open.take() else {
+            tracing::debug!("shell integration: D without an open block ignored");
+            return;
+        };
+        self.last_exit_code = exit_code;
+        // Progress belongs to the command that reported it.
+        self.progress = None;
+        self.track_compat_phase(FtcsState::None);
+        if let Some(block) = self.buffer.finish_command_block(exit_code, &open.fid) {
+            self.pending_command_events.push(block);
+        }
+    }
+
+    /// Report plain markers through `ftcs_state` unless freminal's own
+    /// markers are in use.
+    const fn track_compat_phase(&mut self, phase: FtcsState) {
+        if matches!(self.ftcs_compat.native, FtcsState::None) {
+            self.ftcs_state = phase;
+        }
+    }
+}
 
 /// Parse an OSC 7 URI of the form `file://hostname/path` and return the path
 /// component.
@@ -79,7 +261,9 @@ const fn hex_val(b: u8) -> Option<u8> {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use freminal_common::buffer_states::{
+        command_block::CommandStatus,
         ftcs::{FtcsMarker, FtcsState, PromptKind},
+        ftcs_compat::CompatMarker,
         osc::AnsiOscType,
     };
 
@@ -706,12 +890,12 @@ mod tests {
 
     // ── New tests: foreign-marker rejection ─────────────────────────────
 
-    /// Markers without `freminal=1` must produce no state change and no blocks.
-    /// The handler only receives markers that passed [`parse_ftcs_params`], so
-    /// this tests that [`parse_ftcs_params`] correctly rejects them.
+    /// Markers without `freminal=1` are rejected by [`parse_ftcs_params`]; they
+    /// reach the handler as `CompatMarker`s instead (see the compatibility
+    /// tests below).
     #[test]
     fn foreign_marker_rejection_is_handled_at_parse_layer() {
-        // WezTerm-style: A without freminal=1 → parse returns None → handler never called
+        // WezTerm-style: A without freminal=1 → parse returns None
         use freminal_common::buffer_states::ftcs::parse_ftcs_params;
         assert_eq!(parse_ftcs_params(&["A", "aid=12345"]), None);
         assert_eq!(parse_ftcs_params(&["D", "0", "aid=12345"]), None);
@@ -749,6 +933,276 @@ mod tests {
         );
     }
 
+    // ── Compatibility mode: plain OSC 133 and OSC 633 ───────────────────
+
+    fn plain(handler: &mut TerminalHandler, marker: CompatMarker) {
+        handler.handle_osc(&AnsiOscType::FtcsCompat(marker));
+    }
+
+    fn native(handler: &mut TerminalHandler, marker: FtcsMarker) {
+        handler.handle_osc(&AnsiOscType::Ftcs(marker));
+    }
+
+    fn finished(exit_code: Option<i32>) -> CompatMarker {
+        CompatMarker::CommandFinished { exit_code }
+    }
+
+    /// A → B → E → C → D builds one block carrying the command line.
+    #[test]
+    fn compat_full_cycle_builds_block() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, CompatMarker::PromptStart);
+        assert_eq!(handler.ftcs_state(), FtcsState::InPrompt);
+        plain(&mut handler, CompatMarker::CommandStart);
+        plain(&mut handler, CompatMarker::CommandLine("ls -la".to_owned()));
+        plain(&mut handler, CompatMarker::OutputStart);
+        assert_eq!(handler.ftcs_state(), FtcsState::InOutput);
+        plain(&mut handler, finished(Some(2)));
+
+        assert_eq!(handler.ftcs_state(), FtcsState::None);
+        assert_eq!(handler.last_exit_code(), Some(2));
+        let events = handler.drain_command_events();
+        assert_eq!(events.len(), 1);
+        let block = &events[0];
+        assert_eq!(block.command_line.as_deref(), Some("ls -la"));
+        assert_eq!(block.exit_code, Some(2));
+        assert!(block.command_start_row.is_some());
+        assert!(block.executed());
+    }
+
+    /// A second A before the command ran replaces the first block: the
+    /// prompt was redrawn.
+    #[test]
+    fn compat_repeated_prompt_start_replaces_unrun_block() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, CompatMarker::PromptStart);
+        plain(&mut handler, CompatMarker::CommandStart);
+        plain(&mut handler, CompatMarker::PromptStart);
+
+        assert_eq!(handler.buffer().command_blocks().len(), 1);
+        assert_eq!(handler.buffer().prompt_rows().len(), 1);
+        assert!(handler.drain_command_events().is_empty());
+    }
+
+    /// An A after a command ran without its D finishes that block with no
+    /// exit code and opens a new one.
+    #[test]
+    fn compat_prompt_start_closes_command_missing_its_finish() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, CompatMarker::PromptStart);
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, CompatMarker::PromptStart);
+
+        let blocks = handler.buffer().command_blocks();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].status(), CommandStatus::Unknown);
+        assert_eq!(blocks[1].status(), CommandStatus::Running);
+        // Only a real D reports a finished command.
+        assert!(handler.drain_command_events().is_empty());
+    }
+
+    /// B or C without an A opens a block at the cursor.
+    #[test]
+    fn compat_missing_prompt_start_opens_block() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, CompatMarker::CommandStart);
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, finished(Some(0)));
+
+        let events = handler.drain_command_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].command_start_row,
+            Some(events[0].prompt_start_row)
+        );
+
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, finished(Some(1)));
+        let events = handler.drain_command_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].command_start_row, None);
+        assert_eq!(events[0].exit_code, Some(1));
+    }
+
+    /// D with nothing open, and repeated B/C, change nothing.
+    #[test]
+    fn compat_stray_and_repeated_markers_are_ignored() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, finished(Some(1)));
+        assert!(handler.buffer().command_blocks().is_empty());
+        assert_eq!(handler.last_exit_code(), None);
+
+        plain(&mut handler, CompatMarker::PromptStart);
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, CompatMarker::CommandStart);
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, finished(Some(0)));
+        plain(&mut handler, finished(Some(3)));
+
+        let events = handler.drain_command_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].command_start_row, None);
+        assert_eq!(handler.last_exit_code(), Some(0));
+    }
+
+    /// Plain markers inside a tagged prompt are duplicates and ignored.
+    #[test]
+    fn compat_markers_ignored_inside_native_prompt() {
+        let mut handler = TerminalHandler::new(80, 24);
+        native(
+            &mut handler,
+            FtcsMarker::PromptStart {
+                fid: "n1".to_owned(),
+            },
+        );
+        plain(&mut handler, CompatMarker::PromptStart);
+        plain(&mut handler, CompatMarker::CommandStart);
+        native(
+            &mut handler,
+            FtcsMarker::CommandStart {
+                fid: "n1".to_owned(),
+            },
+        );
+        plain(&mut handler, CompatMarker::OutputStart);
+        native(
+            &mut handler,
+            FtcsMarker::OutputStart {
+                fid: "n1".to_owned(),
+            },
+        );
+        native(
+            &mut handler,
+            FtcsMarker::CommandFinished {
+                exit_code: Some(0),
+                fid: "n1".to_owned(),
+            },
+        );
+
+        let blocks = handler.buffer().command_blocks();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].fid, "n1");
+    }
+
+    /// A plain A sent just before a tagged A is dropped in its favour.
+    #[test]
+    fn compat_block_discarded_by_native_prompt_start() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, CompatMarker::PromptStart);
+        native(
+            &mut handler,
+            FtcsMarker::PromptStart {
+                fid: "n1".to_owned(),
+            },
+        );
+
+        let blocks = handler.buffer().command_blocks();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].fid, "n1");
+        assert_eq!(handler.buffer().prompt_rows().len(), 1);
+    }
+
+    /// A nested shell (e.g. over ssh) inside a tagged command's output gets
+    /// its own blocks; the outer state stays `InOutput`.
+    #[test]
+    fn compat_nested_shell_inside_native_output() {
+        let mut handler = TerminalHandler::new(80, 24);
+        native(
+            &mut handler,
+            FtcsMarker::PromptStart {
+                fid: "ssh".to_owned(),
+            },
+        );
+        native(
+            &mut handler,
+            FtcsMarker::OutputStart {
+                fid: "ssh".to_owned(),
+            },
+        );
+        plain(&mut handler, CompatMarker::PromptStart);
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, finished(Some(0)));
+        assert_eq!(handler.ftcs_state(), FtcsState::InOutput);
+
+        // The remote shell exits in the middle of its next command.
+        plain(&mut handler, CompatMarker::PromptStart);
+        plain(&mut handler, CompatMarker::OutputStart);
+        native(
+            &mut handler,
+            FtcsMarker::CommandFinished {
+                exit_code: Some(0),
+                fid: "ssh".to_owned(),
+            },
+        );
+
+        let blocks = handler.buffer().command_blocks();
+        assert_eq!(blocks.len(), 3);
+        assert!(blocks.iter().all(|b| b.end_row.is_some()));
+        assert_eq!(blocks[2].status(), CommandStatus::Unknown);
+        assert_eq!(handler.drain_command_events().len(), 2);
+        assert_eq!(handler.ftcs_state(), FtcsState::None);
+    }
+
+    /// Without an open block, C and B from a nested shell are not guessed
+    /// into one.
+    #[test]
+    fn compat_no_guessed_block_inside_native_output() {
+        let mut handler = TerminalHandler::new(80, 24);
+        native(
+            &mut handler,
+            FtcsMarker::PromptStart {
+                fid: "n1".to_owned(),
+            },
+        );
+        native(
+            &mut handler,
+            FtcsMarker::OutputStart {
+                fid: "n1".to_owned(),
+            },
+        );
+        plain(&mut handler, CompatMarker::OutputStart);
+        plain(&mut handler, CompatMarker::CommandStart);
+        assert_eq!(handler.buffer().command_blocks().len(), 1);
+    }
+
+    /// `633 P ; Cwd=` sets the working directory the next block records.
+    #[test]
+    fn compat_cwd_property_sets_cwd() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(
+            &mut handler,
+            CompatMarker::Property {
+                key: "Cwd".to_owned(),
+                value: "/srv/app".to_owned(),
+            },
+        );
+        plain(
+            &mut handler,
+            CompatMarker::Property {
+                key: "IsWindows".to_owned(),
+                value: "False".to_owned(),
+            },
+        );
+        plain(&mut handler, CompatMarker::PromptStart);
+
+        assert_eq!(handler.current_working_directory(), Some("/srv/app"));
+        assert_eq!(
+            handler.buffer().command_blocks()[0].cwd.as_deref(),
+            Some("/srv/app")
+        );
+    }
+
+    /// RIS forgets the open block.
+    #[test]
+    fn full_reset_clears_compat_pairing() {
+        let mut handler = TerminalHandler::new(80, 24);
+        plain(&mut handler, CompatMarker::PromptStart);
+        handler.full_reset();
+        plain(&mut handler, finished(Some(0)));
+
+        assert!(handler.buffer().command_blocks().is_empty());
+        assert!(handler.drain_command_events().is_empty());
+    }
+
     // -----------------------------------------------------------------------
     // parse_osc7_uri additional edge cases
     // -----------------------------------------------------------------------
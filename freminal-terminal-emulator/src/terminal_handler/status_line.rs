@@ -0,0 +1,122 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DEC status line (DECSSDT / DECSASD) for [`TerminalHandler`].
+//!
+//! The host-writable status line is a one-row [`Buffer`] of its own.  While
+//! DECSASD points at it, [`TerminalHandler::process_outputs`] swaps it in for
+//! the main buffer, so every sequence lands on the status line and the main
+//! display keeps its cursor and contents untouched.
+
+use freminal_buffer::buffer::Buffer;
+use freminal_common::buffer_states::{
+    cursor::ReverseVideo,
+    format_tag::FormatTag,
+    status_line::{StatusDisplay, StatusLineType},
+    tchar::TChar,
+    terminal_output::TerminalOutput,
+};
+
+use super::TerminalHandler;
+
+impl TerminalHandler {
+    /// A blank status line buffer `width` columns wide, without scrollback.
+    pub(super) fn new_status_buffer(width: usize) -> Buffer {
+        Buffer::new(width, 1).with_scrollback_limit(0)
+    }
+
+    /// Process `output` against the status line buffer.
+    pub(super) fn process_output_on_status_line(&mut self, output: &TerminalOutput) {
+        std::mem::swap(&mut self.buffer, &mut self.status_buffer);
+        self.process_output(output);
+        std::mem::swap(&mut self.buffer, &mut self.status_buffer);
+    }
+
+    /// Whether `output` must reach the status line while DECSASD selects it.
+    ///
+    /// The status line selections themselves and RIS always act on the
+    /// handler as a whole.
+    pub(super) const fn targets_status_line(&self, output: &TerminalOutput) -> bool {
+        matches!(self.status_display, StatusDisplay::StatusLine)
+            && !matches!(
+                output,
+                TerminalOutput::SelectStatusLineType(_)
+                    | TerminalOutput::SelectActiveStatusDisplay(_)
+                    | TerminalOutput::ResetDevice
+            )
+    }
+
+    /// Handle DECSSDT — select the status line type.
+    ///
+    /// Selecting the host-writable line starts it blank; any other type sends
+    /// output back to the main display.
+    pub(super) fn handle_select_status_line_type(&mut self, kind: StatusLineType) {
+        if kind == StatusLineType::HostWritable {
+            if self.status_line_type != StatusLineType::HostWritable {
+                self.status_buffer = Self::new_status_buffer(self.buffer.terminal_width());
+            }
+        } else {
+            self.status_display = StatusDisplay::Main;
+        }
+        self.status_line_type = kind;
+    }
+
+    /// Handle DECSASD — select the display output goes to.
+    ///
+    /// Output can only be directed at the status line while it is
+    /// host-writable.
+    pub(super) fn handle_select_active_status_display(&mut self, display: StatusDisplay) {
+        if display == StatusDisplay::StatusLine
+            && self.status_line_type != StatusLineType::HostWritable
+        {
+            return;
+        }
+        self.status_display = display;
+    }
+
+    /// The status line type selected by DECSSDT.
+    #[must_use]
+    pub const fn status_line_type(&self) -> StatusLineType {
+        self.status_line_type
+    }
+
+    /// The status line's characters and format tags, or `None` when no
+    /// status line is shown.
+    pub fn status_line_contents(&mut self) -> Option<(Vec<TChar>, Vec<FormatTag>)> {
+        match self.status_line_type {
+            StatusLineType::None => None,
+            StatusLineType::Indicator => Some(self.indicator_line()),
+            StatusLineType::HostWritable => {
+                let (chars, tags, _, _) = self.status_buffer.visible_as_tchars_and_tags(0);
+                Some((chars, tags))
+            }
+        }
+    }
+
+    /// The indicator status line: the cursor position in reverse video
+    /// across the whole width.
+    fn indicator_line(&self) -> (Vec<TChar>, Vec<FormatTag>) {
+        let width = self.buffer.terminal_width();
+        let pos = self.buffer.cursor_screen_pos();
+        let text = format!(
+            " Ln {}, Col {}",
+            pos.y + 1,
+            pos.x.min(width.saturating_sub(1)) + 1
+        );
+        let chars: Vec<TChar> = text
+            .bytes()
+            .map(TChar::new_from_single_char)
+            .chain(std::iter::repeat(TChar::Space))
+            .take(width)
+            .collect();
+        let mut tag = FormatTag {
+            start: 0,
+            end: chars.len(),
+            ..FormatTag::default()
+        };
+        tag.colors.set_reverse_video(ReverseVideo::On);
+        (chars, vec![tag])
+    }
+}
@@ -0,0 +1,909 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! tmux control-mode (`tmux -CC`) client protocol.
+//!
+//! When a program in a pane runs `tmux -CC`, tmux stops drawing a screen and
+//! instead speaks a line protocol inside a `DCS 1000 p` string: `%`-prefixed
+//! notifications (`%output`, `%layout-change`, `%window-add`, …) plus
+//! `%begin`/`%end` blocks that carry the replies to commands the client
+//! writes back as plain text lines.
+//!
+//! This module holds the pieces that do not depend on the GUI:
+//!
+//! - [`TmuxControlSession`] — per-gateway state owned by the
+//!   [`TerminalHandler`](crate::terminal_handler::TerminalHandler); turns
+//!   protocol lines into [`TmuxEvent`]s and issues the follow-up queries
+//!   (`list-windows`, `capture-pane`) itself.
+//! - [`TmuxCommandSender`] — a cloneable handle that writes commands to the
+//!   gateway PTY and records what reply each one expects, so that
+//!   `%begin`/`%end` blocks can be matched to commands in FIFO order.
+//! - [`TmuxLayout`] — the parsed `window_layout` tree, and
+//!   [`TmuxLayout::split_plan`] which flattens it into the sequence of
+//!   binary splits that reproduces it in a native pane tree.
+//!
+//! Pane ids (`%N`), window ids (`@N`) and session ids (`$N`) are carried as
+//! bare numbers without their sigil.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use conv2::ApproxFrom;
+use crossbeam_channel::Sender;
+use freminal_common::pty_write::PtyWrite;
+
+use crate::error::TmuxControlError;
+
+/// `list-windows` format: window id, layout and name, space separated.  The
+/// name goes last because it may itself contain spaces.
+const LIST_WINDOWS_FORMAT: &str = "#{window_id} #{window_layout} #{window_name}";
+
+/// Maximum input bytes carried by a single `send-keys -H` command.
+///
+/// Keeps individual command lines short when a large paste is forwarded.
+const SEND_KEYS_CHUNK: usize = 256;
+
+/// Split axis of a tmux layout container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TmuxSplitAxis {
+    /// `{…}` — children are laid out left to right.
+    SideBySide,
+    /// `[…]` — children are stacked top to bottom.
+    Stacked,
+}
+
+/// Content of a [`TmuxLayout`] cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TmuxLayoutContent {
+    /// A leaf holding the pane with this id.
+    Pane(u32),
+    /// A container splitting its area between two or more children.
+    Split {
+        axis: TmuxSplitAxis,
+        children: Vec<TmuxLayout>,
+    },
+}
+
+/// One cell of a tmux `window_layout` tree, in character cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxLayout {
+    pub width: usize,
+    pub height: usize,
+    pub x: usize,
+    pub y: usize,
+    pub content: TmuxLayoutContent,
+}
+
+/// One step of [`TmuxLayout::split_plan`]: split the native pane showing
+/// `target`, put `pane` in the new second half, and give `target`'s half
+/// `ratio` of the space along `axis`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TmuxSplit {
+    pub target: u32,
+    pub pane: u32,
+    pub axis: TmuxSplitAxis,
+    pub ratio: f32,
+}
+
+impl TmuxLayout {
+    /// Parse a `window_layout` string such as
+    /// `b25d,80x24,0,0{40x24,0,0,1,39x24,41,0,2}`.
+    ///
+    /// The leading four-digit checksum is optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TmuxControlError::InvalidLayout`] when the string does not
+    /// follow tmux's layout grammar.
+    pub fn parse(layout: &str) -> Result<Self, TmuxControlError> {
+        let bytes = layout.as_bytes();
+        // A checksum is 4 hex digits and a comma; a cell's leading width is
+        // followed by `x`, never by a comma, so the two cannot be confused.
+        let mut pos = if bytes.len() > 5
+            && bytes[4] == b','
+            && bytes[..4].iter().all(u8::is_ascii_hexdigit)
+        {
+            5
+        } else {
+            0
+        };
+        let invalid = |position: usize| TmuxControlError::InvalidLayout {
+            layout: layout.to_owned(),
+            position,
+        };
+        let cell = parse_cell(bytes, &mut pos).map_err(invalid)?;
+        if pos == bytes.len() {
+            Ok(cell)
+        } else {
+            Err(invalid(pos))
+        }
+    }
+
+    /// The top-left-most pane, which stays in place while
+    /// [`Self::split_plan`] splits the others off it.
+    #[must_use]
+    pub fn first_pane(&self) -> Option<u32> {
+        match &self.content {
+            TmuxLayoutContent::Pane(id) => Some(*id),
+            TmuxLayoutContent::Split { children, .. } => {
+                children.first().and_then(Self::first_pane)
+            }
+        }
+    }
+
+    /// All pane ids in the layout, in depth-first (reading) order.
+    #[must_use]
+    pub fn pane_ids(&self) -> Vec<u32> {
+        let mut ids = Vec::new();
+        self.collect_pane_ids(&mut ids);
+        ids
+    }
+
+    /// Size, in character cells, of the pane `id`, if it is in the layout.
+    #[must_use]
+    pub fn pane_size(&self, id: u32) -> Option<(usize, usize)> {
+        match &self.content {
+            TmuxLayoutContent::Pane(pane) => (*pane == id).then_some((self.width, self.height)),
+            TmuxLayoutContent::Split { children, .. } => {
+                children.iter().find_map(|child| child.pane_size(id))
+            }
+        }
+    }
+
+    fn collect_pane_ids(&self, out: &mut Vec<u32>) {
+        match &self.content {
+            TmuxLayoutContent::Pane(id) => out.push(*id),
+            TmuxLayoutContent::Split { children, .. } => {
+                for child in children {
+                    child.collect_pane_ids(out);
+                }
+            }
+        }
+    }
+
+    /// Flatten the layout into binary splits that rebuild it from a single
+    /// pane holding [`Self::first_pane`].
+    ///
+    /// A container with children `c1 … cn` becomes a right-leaning chain: the
+    /// pane for `c1` is split to make room for `c2 … cn`, that remainder is
+    /// split to make room for `c3 … cn`, and so on.  Each child's own
+    /// subtree is then expanded after the chain, so every `target` exists by
+    /// the time it is split.
+    #[must_use]
+    pub fn split_plan(&self) -> Vec<TmuxSplit> {
+        let mut plan = Vec::new();
+        self.collect_splits(&mut plan);
+        plan
+    }
+
+    fn collect_splits(&self, out: &mut Vec<TmuxSplit>) {
+        let TmuxLayoutContent::Split { axis, children } = &self.content else {
+            return;
+        };
+        let extent = |cell: &Self| match axis {
+            TmuxSplitAxis::SideBySide => cell.width,
+            TmuxSplitAxis::Stacked => cell.height,
+        };
+        for (i, pair) in children.windows(2).enumerate() {
+            let (Some(target), Some(pane)) = (pair[0].first_pane(), pair[1].first_pane()) else {
+                continue;
+            };
+            let remaining: usize = children[i..].iter().map(extent).sum();
+            out.push(TmuxSplit {
+                target,
+                pane,
+                axis: *axis,
+                ratio: share(extent(&pair[0]), remaining),
+            });
+        }
+        for child in children {
+            child.collect_splits(out);
+        }
+    }
+}
+
+/// `part / whole` as a split ratio, falling back to an even split.
+fn share(part: usize, whole: usize) -> f32 {
+    if whole == 0 {
+        return 0.5;
+    }
+    match (f32::approx_from(part), f32::approx_from(whole)) {
+        (Ok(part), Ok(whole)) => part / whole,
+        _ => 0.5,
+    }
+}
+
+/// Parse one `WxH,X,Y` cell and its content starting at `*pos`.
+///
+/// Returns the failing byte offset on error.
+fn parse_cell(bytes: &[u8], pos: &mut usize) -> Result<TmuxLayout, usize> {
+    let width = parse_number(bytes, pos)?;
+    expect(bytes, pos, b'x')?;
+    let height = parse_number(bytes, pos)?;
+    expect(bytes, pos, b',')?;
+    let x = parse_number(bytes, pos)?;
+    expect(bytes, pos, b',')?;
+    let y = parse_number(bytes, pos)?;
+
+    let (axis, close) = match bytes.get(*pos) {
+        Some(b',') => {
+            *pos += 1;
+            let id = parse_number(bytes, pos)?;
+            let id = u32::try_from(id).map_err(|_| *pos)?;
+            return Ok(TmuxLayout {
+                width,
+                height,
+                x,
+                y,
+                content: TmuxLayoutContent::Pane(id),
+            });
+        }
+        Some(b'{') => (TmuxSplitAxis::SideBySide, b'}'),
+        Some(b'[') => (TmuxSplitAxis::Stacked, b']'),
+        _ => return Err(*pos),
+    };
+    *pos += 1;
+
+    let mut children = vec![parse_cell(bytes, pos)?];
+    loop {
+        match bytes.get(*pos) {
+            Some(b',') => {
+                *pos += 1;
+                children.push(parse_cell(bytes, pos)?);
+            }
+            Some(&b) if b == close => {
+                *pos += 1;
+                break;
+            }
+            _ => return Err(*pos),
+        }
+    }
+
+    Ok(TmuxLayout {
+        width,
+        height,
+        x,
+        y,
+        content: TmuxLayoutContent::Split { axis, children },
+    })
+}
+
+fn parse_number(bytes: &[u8], pos: &mut usize) -> Result<usize, usize> {
+    let start = *pos;
+    let mut value: usize = 0;
+    while let Some(&b) = bytes.get(*pos)
+        && b.is_ascii_digit()
+    {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(usize::from(b - b'0')))
+            .ok_or(start)?;
+        *pos += 1;
+    }
+    if *pos == start { Err(start) } else { Ok(value) }
+}
+
+fn expect(bytes: &[u8], pos: &mut usize, want: u8) -> Result<(), usize> {
+    if bytes.get(*pos) == Some(&want) {
+        *pos += 1;
+        Ok(())
+    } else {
+        Err(*pos)
+    }
+}
+
+/// What the reply to a queued command is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TmuxCommandKind {
+    /// The reply carries nothing the client needs (`send-keys`,
+    /// `resize-pane`, …); only errors are reported.
+    Ignore,
+    /// `list-windows` in [`LIST_WINDOWS_FORMAT`].
+    ListWindows,
+    /// `capture-pane` of the given pane's visible screen.
+    CapturePane(u32),
+}
+
+/// Cloneable handle for writing commands to a control-mode gateway.
+///
+/// Every command's kind is queued under the same lock that writes it, so the
+/// FIFO order of the queue always matches the order tmux sees the commands
+/// in, even when several threads (the GUI and each tmux pane's input
+/// forwarder) send concurrently.
+#[derive(Debug, Clone)]
+pub struct TmuxCommandSender {
+    write_tx: Sender<PtyWrite>,
+    pending: Arc<Mutex<VecDeque<TmuxCommandKind>>>,
+}
+
+impl TmuxCommandSender {
+    /// Wrap the gateway pane's PTY write channel.
+    #[must_use]
+    pub fn new(write_tx: Sender<PtyWrite>) -> Self {
+        Self {
+            write_tx,
+            pending: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Write one command line and queue the kind of its reply.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TmuxControlError::SendFailed`] if the gateway PTY channel
+    /// is closed.
+    // The lock must be held across the write as well as the push, or two
+    // senders could queue their kinds in the opposite order to their writes.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn send(&self, kind: TmuxCommandKind, command: &str) -> Result<(), TmuxControlError> {
+        let mut line = Vec::with_capacity(command.len() + 1);
+        line.extend_from_slice(command.as_bytes());
+        line.push(b'\n');
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        self.write_tx
+            .send(PtyWrite::Write(line))
+            .map_err(|e| TmuxControlError::SendFailed(e.to_string()))?;
+        pending.push_back(kind);
+        Ok(())
+    }
+
+    /// Forward input bytes to `pane` as `send-keys -H` commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TmuxControlError::SendFailed`] if the gateway PTY channel
+    /// is closed.
+    pub fn send_keys(&self, pane: u32, bytes: &[u8]) -> Result<(), TmuxControlError> {
+        for chunk in bytes.chunks(SEND_KEYS_CHUNK) {
+            self.send(TmuxCommandKind::Ignore, &send_keys_command(pane, chunk))?;
+        }
+        Ok(())
+    }
+
+    /// Pop the kind of the oldest unanswered command.
+    fn take_reply_kind(&self) -> Option<TmuxCommandKind> {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+    }
+}
+
+/// `send-keys -H` command writing `bytes` to `pane` verbatim.
+#[must_use]
+pub fn send_keys_command(pane: u32, bytes: &[u8]) -> String {
+    let mut command = format!("send-keys -t %{pane} -H");
+    for b in bytes {
+        let _ = write!(command, " {b:02x}");
+    }
+    command
+}
+
+/// `resize-pane` command giving `pane` the requested size in cells.
+#[must_use]
+pub fn resize_pane_command(pane: u32, columns: usize, rows: usize) -> String {
+    format!("resize-pane -t %{pane} -x {columns} -y {rows}")
+}
+
+/// `refresh-client -C` command telling tmux how large this client is.
+#[must_use]
+pub fn refresh_client_size_command(columns: usize, rows: usize) -> String {
+    format!("refresh-client -C {columns}x{rows}")
+}
+
+/// `split-window` command splitting `pane` along `axis`.
+#[must_use]
+pub fn split_window_command(pane: u32, axis: TmuxSplitAxis) -> String {
+    let flag = match axis {
+        TmuxSplitAxis::SideBySide => "-h",
+        TmuxSplitAxis::Stacked => "-v",
+    };
+    format!("split-window {flag} -t %{pane}")
+}
+
+/// `kill-pane` command closing `pane`.
+#[must_use]
+pub fn kill_pane_command(pane: u32) -> String {
+    format!("kill-pane -t %{pane}")
+}
+
+/// `new-window` command opening a window in the attached session.
+#[must_use]
+pub const fn new_window_command() -> &'static str {
+    "new-window"
+}
+
+/// `detach-client` command ending control mode while leaving the session
+/// running.
+#[must_use]
+pub const fn detach_command() -> &'static str {
+    "detach-client"
+}
+
+/// State of a tmux window as reported by `list-windows`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxWindow {
+    pub id: u32,
+    pub name: String,
+    pub layout: TmuxLayout,
+}
+
+/// An event produced by a control-mode gateway, oldest first.
+#[derive(Debug, Clone)]
+pub enum TmuxEvent {
+    /// `DCS 1000 p` arrived: this pane is now a tmux gateway.  `commands`
+    /// writes to it.
+    Started { commands: TmuxCommandSender },
+    /// A window exists with this layout and name.  Sent for every window on
+    /// attach and whenever `%window-add` reports a new one.
+    Window(TmuxWindow),
+    /// `%layout-change`: the panes or geometry of a window changed.
+    LayoutChange { window: u32, layout: TmuxLayout },
+    /// `%window-close` / `%unlinked-window-close`.
+    WindowClose { window: u32 },
+    /// `%window-renamed`.
+    WindowRenamed { window: u32, name: String },
+    /// `%window-pane-changed`: the active pane of a window changed.
+    ActivePaneChanged { window: u32, pane: u32 },
+    /// `%session-window-changed`: the session's current window changed.
+    ActiveWindowChanged { window: u32 },
+    /// `%output` (or `%extended-output`), already unescaped, or the
+    /// captured contents of a pane on attach.
+    Output { pane: u32, data: Vec<u8> },
+    /// A command failed (`%error` block); the lines are tmux's message.
+    CommandError(String),
+    /// `%exit`: tmux is about to leave control mode.
+    Exit { reason: Option<String> },
+    /// The closing ST arrived; the gateway is a normal pane again.
+    Ended,
+}
+
+/// A `%begin` block being collected.
+#[derive(Debug)]
+struct ReplyBlock {
+    /// Whether this client sent the command (the third `%begin` field).
+    ours: bool,
+    lines: Vec<String>,
+}
+
+/// Control-mode state for one gateway pane.
+#[derive(Debug)]
+pub struct TmuxControlSession {
+    commands: TmuxCommandSender,
+    block: Option<ReplyBlock>,
+    /// Whether the first `list-windows` reply has been handled; panes listed
+    /// there are captured so existing screens are not blank.
+    attached: bool,
+}
+
+impl TmuxControlSession {
+    /// Start a session on the gateway's PTY write channel and queue the
+    /// initial `list-windows` query.
+    #[must_use]
+    pub fn start(write_tx: Sender<PtyWrite>) -> Self {
+        let session = Self {
+            commands: TmuxCommandSender::new(write_tx),
+            block: None,
+            attached: false,
+        };
+        session.query_windows();
+        session
+    }
+
+    /// Handle for sending commands to this gateway.
+    #[must_use]
+    pub const fn commands(&self) -> &TmuxCommandSender {
+        &self.commands
+    }
+
+    fn query_windows(&self) {
+        let command = format!("list-windows -F '{LIST_WINDOWS_FORMAT}'");
+        if let Err(e) = self.commands.send(TmuxCommandKind::ListWindows, &command) {
+            error!("tmux: {e}");
+        }
+    }
+
+    /// Interpret one protocol line, appending any resulting events.
+    pub fn handle_line(&mut self, line: &[u8], events: &mut Vec<TmuxEvent>) {
+        let (word, rest) = split_word(line);
+
+        if self.block.is_some() {
+            if word == b"%end" || word == b"%error" {
+                if let Some(block) = self.block.take() {
+                    self.finish_block(block, word == b"%error", events);
+                }
+            } else if let Some(block) = &mut self.block {
+                block.lines.push(String::from_utf8_lossy(line).into_owned());
+            }
+            return;
+        }
+
+        match word {
+            b"%begin" => {
+                // `%begin <time> <number> <flags>`; flags bit 0 marks a
+                // command written by this client.
+                let ours = rest
+                    .rsplit(|&b| b == b' ')
+                    .next()
+                    .and_then(parse_u32)
+                    .is_some_and(|flags| flags & 1 == 1);
+                self.block = Some(ReplyBlock {
+                    ours,
+                    lines: Vec::new(),
+                });
+            }
+            b"%output" => {
+                let (pane, data) = split_word(rest);
+                if let Some(pane) = parse_id(pane, b'%') {
+                    events.push(TmuxEvent::Output {
+                        pane,
+                        data: unescape_output(data),
+                    });
+                }
+            }
+            b"%extended-output" => {
+                // `%extended-output %<pane> <age> … : <data>`
+                let (pane, rest) = split_word(rest);
+                if let (Some(pane), Some(at)) = (
+                    parse_id(pane, b'%'),
+                    rest.windows(3).position(|w| w == b" : "),
+                ) {
+                    events.push(TmuxEvent::Output {
+                        pane,
+                        data: unescape_output(&rest[at + 3..]),
+                    });
+                }
+            }
+            b"%layout-change" => {
+                let (window, rest) = split_word(rest);
+                let (layout, _) = split_word(rest);
+                if let Some(window) = parse_id(window, b'@') {
+                    match TmuxLayout::parse(&String::from_utf8_lossy(layout)) {
+                        Ok(layout) => events.push(TmuxEvent::LayoutChange { window, layout }),
+                        Err(e) => warn!("tmux: {e}"),
+                    }
+                }
+            }
+            b"%window-add" => self.query_windows(),
+            b"%window-close" | b"%unlinked-window-close" => {
+                if let Some(window) = parse_id(rest, b'@') {
+                    events.push(TmuxEvent::WindowClose { window });
+                }
+            }
+            b"%window-renamed" => {
+                let (window, name) = split_word(rest);
+                if let Some(window) = parse_id(window, b'@') {
+                    events.push(TmuxEvent::WindowRenamed {
+                        window,
+                        name: String::from_utf8_lossy(name).into_owned(),
+                    });
+                }
+            }
+            b"%window-pane-changed" => {
+                let (window, pane) = split_word(rest);
+                if let (Some(window), Some(pane)) = (parse_id(window, b'@'), parse_id(pane, b'%')) {
+                    events.push(TmuxEvent::ActivePaneChanged { window, pane });
+                }
+            }
+            b"%session-window-changed" => {
+                let (_session, window) = split_word(rest);
+                if let Some(window) = parse_id(window, b'@') {
+                    events.push(TmuxEvent::ActiveWindowChanged { window });
+                }
+            }
+            b"%exit" => events.push(TmuxEvent::Exit {
+                reason: (!rest.is_empty()).then(|| String::from_utf8_lossy(rest).into_owned()),
+            }),
+            _ => trace!("tmux: ignoring {:?}", String::from_utf8_lossy(line)),
+        }
+    }
+
+    fn finish_block(&mut self, block: ReplyBlock, failed: bool, events: &mut Vec<TmuxEvent>) {
+        if !block.ours {
+            // The reply to the command that started the client (or to one
+            // another client sent); nothing was queued for it.
+            return;
+        }
+        let Some(kind) = self.commands.take_reply_kind() else {
+            warn!("tmux: reply with no outstanding command");
+            return;
+        };
+        if failed {
+            events.push(TmuxEvent::CommandError(block.lines.join("\n")));
+            return;
+        }
+        match kind {
+            TmuxCommandKind::Ignore => {}
+            TmuxCommandKind::ListWindows => {
+                let windows: Vec<TmuxWindow> = block
+                    .lines
+                    .iter()
+                    .filter_map(|line| parse_window_line(line))
+                    .collect();
+                if !self.attached {
+                    self.attached = true;
+                    for pane in windows.iter().flat_map(|w| w.layout.pane_ids()) {
+                        let command = format!("capture-pane -p -e -t %{pane}");
+                        if let Err(e) = self
+                            .commands
+                            .send(TmuxCommandKind::CapturePane(pane), &command)
+                        {
+                            error!("tmux: {e}");
+                        }
+                    }
+                }
+                events.extend(windows.into_iter().map(TmuxEvent::Window));
+            }
+            TmuxCommandKind::CapturePane(pane) => {
+                let mut lines = block.lines;
+                while lines.last().is_some_and(|l| l.trim_end().is_empty()) {
+                    lines.pop();
+                }
+                // Home and clear first so the capture replaces whatever
+                // `%output` already drew instead of being appended to it.
+                let mut data = b"\x1b[H\x1b[2J".to_vec();
+                data.extend_from_slice(lines.join("\r\n").as_bytes());
+                events.push(TmuxEvent::Output { pane, data });
+            }
+        }
+    }
+}
+
+/// Parse one `list-windows` line in [`LIST_WINDOWS_FORMAT`].
+fn parse_window_line(line: &str) -> Option<TmuxWindow> {
+    let mut parts = line.splitn(3, ' ');
+    let id = parse_id(parts.next()?.as_bytes(), b'@')?;
+    let layout = match TmuxLayout::parse(parts.next()?) {
+        Ok(layout) => layout,
+        Err(e) => {
+            warn!("tmux: {e}");
+            return None;
+        }
+    };
+    Some(TmuxWindow {
+        id,
+        name: parts.next().unwrap_or_default().to_owned(),
+        layout,
+    })
+}
+
+/// Split off the first space-separated word.
+fn split_word(line: &[u8]) -> (&[u8], &[u8]) {
+    line.iter()
+        .position(|&b| b == b' ')
+        .map_or((line, &[][..]), |i| (&line[..i], &line[i + 1..]))
+}
+
+fn parse_u32(digits: &[u8]) -> Option<u32> {
+    std::str::from_utf8(digits).ok()?.parse().ok()
+}
+
+/// Parse a sigil-prefixed id such as `%3` or `@1`.
+fn parse_id(word: &[u8], sigil: u8) -> Option<u32> {
+    word.strip_prefix(&[sigil]).and_then(parse_u32)
+}
+
+/// Undo tmux's `%output` escaping: bytes below space and `\` arrive as a
+/// backslash and three octal digits.
+#[must_use]
+pub fn unescape_output(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] == b'\\'
+            && let Some(octal) = data.get(i + 1..i + 4)
+            && octal.iter().all(|b| (b'0'..=b'7').contains(b))
+        {
+            let value = octal
+                .iter()
+                .fold(0u16, |acc, b| acc * 8 + u16::from(b - b'0'));
+            if let Ok(byte) = u8::try_from(value) {
+                out.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(data[i]);
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn session() -> (TmuxControlSession, crossbeam_channel::Receiver<PtyWrite>) {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        (TmuxControlSession::start(tx), rx)
+    }
+
+    fn written(rx: &crossbeam_channel::Receiver<PtyWrite>) -> Vec<String> {
+        rx.try_iter()
+            .filter_map(|w| match w {
+                PtyWrite::Write(bytes) => Some(String::from_utf8(bytes).unwrap()),
+                PtyWrite::Resize(_) => None,
+            })
+            .collect()
+    }
+
+    fn feed(session: &mut TmuxControlSession, lines: &[&str]) -> Vec<TmuxEvent> {
+        let mut events = Vec::new();
+        for line in lines {
+            session.handle_line(line.as_bytes(), &mut events);
+        }
+        events
+    }
+
+    #[test]
+    fn parses_single_pane_layout() {
+        let layout = TmuxLayout::parse("b25d,80x24,0,0,3").unwrap();
+        assert_eq!(layout.content, TmuxLayoutContent::Pane(3));
+        assert_eq!((layout.width, layout.height), (80, 24));
+        assert!(layout.split_plan().is_empty());
+    }
+
+    #[test]
+    fn parses_layout_without_checksum() {
+        let layout = TmuxLayout::parse("80x24,0,0,3").unwrap();
+        assert_eq!(layout.pane_ids(), vec![3]);
+    }
+
+    #[test]
+    fn parses_nested_layout() {
+        let layout =
+            TmuxLayout::parse("5e09,80x24,0,0{40x24,0,0,1,39x24,41,0[39x12,41,0,2,39x11,41,13,3]}")
+                .unwrap();
+        assert_eq!(layout.pane_ids(), vec![1, 2, 3]);
+        assert_eq!(layout.first_pane(), Some(1));
+        let plan = layout.split_plan();
+        assert_eq!(plan.len(), 2);
+        assert_eq!((plan[0].target, plan[0].pane), (1, 2));
+        assert_eq!(plan[0].axis, TmuxSplitAxis::SideBySide);
+        assert!((plan[0].ratio - 40.0 / 79.0).abs() < f32::EPSILON);
+        assert_eq!((plan[1].target, plan[1].pane), (2, 3));
+        assert_eq!(plan[1].axis, TmuxSplitAxis::Stacked);
+        assert_eq!(layout.pane_size(3), Some((39, 11)));
+        assert_eq!(layout.pane_size(4), None);
+    }
+
+    #[test]
+    fn three_way_split_is_a_chain() {
+        let layout = TmuxLayout::parse("80x24,0,0{20x24,0,0,1,29x24,21,0,2,29x24,51,0,3}").unwrap();
+        let plan = layout.split_plan();
+        assert_eq!(
+            plan.iter().map(|s| (s.target, s.pane)).collect::<Vec<_>>(),
+            vec![(1, 2), (2, 3)]
+        );
+        assert!((plan[0].ratio - 20.0 / 78.0).abs() < f32::EPSILON);
+        assert!((plan[1].ratio - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn rejects_malformed_layout() {
+        assert!(matches!(
+            TmuxLayout::parse("80x24,0,0{40x24,0,0,1"),
+            Err(TmuxControlError::InvalidLayout { .. })
+        ));
+        assert!(TmuxLayout::parse("80x24,0,0,1trailing").is_err());
+    }
+
+    #[test]
+    fn unescapes_octal_output() {
+        assert_eq!(unescape_output(b"a\\015\\012\\134b"), b"a\r\n\\b".to_vec());
+        assert_eq!(unescape_output(b"\\9x"), b"\\9x".to_vec());
+    }
+
+    #[test]
+    fn send_keys_is_hex_encoded_and_chunked() {
+        assert_eq!(send_keys_command(2, b"ls\r"), "send-keys -t %2 -H 6c 73 0d");
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let sender = TmuxCommandSender::new(tx);
+        sender.send_keys(1, &[b'a'; SEND_KEYS_CHUNK + 1]).unwrap();
+        assert_eq!(written(&rx).len(), 2);
+    }
+
+    #[test]
+    fn start_queries_windows() {
+        let (_session, rx) = session();
+        assert_eq!(
+            written(&rx),
+            vec![format!("list-windows -F '{LIST_WINDOWS_FORMAT}'\n")]
+        );
+    }
+
+    #[test]
+    fn foreign_blocks_do_not_consume_replies() {
+        let (mut session, rx) = session();
+        let _ = written(&rx);
+        let events = feed(
+            &mut session,
+            &[
+                "%begin 1 1 0",
+                "%end 1 1 0",
+                "%begin 1 2 1",
+                "@1 80x24,0,0,5 zsh",
+                "%end 1 2 1",
+            ],
+        );
+        assert!(matches!(
+            &events[..],
+            [TmuxEvent::Window(TmuxWindow { id: 1, name, .. })] if name == "zsh"
+        ));
+        // The first listing captures every pane it names.
+        assert_eq!(written(&rx), vec!["capture-pane -p -e -t %5\n".to_owned()]);
+
+        let events = feed(&mut session, &["%begin 1 3 1", "$ ls", "", "%end 1 3 1"]);
+        assert!(matches!(
+            &events[..],
+            [TmuxEvent::Output { pane: 5, data }] if data == b"\x1b[H\x1b[2J$ ls"
+        ));
+    }
+
+    #[test]
+    fn error_block_is_reported() {
+        let (mut session, _rx) = session();
+        let events = feed(
+            &mut session,
+            &["%begin 1 2 1", "no such window", "%error 1 2 1"],
+        );
+        assert!(matches!(
+            &events[..],
+            [TmuxEvent::CommandError(message)] if message == "no such window"
+        ));
+    }
+
+    #[test]
+    fn notifications_become_events() {
+        let (mut session, rx) = session();
+        let _ = written(&rx);
+        let events = feed(
+            &mut session,
+            &[
+                "%output %1 hi\\015\\012",
+                "%layout-change @2 1234,80x24,0,0,4 1234,80x24,0,0,4 *",
+                "%window-renamed @2 my window",
+                "%window-pane-changed @2 %4",
+                "%session-window-changed $0 @2",
+                "%window-close @3",
+                "%exit detached",
+            ],
+        );
+        assert_eq!(events.len(), 7);
+        assert!(matches!(&events[0], TmuxEvent::Output { pane: 1, data } if data == b"hi\r\n"));
+        assert!(matches!(
+            &events[1],
+            TmuxEvent::LayoutChange { window: 2, .. }
+        ));
+        assert!(
+            matches!(&events[2], TmuxEvent::WindowRenamed { window: 2, name } if name == "my window")
+        );
+        assert!(matches!(
+            &events[3],
+            TmuxEvent::ActivePaneChanged { window: 2, pane: 4 }
+        ));
+        assert!(matches!(
+            &events[4],
+            TmuxEvent::ActiveWindowChanged { window: 2 }
+        ));
+        assert!(matches!(&events[5], TmuxEvent::WindowClose { window: 3 }));
+        assert!(matches!(&events[6], TmuxEvent::Exit { reason: Some(r) } if r == "detached"));
+    }
+
+    #[test]
+    fn window_add_requeries_windows() {
+        let (mut session, rx) = session();
+        let _ = written(&rx);
+        let events = feed(&mut session, &["%window-add @7"]);
+        assert!(events.is_empty());
+        assert_eq!(
+            written(&rx),
+            vec![format!("list-windows -F '{LIST_WINDOWS_FORMAT}'\n")]
+        );
+    }
+}
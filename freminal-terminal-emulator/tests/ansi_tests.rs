@@ -3,6 +3,7 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
+use freminal_common::buffer_states::charset::{Charset, GSet};
 use freminal_common::buffer_states::terminal_output::TerminalOutput;
 use freminal_common::{
     buffer_states::window_manipulation::WindowManipulation, cursor::CursorVisualStyle,
@@ -107,29 +108,20 @@ fn display_exhaustive_variants_short() {
         TerminalOutput::ScreenAlignmentTest,
         TerminalOutput::CharsetDefault,
         TerminalOutput::CharsetUTF8,
-        TerminalOutput::CharsetG0,
-        TerminalOutput::CharsetG1,
+        TerminalOutput::DesignateCharset {
+            set: GSet::G1,
+            charset: Charset::German,
+        },
+        TerminalOutput::CharsetG0AsGL,
+        TerminalOutput::CharsetG1AsGL,
         TerminalOutput::CharsetG1AsGR,
-        TerminalOutput::CharsetG2,
         TerminalOutput::CharsetG2AsGR,
         TerminalOutput::CharsetG2AsGL,
-        TerminalOutput::CharsetG3,
         TerminalOutput::CharsetG3AsGR,
         TerminalOutput::CharsetG3AsGL,
+        TerminalOutput::SingleShiftG2,
+        TerminalOutput::SingleShiftG3,
         TerminalOutput::DecSpecial,
-        TerminalOutput::CharsetUK,
-        TerminalOutput::CharsetUS,
-        TerminalOutput::CharsetUSASCII,
-        TerminalOutput::CharsetDutch,
-        TerminalOutput::CharsetFinnish,
-        TerminalOutput::CharsetFrench,
-        TerminalOutput::CharsetFrenchCanadian,
-        TerminalOutput::CharsetGerman,
-        TerminalOutput::CharsetItalian,
-        TerminalOutput::CharsetNorwegianDanish,
-        TerminalOutput::CharsetSpanish,
-        TerminalOutput::CharsetSwedish,
-        TerminalOutput::CharsetSwiss,
         TerminalOutput::SaveCursor,
         TerminalOutput::RestoreCursor,
         TerminalOutput::CursorToLowerLeftCorner,
@@ -0,0 +1,112 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! `ConEmu` OSC 9 sub-commands, from the sequence to the pane's state.
+//!
+//! ## Coverage
+//!
+//! - **OSC 9 ; 4 ; st ; pr** — sets normal, error, indeterminate or paused
+//!   progress, or removes it (`st = 0`)
+//! - **OSC 133 ; D** — the end of the command clears its progress
+//! - **OSC 9 ; 9 ; cwd** — reports the working directory
+//! - **OSC 9 ; n** — the other sub-commands are consumed, never shown as
+//!   notifications
+//! - **RIS** — clears the progress
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::osc_conemu::{ProgressState, TaskProgress};
+use vttest_common::VtTestHelper;
+
+fn progress(h: &VtTestHelper) -> Option<TaskProgress> {
+    h.state.handler.progress()
+}
+
+const fn at(state: ProgressState, percent: u8) -> Option<TaskProgress> {
+    Some(TaskProgress { state, percent })
+}
+
+/// Each state is stored with its percentage; `st = 0` removes it.
+#[test]
+fn progress_states_are_tracked() {
+    let mut h = VtTestHelper::new_default();
+    assert_eq!(progress(&h), None);
+
+    h.feed_str("\x1b]9;4;1;25\x1b\\");
+    assert_eq!(progress(&h), at(ProgressState::Normal, 25));
+
+    h.feed_str("\x1b]9;4;3\x07");
+    assert_eq!(progress(&h).unwrap().state, ProgressState::Indeterminate);
+
+    h.feed_str("\x1b]9;4;0\x07");
+    assert_eq!(progress(&h), None);
+}
+
+/// Error and paused reports without a percentage keep the previous one.
+#[test]
+fn error_and_paused_keep_the_percentage() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]9;4;1;60\x07\x1b]9;4;2\x07");
+    assert_eq!(progress(&h), at(ProgressState::Error, 60));
+
+    h.feed_str("\x1b]9;4;4;70\x07");
+    assert_eq!(progress(&h), at(ProgressState::Paused, 70));
+
+    h.feed_str("\x1b]9;4;1\x07");
+    assert_eq!(progress(&h), at(ProgressState::Normal, 0));
+}
+
+/// A malformed report leaves the progress unchanged.
+#[test]
+fn malformed_progress_is_ignored() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]9;4;1;10\x07\x1b]9;4;7;50\x07");
+    assert_eq!(progress(&h), at(ProgressState::Normal, 10));
+}
+
+/// The end of the command that reported progress clears it.
+#[test]
+fn command_finished_clears_progress() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]133;A;freminal=1;fid=t1\x07\x1b]133;C;freminal=1;fid=t1\x07");
+    h.feed_str("\x1b]9;4;1;90\x07");
+    assert!(progress(&h).is_some());
+
+    h.feed_str("\x1b]133;D;0;freminal=1;fid=t1\x07");
+    assert_eq!(progress(&h), None);
+}
+
+/// `9;9` sets the working directory, quoted or not.
+#[test]
+fn working_directory_is_reported() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]9;9;\"/home/user/src\"\x07");
+    assert_eq!(
+        h.state.handler.current_working_directory(),
+        Some("/home/user/src")
+    );
+}
+
+/// Sub-commands are consumed rather than turned into notifications, while a
+/// plain OSC 9 body still is one.
+#[test]
+fn sub_commands_are_not_notifications() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]9;4;1;5\x07\x1b]9;2;hello\x07\x1b]9;12\x07");
+    assert!(h.state.window_commands.is_empty());
+
+    h.feed_str("\x1b]9;Build finished\x07");
+    assert_eq!(h.state.window_commands.len(), 1);
+}
+
+/// RIS removes the progress.
+#[test]
+fn reset_clears_progress() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]9;4;1;50\x07\x1bc");
+    assert_eq!(progress(&h), None);
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The DEC locator, from the control sequences to the DECLRP reports.
+//!
+//! ## Coverage
+//!
+//! - **DECELR** — `CSI Ps ; Pu ' z` turns reporting on, off or on for one
+//!   report, in cell or pixel units
+//! - **DECSLE** — `CSI Pm ' {` selects button-down and button-up reports
+//! - **DECRQLP** — `CSI Ps ' |` answers with the current position, or
+//!   `CSI 0 & w` when the pointer is not over the grid
+//! - **DECEFR** — `CSI Pt ; Pl ; Pb ; Pr ' w` reports once when the pointer
+//!   leaves the rectangle
+//!
+//! Pointer input is fed straight to the handler as the GUI would send it.
+//! Report coordinates are **1-indexed**.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::locator::{LocatorButton, LocatorInput, LocatorPosition};
+use vttest_common::VtTestHelper;
+
+/// The pointer over cell `(col, row)` (0-indexed), 10×20 pixels per cell.
+const fn at(col: usize, row: usize) -> LocatorPosition {
+    LocatorPosition {
+        col,
+        row,
+        x_px: col * 10 + 3,
+        y_px: row * 20 + 5,
+    }
+}
+
+fn move_to(h: &mut VtTestHelper, col: usize, row: usize) {
+    h.state
+        .handler
+        .handle_locator_input(LocatorInput::Moved(at(col, row)));
+}
+
+fn click(h: &mut VtTestHelper, button: LocatorButton, pressed: bool, col: usize, row: usize) {
+    h.state.handler.handle_locator_input(LocatorInput::Button {
+        button,
+        pressed,
+        position: at(col, row),
+    });
+}
+
+/// A helper with cell reporting on and the pointer over column 5, row 3.
+fn enabled_helper() -> VtTestHelper {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1'z");
+    move_to(&mut h, 4, 2);
+    let _ = h.drain_pty_writes();
+    h
+}
+
+/// DECRQLP answers with the pointer position in cells or pixels.
+#[test]
+fn request_reports_the_position() {
+    let mut h = enabled_helper();
+    h.feed_str("\x1b['|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[1;0;3;5;1&w");
+
+    h.feed_str("\x1b[1;1'z\x1b['|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[1;0;46;44;1&w");
+}
+
+/// With reporting off nothing is sent, and the GUI is told not to send input.
+#[test]
+fn disabled_locator_is_silent() {
+    let mut h = VtTestHelper::new_default();
+    assert!(!h.state.handler.locator_enabled());
+    h.feed_str("\x1b['|");
+    move_to(&mut h, 1, 1);
+    click(&mut h, LocatorButton::Left, true, 1, 1);
+    assert!(h.drain_pty_writes_concatenated().is_empty());
+
+    h.feed_str("\x1b[1'z");
+    assert!(h.state.handler.locator_enabled());
+    h.feed_str("\x1b['z");
+    assert!(!h.state.handler.locator_enabled());
+}
+
+/// A request before any pointer input over the grid reports "unavailable".
+#[test]
+fn request_off_the_grid_is_unavailable() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1'z\x1b['|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0&w");
+
+    move_to(&mut h, 0, 0);
+    h.state.handler.handle_locator_input(LocatorInput::Left);
+    h.feed_str("\x1b['|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0&w");
+}
+
+/// DECSLE picks which button transitions are reported; the button mask
+/// tracks what is held.
+#[test]
+fn selected_button_events_are_reported() {
+    let mut h = enabled_helper();
+    click(&mut h, LocatorButton::Left, true, 4, 2);
+    assert!(h.drain_pty_writes_concatenated().is_empty());
+
+    h.feed_str("\x1b[1;3'{");
+    click(&mut h, LocatorButton::Left, true, 4, 2);
+    click(&mut h, LocatorButton::Right, true, 6, 2);
+    click(&mut h, LocatorButton::Right, false, 6, 2);
+    assert_eq!(
+        h.drain_pty_writes_concatenated(),
+        b"\x1b[2;4;3;5;1&w\x1b[6;5;3;7;1&w\x1b[7;4;3;7;1&w"
+    );
+
+    h.feed_str("\x1b[4'{");
+    click(&mut h, LocatorButton::Left, false, 6, 2);
+    assert!(h.drain_pty_writes_concatenated().is_empty());
+}
+
+/// One-shot reporting turns itself off after the first report.
+#[test]
+fn one_shot_reports_once() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[2'z");
+    move_to(&mut h, 0, 0);
+    h.feed_str("\x1b['|\x1b['|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[1;0;1;1;1&w");
+    assert!(!h.state.handler.locator_enabled());
+}
+
+/// The filter rectangle fires once, when the pointer first moves outside it.
+#[test]
+fn filter_rectangle_reports_on_exit() {
+    let mut h = enabled_helper();
+    h.feed_str("\x1b[2;3;4;8'w");
+    move_to(&mut h, 7, 3);
+    assert!(h.drain_pty_writes_concatenated().is_empty());
+
+    move_to(&mut h, 8, 3);
+    move_to(&mut h, 9, 3);
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[10;0;4;9;1&w");
+}
+
+/// Omitted edges are the pointer's own row and column, so any move fires.
+#[test]
+fn filter_edges_default_to_the_pointer() {
+    let mut h = enabled_helper();
+    h.feed_str("\x1b['w");
+    move_to(&mut h, 4, 2);
+    assert!(h.drain_pty_writes_concatenated().is_empty());
+
+    move_to(&mut h, 4, 3);
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[10;0;4;5;1&w");
+}
+
+/// A filter armed while the pointer is already outside reports at once, and
+/// leaving the grid reports the last position seen on it.
+#[test]
+fn filter_reports_immediately_or_on_leaving_the_grid() {
+    let mut h = enabled_helper();
+    h.feed_str("\x1b[5;5;6;6'w");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[10;0;3;5;1&w");
+
+    h.feed_str("\x1b[1;1;9;9'w");
+    h.state.handler.handle_locator_input(LocatorInput::Left);
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[10;0;3;5;1&w");
+}
+
+/// RIS turns the locator off.
+#[test]
+fn reset_disables_the_locator() {
+    let mut h = enabled_helper();
+    h.feed_str("\x1bc");
+    assert!(!h.state.handler.locator_enabled());
+}
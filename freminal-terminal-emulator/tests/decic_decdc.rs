@@ -0,0 +1,98 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DECIC and DECDC column insertion and deletion, end to end.
+//!
+//! ## Coverage
+//!
+//! - **DECIC** — `CSI Pn ' }` inserts blank columns at the cursor on every
+//!   row of the scrolling region
+//! - **DECDC** — `CSI Pn ' ~` deletes columns at the cursor, bringing blank
+//!   columns in at the right margin
+//! - **Margins** — DECSTBM and DECSLRM bound the shift; a cursor outside the
+//!   left/right margins makes both a no-op
+//! - **Wide characters** — a glyph cut by the cursor column or the margin is
+//!   blanked
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use vttest_common::VtTestHelper;
+
+/// A 10×4 screen with `ABCDEFGHIJ` on every row.
+fn filled_helper() -> VtTestHelper {
+    let mut h = VtTestHelper::new(10, 4);
+    h.feed_str("ABCDEFGHIJ\r\nABCDEFGHIJ\r\nABCDEFGHIJ\r\nABCDEFGHIJ");
+    h
+}
+
+/// DECIC shifts every row of the screen, not only the cursor row.
+#[test]
+fn decic_inserts_on_every_row() {
+    let mut h = filled_helper();
+    h.feed_str("\x1b[2;3H\x1b[2'}");
+
+    for row in 0..4 {
+        h.assert_row(row, "AB  CDEFGH");
+    }
+    h.assert_cursor_pos(2, 1);
+}
+
+/// DECDC with no parameter deletes one column.
+#[test]
+fn decdc_defaults_to_one_column() {
+    let mut h = filled_helper();
+    h.feed_str("\x1b[1;3H\x1b['~");
+
+    for row in 0..4 {
+        h.assert_row(row, "ABDEFGHIJ");
+    }
+}
+
+/// The shift stays inside the scrolling region and left/right margins.
+#[test]
+fn shift_is_bounded_by_the_margins() {
+    let mut h = filled_helper();
+    h.feed_str("\x1b[2;3r\x1b[?69h\x1b[3;7s\x1b[2;4H\x1b['}");
+
+    h.assert_row(0, "ABCDEFGHIJ");
+    h.assert_row(1, "ABC DEFHIJ");
+    h.assert_row(2, "ABC DEFHIJ");
+    h.assert_row(3, "ABCDEFGHIJ");
+
+    h.feed_str("\x1b[2;4H\x1b[9'~");
+    h.assert_row(1, "ABC    HIJ");
+    h.assert_row(2, "ABC    HIJ");
+}
+
+/// With the cursor left of the left margin nothing moves.
+#[test]
+fn cursor_outside_the_margins_is_ignored() {
+    let mut h = filled_helper();
+    h.feed_str("\x1b[?69h\x1b[3;7s\x1b[1;1H\x1b['}\x1b['~");
+
+    for row in 0..4 {
+        h.assert_row(row, "ABCDEFGHIJ");
+    }
+}
+
+/// A wide character the cut goes through is blanked rather than split.
+#[test]
+fn wide_characters_at_the_cut_are_blanked() {
+    let mut h = VtTestHelper::new(10, 2);
+    h.feed_str("AあいB");
+
+    // Column 3 is the second half of あ.
+    h.feed_str("\x1b[1;3H\x1b['~");
+    h.assert_row(0, "A いB");
+
+    // Seven columns push い half past the right edge.
+    h.feed_str("\x1b[1;1H\x1b[7'}");
+    h.assert_row(0, "       A");
+}
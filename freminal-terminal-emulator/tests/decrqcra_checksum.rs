@@ -0,0 +1,96 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DECRQCRA rectangle checksums and the XTCHECKSUM options, checked the way
+//! esctest reads the screen back.
+//!
+//! ## Coverage
+//!
+//! - **DECRQCRA** — `CSI Pi ; Pg ; Pt ; Pl ; Pb ; Pr * y` answers
+//!   `DCS Pi ! ~ xxxx ST` once allowed, and nothing before
+//! - **XTCHECKSUM** — `CSI Ps # y` bits for the positive sum, attributes and
+//!   blanks
+//! - **DECOM** — the rectangle is relative to the margins
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use vttest_common::VtTestHelper;
+
+/// A helper with checksum reports allowed and any startup output drained.
+fn checksum_helper() -> VtTestHelper {
+    let mut h = VtTestHelper::new_default();
+    h.state.handler.set_allow_checksum_reports(true);
+    let _ = h.drain_pty_writes();
+    h
+}
+
+/// The reply to `seq`, as a string.
+fn reply(h: &mut VtTestHelper, seq: &str) -> String {
+    h.feed_str(seq);
+    String::from_utf8(h.drain_pty_writes_concatenated()).unwrap()
+}
+
+/// Without the opt-in the request goes unanswered.
+#[test]
+fn checksum_is_not_reported_by_default() {
+    let mut h = VtTestHelper::new_default();
+    let _ = h.drain_pty_writes();
+    h.feed_str("abc\x1b[1;1;1;1;1;3*y");
+    assert!(h.drain_pty_writes_concatenated().is_empty());
+}
+
+/// The default checksum is the negated character sum; the id is echoed.
+#[test]
+fn checksum_of_text() {
+    let mut h = checksum_helper();
+    h.feed_str("abc");
+
+    // 'a' + 'b' + 'c' = 0x126
+    assert_eq!(reply(&mut h, "\x1b[42;1;1;1;1;3*y"), "\x1bP42!~FEDA\x1b\\");
+    assert_eq!(
+        reply(&mut h, "\x1b[1#y\x1b[42;1;1;1;1;3*y"),
+        "\x1bP42!~0126\x1b\\"
+    );
+}
+
+/// Bold adds 0x80 per cell unless XTCHECKSUM bit 1 is set.
+#[test]
+fn checksum_includes_attributes() {
+    let mut h = checksum_helper();
+    h.feed_str("\x1b[1mab\x1b[m\x1b[1#y");
+
+    assert_eq!(reply(&mut h, "\x1b[1;1;1;1;1;2*y"), "\x1bP1!~01C3\x1b\\");
+    assert_eq!(
+        reply(&mut h, "\x1b[3#y\x1b[1;1;1;1;1;2*y"),
+        "\x1bP1!~00C3\x1b\\"
+    );
+}
+
+/// Blank cells count only with XTCHECKSUM bit 2.
+#[test]
+fn checksum_blanks() {
+    let mut h = checksum_helper();
+    assert_eq!(
+        reply(&mut h, "\x1b[1#y\x1b[1;1;1;1;2;2*y"),
+        "\x1bP1!~0000\x1b\\"
+    );
+    assert_eq!(
+        reply(&mut h, "\x1b[5#y\x1b[1;1;1;1;2;2*y"),
+        "\x1bP1!~0080\x1b\\"
+    );
+}
+
+/// Under DECOM the rectangle is relative to the scrolling region.
+#[test]
+fn checksum_is_origin_relative() {
+    let mut h = checksum_helper();
+    h.feed_str("\x1b[3;1HX\x1b[3;10r\x1b[?6h\x1b[1#y");
+    assert_eq!(reply(&mut h, "\x1b[1;1;1;1;1;1*y"), "\x1bP1!~0058\x1b\\");
+}
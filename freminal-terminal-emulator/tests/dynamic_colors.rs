@@ -0,0 +1,133 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Dynamic and special colors, from the sequence to the reply.
+//!
+//! ## Coverage
+//!
+//! - **OSC 17 / 19** — highlight background / foreground set and query;
+//!   queries default to the theme's selection colors
+//! - **OSC 117 / 119** — highlight resets
+//! - **OSC 13 / 14** — mouse pointer colors, stored and reported; **OSC 113**
+//!   reset
+//! - **OSC 5** — special color set and query; queries default to the text
+//!   foreground
+//! - **OSC 105** — reset one special color or all of them
+//! - **RIS** — clears every override
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::colors::SpecialColor;
+use vttest_common::VtTestHelper;
+
+fn reply(h: &mut VtTestHelper, query: &str) -> String {
+    h.feed_str(query);
+    String::from_utf8(h.drain_pty_writes_concatenated()).unwrap()
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> String {
+    format!("rgb:{r:02x}/{g:02x}/{b:02x}")
+}
+
+/// Highlight queries report the theme's selection colors until set.
+#[test]
+fn highlight_queries_default_to_the_theme() {
+    let mut h = VtTestHelper::new_default();
+    let theme = h.state.handler.theme();
+    assert_eq!(
+        reply(&mut h, "\x1b]17;?\x07"),
+        format!("\x1b]17;{}\x1b\\", rgb(theme.selection_bg))
+    );
+    assert_eq!(
+        reply(&mut h, "\x1b]19;?\x07"),
+        format!("\x1b]19;{}\x1b\\", rgb(theme.selection_fg))
+    );
+}
+
+/// Highlight colors are set, reported and reset.
+#[test]
+fn highlight_colors_set_query_and_reset() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]17;#102030\x07\x1b]19;rgb:ff/ee/dd\x1b\\");
+    assert_eq!(
+        h.state.handler.highlight_bg_override(),
+        Some((0x10, 0x20, 0x30))
+    );
+    assert_eq!(
+        h.state.handler.highlight_fg_override(),
+        Some((0xff, 0xee, 0xdd))
+    );
+    assert_eq!(reply(&mut h, "\x1b]17;?\x07"), "\x1b]17;rgb:10/20/30\x1b\\");
+
+    h.feed_str("\x1b]117\x07");
+    assert_eq!(h.state.handler.highlight_bg_override(), None);
+    h.feed_str("\x1b]119\x07");
+    assert_eq!(h.state.handler.highlight_fg_override(), None);
+}
+
+/// Mouse pointer colors are only reported back.
+#[test]
+fn mouse_colors_are_reported() {
+    let mut h = VtTestHelper::new_default();
+    let theme = h.state.handler.theme();
+    assert_eq!(
+        reply(&mut h, "\x1b]14;?\x07"),
+        format!("\x1b]14;{}\x1b\\", rgb(theme.background))
+    );
+
+    h.feed_str("\x1b]13;#abcdef\x07");
+    assert_eq!(reply(&mut h, "\x1b]13;?\x07"), "\x1b]13;rgb:ab/cd/ef\x1b\\");
+    h.feed_str("\x1b]113\x07");
+    assert_eq!(
+        reply(&mut h, "\x1b]13;?\x07"),
+        format!("\x1b]13;{}\x1b\\", rgb(theme.foreground))
+    );
+}
+
+/// Special colors default to the text foreground, follow OSC 5 and are
+/// reset one at a time or all together by OSC 105.
+#[test]
+fn special_colors_set_query_and_reset() {
+    let mut h = VtTestHelper::new_default();
+    let (r, g, b) = h.state.handler.theme().foreground;
+    let default = format!(
+        "rgb:{:04x}/{:04x}/{:04x}",
+        u16::from(r) * 257,
+        u16::from(g) * 257,
+        u16::from(b) * 257
+    );
+    assert_eq!(
+        reply(&mut h, "\x1b]5;1;?\x07"),
+        format!("\x1b]5;1;{default}\x1b\\")
+    );
+
+    h.feed_str("\x1b]5;0;#ff0000\x07\x1b]5;3;#0000ff\x07");
+    let special = h.state.handler.special_colors();
+    assert_eq!(special.get(SpecialColor::Bold), Some((0xff, 0, 0)));
+    assert_eq!(special.get(SpecialColor::Reverse), Some((0, 0, 0xff)));
+    assert_eq!(
+        reply(&mut h, "\x1b]5;0;?\x07"),
+        "\x1b]5;0;rgb:ffff/0000/0000\x1b\\"
+    );
+
+    h.feed_str("\x1b]105;0\x07");
+    let special = h.state.handler.special_colors();
+    assert_eq!(special.get(SpecialColor::Bold), None);
+    assert_eq!(special.get(SpecialColor::Reverse), Some((0, 0, 0xff)));
+
+    h.feed_str("\x1b]105\x07");
+    assert!(h.state.handler.special_colors().is_empty());
+}
+
+/// RIS clears the highlight and special colors.
+#[test]
+fn reset_clears_overrides() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]17;#102030\x07\x1b]5;2;#00ff00\x07\x1bc");
+    assert_eq!(h.state.handler.highlight_bg_override(), None);
+    assert!(h.state.handler.special_colors().is_empty());
+}
@@ -0,0 +1,164 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! `iTerm2` OSC 1337 text commands, from the sequence to the pane's state.
+//!
+//! ## Coverage
+//!
+//! - **OSC 1337 ; SetUserVar=name=base64** — sets or (empty value) removes
+//!   a user variable; at most 256 variables of up to 4096 bytes are kept
+//! - **OSC 1337 ; SetBadgeFormat=base64** — sets the badge, with
+//!   `\(user.name)` expanded as the variables change
+//! - **OSC 1337 ; CurrentDir=path** — sets the working directory
+//! - **OSC 1337 ; SetMark** — records the cursor row as a jump target
+//! - **OSC 1337 ; RequestAttention=** — queued for the GUI
+//! - **OSC 1337 ; ReportCellSize** — answered with the cell size in pixels
+//! - **OSC 1337 ; ClearScrollback** — erases the scrollback, keeping the
+//!   screen
+//! - **RIS** — clears the variables and the badge
+//!
+//! Rows are **0-indexed**.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::base64::encode;
+use freminal_common::buffer_states::osc_iterm2::AttentionRequest;
+use freminal_common::buffer_states::window_manipulation::WindowManipulation;
+use vttest_common::VtTestHelper;
+
+fn set_user_var(name: &str, value: &str) -> String {
+    format!(
+        "\x1b]1337;SetUserVar={name}={}\x07",
+        encode(value.as_bytes())
+    )
+}
+
+fn badge(h: &VtTestHelper) -> Option<String> {
+    h.state.handler.badge().map(|b| b.to_string())
+}
+
+/// Variables are set, replaced and removed.
+#[test]
+fn user_vars_are_tracked() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str(&set_user_var("host", "db1"));
+    h.feed_str(&set_user_var("env", "prod"));
+    h.feed_str(&set_user_var("host", "db2"));
+    let vars = h.state.handler.user_vars();
+    assert_eq!(vars.get("host").map(String::as_str), Some("db2"));
+    assert_eq!(vars.len(), 2);
+
+    h.feed_str(&set_user_var("env", ""));
+    assert!(!h.state.handler.user_vars().contains_key("env"));
+}
+
+/// Past 256 variables new names are ignored, though existing ones can
+/// still change, and values over 4096 bytes are ignored.
+#[test]
+fn user_vars_are_capped() {
+    let mut h = VtTestHelper::new_default();
+    for i in 0..256 {
+        h.feed_str(&set_user_var(&format!("v{i}"), "x"));
+    }
+    h.feed_str(&set_user_var("extra", "x"));
+    h.feed_str(&set_user_var("v0", "y"));
+    let vars = h.state.handler.user_vars();
+    assert_eq!(vars.len(), 256);
+    assert!(!vars.contains_key("extra"));
+    assert_eq!(vars.get("v0").map(String::as_str), Some("y"));
+
+    h.feed_str(&set_user_var("v1", &"z".repeat(4097)));
+    assert_eq!(
+        h.state.handler.user_vars().get("v1").map(String::as_str),
+        Some("x")
+    );
+    h.feed_str(&set_user_var("v1", &"z".repeat(4096)));
+    assert_eq!(h.state.handler.user_vars()["v1"].len(), 4096);
+}
+
+/// The badge follows its variables; an empty format removes it.
+#[test]
+fn badge_expands_user_vars() {
+    let mut h = VtTestHelper::new_default();
+    let format = encode(b"\\(user.host)");
+    h.feed_str(&format!("\x1b]1337;SetBadgeFormat={format}\x1b\\"));
+    assert_eq!(badge(&h), None);
+
+    h.feed_str(&set_user_var("host", "db1"));
+    assert_eq!(badge(&h).as_deref(), Some("db1"));
+
+    h.feed_str("\x1b]1337;SetBadgeFormat=\x07");
+    assert_eq!(badge(&h), None);
+}
+
+/// `CurrentDir=` sets the working directory, `;` included.
+#[test]
+fn current_dir_sets_working_directory() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]1337;CurrentDir=/srv/a;b\x07");
+    assert_eq!(
+        h.state.handler.current_working_directory(),
+        Some("/srv/a;b")
+    );
+}
+
+/// A mark is recorded on the cursor row.
+#[test]
+fn set_mark_records_the_cursor_row() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("a\r\nb\r\n\x1b]1337;SetMark\x07");
+    assert_eq!(h.state.handler.buffer().prompt_rows(), &[2]);
+}
+
+/// Attention requests are forwarded to the GUI.
+#[test]
+fn request_attention_is_forwarded() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]1337;RequestAttention=yes\x07\x1b]1337;RequestAttention=no\x07");
+    assert_eq!(
+        h.state.window_commands,
+        vec![
+            WindowManipulation::RequestAttention(AttentionRequest::Start),
+            WindowManipulation::RequestAttention(AttentionRequest::Stop),
+        ]
+    );
+}
+
+/// The cell size is reported as height, width and a scale of 1.
+#[test]
+fn report_cell_size_answers_in_pixels() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]1337;ReportCellSize\x07");
+    assert_eq!(
+        String::from_utf8(h.drain_pty_writes_concatenated()).unwrap(),
+        "\x1b]1337;ReportCellSize=16.0;8.0;1.0\x1b\\"
+    );
+}
+
+/// `ClearScrollback` drops the rows above the screen and keeps the screen.
+#[test]
+fn clear_scrollback_keeps_the_screen() {
+    let mut h = VtTestHelper::new(10, 3);
+    h.feed_str("1\r\n2\r\n3\r\n4\r\n5");
+    assert!(h.state.handler.buffer().rows().len() > 3);
+
+    h.feed_str("\x1b]1337;ClearScrollback\x07");
+    assert_eq!(h.state.handler.buffer().rows().len(), 3);
+    h.assert_row(0, "3");
+    h.assert_row(2, "5");
+}
+
+/// RIS clears the variables and the badge.
+#[test]
+fn reset_clears_user_vars_and_badge() {
+    let mut h = VtTestHelper::new_default();
+    let format = encode(b"\\(user.host)");
+    h.feed_str(&set_user_var("host", "db1"));
+    h.feed_str(&format!("\x1b]1337;SetBadgeFormat={format}\x07\x1bc"));
+    assert!(h.state.handler.user_vars().is_empty());
+    assert_eq!(badge(&h), None);
+}
@@ -0,0 +1,144 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DECRQPSR presentation state reports and DECRSPS restores.
+//!
+//! ## Coverage
+//!
+//! - **DECCIR** — `CSI 1 $ w` answers `DCS 1 $ u … ST` with the cursor
+//!   position, rendition, DECSCA, DECOM, pending wrap and character sets
+//! - **DECTABSR** — `CSI 2 $ w` answers `DCS 2 $ u … ST` with the tab stops
+//! - **DECRSPS** — `DCS Ps $ t … ST` hands either report back to restore it
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::fonts::FontWeight;
+use vttest_common::VtTestHelper;
+
+/// The reply to `seq`, as a string.
+fn reply(h: &mut VtTestHelper, seq: &str) -> String {
+    h.feed_str(seq);
+    String::from_utf8(h.drain_pty_writes_concatenated()).unwrap()
+}
+
+/// A fresh helper with any startup output drained.
+fn helper() -> VtTestHelper {
+    let h = VtTestHelper::new_default();
+    let _ = h.drain_pty_writes();
+    h
+}
+
+// ─── DECCIR ──────────────────────────────────────────────────────────────────
+
+/// A fresh terminal reports the home position and nothing set.
+#[test]
+fn cursor_information_defaults() {
+    let mut h = helper();
+    assert_eq!(
+        reply(&mut h, "\x1b[1$w"),
+        "\x1bP1$u1;1;1;@;@;@;0;2;@;BBBB\x1b\\"
+    );
+}
+
+/// Position, rendition, protection, DECOM and DEC graphics all show up.
+#[test]
+fn cursor_information_reports_state() {
+    let mut h = helper();
+    h.feed_str("\x1b[5;10H\x1b[1;7m\x1b[1\"q\x1b[?6h\x1b[3;7H\x1b(0");
+    assert_eq!(
+        reply(&mut h, "\x1b[1$w"),
+        "\x1bP1$u3;7;1;I;A;A;0;2;@;0BBB\x1b\\"
+    );
+}
+
+/// Writing the last column reports that column with the wrap flag.
+#[test]
+fn cursor_information_reports_pending_wrap() {
+    let mut h = helper();
+    let width = h.state.handler.buffer().terminal_width();
+    h.feed_str(&format!("\x1b[1;{width}HX"));
+    assert_eq!(
+        reply(&mut h, "\x1b[1$w"),
+        format!("\x1bP1$u1;{width};1;@;@;H;0;2;@;BBBB\x1b\\")
+    );
+}
+
+/// Feeding the report back restores what it describes.
+#[test]
+fn cursor_information_round_trips() {
+    let mut h = helper();
+    h.feed_str("\x1b[4;6H\x1b[1m\x1b(0");
+    let report = reply(&mut h, "\x1b[1$w");
+    let body = report
+        .strip_prefix("\x1bP1$u")
+        .and_then(|r| r.strip_suffix("\x1b\\"))
+        .unwrap()
+        .to_string();
+
+    h.feed_str("\x1b[m\x1b(B\x1b[H");
+    h.feed_str(&format!("\x1bP1$t{body}\x1b\\"));
+    assert_eq!(reply(&mut h, "\x1b[1$w"), report);
+
+    h.feed_str("q");
+    h.assert_cursor_pos(6, 3);
+    let cell = h.state.handler.buffer().visible_rows(0)[3].resolve_cell(5);
+    assert_eq!(cell.tag().font_weight, FontWeight::Bold);
+    assert_eq!(cell.into_utf8(), "─");
+}
+
+/// A malformed report changes nothing.
+#[test]
+fn malformed_cursor_information_is_ignored() {
+    let mut h = helper();
+    h.feed_str("\x1b[2;2H\x1bP1$t9;9;1;@;@\x1b\\");
+    h.assert_cursor_pos(1, 1);
+}
+
+/// Every G set, GL, GR and a pending single shift show up.
+#[test]
+fn cursor_information_reports_charsets() {
+    let mut h = helper();
+    h.feed_str("\x1b)0\x1b*K\x1b/A\x0e\x1b|\x1bN");
+    assert_eq!(
+        reply(&mut h, "\x1b[1$w"),
+        "\x1bP1$u1;1;1;@;@;B;1;3;H;B0KA\x1b\\"
+    );
+}
+
+/// DECRSPS restores the designations and GL.
+#[test]
+fn cursor_information_restores_charsets() {
+    let mut h = helper();
+    h.feed_str("\x1bP1$t1;1;1;@;@;@;1;2;@;B0BB\x1b\\q");
+    h.assert_row(0, "─");
+}
+
+// ─── DECTABSR ────────────────────────────────────────────────────────────────
+
+/// Default stops are every eight columns, 1-based.
+#[test]
+fn tab_stop_report_lists_stops() {
+    let mut h = helper();
+    h.feed_str("\x1b[3g\x1b[1;5H\x1bH\x1b[1;13H\x1bH");
+    assert_eq!(reply(&mut h, "\x1b[2$w"), "\x1bP2$u5/13\x1b\\");
+}
+
+/// DECRSPS 2 replaces every tab stop.
+#[test]
+fn tab_stops_round_trip() {
+    let mut h = helper();
+    h.feed_str("\x1bP2$t3/20\x1b\\");
+    assert_eq!(reply(&mut h, "\x1b[2$w"), "\x1bP2$u3/20\x1b\\");
+
+    h.feed_str("\x1b[H\t");
+    h.assert_cursor_pos(2, 0);
+    h.feed_str("\t");
+    h.assert_cursor_pos(19, 0);
+}
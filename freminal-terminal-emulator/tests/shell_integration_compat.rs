@@ -0,0 +1,126 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Command blocks from third-party shell integrations, from the sequences to
+//! the buffer's blocks.
+//!
+//! ## Coverage
+//!
+//! - **OSC 133 ; A/B/C/D** without `freminal=1` — paired in order into
+//!   command blocks, with the rows the markers were sent on
+//! - **OSC 633 ; A/B/C/D** — the same markers from VS Code's integration
+//! - **OSC 633 ; E** — the escaped command line is stored on the block
+//! - **OSC 633 ; P ; Cwd=** — sets the working directory
+//! - Tagged markers win over plain ones sent in the same prompt, and plain
+//!   markers inside a tagged command's output become nested blocks
+//!
+//! Rows are **0-indexed**.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::command_block::{CommandBlock, CommandStatus};
+use vttest_common::VtTestHelper;
+
+fn blocks(h: &VtTestHelper) -> Vec<CommandBlock> {
+    h.state
+        .handler
+        .buffer()
+        .command_blocks()
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// A starship-style prompt: markers on the rows they were sent from.
+#[test]
+fn plain_osc133_builds_blocks_with_rows() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]133;A;cl=m;aid=7\x07$ \x1b]133;B\x07ls\r\n");
+    h.feed_str("\x1b]133;C\x07a\r\nb\r\n\x1b]133;D;0;aid=7\x07");
+
+    let blocks = blocks(&h);
+    assert_eq!(blocks.len(), 1);
+    let block = &blocks[0];
+    assert_eq!(block.prompt_start_row, 0);
+    assert_eq!(block.command_start_row, Some(0));
+    assert_eq!(block.output_start_row, Some(1));
+    assert_eq!(block.end_row, Some(3));
+    assert_eq!(block.status(), CommandStatus::Success);
+    assert_eq!(h.state.handler.last_exit_code(), Some(0));
+}
+
+/// VS Code's markers, with the command line and working directory.
+#[test]
+fn vscode_osc633_builds_blocks_with_command_line() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]633;P;Cwd=/home/user/my\\x3bdir\x07");
+    h.feed_str("\x1b]633;A\x07$ \x1b]633;B\x07echo a; echo b\r\n");
+    h.feed_str("\x1b]633;E;echo a\\x3b echo b;5f1e\x07\x1b]633;C\x07");
+    h.feed_str("a\r\nb\r\n\x1b]633;D;1\x07");
+
+    assert_eq!(
+        h.state.handler.current_working_directory(),
+        Some("/home/user/my;dir")
+    );
+    let blocks = blocks(&h);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].command_line.as_deref(), Some("echo a; echo b"));
+    assert_eq!(blocks[0].cwd.as_deref(), Some("/home/user/my;dir"));
+    assert_eq!(blocks[0].status(), CommandStatus::Failure(1));
+}
+
+/// A prompt redrawn before the command ran leaves one block, and a command
+/// whose `D` never came is closed by the next prompt.
+#[test]
+fn missing_markers_are_repaired() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]133;A\x07$ \x1b]133;A\x07$ \x1b]133;B\x07");
+    assert_eq!(blocks(&h).len(), 1);
+
+    h.feed_str("sleep 9\r\n\x1b]133;C\x07^C\r\n\x1b]133;A\x07$ ");
+    let blocks = blocks(&h);
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].status(), CommandStatus::Unknown);
+    assert_eq!(blocks[0].end_row, Some(2));
+    assert_eq!(blocks[1].prompt_start_row, 2);
+}
+
+/// Freminal's own markers and a second integration in the same prompt give
+/// one block, not two.
+#[test]
+fn tagged_markers_win_in_the_same_prompt() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]133;A\x07\x1b]133;A;freminal=1;fid=f1\x07$ ");
+    h.feed_str("\x1b]133;B\x07\x1b]133;B;freminal=1;fid=f1\x07ls\r\n");
+    h.feed_str("\x1b]133;C;freminal=1;fid=f1\x07\x1b]133;C\x07");
+    h.feed_str("\x1b]133;D;0;freminal=1;fid=f1\x07\x1b]133;D;0\x07");
+
+    let blocks = blocks(&h);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].fid, "f1");
+    assert_eq!(blocks[0].status(), CommandStatus::Success);
+}
+
+/// A remote shell without freminal's scripts, reached over ssh from one that
+/// has them, gets its own blocks.
+#[test]
+fn remote_shell_inside_tagged_command_gets_blocks() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]133;A;freminal=1;fid=f1\x07$ ssh host\r\n");
+    h.feed_str("\x1b]133;C;freminal=1;fid=f1\x07");
+    h.feed_str("\x1b]133;A\x07remote$ \x1b]133;B\x07uptime\r\n");
+    h.feed_str("\x1b]133;C\x07up 3 days\r\n\x1b]133;D;0\x07");
+    h.feed_str("\x1b]133;A\x07remote$ \x1b]133;B\x07exit\r\n\x1b]133;C\x07");
+    h.feed_str("\x1b]133;D;0;freminal=1;fid=f1\x07");
+
+    let blocks = blocks(&h);
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks[0].fid, "f1");
+    assert_eq!(blocks[1].prompt_start_row, 1);
+    assert_eq!(blocks[1].status(), CommandStatus::Success);
+    assert_eq!(blocks[2].status(), CommandStatus::Unknown);
+}
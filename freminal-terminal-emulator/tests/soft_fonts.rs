@@ -0,0 +1,129 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DECDLD soft fonts, from the download to the characters on the grid.
+//!
+//! ## Coverage
+//!
+//! - **DECDLD** — `DCS Pfn ; Pcn ; Pe ; Pcmw ; Pss ; Pt ; Pcmh ; Pcss { Dscs
+//!   Sxbp1 ; … ST` loads sixel-encoded glyphs into a soft set
+//! - **SCS** — `ESC ( SP F` (and `) * +`, `- . /`) designates soft set `F`
+//!   into G0–G3; characters printed through it become private-use characters
+//!   standing for the loaded glyphs
+//! - **Pe** — erases the set, only the loaded positions, or every set
+//! - **DECCIR** — reports a soft designation as ` F`
+//! - **RIS** — discards every soft set
+//!
+//! Glyph pixel coordinates are **0-indexed** from the top-left of the matrix.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::soft_font::soft_char;
+use vttest_common::VtTestHelper;
+
+/// A 5 × 6 matrix font in set `@`: position 1 is a solid block, position 2
+/// has only its left column on.
+const LOAD: &str = "\x1bP0;1;0;5;0;0;6{@~~~~~;~\x1b\\";
+
+fn soft(final_byte: u8, byte: u8) -> String {
+    soft_char(final_byte, byte).unwrap().to_string()
+}
+
+/// Loaded glyphs are stored at the positions that follow Pcn.
+#[test]
+fn load_stores_the_glyphs() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str(LOAD);
+
+    let font = h.state.handler.soft_font();
+    let block = font.glyph(soft_char(b'@', b'!').unwrap()).unwrap();
+    assert_eq!((block.width(), block.height()), (5, 6));
+    assert!((0..5).all(|x| (0..6).all(|y| block.is_set(x, y))));
+
+    let bar = font.glyph(soft_char(b'@', b'"').unwrap()).unwrap();
+    assert!((0..6).all(|y| bar.is_set(0, y) && !bar.is_set(1, y)));
+
+    assert!(font.glyph(soft_char(b'@', b'#').unwrap()).is_none());
+}
+
+/// Printing through a designated soft set writes its private-use characters.
+#[test]
+fn designated_set_prints_soft_characters() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str(LOAD);
+    h.feed_str("\x1b( @!\"\x1b(Ba");
+    h.assert_row(0, &format!("{}{}a", soft(b'@', b'!'), soft(b'@', b'"')));
+}
+
+/// A set can be designated before it is loaded, and through G1–G3.
+#[test]
+fn designation_does_not_need_a_loaded_set() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b) A\x0e!\x0f!");
+    h.assert_row(0, &format!("{}!", soft(b'A', b'!')));
+    assert!(h.state.handler.soft_font().is_empty());
+}
+
+/// Pe = 1 replaces only the loaded positions, Pe = 0 the whole set and
+/// Pe = 2 every set.
+#[test]
+fn erase_control_keeps_or_discards_glyphs() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str(LOAD);
+    h.feed_str("\x1bP0;1;0;5;0;0;6{A~\x1b\\");
+
+    h.feed_str("\x1bP0;3;1;5;0;0;6{@~\x1b\\");
+    let font = h.state.handler.soft_font();
+    assert!(font.glyph(soft_char(b'@', b'!').unwrap()).is_some());
+    assert!(font.glyph(soft_char(b'@', b'#').unwrap()).is_some());
+
+    h.feed_str("\x1bP0;3;0;5;0;0;6{@~\x1b\\");
+    let font = h.state.handler.soft_font();
+    assert!(font.glyph(soft_char(b'@', b'!').unwrap()).is_none());
+    assert!(font.glyph(soft_char(b'@', b'#').unwrap()).is_some());
+    assert!(font.glyph(soft_char(b'A', b'!').unwrap()).is_some());
+
+    h.feed_str("\x1bP0;1;2;5;0;0;6{@~\x1b\\");
+    let font = h.state.handler.soft_font();
+    assert!(font.glyph(soft_char(b'@', b'#').unwrap()).is_none());
+    assert!(font.glyph(soft_char(b'A', b'!').unwrap()).is_none());
+}
+
+/// A malformed load is ignored and leaves the existing sets alone.
+#[test]
+fn malformed_load_is_ignored() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str(LOAD);
+    h.feed_str("\x1bP0;1;0;5;9{@~\x1b\\");
+    assert!(
+        h.state
+            .handler
+            .soft_font()
+            .glyph(soft_char(b'@', b'!').unwrap())
+            .is_some()
+    );
+}
+
+/// DECCIR reports the soft designation with its intermediate.
+#[test]
+fn cursor_information_reports_the_soft_designation() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b) @\x1b[1$w");
+    assert_eq!(
+        String::from_utf8(h.drain_pty_writes_concatenated()).unwrap(),
+        "\x1bP1$u1;1;1;@;@;@;0;2;@;B @BB\x1b\\"
+    );
+}
+
+/// RIS discards the soft sets.
+#[test]
+fn reset_discards_soft_sets() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str(LOAD);
+    h.feed_str("\x1bc");
+    assert!(h.state.handler.soft_font().is_empty());
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! DEC status line — DECSSDT and DECSASD.
+//!
+//! ## Coverage
+//!
+//! - **DECSSDT** — `CSI Ps $ ~` selects no status line, the indicator line or
+//!   a host-writable one
+//! - **DECSASD** — `CSI Ps $ }` sends output to the main display or the
+//!   host-writable status line, leaving the other untouched
+//! - **RIS** — removes the status line
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::{status_line::StatusLineType, tchar::TChar};
+use vttest_common::VtTestHelper;
+
+/// The status line's text with trailing blanks removed, or `None` when no
+/// status line is shown.
+fn status_text(h: &mut VtTestHelper) -> Option<String> {
+    let (chars, _) = h.state.handler.status_line_contents()?;
+    let text: String = chars
+        .iter()
+        .take_while(|c| !matches!(c, TChar::NewLine))
+        .map(ToString::to_string)
+        .collect();
+    Some(text.trim_end().to_string())
+}
+
+/// No status line is shown by default.
+#[test]
+fn no_status_line_by_default() {
+    let mut h = VtTestHelper::new_default();
+    assert_eq!(h.state.handler.status_line_type(), StatusLineType::None);
+    assert_eq!(status_text(&mut h), None);
+}
+
+/// Text written to the status line stays off the main display, and the
+/// main cursor picks up where it left off.
+#[test]
+fn host_writable_status_line() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("main\x1b[2$~\x1b[1$}\x1b[1mbuild ok\x1b[0$}!");
+
+    assert_eq!(status_text(&mut h).as_deref(), Some("build ok"));
+    h.assert_row(0, "main!");
+    h.assert_cursor_pos(5, 0);
+}
+
+/// Each display keeps its own cursor; rewriting the status line replaces
+/// what was there.
+#[test]
+fn status_line_cursor_is_kept_separately() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[2$~\x1b[1$}first\x1b[0$}\x1b[5;5H\x1b[1$}\r\x1b[Ksecond\x1b[0$}");
+
+    assert_eq!(status_text(&mut h).as_deref(), Some("second"));
+    h.assert_cursor_pos(4, 4);
+}
+
+/// DECSASD 1 does nothing unless the status line is host-writable.
+#[test]
+fn status_display_needs_host_writable_line() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1$}text");
+    h.assert_row(0, "text");
+    assert_eq!(status_text(&mut h), None);
+}
+
+/// Removing the status line sends output back to the main display.
+#[test]
+fn removing_status_line_returns_to_main_display() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[2$~\x1b[1$}status\x1b[0$~main");
+
+    assert_eq!(status_text(&mut h), None);
+    h.assert_row(0, "main");
+}
+
+/// The indicator line shows the cursor position.
+#[test]
+fn indicator_status_line() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1$~\x1b[3;7H");
+    assert_eq!(status_text(&mut h).as_deref(), Some(" Ln 3, Col 7"));
+}
+
+/// RIS removes the status line and its contents.
+#[test]
+fn reset_removes_status_line() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[2$~\x1b[1$}status\x1bc");
+    assert_eq!(status_text(&mut h), None);
+
+    h.feed_str("\x1b[2$~");
+    assert_eq!(status_text(&mut h).as_deref(), Some(""));
+}
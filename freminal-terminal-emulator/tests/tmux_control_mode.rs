@@ -0,0 +1,230 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! tmux control mode (`tmux -CC`) end to end against a fake tmux.
+//!
+//! [`FakeTmux`] replays a captured control-mode transcript from
+//! `tests/tmux_transcripts/` into a headless emulator, stopping at each
+//! command the transcript says the client sends and checking that the
+//! emulator (or the test, standing in for the GUI) actually sent it.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::VecDeque;
+
+use freminal_common::pty_write::PtyWrite;
+use freminal_terminal_emulator::interface::TerminalEmulator;
+use freminal_terminal_emulator::tmux_control::{
+    TmuxCommandKind, TmuxCommandSender, TmuxEvent, TmuxLayoutContent, TmuxSplitAxis,
+    detach_command, kill_pane_command, split_window_command,
+};
+
+/// A tmux server that replays a transcript.
+struct FakeTmux {
+    lines: VecDeque<String>,
+}
+
+impl FakeTmux {
+    fn load(name: &str) -> Self {
+        let path = format!(
+            "{}/tests/tmux_transcripts/{name}.txt",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let text = std::fs::read_to_string(&path).expect("transcript must exist");
+        Self {
+            lines: text
+                .lines()
+                .filter(|l| !l.starts_with('#'))
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+
+    /// Send server lines to `emu` up to the next command the client is
+    /// expected to write, and return that command (`None` at the end).
+    fn serve(&mut self, emu: &mut TerminalEmulator) -> Option<String> {
+        while let Some(line) = self.lines.pop_front() {
+            if let Some(command) = line.strip_prefix("> ") {
+                return Some(command.to_owned());
+            }
+            // tmux writes `\n`; the tty turns it into `\r\n`.
+            emu.handle_incoming_data(format!("{line}\r\n").as_bytes());
+        }
+        None
+    }
+}
+
+/// Command lines the client has written to the gateway PTY.
+fn client_lines(rx: &crossbeam_channel::Receiver<PtyWrite>) -> Vec<String> {
+    rx.try_iter()
+        .filter_map(|w| match w {
+            PtyWrite::Write(bytes) => Some(String::from_utf8(bytes).unwrap()),
+            PtyWrite::Resize(_) => None,
+        })
+        .flat_map(|s| s.lines().map(str::to_owned).collect::<Vec<_>>())
+        .collect()
+}
+
+/// The gateway pane's visible screen as text.
+fn screen_text(emu: &mut TerminalEmulator) -> String {
+    emu.build_snapshot()
+        .visible_chars
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// What the user does when the transcript expects a command that only a user
+/// action would produce.
+fn user_action(expected: &str, commands: &TmuxCommandSender) {
+    if expected.starts_with("send-keys") {
+        commands.send_keys(0, b"ls\r").unwrap();
+    } else if expected.starts_with("split-window") {
+        commands
+            .send(
+                TmuxCommandKind::Ignore,
+                &split_window_command(0, TmuxSplitAxis::SideBySide),
+            )
+            .unwrap();
+    } else if expected.starts_with("kill-pane") {
+        commands
+            .send(TmuxCommandKind::Ignore, &kill_pane_command(2))
+            .unwrap();
+    } else if expected.starts_with("detach-client") {
+        commands
+            .send(TmuxCommandKind::Ignore, detach_command())
+            .unwrap();
+    }
+}
+
+/// Run the whole transcript and return every event, in order.
+fn replay(name: &str) -> (TerminalEmulator, Vec<TmuxEvent>) {
+    let (mut emu, rx) = TerminalEmulator::new_headless(None);
+    emu.handle_incoming_data(b"$ tmux -CC new -s demo\r\n\x1bP1000p");
+
+    let mut fake = FakeTmux::load(name);
+    let mut events = emu.internal.handler.drain_tmux_events();
+    let commands = match events.first() {
+        Some(TmuxEvent::Started { commands }) => commands.clone(),
+        other => panic!("expected Started, got {other:?}"),
+    };
+
+    let mut written: VecDeque<String> = VecDeque::new();
+    while let Some(expected) = fake.serve(&mut emu) {
+        events.extend(emu.internal.handler.drain_tmux_events());
+        written.extend(client_lines(&rx));
+        if written.is_empty() {
+            user_action(&expected, &commands);
+            written.extend(client_lines(&rx));
+        }
+        assert_eq!(written.pop_front().as_deref(), Some(expected.as_str()));
+    }
+    emu.handle_incoming_data(b"\x1b\\");
+    events.extend(emu.internal.handler.drain_tmux_events());
+    assert!(
+        written.is_empty() && client_lines(&rx).is_empty(),
+        "client sent commands the transcript does not expect: {written:?}"
+    );
+    (emu, events)
+}
+
+#[test]
+fn attach_split_and_detach_produce_events_in_order() {
+    let (emu, events) = replay("attach_split_detach");
+    assert!(!emu.internal.handler.in_tmux_control_mode());
+
+    let summary: Vec<String> = events
+        .iter()
+        .map(|e| match e {
+            TmuxEvent::Started { .. } => "started".to_owned(),
+            TmuxEvent::Window(w) => format!("window @{} {}", w.id, w.name),
+            TmuxEvent::LayoutChange { window, layout } => {
+                format!("layout @{window} {:?}", layout.pane_ids())
+            }
+            TmuxEvent::WindowClose { window } => format!("close @{window}"),
+            TmuxEvent::WindowRenamed { window, name } => format!("rename @{window} {name}"),
+            TmuxEvent::ActivePaneChanged { window, pane } => format!("active @{window} %{pane}"),
+            TmuxEvent::ActiveWindowChanged { window } => format!("current @{window}"),
+            TmuxEvent::Output { pane, .. } => format!("output %{pane}"),
+            TmuxEvent::CommandError(message) => format!("error {message}"),
+            TmuxEvent::Exit { .. } => "exit".to_owned(),
+            TmuxEvent::Ended => "ended".to_owned(),
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            "started",
+            "output %0",
+            "window @0 zsh",
+            "output %0",
+            "output %0",
+            "layout @0 [0, 1]",
+            "active @0 %1",
+            "output %1",
+            "current @1",
+            "output %2",
+            "window @0 zsh",
+            "window @1 zsh",
+            "rename @1 logs",
+            "close @1",
+            "exit",
+            "ended",
+        ]
+    );
+}
+
+#[test]
+fn output_is_unescaped_and_layout_is_split_side_by_side() {
+    let (_emu, events) = replay("attach_split_detach");
+
+    let pane0: Vec<u8> = events
+        .iter()
+        .filter_map(|e| match e {
+            TmuxEvent::Output { pane: 0, data } => Some(data.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(
+        String::from_utf8(pane0).unwrap(),
+        "\x1b[?2004h$ \x1b[H\x1b[2J$ ls\r\nREADME.md  src\r\n$ "
+    );
+
+    let layout = events
+        .iter()
+        .find_map(|e| match e {
+            TmuxEvent::LayoutChange { layout, .. } => Some(layout),
+            _ => None,
+        })
+        .unwrap();
+    assert!(matches!(
+        &layout.content,
+        TmuxLayoutContent::Split {
+            axis: TmuxSplitAxis::SideBySide,
+            children,
+        } if children.len() == 2
+    ));
+    let plan = layout.split_plan();
+    assert_eq!(plan.len(), 1);
+    assert_eq!((plan[0].target, plan[0].pane), (0, 1));
+}
+
+#[test]
+fn gateway_screen_does_not_show_the_protocol() {
+    let (mut emu, _events) = replay("attach_split_detach");
+    let text = screen_text(&mut emu);
+    assert!(text.contains("tmux -CC new"));
+    assert!(!text.contains("%begin"));
+    assert!(!text.contains("%output"));
+}
+
+#[test]
+fn output_after_control_mode_ends_is_drawn_normally() {
+    let (mut emu, _events) = replay("attach_split_detach");
+    emu.handle_incoming_data(b"[detached (from session demo)]\r\n");
+    assert!(screen_text(&mut emu).contains("[detached (from session demo)]"));
+}
@@ -5,24 +5,30 @@
 
 //! vttest Menu 3 — Character Set Tests.
 //!
-//! Tests for G0 charset designation and DEC Special Graphics character mapping,
-//! the only character-set feature currently implemented in Freminal.
+//! Tests for G0–G3 designation, GL invocation, single shifts and the
+//! character sets Freminal maps: DEC Special Graphics, DEC Supplemental,
+//! DEC Technical, ISO Latin-1 Supplemental and the VT220 NRC sets.
 //!
 //! ## Coverage
 //!
 //! - **`ESC ( 0`** — designate DEC Special Graphics for G0
 //! - **`ESC ( B`** — designate US ASCII for G0 (restore default)
-//! - **SI / SO** — invoke G0 (SI) and G1 (SO); consumed as C0 control
-//!   characters (no-op — Freminal uses a simplified single-slot charset model)
+//! - **SI / SO** — invoke G0 (SI) and G1 (SO) into GL
+//! - **LS2 / LS3** — `ESC n` and `ESC o` invoke G2 and G3 into GL
+//! - **SS2 / SS3** — `ESC N` and `ESC O` take one character from G2 or G3
+//! - **`ESC - A`** — designate ISO Latin-1 Supplemental (96 characters)
+//! - **NRC sets** — UK, German, French, Swedish and the rest replace their
+//!   national positions
+//! - **DEC Supplemental / Technical** — `ESC ( <`, `ESC ( % 5`, `ESC ( >`
+//! - **DECSC / DECRC** — the charset state is saved and restored
 //! - **Complete DEC Special Graphics table** — all 32 mapped code points
 //!   (0x5F–0x7E → Unicode equivalents)
 //! - **Pass-through** — bytes outside 0x5F–0x7E are unaffected by G0 mode
 //!
 //! ## Excluded
 //!
-//! - **VT220 locking/single shifts** (SS2/SS3, G2/G3): not implemented.
-//! - **National Replacement Character sets**: requires DECNRCM (Task 20.12).
-//! - **ISO Latin character sets**: not implemented.
+//! - **GR invocation** (LS1R/LS2R/LS3R): tracked, but the PTY stream is
+//!   UTF-8, so no byte reaches GR.
 //!
 //! All cursor positions in the helper API are **0-indexed** (`x` = column,
 //! `y` = row).
@@ -63,33 +69,20 @@ fn dec_special_activate_and_deactivate() {
     );
 }
 
-/// SI (0x0F) and SO (0x0E) are defined in the VT100 standard to invoke G0 and
-/// G1 respectively. Freminal recognises them as C0 control characters and
-/// silently consumes them (no-op — Freminal uses a simplified single-slot
-/// charset model where G0 is always the active GL set). They must NOT appear
-/// as visible data in the terminal buffer.
+/// SO (0x0E) invokes G1 into GL and SI (0x0F) puts G0 back. Neither
+/// leaves a visible artifact in the buffer.
 #[test]
-fn si_so_consumed_as_control_characters() {
+fn si_so_invoke_g0_and_g1() {
     let mut h = VtTestHelper::new_default();
 
-    // With DEC Special Graphics active, write a known mapped char.
-    h.feed(b"\x1b(0"); // designate DEC Special for G0
-    h.feed(b"\x6a"); // 0x6a → '┘'
+    h.feed(b"\x1b)0"); // designate DEC Special for G1
+    h.feed(b"\x6a"); // G0 is still ASCII → 'j'
+    h.feed(b"\x0e"); // SO — G1 into GL
+    h.feed(b"\x6a"); // → '┘'
+    h.feed(b"\x0f"); // SI — G0 into GL
+    h.feed(b"\x6a"); // → 'j'
 
-    // SO (0x0E) and SI (0x0F) are consumed as control characters.
-    // DEC Special Graphics mode remains active throughout.
-    h.feed(b"\x0e"); // SO — consumed; no visible output
-    h.feed(b"\x6a"); // still in DEC Special mode → '┘'
-    h.feed(b"\x0f"); // SI — consumed; no visible output
-    h.feed(b"\x6a"); // still in DEC Special mode → '┘'
-
-    // The row should contain exactly three '┘' glyphs and no SI/SO artifacts.
-    let row = h.screen_text()[0].clone();
-    let box_count = row.chars().filter(|&c| c == '\u{2518}').count();
-    assert_eq!(
-        box_count, 3,
-        "All three 0x6a writes must produce ┘; SO/SI must be consumed silently; got: {row:?}"
-    );
+    h.assert_row(0, "j┘j");
 }
 
 /// After leaving and re-entering DEC Special mode, the mapping is still active.
@@ -402,6 +395,134 @@ fn dec_special_draw_box() {
     h.assert_row(2, "└──┘");
 }
 
+// ─── G2 / G3, Locking and Single Shifts ─────────────────────────────────────
+
+/// LS2 (`ESC n`) and LS3 (`ESC o`) lock G2 and G3 into GL until SI.
+#[test]
+fn locking_shifts_g2_and_g3() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b[?42h\x1b*0\x1b+K"); // G2 = DEC Special, G3 = German
+    h.feed(b"\x1bnqq\x1bo{}\x0fq");
+    h.assert_row(0, "──äüq");
+}
+
+/// SS2 (`ESC N`) and SS3 (`ESC O`) affect exactly one character.
+#[test]
+fn single_shifts_affect_one_character() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b*0\x1b+>"); // G2 = DEC Special, G3 = DEC Technical
+    h.feed(b"\x1bNqq\x1bOaa");
+    h.assert_row(0, "─qαa");
+}
+
+/// 8-bit SS2 / SS3 work once S8C1T is on.
+#[test]
+fn eight_bit_single_shifts() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b G\x1b*0");
+    h.feed(b"\x8eq\x8fq");
+    h.assert_row(0, "─q");
+}
+
+/// `ESC - A` designates the 96-character Latin-1 set into G1.
+#[test]
+fn latin1_supplemental_into_g1() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b-A\x0eAi\x0fAi");
+    h.assert_row(0, "ÁéAi");
+}
+
+// ─── National Replacement Character Sets ─────────────────────────────────────
+
+/// With DECNRCM set, each NRC set replaces its own national positions and
+/// leaves the rest of ASCII alone.
+#[test]
+fn national_replacement_sets() {
+    for (designator, expected) in [
+        ("A", "£@[\\]^_`{|}~ AZaz"),
+        ("K", "#§ÄÖÜ^_`äöüß AZaz"),
+        ("R", "£à°ç§^_`éùè¨ AZaz"),
+        ("Q", "#àâçêî_ôéùèû AZaz"),
+        ("Y", "£§°çé^_ùàòèì AZaz"),
+        ("E", "#ÄÆØÅÜ_äæøåü AZaz"),
+        ("Z", "£§¡Ñ¿^_`°ñç~ AZaz"),
+        ("H", "#ÉÄÖÅÜ_éäöåü AZaz"),
+        ("=", "ùàéçêîèôäöüû AZaz"),
+        ("4", "£¾ĳ½|^_`¨ƒ¼´ AZaz"),
+        ("C", "#@ÄÖÅÜ_éäöåü AZaz"),
+        ("%6", "#@ÃÇÕ^_`ãçõ~ AZaz"),
+    ] {
+        let mut h = VtTestHelper::new_default();
+        h.feed_str(&format!("\x1b[?42h\x1b({designator}#@[\\]^_`{{|}}~ AZaz"));
+        h.assert_row(0, expected);
+    }
+}
+
+/// An NRC set designated into G1 only applies after SO.
+#[test]
+fn national_set_in_g1_needs_shift_out() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b[?42h\x1b)K{\x0e{\x0f{");
+    h.assert_row(0, "{ä{");
+}
+
+/// NRC designations are ignored while DECNRCM (`?42`) is reset, and take
+/// effect once it is set.
+#[test]
+fn national_sets_ignored_without_nrc_mode() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b(K{|}\x1b)K\x0e[\x0f");
+    h.assert_row(0, "{|}[");
+
+    h.feed(b"\x1b[?42h{|}");
+    h.assert_row(0, "{|}[äöü");
+
+    h.feed(b"\x1b[?42l{");
+    h.assert_row(0, "{|}[äöü{");
+}
+
+// ─── DEC Supplemental and DEC Technical ──────────────────────────────────────
+
+/// DEC Supplemental is Latin-1 in GL except where DEC MCS differs.
+#[test]
+fn dec_supplemental_graphics() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b(<!#(1W]w}");
+    h.assert_row(0, "¡£¤±ŒŸœÿ");
+
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b(%5AZaz");
+    h.assert_row(0, "ÁÚáú");
+}
+
+/// DEC Technical maps Greek and mathematical symbols.
+#[test]
+fn dec_technical() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b(>DWabpB<>{}");
+    h.assert_row(0, "ΔΩαβπ∞≤≥←→");
+}
+
+// ─── Save / Restore ──────────────────────────────────────────────────────────
+
+/// DECSC saves the designations and GL; DECRC restores them.
+#[test]
+fn decsc_saves_charset_state() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b)0\x0e\x1b7"); // G1 = DEC Special in GL, then DECSC
+    h.feed(b"\x0f\x1b)B"); // SI, G1 = ASCII
+    h.feed(b"q\x1b8\x1b[1;2Hq"); // 'q', DECRC, then 'q' beside it
+    h.assert_row(0, "q─");
+}
+
+/// RIS puts every G set back to ASCII and G0 into GL.
+#[test]
+fn ris_resets_charsets() {
+    let mut h = VtTestHelper::new_default();
+    h.feed(b"\x1b(K\x1b)0\x0e\x1bc{q");
+    h.assert_row(0, "{q");
+}
+
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
 /// Feed a single DEC Special Graphics byte and assert the first character on
@@ -0,0 +1,296 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! vttest Menu 11.6.6 — VT420 Rectangular Area Functions.
+//!
+//! These tests follow vttest's "Test Rectangular area functions" screens:
+//! fill a known pattern, run one rectangle operation over part of it, and
+//! check the result cell by cell instead of by eye.
+//!
+//! ## Coverage
+//!
+//! - **DECFRA** — fill with a character in the current attributes; control
+//!   and C1 codes as `Pch` are ignored
+//! - **DECERA** — erase to default blanks
+//! - **DECSERA** — erase characters, keeping attributes
+//! - **DECCRA** — copy, including overlapping source and destination
+//! - **DECCARA / DECRARA** — change and reverse attributes, in stream and
+//!   rectangle extent (**DECSACE**)
+//! - **DECOM** — coordinates relative to DECSTBM / DECSLRM margins and
+//!   clipped to them
+//!
+//! None of the operations move the cursor.
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::{
+    cursor::ReverseVideo,
+    fonts::{FontWeight, UnderlineStyle},
+    format_tag::FormatTag,
+};
+use vttest_common::VtTestHelper;
+
+/// A helper with rows 1–6 reading `Row 1 abcdefghijklmnopqrstuvwxyz` etc.
+fn patterned() -> VtTestHelper {
+    let mut h = VtTestHelper::new_default();
+    for row in 1..=6 {
+        h.feed_str(&format!(
+            "\x1b[{row};1HRow {row} abcdefghijklmnopqrstuvwxyz"
+        ));
+    }
+    h
+}
+
+/// The format tag of the cell at (`col`, `row`), 0-indexed.
+fn tag_at(h: &VtTestHelper, col: usize, row: usize) -> FormatTag {
+    h.state.handler.buffer().visible_rows(0)[row]
+        .resolve_cell(col)
+        .tag()
+        .clone()
+}
+
+fn is_bold(h: &VtTestHelper, col: usize, row: usize) -> bool {
+    tag_at(h, col, row).font_weight == FontWeight::Bold
+}
+
+// ─── DECFRA ──────────────────────────────────────────────────────────────────
+
+/// `CSI 42 ; 2 ; 7 ; 4 ; 9 $ x` fills rows 2–4, columns 7–9 with `*`.
+#[test]
+fn decfra_fills_rectangle() {
+    let mut h = patterned();
+    h.feed_str("\x1b[42;2;7;4;9$x");
+
+    h.assert_row(0, "Row 1 abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(1, "Row 2 ***defghijklmnopqrstuvwxyz");
+    h.assert_row(2, "Row 3 ***defghijklmnopqrstuvwxyz");
+    h.assert_row(3, "Row 4 ***defghijklmnopqrstuvwxyz");
+    h.assert_row(4, "Row 5 abcdefghijklmnopqrstuvwxyz");
+}
+
+/// The fill character takes the current SGR attributes.
+#[test]
+fn decfra_uses_current_attributes() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1m\x1b[88;1;1;1;3$x\x1b[0m");
+
+    h.assert_row(0, "XXX");
+    assert!(is_bold(&h, 2, 0));
+    assert!(!is_bold(&h, 3, 0));
+}
+
+/// Control characters, DEL and C1 codes are not valid fill characters.
+#[test]
+fn decfra_ignores_control_characters() {
+    let mut h = patterned();
+    h.feed_str("\x1b[10;1;1;6;80$x\x1b[127;1;1;6;80$x\x1b[150;1;1;6;80$x");
+
+    h.assert_row(0, "Row 1 abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(5, "Row 6 abcdefghijklmnopqrstuvwxyz");
+}
+
+/// Omitted bottom/right default to the last row and column.
+#[test]
+fn decfra_defaults_extend_to_screen_edge() {
+    let mut h = VtTestHelper::new(10, 4);
+    h.feed_str("\x1b[43;3;8$x");
+
+    h.assert_row(1, "");
+    h.assert_row(2, "       +++");
+    h.assert_row(3, "       +++");
+}
+
+// ─── DECERA / DECSERA ────────────────────────────────────────────────────────
+
+/// `CSI 2 ; 7 ; 5 ; 12 $ z` erases a 4x6 block and nothing else.
+#[test]
+fn decera_erases_rectangle() {
+    let mut h = patterned();
+    h.feed_str("\x1b[1m\x1b[2;7;5;12$z");
+
+    h.assert_row(0, "Row 1 abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(1, "Row 2       ghijklmnopqrstuvwxyz");
+    h.assert_row(4, "Row 5       ghijklmnopqrstuvwxyz");
+    h.assert_row(5, "Row 6 abcdefghijklmnopqrstuvwxyz");
+    // DECERA blanks use default attributes, not the current SGR.
+    assert_eq!(tag_at(&h, 7, 2), FormatTag::default());
+}
+
+/// DECSERA erases the characters but leaves each cell's attributes.
+#[test]
+fn decsera_keeps_attributes() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("plain \x1b[7mreverse\x1b[0m tail");
+    h.feed_str("\x1b[1;1;1;13${");
+
+    h.assert_row(0, &format!("{}tail", " ".repeat(14)));
+    assert_eq!(tag_at(&h, 0, 0), FormatTag::default());
+    assert_eq!(tag_at(&h, 6, 0).colors.reverse_video, ReverseVideo::On);
+    assert_eq!(tag_at(&h, 12, 0).colors.reverse_video, ReverseVideo::On);
+}
+
+// ─── DECCRA ──────────────────────────────────────────────────────────────────
+
+/// Copy rows 1–2, columns 7–9 to row 5, column 20.
+#[test]
+fn deccra_copies_rectangle() {
+    let mut h = patterned();
+    h.feed_str("\x1b[1;7;2;9;1;5;20;1$v");
+
+    h.assert_row(0, "Row 1 abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(4, "Row 5 abcdefghijklmabcqrstuvwxyz");
+    h.assert_row(5, "Row 6 abcdefghijklmabcqrstuvwxyz");
+}
+
+/// Overlapping copies read the whole source before writing.
+#[test]
+fn deccra_overlapping_copy() {
+    let mut h = patterned();
+    h.feed_str("\x1b[1;1;3;5;1;2;3;1$v");
+
+    h.assert_row(0, "Row 1 abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(1, "RoRow 1bcdefghijklmnopqrstuvwxyz");
+    h.assert_row(2, "RoRow 2bcdefghijklmnopqrstuvwxyz");
+    h.assert_row(3, "RoRow 3bcdefghijklmnopqrstuvwxyz");
+    h.assert_row(4, "Row 5 abcdefghijklmnopqrstuvwxyz");
+}
+
+/// Attributes travel with the copied characters.
+#[test]
+fn deccra_copies_attributes() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1mB\x1b[0mn");
+    h.feed_str("\x1b[1;1;1;2;1;3;1;1$v");
+
+    h.assert_row(2, "Bn");
+    assert!(is_bold(&h, 0, 2));
+    assert!(!is_bold(&h, 1, 2));
+}
+
+// ─── DECCARA / DECRARA / DECSACE ─────────────────────────────────────────────
+
+/// In the default stream extent, DECCARA wraps from the start position to
+/// the end of the line and continues from column 1 on the following rows.
+#[test]
+fn deccara_stream_extent() {
+    let mut h = patterned();
+    h.feed_str("\x1b[2;30;3;5;1$r");
+
+    assert!(!is_bold(&h, 28, 1));
+    assert!(is_bold(&h, 29, 1));
+    assert!(is_bold(&h, 79, 1));
+    assert!(is_bold(&h, 0, 2));
+    assert!(is_bold(&h, 4, 2));
+    assert!(!is_bold(&h, 5, 2));
+}
+
+/// After `CSI 2 * x`, DECCARA changes exactly the rectangle.
+#[test]
+fn deccara_rectangle_extent() {
+    let mut h = patterned();
+    h.feed_str("\x1b[2*x\x1b[2;5;3;10;1;4$r");
+
+    for row in 1..=2 {
+        assert!(!is_bold(&h, 3, row));
+        assert!(is_bold(&h, 4, row));
+        assert!(is_bold(&h, 9, row));
+        assert!(!is_bold(&h, 10, row));
+    }
+    assert_eq!(
+        tag_at(&h, 4, 1).font_decorations.underline_style(),
+        UnderlineStyle::Single
+    );
+    assert!(!is_bold(&h, 20, 1));
+}
+
+/// `CSI 0 $ r` (or no attributes) turns every rectangle attribute off.
+#[test]
+fn deccara_zero_clears_attributes() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1;4;5;7mabc\x1b[0m");
+    h.feed_str("\x1b[2*x\x1b[1;2;1;2$r");
+
+    assert!(is_bold(&h, 0, 0));
+    assert_eq!(tag_at(&h, 1, 0), FormatTag::default());
+    assert!(is_bold(&h, 2, 0));
+}
+
+/// DECRARA reverses each cell's own state.
+#[test]
+fn decrara_toggles_attributes() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[7mab\x1b[0mcd");
+    h.feed_str("\x1b[2*x\x1b[1;2;1;3;7$t");
+
+    assert_eq!(tag_at(&h, 0, 0).colors.reverse_video, ReverseVideo::On);
+    assert_eq!(tag_at(&h, 1, 0).colors.reverse_video, ReverseVideo::Off);
+    assert_eq!(tag_at(&h, 2, 0).colors.reverse_video, ReverseVideo::On);
+    assert_eq!(tag_at(&h, 3, 0).colors.reverse_video, ReverseVideo::Off);
+}
+
+/// RIS restores the stream extent.
+#[test]
+fn ris_resets_decsace() {
+    let mut h = patterned();
+    h.feed_str("\x1b[2*x\x1bc");
+    for row in 1..=2 {
+        h.feed_str(&format!("\x1b[{row};1H0123456789"));
+    }
+    h.feed_str("\x1b[1;8;2;2;1$r");
+
+    assert!(is_bold(&h, 9, 0));
+    assert!(is_bold(&h, 0, 1));
+}
+
+// ─── DECOM and margins ───────────────────────────────────────────────────────
+
+/// With DECOM set, coordinates count from the DECSTBM / DECSLRM margins and
+/// the area is clipped to them.
+#[test]
+fn origin_mode_uses_margins() {
+    let mut h = patterned();
+    // Margins: rows 2–4, columns 5–10; then origin mode.
+    h.feed_str("\x1b[2;4r\x1b[?69h\x1b[5;10s\x1b[?6h");
+    h.feed_str("\x1b[35;1;1;99;2$x");
+
+    h.assert_row(0, "Row 1 abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(1, "Row ##abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(3, "Row ##abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(4, "Row 5 abcdefghijklmnopqrstuvwxyz");
+
+    // Erase from margin column 5 to "the end": stops at the right margin.
+    h.feed_str("\x1b[2;5$z");
+    h.assert_row(1, "Row ##abcdefghijklmnopqrstuvwxyz");
+    h.assert_row(2, "Row ##ab  efghijklmnopqrstuvwxyz");
+    h.assert_row(3, "Row ##ab  efghijklmnopqrstuvwxyz");
+}
+
+/// Without DECOM the margins are ignored.
+#[test]
+fn margins_without_origin_mode_are_ignored() {
+    let mut h = patterned();
+    h.feed_str("\x1b[2;4r\x1b[?69h\x1b[5;10s");
+    h.feed_str("\x1b[1;1;6;3$z");
+
+    for row in 0..6 {
+        h.assert_row(row, &format!("    {} abcdefghijklmnopqrstuvwxyz", row + 1));
+    }
+}
+
+/// Rectangle operations leave the cursor where it was.
+#[test]
+fn operations_do_not_move_cursor() {
+    let mut h = patterned();
+    h.feed_str("\x1b[3;12H");
+    h.feed_str("\x1b[42;1;1;2;2$x\x1b[1;1;1;1$z\x1b[1;1;1;1${");
+    h.feed_str("\x1b[1;1;2;2;1;5;5;1$v\x1b[1;1;2;2;1$r\x1b[1;1;2;2$t");
+
+    h.assert_cursor_pos(11, 2);
+}
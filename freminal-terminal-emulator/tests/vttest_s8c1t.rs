@@ -547,3 +547,45 @@ fn response_decreqtparm_uses_8bit_csi_in_s8c1t() {
         "DECREQTPARM body mismatch"
     );
 }
+
+/// XTREPORTSGR response in S8C1T mode should use 8-bit CSI.
+#[test]
+fn response_xtreportsgr_uses_8bit_csi_in_s8c1t() {
+    let mut h = VtTestHelper::new_default();
+
+    h.feed(b"\x1b G");
+    let _ = h.drain_pty_writes();
+
+    // XTREPORTSGR over the top-left cell: ESC [ 1 ; 1 ; 1 ; 1 # |
+    h.feed(b"\x1b[1;1;1;1#|");
+
+    let response = h.drain_pty_writes_concatenated();
+
+    assert_eq!(
+        response[0], 0x9B,
+        "expected 8-bit CSI for XTREPORTSGR, got 0x{:02X}",
+        response[0]
+    );
+    assert_eq!(&response[1..], b"0m", "XTREPORTSGR body mismatch");
+}
+
+/// XTREPORTCOLORS response in S8C1T mode should use 8-bit CSI.
+#[test]
+fn response_xtreportcolors_uses_8bit_csi_in_s8c1t() {
+    let mut h = VtTestHelper::new_default();
+
+    h.feed(b"\x1b G");
+    let _ = h.drain_pty_writes();
+
+    // XTREPORTCOLORS: ESC [ # R
+    h.feed(b"\x1b[#R");
+
+    let response = h.drain_pty_writes_concatenated();
+
+    assert_eq!(
+        response[0], 0x9B,
+        "expected 8-bit CSI for XTREPORTCOLORS, got 0x{:02X}",
+        response[0]
+    );
+    assert_eq!(&response[1..], b"0;0#Q", "XTREPORTCOLORS body mismatch");
+}
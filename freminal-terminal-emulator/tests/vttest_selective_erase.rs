@@ -0,0 +1,145 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! vttest VT220 screen-display functions — DECSCA, DECSED and DECSEL.
+//!
+//! vttest writes a screen mixing protected and unprotected text, erases it
+//! selectively, and asks the user to check that only the protected text is
+//! left.  These tests make the same check cell by cell.
+//!
+//! ## Coverage
+//!
+//! - **DECSCA** — `CSI 1 " q` protects the characters that follow, `CSI 0 " q`
+//!   and `CSI 2 " q` stop protecting; SGR 0 leaves protection alone
+//! - **DECSED** — `CSI ? Ps J` for Ps 0, 1 and 2
+//! - **DECSEL** — `CSI ? Ps K` for Ps 0, 1 and 2
+//! - **DECSERA** — `CSI Pt ; Pl ; Pb ; Pr $ {` spares protected characters
+//! - **ED / EL / DECERA** — still erase protected characters
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::buffer_states::fonts::FontWeight;
+use vttest_common::VtTestHelper;
+
+/// Rows 1–3 read `ab PROT cd`, with `PROT` written under DECSCA 1.
+fn protected_screen() -> VtTestHelper {
+    let mut h = VtTestHelper::new_default();
+    for row in 1..=3 {
+        h.feed_str(&format!("\x1b[{row};1Hab \x1b[1\"qPROT\x1b[0\"q cd"));
+    }
+    h
+}
+
+// ─── DECSEL ──────────────────────────────────────────────────────────────────
+
+/// `CSI ? 2 K` erases the whole line except the protected text.
+#[test]
+fn decsel_all_spares_protected_text() {
+    let mut h = protected_screen();
+    h.feed_str("\x1b[2;1H\x1b[?2K");
+
+    h.assert_row(0, "ab PROT cd");
+    h.assert_row(1, "   PROT");
+    h.assert_row(2, "ab PROT cd");
+}
+
+/// `CSI ? K` and `CSI ? 1 K` erase right and left of the cursor.
+#[test]
+fn decsel_partial() {
+    let mut h = protected_screen();
+    h.feed_str("\x1b[1;5H\x1b[?K");
+    h.feed_str("\x1b[2;5H\x1b[?1K");
+
+    h.assert_row(0, "ab PROT");
+    h.assert_row(1, "   PROT cd");
+}
+
+// ─── DECSED ──────────────────────────────────────────────────────────────────
+
+/// `CSI ? 2 J` erases the display except the protected text.
+#[test]
+fn decsed_all_spares_protected_text() {
+    let mut h = protected_screen();
+    h.feed_str("\x1b[?2J");
+
+    for row in 0..3 {
+        h.assert_row(row, "   PROT");
+    }
+    h.assert_cursor_pos(10, 2);
+}
+
+/// `CSI ? 0 J` from the middle of row 2 and `CSI ? 1 J` from the middle of
+/// row 2.
+#[test]
+fn decsed_partial() {
+    let mut h = protected_screen();
+    h.feed_str("\x1b[2;9H\x1b[?0J");
+    h.assert_row(0, "ab PROT cd");
+    h.assert_row(1, "ab PROT");
+    h.assert_row(2, "   PROT");
+
+    let mut h = protected_screen();
+    h.feed_str("\x1b[2;2H\x1b[?1J");
+    h.assert_row(0, "   PROT");
+    h.assert_row(1, "   PROT cd");
+    h.assert_row(2, "ab PROT cd");
+}
+
+/// Selective erase keeps the attributes of the characters it erases.
+#[test]
+fn decsed_keeps_attributes() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1mbold\x1b[m\x1b[?2J");
+
+    h.assert_row(0, "");
+    let tag = h.state.handler.buffer().visible_rows(0)[0]
+        .resolve_cell(0)
+        .tag()
+        .clone();
+    assert_eq!(tag.font_weight, FontWeight::Bold);
+}
+
+// ─── DECSCA ──────────────────────────────────────────────────────────────────
+
+/// SGR 0 does not end protection; only DECSCA does.
+#[test]
+fn sgr_reset_keeps_protection() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1\"q\x1b[1mA\x1b[mB\x1b[2\"qC\x1b[?2J");
+
+    h.assert_row(0, "AB");
+}
+
+/// DECSERA honours DECSCA too.
+#[test]
+fn decsera_spares_protected_text() {
+    let mut h = protected_screen();
+    h.feed_str("\x1b[1;1;2;10${");
+
+    h.assert_row(0, "   PROT");
+    h.assert_row(1, "   PROT");
+    h.assert_row(2, "ab PROT cd");
+}
+
+// ─── Non-selective erases ────────────────────────────────────────────────────
+
+/// ED, EL and DECERA erase protected text as before.
+#[test]
+fn plain_erases_ignore_protection() {
+    let mut h = protected_screen();
+    h.feed_str("\x1b[1;1H\x1b[2K");
+    h.feed_str("\x1b[2;1;2;10$z");
+    h.assert_row(0, "");
+    h.assert_row(1, "");
+    h.assert_row(2, "ab PROT cd");
+
+    h.feed_str("\x1b[2J");
+    h.assert_row(2, "");
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! xterm palette stack — XTPUSHCOLORS, XTPOPCOLORS and XTREPORTCOLORS.
+//!
+//! ## Coverage
+//!
+//! - **XTPUSHCOLORS** — `CSI # P` pushes the OSC 4 palette and the OSC
+//!   10/11/12 colors; `CSI Ps # P` stores them in slot `Ps`
+//! - **XTPOPCOLORS** — `CSI # Q` pops them back, undoing later OSC 4 and
+//!   OSC 10/11/12 changes; `CSI Ps # Q` restores slot `Ps` without popping
+//! - **XTREPORTCOLORS** — `CSI # R` answers `CSI depth ; stored # Q`
+//! - **RIS** — empties the stack
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use vttest_common::VtTestHelper;
+
+fn reply(h: &mut VtTestHelper, query: &str) -> String {
+    h.feed_str(query);
+    String::from_utf8(h.drain_pty_writes_concatenated()).unwrap()
+}
+
+/// A theme change between push and pop is undone exactly.
+#[test]
+fn pop_restores_palette_and_dynamic_colors() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]4;1;rgb:11/22/33\x07\x1b]10;rgb:01/02/03\x07");
+    let before_palette = h.state.handler.palette().clone();
+    let before_fg = reply(&mut h, "\x1b]10;?\x07");
+    let before_cursor = reply(&mut h, "\x1b]12;?\x07");
+
+    h.feed_str("\x1b[#P");
+    h.feed_str("\x1b]4;1;rgb:ff/ff/ff\x07\x1b]4;200;rgb:00/00/00\x07");
+    h.feed_str("\x1b]10;rgb:aa/aa/aa\x07\x1b]12;rgb:bb/bb/bb\x07");
+    h.feed_str("\x1b[#Q");
+
+    assert_eq!(h.state.handler.palette(), &before_palette);
+    assert_eq!(reply(&mut h, "\x1b]10;?\x07"), before_fg);
+    assert_eq!(reply(&mut h, "\x1b]12;?\x07"), before_cursor);
+}
+
+/// Pushes nest; each pop restores the matching push.
+#[test]
+fn pushes_nest() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b]11;rgb:00/00/01\x07\x1b[#P");
+    h.feed_str("\x1b]11;rgb:00/00/02\x07\x1b[#P");
+    h.feed_str("\x1b]11;rgb:00/00/03\x07");
+
+    h.feed_str("\x1b[#Q");
+    assert_eq!(reply(&mut h, "\x1b]11;?\x07"), "\x1b]11;rgb:00/00/02\x1b\\");
+    h.feed_str("\x1b[#Q");
+    assert_eq!(reply(&mut h, "\x1b]11;?\x07"), "\x1b]11;rgb:00/00/01\x1b\\");
+}
+
+/// The report counts the stack and the numbered slots.
+#[test]
+fn report_gives_depth_and_stored_count() {
+    let mut h = VtTestHelper::new_default();
+    assert_eq!(reply(&mut h, "\x1b[#R"), "\x1b[0;0#Q");
+
+    h.feed_str("\x1b[#P\x1b[#P\x1b[7#P");
+    assert_eq!(reply(&mut h, "\x1b[#R"), "\x1b[2;3#Q");
+
+    h.feed_str("\x1b[#Q\x1b[7#Q");
+    assert_eq!(reply(&mut h, "\x1b[#R"), "\x1b[1;2#Q");
+}
+
+/// RIS empties the stack.
+#[test]
+fn reset_empties_the_stack() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[#P\x1b[3#P\x1bc");
+    assert_eq!(reply(&mut h, "\x1b[#R"), "\x1b[0;0#Q");
+}
@@ -0,0 +1,117 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! xterm SGR stack — XTPUSHSGR, XTPOPSGR and XTREPORTSGR.
+//!
+//! ## Coverage
+//!
+//! - **XTPUSHSGR** — `CSI # {` saves the whole rendition, `CSI Pm # {` only
+//!   the listed attribute groups
+//! - **XTPOPSGR** — `CSI # }` restores the saved groups; popping an empty
+//!   stack does nothing
+//! - **XTREPORTSGR** — `CSI Pt ; Pl ; Pb ; Pr # |` answers with the SGR shared
+//!   by every character in the rectangle
+//!
+//! All cursor positions in the helper API are **0-indexed** (`x` = column,
+//! `y` = row). CSI sequences use **1-indexed** coordinates.
+
+#![allow(clippy::unwrap_used)]
+
+mod vttest_common;
+
+use freminal_common::{
+    buffer_states::{fonts::FontWeight, format_tag::FormatTag},
+    colors::TerminalColor,
+};
+use vttest_common::VtTestHelper;
+
+/// The tag of the cell at (`x`, `y`).
+fn cell_tag(h: &VtTestHelper, x: usize, y: usize) -> FormatTag {
+    h.state.handler.buffer().visible_rows(0)[y]
+        .resolve_cell(x)
+        .tag()
+        .clone()
+}
+
+// ─── XTPUSHSGR / XTPOPSGR ────────────────────────────────────────────────────
+
+/// Push everything, reset, pop: the text after the pop is bold red again.
+#[test]
+fn pop_restores_whole_rendition() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1;31m\x1b[#{\x1b[0mA\x1b[#}B");
+
+    h.assert_row(0, "AB");
+    let plain = cell_tag(&h, 0, 0);
+    assert_eq!(plain.font_weight, FontWeight::Normal);
+    assert_eq!(plain.colors.color, TerminalColor::Default);
+
+    let restored = cell_tag(&h, 1, 0);
+    assert_eq!(restored.font_weight, FontWeight::Bold);
+    assert_eq!(restored.colors.color, TerminalColor::Red);
+}
+
+/// `CSI 30 # {` saves only the foreground; bold set after the push survives
+/// the pop.
+#[test]
+fn pop_restores_only_pushed_groups() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[31m\x1b[30#{\x1b[32;1m\x1b[#}A");
+
+    let tag = cell_tag(&h, 0, 0);
+    assert_eq!(tag.colors.color, TerminalColor::Red);
+    assert_eq!(tag.font_weight, FontWeight::Bold);
+}
+
+/// Pushes nest, and a pop with nothing saved leaves the rendition alone.
+#[test]
+fn pushes_nest_and_extra_pops_are_ignored() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[31m\x1b[#{\x1b[32m\x1b[#{\x1b[33m");
+    h.feed_str("\x1b[#}A\x1b[#}B\x1b[#}C");
+
+    assert_eq!(cell_tag(&h, 0, 0).colors.color, TerminalColor::Green);
+    assert_eq!(cell_tag(&h, 1, 0).colors.color, TerminalColor::Red);
+    assert_eq!(cell_tag(&h, 2, 0).colors.color, TerminalColor::Red);
+}
+
+// ─── XTREPORTSGR ─────────────────────────────────────────────────────────────
+
+/// The report lists what every character in the rectangle has in common.
+#[test]
+fn report_lists_shared_rendition() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[1;31mA\x1b[22mB\x1b[0m");
+    let _ = h.drain_pty_writes_concatenated();
+
+    h.feed_str("\x1b[1;1;1;1#|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0;1;31m");
+
+    h.feed_str("\x1b[1;1;1;2#|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0;31m");
+}
+
+/// A rectangle of default cells reports a plain reset.
+#[test]
+fn report_on_blank_area_is_reset() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[5;5;6;10#|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0m");
+}
+
+/// Blink is reported when every character blinks, and dropped when one
+/// does not.
+#[test]
+fn report_includes_shared_blink() {
+    let mut h = VtTestHelper::new_default();
+    h.feed_str("\x1b[5mAB\x1b[25mC\x1b[0m");
+    let _ = h.drain_pty_writes_concatenated();
+
+    h.feed_str("\x1b[1;1;1;2#|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0;5m");
+
+    h.feed_str("\x1b[1;1;1;3#|");
+    assert_eq!(h.drain_pty_writes_concatenated(), b"\x1b[0m");
+}
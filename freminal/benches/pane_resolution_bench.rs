@@ -148,6 +148,7 @@ fn make_bench_pane(id: PaneId) -> Pane {
         search_buffer_rx,
         pty_dead_rx,
         command_event_rx,
+        tmux_event_rx: crossbeam_channel::never(),
         echo_off: Arc::new(AtomicBool::new(false)),
         child_pid: None,
         history_seed: new_seeded_history(),
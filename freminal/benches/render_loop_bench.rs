@@ -34,6 +34,7 @@ use freminal::gui::renderer::{
     BackgroundFrame, FgRenderOptions, build_background_instances, build_foreground_instances,
 };
 use freminal::gui::shaping::ShapingCache;
+use freminal_common::colors::SpecialColors;
 use freminal_common::config::Config;
 use freminal_common::cursor::CursorVisualStyle;
 use freminal_common::themes::CATPPUCCIN_MOCHA;
@@ -659,6 +660,7 @@ fn bench_bg_instances(c: &mut Criterion) {
                             theme: &CATPPUCCIN_MOCHA,
                             cursor_color_override: None,
                             reverse_screen: false,
+                            special_colors: SpecialColors::new(),
                         },
                         &mut instances,
                         &mut deco,
@@ -776,6 +778,7 @@ fn bench_bg_instances_partial_dirty(c: &mut Criterion) {
                         theme: &CATPPUCCIN_MOCHA,
                         cursor_color_override: None,
                         reverse_screen: false,
+                        special_colors: SpecialColors::new(),
                     },
                     &mut instances,
                     &mut deco,
@@ -816,6 +819,7 @@ fn bench_bg_instances_partial_dirty(c: &mut Criterion) {
                         theme: &CATPPUCCIN_MOCHA,
                         cursor_color_override: None,
                         reverse_screen: false,
+                        special_colors: SpecialColors::new(),
                     },
                     &mut instances,
                     &mut deco,
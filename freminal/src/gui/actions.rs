@@ -94,6 +94,12 @@ impl PerWindowState {
 
 impl super::FreminalGui {
     pub(super) fn close_focused_pane(ui: &egui::Ui, win: &mut PerWindowState) {
+        // A tmux pane is killed in tmux; the native pane closes when tmux
+        // drops it from the layout.
+        if win.tmux_kill_active_pane() {
+            return;
+        }
+
         let tab = win.tabs.active_tab_mut();
         let target = tab.active_pane;
 
@@ -556,6 +562,12 @@ impl super::FreminalGui {
                 // so the file is fully flushed before we return.
                 self.toggle_recording();
             }
+            KeyAction::DumpFlightRecorder => {
+                // Snapshot this window's flight recorder ring into a new
+                // FREC file; the ring keeps running.  Reports the path (or
+                // why it failed) as a toast.
+                self.dump_flight_recorder_with_toast(window_id);
+            }
             KeyAction::RenameTab => {
                 // Begin an inline rename on the active tab.  The tab bar
                 // renders a TextEdit in place of the label while
@@ -936,6 +948,7 @@ mod tests {
             search_buffer_rx,
             pty_dead_rx,
             command_event_rx,
+            tmux_event_rx: crossbeam_channel::never(),
             recent_commands: std::collections::VecDeque::new(),
             history_seed: crate::gui::shell_history::new_seeded_history(),
             shell_program: None,
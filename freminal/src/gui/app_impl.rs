@@ -441,8 +441,7 @@ struct ChromeSignalInputs {
 /// snapshot build than it is today.
 fn stage_chrome_signals(
     win: &PerWindowState,
-    tab_title_policy: freminal_common::config::TabTitlePolicy,
-    tab_title_separator: &str,
+    tab_title_config: &freminal_common::config::TabTitleConfig,
     pane_layout: &[(panes::PaneId, egui::Rect)],
     zoomed_pane: Option<panes::PaneId>,
     per_pane_damage: &[frame_damage::PaneDamageInput],
@@ -457,10 +456,7 @@ fn stage_chrome_signals(
         tab_titles: win
             .tabs
             .iter()
-            .map(|t| {
-                t.display_name(tab_title_policy, tab_title_separator)
-                    .into_owned()
-            })
+            .map(|t| t.display_name(tab_title_config).into_owned())
             .collect(),
         pane_ids: pane_layout.iter().map(|(id, _)| *id).collect(),
         zoomed_pane,
@@ -525,15 +521,40 @@ impl freminal_windowing::App for FreminalGui {
             return;
         }
 
+        // Give the window its recording swap and flight recorder before
+        // any of its panes exist, so they record from their first event.
+        self.open_window_recording(window_id);
+
         let os_dark_mode = ctx.global_style().visuals.dark_mode;
 
         if let Some(initial) = self.initial_state.take() {
+            // A `--play` run has no shell: no session auto-save, no remote
+            // control, and the window shows the recording.
+            if self.playback.is_some() {
+                self.create_first_window_for_playback(
+                    window_id,
+                    ctx,
+                    handle,
+                    os_dark_mode,
+                    initial.repaint_handle,
+                    initial.window_post,
+                );
+                return;
+            }
+
             // Start the periodic session auto-save timer, bound to the first
             // window's repaint handle so it can wake the (otherwise sleeping)
             // event loop when a save is due.  Spawned exactly once, here at
             // first-window creation.
             self.spawn_session_autosave_timer(Arc::clone(&initial.repaint_handle));
 
+            // Bind the remote-control socket (when enabled) before the first
+            // PTY spawns so every shell inherits `FREMINAL_LISTEN_ON`.
+            self.start_remote_control(Arc::clone(&initial.repaint_handle));
+
+            // Attach the redaction rules to whatever is recording.
+            self.sync_redaction();
+
             // First window — spawn the initial PTY tab now, or if a
             // startup layout/session-restore applies, delegate to the
             // layout machinery (which will build the tabs itself and
@@ -618,6 +639,7 @@ impl freminal_windowing::App for FreminalGui {
                 super::pty::PtyTabInitialState {
                     theme,
                     auto_detect_urls: self.config.ui.auto_detect_urls,
+                    allow_checksum_reports: self.config.security.allow_checksum_reports,
                     cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
                         &self.config.cursor.shape,
                         self.config.cursor.blink,
@@ -629,7 +651,7 @@ impl freminal_windowing::App for FreminalGui {
                     cwd: None,
                     shell_override: None,
                     extra_env: None,
-                    recording_swap: self.recording_swap.clone(),
+                    recording_swap: self.recording_swap(window_id),
                     recording_pane_id: pane_id.raw().try_into().unwrap_or(u32::MAX),
                     set_term_program: self.config.shell_integration.set_term_program,
                 },
@@ -722,6 +744,7 @@ impl freminal_windowing::App for FreminalGui {
                         chrome_frames_rendered: 0,
                         pending_terminal_requested_delay: None,
                         frame_stats: super::window::FrameStats::default(),
+                        tmux_sessions: Vec::new(),
                     };
                     self.windows.insert(window_id, win);
 
@@ -855,9 +878,10 @@ impl freminal_windowing::App for FreminalGui {
 
         self.windows.remove(&window_id);
 
-        // Emit WindowClose recording event (only for known windows), and clean up the mapping.
-        if let Some(rec_wid) = self.recording_window_ids.remove(&window_id)
-            && let Some(h) = self.recording_swap.load_full()
+        // Emit WindowClose recording event (only for known windows), drop the
+        // window's recording swap and flight recorder, and clean up the mapping.
+        if let Some(&rec_wid) = self.recording_window_ids.get(&window_id)
+            && let Some(h) = self.recording_handle_for(window_id)
         {
             h.emit(
                 freminal_terminal_emulator::recording::EventPayload::WindowClose {
@@ -865,6 +889,8 @@ impl freminal_windowing::App for FreminalGui {
                 },
             );
         }
+        self.close_window_recording(window_id);
+        self.recording_window_ids.remove(&window_id);
 
         true
     }
@@ -1091,8 +1117,9 @@ impl freminal_windowing::App for FreminalGui {
                         active_tab.pane_tree.find(pane_id).map(|pane| {
                             let snap = pane.arc_swap.load();
                             PaneSnapshotInputs {
-                                mouse_tracking_active: snap.mouse_tracking
-                                    != freminal_common::buffer_states::modes::mouse::MouseTrack::NoTracking,
+                                mouse_tracking_active: snap.locator_enabled
+                                    || snap.mouse_tracking
+                                        != freminal_common::buffer_states::modes::mouse::MouseTrack::NoTracking,
                                 has_urls: snap.has_urls,
                                 scroll_offset: snap.scroll_offset,
                                 is_alternate_screen: snap.is_alternate_screen,
@@ -1312,6 +1339,12 @@ impl freminal_windowing::App for FreminalGui {
         // window returned above).  Cheap no-op when not due.
         self.poll_session_autosave();
 
+        // ── Remote-control calls ─────────────────────────────────────────────
+        // Connection threads queue `freminal msg` commands and wake the event
+        // loop; run them here, before this window's state is taken out of
+        // `self.windows`, so a command can target any window.
+        self.poll_remote_control(handle);
+
         // ── Focus or create settings window (deferred from menu/keybind) ─────
         if self.pending_focus_settings {
             self.pending_focus_settings = false;
@@ -1543,25 +1576,33 @@ impl freminal_windowing::App for FreminalGui {
         // it goes permanently stale for every other pane (regression that
         // left the visual bell overlay stuck; see `paint_bell_flash`).
         let window_focus = WindowFocus::from_bool(ctx.input(|i| i.focused));
-        let tab_title_policy = self.config.tab_title.policy;
-        let tab_title_separator = self.config.tab_title.separator.clone();
+        let tab_title_config = self.config.tab_title.clone();
         drain_command_finished_events(
             &mut win.tabs,
-            tab_title_policy,
-            &tab_title_separator,
+            &tab_title_config,
             &self.config.notifications,
             &self.config.bell,
             window_focus,
             &self.toasts,
         );
 
+        // ── Apply tmux control-mode events ────────────────────────────────────
+        // Runs before the dead-pane poll so mirrored panes that tmux dropped
+        // this frame are closed by it. See `gui::tmux`.
+        self.drain_tmux_events(&mut win);
+
+        // ── Advance `--play` playback ─────────────────────────────────────────
+        // Feeds the recorded panes up to the clock's position, rebuilding the
+        // tabs when the recorded tree changed. See `gui::playback`.
+        self.advance_playback(&mut win, ctx);
+
         // ── Poll all tabs for PTY death signals ───────────────────────────────
         // See `process_dead_panes` for the full description; extracted as a
         // zero-egui, zero-`self.windows` helper (Task 122.7). It cannot
         // itself close the window, so a `CloseWindow` outcome means the
         // caller must perform the reinsert-and-close dance that the
         // original inline code did directly.
-        match process_dead_panes(&mut win, &self.recording_swap) {
+        match process_dead_panes(&mut win, &self.recording_swap(window_id)) {
             DeadPaneOutcome::Continue => {}
             DeadPaneOutcome::CloseWindow => {
                 self.windows.insert(window_id, win);
@@ -1710,6 +1751,12 @@ impl freminal_windowing::App for FreminalGui {
                 self.dispatch_tab_bar_action(tab_action, &mut win);
             }
 
+            if self.playback.is_some() {
+                let bar = Panel::bottom("playback_bar")
+                    .show(&mut root_ui, |ui| self.show_playback_bar(ui));
+                head_rects.push(bar.response.rect);
+            }
+
             win.published.publish_chrome_head_rects(head_rects);
 
             (menu_open, Some(root_ui))
@@ -2526,12 +2573,20 @@ impl freminal_windowing::App for FreminalGui {
                     continue;
                 };
 
+                // A DEC status line (DECSSDT) takes the pane's bottom row, so
+                // the grid gets one row fewer while it is shown.
+                let grid_height_chars = if pane.arc_swap.load().status_line.is_some() {
+                    pane_height_chars.saturating_sub(1).max(1)
+                } else {
+                    pane_height_chars
+                };
+
                 // Debounced resize: only send when char dims changed.
-                let new_size = (pane_width_chars, pane_height_chars);
+                let new_size = (pane_width_chars, grid_height_chars);
                 if new_size != pane.view_state.last_sent_size {
                     if let Err(e) = pane.input_tx.send(InputEvent::Resize(
                         pane_width_chars,
-                        pane_height_chars,
+                        grid_height_chars,
                         font_width,
                         font_height,
                     )) {
@@ -2649,7 +2704,7 @@ impl freminal_windowing::App for FreminalGui {
                 // Hold the Arc locally so the borrow in `RecordingContext.handle`
                 // remains valid for the lifetime of `rec_ctx`.
                 let rec_window_id = self.recording_window_id(window_id);
-                let rec_handle = self.recording_swap.load_full();
+                let rec_handle = self.recording_handle_for(window_id);
                 let rec_ctx = rec_handle.as_ref().map(|h| {
                     freminal_terminal_emulator::recording::RecordingContext {
                         handle: h,
@@ -2657,6 +2712,9 @@ impl freminal_windowing::App for FreminalGui {
                         // Saturating `u64 -> u32` for recording: pane IDs are
                         // monotonic from 0 and never approach u32::MAX.
                         pane_id: u32::try_from(pane_id.raw()).unwrap_or(u32::MAX),
+                        // Unlike `is_echo_off` above, not gated on the
+                        // password indicator setting.
+                        echo_off: pane.echo_off.load(std::sync::atomic::Ordering::Relaxed),
                     }
                 });
 
@@ -3131,13 +3189,13 @@ impl freminal_windowing::App for FreminalGui {
             // `win.pending_chrome_damage` near the end of `update()`.
             let (chrome_tab_snapshot, chrome_signals) = stage_chrome_signals(
                 &win,
-                self.config.tab_title.policy,
-                &self.config.tab_title.separator,
+                &self.config.tab_title,
                 &pane_layout,
                 zoomed_pane,
                 &per_pane_damage,
                 &ChromeSignalInputs {
-                    ui_overlay_open,
+                    // The playback bar's timeline moves every frame.
+                    ui_overlay_open: ui_overlay_open || self.playback.is_some(),
                     chrome_style_changed,
                     active_pane_changed,
                     shader_recomposites,
@@ -3582,10 +3640,7 @@ impl freminal_windowing::App for FreminalGui {
             // calling `send_viewport_cmd` unconditionally every frame triggers
             // an infinite repaint loop (~3 % idle CPU).
             let active_tab = win.tabs.active_tab();
-            let active_title = active_tab.display_name(
-                self.config.tab_title.policy,
-                &self.config.tab_title.separator,
-            );
+            let active_title = active_tab.display_name(&self.config.tab_title);
             let window_title = if active_title.is_empty() {
                 "Freminal"
             } else {
@@ -4250,6 +4305,7 @@ impl FreminalGui {
             super::pty::PtyTabInitialState {
                 theme,
                 auto_detect_urls: self.config.ui.auto_detect_urls,
+                allow_checksum_reports: self.config.security.allow_checksum_reports,
                 cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
                     &self.config.cursor.shape,
                     self.config.cursor.blink,
@@ -4261,7 +4317,7 @@ impl FreminalGui {
                 cwd: None,
                 shell_override: None,
                 extra_env: None,
-                recording_swap: self.recording_swap.clone(),
+                recording_swap: self.recording_swap(window_id),
                 recording_pane_id: pane_id.raw().try_into().unwrap_or(u32::MAX),
                 set_term_program: self.config.shell_integration.set_term_program,
             },
@@ -4328,6 +4384,61 @@ impl FreminalGui {
         self.windows.insert(window_id, win);
     }
 
+    /// Create the first window of a `--play` run, showing the recording's
+    /// first window as read-only panes (see `gui::playback`).
+    fn create_first_window_for_playback(
+        &mut self,
+        window_id: WindowId,
+        ctx: &egui::Context,
+        handle: &freminal_windowing::WindowHandle<'_>,
+        os_dark_mode: bool,
+        repaint_handle: Arc<std::sync::OnceLock<(freminal_windowing::RepaintProxy, WindowId)>>,
+        window_post: Arc<Mutex<WindowPostRenderer>>,
+    ) {
+        let proxy = handle.event_loop_proxy();
+        let _ = repaint_handle.set((proxy, window_id));
+
+        let theme = freminal_common::themes::by_slug(self.config.theme.active_slug(os_dark_mode))
+            .unwrap_or(&freminal_common::themes::CATPPUCCIN_MOCHA);
+        rendering::set_egui_options(
+            ctx,
+            theme,
+            self.config.ui.background_opacity,
+            &self.gui_theme,
+        );
+
+        let terminal_widget = FreminalTerminalWidget::new(ctx, &self.config).unwrap_or_else(|e| {
+            tracing::error!("fatal: failed to initialise terminal widget (font manager): {e}");
+            std::process::exit(1);
+        });
+
+        // The window needs tabs to exist; the first frame's playback advance
+        // rebuilds them and feeds the initial replay.
+        let mut tabs = self
+            .playback_tabs(&super::playback::PaneEnv {
+                repaint_handle: &repaint_handle,
+                window_post: &window_post,
+                cell_size: terminal_widget.cell_size(),
+                os_dark_mode,
+            })
+            .into_iter();
+        let Some(first) = tabs.next() else {
+            return;
+        };
+
+        let mut win = Self::new_per_window_state(
+            first,
+            terminal_widget,
+            os_dark_mode,
+            window_post,
+            repaint_handle,
+        );
+        for tab in tabs {
+            win.tabs.add_tab(tab);
+        }
+        self.windows.insert(window_id, win);
+    }
+
     /// Render the fatal-error panel for a window that has no
     /// [`PerWindowState`] because the only/last shell failed to spawn.
     ///
@@ -4410,6 +4521,7 @@ impl FreminalGui {
             chrome_frames_rendered: 0,
             pending_terminal_requested_delay: None,
             frame_stats: super::window::FrameStats::default(),
+            tmux_sessions: Vec::new(),
         }
     }
 
@@ -11,6 +11,7 @@
 //! Uses shelf-based bin packing with LRU eviction.
 
 use conv2::{ApproxFrom, ValueFrom};
+use freminal_common::buffer_states::soft_font::SoftFont;
 use rustc_hash::FxHashMap;
 
 use swash::scale::image::Content;
@@ -160,6 +161,12 @@ pub struct GlyphAtlas {
     /// char is a sufficient key. Stored separately from `entries` so it never
     /// collides with font `GlyphKey`s.
     procedural_entries: FxHashMap<char, AtlasEntry>,
+    /// DECDLD soft-font glyphs, keyed by their private-use char and scaled
+    /// to the cell like `procedural_entries`.  Valid for the soft font
+    /// generation in `soft_font_generation`; a reload drops them all.
+    soft_font_entries: FxHashMap<char, AtlasEntry>,
+    /// The [`SoftFont::generation`] `soft_font_entries` were drawn from.
+    soft_font_generation: u64,
     /// Shelf list, ordered by Y position.
     shelves: Vec<Shelf>,
     /// Global LRU generation counter, incremented on each lookup/insert.
@@ -193,6 +200,8 @@ impl GlyphAtlas {
             pixels: vec![0u8; pixel_count],
             entries: FxHashMap::default(),
             procedural_entries: FxHashMap::default(),
+            soft_font_entries: FxHashMap::default(),
+            soft_font_generation: 0,
             shelves: Vec::new(),
             generation: 0,
             scale_ctx: ScaleContext::new(),
@@ -304,6 +313,7 @@ impl GlyphAtlas {
     pub fn clear(&mut self) {
         self.entries.clear();
         self.procedural_entries.clear();
+        self.soft_font_entries.clear();
         self.shelves.clear();
         self.pixels.fill(0);
         self.dirty_rects.clear();
@@ -365,6 +375,50 @@ impl GlyphAtlas {
         self.procedural_entries.get(&ch)
     }
 
+    /// Get or insert a DECDLD soft-font glyph, scaled to fill a `w` × `h`
+    /// cell by [`crate::gui::soft_glyphs::generate_alpha`].
+    ///
+    /// Returns `None` if `ch` is not a loaded soft character or the glyph
+    /// cannot be packed into the atlas.
+    pub fn get_or_insert_soft_font(
+        &mut self,
+        ch: char,
+        soft_font: &SoftFont,
+        w: u32,
+        h: u32,
+    ) -> Option<&AtlasEntry> {
+        if soft_font.generation() != self.soft_font_generation {
+            // The old bitmaps stay packed until their shelves are evicted.
+            self.soft_font_entries.clear();
+            self.soft_font_generation = soft_font.generation();
+        }
+
+        if let Some(shelf_idx) = self.soft_font_entries.get(&ch).map(|e| e.shelf_idx) {
+            self.generation += 1;
+            let current_gen = self.generation;
+            if let Some(shelf) = self.shelves.get_mut(shelf_idx) {
+                shelf.last_used = current_gen;
+            }
+            return self.soft_font_entries.get(&ch);
+        }
+
+        let glyph = soft_font.glyph(ch)?;
+        let data =
+            crate::gui::soft_glyphs::generate_alpha(glyph, usize_from_u32(w), usize_from_u32(h));
+        let rasterized = RasterizedGlyph {
+            data,
+            width: w,
+            height: h,
+            bearing_x: 0,
+            bearing_y: 0,
+            is_color: false,
+        };
+
+        let entry = self.insert_rasterized_raw(&rasterized)?;
+        self.soft_font_entries.entry(ch).or_insert(entry);
+        self.soft_font_entries.get(&ch)
+    }
+
     // -----------------------------------------------------------------------
     //  Rasterisation
     // -----------------------------------------------------------------------
@@ -699,6 +753,8 @@ impl GlyphAtlas {
         self.entries.retain(|_, entry| entry.shelf_idx != shelf_idx);
         self.procedural_entries
             .retain(|_, entry| entry.shelf_idx != shelf_idx);
+        self.soft_font_entries
+            .retain(|_, entry| entry.shelf_idx != shelf_idx);
 
         // Reset the shelf's horizontal cursor so it can be reused.
         if let Some(shelf) = self.shelves.get_mut(shelf_idx) {
@@ -769,8 +825,12 @@ impl GlyphAtlas {
         // Procedural box-drawing / block-element entries (Task #410) live in a
         // separate map but reference the same texture, so their UVs must be
         // rescaled identically or cached box/block glyphs would sample the
-        // wrong region after a grow.
-        for entry in self.procedural_entries.values_mut() {
+        // wrong region after a grow.  The same goes for soft-font glyphs.
+        for entry in self
+            .procedural_entries
+            .values_mut()
+            .chain(self.soft_font_entries.values_mut())
+        {
             entry.uv_rect[0] *= scale;
             entry.uv_rect[1] *= scale;
             entry.uv_rect[2] *= scale;
@@ -347,12 +347,7 @@ impl super::FreminalGui {
         let Some(tab) = win.tabs.iter().nth(tab_index) else {
             return Vec::new();
         };
-        let tab_name = tab
-            .display_name(
-                self.config.tab_title.policy,
-                &self.config.tab_title.separator,
-            )
-            .into_owned();
+        let tab_name = tab.display_name(&self.config.tab_title).into_owned();
         let panes = tab.pane_tree.iter_panes().unwrap_or_default();
         gather_tab_running(
             &panes,
@@ -386,12 +381,7 @@ impl super::FreminalGui {
             return Vec::new();
         }
         let tab = win.tabs.active_tab();
-        let tab_name = tab
-            .display_name(
-                self.config.tab_title.policy,
-                &self.config.tab_title.separator,
-            )
-            .into_owned();
+        let tab_name = tab.display_name(&self.config.tab_title).into_owned();
         let target = tab.active_pane;
         let Ok(panes) = tab.pane_tree.iter_panes() else {
             return Vec::new();
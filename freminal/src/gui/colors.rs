@@ -7,6 +7,7 @@ use conv2::ConvUtil;
 use egui::Color32;
 use freminal_common::colors::TerminalColor;
 use freminal_common::themes::ThemePalette;
+use freminal_terminal_emulator::snapshot::TerminalSnapshot;
 
 /// Convert an `(r, g, b)` tuple to a `Color32`.
 #[must_use]
@@ -41,6 +42,21 @@ pub const fn selection_fg_f(theme: &ThemePalette) -> [f32; 4] {
     rgb_to_f32(theme.selection_fg)
 }
 
+/// The snapshot's theme with its OSC 17 / 19 highlight overrides applied,
+/// so the selection — and the command-block hover tint derived from it —
+/// follow them.
+#[must_use]
+pub const fn highlight_theme(snap: &TerminalSnapshot) -> ThemePalette {
+    let mut theme = *snap.theme;
+    if let Some(bg) = snap.highlight_bg_override {
+        theme.selection_bg = bg;
+    }
+    if let Some(fg) = snap.highlight_fg_override {
+        theme.selection_fg = fg;
+    }
+    theme
+}
+
 /// Cursor color as `[f32; 4]` from the active theme, with optional override.
 #[must_use]
 pub const fn cursor_f(
@@ -381,6 +397,17 @@ mod tests {
         check("BrightWhite", TerminalColor::BrightWhite);
     }
 
+    #[test]
+    fn highlight_overrides_replace_the_selection_colors() {
+        let mut snap = TerminalSnapshot::empty();
+        assert_eq!(highlight_theme(&snap), *snap.theme);
+
+        snap.highlight_bg_override = Some((1, 2, 3));
+        let theme = highlight_theme(&snap);
+        assert_eq!(theme.selection_bg, (1, 2, 3));
+        assert_eq!(theme.selection_fg, snap.theme.selection_fg);
+    }
+
     /// Theme-derived accessors produce correct values.
     #[test]
     fn theme_accessor_fns() {
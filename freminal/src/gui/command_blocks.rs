@@ -311,6 +311,7 @@ mod tests {
             end_row: Some(end),
             exit_code: exit,
             cwd: None,
+            command_line: None,
             started_at: started,
             executed_at: Some(started),
             finished_at: Some(started + Duration::from_secs(1)),
@@ -328,6 +329,7 @@ mod tests {
             end_row: None,
             exit_code: None,
             cwd: None,
+            command_line: None,
             started_at: SystemTime::UNIX_EPOCH,
             executed_at: None,
             finished_at: None,
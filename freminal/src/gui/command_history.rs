@@ -123,7 +123,8 @@ pub enum PaletteAction {
 /// Extract the command text for a finished `CommandBlock` from a
 /// snapshot's visible window.
 ///
-/// Returns `None` when:
+/// A command line reported by the shell (`OSC 633 E`) is used as-is,
+/// wherever the block's rows are.  Otherwise returns `None` when:
 ///
 /// - the block has no `command_start_row` (OSC 133 B never fired), or
 /// - the block has no `output_start_row` (OSC 133 C never fired), or
@@ -136,6 +137,11 @@ pub enum PaletteAction {
 /// place of internal newlines so the palette displays them compactly.
 #[must_use]
 pub fn extract_command_text(snap: &TerminalSnapshot, block: &CommandBlock) -> Option<String> {
+    if let Some(command_line) = block.command_line.as_deref() {
+        let trimmed = command_line.trim();
+        return (!trimmed.is_empty()).then(|| trimmed.to_owned());
+    }
+
     let cmd_start_buf = block.command_start_row?;
     let cmd_end_buf = block.output_start_row?;
     if cmd_end_buf < cmd_start_buf {
@@ -632,6 +638,7 @@ mod tests {
             end_row: Some(output_start.unwrap_or(prompt_row) + 1),
             exit_code,
             cwd: None,
+            command_line: None,
             started_at: SystemTime::UNIX_EPOCH,
             executed_at: Some(SystemTime::UNIX_EPOCH),
             finished_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1)),
@@ -663,6 +670,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extract_command_text_prefers_reported_command_line() {
+        // The rows have scrolled away, but the shell reported the command.
+        let snap = make_snapshot(&["a", "b"], 10);
+        let mut block = finished_block(2, None, Some(3), Some(0));
+        block.command_line = Some("  cargo test --workspace ".to_owned());
+        assert_eq!(
+            extract_command_text(&snap, &block),
+            Some("cargo test --workspace".to_owned())
+        );
+    }
+
     #[test]
     fn extract_command_text_returns_none_when_command_start_missing() {
         let snap = make_snapshot(&["$ ", "ls"], 0);
@@ -44,7 +44,7 @@
 //! per-frame `central_body` helpers would turn this module into a
 //! grab-bag instead of the one real concept above.
 
-use freminal_common::config::{BellConfig, Config, NotificationsConfig, TabTitlePolicy};
+use freminal_common::config::{BellConfig, Config, NotificationsConfig, TabTitleConfig};
 use freminal_common::pty_write::PtyWrite;
 use freminal_terminal_emulator::io::InputEvent;
 use freminal_terminal_emulator::recording::RecordingSwap;
@@ -127,8 +127,7 @@ pub(super) enum DeadPaneOutcome {
 /// phases or interleave routing into the loop.
 pub(super) fn drain_command_finished_events(
     tabs: &mut TabManager,
-    tab_title_policy: TabTitlePolicy,
-    tab_title_separator: &str,
+    tab_title_config: &TabTitleConfig,
     notifications_config: &NotificationsConfig,
     bell_config: &BellConfig,
     window_focus: WindowFocus,
@@ -143,9 +142,7 @@ pub(super) fn drain_command_finished_events(
         // `tab` mutably, so `display_name` cannot be called inside the
         // inner loop. Used for the `{tab_name}` notification template
         // token (Task 76.5).
-        let tab_name = tab
-            .display_name(tab_title_policy, tab_title_separator)
-            .into_owned();
+        let tab_name = tab.display_name(tab_title_config).into_owned();
         if let Ok(panes) = tab.pane_tree.iter_panes_mut() {
             for pane in panes {
                 while let Ok(event) = pane.command_event_rx.try_recv() {
@@ -484,7 +481,7 @@ mod tests {
     use crate::gui::tabs::{Tab, TabId, TabManager};
     use freminal_common::buffer_states::command_block::{CommandBlock, CommandBlockId};
     use freminal_common::buffer_states::window_manipulation::WindowManipulation;
-    use freminal_common::config::{BellConfig, Config, NotificationsConfig, TabTitlePolicy};
+    use freminal_common::config::{BellConfig, Config, NotificationsConfig, TabTitleConfig};
     use freminal_terminal_emulator::io::WindowCommand;
     use freminal_terminal_emulator::snapshot::TerminalSnapshot;
     use std::sync::Arc;
@@ -570,6 +567,7 @@ mod tests {
             ))),
             render_cache: crate::gui::terminal::PaneRenderCache::new(),
             command_event_rx,
+            tmux_event_rx: crossbeam_channel::never(),
             recent_commands: std::collections::VecDeque::new(),
             history_seed: crate::gui::shell_history::new_seeded_history(),
             shell_program: None,
@@ -605,6 +603,7 @@ mod tests {
             end_row: Some(1),
             exit_code,
             cwd: None,
+            command_line: None,
             started_at: executed,
             executed_at: Some(executed),
             finished_at: Some(executed + Duration::from_secs(dur_secs)),
@@ -642,8 +641,7 @@ mod tests {
 
         drain_command_finished_events(
             &mut tabs,
-            TabTitlePolicy::default(),
-            "",
+            &TabTitleConfig::default(),
             &notifications_config,
             &bell_config,
             WindowFocus::Unfocused,
@@ -702,8 +700,7 @@ mod tests {
         // rather than also overriding the routing policy.
         drain_command_finished_events(
             &mut tabs,
-            TabTitlePolicy::default(),
-            "",
+            &TabTitleConfig::default(),
             &notifications_config,
             &bell_config,
             WindowFocus::Focused,
@@ -742,8 +739,7 @@ mod tests {
 
         drain_command_finished_events(
             &mut tabs,
-            TabTitlePolicy::default(),
-            "",
+            &TabTitleConfig::default(),
             &notifications_config,
             &bell_config,
             WindowFocus::Unfocused,
@@ -63,6 +63,10 @@ pub enum ChromeIcon {
     Plus,
     /// Warning triangle — keybinding-conflict and risky-paste warnings.
     Warning,
+    /// Play — start `--play` playback.
+    Play,
+    /// Pause — pause `--play` playback.
+    Pause,
 }
 
 impl ChromeIcon {
@@ -80,6 +84,8 @@ impl ChromeIcon {
         Self::Minus,
         Self::Plus,
         Self::Warning,
+        Self::Play,
+        Self::Pause,
     ];
 
     /// The Nerd Font codepoint for this icon.
@@ -111,6 +117,10 @@ impl ChromeIcon {
             Self::Plus => '\u{f067}',
             // nf-fa-warning (exclamation triangle)
             Self::Warning => '\u{f071}',
+            // nf-fa-play
+            Self::Play => '\u{f04b}',
+            // nf-fa-pause
+            Self::Pause => '\u{f04c}',
         }
     }
 
@@ -183,7 +193,7 @@ mod tests {
             codepoints.len(),
             "duplicate codepoints in ChromeIcon::ALL"
         );
-        assert_eq!(before, 10, "ChromeIcon::ALL must list every variant");
+        assert_eq!(before, 12, "ChromeIcon::ALL must list every variant");
     }
 
     #[test]
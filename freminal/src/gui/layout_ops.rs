@@ -734,6 +734,7 @@ impl FreminalGui {
             chrome_frames_rendered: 0,
             pending_terminal_requested_delay: None,
             frame_stats: super::window::FrameStats::default(),
+            tmux_sessions: Vec::new(),
         };
         self.windows.insert(window_id, win);
 
@@ -752,7 +753,7 @@ impl FreminalGui {
         inner_size: (u32, u32),
     ) {
         let rec_wid = self.recording_window_id(window_id);
-        if let Some(h) = self.recording_swap.load_full() {
+        if let Some(h) = self.recording_handle_for(window_id) {
             h.emit(
                 freminal_terminal_emulator::recording::EventPayload::WindowCreate {
                     window_id: rec_wid,
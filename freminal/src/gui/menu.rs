@@ -4,7 +4,8 @@
 // https://opensource.org/licenses/MIT.
 
 use egui;
-use freminal_common::config::TabTitlePolicy;
+use freminal_common::buffer_states::osc_conemu::{ProgressState, TaskProgress};
+use freminal_common::config::TabTitleConfig;
 use freminal_common::keybindings::KeyAction;
 
 use super::TabBarAction;
@@ -142,7 +143,7 @@ impl super::FreminalGui {
         }
 
         let session_resp = ui.menu_button("Session", |ui| {
-            self.show_session_menu(ui, win);
+            self.show_session_menu(ui, win, window_id);
         });
         if session_resp.inner.is_some() {
             *any_menu_open = true;
@@ -327,7 +328,12 @@ impl super::FreminalGui {
     /// When a recording is in progress, the destination path is shown as
     /// a dimmed, non-interactive line below the toggle so the user can
     /// see where the file is being written.
-    fn show_session_menu(&mut self, ui: &mut egui::Ui, win: &mut PerWindowState) {
+    fn show_session_menu(
+        &mut self,
+        ui: &mut egui::Ui,
+        win: &mut PerWindowState,
+        window_id: super::WindowId,
+    ) {
         let recording = self.is_recording();
         let label = if recording {
             "Stop Recording"
@@ -342,6 +348,21 @@ impl super::FreminalGui {
             ui.close();
         }
 
+        // Only meaningful while `[recording] flight_recorder` is on.
+        let dump_resp = ui.add_enabled(
+            self.is_flight_recording(window_id),
+            self.menu_button_for("Save Flight Recorder", KeyAction::DumpFlightRecorder),
+        );
+        if dump_resp.clicked() {
+            self.dump_flight_recorder_with_toast(window_id);
+            ui.close();
+        }
+        if !self.is_flight_recording(window_id) {
+            dump_resp.on_disabled_hover_text(
+                "Enable the flight recorder in Settings → Logging to keep recent history.",
+            );
+        }
+
         if recording && let Some(path) = self.recording_path.as_ref() {
             ui.separator();
             ui.add_enabled(
@@ -647,8 +668,7 @@ impl super::FreminalGui {
                     &mut win.rename_buffer,
                     &mut win.dragging_tab,
                     &mut drag_ended_this_frame,
-                    self.config.tab_title.policy,
-                    &self.config.tab_title.separator,
+                    &self.config.tab_title,
                 );
                 current_rects[orig_idx] = tab_rect;
                 if !matches!(tab_action, TabBarAction::None) {
@@ -857,15 +877,14 @@ impl super::FreminalGui {
         rename_buffer: &mut String,
         dragging_tab: &mut Option<usize>,
         drag_ended_this_frame: &mut bool,
-        title_policy: TabTitlePolicy,
-        title_separator: &str,
+        title_config: &TabTitleConfig,
     ) -> (TabBarAction, egui::Rect) {
         let mut action = TabBarAction::None;
         let pane = tab.active_pane();
         // Resolve the tab label under the configured title policy, combining
         // the user-assigned custom name with the shell-asserted OSC title.
         // An empty result falls back to a "Shell" placeholder.
-        let resolved = tab.display_name(title_policy, title_separator);
+        let resolved = tab.display_name(title_config);
         let label = if resolved.is_empty() {
             "Shell"
         } else {
@@ -873,6 +892,7 @@ impl super::FreminalGui {
         };
 
         let has_bell = pane.is_some_and(|p| p.bell_active) && !is_active;
+        let progress = pane.and_then(|p| p.arc_swap.load().progress);
 
         // The tab label text itself; status indicators (broadcast, lock, bell)
         // are drawn as separate bundled-icon glyphs ahead of it (see the icon
@@ -902,7 +922,8 @@ impl super::FreminalGui {
                     // Status indicators, drawn as bundled icon glyphs ahead
                     // of the label (palette-tinted, monospace family so they
                     // resolve from the bundled Nerd Font rather than the
-                    // proportional UI font). Order: broadcast, lock, bell.
+                    // proportional UI font). Order: broadcast, lock, bell,
+                    // then the task progress indicator.
                     let warn_color = ui.visuals().warn_fg_color;
                     // Text color: legible OnAccent over the active tab's
                     // accent fill; otherwise the normal/bell color.
@@ -926,6 +947,9 @@ impl super::FreminalGui {
                         // Unacknowledged bell on a non-focused tab.
                         ui.label(ChromeIcon::Bell.rich_text_colored(warn_color));
                     }
+                    if let Some(progress) = progress {
+                        Self::show_tab_progress(ui, progress, label_color);
+                    }
 
                     let rich_label = egui::RichText::new(&display_label)
                         .size(13.0)
@@ -1001,6 +1025,39 @@ impl super::FreminalGui {
         (action, frame_response.rect)
     }
 
+    /// Draw a tab's `OSC 9 ; 4` task progress: a spinner while the progress
+    /// is indeterminate, otherwise a small bar tinted by state.
+    ///
+    /// `normal_color` is the tab's label color, so a normal bar stays legible
+    /// over the active tab's accent fill.
+    fn show_tab_progress(ui: &mut egui::Ui, progress: TaskProgress, normal_color: egui::Color32) {
+        let response = match progress.state {
+            ProgressState::Indeterminate => {
+                ui.add(egui::Spinner::new().size(12.0).color(normal_color))
+            }
+            state => {
+                let fill = match state {
+                    ProgressState::Error => ui.visuals().error_fg_color,
+                    ProgressState::Paused => ui.visuals().warn_fg_color,
+                    ProgressState::Normal | ProgressState::Indeterminate => normal_color,
+                };
+                ui.add(
+                    egui::ProgressBar::new(f32::from(progress.percent) / 100.0)
+                        .desired_width(36.0)
+                        .desired_height(6.0)
+                        .fill(fill),
+                )
+            }
+        };
+        let hover = match progress.state {
+            ProgressState::Normal => format!("{}%", progress.percent),
+            ProgressState::Error => format!("Failed at {}%", progress.percent),
+            ProgressState::Indeterminate => "Working\u{2026}".to_owned(),
+            ProgressState::Paused => format!("Paused at {}%", progress.percent),
+        };
+        response.on_hover_text(hover);
+    }
+
     /// Show the floating "Save Layout" name-entry prompt.
     ///
     /// Rendered every frame when `pending_save_layout` is `Some`.  Returns
@@ -31,6 +31,7 @@ pub mod search;
 pub mod settings;
 pub mod shaping;
 pub mod shell_history;
+pub mod soft_glyphs;
 pub mod tabs;
 pub mod terminal;
 pub mod view_state;
@@ -54,14 +55,17 @@ mod menu;
 mod notifications;
 pub mod paste_guard;
 mod platform;
+mod playback;
 mod pointer_motion;
 mod published_frame_state;
 mod recording;
+mod remote_control;
 mod rendering;
 mod run;
 mod session;
 mod settings_dispatch;
 mod tab_spawning;
+mod tmux;
 mod toast;
 mod welcome;
 pub(crate) mod window;
@@ -224,30 +228,46 @@ struct FreminalGui {
     /// persisted — same fallback as today's `config_path == None`).
     app_state_path: Option<std::path::PathBuf>,
 
-    /// Shared, hot-swappable FREC v2 recording handle.
+    /// Hot-swappable FREC v2 recording handle of each open window.
     ///
-    /// When the inner `Option<RecordingHandle>` is `Some`, topology,
-    /// window, input, and PTY events are emitted by all panes.  The GUI
-    /// can toggle recording on and off at runtime by storing a new value
-    /// into this swap; every pane observes the change on its next event
-    /// without any rewiring.
-    recording_swap: freminal_terminal_emulator::recording::RecordingSwap,
+    /// When a window's inner `Option<RecordingHandle>` is `Some`, topology,
+    /// window, input, and PTY events are emitted by its panes.  The GUI
+    /// can toggle recording on and off at runtime by storing new values
+    /// into these swaps; every pane observes the change on its next event
+    /// without any rewiring.  See `refresh_recording_swap`.
+    recording_swaps: HashMap<WindowId, freminal_terminal_emulator::recording::RecordingSwap>,
 
     /// Join handle for the currently-active recording writer thread.
     ///
     /// Held on the GUI side so that `toggle_recording` can deterministically
     /// wait for the writer to finalize the file when recording is stopped.
-    /// `Some` while a recording is in progress (mirrors `recording_swap`
+    /// `Some` while a recording is in progress (mirrors `recording_swaps`
     /// holding `Some`); `None` when no recording is active.
     recording_join: Option<freminal_terminal_emulator::recording::RecordingJoinHandle>,
 
     /// Path of the currently-active recording file, if any.
     ///
     /// Used by the menu bar and UI to display the recording destination
-    /// and by `toggle_recording` for logging. Mirrors `recording_swap`:
+    /// and by `toggle_recording` for logging. Mirrors `recording_swaps`:
     /// `Some` when a recording is active, `None` otherwise.
     recording_path: Option<std::path::PathBuf>,
 
+    /// Handle of the active file recording, if any.
+    ///
+    /// Kept apart from `recording_swaps` because each swap holds the fan-out
+    /// of this handle and its window's flight recorder; see
+    /// `refresh_recording_swap`.
+    recording_handle: Option<freminal_terminal_emulator::recording::RecordingHandle>,
+
+    /// The always-on in-memory recorders, one ring per window (keyed by
+    /// recording window ID), running while `[recording] flight_recorder`
+    /// is enabled.
+    flight_recorders: freminal_terminal_emulator::flight_recorder::WindowFlightRecorders,
+
+    /// Redaction rules from `[recording] redact_*`, attached to the handle
+    /// published in `recording_swaps`. `None` when redaction is off.
+    redactor: Option<Arc<freminal_terminal_emulator::redaction::Redactor>>,
+
     /// Path to the `config.toml` file currently backing `self.config`, if
     /// one was resolved at startup. `None` when running with no config
     /// file (e.g. fresh first launch before any config exists).
@@ -366,6 +386,16 @@ struct FreminalGui {
     /// whether the session needs saving.  The thread also wakes the event loop
     /// via the repaint proxy so this is observed promptly even at idle.
     session_save_due: Arc<std::sync::atomic::AtomicBool>,
+
+    /// Remote-control socket listener, bound at first-window creation when
+    /// `[security] allow_remote_control` is set.  `update()` drains its
+    /// queued calls each frame; dropping it removes the socket file.
+    remote_control: Option<remote_control::RemoteControlServer>,
+
+    /// The `--play` viewer.  `Some` for the whole run when freminal was
+    /// started on a recording; the first window then shows the recording
+    /// instead of a shell, and nothing that spawns a shell is allowed.
+    playback: Option<playback::PlaybackViewer>,
 }
 
 impl FreminalGui {
@@ -377,7 +407,7 @@ impl FreminalGui {
         repaint_handle: Arc<OnceLock<(RepaintProxy, WindowId)>>,
         config_path: Option<std::path::PathBuf>,
         window_post: Arc<Mutex<WindowPostRenderer>>,
-        recording_swap: freminal_terminal_emulator::recording::RecordingSwap,
+        recording_swap: &freminal_terminal_emulator::recording::RecordingSwap,
     ) -> Self {
         // Push pending shader to the shared WindowPostRenderer.  The first
         // window's tab is spawned lazily in `on_window_created`, so any
@@ -484,9 +514,16 @@ impl FreminalGui {
                 .unwrap_or_default(),
             app_state,
             app_state_path,
-            recording_swap,
+            // A recording started with `--recording-path` is already in
+            // the swap; adopt it so every window's swap can carry it beside
+            // that window's flight recorder.
+            recording_handle: recording_swap.load_full().map(|h| (*h).clone()),
+            recording_swaps: HashMap::new(),
             recording_join: None,
             recording_path: None,
+            flight_recorders:
+                freminal_terminal_emulator::flight_recorder::WindowFlightRecorders::default(),
+            redactor: None,
             config_path,
             recording_window_ids: HashMap::new(),
             next_recording_window_id: 0,
@@ -505,6 +542,8 @@ impl FreminalGui {
             fatal_error: None,
             last_session_fingerprint: None,
             session_save_due: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            remote_control: None,
+            playback: None,
         };
 
         if !layout_errors.is_empty() {
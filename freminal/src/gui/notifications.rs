@@ -1560,6 +1560,7 @@ mod tests {
             end_row: Some(1),
             exit_code,
             cwd: None,
+            command_line: None,
             started_at: executed,
             executed_at: Some(executed),
             finished_at: Some(executed + Duration::from_secs(dur_secs)),
@@ -1610,6 +1611,7 @@ mod tests {
             end_row: None,
             exit_code: None,
             cwd: None,
+            command_line: None,
             started_at: std::time::SystemTime::now(),
             executed_at: Some(std::time::SystemTime::now()),
             finished_at: None,
@@ -1638,6 +1640,7 @@ mod tests {
             end_row: Some(0),
             exit_code: Some(130),
             cwd: None,
+            command_line: None,
             // User idled 30s at the prompt before Ctrl-C.
             started_at: started,
             executed_at: None,
@@ -1710,6 +1713,7 @@ mod tests {
             end_row: Some(1),
             exit_code,
             cwd: cwd.map(str::to_owned),
+            command_line: None,
             started_at: executed,
             executed_at: Some(executed),
             finished_at: Some(executed + Duration::from_secs(dur_secs)),
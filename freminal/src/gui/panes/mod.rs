@@ -32,6 +32,7 @@ use freminal_common::geometry::{Point, Rect, point};
 use freminal_common::pty_write::PtyWrite;
 use freminal_terminal_emulator::io::{InputEvent, WindowCommand};
 use freminal_terminal_emulator::snapshot::TerminalSnapshot;
+use freminal_terminal_emulator::tmux_control::TmuxEvent;
 
 use super::pty::CommandFinishedEvent;
 use super::terminal::PaneRenderCache;
@@ -222,6 +223,13 @@ pub struct Pane {
     /// flip the tab's pending-event indicator.
     pub command_event_rx: Receiver<CommandFinishedEvent>,
 
+    /// Receiver for [`TmuxEvent`]s emitted while this pane is the gateway of
+    /// a `tmux -CC` session.
+    ///
+    /// Drained once per frame by the GUI (see `gui::tmux`), which mirrors
+    /// the tmux session's windows and panes as native tabs and panes.
+    pub tmux_event_rx: Receiver<TmuxEvent>,
+
     /// Bounded ring buffer of recently finished commands for this pane.
     ///
     /// Capped at [`RECENT_COMMANDS_CAP`]; the oldest entry is dropped when the
@@ -323,6 +331,7 @@ impl Pane {
             render_state: super::terminal::new_render_state(window_post),
             render_cache: PaneRenderCache::new(),
             command_event_rx: channels.command_event_rx,
+            tmux_event_rx: channels.tmux_event_rx,
             history_seed: channels.history_seed,
             shell_program: channels.shell_program,
             shell_histfile_last_seen: None,
@@ -1756,6 +1765,7 @@ mod tests {
             ))),
             render_cache: crate::gui::terminal::PaneRenderCache::new(),
             command_event_rx,
+            tmux_event_rx: crossbeam_channel::never(),
             recent_commands: VecDeque::new(),
             history_seed: crate::gui::shell_history::new_seeded_history(),
             shell_program: None,
@@ -0,0 +1,450 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! The `--play` FREC viewer.
+//!
+//! A playback run opens one read-only window instead of a shell.  Every
+//! frame the [`PlaybackClock`] picks a position in the recording and
+//! [`Playback`] walks there, keeping the recorded window/tab/pane tree
+//! current and returning the output and resizes each recorded pane needs.
+//! Each recorded pane is a native pane from [`pty::spawn_playback_pane`]
+//! whose emulator is fed those steps in recording order.
+//!
+//! The window shows one recorded window at a time.  Its native tabs and
+//! panes are rebuilt whenever the recorded tree changes shape, the
+//! timeline moves backwards, or a different recorded window is picked;
+//! the rebuilt panes replay the recording from the start (the seek index
+//! keeps finding the position cheap, the replay merges output).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use conv2::ConvUtil as _;
+use freminal_common::pty_write::FreminalTerminalSize;
+use freminal_common::send_or_log;
+use freminal_common::terminal_size::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use freminal_terminal_emulator::io::InputEvent;
+use freminal_terminal_emulator::playback::{Playback, PlaybackClock, PlaybackStep};
+use freminal_terminal_emulator::recording::{
+    PaneNodeSnapshot, ParsedRecording, RecordingSplitDirection, WindowSnapshot,
+};
+use freminal_windowing::{RepaintProxy, WindowId};
+use tracing::{error, warn};
+
+use super::icons::ChromeIcon;
+use super::panes::{self, PaneId, SplitDirection};
+use super::renderer::WindowPostRenderer;
+use super::tabs::{Tab, TabId};
+use super::window::PerWindowState;
+use super::{FreminalGui, pty};
+
+/// Speeds offered in the playback bar.
+const SPEEDS: [f64; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+
+/// A native pane showing one recorded pane.
+struct PlaybackPane {
+    /// The native pane.
+    id: PaneId,
+
+    /// Feeds the pane's emulator.  Dropping it ends the pane.
+    feed: pty::PlaybackFeed,
+}
+
+/// State of the `--play` viewer.
+pub(super) struct PlaybackViewer {
+    /// Position in the recording and the recorded tree at that position.
+    playback: Playback,
+
+    /// Play/pause and speed.
+    clock: PlaybackClock,
+
+    /// Recorded window shown; `None` shows the first one.
+    window: Option<u32>,
+
+    /// Native panes of the shown window, by recorded pane ID.
+    panes: HashMap<u32, PlaybackPane>,
+
+    /// Feed of the blank pane shown while the window has no recorded tabs.
+    blank: Option<pty::PlaybackFeed>,
+
+    /// The native tabs must be rebuilt on the next frame.
+    needs_rebuild: bool,
+}
+
+impl PlaybackViewer {
+    /// A viewer for `recording`, playing from the start.
+    pub(super) fn new(recording: ParsedRecording) -> Self {
+        let playback = Playback::new(recording);
+        let mut clock = PlaybackClock::new(playback.duration_us());
+        clock.play(Instant::now());
+        Self {
+            playback,
+            clock,
+            window: None,
+            panes: HashMap::new(),
+            blank: None,
+            needs_rebuild: true,
+        }
+    }
+
+    /// The recorded window currently shown.
+    fn shown_window(&self) -> Option<&WindowSnapshot> {
+        let windows = &self.playback.topology().windows;
+        self.window
+            .and_then(|id| windows.iter().find(|w| w.window_id == id))
+            .or_else(|| windows.first())
+    }
+}
+
+/// What spawning a playback pane needs from the window that shows it.
+pub(super) struct PaneEnv<'a> {
+    pub(super) repaint_handle: &'a Arc<OnceLock<(RepaintProxy, WindowId)>>,
+    pub(super) window_post: &'a Arc<Mutex<WindowPostRenderer>>,
+    pub(super) cell_size: (u32, u32),
+    pub(super) os_dark_mode: bool,
+}
+
+impl<'a> PaneEnv<'a> {
+    /// The environment of an existing window.
+    const fn of(win: &'a PerWindowState) -> Self {
+        Self {
+            repaint_handle: &win.repaint_handle,
+            window_post: &win.window_post,
+            cell_size: win.terminal_widget.cell_size(),
+            os_dark_mode: win.os_dark_mode,
+        }
+    }
+}
+
+impl FreminalGui {
+    /// Build the native tabs for the shown recorded window, replacing the
+    /// viewer's panes.  Never empty: a window without recorded tabs gets a
+    /// single blank one.
+    pub(super) fn playback_tabs(&mut self, env: &PaneEnv<'_>) -> Vec<Tab> {
+        let Some(mut viewer) = self.playback.take() else {
+            return Vec::new();
+        };
+        viewer.panes.clear();
+        viewer.blank = None;
+
+        let mut tabs = Vec::new();
+        let shown = viewer.shown_window().map(|w| w.tabs.clone());
+        for (index, snapshot) in shown.iter().flatten().enumerate() {
+            let node = &snapshot.pane_tree.node;
+            let root = self.spawn_playback_pane(&mut viewer, env, Some(node.first_pane_id()));
+            let root_id = root.id;
+            let index = u64::try_from(index).unwrap_or(u64::MAX);
+            let mut tab = Tab::new(TabId::offset(index), root);
+            self.place_playback_node(&mut viewer, env, &mut tab, node, root_id);
+            tabs.push(tab);
+        }
+        if tabs.is_empty() {
+            let blank = self.spawn_playback_pane(&mut viewer, env, None);
+            tabs.push(Tab::new(TabId::first(), blank));
+        }
+
+        self.playback = Some(viewer);
+        tabs
+    }
+
+    /// Split the native pane `at` so `tab` matches the recorded `node`.
+    /// The pane for `node`'s first leaf must already be `at`.
+    fn place_playback_node(
+        &self,
+        viewer: &mut PlaybackViewer,
+        env: &PaneEnv<'_>,
+        tab: &mut Tab,
+        node: &PaneNodeSnapshot,
+        at: PaneId,
+    ) {
+        let PaneNodeSnapshot::Split {
+            direction,
+            ratio,
+            first,
+            second,
+        } = node
+        else {
+            return;
+        };
+        let direction = match direction {
+            RecordingSplitDirection::Horizontal => SplitDirection::Horizontal,
+            RecordingSplitDirection::Vertical => SplitDirection::Vertical,
+        };
+
+        let pane = self.spawn_playback_pane(viewer, env, Some(second.first_pane_id()));
+        let second_at = match tab.pane_tree.split_with_id(at, direction, pane) {
+            Ok(id) => id,
+            Err(e) => {
+                error!("Failed to rebuild recorded split: {e}");
+                return;
+            }
+        };
+        if let Err(e) = tab.pane_tree.set_split_ratio(at, direction, *ratio) {
+            warn!("Failed to size recorded split: {e}");
+        }
+
+        self.place_playback_node(viewer, env, tab, first, at);
+        self.place_playback_node(viewer, env, tab, second, second_at);
+    }
+
+    /// Spawn a read-only native pane for `recorded` (or a blank one) and
+    /// register it with `viewer`.  The replay sizes it.
+    fn spawn_playback_pane(
+        &self,
+        viewer: &mut PlaybackViewer,
+        env: &PaneEnv<'_>,
+        recorded: Option<u32>,
+    ) -> panes::Pane {
+        let theme =
+            freminal_common::themes::by_slug(self.config.theme.active_slug(env.os_dark_mode))
+                .unwrap_or(&freminal_common::themes::CATPPUCCIN_MOCHA);
+
+        let pane_id = self
+            .pane_id_gen
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .next_id();
+
+        let (cols, rows) = (usize::from(DEFAULT_WIDTH), usize::from(DEFAULT_HEIGHT));
+        let cw = env.cell_size.0.value_as::<usize>().unwrap_or(0);
+        let ch = env.cell_size.1.value_as::<usize>().unwrap_or(0);
+        let (channels, feed) = pty::spawn_playback_pane(
+            self.config.scrollback.limit,
+            pty::PtyTabInitialState {
+                theme,
+                auto_detect_urls: self.config.ui.auto_detect_urls,
+                allow_checksum_reports: self.config.security.allow_checksum_reports,
+                cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
+                    &self.config.cursor.shape,
+                    self.config.cursor.blink,
+                ),
+            },
+            env.repaint_handle,
+            &FreminalTerminalSize {
+                width: cols,
+                height: rows,
+                pixel_width: cw * cols,
+                pixel_height: ch * rows,
+            },
+            pane_id.raw().try_into().unwrap_or(u32::MAX),
+        );
+
+        let pane = panes::Pane::from_channels(
+            pane_id,
+            channels,
+            Arc::clone(env.window_post),
+            "Playback".to_owned(),
+        );
+        send_or_log!(
+            pane.input_tx,
+            InputEvent::ThemeModeUpdate(self.config.theme.mode, env.os_dark_mode),
+            "Failed to send ThemeModeUpdate to playback pane"
+        );
+
+        match recorded {
+            Some(id) => {
+                viewer.panes.insert(id, PlaybackPane { id: pane_id, feed });
+            }
+            None => viewer.blank = Some(feed),
+        }
+        pane
+    }
+
+    /// Move the recording to the clock's position: rebuild the native tabs
+    /// if needed, feed every pane its steps, and mirror the recorded focus.
+    pub(super) fn advance_playback(&mut self, win: &mut PerWindowState, ctx: &egui::Context) {
+        let Some(viewer) = self.playback.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        if viewer.clock.is_playing() && viewer.clock.is_finished(now) {
+            viewer.clock.pause(now);
+        }
+
+        let update = viewer.playback.seek(viewer.clock.position_us(now));
+        let rebuild = update.rebuild || viewer.needs_rebuild;
+        let steps = if rebuild && !update.rebuild {
+            viewer.playback.replay()
+        } else {
+            update.steps
+        };
+        let playing = viewer.clock.is_playing();
+
+        if rebuild {
+            let mut tabs = self.playback_tabs(&PaneEnv::of(win)).into_iter();
+            if let Some(first) = tabs.next() {
+                win.tabs = super::tabs::TabManager::new(first);
+                for tab in tabs {
+                    win.tabs.add_tab(tab);
+                }
+            }
+        }
+
+        let Some(viewer) = self.playback.as_mut() else {
+            return;
+        };
+        viewer.needs_rebuild = false;
+        let (cell_w, cell_h) = win.terminal_widget.cell_size();
+        let cw = cell_w.value_as::<usize>().unwrap_or(0);
+        let ch = cell_h.value_as::<usize>().unwrap_or(0);
+        for step in steps {
+            let (pane_id, event) = match step {
+                PlaybackStep::Output { pane_id, data } => {
+                    (pane_id, InputEvent::PlaybackOutput(data))
+                }
+                PlaybackStep::Resize {
+                    pane_id,
+                    cols,
+                    rows,
+                } => {
+                    let cols = cols.value_as::<usize>().unwrap_or(usize::MAX).max(1);
+                    let rows = rows.value_as::<usize>().unwrap_or(usize::MAX).max(1);
+                    (pane_id, InputEvent::Resize(cols, rows, cw, ch))
+                }
+            };
+            // Panes of other recorded windows are not shown.
+            if let Some(pane) = viewer.panes.get(&pane_id) {
+                send_or_log!(pane.feed.events, event, "Failed to feed playback pane");
+            }
+        }
+
+        if rebuild || update.focus_changed {
+            sync_playback_focus(viewer, win);
+        }
+        if playing {
+            ctx.request_repaint_after(Duration::from_millis(16));
+        }
+    }
+
+    /// The playback bar: play/pause, position, speed, recorded window and
+    /// the timeline.
+    pub(super) fn show_playback_bar(&mut self, ui: &mut egui::Ui) {
+        let Some(viewer) = self.playback.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        let position = viewer.clock.position_us(now);
+        let duration = viewer.playback.duration_us();
+
+        ui.horizontal(|ui| {
+            let (icon, hint) = if viewer.clock.is_playing() {
+                (ChromeIcon::Pause, "Pause")
+            } else {
+                (ChromeIcon::Play, "Play")
+            };
+            if ui.button(icon.rich_text()).on_hover_text(hint).clicked() {
+                if viewer.clock.is_playing() {
+                    viewer.clock.pause(now);
+                } else {
+                    viewer.clock.play(now);
+                }
+            }
+
+            ui.monospace(format!(
+                "{} / {}",
+                format_position(position),
+                format_position(duration)
+            ));
+
+            let speed = viewer.clock.speed();
+            egui::ComboBox::from_id_salt("playback_speed")
+                .selected_text(format!("{speed}x"))
+                .width(64.0)
+                .show_ui(ui, |ui| {
+                    for choice in SPEEDS {
+                        let selected = (speed - choice).abs() < f64::EPSILON;
+                        if ui
+                            .selectable_label(selected, format!("{choice}x"))
+                            .clicked()
+                        {
+                            viewer.clock.set_speed(now, choice);
+                        }
+                    }
+                });
+
+            let windows: Vec<u32> = viewer
+                .playback
+                .topology()
+                .windows
+                .iter()
+                .map(|w| w.window_id)
+                .collect();
+            if windows.len() > 1 {
+                let shown = viewer.shown_window().map(|w| w.window_id);
+                egui::ComboBox::from_id_salt("playback_window")
+                    .selected_text(shown.map_or_else(String::new, |id| format!("Window {id}")))
+                    .show_ui(ui, |ui| {
+                        for id in windows {
+                            if ui
+                                .selectable_label(shown == Some(id), format!("Window {id}"))
+                                .clicked()
+                                && shown != Some(id)
+                            {
+                                viewer.window = Some(id);
+                                viewer.needs_rebuild = true;
+                            }
+                        }
+                    });
+            }
+
+            let mut seconds = Duration::from_micros(position).as_secs_f64();
+            let end = Duration::from_micros(duration).as_secs_f64();
+            ui.spacing_mut().slider_width = ui.available_width().max(64.0);
+            let timeline = egui::Slider::new(&mut seconds, 0.0..=end).show_value(false);
+            if ui.add(timeline).changed() {
+                let target = Duration::try_from_secs_f64(seconds)
+                    .map_or(0, |d| u64::try_from(d.as_micros()).unwrap_or(u64::MAX));
+                viewer.clock.seek(now, target);
+            }
+        });
+
+        if viewer.needs_rebuild {
+            ui.ctx().request_repaint();
+        }
+    }
+}
+
+/// Point each native tab's focus and zoom, and the active tab, at what the
+/// recording has.
+fn sync_playback_focus(viewer: &PlaybackViewer, win: &mut PerWindowState) {
+    let Some(window) = viewer.shown_window() else {
+        return;
+    };
+    let native = |id: u32| viewer.panes.get(&id).map(|pane| pane.id);
+
+    let mut active = 0;
+    for (index, (snapshot, tab)) in window.tabs.iter().zip(win.tabs.iter_mut()).enumerate() {
+        if let Some(id) = native(snapshot.active_pane) {
+            tab.active_pane = id;
+        }
+        tab.zoomed_pane = snapshot.zoomed_pane.and_then(native);
+        if snapshot.tab_id == window.active_tab {
+            active = index;
+        }
+    }
+    if let Err(e) = win.tabs.switch_to(active) {
+        warn!("Failed to switch to recorded tab: {e}");
+    }
+}
+
+/// `m:ss.t` for a recording position.
+fn format_position(us: u64) -> String {
+    let tenths = us / 100_000;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_position;
+
+    #[test]
+    fn positions_format_as_minutes_seconds_tenths() {
+        assert_eq!(format_position(0), "0:00.0");
+        assert_eq!(format_position(1_250_000), "0:01.2");
+        assert_eq!(format_position(61_000_000), "1:01.0");
+        assert_eq!(format_position(3_600_000_000), "60:00.0");
+    }
+}
@@ -28,6 +28,9 @@ use freminal_terminal_emulator::io::{InputEvent, PtyRead, WindowCommand};
 use freminal_terminal_emulator::recording::{EventPayload, RecordingSwap};
 use freminal_terminal_emulator::snapshot::TerminalSnapshot;
 use freminal_terminal_emulator::terminal_handler::TerminalHandler;
+use freminal_terminal_emulator::tmux_control::{
+    TmuxCommandKind, TmuxCommandSender, TmuxEvent, resize_pane_command,
+};
 use freminal_windowing::{RepaintProxy, WindowId};
 
 /// Ask the system allocator to release free heap pages back to the OS.
@@ -132,7 +135,11 @@ const MAX_PTY_READ_BATCH: usize = 64;
 /// - `NoRepaint`: `Key`, `FocusChange` (child-fd writes only, no emulator state
 ///   change — the echo arrives later via `pty_read_rx`, which requests its own
 ///   repaint); `ExtractSelection` (read-only; the GUI blocks on `clipboard_rx`
-///   in the SAME frame, so no future wake is needed).
+///   in the SAME frame, so no future wake is needed); `ExtractText` (read-only;
+///   the remote-control connection thread waits on its reply channel);
+///   `AllowChecksumReports`
+///   (only changes whether later DECRQCRA requests are answered); `Locator`
+///   (pointer state nothing draws, plus any DECLRP report written to the child).
 /// - `Repaint`: `Resize`, `ScrollOffset`, `ThemeChange`, `CursorConfigChange`,
 ///   `AutoDetectUrls`, `ThemeModeUpdate`, `ClearScrollback` (all mutate
 ///   snapshot-visible state), and `RequestSearchBuffer` (read-only, but the GUI
@@ -163,20 +170,29 @@ enum InputOutcome {
 /// - `ExtractSelection`: read-only; the result is delivered on `clipboard_tx`
 ///   and the GUI consumes it with a BLOCKING `clipboard_rx.recv_timeout` in the
 ///   SAME frame that requested it, so no future wake is needed.
+/// - `ExtractText`: read-only; the text goes to the remote-control
+///   connection thread waiting on its reply channel, not to the GUI.
+/// - `AllowChecksumReports`: only changes whether later DECRQCRA requests are
+///   answered; nothing on screen changes.
+/// - `Locator`: records the pointer for DEC locator reporting and may write
+///   a DECLRP report to the child fd; nothing on screen changes.
 ///
 /// `true` (repaint needed):
 /// - `Resize`, `ScrollOffset`, `ThemeChange`, `CursorConfigChange`,
-///   `AutoDetectUrls`, `ThemeModeUpdate`, `ClearScrollback`: all mutate
-///   snapshot-visible state.
+///   `AutoDetectUrls`, `ThemeModeUpdate`, `ClearScrollback`,
+///   `PlaybackOutput`: all mutate snapshot-visible state.
 /// - `RequestSearchBuffer`: read-only, BUT the GUI POLLS `search_buffer_rx` on
 ///   a LATER frame (not a blocking recv), so it needs a guaranteed wake or the
 ///   result can stall while the terminal is idle and the cursor-blink wake is
 ///   suppressed.
 const fn input_event_needs_repaint(event: &InputEvent) -> bool {
     match event {
-        InputEvent::Key(_) | InputEvent::FocusChange(_) | InputEvent::ExtractSelection { .. } => {
-            false
-        }
+        InputEvent::Key(_)
+        | InputEvent::FocusChange(_)
+        | InputEvent::ExtractSelection { .. }
+        | InputEvent::ExtractText { .. }
+        | InputEvent::AllowChecksumReports(_)
+        | InputEvent::Locator(_) => false,
         InputEvent::Resize(..)
         | InputEvent::ScrollOffset { .. }
         | InputEvent::ThemeChange(_)
@@ -184,7 +200,8 @@ const fn input_event_needs_repaint(event: &InputEvent) -> bool {
         | InputEvent::RequestSearchBuffer
         | InputEvent::AutoDetectUrls(_)
         | InputEvent::ClearScrollback
-        | InputEvent::CursorConfigChange(_) => true,
+        | InputEvent::CursorConfigChange(_)
+        | InputEvent::PlaybackOutput(_) => true,
     }
 }
 
@@ -318,6 +335,15 @@ pub struct TabChannels {
     /// the right parser is selected.  `None` when a positional `command`
     /// was specified or when no shell could be resolved.
     pub shell_program: Option<std::path::PathBuf>,
+
+    /// Receiver for [`TmuxEvent`]s produced while this pane is the gateway
+    /// of a `tmux -CC` session.
+    ///
+    /// The PTY consumer thread drains `TerminalHandler::drain_tmux_events`
+    /// after each batch and forwards the events here.  The GUI turns them
+    /// into native tabs and panes (see `gui::tmux`).  Never receives
+    /// anything for panes that do not run tmux in control mode.
+    pub tmux_event_rx: Receiver<TmuxEvent>,
 }
 
 /// Already-resolved config values applied once, immediately after a new
@@ -337,6 +363,9 @@ pub struct PtyTabInitialState {
     /// Auto-detect plain URLs in terminal output
     /// (`InputEvent::AutoDetectUrls` is the live-apply equivalent).
     pub auto_detect_urls: bool,
+    /// Answer DECRQCRA checksum requests
+    /// (`InputEvent::AllowChecksumReports` is the live-apply equivalent).
+    pub allow_checksum_reports: bool,
     /// Cursor shape/blink style, resolved from `config.cursor` via
     /// [`CursorVisualStyle::from_config`](freminal_common::cursor::CursorVisualStyle::from_config)
     /// (`InputEvent::CursorConfigChange` is the live-apply equivalent;
@@ -359,6 +388,9 @@ fn apply_initial_state(handler: &mut TerminalHandler, initial_state: PtyTabIniti
         .buffer_mut()
         .set_auto_detect_urls(initial_state.auto_detect_urls);
 
+    // DECRQCRA stays unanswered unless the user opted in.
+    handler.set_allow_checksum_reports(initial_state.allow_checksum_reports);
+
     // Seed the cursor's initial shape/blink from `config.cursor` (issue
     // #406). Like `theme`, this is only the *starting* state: a running
     // program's own DECSCUSR / XTCBlink request still overrides it
@@ -444,6 +476,7 @@ pub fn spawn_pty_tab(
     let (search_buffer_tx, search_buffer_rx) = crossbeam_channel::bounded::<(usize, Vec<TChar>)>(1);
     let (pty_dead_tx, pty_dead_rx) = crossbeam_channel::bounded::<()>(1);
     let (command_event_tx, command_event_rx) = unbounded::<CommandFinishedEvent>();
+    let (tmux_event_tx, tmux_event_rx) = unbounded::<TmuxEvent>();
 
     let repaint_handle_pty = Arc::clone(repaint_handle);
 
@@ -500,6 +533,7 @@ pub fn spawn_pty_tab(
         tab_cfg.recording_swap,
         tab_cfg.recording_pane_id,
         command_event_tx,
+        tmux_event_tx,
         reader_shutdown,
     );
 
@@ -516,9 +550,224 @@ pub fn spawn_pty_tab(
         command_event_rx,
         history_seed,
         shell_program,
+        tmux_event_rx,
     })
 }
 
+/// Spawn a pane backed by a tmux pane instead of a local PTY.
+///
+/// The pane runs the normal consumer thread over a headless emulator.  Its
+/// "PTY output" is whatever the GUI pushes into the returned `Sender` — the
+/// unescaped `%output` of `tmux_pane` — and everything the emulator would
+/// write to a PTY is forwarded to tmux over the gateway's control channel:
+/// key bytes become `send-keys -H`, resizes become `resize-pane`.
+///
+/// Dropping the returned `Sender` ends the pane exactly like a shell exit,
+/// so the usual dead-pane path closes it.
+pub fn spawn_tmux_pane(
+    commands: TmuxCommandSender,
+    tmux_pane: u32,
+    scrollback_limit: usize,
+    initial_state: PtyTabInitialState,
+    repaint_handle: &Arc<OnceLock<(RepaintProxy, WindowId)>>,
+    initial_size: &FreminalTerminalSize,
+    recording_pane_id: u32,
+) -> (TabChannels, Sender<PtyRead>) {
+    let (mut terminal, pty_write_rx) = TerminalEmulator::new_headless(Some(scrollback_limit));
+    apply_initial_state(&mut terminal.internal.handler, initial_state);
+    // Seed the size directly rather than through `handle_resize_event`: tmux
+    // already has the pane at this size, so there is no resize to forward.
+    terminal
+        .internal
+        .set_win_size(initial_size.width, initial_size.height, 0, 0);
+
+    let arc_swap: Arc<ArcSwap<TerminalSnapshot>> =
+        Arc::new(ArcSwap::from_pointee(TerminalSnapshot::empty()));
+    let arc_swap_gui = Arc::clone(&arc_swap);
+    let pty_write_tx = terminal.clone_write_tx();
+
+    let (pty_read_tx, pty_read_rx) = unbounded::<PtyRead>();
+    let (input_tx, input_rx) = unbounded::<InputEvent>();
+    let (window_cmd_tx, window_cmd_rx) = unbounded::<WindowCommand>();
+    let (clipboard_tx, clipboard_rx) = crossbeam_channel::bounded::<String>(1);
+    let (search_buffer_tx, search_buffer_rx) = crossbeam_channel::bounded::<(usize, Vec<TChar>)>(1);
+    let (pty_dead_tx, pty_dead_rx) = crossbeam_channel::bounded::<()>(1);
+    let (command_event_tx, command_event_rx) = unbounded::<CommandFinishedEvent>();
+    let (tmux_event_tx, tmux_event_rx) = unbounded::<TmuxEvent>();
+
+    // The forwarder exits once the consumer thread drops the emulator (and
+    // with it the last `PtyWrite` sender).
+    if let Err(e) = std::thread::Builder::new()
+        .name(format!("freminal-tmux-input-{tmux_pane}"))
+        .spawn(move || {
+            for write in pty_write_rx {
+                let result = match write {
+                    PtyWrite::Write(bytes) => commands.send_keys(tmux_pane, &bytes),
+                    PtyWrite::Resize(size) => commands.send(
+                        TmuxCommandKind::Ignore,
+                        &resize_pane_command(tmux_pane, size.width, size.height),
+                    ),
+                };
+                if let Err(e) = result {
+                    error!("Failed to forward input to tmux pane %{tmux_pane}: {e}");
+                }
+            }
+        })
+    {
+        error!("Failed to spawn tmux input forwarder: {e}");
+    }
+
+    spawn_pty_consumer_thread(
+        terminal,
+        pty_read_rx,
+        input_rx,
+        window_cmd_tx,
+        clipboard_tx,
+        search_buffer_tx,
+        None,
+        arc_swap,
+        Arc::clone(repaint_handle),
+        pty_dead_tx,
+        RecordingSwap::default(),
+        recording_pane_id,
+        command_event_tx,
+        tmux_event_tx,
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    let channels = TabChannels {
+        arc_swap: arc_swap_gui,
+        input_tx,
+        pty_write_tx,
+        window_cmd_rx,
+        clipboard_rx,
+        search_buffer_rx,
+        pty_dead_rx,
+        echo_off: Arc::new(AtomicBool::new(false)),
+        child_pid: None,
+        command_event_rx,
+        history_seed: crate::gui::shell_history::new_seeded_history(),
+        shell_program: None,
+        tmux_event_rx,
+    };
+    (channels, pty_read_tx)
+}
+
+/// What the playback viewer holds for a pane from [`spawn_playback_pane`].
+pub struct PlaybackFeed {
+    /// Straight to the consumer thread: recorded output
+    /// (`InputEvent::PlaybackOutput`) and recorded `Resize`s, in order.
+    pub events: Sender<InputEvent>,
+
+    /// Never written.  Held so the consumer thread keeps running until the
+    /// viewer drops the pane; dropping it ends the pane like a shell exit.
+    _pty_read: Sender<PtyRead>,
+}
+
+/// Spawn a read-only pane for the FREC playback viewer.
+///
+/// Like [`spawn_tmux_pane`] it runs the normal consumer thread over a
+/// headless emulator, but nothing reaches a child: keystrokes and anything
+/// the emulator writes back (query replies, resizes) are discarded.  The
+/// pane's size follows the recording, so resizes from the GUI are dropped
+/// too; the viewer sends the recorded ones on [`PlaybackFeed::events`].
+pub fn spawn_playback_pane(
+    scrollback_limit: usize,
+    initial_state: PtyTabInitialState,
+    repaint_handle: &Arc<OnceLock<(RepaintProxy, WindowId)>>,
+    initial_size: &FreminalTerminalSize,
+    recording_pane_id: u32,
+) -> (TabChannels, PlaybackFeed) {
+    let (mut terminal, pty_write_rx) = TerminalEmulator::new_headless(Some(scrollback_limit));
+    apply_initial_state(&mut terminal.internal.handler, initial_state);
+    terminal
+        .internal
+        .set_win_size(initial_size.width, initial_size.height, 0, 0);
+
+    let arc_swap: Arc<ArcSwap<TerminalSnapshot>> =
+        Arc::new(ArcSwap::from_pointee(TerminalSnapshot::empty()));
+    let arc_swap_gui = Arc::clone(&arc_swap);
+    let pty_write_tx = terminal.clone_write_tx();
+
+    let (pty_read_tx, pty_read_rx) = unbounded::<PtyRead>();
+    let (input_tx, input_rx) = unbounded::<InputEvent>();
+    let (gui_input_tx, gui_input_rx) = unbounded::<InputEvent>();
+    let (window_cmd_tx, window_cmd_rx) = unbounded::<WindowCommand>();
+    let (clipboard_tx, clipboard_rx) = crossbeam_channel::bounded::<String>(1);
+    let (search_buffer_tx, search_buffer_rx) = crossbeam_channel::bounded::<(usize, Vec<TChar>)>(1);
+    let (pty_dead_tx, pty_dead_rx) = crossbeam_channel::bounded::<()>(1);
+    let (command_event_tx, command_event_rx) = unbounded::<CommandFinishedEvent>();
+    let (tmux_event_tx, tmux_event_rx) = unbounded::<TmuxEvent>();
+
+    // Filters the GUI's input and swallows the emulator's writes.  Exits
+    // when either side goes away.
+    let forward_tx = input_tx.clone();
+    if let Err(e) = std::thread::Builder::new()
+        .name(format!("freminal-playback-input-{recording_pane_id}"))
+        .spawn(move || {
+            loop {
+                crossbeam_channel::select! {
+                    recv(gui_input_rx) -> msg => match msg {
+                        Ok(InputEvent::Resize(..) | InputEvent::Key(_)) => {}
+                        Ok(event) => {
+                            if forward_tx.send(event).is_err() {
+                                return;
+                            }
+                        }
+                        Err(_) => return,
+                    },
+                    recv(pty_write_rx) -> msg => {
+                        if msg.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        })
+    {
+        error!("Failed to spawn playback input filter: {e}");
+    }
+
+    spawn_pty_consumer_thread(
+        terminal,
+        pty_read_rx,
+        input_rx,
+        window_cmd_tx,
+        clipboard_tx,
+        search_buffer_tx,
+        None,
+        arc_swap,
+        Arc::clone(repaint_handle),
+        pty_dead_tx,
+        RecordingSwap::default(),
+        recording_pane_id,
+        command_event_tx,
+        tmux_event_tx,
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    let channels = TabChannels {
+        arc_swap: arc_swap_gui,
+        input_tx: gui_input_tx,
+        pty_write_tx,
+        window_cmd_rx,
+        clipboard_rx,
+        search_buffer_rx,
+        pty_dead_rx,
+        echo_off: Arc::new(AtomicBool::new(false)),
+        child_pid: None,
+        command_event_rx,
+        history_seed: crate::gui::shell_history::new_seeded_history(),
+        shell_program: None,
+        tmux_event_rx,
+    };
+    let feed = PlaybackFeed {
+        events: input_tx,
+        _pty_read: pty_read_tx,
+    };
+    (channels, feed)
+}
+
 /// Spawn the PTY consumer thread that owns a `TerminalEmulator`.
 ///
 /// This thread:
@@ -546,6 +795,7 @@ fn spawn_pty_consumer_thread(
     recording_swap: RecordingSwap,
     recording_pane_id: u32,
     command_event_tx: Sender<CommandFinishedEvent>,
+    tmux_event_tx: Sender<TmuxEvent>,
     reader_shutdown: Arc<AtomicBool>,
 ) {
     let thread_name = format!("freminal-pty-consumer-{recording_pane_id}");
@@ -612,6 +862,9 @@ fn spawn_pty_consumer_thread(
             const IDLE_COMPRESSION_BUDGET: usize = 4096;
 
             let mut emulator = terminal;
+            // Read when recording input, so the recorder's redaction layer
+            // can withhold what is typed at password prompts.
+            let echo_off = emulator.echo_off_atomic();
 
             let child_exit = child_exit_rx.unwrap_or_else(crossbeam_channel::never::<()>);
 
@@ -664,6 +917,12 @@ fn spawn_pty_consumer_thread(
                 let events = emulator.internal.handler.drain_command_events();
                 forward_command_events(events, recording_pane_id, &command_event_tx);
 
+                // Forward tmux control-mode events when this pane is the
+                // gateway of a `tmux -CC` session.
+                for event in emulator.internal.handler.drain_tmux_events() {
+                    send_or_log!(tmux_event_tx, event, "Failed to send tmux event to GUI");
+                }
+
                 let snap = emulator.build_snapshot();
                 arc_swap.store(Arc::new(snap));
 
@@ -722,10 +981,16 @@ fn spawn_pty_consumer_thread(
                                 error!("Failed to forward key bytes to PTY: {e}");
                             }
                             if let Some(rec) = recording_swap.load_full() {
-                                rec.emit(EventPayload::PtyInput {
-                                    pane_id: recording_pane_id,
-                                    data: bytes,
-                                });
+                                let echo_off = echo_off
+                                    .as_ref()
+                                    .is_some_and(|e| e.load(Ordering::Relaxed));
+                                rec.emit_input(
+                                    EventPayload::PtyInput {
+                                        pane_id: recording_pane_id,
+                                        data: bytes,
+                                    },
+                                    echo_off,
+                                );
                             }
                         }
                         InputEvent::FocusChange(focused) => {
@@ -734,6 +999,11 @@ fn spawn_pty_consumer_thread(
                             // change, nothing for the GUI to render (NoRepaint).
                             emulator.internal.send_focus_event(focused);
                         }
+                        InputEvent::Locator(locator_input) => {
+                            // Pointer state for DECRQLP / DECEFR, plus any
+                            // DECLRP report it triggers (NoRepaint).
+                            emulator.internal.handler.handle_locator_input(locator_input);
+                        }
                         InputEvent::ScrollOffset { offset, extra_rows } => {
                             emulator.set_requested_scroll_window(offset, extra_rows);
                         }
@@ -750,6 +1020,9 @@ fn spawn_pty_consumer_thread(
                                 .buffer_mut()
                                 .set_auto_detect_urls(enabled);
                         }
+                        InputEvent::AllowChecksumReports(allow) => {
+                            emulator.internal.handler.set_allow_checksum_reports(allow);
+                        }
                         InputEvent::ThemeModeUpdate(theme_mode, os_is_dark) => {
                             emulator.internal.modes.theme_mode = theme_mode;
                             // Sync the live theming state to match the OS preference
@@ -777,6 +1050,18 @@ fn spawn_pty_consumer_thread(
                             );
                             let _ = clipboard_tx.send(text);
                         }
+                        InputEvent::ExtractText {
+                            start_row,
+                            end_row,
+                            end_col,
+                            reply,
+                        } => {
+                            // Read-only; the remote-control connection thread
+                            // waits on `reply`, so the GUI needs no wake.
+                            let text = emulator
+                                .extract_selection_text(start_row, 0, end_row, end_col, false);
+                            let _ = reply.send(text);
+                        }
                         InputEvent::RequestSearchBuffer => {
                             // Read-only, BUT the GUI POLLS `search_buffer_rx` on a
                             // LATER frame (not a blocking recv), so a repaint MUST
@@ -796,6 +1081,11 @@ fn spawn_pty_consumer_thread(
                             emulator.internal.handler.buffer_mut().erase_scrollback();
                             emulator.set_requested_scroll_offset(0);
                         }
+                        InputEvent::PlaybackOutput(bytes) => {
+                            // Recorded output from the playback viewer; fed
+                            // like a PTY read. Not re-emitted to the recorder.
+                            emulator.handle_incoming_data(&bytes);
+                        }
                     }
 
                     outcome
@@ -991,6 +1281,7 @@ fn spawn_pty_consumer_thread(
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use freminal_common::buffer_states::locator::LocatorInput;
 
     /// Helper: build a fresh `CommandBlock` with the given fid.
     fn block_with_fid(fid: &str) -> CommandBlock {
@@ -1190,6 +1481,7 @@ mod tests {
             PtyTabInitialState {
                 theme: &DRACULA,
                 auto_detect_urls: seeded_auto_detect_urls,
+                allow_checksum_reports: true,
                 cursor_style: CursorVisualStyle::VerticalLineCursorBlink,
             },
         );
@@ -1199,6 +1491,7 @@ mod tests {
             handler.buffer_mut().auto_detect_urls(),
             seeded_auto_detect_urls
         );
+        assert!(handler.allow_checksum_reports());
         assert_eq!(
             handler.cursor_visual_style(),
             CursorVisualStyle::VerticalLineCursorBlink
@@ -1232,6 +1525,12 @@ mod tests {
             end_col: 1,
             is_block: false,
         }));
+        assert!(!input_event_needs_repaint(
+            &InputEvent::AllowChecksumReports(true)
+        ));
+        assert!(!input_event_needs_repaint(&InputEvent::Locator(
+            LocatorInput::Left
+        )));
 
         // Repaint: everything that mutates snapshot-visible state, plus
         // RequestSearchBuffer (polled on a later frame -> needs a guaranteed
@@ -1253,6 +1552,9 @@ mod tests {
         assert!(input_event_needs_repaint(&InputEvent::RequestSearchBuffer));
         assert!(input_event_needs_repaint(&InputEvent::AutoDetectUrls(true)));
         assert!(input_event_needs_repaint(&InputEvent::ClearScrollback));
+        assert!(input_event_needs_repaint(&InputEvent::PlaybackOutput(
+            b"x".to_vec()
+        )));
         assert!(input_event_needs_repaint(&InputEvent::CursorConfigChange(
             CursorVisualStyle::VerticalLineCursorBlink,
         )));
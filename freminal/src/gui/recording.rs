@@ -11,26 +11,43 @@
 //! this module wires the toggle (`KeyAction::ToggleRecording` and the
 //! Session menu entry) into the application state.
 //!
-//! # Why two fields on `FreminalGui`
+//! It also owns the flight recorder (`[recording] flight_recorder`): one
+//! in-memory ring per window, fed through that window's swap, dumped to a
+//! FREC file on `KeyAction::DumpFlightRecorder`, the Session menu, or
+//! `freminal msg dump-flight-recorder`.  A dump holds the focused window
+//! only.
 //!
-//! `recording_swap` is shared with every PTY thread via `RecordingSwap`
-//! (an `ArcSwap`). Each pane checks the swap on every event it emits; a
-//! non-`None` value means "record this event". The GUI flips recording
-//! on or off by storing a new handle (or clearing it) into the swap —
-//! no per-pane plumbing required.
+//! # Why a swap per window
+//!
+//! Each window has a `RecordingSwap` (an `ArcSwap`) in `recording_swaps`,
+//! shared with the PTY threads of every pane in that window. Each pane
+//! checks the swap on every event it emits; a non-`None` value means
+//! "record this event". The GUI flips recording on or off by storing a new
+//! handle (or clearing it) into the swaps — no per-pane plumbing required.
+//! Keeping one swap per window lets the window's own flight recorder ring
+//! sit beside the file recording without seeing other windows' events.
 //!
 //! `recording_join` lives solely on the GUI side so that stopping a
 //! recording can deterministically block until the writer thread has
 //! finalized the output file. Without it the file would only be flushed
 //! when the *last* `RecordingHandle` clone is dropped, which happens at
 //! process shutdown — unsuitable for a runtime toggle.
+//!
+//! When a window's flight recorder runs alongside a file recording, its
+//! swap holds a single handle fanning out to both (see
+//! [`RecordingHandle::fan_out`]); `recording_handle` and `flight_recorders`
+//! keep the parts so either can be swapped out without the other.
 
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+use freminal_terminal_emulator::flight_recorder::{FlightRecorder, FlightRecorderLimits};
 use freminal_terminal_emulator::recording::{
-    PaneNodeSnapshot, RecordingMetadata, TabSnapshot, TopologySnapshot, WindowSnapshot,
-    start_recording,
+    PaneNodeSnapshot, RecordingHandle, RecordingMetadata, RecordingSwap, TabSnapshot,
+    TopologySnapshot, WindowSnapshot, empty_recording_swap, start_recording,
 };
+use freminal_terminal_emulator::redaction::Redactor;
 
 impl super::FreminalGui {
     /// Build a full [`TopologySnapshot`] describing every open window,
@@ -127,8 +144,232 @@ impl super::FreminalGui {
 
     /// Return `true` when a FREC v2 recording is currently being written.
     #[must_use]
-    pub(super) fn is_recording(&self) -> bool {
-        self.recording_swap.load().is_some()
+    pub(super) const fn is_recording(&self) -> bool {
+        self.recording_handle.is_some()
+    }
+
+    /// Return `true` when `window_id` has a flight recorder ring.
+    #[must_use]
+    pub(super) fn is_flight_recording(&self, window_id: super::WindowId) -> bool {
+        self.window_flight_recorder(window_id).is_some()
+    }
+
+    /// The flight recorder ring of `window_id`, if it has one.
+    fn window_flight_recorder(&self, window_id: super::WindowId) -> Option<&FlightRecorder> {
+        self.recording_window_ids
+            .get(&window_id)
+            .and_then(|rec_wid| self.flight_recorders.get(*rec_wid))
+    }
+
+    /// The swap the panes of `window_id` record through.
+    ///
+    /// A window without one (which `open_window_recording` rules out) gets
+    /// a fresh swap that records nothing.
+    #[must_use]
+    pub(super) fn recording_swap(&self, window_id: super::WindowId) -> RecordingSwap {
+        self.recording_swaps
+            .get(&window_id)
+            .cloned()
+            .unwrap_or_else(empty_recording_swap)
+    }
+
+    /// [`Self::recording_swap`] for the window `repaint_handle` belongs to.
+    #[must_use]
+    pub(super) fn recording_swap_for_handle(
+        &self,
+        repaint_handle: &std::sync::OnceLock<(freminal_windowing::RepaintProxy, super::WindowId)>,
+    ) -> RecordingSwap {
+        repaint_handle
+            .get()
+            .map_or_else(empty_recording_swap, |(_, window_id)| {
+                self.recording_swap(*window_id)
+            })
+    }
+
+    /// The handle currently recording `window_id`'s events, if any.
+    #[must_use]
+    pub(super) fn recording_handle_for(
+        &self,
+        window_id: super::WindowId,
+    ) -> Option<Arc<RecordingHandle>> {
+        self.recording_swaps
+            .get(&window_id)
+            .and_then(|swap| swap.load_full())
+    }
+
+    /// Give a new window its recording swap and, while
+    /// `[recording] flight_recorder` is on, its own flight recorder ring.
+    ///
+    /// Runs before the window's first pane is spawned, so every pane
+    /// records through the swap from its first event.
+    pub(super) fn open_window_recording(&mut self, window_id: super::WindowId) {
+        self.recording_swaps
+            .entry(window_id)
+            .or_insert_with(empty_recording_swap);
+        if let Some(limits) = self.flight_recorder_limits() {
+            self.open_flight_recorder(window_id, limits);
+        }
+        self.refresh_recording_swap();
+    }
+
+    /// Drop the recording swap and flight recorder ring of a closed window.
+    pub(super) fn close_window_recording(&mut self, window_id: super::WindowId) {
+        if let Some(swap) = self.recording_swaps.remove(&window_id) {
+            // Release the handles the window's PTY threads may still hold.
+            swap.store(None);
+        }
+        if let Some(rec_wid) = self.recording_window_ids.get(&window_id) {
+            self.flight_recorders.close(*rec_wid);
+        }
+    }
+
+    /// Publish the file recording and each window's flight recorder,
+    /// whichever are active, to that window's panes through its swap.
+    fn refresh_recording_swap(&self) {
+        for (window_id, swap) in &self.recording_swaps {
+            let handles = self.recording_handle.iter().chain(
+                self.window_flight_recorder(*window_id)
+                    .map(FlightRecorder::handle),
+            );
+            swap.store(
+                RecordingHandle::fan_out(handles)
+                    .map(|handle| Arc::new(handle.with_redactor(self.redactor.clone()))),
+            );
+        }
+    }
+
+    /// Rebuild the redactor from `[recording]` and republish the recording
+    /// handle with it.
+    ///
+    /// Patterns that fail to compile are skipped and reported as error
+    /// toasts, as the paste guard does.
+    pub(super) fn sync_redaction(&mut self) {
+        let (redactor, invalid) = Redactor::new(
+            self.config.recording.redact_echo_off,
+            &self.config.recording.redact_patterns,
+        );
+        for (pattern, err) in invalid {
+            tracing::error!("Recording redaction: ignoring invalid pattern `{pattern}`: {err}");
+            self.push_error_toast(
+                "Invalid redaction pattern",
+                Some(format!("`{pattern}` — {err}")),
+            );
+        }
+        self.redactor = (!redactor.is_noop()).then(|| Arc::new(redactor));
+        self.refresh_recording_swap();
+    }
+
+    /// Metadata for a recording that starts now, with the current topology
+    /// as its initial state.
+    fn recording_metadata_now(&mut self) -> RecordingMetadata {
+        RecordingMetadata {
+            freminal_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: unix_now(),
+            term: "xterm-256color".to_string(),
+            initial_topology: self.build_topology_snapshot(),
+            scrollback_limit: self.config.scrollback.limit.try_into().unwrap_or(u32::MAX),
+        }
+    }
+
+    /// The ring limits `[recording]` asks for, or `None` while the flight
+    /// recorder is off (always, in a `--play` run).
+    fn flight_recorder_limits(&self) -> Option<FlightRecorderLimits> {
+        (self.config.recording.flight_recorder && self.playback.is_none()).then(|| {
+            FlightRecorderLimits {
+                max_duration: Duration::from_mins(u64::from(
+                    self.config.recording.flight_recorder_minutes,
+                )),
+                max_bytes: usize::try_from(self.config.recording.flight_recorder_max_mb)
+                    .unwrap_or(usize::MAX)
+                    .saturating_mul(1024 * 1024),
+            }
+        })
+    }
+
+    /// Start a flight recorder ring for `window_id`, replacing any it had.
+    ///
+    /// Failures are logged and toasted; the window simply has no ring.
+    fn open_flight_recorder(&mut self, window_id: super::WindowId, limits: FlightRecorderLimits) {
+        let rec_wid = self.recording_window_id(window_id);
+        let metadata = self.recording_metadata_now();
+        if let Err(e) = self.flight_recorders.open(rec_wid, metadata, limits, 4096) {
+            tracing::error!("Failed to start flight recorder for window {rec_wid}: {e}");
+            self.push_error_toast("Failed to start flight recorder", Some(e.to_string()));
+        }
+    }
+
+    /// Start, stop, or restart every window's flight recorder so they match
+    /// `[recording]` in the current config.
+    ///
+    /// Rings whose limits changed are replaced, which discards what they
+    /// had retained.  Failures are logged and toasted; the window's
+    /// recorder simply stays off.
+    pub(super) fn sync_flight_recorder(&mut self) {
+        let wanted = self.flight_recorder_limits();
+        if wanted == self.flight_recorders.limits() {
+            return;
+        }
+
+        self.flight_recorders.clear();
+        if let Some(limits) = wanted {
+            let window_ids: Vec<super::WindowId> = self.recording_swaps.keys().copied().collect();
+            for window_id in window_ids {
+                self.open_flight_recorder(window_id, limits);
+            }
+            tracing::info!(
+                "Flight recorder keeping {} min / {} MiB per window",
+                self.config.recording.flight_recorder_minutes,
+                self.config.recording.flight_recorder_max_mb
+            );
+        }
+        self.refresh_recording_swap();
+    }
+
+    /// Write the ring of `window_id` to a new file in the recording library
+    /// directory and return its path.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message when the window has no flight
+    /// recorder, no recording directory is available, or the file cannot
+    /// be written.
+    pub(super) fn dump_flight_recorder(
+        &self,
+        window_id: super::WindowId,
+    ) -> Result<PathBuf, String> {
+        let Some(recorder) = self.window_flight_recorder(window_id) else {
+            return Err("the flight recorder is not enabled".to_owned());
+        };
+        let dir = freminal_common::config::recording_library_dir()
+            .ok_or_else(|| "no recording library directory is available".to_owned())?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+        let path = dir.join(format!("freminal-flight-{}.frec", unix_now()));
+        let count = recorder
+            .dump(&path)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        tracing::info!(
+            "Dumped {count} flight recorder events to {}",
+            path.display()
+        );
+        Ok(path)
+    }
+
+    /// [`Self::dump_flight_recorder`] with the outcome reported as a toast.
+    pub(super) fn dump_flight_recorder_with_toast(&self, window_id: super::WindowId) {
+        match self.dump_flight_recorder(window_id) {
+            Ok(path) => self.route_freminal_toast(
+                freminal_common::config::FreminalToastCategory::Recording,
+                crate::gui::toast::ToastKind::Info,
+                "Flight recorder saved",
+                Some(format!("Saved to {}", path.display())),
+                crate::gui::toast::ToastPlacement::WINDOW_CENTERED,
+            ),
+            Err(e) => {
+                tracing::error!("Flight recorder dump failed: {e}");
+                self.push_error_toast("Failed to save flight recorder", Some(e));
+            }
+        }
     }
 
     /// Toggle the FREC v2 recording on or off.
@@ -164,25 +405,16 @@ impl super::FreminalGui {
             return;
         };
 
-        // Timestamped filename: freminal-YYYYMMDD-HHMMSS.frec
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_or(0, |d| d.as_secs());
-        let filename = format!("freminal-{now}.frec");
+        let metadata = self.recording_metadata_now();
+        // Timestamped filename: freminal-<unix seconds>.frec
+        let filename = format!("freminal-{}.frec", metadata.created_at);
         let path = dir.join(&filename);
 
-        let metadata = RecordingMetadata {
-            freminal_version: env!("CARGO_PKG_VERSION").to_string(),
-            created_at: now,
-            term: "xterm-256color".to_string(),
-            initial_topology: self.build_topology_snapshot(),
-            scrollback_limit: self.config.scrollback.limit.try_into().unwrap_or(u32::MAX),
-        };
-
         match start_recording(&path, metadata, 4096) {
             Ok((handle, join)) => {
                 tracing::info!("Started recording to {}", path.display());
-                self.recording_swap.store(Some(Arc::new(handle)));
+                self.recording_handle = Some(handle);
+                self.refresh_recording_swap();
                 self.recording_join = Some(join);
                 self.route_freminal_toast(
                     freminal_common::config::FreminalToastCategory::Recording,
@@ -204,11 +436,13 @@ impl super::FreminalGui {
     }
 
     fn stop_recording(&mut self) {
-        // Clear the swap first so PTY threads immediately stop emitting
-        // events. Dropping the handle(s) they may have cloned inside
-        // `RecordingSwap` closes the sender side of the channel, which
-        // causes the writer thread to exit its loop and finalize.
-        self.recording_swap.store(None);
+        // Take the file handle out of the swaps first so PTY threads
+        // immediately stop sending to it (the flight recorders, if any,
+        // keep running). Dropping the handle(s) they may have cloned
+        // inside `RecordingSwap` closes the sender side of the channel,
+        // which causes the writer thread to exit its loop and finalize.
+        self.recording_handle = None;
+        self.refresh_recording_swap();
 
         if let Some(mut join) = self.recording_join.take() {
             // Block until the writer has flushed and finalized the file.
@@ -238,3 +472,10 @@ impl super::FreminalGui {
         }
     }
 }
+
+/// Seconds since the Unix epoch, or 0 if the clock is before it.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
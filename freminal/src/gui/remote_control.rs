@@ -0,0 +1,668 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Remote-control socket server (`[security] allow_remote_control`).
+//!
+//! An accept thread owns the `UnixListener` and spawns one thread per client
+//! connection.  Connection threads decode request lines and forward each
+//! command to the GUI thread as a [`RemoteCall`] over a crossbeam channel,
+//! waking the event loop through the repaint proxy.  `update()` drains the
+//! channel via [`FreminalGui::poll_remote_control`], runs the command with
+//! full GUI state, and answers on the call's reply channel.  The GUI thread
+//! never blocks on a client; a connection thread that gets no answer in
+//! [`REPLY_TIMEOUT`] reports an error to its client instead.
+//!
+//! The protocol and socket discovery live in
+//! [`freminal_common::remote_control`].
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender};
+use freminal_common::keybindings::KeyAction;
+use freminal_common::remote_control::{
+    PaneInfo, RemoteCommand, RemoteResponse, RemoteResult, SplitAxis, TabInfo, TextExtent,
+    WindowInfo,
+};
+use freminal_terminal_emulator::io::InputEvent;
+use freminal_windowing::{RepaintProxy, WindowId};
+use tracing::{error, info, warn};
+
+use super::FreminalGui;
+use super::panes::{PaneId, SplitDirection};
+
+/// How long a connection thread waits for the GUI thread to answer a call.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a `get-text` connection thread waits for the PTY thread to
+/// extract a pane's text.
+const EXTRACT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// One command forwarded from a connection thread to the GUI thread.
+pub(super) struct RemoteCall {
+    command: RemoteCommand,
+    reply: Sender<RemoteReply>,
+}
+
+/// The GUI thread's answer to one [`RemoteCall`].
+enum RemoteReply {
+    /// The finished response.
+    Done(RemoteResponse),
+    /// `get-text`: the pane's PTY thread sends the text on this channel.
+    /// The connection thread waits for it so the GUI thread never does.
+    PendingText(Receiver<String>),
+}
+
+/// GUI-side handle to a running remote-control listener.
+///
+/// Dropping it removes the socket file.  The accept thread is left blocked
+/// in `accept()` and ends with the process.
+pub(super) struct RemoteControlServer {
+    socket_path: PathBuf,
+    calls: Receiver<RemoteCall>,
+}
+
+impl Drop for RemoteControlServer {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.socket_path) {
+            warn!(
+                "Failed to remove remote-control socket {}: {e}",
+                self.socket_path.display()
+            );
+        }
+    }
+}
+
+#[cfg(unix)]
+impl RemoteControlServer {
+    /// Bind this process's socket and start accepting clients.
+    fn start(repaint_handle: Arc<OnceLock<(RepaintProxy, WindowId)>>) -> std::io::Result<Self> {
+        use std::os::unix::fs::DirBuilderExt;
+        use std::os::unix::net::UnixListener;
+
+        let dir = freminal_common::remote_control::socket_dir();
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&dir)?;
+        ensure_private_dir(&dir)?;
+        let socket_path = freminal_common::remote_control::socket_path_in(&dir, std::process::id());
+        // A leftover socket with our pid can only be from a dead process
+        // whose pid was recycled; binding would fail with EADDRINUSE.
+        if socket_path.exists() {
+            std::fs::remove_file(&socket_path)?;
+        }
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let (calls_tx, calls_rx) = crossbeam_channel::unbounded::<RemoteCall>();
+        std::thread::Builder::new()
+            .name("remote-control-accept".to_owned())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => {
+                            let calls_tx = calls_tx.clone();
+                            let repaint_handle = Arc::clone(&repaint_handle);
+                            if let Err(e) = std::thread::Builder::new()
+                                .name("remote-control-conn".to_owned())
+                                .spawn(move || {
+                                    serve_connection(&stream, &calls_tx, &repaint_handle);
+                                })
+                            {
+                                error!("Failed to spawn remote-control connection thread: {e}");
+                            }
+                        }
+                        Err(e) => warn!("Remote-control accept failed: {e}"),
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            socket_path,
+            calls: calls_rx,
+        })
+    }
+}
+
+#[cfg(not(unix))]
+impl RemoteControlServer {
+    /// Remote control is Unix-only; always fails.
+    fn start(_repaint_handle: Arc<OnceLock<(RepaintProxy, WindowId)>>) -> std::io::Result<Self> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "remote control is only supported on Unix platforms",
+        ))
+    }
+}
+
+/// Refuse a socket directory that another user could have planted or can
+/// reach into.
+///
+/// `DirBuilder::create` with `recursive(true)` accepts an existing directory
+/// as-is, and without `XDG_RUNTIME_DIR` the path is a predictable
+/// `$TMPDIR/freminal-$USER`.  That directory is the only access control on a
+/// socket that can type into every pane, so it must be a real directory (not
+/// a symlink) owned by us with mode 0700.
+#[cfg(unix)]
+fn ensure_private_dir(dir: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let meta = std::fs::symlink_metadata(dir)?;
+    let problem = if !meta.file_type().is_dir() {
+        "is a symlink or not a directory"
+    } else if meta.uid() != nix::unistd::getuid().as_raw() {
+        "is owned by another user"
+    } else if meta.permissions().mode() & 0o777 != 0o700 {
+        "does not have mode 0700"
+    } else {
+        return Ok(());
+    };
+    Err(std::io::Error::new(
+        std::io::ErrorKind::PermissionDenied,
+        format!("socket directory {} {problem}", dir.display()),
+    ))
+}
+
+/// Answer every request line on one client connection, in order.
+#[cfg(unix)]
+fn serve_connection(
+    stream: &std::os::unix::net::UnixStream,
+    calls_tx: &Sender<RemoteCall>,
+    repaint_handle: &OnceLock<(RepaintProxy, WindowId)>,
+) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut writer = stream;
+    for line in BufReader::new(stream).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Remote-control read failed: {e}");
+                return;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match freminal_common::remote_control::decode_request(&line) {
+            Ok(request) => forward_call(request.command, calls_tx, repaint_handle),
+            Err(e) => RemoteResponse::error(e.to_string()),
+        };
+        let encoded = match freminal_common::remote_control::encode_line(&response) {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                error!("Failed to encode remote-control response: {e}");
+                return;
+            }
+        };
+        if let Err(e) = writer.write_all(encoded.as_bytes()) {
+            warn!("Remote-control write failed: {e}");
+            return;
+        }
+    }
+}
+
+/// Hand `command` to the GUI thread and wait for its answer.
+#[cfg(unix)]
+fn forward_call(
+    command: RemoteCommand,
+    calls_tx: &Sender<RemoteCall>,
+    repaint_handle: &OnceLock<(RepaintProxy, WindowId)>,
+) -> RemoteResponse {
+    let (reply_tx, reply_rx) = crossbeam_channel::bounded(1);
+    if calls_tx
+        .send(RemoteCall {
+            command,
+            reply: reply_tx,
+        })
+        .is_err()
+    {
+        return RemoteResponse::error("Freminal is shutting down");
+    }
+    // Wake the (otherwise sleeping) event loop so `update()` drains the call.
+    if let Some((proxy, wid)) = repaint_handle.get() {
+        proxy.request_repaint(*wid);
+    }
+    match reply_rx.recv_timeout(REPLY_TIMEOUT) {
+        Ok(RemoteReply::Done(response)) => response,
+        Ok(RemoteReply::PendingText(text_rx)) => match text_rx.recv_timeout(EXTRACT_TIMEOUT) {
+            Ok(text) => RemoteResponse::with_result(RemoteResult::Text(text)),
+            Err(e) => RemoteResponse::error(format!("pane did not return its text: {e}")),
+        },
+        Err(_) => RemoteResponse::error("Freminal did not answer in time"),
+    }
+}
+
+/// Where a remote command's target pane lives.
+#[derive(Clone, Copy)]
+struct PaneLocation {
+    window_id: WindowId,
+    tab_index: usize,
+    pane_id: PaneId,
+}
+
+impl FreminalGui {
+    /// Bind the remote-control socket if `[security] allow_remote_control`
+    /// is set.  Called once, at first-window creation, before any PTY is
+    /// spawned so every shell inherits `FREMINAL_LISTEN_ON`.
+    pub(super) fn start_remote_control(
+        &mut self,
+        repaint_handle: Arc<OnceLock<(RepaintProxy, WindowId)>>,
+    ) {
+        if !self.config.security.allow_remote_control {
+            return;
+        }
+        match RemoteControlServer::start(repaint_handle) {
+            Ok(server) => {
+                info!(
+                    "Remote control listening on {}",
+                    server.socket_path.display()
+                );
+                freminal_common::remote_control::set_listening_socket(server.socket_path.clone());
+                self.remote_control = Some(server);
+            }
+            Err(e) => {
+                error!("Failed to start remote control: {e}");
+                self.push_error_toast(
+                    "Remote control unavailable",
+                    Some(format!("The control socket could not be created: {e}")),
+                );
+            }
+        }
+    }
+
+    /// Run every remote-control call that arrived since the last frame.
+    ///
+    /// Called near the top of `update()`, while every window's state is
+    /// still in `self.windows`.  Cheap no-op when remote control is off or
+    /// nothing is queued.
+    pub(super) fn poll_remote_control(&mut self, handle: &freminal_windowing::WindowHandle<'_>) {
+        let calls: Vec<RemoteCall> = match &self.remote_control {
+            Some(server) => server.calls.try_iter().collect(),
+            None => return,
+        };
+        for call in calls {
+            let reply = self.run_remote_command(call.command, handle);
+            // The client may have timed out and gone away; nothing to do.
+            let _ = call.reply.send(reply);
+        }
+    }
+
+    fn run_remote_command(
+        &mut self,
+        command: RemoteCommand,
+        handle: &freminal_windowing::WindowHandle<'_>,
+    ) -> RemoteReply {
+        let result = match command {
+            RemoteCommand::Ls => Ok(Some(RemoteResult::Windows(self.remote_ls()))),
+            RemoteCommand::SendText { pane, text } => self
+                .locate_pane(pane)
+                .and_then(|loc| self.remote_send_text(loc, text))
+                .map(|()| None),
+            RemoteCommand::GetText { pane, extent } => {
+                return match self
+                    .locate_pane(pane)
+                    .and_then(|loc| self.remote_get_text(loc, extent))
+                {
+                    Ok(text_rx) => RemoteReply::PendingText(text_rx),
+                    Err(message) => RemoteReply::Done(RemoteResponse::error(message)),
+                };
+            }
+            RemoteCommand::Split { pane, direction } => self
+                .locate_pane(pane)
+                .and_then(|loc| self.remote_split(loc, direction, handle))
+                .map(|id| Some(RemoteResult::Pane(id))),
+            RemoteCommand::Focus { pane } => self
+                .locate_pane(Some(pane))
+                .and_then(|loc| self.remote_focus(loc, handle))
+                .map(|()| None),
+            RemoteCommand::SetTabTitle { pane, title } => self
+                .locate_pane(pane)
+                .and_then(|loc| self.remote_set_tab_title(loc, title.as_deref()))
+                .map(|()| None),
+            RemoteCommand::LoadLayout { layout, args, vars } => self
+                .remote_load_layout(&layout, &args, &vars, handle)
+                .map(|()| None),
+            RemoteCommand::Action { pane, action } => self
+                .locate_pane(pane)
+                .and_then(|loc| self.remote_action(loc, &action, handle))
+                .map(|()| None),
+            RemoteCommand::DumpFlightRecorder => self
+                .locate_pane(None)
+                .and_then(|loc| self.dump_flight_recorder(loc.window_id))
+                .map(|path| Some(RemoteResult::Path(path))),
+        };
+        RemoteReply::Done(match result {
+            Ok(Some(result)) => RemoteResponse::with_result(result),
+            Ok(None) => RemoteResponse::ok(),
+            Err(message) => RemoteResponse::error(message),
+        })
+    }
+
+    /// Terminal windows ordered by their stable per-process id.
+    fn remote_windows_in_order(&mut self) -> Vec<(u32, WindowId)> {
+        let window_ids: Vec<WindowId> = self.windows.keys().copied().collect();
+        let mut ordered: Vec<(u32, WindowId)> = window_ids
+            .into_iter()
+            .map(|wid| (self.recording_window_id(wid), wid))
+            .collect();
+        ordered.sort_by_key(|(id, _)| *id);
+        ordered
+    }
+
+    /// Resolve a pane id, or the focused window's active pane when `None`.
+    fn locate_pane(&mut self, pane: Option<u64>) -> Result<PaneLocation, String> {
+        let ordered = self.remote_windows_in_order();
+        let Some(raw) = pane else {
+            // The focused window, falling back to the oldest one when no
+            // Freminal window has OS focus (the usual case for scripts run
+            // from another terminal).
+            let window_id = ordered
+                .iter()
+                .map(|(_, wid)| *wid)
+                .find(|wid| self.windows.get(wid).is_some_and(|w| w.prev_window_focused))
+                .or_else(|| ordered.first().map(|(_, wid)| *wid))
+                .ok_or_else(|| "no terminal window is open".to_owned())?;
+            let win = self
+                .windows
+                .get(&window_id)
+                .ok_or_else(|| "no terminal window is open".to_owned())?;
+            return Ok(PaneLocation {
+                window_id,
+                tab_index: win.tabs.active_index(),
+                pane_id: win.tabs.active_tab().active_pane,
+            });
+        };
+        for (_, window_id) in ordered {
+            let Some(win) = self.windows.get(&window_id) else {
+                continue;
+            };
+            for (tab_index, tab) in win.tabs.iter().enumerate() {
+                let panes = tab.pane_tree.iter_panes().unwrap_or_default();
+                if let Some(found) = panes.iter().find(|p| p.id.raw() == raw) {
+                    return Ok(PaneLocation {
+                        window_id,
+                        tab_index,
+                        pane_id: found.id,
+                    });
+                }
+            }
+        }
+        Err(format!("no such pane: {raw}"))
+    }
+
+    fn remote_ls(&mut self) -> Vec<WindowInfo> {
+        let title_config = self.config.tab_title.clone();
+        let mut windows = Vec::new();
+        for (id, window_id) in self.remote_windows_in_order() {
+            let Some(win) = self.windows.get(&window_id) else {
+                continue;
+            };
+            let active_index = win.tabs.active_index();
+            let tabs = win
+                .tabs
+                .iter()
+                .enumerate()
+                .map(|(index, tab)| {
+                    let panes = match tab.pane_tree.iter_panes() {
+                        Ok(panes) => panes,
+                        Err(e) => {
+                            error!("remote ls: failed to walk pane tree: {e}");
+                            Vec::new()
+                        }
+                    };
+                    TabInfo {
+                        index,
+                        title: tab.display_name(&title_config).into_owned(),
+                        active: index == active_index,
+                        panes: panes
+                            .into_iter()
+                            .map(|pane| {
+                                let snap = pane.arc_swap.load();
+                                PaneInfo {
+                                    id: pane.id.raw(),
+                                    title: pane.title.clone(),
+                                    active: pane.id == tab.active_pane,
+                                    cwd: snap.cwd.clone(),
+                                    columns: snap.term_width,
+                                    rows: snap.term_height,
+                                }
+                            })
+                            .collect(),
+                    }
+                })
+                .collect();
+            windows.push(WindowInfo {
+                id,
+                focused: win.prev_window_focused,
+                tabs,
+            });
+        }
+        windows
+    }
+
+    fn remote_send_text(&self, loc: PaneLocation, text: String) -> Result<(), String> {
+        let pane = self
+            .windows
+            .get(&loc.window_id)
+            .and_then(|win| win.tabs.iter().nth(loc.tab_index))
+            .and_then(|tab| tab.pane_tree.find(loc.pane_id))
+            .ok_or_else(|| format!("no such pane: {}", loc.pane_id.raw()))?;
+        pane.input_tx
+            .send(InputEvent::Key(text.into_bytes()))
+            .map_err(|e| format!("failed to send text to pane: {e}"))
+    }
+
+    /// Ask the pane's PTY thread for its text.  Returns the channel the text
+    /// arrives on; the caller's connection thread does the waiting.
+    fn remote_get_text(
+        &self,
+        loc: PaneLocation,
+        extent: TextExtent,
+    ) -> Result<Receiver<String>, String> {
+        let pane = self
+            .windows
+            .get(&loc.window_id)
+            .and_then(|win| win.tabs.iter().nth(loc.tab_index))
+            .and_then(|tab| tab.pane_tree.find(loc.pane_id))
+            .ok_or_else(|| format!("no such pane: {}", loc.pane_id.raw()))?;
+        let snap = pane.arc_swap.load();
+        let start_row = match extent {
+            TextExtent::Screen => snap.total_rows.saturating_sub(snap.term_height),
+            TextExtent::All => 0,
+        };
+        let (reply, text_rx) = crossbeam_channel::bounded(1);
+        pane.input_tx
+            .send(InputEvent::ExtractText {
+                start_row,
+                end_row: snap.total_rows.saturating_sub(1),
+                end_col: snap.term_width.saturating_sub(1),
+                reply,
+            })
+            .map_err(|e| format!("failed to request pane text: {e}"))?;
+        Ok(text_rx)
+    }
+
+    /// Raise the pane's window, switch to its tab, and make it the active
+    /// pane, moving DEC 1004 focus the same way pane navigation does.
+    fn remote_focus(
+        &mut self,
+        loc: PaneLocation,
+        handle: &freminal_windowing::WindowHandle<'_>,
+    ) -> Result<(), String> {
+        self.focus_pane_location(loc)?;
+        handle.focus_window(loc.window_id);
+        handle.request_repaint(loc.window_id);
+        Ok(())
+    }
+
+    /// Make `loc` the visible tab and active pane of its window without
+    /// raising the window.
+    fn focus_pane_location(&mut self, loc: PaneLocation) -> Result<(), String> {
+        let win = self
+            .windows
+            .get_mut(&loc.window_id)
+            .ok_or_else(|| "window closed".to_owned())?;
+        if win.tabs.active_index() != loc.tab_index {
+            win.tabs
+                .switch_to(loc.tab_index)
+                .map_err(|e| format!("failed to switch tab: {e}"))?;
+        }
+        let tab = win.tabs.active_tab_mut();
+        // A zoomed sibling would hide the pane we are focusing.
+        if tab.zoomed_pane.is_some_and(|z| z != loc.pane_id) {
+            tab.zoomed_pane = None;
+        }
+        let old_id = tab.active_pane;
+        if old_id == loc.pane_id {
+            return Ok(());
+        }
+        if let Some(old_pane) = tab.pane_tree.find(old_id)
+            && let Err(e) = old_pane.input_tx.send(InputEvent::FocusChange(false))
+        {
+            error!("Failed to send FocusChange(false) to pane {old_id}: {e}");
+        }
+        tab.active_pane = loc.pane_id;
+        if let Some(new_pane) = tab.pane_tree.find(loc.pane_id)
+            && let Err(e) = new_pane.input_tx.send(InputEvent::FocusChange(true))
+        {
+            error!(
+                "Failed to send FocusChange(true) to pane {}: {e}",
+                loc.pane_id
+            );
+        }
+        Ok(())
+    }
+
+    fn remote_split(
+        &mut self,
+        loc: PaneLocation,
+        axis: SplitAxis,
+        handle: &freminal_windowing::WindowHandle<'_>,
+    ) -> Result<u64, String> {
+        self.focus_pane_location(loc)?;
+        let mut win = self
+            .windows
+            .remove(&loc.window_id)
+            .ok_or_else(|| "window closed".to_owned())?;
+        // Same mapping as the `SplitVertical` / `SplitHorizontal` actions.
+        let direction = match axis {
+            SplitAxis::Vertical => SplitDirection::Horizontal,
+            SplitAxis::Horizontal => SplitDirection::Vertical,
+        };
+        self.spawn_split_pane(&mut win, direction);
+        let new_id = win.tabs.active_tab().active_pane;
+        self.windows.insert(loc.window_id, win);
+        handle.request_repaint(loc.window_id);
+        if new_id == loc.pane_id {
+            // `spawn_split_pane` has already logged and toasted the cause.
+            return Err("failed to split pane".to_owned());
+        }
+        Ok(new_id.raw())
+    }
+
+    fn remote_set_tab_title(
+        &mut self,
+        loc: PaneLocation,
+        title: Option<&str>,
+    ) -> Result<(), String> {
+        let tab = self
+            .windows
+            .get_mut(&loc.window_id)
+            .and_then(|win| win.tabs.iter_mut().nth(loc.tab_index))
+            .ok_or_else(|| "tab closed".to_owned())?;
+        // Same normalisation as an inline tab rename: blank clears.
+        tab.custom_name = title
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_owned);
+        Ok(())
+    }
+
+    /// Load a layout into the focused (or oldest) window, exactly as picking
+    /// it from the Layouts menu would.
+    fn remote_load_layout(
+        &mut self,
+        layout: &str,
+        args: &[String],
+        vars: &std::collections::HashMap<String, String>,
+        handle: &freminal_windowing::WindowHandle<'_>,
+    ) -> Result<(), String> {
+        let path = Self::resolve_startup_layout_path(layout);
+        let resolved = freminal_common::layout::Layout::from_file(&path)
+            .and_then(|l| l.apply_variables(args, vars).resolve())
+            .map_err(|e| format!("failed to load layout {}: {e}", path.display()))?;
+        let window_id = self.locate_pane(None)?.window_id;
+        let commands = self.apply_layout(&resolved, window_id, handle);
+        self.inject_layout_commands(&commands);
+        handle.request_repaint(window_id);
+        Ok(())
+    }
+
+    /// Focus the pane, then run `action` through the same path a menu click
+    /// takes.
+    fn remote_action(
+        &mut self,
+        loc: PaneLocation,
+        action: &str,
+        handle: &freminal_windowing::WindowHandle<'_>,
+    ) -> Result<(), String> {
+        let action = KeyAction::from_str(action).map_err(|e| e.to_string())?;
+        self.focus_pane_location(loc)?;
+        let mut win = self
+            .windows
+            .remove(&loc.window_id)
+            .ok_or_else(|| "window closed".to_owned())?;
+        let mut deferred = Vec::new();
+        Self::dispatch_menu_action(&mut win, action, &mut deferred);
+        for action in deferred {
+            self.dispatch_deferred_action(action, &mut win, loc.window_id, handle);
+        }
+        self.windows.insert(loc.window_id, win);
+        handle.request_repaint(loc.window_id);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use super::ensure_private_dir;
+
+    fn set_mode(path: &std::path::Path, mode: u32) {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn private_dir_accepts_owned_0700_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        set_mode(tmp.path(), 0o700);
+        ensure_private_dir(tmp.path()).unwrap();
+    }
+
+    #[test]
+    fn private_dir_rejects_loose_permissions() {
+        let tmp = tempfile::tempdir().unwrap();
+        set_mode(tmp.path(), 0o755);
+        let err = ensure_private_dir(tmp.path()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn private_dir_rejects_symlink() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("target");
+        std::fs::create_dir(&target).unwrap();
+        set_mode(&target, 0o700);
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        let err = ensure_private_dir(&link).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    }
+}
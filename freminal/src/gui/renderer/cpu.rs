@@ -0,0 +1,668 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Software rendering backend for screenshots without a GL context.
+//!
+//! [`CpuRenderer`] draws a [`TerminalSnapshot`] into an RGBA image so that
+//! `freminal render --screenshot` works on headless build hosts.  It shares
+//! everything with the GPU path up to the draw calls: text is shaped by
+//! [`ShapingCache`], glyphs (including the procedural box-drawing set) are
+//! rasterised into a [`GlyphAtlas`], and the instance and vertex buffers come
+//! from the same builders in [`super::vertex`].  Only the final step differs:
+//! instead of uploading the buffers, they are rasterised here following the
+//! shaders in `shaders/`, in the same pass order as
+//! [`super::gpu::TerminalRenderer`] — cell backgrounds, decorations and the
+//! cursor, glyphs, then inline images.
+//!
+//! Compositing uses premultiplied alpha with the `ONE, ONE_MINUS_SRC_ALPHA`
+//! blend egui sets up, and textures are sampled bilinearly like the GPU's
+//! `LINEAR` filter, so the output matches the window pixel for pixel up to
+//! rounding.  Window-level effects are not drawn: the background image,
+//! background opacity and the post-processing shader.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use conv2::{ApproxFrom, ConvUtil, ValueFrom};
+use freminal_common::colors::TerminalColor;
+use freminal_common::config::Config;
+use freminal_terminal_emulator::InlineImage;
+use freminal_terminal_emulator::LineWidth;
+use freminal_terminal_emulator::snapshot::TerminalSnapshot;
+use image::{ImageFormat, RgbaImage};
+
+use super::vertex::{
+    BG_INSTANCE_FLOATS, BackgroundFrame, DECO_VERTEX_FLOATS, FG_INSTANCE_FLOATS, FgRenderOptions,
+    IMG_VERTEX_FLOATS, ImageDrawEntry, VERTS_PER_QUAD, build_background_instances,
+    build_foreground_instances, build_image_verts,
+};
+use crate::gui::atlas::GlyphAtlas;
+use crate::gui::colors::{highlight_theme, internal_color_to_gl};
+use crate::gui::font_manager::{FontManager, FontManagerError};
+use crate::gui::shaping::{ShapedLine, ShapingCache};
+
+/// Draws terminal snapshots into RGBA images on the CPU.
+///
+/// Keeps its font manager, shaping cache and glyph atlas between calls, so
+/// rendering several snapshots with one renderer only rasterises each glyph
+/// once.
+pub struct CpuRenderer {
+    font_manager: FontManager,
+    shaping_cache: ShapingCache,
+    atlas: GlyphAtlas,
+    ligatures: bool,
+}
+
+impl CpuRenderer {
+    /// Create a renderer using the fonts and ligature setting from `config`.
+    ///
+    /// `pixels_per_point` scales the font as it would on a display with that
+    /// scale factor; `1.0` gives the same cell size as an unscaled window.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`FontManagerError`] from loading the fonts.
+    pub fn new(config: &Config, pixels_per_point: f32) -> Result<Self, FontManagerError> {
+        Ok(Self {
+            font_manager: FontManager::new(config, pixels_per_point)?,
+            shaping_cache: ShapingCache::new(),
+            atlas: GlyphAtlas::default(),
+            ligatures: config.font.ligatures,
+        })
+    }
+
+    /// Cell size in pixels, `(width, height)`.
+    #[must_use]
+    pub const fn cell_size(&self) -> (u32, u32) {
+        self.font_manager.cell_size()
+    }
+
+    /// Draw the visible screen of `snap`.
+    ///
+    /// The image is `term_width` cells wide and `term_height` cells tall.
+    /// Scrollback is never drawn, and there is no selection or search
+    /// highlight.  The cursor is drawn in its "on" blink phase when the
+    /// snapshot shows it.
+    pub fn render(&mut self, snap: &TerminalSnapshot) -> RgbaImage {
+        let (cell_w, cell_h) = self.font_manager.cell_size();
+        let col_px = f32::approx_from(cell_w).unwrap_or(0.0);
+        let row_px = f32::approx_from(cell_h).unwrap_or(0.0);
+        let reverse_screen = !snap.is_normal_display;
+
+        let shaped_lines: Vec<Arc<ShapedLine>> = self
+            .shaping_cache
+            .shape_visible(
+                &snap.visible_chars,
+                &snap.visible_tags,
+                snap.term_width,
+                &mut self.font_manager,
+                col_px,
+                self.ligatures,
+                &snap.visible_line_widths,
+            )
+            .into_iter()
+            .skip(snap.window_extra_rows)
+            .take(snap.term_height)
+            .collect();
+
+        let (cursor_pixel_pos, cursor_x_scale) = cursor_placement(snap, col_px, row_px);
+        let theme = highlight_theme(snap);
+        let mut bg_instances = Vec::new();
+        let mut deco_verts = Vec::new();
+        let _cursor_drawn = build_background_instances(
+            &BackgroundFrame {
+                shaped_lines: &shaped_lines,
+                cell_width: cell_w,
+                cell_height: cell_h,
+                ascent: self.font_manager.ascent(),
+                underline_offset: self.font_manager.underline_offset(),
+                strikeout_offset: self.font_manager.strikeout_offset(),
+                stroke_size: self.font_manager.stroke_size(),
+                show_cursor: snap.show_cursor,
+                cursor_blink_on: true,
+                cursor_pixel_pos,
+                cursor_width_scale: cursor_x_scale,
+                cursor_visual_style: &snap.cursor_visual_style,
+                selection: None,
+                selection_is_block: false,
+                match_highlights: &[],
+                command_block_hover_rows: None,
+                term_width_cols: snap.term_width,
+                theme: &theme,
+                cursor_color_override: snap.cursor_color_override,
+                reverse_screen,
+                special_colors: snap.special_colors,
+            },
+            &mut bg_instances,
+            &mut deco_verts,
+        );
+
+        let mut fg_instances = Vec::new();
+        build_foreground_instances(
+            &shaped_lines,
+            &mut self.atlas,
+            &self.font_manager,
+            cell_h,
+            self.font_manager.ascent(),
+            &FgRenderOptions {
+                reverse_screen,
+                soft_font: Some(Arc::clone(&snap.soft_font)),
+                special_colors: snap.special_colors,
+                ..FgRenderOptions::all_visible(None)
+            },
+            &theme,
+            &mut fg_instances,
+        );
+
+        let placements = snap
+            .visible_image_placements
+            .get(snap.window_extra_rows.saturating_mul(snap.term_width)..)
+            .unwrap_or_default();
+        let mut image_verts = Vec::new();
+        let mut image_draw_order = Vec::new();
+        build_image_verts(
+            placements,
+            &snap.images,
+            snap.term_width,
+            cell_w,
+            cell_h,
+            &mut image_verts,
+            &mut image_draw_order,
+        );
+
+        let mut canvas = Canvas::new(
+            pixels_across(snap.term_width, cell_w),
+            pixels_across(snap.term_height, cell_h),
+            // The panel clear the GPU path leaves default-background
+            // cells showing.
+            internal_color_to_gl(TerminalColor::DefaultBackground, false, snap.theme),
+        );
+
+        canvas.draw_backgrounds(&bg_instances, col_px, row_px);
+        canvas.draw_decorations(&deco_verts);
+        canvas.draw_glyphs(
+            &fg_instances,
+            &Texture {
+                pixels: self.atlas.pixels(),
+                width: self.atlas.size(),
+                height: self.atlas.size(),
+            },
+        );
+        canvas.draw_images(&image_verts, &image_draw_order, &snap.images);
+        canvas.into_image()
+    }
+}
+
+/// Encode `image` as PNG.
+///
+/// # Errors
+///
+/// Returns the encoder's error; encoding into memory does not fail in
+/// practice.
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+/// Where the cursor is drawn and its width scale, the same placement as the
+/// widget without the trail animation.
+fn cursor_placement(snap: &TerminalSnapshot, col_px: f32, row_px: f32) -> ((f32, f32), f32) {
+    let x_scale = if snap
+        .visible_line_widths
+        .get(snap.cursor_pos.y.saturating_add(snap.window_extra_rows))
+        .copied()
+        .unwrap_or(LineWidth::Normal)
+        .is_double_width()
+    {
+        2.0
+    } else {
+        1.0
+    };
+    let pos = (
+        f32::approx_from(snap.cursor_pos.x).unwrap_or(0.0) * col_px * x_scale,
+        f32::approx_from(snap.cursor_pos.y).unwrap_or(0.0) * row_px,
+    );
+    (pos, x_scale)
+}
+
+fn pixels_across(cells: usize, cell_px: u32) -> u32 {
+    u32::value_from(cells)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(cell_px)
+}
+
+const fn premultiply([r, g, b, a]: [f32; 4]) -> [f32; 4] {
+    [r * a, g * a, b * a, a]
+}
+
+// ---------------------------------------------------------------------------
+//  Rasterisation
+// ---------------------------------------------------------------------------
+
+/// A triangle corner: pixel position (top-left origin) and texture
+/// coordinate.
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    x: f32,
+    y: f32,
+    u: f32,
+    v: f32,
+}
+
+impl Vertex {
+    const fn new(x: f32, y: f32, u: f32, v: f32) -> Self {
+        Self { x, y, u, v }
+    }
+}
+
+/// A premultiplied RGBA framebuffer.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, clear: [f32; 4]) -> Self {
+        let len = usize::value_from(width)
+            .unwrap_or(0)
+            .saturating_mul(usize::value_from(height).unwrap_or(0));
+        Self {
+            width,
+            height,
+            pixels: vec![clear; len],
+        }
+    }
+
+    /// Cell backgrounds: one `cell_w`×`cell_h` quad per instance, as in
+    /// `bg_inst.vert`.
+    fn draw_backgrounds(&mut self, instances: &[f32], cell_w: f32, cell_h: f32) {
+        for inst in instances.chunks_exact(BG_INSTANCE_FLOATS) {
+            let x0 = inst[0] * cell_w;
+            let y0 = inst[1] * cell_h;
+            let color = premultiply([inst[2], inst[3], inst[4], inst[5]]);
+            self.fill_rect([x0, y0, x0 + cell_w, y0 + cell_h], [0.0; 4], &mut |_, _| {
+                color
+            });
+        }
+    }
+
+    /// Underlines, strikethrough and the cursor: flat-colored triangles.
+    fn draw_decorations(&mut self, verts: &[f32]) {
+        for tri in verts.chunks_exact(3 * DECO_VERTEX_FLOATS) {
+            let vertex = |i: usize| {
+                let v = &tri[i * DECO_VERTEX_FLOATS..];
+                Vertex::new(v[0], v[1], 0.0, 0.0)
+            };
+            let color = premultiply([tri[2], tri[3], tri[4], tri[5]]);
+            self.fill_triangle([vertex(0), vertex(1), vertex(2)], &mut |_, _| color);
+        }
+    }
+
+    /// Glyph quads sampled from the atlas, as in `fg.frag`.
+    fn draw_glyphs(&mut self, instances: &[f32], atlas: &Texture<'_>) {
+        for inst in instances.chunks_exact(FG_INSTANCE_FLOATS) {
+            let [
+                x,
+                y,
+                width,
+                height,
+                u0,
+                v0,
+                u1,
+                v1,
+                red,
+                green,
+                blue,
+                alpha,
+                is_color,
+            ] = inst
+            else {
+                continue;
+            };
+            let is_color = *is_color > 0.5;
+            let tint = [*red, *green, *blue, *alpha];
+            let rect = [*x, *y, x + width, y + height];
+            self.fill_rect(rect, [*u0, *v0, *u1, *v1], &mut |u, v| {
+                let texel = atlas.sample(u, v);
+                if is_color {
+                    // Color emoji are premultiplied in the atlas already.
+                    texel
+                } else {
+                    // Monochrome glyphs are white+alpha, tinted here.
+                    premultiply([tint[0], tint[1], tint[2], tint[3] * texel[3]])
+                }
+            });
+        }
+    }
+
+    /// Inline images; vertex slab N belongs to `draw_order[N]`.
+    fn draw_images(
+        &mut self,
+        verts: &[f32],
+        draw_order: &[ImageDrawEntry],
+        images: &HashMap<u64, InlineImage>,
+    ) {
+        let slab = VERTS_PER_QUAD * IMG_VERTEX_FLOATS;
+        for (entry, quad) in draw_order.iter().zip(verts.chunks_exact(slab)) {
+            let Some(img) = images.get(&entry.image_id) else {
+                continue;
+            };
+            let texture = Texture {
+                pixels: &img.pixels,
+                width: img.width_px,
+                height: img.height_px,
+            };
+            for tri in quad.chunks_exact(3 * IMG_VERTEX_FLOATS) {
+                let vertex = |i: usize| {
+                    let v = &tri[i * IMG_VERTEX_FLOATS..];
+                    Vertex::new(v[0], v[1], v[2], v[3])
+                };
+                // Image pixels are straight alpha.
+                self.fill_triangle([vertex(0), vertex(1), vertex(2)], &mut |u, v| {
+                    premultiply(texture.sample(u, v))
+                });
+            }
+        }
+    }
+
+    /// Blend `src` over the pixel at `(x, y)` with `ONE, ONE_MINUS_SRC_ALPHA`.
+    fn blend(&mut self, x: u32, y: u32, src: [f32; 4]) {
+        let index = usize::value_from(y)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(usize::value_from(self.width).unwrap_or(0))
+            .saturating_add(usize::value_from(x).unwrap_or(usize::MAX));
+        if let Some(dst) = self.pixels.get_mut(index) {
+            let keep = 1.0 - src[3];
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d = d.mul_add(keep, s);
+            }
+        }
+    }
+
+    /// Fill the axis-aligned rectangle `[x0, y0, x1, y1]`, interpolating
+    /// the texture rectangle `[u0, v0, u1, v1]` across it — the unit-quad
+    /// instancing the background and foreground shaders do.
+    fn fill_rect(
+        &mut self,
+        [x0, y0, x1, y1]: [f32; 4],
+        [u0, v0, u1, v1]: [f32; 4],
+        shade: &mut impl FnMut(f32, f32) -> [f32; 4],
+    ) {
+        let top_left = Vertex::new(x0, y0, u0, v0);
+        let top_right = Vertex::new(x1, y0, u1, v0);
+        let bottom_left = Vertex::new(x0, y1, u0, v1);
+        let bottom_right = Vertex::new(x1, y1, u1, v1);
+        self.fill_triangle([top_left, top_right, bottom_left], shade);
+        self.fill_triangle([top_right, bottom_right, bottom_left], shade);
+    }
+
+    /// Fill a triangle, calling `shade` with the interpolated texture
+    /// coordinate at every covered pixel centre.
+    ///
+    /// Coverage follows the GL rules: a pixel is covered when its centre is
+    /// inside the triangle, and a centre exactly on an edge belongs to only
+    /// one of the two triangles sharing that edge, so a translucent quad is
+    /// not blended twice along its diagonal.
+    fn fill_triangle(
+        &mut self,
+        [v0, mut v1, mut v2]: [Vertex; 3],
+        shade: &mut impl FnMut(f32, f32) -> [f32; 4],
+    ) {
+        let mut area = edge(v0, v1, v2.x, v2.y);
+        if area < 0.0 {
+            std::mem::swap(&mut v1, &mut v2);
+            area = -area;
+        }
+        if area <= 0.0 || !area.is_finite() {
+            return;
+        }
+
+        let min_x = v0.x.min(v1.x).min(v2.x).floor().max(0.0);
+        let min_y = v0.y.min(v1.y).min(v2.y).floor().max(0.0);
+        let max_x = v0.x.max(v1.x).max(v2.x).ceil();
+        let max_y = v0.y.max(v1.y).max(v2.y).ceil();
+        let clamp = |v: f32, limit: u32| {
+            v.approx_as::<u32>()
+                .unwrap_or(if v > 0.0 { limit } else { 0 })
+                .min(limit)
+        };
+        let (x_start, x_end) = (clamp(min_x, self.width), clamp(max_x, self.width));
+        let (y_start, y_end) = (clamp(min_y, self.height), clamp(max_y, self.height));
+
+        for py in y_start..y_end {
+            let cy = f32::approx_from(py).unwrap_or(0.0) + 0.5;
+            for px in x_start..x_end {
+                let cx = f32::approx_from(px).unwrap_or(0.0) + 0.5;
+                let w0 = edge(v1, v2, cx, cy);
+                let w1 = edge(v2, v0, cx, cy);
+                let w2 = edge(v0, v1, cx, cy);
+                if !(covers(w0, v1, v2) && covers(w1, v2, v0) && covers(w2, v0, v1)) {
+                    continue;
+                }
+                let (l0, l1, l2) = (w0 / area, w1 / area, w2 / area);
+                let u = l2.mul_add(v2.u, l0.mul_add(v0.u, l1 * v1.u));
+                let v = l2.mul_add(v2.v, l0.mul_add(v0.v, l1 * v1.v));
+                self.blend(px, py, shade(u, v));
+            }
+        }
+    }
+
+    /// Un-premultiply into 8-bit RGBA.
+    fn into_image(self) -> RgbaImage {
+        let mut bytes = Vec::with_capacity(self.pixels.len().saturating_mul(4));
+        for [r, g, b, a] in self.pixels {
+            let unmul = |c: f32| if a > 0.0 { c / a } else { 0.0 };
+            bytes.extend([unmul(r), unmul(g), unmul(b), a].map(to_u8));
+        }
+        RgbaImage::from_raw(self.width, self.height, bytes)
+            .unwrap_or_else(|| RgbaImage::new(self.width, self.height))
+    }
+}
+
+/// Twice the signed area of `(p, q, (x, y))`; positive when the point is on
+/// the inner side of the edge `p → q` of a positively wound triangle.
+fn edge(p: Vertex, q: Vertex, x: f32, y: f32) -> f32 {
+    (q.x - p.x).mul_add(y - p.y, -((q.y - p.y) * (x - p.x)))
+}
+
+/// Whether a pixel centre with edge value `w` for edge `p → q` is covered.
+///
+/// Centres exactly on the edge go to exactly one of the two triangles that
+/// share it, because the two traverse the edge in opposite directions.
+fn covers(w: f32, p: Vertex, q: Vertex) -> bool {
+    let (dx, dy) = (q.x - p.x, q.y - p.y);
+    w > 0.0 || (w == 0.0 && (dy > 0.0 || (dy == 0.0 && dx < 0.0)))
+}
+
+fn to_u8(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0)
+        .round()
+        .approx_as::<u8>()
+        .unwrap_or(0)
+}
+
+/// An RGBA8 texture, sampled with clamp-to-edge bilinear filtering.
+struct Texture<'a> {
+    pixels: &'a [u8],
+    width: u32,
+    height: u32,
+}
+
+impl Texture<'_> {
+    /// Bilinear sample at normalised `(u, v)`, as `[0, 1]` floats.
+    fn sample(&self, u: f32, v: f32) -> [f32; 4] {
+        let width = f32::approx_from(self.width).unwrap_or(0.0);
+        let height = f32::approx_from(self.height).unwrap_or(0.0);
+        let (tx, ty) = (u.mul_add(width, -0.5), v.mul_add(height, -0.5));
+        let (fx, fy) = (tx.floor(), ty.floor());
+        let (ax, ay) = (tx - fx, ty - fy);
+
+        let texel = |x: f32, y: f32| -> [f32; 4] {
+            let x = x
+                .max(0.0)
+                .approx_as::<u32>()
+                .unwrap_or(0)
+                .min(self.width.saturating_sub(1));
+            let y = y
+                .max(0.0)
+                .approx_as::<u32>()
+                .unwrap_or(0)
+                .min(self.height.saturating_sub(1));
+            let index = usize::value_from(y)
+                .unwrap_or(0)
+                .saturating_mul(usize::value_from(self.width).unwrap_or(0))
+                .saturating_add(usize::value_from(x).unwrap_or(0))
+                .saturating_mul(4);
+            self.pixels.get(index..index + 4).map_or([0.0; 4], |p| {
+                [p[0], p[1], p[2], p[3]].map(|c| f32::from(c) / 255.0)
+            })
+        };
+
+        let lerp = |p: [f32; 4], q: [f32; 4], t: f32| -> [f32; 4] {
+            [0, 1, 2, 3].map(|i| (q[i] - p[i]).mul_add(t, p[i]))
+        };
+        let top = lerp(texel(fx, fy), texel(fx + 1.0, fy), ax);
+        let bottom = lerp(texel(fx, fy + 1.0), texel(fx + 1.0, fy + 1.0), ax);
+        lerp(top, bottom, ay)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use freminal_common::themes;
+    use freminal_terminal_emulator::headless::HeadlessTerminal;
+
+    /// Render `bytes` on a `cols`×`rows` screen with the cursor hidden.
+    fn render(bytes: &[u8], cols: usize, rows: usize, ligatures: bool) -> (RgbaImage, (u32, u32)) {
+        let mut config = Config::default();
+        config.font.ligatures = ligatures;
+        let mut renderer = CpuRenderer::new(&config, 1.0).unwrap();
+        let mut term = HeadlessTerminal::new(cols, rows, themes::DEFAULT_THEME);
+        term.feed(b"\x1b[?25l");
+        term.feed(bytes);
+        (renderer.render(&term.snapshot()), renderer.cell_size())
+    }
+
+    fn rgb(pixel: image::Rgba<u8>) -> (u8, u8, u8) {
+        (pixel[0], pixel[1], pixel[2])
+    }
+
+    #[test]
+    fn blank_screen_is_the_theme_background() {
+        let (image, (cell_w, cell_h)) = render(b"", 4, 2, true);
+        assert_eq!(image.dimensions(), (4 * cell_w, 2 * cell_h));
+        assert!(
+            image
+                .pixels()
+                .all(|p| rgb(*p) == themes::DEFAULT_THEME.background && p[3] == 255)
+        );
+    }
+
+    #[test]
+    fn cell_backgrounds_fill_their_cells() {
+        let (image, (cell_w, cell_h)) = render(b" \x1b[41m \x1b[0m ", 3, 1, true);
+        let red = themes::DEFAULT_THEME.ansi[1];
+        for y in 0..cell_h {
+            assert_eq!(
+                rgb(*image.get_pixel(cell_w - 1, y)),
+                themes::DEFAULT_THEME.background
+            );
+            assert_eq!(rgb(*image.get_pixel(cell_w, y)), red);
+            assert_eq!(rgb(*image.get_pixel(2 * cell_w - 1, y)), red);
+            assert_eq!(
+                rgb(*image.get_pixel(2 * cell_w, y)),
+                themes::DEFAULT_THEME.background
+            );
+        }
+    }
+
+    #[test]
+    fn box_drawing_spans_the_whole_cell() {
+        let (image, (cell_w, cell_h)) = render("───".as_bytes(), 3, 1, true);
+        let fg = themes::DEFAULT_THEME.foreground;
+        // The procedural line is centred vertically; find its row and check
+        // it runs unbroken across all three cells.
+        let row = (0..cell_h)
+            .find(|&y| rgb(*image.get_pixel(cell_w / 2, y)) == fg)
+            .unwrap();
+        assert!((0..3 * cell_w).all(|x| rgb(*image.get_pixel(x, row)) == fg));
+    }
+
+    #[test]
+    fn soft_font_glyphs_fill_the_cell() {
+        // A solid 5 × 6 glyph at position 1 of set `@`; position 2 is unloaded.
+        let (image, (cell_w, cell_h)) =
+            render(b"\x1bP0;1;0;5;0;0;6{@~~~~~\x1b\\\x1b( @!\"", 2, 1, true);
+        let fg = themes::DEFAULT_THEME.foreground;
+        let bg = themes::DEFAULT_THEME.background;
+        for y in 0..cell_h {
+            assert!((0..cell_w).all(|x| rgb(*image.get_pixel(x, y)) == fg));
+            assert!((cell_w..2 * cell_w).all(|x| rgb(*image.get_pixel(x, y)) == bg));
+        }
+    }
+
+    #[test]
+    fn emoji_keep_their_own_colors() {
+        let (image, _) = render("\u{1f7e5}".as_bytes(), 2, 1, true);
+        // A red square: strongly red pixels that are neither the theme
+        // background nor the grey of a tinted monochrome glyph.
+        assert!(image.pixels().any(|p| p[0] > 180 && p[1] < 80 && p[2] < 80));
+    }
+
+    #[test]
+    fn ligatures_change_the_rendering() {
+        let (with, _) = render(b"a -> b != c", 11, 1, true);
+        let (without, _) = render(b"a -> b != c", 11, 1, false);
+        assert_eq!(with.dimensions(), without.dimensions());
+        assert_ne!(with.as_raw(), without.as_raw());
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let (image, _) = render(b"\x1b[1;32mok\x1b[0m", 4, 2, true);
+        let png = encode_png(&image).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        let decoded = image::load_from_memory_with_format(&png, ImageFormat::Png)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(decoded, image);
+    }
+
+    #[test]
+    fn translucent_quads_are_blended_once() {
+        let mut canvas = Canvas::new(10, 10, [0.0, 0.0, 0.0, 1.0]);
+        let half_white = premultiply([1.0, 1.0, 1.0, 0.5]);
+        canvas.fill_rect([0.0, 0.0, 10.0, 10.0], [0.0; 4], &mut |_, _| half_white);
+        let image = canvas.into_image();
+        assert!(image.pixels().all(|p| rgb(*p) == (128, 128, 128)));
+    }
+
+    #[test]
+    fn textures_are_sampled_at_texel_centres() {
+        let pixels = [255, 0, 0, 255, 0, 0, 255, 255];
+        let texture = Texture {
+            pixels: &pixels,
+            width: 2,
+            height: 1,
+        };
+        let assert_close = |actual: [f32; 4], expected: [f32; 4]| {
+            assert!(
+                actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(a, e)| (a - e).abs() < f32::EPSILON),
+                "expected {expected:?}, got {actual:?}"
+            );
+        };
+        assert_close(texture.sample(0.25, 0.5), [1.0, 0.0, 0.0, 1.0]);
+        assert_close(texture.sample(0.75, 0.5), [0.0, 0.0, 1.0, 1.0]);
+        assert_close(texture.sample(0.5, 0.5), [0.5, 0.0, 0.5, 1.0]);
+    }
+}
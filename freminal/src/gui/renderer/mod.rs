@@ -5,6 +5,9 @@
 
 //! Terminal rendering pipeline split into focused sub-modules.
 //!
+//! - [`cpu`] — [`CpuRenderer`], a software backend that composites a
+//!   snapshot into an RGBA image through the same vertex builders, for
+//!   screenshots without a GL context (`freminal render --screenshot`).
 //! - [`gpu`] — [`TerminalRenderer`] struct, GL init/draw/destroy, shader compilation,
 //!   VAO/VBO setup, and GL upload helpers.
 //! - [`shaders`] — GLSL source string constants for the four shader passes
@@ -20,6 +23,7 @@
 //!   shader (issue #433). Companion to `toast_pass`; see `toast_text_pass`
 //!   module docs.
 
+pub mod cpu;
 pub mod errors;
 pub mod gpu;
 pub(super) mod shaders;
@@ -27,6 +31,7 @@ pub mod toast_pass;
 pub mod toast_text_pass;
 pub mod vertex;
 
+pub use cpu::CpuRenderer;
 pub use gpu::{TerminalRenderer, WindowPostRenderer};
 pub use toast_pass::{ToastQuad, ToastRenderer};
 pub use toast_text_pass::{ToastTextMetrics, ToastTextRenderer, ToastTextRun};
@@ -10,7 +10,12 @@
 //! GPU by the [`super::gpu`] module.
 
 use conv2::{ApproxFrom, ConvUtil, ValueFrom};
-use freminal_common::buffer_states::fonts::{BlinkState, FontDecorations, UnderlineStyle};
+use freminal_common::buffer_states::cursor::{ReverseVideo, StateColors};
+use freminal_common::buffer_states::fonts::{
+    BlinkState, FontDecorations, FontWeight, UnderlineStyle,
+};
+use freminal_common::buffer_states::soft_font::{SoftFont, is_soft_char};
+use freminal_common::colors::{SpecialColor, SpecialColors, TerminalColor};
 use freminal_common::cursor::CursorVisualStyle;
 use freminal_common::themes::ThemePalette;
 use freminal_terminal_emulator::LineWidth;
@@ -26,7 +31,7 @@ use super::super::{
         search_match_bg_f, selection_bg_f, selection_fg_f,
     },
     font_manager::FontManager,
-    shaping::{ShapedGlyph, ShapedLine},
+    shaping::{ShapedGlyph, ShapedLine, ShapedRun},
 };
 
 // ---------------------------------------------------------------------------
@@ -126,6 +131,39 @@ const fn effective_bg(
     }
 }
 
+/// The run's colors with the OSC 5 special colors applied.
+///
+/// Text in the default foreground takes the special color of the first of
+/// its bold, underline, blink and reverse attributes that has one.  The
+/// color replaces the foreground before any reverse-video swap, so
+/// reversed text gets it as its background.
+fn special_run_colors(run: &ShapedRun, special: &SpecialColors) -> StateColors {
+    let mut colors = run.colors;
+    if special.is_empty() || colors.color != TerminalColor::Default {
+        return colors;
+    }
+    let attributes = [
+        (SpecialColor::Bold, run.font_weight == FontWeight::Bold),
+        (
+            SpecialColor::Underline,
+            run.font_decorations.underline_style().is_active(),
+        ),
+        (SpecialColor::Blink, run.blink != BlinkState::None),
+        (
+            SpecialColor::Reverse,
+            colors.reverse_video == ReverseVideo::On,
+        ),
+    ];
+    if let Some((r, g, b)) = attributes
+        .iter()
+        .filter(|(_, active)| *active)
+        .find_map(|(color, _)| special.get(*color))
+    {
+        colors.color = TerminalColor::Custom(r, g, b);
+    }
+    colors
+}
+
 // ---------------------------------------------------------------------------
 //  Vertex stride constants (in f32 components)
 // ---------------------------------------------------------------------------
@@ -179,6 +217,10 @@ pub struct FgRenderOptions {
     /// pane. Composed with per-cell SGR-7 by XOR via [`effective_fg`] /
     /// [`effective_bg`] (Task 115.2).
     pub reverse_screen: bool,
+    /// The pane's DECDLD soft fonts, for cells holding soft characters.
+    pub soft_font: Option<Arc<SoftFont>>,
+    /// OSC 5 special colors, applied by [`special_run_colors`].
+    pub special_colors: SpecialColors,
 }
 
 impl FgRenderOptions {
@@ -192,6 +234,8 @@ impl FgRenderOptions {
             text_blink_slow_visible: true,
             text_blink_fast_visible: true,
             reverse_screen: false,
+            soft_font: None,
+            special_colors: SpecialColors::new(),
         }
     }
 }
@@ -323,6 +367,8 @@ pub struct BackgroundFrame<'a> {
     /// pane. Composed with per-cell SGR-7 by XOR via [`effective_fg`] /
     /// [`effective_bg`] (Task 115.2).
     pub reverse_screen: bool,
+    /// OSC 5 special colors, applied by [`special_run_colors`].
+    pub special_colors: SpecialColors,
 }
 
 /// Build the two-pass background data: instanced cell BGs + decoration quads.
@@ -383,6 +429,7 @@ pub fn build_background_instances(
     let theme = frame.theme;
     let cursor_color_override = frame.cursor_color_override;
     let reverse_screen = frame.reverse_screen;
+    let special_colors = &frame.special_colors;
     // Reuse existing heap allocations — clear but keep capacity.
     instances.clear();
     deco.clear();
@@ -403,7 +450,8 @@ pub fn build_background_instances(
             // (swapped-from-foreground) effective background and must be
             // drawn, while a cell whose effective background becomes
             // DefaultBackground must still be skipped.
-            let bg_color_raw = effective_bg(&run.colors, reverse_screen);
+            let colors = special_run_colors(run, special_colors);
+            let bg_color_raw = effective_bg(&colors, reverse_screen);
 
             // Skip default backgrounds (transparent — the terminal base color
             // is rendered as a panel clear, not explicit quads).
@@ -433,6 +481,7 @@ pub fn build_background_instances(
         // --- Underline and strikethrough decoration quads ---
         for run in &line.runs {
             let is_faint = run.font_decorations.contains(FontDecorations::Faint);
+            let colors = special_run_colors(run, special_colors);
             let underline_style = run.font_decorations.underline_style();
             let has_strike = run
                 .font_decorations
@@ -458,7 +507,7 @@ pub fn build_background_instances(
                     ul_color_raw,
                     freminal_common::colors::TerminalColor::DefaultUnderlineColor
                 ) {
-                    internal_color_to_gl(effective_fg(&run.colors, reverse_screen), is_faint, theme)
+                    internal_color_to_gl(effective_fg(&colors, reverse_screen), is_faint, theme)
                 } else {
                     internal_color_to_gl(ul_color_raw, is_faint, theme)
                 };
@@ -488,11 +537,8 @@ pub fn build_background_instances(
                 // Task 115.2: strikethrough uses the effective (DECSCNM ×
                 // SGR-7 composed) foreground so it stays visible under
                 // whole-screen reverse video.
-                let fg_color = internal_color_to_gl(
-                    effective_fg(&run.colors, reverse_screen),
-                    is_faint,
-                    theme,
-                );
+                let fg_color =
+                    internal_color_to_gl(effective_fg(&colors, reverse_screen), is_faint, theme);
                 // strikeout_offset from OS/2 is positive (above baseline in font
                 // coords).  In top-down pixel coords, subtracting it from the
                 // baseline places the line above the baseline (middle of cell).
@@ -742,7 +788,10 @@ pub fn build_foreground_instances(
             // (already resolved in `colors.color()`) with whole-screen
             // DECSCNM by XOR via `effective_fg`.
             let normal_fg = internal_color_to_gl(
-                effective_fg(&run.colors, opts.reverse_screen),
+                effective_fg(
+                    &special_run_colors(run, &opts.special_colors),
+                    opts.reverse_screen,
+                ),
                 is_faint,
                 theme,
             );
@@ -766,7 +815,17 @@ pub fn build_foreground_instances(
                         normal_fg
                     };
 
-                if run_visible {
+                if run_visible
+                    && !try_emit_cell_filling_glyph(
+                        instances,
+                        glyph,
+                        atlas,
+                        font_manager,
+                        opts.soft_font.as_deref(),
+                        fg_color,
+                        &row_params,
+                    )
+                {
                     emit_glyph_instance(
                         instances,
                         glyph,
@@ -1200,7 +1259,6 @@ fn emit_procedural_glyph(
     use conv2::{ApproxFrom, RoundToNearest};
 
     let cell_top = row_params.cell_y_range[0];
-    let cell_bottom = row_params.cell_y_range[1];
     let cell_w = font_manager.cell_width();
     let cell_h = font_manager.cell_height();
 
@@ -1216,10 +1274,50 @@ fn emit_procedural_glyph(
         Some(e) => e.clone(),
         None => return,
     };
+    push_cell_filling_instance(instances, glyph, &entry, cell_w, fg_color, row_params);
+}
+
+/// Emit a DECDLD soft-font glyph.
+///
+/// The bitmap is scaled to the cell and placed exactly like a procedural
+/// glyph.  A soft character whose set has no glyph at that position draws
+/// nothing, as on a VT terminal.
+fn emit_soft_font_glyph(
+    instances: &mut Vec<f32>,
+    glyph: &ShapedGlyph,
+    atlas: &mut GlyphAtlas,
+    font_manager: &FontManager,
+    soft_font: &SoftFont,
+    fg_color: [f32; 4],
+    row_params: &RowGlyphParams,
+) {
+    let cell_w = font_manager.cell_width();
+    let entry = match atlas.get_or_insert_soft_font(
+        glyph.source_char,
+        soft_font,
+        cell_w,
+        font_manager.cell_height(),
+    ) {
+        Some(e) => e.clone(),
+        None => return,
+    };
+    push_cell_filling_instance(instances, glyph, &entry, cell_w, fg_color, row_params);
+}
+
+/// Push one instance whose quad is the glyph's cell rectangle.
+fn push_cell_filling_instance(
+    instances: &mut Vec<f32>,
+    glyph: &ShapedGlyph,
+    entry: &AtlasEntry,
+    cell_w: u32,
+    fg_color: [f32; 4],
+    row_params: &RowGlyphParams,
+) {
     if entry.width == 0 || entry.height == 0 {
         return;
     }
     let [u0, v0, u1, v1] = entry.uv_rect;
+    let [cell_top, cell_bottom] = row_params.cell_y_range;
 
     // Exact cell rectangle, with DECDWL (x_scale) / DECDHL (y_scale,
     // y_origin_shift) applied so double-width/height rows still fill correctly.
@@ -1379,6 +1477,49 @@ fn emit_normalized_fallback_glyph(
     ]);
 }
 
+/// Emit `glyph` if it is drawn to fill its cell rather than from a font.
+///
+/// Returns `true` if the glyph was handled here (whether or not anything was
+/// drawn), `false` if the caller should rasterise it from the font.
+fn try_emit_cell_filling_glyph(
+    instances: &mut Vec<f32>,
+    glyph: &ShapedGlyph,
+    atlas: &mut GlyphAtlas,
+    font_manager: &FontManager,
+    soft_font: Option<&SoftFont>,
+    fg_color: [f32; 4],
+    row_params: &RowGlyphParams,
+) -> bool {
+    // Characters written through a DECDLD soft set are private-use code
+    // points standing for a loaded bitmap, never for a font glyph.
+    if is_soft_char(glyph.source_char) {
+        if let Some(soft_font) = soft_font {
+            emit_soft_font_glyph(
+                instances,
+                glyph,
+                atlas,
+                font_manager,
+                soft_font,
+                fg_color,
+                row_params,
+            );
+        }
+        return true;
+    }
+
+    // Procedural box-drawing / block-element glyphs (Task #410) are drawn to
+    // fill the cell rectangle EXACTLY, so they tile with their neighbours with
+    // no seam. They bypass the font-glyph baseline/bearing/clip math entirely
+    // (that math accumulates sub-pixel error that shows up as hairline gaps
+    // between rows). Only single-cell-wide glyphs qualify.
+    if crate::gui::box_drawing::is_procedural(glyph.source_char) && glyph.cell_width == 1 {
+        emit_procedural_glyph(instances, glyph, atlas, font_manager, fg_color, row_params);
+        return true;
+    }
+
+    false
+}
+
 /// Emit a single foreground glyph instance (13 floats).
 ///
 /// Looks up (or rasterises) the atlas entry for the glyph, then pushes one
@@ -1402,16 +1543,6 @@ fn emit_glyph_instance(
     let cell_top = row_params.cell_y_range[0];
     let cell_bottom = row_params.cell_y_range[1];
 
-    // Procedural box-drawing / block-element glyphs (Task #410) are drawn to
-    // fill the cell rectangle EXACTLY, so they tile with their neighbours with
-    // no seam. They bypass the font-glyph baseline/bearing/clip math entirely
-    // (that math accumulates sub-pixel error that shows up as hairline gaps
-    // between rows). Only single-cell-wide glyphs qualify.
-    if crate::gui::box_drawing::is_procedural(glyph.source_char) && glyph.cell_width == 1 {
-        emit_procedural_glyph(instances, glyph, atlas, font_manager, fg_color, row_params);
-        return;
-    }
-
     // Rasterize glyphs at the font's actual pixels-per-em — the SAME size the
     // cell metrics (ascent/descent/baseline/cell width) were computed at — not
     // the cell *height*. The cell height can be larger than the font ppem
@@ -1941,6 +2072,7 @@ mod tests {
                 theme: &themes::CATPPUCCIN_MOCHA,
                 cursor_color_override: None,
                 reverse_screen: false,
+                special_colors: SpecialColors::new(),
             },
             &mut instances,
             &mut deco,
@@ -1983,6 +2115,7 @@ mod tests {
                 theme: &themes::CATPPUCCIN_MOCHA,
                 cursor_color_override: None,
                 reverse_screen,
+                special_colors: SpecialColors::new(),
             },
             &mut instances,
             &mut deco,
@@ -2091,6 +2224,83 @@ mod tests {
         );
     }
 
+    // -----------------------------------------------------------------------
+    //  OSC 5 special colors
+    // -----------------------------------------------------------------------
+
+    fn special(color: SpecialColor, rgb: (u8, u8, u8)) -> SpecialColors {
+        let mut special = SpecialColors::new();
+        special.set(color, rgb);
+        special
+    }
+
+    #[test]
+    fn special_colors_replace_the_default_foreground_only() {
+        let mut line = make_line(1, 8.0, default_colors(), FontDecorationFlags::empty());
+        Arc::make_mut(&mut line).runs[0].font_weight = FontWeight::Bold;
+        let run = &line.runs[0];
+        let bold = special(SpecialColor::Bold, (1, 2, 3));
+
+        assert_eq!(
+            special_run_colors(run, &bold).color,
+            TerminalColor::Custom(1, 2, 3)
+        );
+        assert_eq!(
+            special_run_colors(run, &special(SpecialColor::Blink, (1, 2, 3))).color,
+            TerminalColor::Default,
+            "a special color for an attribute the run lacks is ignored"
+        );
+
+        let mut red = (*line).clone();
+        red.runs[0].colors = default_colors().with_color(TerminalColor::Red);
+        assert_eq!(
+            special_run_colors(&red.runs[0], &bold).color,
+            TerminalColor::Red,
+            "explicit foreground colors are kept"
+        );
+    }
+
+    #[test]
+    fn special_reverse_color_becomes_the_background() {
+        let colors = default_colors().with_reverse_video(ReverseVideo::On);
+        let line = make_line(1, 8.0, colors, FontDecorationFlags::empty());
+        let mut instances = Vec::new();
+        let mut deco = Vec::new();
+        let _ = build_background_instances(
+            &BackgroundFrame {
+                shaped_lines: &[line],
+                cell_width: 8,
+                cell_height: 16,
+                ascent: 14.0,
+                underline_offset: 13.0,
+                strikeout_offset: 8.0,
+                stroke_size: 1.0,
+                show_cursor: false,
+                cursor_blink_on: true,
+                cursor_pixel_pos: (0.0, 0.0),
+                cursor_width_scale: 1.0,
+                cursor_visual_style: &CursorVisualStyle::BlockCursorSteady,
+                selection: None,
+                selection_is_block: false,
+                match_highlights: &[],
+                command_block_hover_rows: None,
+                term_width_cols: 0,
+                theme: &themes::CATPPUCCIN_MOCHA,
+                cursor_color_override: None,
+                reverse_screen: false,
+                special_colors: special(SpecialColor::Reverse, (0x10, 0x20, 0x30)),
+            },
+            &mut instances,
+            &mut deco,
+        );
+        let expected = internal_color_to_gl(
+            TerminalColor::Custom(0x10, 0x20, 0x30),
+            false,
+            &themes::CATPPUCCIN_MOCHA,
+        );
+        assert_eq!(&instances[2..6], expected.as_slice());
+    }
+
     #[test]
     fn effective_fg_bg_xor_truth_table() {
         // `build_foreground_instances` requires a real GlyphAtlas/FontManager
@@ -2993,6 +3203,7 @@ mod tests {
                 theme: &themes::CATPPUCCIN_MOCHA,
                 cursor_color_override: None,
                 reverse_screen: false,
+                special_colors: SpecialColors::new(),
             },
             &mut instances,
             &mut deco,
@@ -3344,6 +3555,7 @@ mod tests {
                 theme: &themes::CATPPUCCIN_MOCHA,
                 cursor_color_override: None,
                 reverse_screen: false,
+                special_colors: SpecialColors::new(),
             },
             &mut instances,
             &mut deco,
@@ -3408,6 +3620,7 @@ mod tests {
                 theme: &themes::CATPPUCCIN_MOCHA,
                 cursor_color_override: None,
                 reverse_screen: false,
+                special_colors: SpecialColors::new(),
             },
             &mut instances,
             &mut deco,
@@ -10,6 +10,7 @@ use conv2::ConvUtil;
 use crossbeam_channel::{Receiver, Sender};
 use egui::{self, Pos2, Vec2, ViewportCommand};
 use freminal_common::base64::encode;
+use freminal_common::buffer_states::osc_iterm2::AttentionRequest;
 use freminal_common::buffer_states::window_manipulation::{Notification99Data, WindowManipulation};
 use freminal_common::config::BellMode;
 use freminal_common::gui_theme::GuiTheme;
@@ -575,6 +576,21 @@ pub(super) fn handle_window_manipulation(
                 }
             }
 
+            // iTerm2 `OSC 1337 ; RequestAttention=`.  `yes` keeps asking
+            // until the window is focused; `once` and `fireworks` ask
+            // briefly; `no` withdraws the request.
+            WindowManipulation::RequestAttention(request) => {
+                let attention = match request {
+                    AttentionRequest::Start => egui::UserAttentionType::Critical,
+                    AttentionRequest::Once | AttentionRequest::Fireworks => {
+                        egui::UserAttentionType::Informational
+                    }
+                    AttentionRequest::Stop => egui::UserAttentionType::Reset,
+                };
+                ui.ctx()
+                    .send_viewport_cmd(ViewportCommand::RequestUserAttention(attention));
+            }
+
             // OSC 9 / OSC 777 desktop notification (Task 76).  Collect into
             // the out-parameter; the caller in `app_impl::update()` (where
             // `self.config` and the toast stack are in scope) dispatches each
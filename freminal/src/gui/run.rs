@@ -26,7 +26,8 @@ pub fn run(
     config_path: Option<std::path::PathBuf>,
     repaint_handle: Arc<OnceLock<(RepaintProxy, WindowId)>>,
     window_post: Arc<Mutex<WindowPostRenderer>>,
-    recording_swap: freminal_terminal_emulator::recording::RecordingSwap,
+    recording_swap: &freminal_terminal_emulator::recording::RecordingSwap,
+    playback: Option<freminal_terminal_emulator::recording::ParsedRecording>,
 ) -> Result<()> {
     let icon_bytes = include_bytes!("../../../assets/icon.png");
     let image = image::load_from_memory(icon_bytes)
@@ -73,6 +74,7 @@ pub fn run(
         recording_swap,
     );
     app.icon = Some(icon);
+    app.playback = playback.map(super::playback::PlaybackViewer::new);
 
     freminal_windowing::run(window_config, app).map_err(|e| anyhow::anyhow!(e.to_string()))
 }
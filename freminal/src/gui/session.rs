@@ -104,9 +104,10 @@ impl FreminalGui {
     ///
     /// Skips saving when the user launched with an ad-hoc command
     /// (`freminal -- vim foo`): those panes run a one-shot program and are not
-    /// meaningfully restorable.  Failures are logged but never fatal.
+    /// meaningfully restorable.  A `--play` run is skipped too: its panes
+    /// are a recording, not shells.  Failures are logged but never fatal.
     pub(super) fn maybe_auto_save_session(&mut self) {
-        if !self.args.command.is_empty() {
+        if !self.args.command.is_empty() || self.playback.is_some() {
             return;
         }
 
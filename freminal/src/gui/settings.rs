@@ -1200,6 +1200,39 @@ impl SettingsModal {
             ui.visuals().weak_text_color(),
             "Log level changes take effect on next launch.",
         );
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(8.0);
+        ui.heading("Flight Recorder");
+        ui.add_space(4.0);
+        ui.checkbox(
+            &mut self.draft.recording.flight_recorder,
+            "Keep recent history in memory",
+        )
+        .clickable();
+        ui.add_enabled_ui(self.draft.recording.flight_recorder, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Minutes:");
+                ui.add(
+                    DragValue::new(&mut self.draft.recording.flight_recorder_minutes)
+                        .range(1..=120),
+                );
+                ui.label("Memory limit (MiB):");
+                ui.add(
+                    DragValue::new(&mut self.draft.recording.flight_recorder_max_mb)
+                        .range(1..=1024),
+                );
+            });
+        });
+        ui.add_space(4.0);
+        ui.colored_label(
+            ui.visuals().weak_text_color(),
+            "Each window keeps its own history within these limits. \
+             Session → Save Flight Recorder writes the window's retained \
+             events to a .frec file in the recordings directory. Changing a \
+             limit discards what has been kept so far.",
+        );
     }
 
     /// Render the read-only / warning banner shown at the top of the settings
@@ -1565,6 +1598,21 @@ impl SettingsModal {
             });
         }
 
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label("Template:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.draft.tab_title.template)
+                    .hint_text("{title}")
+                    .desired_width(240.0),
+            );
+        });
+        ui.colored_label(
+            ui.visuals().weak_text_color(),
+            "Replaces the shell title. Tokens: {title}, {cwd}, {user.<name>} \
+             (set with OSC 1337 SetUserVar).",
+        );
+
         // Live preview of how a renamed tab whose shell set a cwd-style
         // title would render under the current policy.
         ui.add_space(8.0);
@@ -2123,6 +2171,37 @@ impl SettingsModal {
              sudo, ssh, passwd).",
         );
 
+        ui.add_space(12.0);
+
+        ui.checkbox(
+            &mut self.draft.security.allow_remote_control,
+            "Allow Remote Control",
+        )
+        .clickable();
+        ui.add_space(4.0);
+        ui.colored_label(
+            ui.visuals().weak_text_color(),
+            "Listen on a per-user socket so `freminal msg` can send text to, \
+             read, split, and focus panes.\n\
+             Any process running as your user gains that access. \
+             Takes effect at the next launch.",
+        );
+
+        ui.add_space(12.0);
+
+        ui.checkbox(
+            &mut self.draft.security.allow_checksum_reports,
+            "Allow Checksum Reports (DECRQCRA)",
+        )
+        .clickable();
+        ui.add_space(4.0);
+        ui.colored_label(
+            ui.visuals().weak_text_color(),
+            "Answer rectangle checksum requests, which test suites such as \
+             esctest use to check the screen.\n\
+             The checksum lets a program read back what is on the screen.",
+        );
+
         ui.add_space(16.0);
         ui.separator();
         ui.add_space(8.0);
@@ -2132,6 +2211,34 @@ impl SettingsModal {
         ui.separator();
         ui.add_space(8.0);
         self.show_close_guard_section(ui);
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(8.0);
+        self.show_recording_redaction_section(ui);
+    }
+
+    /// The Recording Redaction subsection of the Security tab.
+    fn show_recording_redaction_section(&mut self, ui: &mut Ui) {
+        ui.heading("Recording Redaction");
+        ui.add_space(4.0);
+        ui.colored_label(
+            ui.visuals().weak_text_color(),
+            "Applies to session recordings and the flight recorder. Each \
+             redaction leaves a marker in the .frec file.",
+        );
+        ui.add_space(8.0);
+
+        ui.checkbox(
+            &mut self.draft.recording.redact_echo_off,
+            "Leave out input typed at password prompts (echo off)",
+        )
+        .clickable();
+
+        ui.add_space(8.0);
+        ui.label("Secret patterns to mask (Rust regex):");
+        ui.add_space(2.0);
+        show_regex_list_editor(ui, &mut self.draft.recording.redact_patterns);
     }
 
     /// The Close Guard subsection of the Security tab (Task 98.9).
@@ -2203,43 +2310,7 @@ impl SettingsModal {
                 ui.label("Dangerous patterns (Rust regex):");
                 ui.add_space(2.0);
 
-                // Index of a row to delete, applied after the loop so we do
-                // not mutate the Vec while iterating it.
-                let mut remove_index: Option<usize> = None;
-                for (idx, pattern) in self.draft.paste_guard.pattern_list.iter_mut().enumerate() {
-                    ui.horizontal(|ui| {
-                        if ui
-                            .button(ChromeIcon::Minus.rich_text())
-                            .on_hover_text("Remove")
-                            .clicked()
-                        {
-                            remove_index = Some(idx);
-                        }
-                        let valid = regex::Regex::new(pattern).is_ok();
-                        let error_color = ui.visuals().error_fg_color;
-                        let edit = egui::TextEdit::singleline(pattern)
-                            .desired_width(f32::INFINITY)
-                            .font(egui::TextStyle::Monospace)
-                            .text_color_opt((!valid).then_some(error_color));
-                        ui.add(edit).on_hover_text(if valid {
-                            "Valid regex"
-                        } else {
-                            "Invalid regex \u{2014} this pattern is ignored at match time"
-                        });
-                    });
-                }
-                if let Some(idx) = remove_index {
-                    self.draft.paste_guard.pattern_list.remove(idx);
-                }
-
-                ui.add_space(2.0);
-                if ui
-                    .button(ChromeIcon::Plus.rich_text().strong())
-                    .on_hover_text("Add pattern")
-                    .clicked()
-                {
-                    self.draft.paste_guard.pattern_list.push(String::new());
-                }
+                show_regex_list_editor(ui, &mut self.draft.paste_guard.pattern_list);
             });
         });
 
@@ -3008,6 +3079,48 @@ fn startup_layout_is_missing(
 }
 
 /// Paint a small colored rectangle as an inline swatch.
+/// Editable list of regex patterns, one row per entry with a remove button
+/// and an add button below. Invalid patterns are shown in the error color.
+fn show_regex_list_editor(ui: &mut Ui, patterns: &mut Vec<String>) {
+    // Index of a row to delete, applied after the loop so we do not mutate
+    // the Vec while iterating it.
+    let mut remove_index: Option<usize> = None;
+    for (idx, pattern) in patterns.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            if ui
+                .button(ChromeIcon::Minus.rich_text())
+                .on_hover_text("Remove")
+                .clicked()
+            {
+                remove_index = Some(idx);
+            }
+            let valid = regex::Regex::new(pattern).is_ok();
+            let error_color = ui.visuals().error_fg_color;
+            let edit = egui::TextEdit::singleline(pattern)
+                .desired_width(f32::INFINITY)
+                .font(egui::TextStyle::Monospace)
+                .text_color_opt((!valid).then_some(error_color));
+            ui.add(edit).on_hover_text(if valid {
+                "Valid regex"
+            } else {
+                "Invalid regex \u{2014} this pattern is ignored at match time"
+            });
+        });
+    }
+    if let Some(idx) = remove_index {
+        patterns.remove(idx);
+    }
+
+    ui.add_space(2.0);
+    if ui
+        .button(ChromeIcon::Plus.rich_text().strong())
+        .on_hover_text("Add pattern")
+        .clicked()
+    {
+        patterns.push(String::new());
+    }
+}
+
 fn color_swatch(ui: &mut Ui, (r, g, b): (u8, u8, u8), size: egui::Vec2) {
     let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
     ui.painter()
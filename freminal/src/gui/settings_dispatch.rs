@@ -122,6 +122,32 @@ impl FreminalGui {
             }
         }
 
+        // Broadcast the DECRQCRA opt-in to all panes when changed.
+        if new_cfg.security.allow_checksum_reports != self.config.security.allow_checksum_reports {
+            let allow = new_cfg.security.allow_checksum_reports;
+            for win in self.windows.values() {
+                for tab in win.tabs.iter() {
+                    match tab.pane_tree.iter_panes() {
+                        Ok(panes) => {
+                            for pane in panes {
+                                send_or_log!(
+                                    pane.input_tx,
+                                    InputEvent::AllowChecksumReports(allow),
+                                    "Failed to send AllowChecksumReports to PTY thread"
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!(
+                                "iter_panes() failed on tab during checksum report \
+                                 apply: {e}; skipping this tab"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
         // Broadcast cursor shape/blink changes to all panes (issue #406).
         // Like the initial pane-spawn seed, this only supplies the value in
         // effect until a running program's own DECSCUSR / `XTCBlink`
@@ -165,6 +191,11 @@ impl FreminalGui {
         // an un-applied preview to the persisted value.
         self.gui_theme = self.config.chrome.profile.defaults();
 
+        // Start, stop, or resize the flight recorder to match `[recording]`,
+        // and pick up any change to the redaction rules.
+        self.sync_flight_recorder();
+        self.sync_redaction();
+
         // Rebuild the paste-guard pattern cache from the new config and report
         // any patterns that fail to compile (skipped at match time).
         let invalid = self.paste_guard.rebuild(&self.config.paste_guard);
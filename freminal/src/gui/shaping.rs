@@ -637,6 +637,28 @@ pub fn shape_placeholder_line(
     }
 }
 
+/// Shape the DEC status line (DECSSDT) drawn below the grid.
+///
+/// The line is flattened like a snapshot row but lives outside
+/// `visible_chars`, so it is shaped on its own, without the line cache.
+#[must_use]
+pub fn shape_status_line(
+    chars: &[TChar],
+    tags: &[FormatTag],
+    term_width: usize,
+    font_manager: &mut FontManager,
+    cell_width: f32,
+    ligatures: bool,
+) -> ShapedLine {
+    let line_chars = split_into_lines(chars).first().copied().unwrap_or_default();
+    let runs = segment_line(line_chars, tags, 0, term_width, font_manager);
+    let features = shaping_features(ligatures);
+    ShapedLine {
+        runs: shape_runs(&runs, font_manager, cell_width, ligatures, &features),
+        line_width: LineWidth::Normal,
+    }
+}
+
 /// Shape a single `TextRun` via `rustybuzz`.
 fn shape_single_run(
     run: &TextRun,
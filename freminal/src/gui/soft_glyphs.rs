@@ -0,0 +1,70 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! Rasterising DECDLD soft-font glyphs for the glyph atlas.
+//!
+//! A soft glyph is a small on/off matrix sized for the VT screen it was
+//! designed for (e.g. 10 × 16 for 80 × 24).  Like the procedural glyphs in
+//! [`crate::gui::box_drawing`], it is drawn to fill the exact cell pixel
+//! rectangle: each cell pixel takes the matrix pixel under it, so the glyph
+//! scales with the font while keeping its hard pixel edges.
+
+use freminal_common::buffer_states::soft_font::SoftGlyph;
+
+/// Bytes per pixel in the generated RGBA bitmap.
+const BPP: usize = 4;
+
+/// Generate the RGBA (white + alpha) bitmap for `glyph` scaled to `w × h`.
+///
+/// Returns a buffer of length `w * h * 4`; pixels that are off in the
+/// matrix are fully transparent.
+#[must_use]
+pub fn generate_alpha(glyph: &SoftGlyph, w: usize, h: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; w * h * BPP];
+    for y in 0..h {
+        let src_y = y * glyph.height() / h;
+        for x in 0..w {
+            if glyph.is_set(x * glyph.width() / w, src_y) {
+                let idx = (y * w + x) * BPP;
+                buf[idx..idx + BPP].fill(255);
+            }
+        }
+    }
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use freminal_common::buffer_states::soft_font::{SoftFont, SoftFontLoad, soft_char};
+
+    fn alpha_at(buf: &[u8], w: usize, x: usize, y: usize) -> u8 {
+        buf[(y * w + x) * BPP + 3]
+    }
+
+    #[test]
+    fn matrix_is_scaled_to_the_cell() {
+        // A 5 × 6 glyph whose left column is on.
+        let mut font = SoftFont::default();
+        font.load(SoftFontLoad::parse(b"0;1;0;5;0;0;6{@~").unwrap());
+        let glyph = font.glyph(soft_char(b'@', b'!').unwrap()).unwrap();
+
+        let buf = generate_alpha(glyph, 10, 12);
+        assert_eq!(buf.len(), 10 * 12 * BPP);
+        for y in 0..12 {
+            assert_eq!(alpha_at(&buf, 10, 0, y), 255);
+            assert_eq!(alpha_at(&buf, 10, 1, y), 255);
+            assert_eq!(alpha_at(&buf, 10, 2, y), 0);
+        }
+    }
+
+    #[test]
+    fn empty_cell_is_empty() {
+        let glyph = SoftGlyph::blank(10, 16);
+        assert!(generate_alpha(&glyph, 0, 0).is_empty());
+        assert!(generate_alpha(&glyph, 8, 8).iter().all(|&b| b == 0));
+    }
+}
@@ -22,6 +22,16 @@ impl FreminalGui {
     /// Uses the stored `Args` and `Config` to configure the new terminal.
     /// Logs an error and does nothing if the PTY fails to start.
     pub(super) fn spawn_new_tab(&self, win: &mut PerWindowState) {
+        // Inside a tmux window the new tab is a new tmux window; it appears
+        // once tmux reports it.
+        if win.tmux_new_window() {
+            return;
+        }
+        // A playback window only shows the recording's tabs.
+        if self.playback.is_some() {
+            return;
+        }
+
         let theme =
             freminal_common::themes::by_slug(self.config.theme.active_slug(win.os_dark_mode))
                 .unwrap_or(&freminal_common::themes::CATPPUCCIN_MOCHA);
@@ -69,6 +79,7 @@ impl FreminalGui {
             pty::PtyTabInitialState {
                 theme,
                 auto_detect_urls: self.config.ui.auto_detect_urls,
+                allow_checksum_reports: self.config.security.allow_checksum_reports,
                 cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
                     &self.config.cursor.shape,
                     self.config.cursor.blink,
@@ -80,7 +91,7 @@ impl FreminalGui {
                 cwd: cwd_path,
                 shell_override: None,
                 extra_env: None,
-                recording_swap: self.recording_swap.clone(),
+                recording_swap: self.recording_swap_for_handle(&win.repaint_handle),
                 recording_pane_id: pane_id.raw().try_into().unwrap_or(u32::MAX),
                 set_term_program: self.config.shell_integration.set_term_program,
             },
@@ -174,6 +185,16 @@ impl FreminalGui {
         win: &mut PerWindowState,
         direction: panes::SplitDirection,
     ) {
+        // A tmux pane is split by tmux; the new pane arrives with the
+        // resulting `%layout-change`.
+        if win.tmux_split_active_pane(direction) {
+            return;
+        }
+        // A playback window only shows the recording's panes.
+        if self.playback.is_some() {
+            return;
+        }
+
         let theme =
             freminal_common::themes::by_slug(self.config.theme.active_slug(win.os_dark_mode))
                 .unwrap_or(&freminal_common::themes::CATPPUCCIN_MOCHA);
@@ -202,6 +223,7 @@ impl FreminalGui {
             pty::PtyTabInitialState {
                 theme,
                 auto_detect_urls: self.config.ui.auto_detect_urls,
+                allow_checksum_reports: self.config.security.allow_checksum_reports,
                 cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
                     &self.config.cursor.shape,
                     self.config.cursor.blink,
@@ -213,7 +235,7 @@ impl FreminalGui {
                 cwd: cwd_path,
                 shell_override: None,
                 extra_env: None,
-                recording_swap: self.recording_swap.clone(),
+                recording_swap: self.recording_swap_for_handle(&win.repaint_handle),
                 recording_pane_id: new_pane_id.raw().try_into().unwrap_or(u32::MAX),
                 set_term_program: self.config.shell_integration.set_term_program,
             },
@@ -338,6 +360,7 @@ impl FreminalGui {
             pty::PtyTabInitialState {
                 theme,
                 auto_detect_urls: self.config.ui.auto_detect_urls,
+                allow_checksum_reports: self.config.security.allow_checksum_reports,
                 cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
                     &self.config.cursor.shape,
                     self.config.cursor.blink,
@@ -349,7 +372,7 @@ impl FreminalGui {
                 cwd,
                 shell_override,
                 extra_env,
-                recording_swap: self.recording_swap.clone(),
+                recording_swap: self.recording_swap_for_handle(repaint_handle),
                 recording_pane_id: pane_id.raw().try_into().unwrap_or(u32::MAX),
                 set_term_program: self.config.shell_integration.set_term_program,
             },
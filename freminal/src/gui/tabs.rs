@@ -14,8 +14,9 @@
 //! shared with the PTY thread.
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 
-use freminal_common::config::TabTitlePolicy;
+use freminal_common::config::{TabTitleConfig, TabTitlePolicy};
 
 use super::panes::{Pane, PaneId, PaneTree};
 
@@ -127,10 +128,11 @@ impl Tab {
         self.broadcast_input
     }
 
-    /// Return the name to display for this tab under the given title policy.
+    /// Return the name to display for this tab under the given title config.
     ///
     /// Combines the user-assigned `custom_name` with the shell-asserted OSC
-    /// title (the active pane's `title`) according to `policy`:
+    /// title (the active pane's `title`, or `config.template` expanded for
+    /// the active pane when one is set) according to `config.policy`:
     ///
     /// - [`TabTitlePolicy::Prefix`]: when both exist, `"{custom}{sep}{osc}"`;
     ///   otherwise whichever is present.
@@ -142,27 +144,28 @@ impl Tab {
     /// Returns an empty string if neither a custom name nor an OSC title is
     /// available (the UI layer substitutes a `"Shell"` placeholder).
     #[must_use]
-    pub fn display_name(&self, policy: TabTitlePolicy, separator: &str) -> Cow<'_, str> {
+    pub fn display_name(&self, config: &TabTitleConfig) -> Cow<'_, str> {
         // The OSC title lives on the active pane. An unresolved active pane
         // is a programming bug; treat it as an empty OSC title.
         let osc = self
             .active_pane()
-            .map(|p| p.title.as_str())
+            .map(|p| shell_title(p, &config.template))
             .filter(|t| !t.is_empty());
         let custom = self.custom_name.as_deref().filter(|c| !c.is_empty());
+        let separator = &config.separator;
 
         match (custom, osc) {
             // Both present: combining policies join them; the "wins" policies
             // pick a single side.
-            (Some(c), Some(o)) => match policy {
+            (Some(c), Some(o)) => match config.policy {
                 TabTitlePolicy::Prefix => Cow::Owned(format!("{c}{separator}{o}")),
                 TabTitlePolicy::Suffix => Cow::Owned(format!("{o}{separator}{c}")),
                 TabTitlePolicy::CustomWins => Cow::Borrowed(c),
-                TabTitlePolicy::OscWins => Cow::Borrowed(o),
+                TabTitlePolicy::OscWins => o,
             },
             // Only one side present: every policy degrades to that side.
             (Some(c), None) => Cow::Borrowed(c),
-            (None, Some(o)) => Cow::Borrowed(o),
+            (None, Some(o)) => o,
             (None, None) => Cow::Borrowed(""),
         }
     }
@@ -222,6 +225,56 @@ impl std::fmt::Debug for Tab {
     }
 }
 
+/// The shell's side of a tab title: the pane's OSC title, or `template`
+/// expanded for the pane when it is not empty.
+fn shell_title<'a>(pane: &'a Pane, template: &str) -> Cow<'a, str> {
+    if template.is_empty() {
+        return Cow::Borrowed(&pane.title);
+    }
+    let snap = pane.arc_swap.load();
+    Cow::Owned(expand_title_template(
+        template,
+        &pane.title,
+        snap.cwd.as_deref().unwrap_or(""),
+        &snap.user_vars,
+    ))
+}
+
+/// Expand the `{title}`, `{cwd}` and `{user.<name>}` tokens of a
+/// `[tab_title] template`.
+///
+/// Unset user variables expand to nothing; any other `{...}` is kept as
+/// written.  Expanded values are not scanned again, so a title containing
+/// a token stays as the shell sent it.
+fn expand_title_template(
+    template: &str,
+    title: &str,
+    cwd: &str,
+    user_vars: &BTreeMap<String, String>,
+) -> String {
+    let mut out = String::with_capacity(template.len() + title.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let Some(close) = rest.find('}') else {
+            break;
+        };
+        let token = &rest[1..close];
+        match token {
+            "title" => out.push_str(title),
+            "cwd" => out.push_str(cwd),
+            _ => match token.strip_prefix("user.") {
+                Some(name) => out.push_str(user_vars.get(name).map_or("", String::as_str)),
+                None => out.push_str(&rest[..=close]),
+            },
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Error type for `TabManager` operations.
 #[derive(Debug, thiserror::Error)]
 pub enum TabError {
@@ -519,6 +572,7 @@ mod tests {
             search_buffer_rx,
             pty_dead_rx,
             command_event_rx,
+            tmux_event_rx: crossbeam_channel::never(),
             recent_commands: std::collections::VecDeque::new(),
             history_seed: crate::gui::shell_history::new_seeded_history(),
             shell_program: None,
@@ -556,7 +610,11 @@ mod tests {
     fn name_under(custom: Option<&str>, osc: &str, policy: TabTitlePolicy) -> String {
         let mut tab = dummy_tab(TabId(0), osc);
         tab.custom_name = custom.map(str::to_owned);
-        tab.display_name(policy, ": ").into_owned()
+        let config = TabTitleConfig {
+            policy,
+            ..TabTitleConfig::default()
+        };
+        tab.display_name(&config).into_owned()
     }
 
     #[test]
@@ -581,7 +639,7 @@ mod tests {
     fn display_name_defaults_to_pane_title() {
         let tab = dummy_tab(TabId(0), "PaneTitle");
         assert!(tab.custom_name.is_none());
-        assert_eq!(tab.display_name(TabTitlePolicy::Prefix, ": "), "PaneTitle");
+        assert_eq!(tab.display_name(&TabTitleConfig::default()), "PaneTitle");
     }
 
     #[test]
@@ -697,6 +755,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_name_expands_the_template_for_the_active_pane() {
+        let mut tab = dummy_tab(TabId(0), "vim");
+        let pane = tab.active_pane_mut().unwrap();
+        pane.arc_swap.store(Arc::new(TerminalSnapshot {
+            cwd: Some("/srv".to_owned()),
+            user_vars: Arc::new(BTreeMap::from([("host".to_owned(), "db1".to_owned())])),
+            ..TerminalSnapshot::empty()
+        }));
+        tab.custom_name = Some("ops".to_owned());
+
+        let config = TabTitleConfig {
+            template: "{user.host}:{cwd} {title}{user.unset}".to_owned(),
+            ..TabTitleConfig::default()
+        };
+        assert_eq!(tab.display_name(&config), "ops: db1:/srv vim");
+    }
+
+    #[test]
+    fn title_template_keeps_unknown_tokens_and_shell_text() {
+        let vars = BTreeMap::new();
+        assert_eq!(
+            expand_title_template("{title} {other} {", "{cwd}", "/tmp", &vars),
+            "{cwd} {other} {"
+        );
+        assert_eq!(expand_title_template("{user.x}", "t", "", &vars), "");
+    }
+
     #[test]
     fn next_tab_id_increments() {
         let tab = dummy_tab(TabId(0), "Tab 1");
@@ -10,7 +10,9 @@
 
 use conv2::ConvUtil;
 use egui::Pos2;
-use freminal_common::buffer_states::{format_tag::FormatTag, tchar::TChar};
+use freminal_common::buffer_states::{
+    format_tag::FormatTag, locator::LocatorPosition, tchar::TChar,
+};
 use freminal_terminal_emulator::snapshot::TerminalSnapshot;
 
 /// Compute the buffer-absolute row index of the first visible row.
@@ -185,6 +187,30 @@ pub(super) fn encode_egui_mouse_pos_as_usize(
     (x, y)
 }
 
+/// Convert an egui pointer position to a DEC locator position: the grid cell
+/// plus the device-pixel offset from the terminal origin.
+pub(super) fn locator_position(
+    pos: Pos2,
+    character_size: (f32, f32),
+    origin: Pos2,
+    pixels_per_point: f32,
+) -> LocatorPosition {
+    let (col, row) = encode_egui_mouse_pos_as_usize(pos, character_size, origin);
+    let to_px = |rel: f32| {
+        (rel.max(0.0) * pixels_per_point)
+            .floor()
+            .approx_as::<usize>()
+            .unwrap_or(0)
+    };
+
+    LocatorPosition {
+        col,
+        row,
+        x_px: to_px(pos.x - origin.x),
+        y_px: to_px(pos.y - origin.y),
+    }
+}
+
 #[cfg(test)]
 mod visible_window_start_tests {
     use super::*;
@@ -576,3 +602,27 @@ mod url_at_cell_tests {
         assert_eq!(url_at_cell(0, 3, &chars, &tags, window_start, &[]), None);
     }
 }
+
+#[cfg(test)]
+mod locator_position_tests {
+    use super::*;
+
+    #[test]
+    fn cells_and_device_pixels_are_relative_to_the_origin() {
+        let pos = locator_position(
+            Pos2::new(35.0, 50.0),
+            (10.0, 20.0),
+            Pos2::new(10.0, 5.0),
+            2.0,
+        );
+        assert_eq!(
+            pos,
+            LocatorPosition {
+                col: 2,
+                row: 2,
+                x_px: 50,
+                y_px: 90,
+            }
+        );
+    }
+}
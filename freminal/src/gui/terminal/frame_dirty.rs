@@ -28,7 +28,7 @@ use freminal_terminal_emulator::snapshot::TerminalSnapshot;
 use crate::gui::view_state::{CellCoord, ImageAnimationTick, ViewState};
 
 use super::widget::{
-    FoldLayout, PaneRenderCache, RenderState, compute_command_block_hover_rows,
+    ColorOverrides, FoldLayout, PaneRenderCache, RenderState, compute_command_block_hover_rows,
     image_pixels_changed,
 };
 
@@ -280,11 +280,13 @@ pub(super) fn evaluate_frame_dirty_state(
     // and, worse, sticky — dropping it is what lets an idle screen fall
     // through to the cheap cursor-only / no-op path.
     //
-    // Also force a full rebuild when the theme palette changes, since
-    // foreground/background colors are baked into the vertex buffers.
+    // Also force a full rebuild when the theme palette, or the OSC 17 / 19
+    // highlight or OSC 5 special colors, change, since foreground/background
+    // colors are baked into the vertex buffers.
     let theme_changed = cache
         .previous_theme
-        .is_none_or(|prev| !std::ptr::eq(prev, snap.theme));
+        .is_none_or(|prev| !std::ptr::eq(prev, snap.theme))
+        || ColorOverrides::of(snap) != cache.previous_color_overrides;
     // Detect terminal grid resize (cols or rows changed).  The cell
     // background and foreground instance VBOs hold per-cell vertices
     // that encode column indices and pixel positions based on the
@@ -308,7 +310,16 @@ pub(super) fn evaluate_frame_dirty_state(
         || cache
             .last_rendered_line_widths
             .as_ref()
-            .is_none_or(|prev| !Arc::ptr_eq(prev, &snap.visible_line_widths));
+            .is_none_or(|prev| !Arc::ptr_eq(prev, &snap.visible_line_widths))
+        || match (&cache.last_rendered_status_line, &snap.status_line) {
+            (None, None) => false,
+            (Some(prev), Some(line)) => !Arc::ptr_eq(prev, line),
+            _ => true,
+        }
+        || cache
+            .last_rendered_soft_font
+            .as_ref()
+            .is_none_or(|prev| !Arc::ptr_eq(prev, &snap.soft_font));
 
     // Clear the selection when actual terminal text content changes so
     // stale highlights don't linger over shifted text.  We use
@@ -658,11 +669,13 @@ mod evaluate_frame_dirty_state_tests {
     ) -> PaneRenderCache {
         let mut cache = PaneRenderCache::new();
         cache.previous_theme = Some(snap.theme);
+        cache.previous_color_overrides = ColorOverrides::of(snap);
         cache.previous_term_width = snap.term_width;
         cache.previous_term_height = snap.term_height;
         cache.previous_fold_epoch = 0;
         cache.last_rendered_visible = Some(Arc::clone(&snap.visible_chars));
         cache.last_rendered_line_widths = Some(Arc::clone(&snap.visible_line_widths));
+        cache.last_rendered_soft_font = Some(Arc::clone(&snap.soft_font));
         cache.previous_selection = None;
         // These tests drive `ViewState::new()`, whose search state is
         // default-constructed, so a settled cache is one that already agrees
@@ -801,6 +814,22 @@ mod evaluate_frame_dirty_state_tests {
         assert!(outcome.observations.content_changed);
     }
 
+    #[test]
+    fn highlight_color_change_forces_full_rebuild() {
+        // OSC 17 changed the selection color: the selection quads carry it,
+        // so the cached vertex buffers are stale.
+        let mut snap = base_snapshot();
+        let cache = settled_cache(&snap, true, true);
+        snap.highlight_bg_override = Some((1, 2, 3));
+        let mut view_state = ViewState::new();
+        let render_state = render_state_with_deco_verts(true);
+
+        let outcome = call(&snap, &mut view_state, &cache, &render_state, true, true);
+
+        assert_eq!(outcome.rebuild, VertexRebuild::ReevaluateFullRebuild);
+        assert!(outcome.observations.content_changed);
+    }
+
     #[test]
     fn dims_change_beats_cursor_change() {
         // A terminal resize AND a cursor-blink change happen on the same
@@ -818,6 +847,22 @@ mod evaluate_frame_dirty_state_tests {
         assert!(outcome.observations.content_changed);
     }
 
+    #[test]
+    fn soft_font_reload_beats_cursor_change() {
+        // A DECDLD load replaces the snapshot's soft-font `Arc` while the
+        // grid text stays the same: the cells must still be re-rasterised.
+        let mut snap = base_snapshot();
+        let cache = settled_cache(&snap, true, true);
+        snap.soft_font = Arc::default();
+        let mut view_state = ViewState::new();
+        let render_state = render_state_with_deco_verts(true);
+
+        let outcome = call(&snap, &mut view_state, &cache, &render_state, false, true);
+
+        assert_eq!(outcome.rebuild, VertexRebuild::ReevaluateFullRebuild);
+        assert!(outcome.observations.content_changed);
+    }
+
     // ── content-changed selection auto-clear (issue #470) ────────────────
 
     /// Put a committed (not in-progress) selection on `view_state`.
@@ -17,6 +17,7 @@ use conv2::ConvUtil;
 use crossbeam_channel::Sender;
 use egui::{Event, InputState, Key, Modifiers, PointerButton, Rect};
 use freminal_common::buffer_states::command_block::{CommandBlock, CommandBlockId};
+use freminal_common::buffer_states::locator::{LocatorButton, LocatorInput};
 use freminal_common::buffer_states::modes::{
     application_escape_key::ApplicationEscapeKey, decarm::Decarm, decbkm::Decbkm, decckm::Decckm,
     keypad::KeypadMode, lnm::Lnm, mouse::MouseTrack,
@@ -43,7 +44,8 @@ use freminal_terminal_emulator::{
 use std::borrow::Cow;
 
 use super::coords::{
-    encode_egui_mouse_pos_as_usize, visible_window_start, visible_window_start_for,
+    encode_egui_mouse_pos_as_usize, locator_position, visible_window_start,
+    visible_window_start_for,
 };
 use super::widget::hit_test_placeholder;
 use crate::gui::folding::{compute_extra_rows, compute_fold_ranges};
@@ -1660,6 +1662,9 @@ pub(super) fn write_input_to_terminal(params: WriteInputParams<'_, '_>) -> Write
 
     let mouse_encoding = &snap.mouse_encoding;
 
+    // The DEC locator follows the same rule as mouse tracking above.
+    let locator_active = snap.locator_enabled && view_state.scroll_offset == 0;
+
     for event in &input.raw.events {
         // `trace`, not `info`: `Event::Text` / `Event::Paste` carry typed
         // text and clipboard contents, and this runs before active-pane
@@ -2263,6 +2268,13 @@ pub(super) fn write_input_to_terminal(params: WriteInputParams<'_, '_>) -> Write
             Event::PointerGone => {
                 view_state.mouse_position = None;
                 last_reported_mouse_pos = None;
+                if locator_active {
+                    send_or_log!(
+                        input_tx,
+                        InputEvent::Locator(LocatorInput::Left),
+                        "Failed to send locator event"
+                    );
+                }
                 continue;
             }
             Event::WindowFocused(focused) => {
@@ -2297,6 +2309,27 @@ pub(super) fn write_input_to_terminal(params: WriteInputParams<'_, '_>) -> Write
             Event::PointerMoved(pos) => {
                 view_state.mouse_position = Some(*pos);
 
+                // DEC locator reporting replaces xterm mouse tracking and
+                // selection while it is enabled.
+                if locator_active {
+                    let locator_input = if terminal_rect.contains(*pos) {
+                        LocatorInput::Moved(locator_position(
+                            *pos,
+                            (character_size_x, character_size_y),
+                            terminal_origin,
+                            input.pixels_per_point,
+                        ))
+                    } else {
+                        LocatorInput::Left
+                    };
+                    send_or_log!(
+                        input_tx,
+                        InputEvent::Locator(locator_input),
+                        "Failed to send locator event"
+                    );
+                    continue;
+                }
+
                 // Ignore pointer moves outside the terminal area (e.g. over
                 // the tab bar) so they do not pollute mouse-tracking state or
                 // start spurious text selections.
@@ -2445,6 +2478,31 @@ pub(super) fn write_input_to_terminal(params: WriteInputParams<'_, '_>) -> Write
                     continue;
                 }
 
+                if locator_active {
+                    let button = match button {
+                        PointerButton::Primary => LocatorButton::Left,
+                        PointerButton::Middle => LocatorButton::Middle,
+                        PointerButton::Secondary => LocatorButton::Right,
+                        PointerButton::Extra1 | PointerButton::Extra2 => LocatorButton::M4,
+                    };
+                    let position = locator_position(
+                        *pos,
+                        (character_size_x, character_size_y),
+                        terminal_origin,
+                        input.pixels_per_point,
+                    );
+                    send_or_log!(
+                        input_tx,
+                        InputEvent::Locator(LocatorInput::Button {
+                            button,
+                            pressed: *pressed,
+                            position,
+                        }),
+                        "Failed to send locator event"
+                    );
+                    continue;
+                }
+
                 state_changed = true;
 
                 let (x, y) = encode_egui_mouse_pos_as_usize(
@@ -2779,10 +2837,13 @@ pub(super) fn write_input_to_terminal(params: WriteInputParams<'_, '_>) -> Write
             if let Some(ctx) = recording_ctx {
                 // For paste events, emit ClipboardPaste instead of KeyboardInput.
                 if let Event::Paste(text) = event {
-                    ctx.handle.emit(EventPayload::ClipboardPaste {
-                        pane_id: ctx.pane_id,
-                        data: text.as_bytes().to_vec(),
-                    });
+                    ctx.handle.emit_input(
+                        EventPayload::ClipboardPaste {
+                            pane_id: ctx.pane_id,
+                            data: text.as_bytes().to_vec(),
+                        },
+                        ctx.echo_off,
+                    );
                 } else {
                     let (key_name, modifier_bits) = match event {
                         Event::Text(text) => (text.clone(), 0u8),
@@ -2794,13 +2855,16 @@ pub(super) fn write_input_to_terminal(params: WriteInputParams<'_, '_>) -> Write
                         Event::Cut => ("Ctrl+X".to_string(), 2u8),
                         _ => (String::new(), 0u8),
                     };
-                    ctx.handle.emit(EventPayload::KeyboardInput {
-                        window_id: ctx.window_id,
-                        pane_id: ctx.pane_id,
-                        key_name,
-                        modifiers: modifier_bits,
-                        encoded,
-                    });
+                    ctx.handle.emit_input(
+                        EventPayload::KeyboardInput {
+                            window_id: ctx.window_id,
+                            pane_id: ctx.pane_id,
+                            key_name,
+                            modifiers: modifier_bits,
+                            encoded,
+                        },
+                        ctx.echo_off,
+                    );
                 }
             }
         }
@@ -2851,6 +2915,7 @@ mod fold_target_tests {
             end_row: Some(end),
             exit_code: Some(0),
             cwd: None,
+            command_line: None,
             started_at: SystemTime::UNIX_EPOCH,
             executed_at: Some(SystemTime::UNIX_EPOCH),
             finished_at: Some(SystemTime::UNIX_EPOCH),
@@ -2868,6 +2933,7 @@ mod fold_target_tests {
             end_row: None,
             exit_code: None,
             cwd: None,
+            command_line: None,
             started_at: SystemTime::UNIX_EPOCH,
             executed_at: None,
             finished_at: None,
@@ -3063,6 +3129,7 @@ mod scroll_window_tests {
             end_row: Some(end),
             exit_code: Some(0),
             cwd: None,
+            command_line: None,
             started_at: SystemTime::UNIX_EPOCH,
             executed_at: Some(SystemTime::UNIX_EPOCH),
             finished_at: Some(SystemTime::UNIX_EPOCH),
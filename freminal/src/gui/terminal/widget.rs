@@ -17,14 +17,18 @@ use crate::gui::{
 use crossbeam_channel::{Receiver, Sender};
 use freminal_common::{
     buffer_states::{
-        command_block::CommandStatus, pointer_shape::PointerShape, tchar::TChar, url::Url,
+        command_block::CommandStatus, pointer_shape::PointerShape, soft_font::SoftFont,
+        tchar::TChar, url::Url,
     },
+    colors::SpecialColors,
     config::Config,
     send_or_log,
     themes::ThemePalette,
 };
 use freminal_terminal_emulator::{
-    InlineImage, LineWidth, io::InputEvent, snapshot::TerminalSnapshot,
+    InlineImage, LineWidth,
+    io::InputEvent,
+    snapshot::{StatusLineSnapshot, TerminalSnapshot},
 };
 
 use egui::{self, Color32, Context, CursorIcon, Key, Pos2, Rect, Ui};
@@ -32,6 +36,7 @@ use egui::{self, Color32, Context, CursorIcon, Key, Pos2, Rect, Ui};
 use super::{
     super::{
         atlas::GlyphAtlas,
+        colors::highlight_theme,
         font_manager::FontManager,
         renderer::{
             BackgroundFrame, CURSOR_QUAD_FLOATS, FgRenderOptions, ImageDrawEntry, MatchHighlight,
@@ -773,6 +778,26 @@ fn paint_bell_flash(
     bell_flash_repaint_delay(outcome)
 }
 
+/// Paint the `OSC 1337 ; SetBadgeFormat=` badge: large, faint text in the
+/// top-right corner of the pane, one cell in from the edges.  Multi-line
+/// badges keep their line breaks.
+fn paint_badge(ui: &Ui, terminal_rect: Rect, badge: &str, cell_height: f32) {
+    let color = ui.visuals().text_color().gamma_multiply(BADGE_OPACITY);
+    ui.painter().text(
+        terminal_rect.right_top() + egui::vec2(-cell_height, cell_height),
+        egui::Align2::RIGHT_TOP,
+        badge,
+        egui::FontId::proportional(cell_height * BADGE_SCALE),
+        color,
+    );
+}
+
+/// Badge text height as a multiple of the cell height.
+const BADGE_SCALE: f32 = 2.0;
+
+/// Opacity of the badge text over the terminal content.
+const BADGE_OPACITY: f32 = 0.25;
+
 /// Context menu action produced by the right-click popup.
 ///
 /// These actions are dispatched after `render_context_menu` returns because
@@ -1274,6 +1299,25 @@ pub fn new_render_state(window_post: Arc<Mutex<WindowPostRenderer>>) -> Arc<Mute
 // Bools are inherently boolean dirty-tracking flags (cursor blink on/off,
 // cursor shown/hidden, text blink visible, overlay open) — enums would add
 // noise without improving clarity.
+/// A snapshot's OSC 17 / 19 highlight overrides and OSC 5 special colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct ColorOverrides {
+    highlight_bg: Option<(u8, u8, u8)>,
+    highlight_fg: Option<(u8, u8, u8)>,
+    special: SpecialColors,
+}
+
+impl ColorOverrides {
+    /// The overrides carried by `snap`.
+    pub(super) const fn of(snap: &TerminalSnapshot) -> Self {
+        Self {
+            highlight_bg: snap.highlight_bg_override,
+            highlight_fg: snap.highlight_fg_override,
+            special: snap.special_colors,
+        }
+    }
+}
+
 #[allow(clippy::struct_excessive_bools)]
 pub struct PaneRenderCache {
     /// Mouse state from the most recently rendered frame.
@@ -1307,10 +1351,20 @@ pub struct PaneRenderCache {
     /// change (e.g. DECDWL/DECDHL toggle), we must force a full rebuild so
     /// glyph scaling is re-applied.
     pub(super) last_rendered_line_widths: Option<Arc<Vec<freminal_terminal_emulator::LineWidth>>>,
+    /// Status line from the last full vertex rebuild.  It is drawn in the
+    /// same vertex batch as the grid, so a change forces a full rebuild.
+    pub(super) last_rendered_status_line: Option<Arc<StatusLineSnapshot>>,
+    /// DECDLD soft fonts from the last full vertex rebuild.  Reloading a set
+    /// changes the bitmaps of cells whose text is unchanged.
+    pub(super) last_rendered_soft_font: Option<Arc<SoftFont>>,
     /// Theme pointer from the last full vertex rebuild.  When this changes,
     /// we must force a full rebuild so foreground/background vertex colors
     /// are re-resolved against the new palette.
     pub(super) previous_theme: Option<&'static ThemePalette>,
+    /// OSC 17 / 19 highlight overrides and OSC 5 special colors from the
+    /// last full vertex rebuild.  Like the theme, they are baked into the
+    /// vertex colors.
+    pub(super) previous_color_overrides: ColorOverrides,
     /// The normalised selection from the last full vertex rebuild, used to
     /// detect selection changes that require a full rebuild.
     pub(super) previous_selection: Option<(CellCoord, CellCoord)>,
@@ -1481,7 +1535,10 @@ impl PaneRenderCache {
             previous_cursor_color_override: None,
             last_rendered_visible: None,
             last_rendered_line_widths: None,
+            last_rendered_status_line: None,
+            last_rendered_soft_font: None,
             previous_theme: None,
+            previous_color_overrides: ColorOverrides::default(),
             previous_selection: None,
             previous_text_blink_slow_visible: true,
             previous_text_blink_fast_visible: true,
@@ -1570,6 +1627,8 @@ impl PaneRenderCache {
     pub fn invalidate_content(&mut self) {
         self.last_rendered_visible = None;
         self.last_rendered_line_widths = None;
+        self.last_rendered_status_line = None;
+        self.last_rendered_soft_font = None;
         self.shaping_cache.clear();
         self.last_rendered_image_pixel_ptrs.clear();
     }
@@ -2628,7 +2687,7 @@ impl FreminalTerminalWidget {
                         // `cache.placeholder_hit_rects` so the input handler can
                         // turn primary clicks on those rows into `view_state.unfold()`.
                         cache.placeholder_hit_rects.clear();
-                        let rendered_shaped_lines: Vec<Arc<ShapedLine>> =
+                        let mut rendered_shaped_lines: Vec<Arc<ShapedLine>> =
                             if row_map.ranges().is_empty()
                                 && render_skip == 0
                                 && snap.window_extra_rows == 0
@@ -2695,6 +2754,20 @@ impl FreminalTerminalWidget {
                                 out
                             };
 
+                        // The DEC status line takes the row below the grid.
+                        if let Some(status) = &snap.status_line {
+                            rendered_shaped_lines.push(Arc::new(
+                                crate::gui::shaping::shape_status_line(
+                                    &status.chars,
+                                    &status.tags,
+                                    snap.term_width,
+                                    &mut self.font_manager,
+                                    cell_w_f,
+                                    self.ligatures,
+                                ),
+                            ));
+                        }
+
                         // Build search match highlights from the current search state.
                         // Only matches within the flattened window are included, with
                         // rows converted from buffer-absolute to snapshot-relative.
@@ -2755,6 +2828,7 @@ impl FreminalTerminalWidget {
                         // vertex-rebuild decision) so a hover-only change can force a
                         // rebuild; reuse it here.
                         let command_block_hover_rows = command_block_hover_rows_early;
+                        let theme = highlight_theme(snap);
 
                         // Acquire the lock early so all vertex builders can write
                         // directly into the persistent `RenderState` Vecs, reusing
@@ -2786,7 +2860,7 @@ impl FreminalTerminalWidget {
                                 match_highlights: &search_highlights,
                                 command_block_hover_rows,
                                 term_width_cols: snap.term_width,
-                                theme: snap.theme,
+                                theme: &theme,
                                 cursor_color_override: snap.cursor_color_override,
                                 // Task 115.2: DECSCNM (whole-screen reverse video)
                                 // composes with per-cell SGR-7 by XOR inside the
@@ -2794,6 +2868,7 @@ impl FreminalTerminalWidget {
                                 // `is_normal_display` is `true` for normal display,
                                 // so DECSCNM-active is its negation.
                                 reverse_screen: !snap.is_normal_display,
+                                special_colors: snap.special_colors,
                             },
                             &mut rs_ref.bg_instances,
                             &mut rs_ref.deco_verts,
@@ -2824,6 +2899,8 @@ impl FreminalTerminalWidget {
                             // Task 115.2: see the matching `BackgroundFrame`
                             // construction above for the XOR-compose rationale.
                             reverse_screen: !snap.is_normal_display,
+                            soft_font: Some(Arc::clone(&snap.soft_font)),
+                            special_colors: snap.special_colors,
                         };
                         build_foreground_instances(
                             &rendered_shaped_lines,
@@ -2832,7 +2909,7 @@ impl FreminalTerminalWidget {
                             cell_h,
                             self.font_manager.ascent(),
                             &fg_opts,
-                            snap.theme,
+                            &theme,
                             &mut rs_ref.fg_instances,
                         );
                         build_image_verts(
@@ -2872,7 +2949,12 @@ impl FreminalTerminalWidget {
                         cache.last_rendered_visible = Some(Arc::clone(&snap.visible_chars));
                         cache.last_rendered_line_widths =
                             Some(Arc::clone(&snap.visible_line_widths));
+                        cache
+                            .last_rendered_status_line
+                            .clone_from(&snap.status_line);
+                        cache.last_rendered_soft_font = Some(Arc::clone(&snap.soft_font));
                         cache.previous_theme = Some(snap.theme);
+                        cache.previous_color_overrides = ColorOverrides::of(snap);
                         cache.previous_selection = current_selection;
                         cache.previous_text_blink_slow_visible = view_state.text_blink_slow_visible;
                         cache.previous_text_blink_fast_visible = view_state.text_blink_fast_visible;
@@ -3166,6 +3248,11 @@ impl FreminalTerminalWidget {
         cache.scrollbar_was_rendered_last_frame = scrollbar_outcome.rendered;
         cache.scrollbar_was_hovered_last_frame = scrollbar_outcome.hovered;
 
+        // ── iTerm2 badge ─────────────────────────────────────────────
+        if let Some(badge) = &snap.badge {
+            paint_badge(ui, terminal_rect, badge, logical_cell_h);
+        }
+
         // ── Visual bell flash overlay ────────────────────────────────
         // Fold the returned delay into `cache` (subtask 121.12) — see
         // `paint_bell_flash`'s doc comment for why it does not request the
@@ -4618,6 +4705,7 @@ mod gutter_hover_trigger_tests {
             end_row: Some(3),
             exit_code: Some(0),
             cwd: None,
+            command_line: None,
             started_at: SystemTime::UNIX_EPOCH,
             executed_at: Some(SystemTime::UNIX_EPOCH),
             finished_at: Some(SystemTime::UNIX_EPOCH),
@@ -0,0 +1,838 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! tmux control-mode (`tmux -CC`) integration.
+//!
+//! When a pane runs `tmux -CC`, its PTY consumer thread turns the control
+//! protocol into [`TmuxEvent`]s (see `freminal_terminal_emulator::tmux_control`)
+//! and forwards them on the pane's `tmux_event_rx`.  This module applies
+//! them once per frame: every tmux window becomes a native tab and every
+//! tmux pane a native pane, fed from `%output` and typing back into tmux
+//! with `send-keys`.  The pane running tmux — the *gateway* — stays where it
+//! is.
+//!
+//! tmux remains the source of truth for the layout.  Splitting, closing or
+//! opening a tab from a mirrored pane sends the matching tmux command, and
+//! the native tree only changes when tmux reports the result through
+//! `%layout-change`, `%window-add` or `%window-close`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use conv2::ConvUtil as _;
+use crossbeam_channel::Sender;
+use freminal_common::pty_write::FreminalTerminalSize;
+use freminal_common::send_or_log;
+use freminal_common::terminal_size::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use freminal_terminal_emulator::io::{InputEvent, PtyRead};
+use freminal_terminal_emulator::tmux_control::{
+    TmuxCommandKind, TmuxCommandSender, TmuxEvent, TmuxLayout, TmuxSplitAxis, kill_pane_command,
+    new_window_command, refresh_client_size_command, split_window_command,
+};
+use tracing::{error, info, trace, warn};
+
+use super::panes::{self, PaneId, SplitDirection};
+use super::playback::PaneEnv;
+use super::tabs::{Tab, TabId, TabManager};
+use super::window::PerWindowState;
+use super::{FreminalGui, pty};
+
+/// Cap on `%output` buffered for a tmux pane that has no native pane yet.
+///
+/// The `capture-pane` sent when the pane is first listed repaints it
+/// anyway, so anything beyond this is dropped rather than held.
+const ORPHAN_OUTPUT_CAP: usize = 64 * 1024;
+
+/// Spawns the native pane mirroring a tmux pane at `(columns, rows)`,
+/// returning it together with the sender that feeds it `%output`.
+type SpawnPane<'a> =
+    dyn FnMut(&TmuxCommandSender, u32, (usize, usize)) -> (panes::Pane, Sender<PtyRead>) + 'a;
+
+/// A native pane mirroring one tmux pane.
+struct TmuxPane {
+    /// tmux window the pane belongs to.
+    window: u32,
+
+    /// The native pane showing it.
+    id: PaneId,
+
+    /// Feeds the pane's `%output` into its emulator.  Dropping it ends the
+    /// native pane through the normal dead-pane path.
+    output_tx: Sender<PtyRead>,
+}
+
+/// What is left for the GUI to do after a session applied an event.
+#[derive(Debug, PartialEq, Eq)]
+enum SessionOutcome {
+    /// Nothing; keep the session.
+    Continue,
+
+    /// A tmux command failed with this message.
+    CommandFailed(String),
+
+    /// tmux is leaving control mode, with its reason if it gave one.
+    Exiting(Option<String>),
+
+    /// Control mode has ended; drop the session.
+    Ended,
+}
+
+/// One `tmux -CC` client and the native tabs and panes mirroring it.
+pub(super) struct TmuxSession {
+    /// The pane running `tmux -CC`.
+    gateway: PaneId,
+
+    /// Command channel to the tmux client, shared with every mirrored
+    /// pane's input forwarder.
+    commands: TmuxCommandSender,
+
+    /// tmux window id → the native tab showing it.
+    windows: HashMap<u32, TabId>,
+
+    /// tmux pane id → the native pane showing it.
+    panes: HashMap<u32, TmuxPane>,
+
+    /// `%output` received for panes that do not have a native pane yet.
+    orphan_output: HashMap<u32, Vec<u8>>,
+}
+
+impl TmuxSession {
+    /// Start mirroring the client running in `gateway`, sizing tmux's
+    /// windows to the gateway pane.
+    fn start(gateway: PaneId, commands: TmuxCommandSender, tabs: &TabManager) -> Self {
+        let session = Self {
+            gateway,
+            commands,
+            windows: HashMap::new(),
+            panes: HashMap::new(),
+            orphan_output: HashMap::new(),
+        };
+        let (cols, rows) = tabs
+            .iter()
+            .find_map(|t| t.pane_tree.find(gateway))
+            .map_or((0, 0), |p| p.view_state.last_sent_size);
+        if cols > 0 && rows > 0 {
+            session.send(&refresh_client_size_command(cols, rows));
+        }
+        session
+    }
+
+    /// The tmux pane shown by the native pane `id`, if any.
+    fn tmux_pane_for(&self, id: PaneId) -> Option<u32> {
+        self.panes
+            .iter()
+            .find_map(|(tmux_id, pane)| (pane.id == id).then_some(*tmux_id))
+    }
+
+    /// Send a command whose reply nobody waits for.
+    fn send(&self, command: &str) {
+        if let Err(e) = self.commands.send(TmuxCommandKind::Ignore, command) {
+            error!("Failed to send tmux command '{command}': {e}");
+        }
+    }
+
+    /// Ask tmux to split `pane` in `direction`.
+    fn split(&self, pane: u32, direction: SplitDirection) {
+        let axis = match direction {
+            SplitDirection::Horizontal => TmuxSplitAxis::SideBySide,
+            SplitDirection::Vertical => TmuxSplitAxis::Stacked,
+        };
+        self.send(&split_window_command(pane, axis));
+    }
+
+    /// Feed `%output` to the pane's emulator, or hold it until the pane
+    /// exists.
+    fn feed(&mut self, pane: u32, data: Vec<u8>) {
+        let Some(target) = self.panes.get(&pane) else {
+            let pending = self.orphan_output.entry(pane).or_default();
+            if pending.len() + data.len() <= ORPHAN_OUTPUT_CAP {
+                pending.extend(data);
+            }
+            return;
+        };
+        let read_amount = data.len();
+        if target
+            .output_tx
+            .send(PtyRead {
+                buf: data,
+                read_amount,
+            })
+            .is_err()
+        {
+            // The native pane was closed locally (e.g. its tab was closed);
+            // stop mirroring it.
+            trace!("tmux pane %{pane} has no live native pane; dropping it");
+            self.panes.remove(&pane);
+        }
+    }
+
+    /// Apply one event to the native tabs in `tabs`, spawning mirrored
+    /// panes with `spawn`.
+    fn apply(
+        &mut self,
+        tabs: &mut TabManager,
+        event: TmuxEvent,
+        spawn: &mut SpawnPane<'_>,
+    ) -> SessionOutcome {
+        match event {
+            // Handled by `FreminalGui::apply_tmux_event` before a session
+            // exists.
+            TmuxEvent::Started { .. } => {}
+            TmuxEvent::Window(window) => {
+                if let Some(&tab_id) = self.windows.get(&window.id) {
+                    if let Some(tab) = tab_by_id_mut(tabs, tab_id) {
+                        tab.custom_name = Some(window.name);
+                    }
+                } else {
+                    self.open_window(tabs, window.id, &window.layout, window.name, spawn);
+                }
+                self.sync_layout(tabs, window.id, &window.layout, spawn);
+            }
+            TmuxEvent::LayoutChange { window, layout } => {
+                self.sync_layout(tabs, window, &layout, spawn);
+            }
+            TmuxEvent::WindowClose { window } => {
+                self.windows.remove(&window);
+                self.panes.retain(|_, pane| pane.window != window);
+            }
+            TmuxEvent::WindowRenamed { window, name } => {
+                if let Some(&tab_id) = self.windows.get(&window)
+                    && let Some(tab) = tab_by_id_mut(tabs, tab_id)
+                {
+                    tab.custom_name = Some(name);
+                }
+            }
+            TmuxEvent::ActivePaneChanged { window, pane } => {
+                let (Some(&tab_id), Some(native)) = (
+                    self.windows.get(&window),
+                    self.panes.get(&pane).map(|p| p.id),
+                ) else {
+                    return SessionOutcome::Continue;
+                };
+                if let Some(tab) = tab_by_id_mut(tabs, tab_id)
+                    && tab.active_pane != native
+                {
+                    if let Some(old) = tab.pane_tree.find(tab.active_pane) {
+                        send_or_log!(
+                            old.input_tx,
+                            InputEvent::FocusChange(false),
+                            "Failed to send FocusChange(false) to previous tmux pane"
+                        );
+                    }
+                    if let Some(new) = tab.pane_tree.find(native) {
+                        send_or_log!(
+                            new.input_tx,
+                            InputEvent::FocusChange(true),
+                            "Failed to send FocusChange(true) to tmux pane"
+                        );
+                    }
+                    tab.active_pane = native;
+                }
+            }
+            TmuxEvent::ActiveWindowChanged { window } => {
+                let Some(&tab_id) = self.windows.get(&window) else {
+                    return SessionOutcome::Continue;
+                };
+                let index = tabs.iter().position(|t| t.id == tab_id);
+                if let Some(index) = index
+                    && let Err(e) = tabs.switch_to(index)
+                {
+                    error!("Failed to switch to tmux window @{window}: {e}");
+                }
+            }
+            TmuxEvent::Output { pane, data } => self.feed(pane, data),
+            TmuxEvent::CommandError(message) => return SessionOutcome::CommandFailed(message),
+            TmuxEvent::Exit { reason } => return SessionOutcome::Exiting(reason),
+            TmuxEvent::Ended => return SessionOutcome::Ended,
+        }
+        SessionOutcome::Continue
+    }
+
+    /// Open a native tab for tmux window `window`, holding its first pane.
+    /// The remaining panes are added by [`Self::sync_layout`].
+    fn open_window(
+        &mut self,
+        tabs: &mut TabManager,
+        window: u32,
+        layout: &TmuxLayout,
+        name: String,
+        spawn: &mut SpawnPane<'_>,
+    ) {
+        let Some(first) = layout.first_pane() else {
+            warn!("tmux window @{window} has no panes");
+            return;
+        };
+        let pane = self.spawn_pane(window, first, layout, spawn);
+        let tab_id = tabs.next_tab_id();
+        let mut tab = Tab::new(tab_id, pane);
+        tab.custom_name = Some(name);
+        tabs.add_tab(tab);
+        self.windows.insert(window, tab_id);
+    }
+
+    /// Bring the native tab for `window` in line with `layout`.
+    ///
+    /// Panes tmux no longer shows are dropped (their feeder closes, so the
+    /// dead-pane path removes them) and new panes are split off their
+    /// neighbour in [`TmuxLayout::split_plan`] order with tmux's ratios.
+    /// Panes that already exist keep their place and size.
+    fn sync_layout(
+        &mut self,
+        tabs: &mut TabManager,
+        window: u32,
+        layout: &TmuxLayout,
+        spawn: &mut SpawnPane<'_>,
+    ) {
+        let Some(&tab_id) = self.windows.get(&window) else {
+            trace!("Layout for unknown tmux window @{window}");
+            return;
+        };
+
+        let wanted = layout.pane_ids();
+        self.panes
+            .retain(|id, pane| pane.window != window || wanted.contains(id));
+
+        for split in layout.split_plan() {
+            if self.panes.contains_key(&split.pane) {
+                continue;
+            }
+            let Some(target) = self.panes.get(&split.target).map(|p| p.id) else {
+                warn!(
+                    "tmux pane %{} has no native pane to split for %{}",
+                    split.target, split.pane
+                );
+                continue;
+            };
+            let pane = self.spawn_pane(window, split.pane, layout, spawn);
+            let direction = split_direction(split.axis);
+            let Some(tab) = tab_by_id_mut(tabs, tab_id) else {
+                return;
+            };
+            if let Err(e) = tab.pane_tree.split_with_id(target, direction, pane) {
+                error!("Failed to insert tmux pane %{}: {e}", split.pane);
+                self.panes.remove(&split.pane);
+                continue;
+            }
+            if let Err(e) = tab
+                .pane_tree
+                .set_split_ratio(target, direction, split.ratio)
+            {
+                warn!("Failed to size tmux pane %{}: {e}", split.pane);
+            }
+        }
+    }
+
+    /// Spawn a native pane mirroring tmux pane `tmux_pane` and register it.
+    /// Any output that arrived before it existed is replayed into it.
+    fn spawn_pane(
+        &mut self,
+        window: u32,
+        tmux_pane: u32,
+        layout: &TmuxLayout,
+        spawn: &mut SpawnPane<'_>,
+    ) -> panes::Pane {
+        let (cols, rows) = layout
+            .pane_size(tmux_pane)
+            .unwrap_or_else(|| (usize::from(DEFAULT_WIDTH), usize::from(DEFAULT_HEIGHT)));
+        let (pane, output_tx) = spawn(&self.commands, tmux_pane, (cols.max(1), rows.max(1)));
+
+        self.panes.insert(
+            tmux_pane,
+            TmuxPane {
+                window,
+                id: pane.id,
+                output_tx,
+            },
+        );
+        if let Some(pending) = self.orphan_output.remove(&tmux_pane) {
+            self.feed(tmux_pane, pending);
+        }
+        pane
+    }
+}
+
+/// Native split direction for a tmux layout axis.
+const fn split_direction(axis: TmuxSplitAxis) -> SplitDirection {
+    match axis {
+        TmuxSplitAxis::SideBySide => SplitDirection::Horizontal,
+        TmuxSplitAxis::Stacked => SplitDirection::Vertical,
+    }
+}
+
+/// Mutable access to the tab with stable id `id`.
+fn tab_by_id_mut(tabs: &mut TabManager, id: TabId) -> Option<&mut Tab> {
+    tabs.iter_mut().find(|t| t.id == id)
+}
+
+/// The session and tmux pane id behind the active pane of `tabs`, if it
+/// mirrors a tmux pane.
+fn active_tmux_pane<'a>(
+    tabs: &TabManager,
+    sessions: &'a [TmuxSession],
+) -> Option<(&'a TmuxSession, u32)> {
+    let active = tabs.active_tab().active_pane;
+    sessions
+        .iter()
+        .find_map(|s| s.tmux_pane_for(active).map(|pane| (s, pane)))
+}
+
+impl PerWindowState {
+    /// Ask tmux to split the active pane when it mirrors a tmux pane.
+    ///
+    /// Returns `false`, without doing anything, for ordinary panes.
+    pub(super) fn tmux_split_active_pane(&self, direction: SplitDirection) -> bool {
+        let Some((session, pane)) = active_tmux_pane(&self.tabs, &self.tmux_sessions) else {
+            return false;
+        };
+        session.split(pane, direction);
+        true
+    }
+
+    /// Ask tmux for a new window when the active pane mirrors a tmux pane.
+    ///
+    /// Returns `false`, without doing anything, for ordinary panes.
+    pub(super) fn tmux_new_window(&self) -> bool {
+        let Some((session, _)) = active_tmux_pane(&self.tabs, &self.tmux_sessions) else {
+            return false;
+        };
+        session.send(new_window_command());
+        true
+    }
+
+    /// Ask tmux to kill the active pane when it mirrors a tmux pane.
+    ///
+    /// Returns `false`, without doing anything, for ordinary panes.
+    pub(super) fn tmux_kill_active_pane(&self) -> bool {
+        let Some((session, pane)) = active_tmux_pane(&self.tabs, &self.tmux_sessions) else {
+            return false;
+        };
+        session.send(&kill_pane_command(pane));
+        true
+    }
+}
+
+impl FreminalGui {
+    /// Drain tmux control-mode events from every pane in `win` and apply
+    /// them.  Called once per frame, before dead panes are reaped, so panes
+    /// dropped here are closed on the same path as a shell exit.
+    pub(super) fn drain_tmux_events(&self, win: &mut PerWindowState) {
+        let mut batches: Vec<(PaneId, Vec<TmuxEvent>)> = Vec::new();
+        for tab in win.tabs.iter() {
+            if let Ok(panes) = tab.pane_tree.iter_panes() {
+                for pane in panes {
+                    let events: Vec<TmuxEvent> = pane.tmux_event_rx.try_iter().collect();
+                    if !events.is_empty() {
+                        batches.push((pane.id, events));
+                    }
+                }
+            }
+        }
+
+        // A session ends with its gateway pane; dropping it drops the
+        // feeders, which closes every mirrored pane.
+        let tabs = &win.tabs;
+        win.tmux_sessions
+            .retain(|s| tabs.iter().any(|t| t.pane_tree.find(s.gateway).is_some()));
+
+        for (gateway, events) in batches {
+            for event in events {
+                self.apply_tmux_event(win, gateway, event);
+            }
+        }
+    }
+
+    fn apply_tmux_event(&self, win: &mut PerWindowState, gateway: PaneId, event: TmuxEvent) {
+        if let TmuxEvent::Started { commands } = event {
+            info!("tmux control mode started in pane {gateway}");
+            let session = TmuxSession::start(gateway, commands, &win.tabs);
+            win.tmux_sessions.retain(|s| s.gateway != gateway);
+            win.tmux_sessions.push(session);
+            return;
+        }
+
+        let Some(index) = win.tmux_sessions.iter().position(|s| s.gateway == gateway) else {
+            trace!("Dropping tmux event from pane {gateway} with no session: {event:?}");
+            return;
+        };
+        // Take the session out of `win` so tabs and panes can be borrowed
+        // mutably while it is updated.
+        let mut session = win.tmux_sessions.swap_remove(index);
+        let env = PaneEnv {
+            repaint_handle: &win.repaint_handle,
+            window_post: &win.window_post,
+            cell_size: win.terminal_widget.cell_size(),
+            os_dark_mode: win.os_dark_mode,
+        };
+        let outcome = session.apply(&mut win.tabs, event, &mut |commands, tmux_pane, size| {
+            self.spawn_tmux_pane(&env, commands, tmux_pane, size)
+        });
+        match outcome {
+            SessionOutcome::Continue => {}
+            SessionOutcome::CommandFailed(message) => {
+                self.push_error_toast("tmux command failed", Some(message));
+            }
+            SessionOutcome::Exiting(reason) => {
+                if let Some(reason) = reason {
+                    self.push_info_toast("tmux client exited", Some(reason));
+                }
+            }
+            SessionOutcome::Ended => {
+                info!("tmux control mode ended in pane {gateway}");
+                return;
+            }
+        }
+        win.tmux_sessions.push(session);
+    }
+
+    /// Spawn a native pane of `cols` × `rows` cells mirroring tmux pane
+    /// `tmux_pane`, returning it and the sender that feeds it `%output`.
+    fn spawn_tmux_pane(
+        &self,
+        env: &PaneEnv<'_>,
+        commands: &TmuxCommandSender,
+        tmux_pane: u32,
+        (cols, rows): (usize, usize),
+    ) -> (panes::Pane, Sender<PtyRead>) {
+        let theme =
+            freminal_common::themes::by_slug(self.config.theme.active_slug(env.os_dark_mode))
+                .unwrap_or(&freminal_common::themes::CATPPUCCIN_MOCHA);
+
+        let (cell_w, cell_h) = env.cell_size;
+        let cw = cell_w.value_as::<usize>().unwrap_or(0);
+        let ch = cell_h.value_as::<usize>().unwrap_or(0);
+
+        let pane_id = self
+            .pane_id_gen
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .next_id();
+
+        let (channels, output_tx) = pty::spawn_tmux_pane(
+            commands.clone(),
+            tmux_pane,
+            self.config.scrollback.limit,
+            pty::PtyTabInitialState {
+                theme,
+                auto_detect_urls: self.config.ui.auto_detect_urls,
+                allow_checksum_reports: self.config.security.allow_checksum_reports,
+                cursor_style: freminal_common::cursor::CursorVisualStyle::from_config(
+                    &self.config.cursor.shape,
+                    self.config.cursor.blink,
+                ),
+            },
+            env.repaint_handle,
+            &FreminalTerminalSize {
+                width: cols,
+                height: rows,
+                pixel_width: cw * cols,
+                pixel_height: ch * rows,
+            },
+            pane_id.raw().try_into().unwrap_or(u32::MAX),
+        );
+
+        let pane = panes::Pane::from_channels(
+            pane_id,
+            channels,
+            Arc::clone(env.window_post),
+            "Terminal".to_owned(),
+        );
+        send_or_log!(
+            pane.input_tx,
+            InputEvent::ThemeModeUpdate(self.config.theme.mode, env.os_dark_mode),
+            "Failed to send ThemeModeUpdate to tmux pane"
+        );
+        (pane, output_tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::atomic::AtomicBool;
+
+    use crossbeam_channel::{Receiver, TryRecvError, unbounded};
+    use freminal_common::pty_write::PtyWrite;
+    use freminal_terminal_emulator::snapshot::TerminalSnapshot;
+    use freminal_terminal_emulator::tmux_control::TmuxControlSession;
+
+    use super::*;
+    use crate::gui::panes::{Pane, PaneIdGenerator};
+
+    /// A `Pane` with connected but inert channels, standing in for the
+    /// headless emulator `pty::spawn_tmux_pane` would start.
+    fn test_pane(id: PaneId) -> Pane {
+        let arc_swap = Arc::new(arc_swap::ArcSwap::from_pointee(TerminalSnapshot::empty()));
+        let (input_tx, _input_rx) = unbounded();
+        let (pty_write_tx, _pty_write_rx) = unbounded();
+        let (_window_cmd_tx, window_cmd_rx) = unbounded();
+        let (_clipboard_tx, clipboard_rx) = crossbeam_channel::bounded(1);
+        let (_search_buffer_tx, search_buffer_rx) = crossbeam_channel::bounded(1);
+        let (_pty_dead_tx, pty_dead_rx) = crossbeam_channel::bounded(1);
+        let (_command_event_tx, command_event_rx) = unbounded();
+        Pane {
+            id,
+            arc_swap,
+            input_tx,
+            pty_write_tx,
+            window_cmd_rx,
+            clipboard_rx,
+            search_buffer_rx,
+            pty_dead_rx,
+            title: String::new(),
+            bell_active: false,
+            pending_copy: false,
+            title_stack: Vec::new(),
+            view_state: crate::gui::view_state::ViewState::new(),
+            echo_off: Arc::new(AtomicBool::new(false)),
+            child_pid: None,
+            render_state: crate::gui::terminal::new_render_state(Arc::new(std::sync::Mutex::new(
+                crate::gui::renderer::WindowPostRenderer::new(),
+            ))),
+            render_cache: crate::gui::terminal::PaneRenderCache::new(),
+            command_event_rx,
+            tmux_event_rx: crossbeam_channel::never(),
+            recent_commands: std::collections::VecDeque::new(),
+            history_seed: crate::gui::shell_history::new_seeded_history(),
+            shell_program: None,
+            shell_histfile_last_seen: None,
+            command_texts: HashMap::new(),
+        }
+    }
+
+    /// A gateway pane attached to a fake tmux client: protocol lines go
+    /// through a real `TmuxControlSession`, and its events are applied to
+    /// `tabs` with stand-in panes.
+    struct Harness {
+        control: TmuxControlSession,
+        writes: Receiver<PtyWrite>,
+        session: TmuxSession,
+        tabs: TabManager,
+        ids: PaneIdGenerator,
+        /// The `%output` each mirrored pane received, by tmux pane.
+        outputs: HashMap<u32, Receiver<PtyRead>>,
+        /// The size each mirrored pane was spawned at, by tmux pane.
+        sizes: HashMap<u32, (usize, usize)>,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let mut ids = PaneIdGenerator::new(0);
+            let mut gateway = test_pane(ids.next_id());
+            gateway.view_state.last_sent_size = (100, 30);
+            let gateway_id = gateway.id;
+            let tabs = TabManager::new(Tab::new(TabId::first(), gateway));
+
+            let (tx, writes) = unbounded();
+            let control = TmuxControlSession::start(tx);
+            let session = TmuxSession::start(gateway_id, control.commands().clone(), &tabs);
+            Self {
+                control,
+                writes,
+                session,
+                tabs,
+                ids,
+                outputs: HashMap::new(),
+                sizes: HashMap::new(),
+            }
+        }
+
+        fn feed(&mut self, lines: &[&str]) -> Vec<SessionOutcome> {
+            let mut events = Vec::new();
+            for line in lines {
+                self.control.handle_line(line.as_bytes(), &mut events);
+            }
+            events.into_iter().map(|event| self.apply(event)).collect()
+        }
+
+        fn apply(&mut self, event: TmuxEvent) -> SessionOutcome {
+            let Self {
+                session,
+                tabs,
+                ids,
+                outputs,
+                sizes,
+                ..
+            } = self;
+            session.apply(tabs, event, &mut |_, tmux_pane, size| {
+                let (tx, rx) = unbounded();
+                outputs.insert(tmux_pane, rx);
+                sizes.insert(tmux_pane, size);
+                (test_pane(ids.next_id()), tx)
+            })
+        }
+
+        /// Command lines written to tmux since the last call.
+        fn written(&self) -> Vec<String> {
+            self.writes
+                .try_iter()
+                .filter_map(|w| match w {
+                    PtyWrite::Write(bytes) => Some(String::from_utf8(bytes).unwrap()),
+                    PtyWrite::Resize(_) => None,
+                })
+                .collect()
+        }
+
+        /// The `%output` received by tmux pane `pane`, or `None` once its
+        /// feeder has been dropped.
+        fn output(&self, pane: u32) -> Option<Vec<u8>> {
+            let rx = &self.outputs[&pane];
+            let mut data = Vec::new();
+            loop {
+                match rx.try_recv() {
+                    Ok(read) => data.extend(read.buf),
+                    Err(TryRecvError::Empty) => return Some(data),
+                    Err(TryRecvError::Disconnected) => return None,
+                }
+            }
+        }
+
+        fn tmux_panes(&self) -> Vec<u32> {
+            let mut panes: Vec<u32> = self.session.panes.keys().copied().collect();
+            panes.sort_unstable();
+            panes
+        }
+
+        /// Attach with one window `@1` split side by side into `%1` and
+        /// `%2`, and answer everything the attach sent: `%1` is captured
+        /// showing `$ ls`, `%2` blank.
+        fn attached() -> Self {
+            let mut harness = Self::new();
+            let _ = harness.written();
+            harness.feed(&[
+                "%begin 1 1 0",
+                "%end 1 1 0",
+                "%begin 1 2 1",
+                "@1 80x24,0,0{40x24,0,0,1,39x24,41,0,2} zsh",
+                "%end 1 2 1",
+                "%begin 1 3 1",
+                "%end 1 3 1",
+                "%begin 1 4 1",
+                "$ ls",
+                "%end 1 4 1",
+                "%begin 1 5 1",
+                "%end 1 5 1",
+            ]);
+            harness
+        }
+    }
+
+    #[test]
+    fn start_sizes_tmux_to_the_gateway() {
+        let harness = Harness::new();
+        let written = harness.written();
+        assert_eq!(written.len(), 2);
+        assert!(written[0].starts_with("list-windows -F "));
+        assert_eq!(written[1], "refresh-client -C 100x30\n");
+    }
+
+    #[test]
+    fn attach_opens_a_tab_per_window_and_captures_its_panes() {
+        let harness = Harness::attached();
+        assert_eq!(
+            harness.written(),
+            vec![
+                "capture-pane -p -e -t %1\n".to_owned(),
+                "capture-pane -p -e -t %2\n".to_owned(),
+            ]
+        );
+        assert_eq!(harness.tabs.tab_count(), 2);
+        let tab = harness.tabs.iter().last().unwrap();
+        assert_eq!(tab.custom_name.as_deref(), Some("zsh"));
+        assert_eq!(tab.pane_tree.pane_count().unwrap(), 2);
+        assert_eq!(harness.tmux_panes(), vec![1, 2]);
+        assert_eq!(harness.sizes[&1], (40, 24));
+        assert_eq!(harness.sizes[&2], (39, 24));
+    }
+
+    #[test]
+    fn captures_and_output_reach_the_mirrored_pane() {
+        let mut harness = Harness::attached();
+        harness.feed(&["%output %1 hi\\015\\012"]);
+        assert_eq!(
+            harness.output(1).unwrap(),
+            b"\x1b[H\x1b[2J$ lshi\r\n".to_vec()
+        );
+        assert_eq!(harness.output(2).unwrap(), b"\x1b[H\x1b[2J".to_vec());
+    }
+
+    #[test]
+    fn layout_change_drops_removed_panes_and_splits_new_ones() {
+        let mut harness = Harness::attached();
+        let _ = harness.written();
+        let outcomes = harness.feed(&["%layout-change @1 80x24,0,0[80x12,0,0,1,80x11,0,13,4]"]);
+        assert_eq!(outcomes, vec![SessionOutcome::Continue]);
+        assert_eq!(harness.tmux_panes(), vec![1, 4]);
+        assert_eq!(harness.sizes[&4], (80, 11));
+        // `%2`'s feeder is gone, so its native pane dies on the next frame.
+        assert_eq!(harness.output(2), None);
+        let tab = harness.tabs.iter().last().unwrap();
+        assert_eq!(tab.pane_tree.pane_count().unwrap(), 3);
+        // Layout changes are mirrored, never echoed back to tmux.
+        assert!(harness.written().is_empty());
+    }
+
+    #[test]
+    fn window_add_and_close_open_and_drop_a_tab() {
+        let mut harness = Harness::attached();
+        let _ = harness.written();
+
+        harness.feed(&["%window-add @2"]);
+        let written = harness.written();
+        assert_eq!(written.len(), 1);
+        assert!(written[0].starts_with("list-windows -F "));
+
+        // Output that arrives before the pane exists is replayed into it.
+        harness.feed(&[
+            "%output %3 early",
+            "%begin 1 7 1",
+            "@1 80x24,0,0{40x24,0,0,1,39x24,41,0,2} zsh",
+            "@2 80x24,0,0,3 vim",
+            "%end 1 7 1",
+        ]);
+        assert_eq!(harness.tabs.tab_count(), 3);
+        let tab = harness.tabs.iter().last().unwrap();
+        assert_eq!(tab.custom_name.as_deref(), Some("vim"));
+        assert_eq!(harness.tmux_panes(), vec![1, 2, 3]);
+        assert_eq!(harness.output(3).unwrap(), b"early".to_vec());
+        // Only the first listing captures panes.
+        assert!(harness.written().is_empty());
+
+        harness.feed(&["%window-close @2"]);
+        assert!(!harness.session.windows.contains_key(&2));
+        assert_eq!(harness.tmux_panes(), vec![1, 2]);
+        assert_eq!(harness.output(3), None);
+    }
+
+    #[test]
+    fn exit_is_reported_and_end_drops_the_session() {
+        let mut harness = Harness::attached();
+        assert_eq!(
+            harness.feed(&["%exit detached"]),
+            vec![SessionOutcome::Exiting(Some("detached".to_owned()))]
+        );
+        assert_eq!(harness.apply(TmuxEvent::Ended), SessionOutcome::Ended);
+    }
+
+    #[test]
+    fn split_from_a_mirrored_pane_asks_tmux() {
+        let mut harness = Harness::attached();
+        let _ = harness.written();
+        harness.tabs.switch_to(0).unwrap();
+        let sessions = std::slice::from_ref(&harness.session);
+        assert!(active_tmux_pane(&harness.tabs, sessions).is_none());
+
+        harness.tabs.switch_to(1).unwrap();
+        let active = harness.tabs.active_tab().active_pane;
+        let sessions = std::slice::from_ref(&harness.session);
+        let (session, pane) = active_tmux_pane(&harness.tabs, sessions).unwrap();
+        assert_eq!(harness.session.tmux_pane_for(active), Some(pane));
+        session.split(pane, SplitDirection::Vertical);
+        assert_eq!(
+            harness.written(),
+            vec![format!("split-window -v -t %{pane}\n")]
+        );
+    }
+}
@@ -480,6 +480,12 @@ pub(super) struct PerWindowState {
     /// classes (a genuinely-unchanged redraw costs no CPU rebuild; a `Full`
     /// frame rebuilds the visible vertex data).
     pub(super) frame_stats: FrameStats,
+
+    /// `tmux -CC` sessions whose gateway pane lives in this window.
+    ///
+    /// Each entry maps the session's tmux windows and panes to the native
+    /// tabs and panes mirroring them.  See [`super::tmux`].
+    pub(super) tmux_sessions: Vec<super::tmux::TmuxSession>,
 }
 
 /// Diagnostic per-frame render attribution (see [`PerWindowState::frame_stats`]).
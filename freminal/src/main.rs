@@ -57,11 +57,19 @@ use tracing_subscriber::{
 };
 
 pub mod gui;
+mod rec_cli;
+mod remote_client;
+mod render_cli;
 mod shell_integration;
 use anyhow::Result;
-use freminal_common::{args::Args, config, config::load_config_with_warnings};
+use freminal_common::{
+    args::{Args, CliCommand},
+    config,
+    config::load_config_with_warnings,
+};
 use freminal_terminal_emulator::recording::{
-    RecordingMetadata, RecordingSwap, TopologySnapshot, empty_recording_swap, start_recording,
+    RecordingMetadata, RecordingSwap, TopologySnapshot, empty_recording_swap, parse_recording,
+    start_recording,
 };
 
 use clap::Parser;
@@ -73,6 +81,16 @@ use clap::Parser;
 /// user-visible toast and so that a startup layout or session restore can
 /// replace the tabs without leaving an orphaned PTY behind.
 fn normal_run(args: Args, cfg: freminal_common::config::Config) -> Result<()> {
+    // Load the `--play` recording before any window opens so an unreadable
+    // file is reported on the command line.
+    let playback = match args.play {
+        Some(ref path) => Some(
+            parse_recording(path)
+                .map_err(|e| anyhow::anyhow!("Failed to open {}: {e}", path.display()))?,
+        ),
+        None => None,
+    };
+
     // Shared recording swap. Populated below if --recording-path was given;
     // the GUI can also mutate it at runtime via the ToggleRecording action.
     let recording_swap: RecordingSwap = empty_recording_swap();
@@ -118,7 +136,8 @@ fn normal_run(args: Args, cfg: freminal_common::config::Config) -> Result<()> {
         config_path,
         repaint_handle,
         window_post,
-        recording_swap,
+        &recording_swap,
+        playback,
     )
 }
 
@@ -132,6 +151,17 @@ fn main() {
 
     let args = Args::parse();
 
+    // Utility subcommands never open a window; run them before config
+    // loading and logging so their output stays clean.
+    if let Some(subcommand) = args.subcommand.clone() {
+        let code = match subcommand {
+            CliCommand::Msg(msg) => remote_client::run(msg),
+            CliCommand::Rec(rec) => rec_cli::run(rec),
+            CliCommand::Render(render) => render_cli::run(&render, args.config.as_deref()),
+        };
+        std::process::exit(code);
+    }
+
     // Collect warnings that occur before the tracing subscriber is
     // initialised.  They are replayed as `warn!()` once logging is ready.
     // On Windows (with windows_subsystem = "windows") there is no console
@@ -0,0 +1,490 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! `freminal rec`: inspect, slice, and dump FREC session recordings.
+//!
+//! Runs before config loading and logging setup, prints the result to
+//! stdout (errors to stderr), and returns the process exit code.  Decoding
+//! and slicing live in [`freminal_terminal_emulator::recording`] so the
+//! tooling always reads the format the recorder writes.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write as _};
+use std::path::Path;
+
+use freminal_common::args::{RecArgs, RecCommand};
+use freminal_terminal_emulator::asciicast::{AsciicastError, export_asciicast, import_asciicast};
+use freminal_terminal_emulator::playback::topology_after;
+use freminal_terminal_emulator::recording::{
+    EventPayload, EventType, PaneNodeSnapshot, ParsedRecording, TopologySnapshot, parse_recording,
+    write_recording,
+};
+
+/// Exit code for an unreadable recording or a failed write.
+const EXIT_IO_FAILED: i32 = 1;
+
+/// Exit code for arguments that do not fit the recording.
+const EXIT_BAD_ARGUMENTS: i32 = 2;
+
+/// A failed `freminal rec` (or `freminal render`) command and the exit code
+/// it maps to.
+pub struct RecFailure {
+    pub code: i32,
+    pub message: String,
+}
+
+impl RecFailure {
+    pub fn io(message: impl std::fmt::Display) -> Self {
+        Self {
+            code: EXIT_IO_FAILED,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn usage(message: impl std::fmt::Display) -> Self {
+        Self {
+            code: EXIT_BAD_ARGUMENTS,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Run one `freminal rec` invocation and return the process exit code.
+pub fn run(rec: RecArgs) -> i32 {
+    let result = match rec.command {
+        RecCommand::Info { file } => {
+            load(&file).map(|recording| print!("{}", format_info(&recording)))
+        }
+        RecCommand::Cat {
+            file,
+            pane,
+            visible,
+            input,
+            framed,
+        } => load(&file).and_then(|recording| cat(&recording, pane, visible, input, framed)),
+        RecCommand::Extract {
+            file,
+            output,
+            from,
+            to,
+        } => load(&file).and_then(|recording| extract(&recording, &output, from, to)),
+        RecCommand::Events {
+            file,
+            pane,
+            types,
+            from,
+            to,
+        } => load(&file).and_then(|recording| events(&recording, pane, &types, from, to)),
+        RecCommand::ExportCast { file, output, pane } => {
+            load(&file).and_then(|recording| export_cast(&recording, &output, pane))
+        }
+        RecCommand::ImportCast { file, output } => import_cast(&file, &output),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(failure) => {
+            eprintln!("freminal rec: {}", failure.message);
+            failure.code
+        }
+    }
+}
+
+pub fn load(file: &Path) -> Result<ParsedRecording, RecFailure> {
+    parse_recording(file).map_err(|e| RecFailure::io(format!("{}: {e}", file.display())))
+}
+
+/// Write one pane's PTY stream to stdout.
+fn cat(
+    recording: &ParsedRecording,
+    pane: Option<u32>,
+    visible: bool,
+    input: bool,
+    framed: bool,
+) -> Result<(), RecFailure> {
+    let pane = match pane {
+        Some(pane) => pane,
+        None => only_pane(recording)?,
+    };
+    let mut stdout = std::io::stdout().lock();
+    for data in recording
+        .events
+        .iter()
+        .filter_map(|event| pty_data(&event.payload, pane, input))
+    {
+        let written = if framed {
+            let len = u32::try_from(data.len()).map_err(RecFailure::io)?;
+            stdout
+                .write_all(&len.to_le_bytes())
+                .and_then(|()| stdout.write_all(data))
+        } else if visible {
+            stdout.write_all(make_visible(data).as_bytes())
+        } else {
+            stdout.write_all(data)
+        };
+        written.map_err(RecFailure::io)?;
+    }
+    stdout.flush().map_err(RecFailure::io)
+}
+
+/// The pane `cat` defaults to: the only one the recording ever had.
+pub fn only_pane(recording: &ParsedRecording) -> Result<u32, RecFailure> {
+    let panes = pane_byte_counts(recording);
+    let mut ids = panes
+        .keys()
+        .copied()
+        .chain(topology_pane_ids(&recording.metadata.initial_topology));
+    let Some(first) = ids.next() else {
+        return Err(RecFailure::usage("the recording has no panes"));
+    };
+    if ids.all(|id| id == first) {
+        Ok(first)
+    } else {
+        Err(RecFailure::usage(
+            "the recording has several panes; pick one with --pane (see `freminal rec info`)",
+        ))
+    }
+}
+
+/// The PTY bytes of `payload` if it is output (or input) for `pane`.
+const fn pty_data(payload: &EventPayload, pane: u32, input: bool) -> Option<&[u8]> {
+    match payload {
+        EventPayload::PtyOutput { pane_id, data } if !input && *pane_id == pane => {
+            Some(data.as_slice())
+        }
+        EventPayload::PtyInput { pane_id, data } if input && *pane_id == pane => {
+            Some(data.as_slice())
+        }
+        _ => None,
+    }
+}
+
+/// Render PTY bytes with control characters in caret notation, like
+/// `cat -v`: ESC becomes `^[`, CR `^M`, DEL `^?`.  Newlines and tabs are
+/// kept so the output stays readable, and bytes that are not valid UTF-8
+/// are shown as `\xNN`.
+fn make_visible(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len());
+    for chunk in data.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\n' | '\t' => out.push(c),
+                '\x7f' => out.push_str("^?"),
+                c if c.is_ascii_control() => {
+                    out.push('^');
+                    out.push(char::from_u32(u32::from(c) ^ 0x40).unwrap_or('?'));
+                }
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(out, "\\x{byte:02X}");
+        }
+    }
+    out
+}
+
+/// Write the events between `from` and `to` to a new recording at `output`.
+fn extract(
+    recording: &ParsedRecording,
+    output: &Path,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<(), RecFailure> {
+    let (start, end) = time_range(recording, from, to)?;
+    let (metadata, events) = recording.slice(start, end);
+    let file =
+        File::create(output).map_err(|e| RecFailure::io(format!("{}: {e}", output.display())))?;
+    write_recording(BufWriter::new(file), &metadata, &events)
+        .map_err(|e| RecFailure::io(format!("{}: {e}", output.display())))
+}
+
+/// Write one pane of `recording` to `output` as asciicast v2.
+fn export_cast(
+    recording: &ParsedRecording,
+    output: &Path,
+    pane: Option<u32>,
+) -> Result<(), RecFailure> {
+    let pane = match pane {
+        Some(pane) => pane,
+        None => only_pane(recording)?,
+    };
+    // Render first so a bad pane does not clobber an existing file.
+    let mut cast = Vec::new();
+    export_asciicast(recording, pane, &mut cast).map_err(|e| match e {
+        AsciicastError::UnknownPane(_) => RecFailure::usage(e),
+        e => RecFailure::io(e),
+    })?;
+    std::fs::write(output, cast).map_err(|e| RecFailure::io(format!("{}: {e}", output.display())))
+}
+
+/// Convert the asciicast file at `file` to a FREC recording at `output`.
+fn import_cast(file: &Path, output: &Path) -> Result<(), RecFailure> {
+    let cast = File::open(file).map_err(|e| RecFailure::io(format!("{}: {e}", file.display())))?;
+    let (metadata, events) = import_asciicast(BufReader::new(cast))
+        .map_err(|e| RecFailure::io(format!("{}: {e}", file.display())))?;
+    let out =
+        File::create(output).map_err(|e| RecFailure::io(format!("{}: {e}", output.display())))?;
+    write_recording(BufWriter::new(out), &metadata, &events)
+        .map_err(|e| RecFailure::io(format!("{}: {e}", output.display())))
+}
+
+/// Resolve `--from`/`--to` against the recording's duration.
+fn time_range(
+    recording: &ParsedRecording,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<(u64, u64), RecFailure> {
+    let start = from.unwrap_or(0);
+    let end = to.unwrap_or(recording.total_duration_us);
+    if start > end {
+        return Err(RecFailure::usage("--from is after --to"));
+    }
+    Ok((start, end))
+}
+
+/// Print the matching events as JSON lines.
+fn events(
+    recording: &ParsedRecording,
+    pane: Option<u32>,
+    types: &[String],
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<(), RecFailure> {
+    let types = types
+        .iter()
+        .map(|name| {
+            EventType::from_name(name)
+                .ok_or_else(|| RecFailure::usage(format!("unknown event type `{name}`")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (start, end) = time_range(recording, from, to)?;
+
+    let mut stdout = std::io::stdout().lock();
+    for (index, event) in recording.events.iter().enumerate() {
+        let event_type = event.payload.event_type();
+        if event.timestamp_us < start
+            || event.timestamp_us > end
+            || pane.is_some_and(|id| !event.payload.involves_pane(id))
+            || (!types.is_empty() && !types.contains(&event_type))
+        {
+            continue;
+        }
+        let line = event_json(index, event.timestamp_us, &event.payload)?;
+        writeln!(stdout, "{line}").map_err(RecFailure::io)?;
+    }
+    stdout.flush().map_err(RecFailure::io)
+}
+
+/// One event as a flat JSON object: `index`, `timestamp_us`, and `type`
+/// alongside the payload fields.
+///
+/// Byte fields (`data`, `encoded`) are decoded as lossy UTF-8 so the
+/// output stays greppable; use `freminal rec cat` for the exact bytes.
+fn event_json(
+    index: usize,
+    timestamp_us: u64,
+    payload: &EventPayload,
+) -> Result<serde_json::Value, RecFailure> {
+    let mut object = serde_json::Map::new();
+    object.insert("index".to_owned(), index.into());
+    object.insert("timestamp_us".to_owned(), timestamp_us.into());
+    object.insert("type".to_owned(), payload.event_type().name().into());
+
+    // Payloads serialize as `{"Variant": {fields}}`; keep only the fields.
+    let value = serde_json::to_value(payload).map_err(RecFailure::io)?;
+    if let serde_json::Value::Object(variant) = value {
+        for (_, fields) in variant {
+            if let serde_json::Value::Object(fields) = fields {
+                object.extend(fields);
+            }
+        }
+    }
+    match payload {
+        EventPayload::PtyOutput { data, .. }
+        | EventPayload::PtyInput { data, .. }
+        | EventPayload::ClipboardPaste { data, .. } => {
+            object.insert(
+                "data".to_owned(),
+                String::from_utf8_lossy(data).into_owned().into(),
+            );
+        }
+        EventPayload::KeyboardInput { encoded, .. } => {
+            object.insert(
+                "encoded".to_owned(),
+                String::from_utf8_lossy(encoded).into_owned().into(),
+            );
+        }
+        _ => {}
+    }
+    Ok(serde_json::Value::Object(object))
+}
+
+/// Render the `info` summary.
+fn format_info(recording: &ParsedRecording) -> String {
+    let metadata = &recording.metadata;
+    let mut out = String::new();
+    let _ = writeln!(out, "freminal version: {}", metadata.freminal_version);
+    let _ = writeln!(out, "created at:       {} (unix)", metadata.created_at);
+    let _ = writeln!(out, "TERM:             {}", metadata.term);
+    let _ = writeln!(out, "scrollback limit: {}", metadata.scrollback_limit);
+    let _ = writeln!(
+        out,
+        "duration:         {}",
+        format_duration(recording.total_duration_us)
+    );
+    let _ = writeln!(out, "events:           {}", recording.total_events);
+    let _ = writeln!(out, "seek entries:     {}", recording.seek_index.len());
+
+    out.push_str("\ntopology at start:\n");
+    format_topology(&mut out, &metadata.initial_topology);
+    let end = topology_after(recording, recording.events.len());
+    if end != metadata.initial_topology {
+        out.push_str("\ntopology at end:\n");
+        format_topology(&mut out, &end);
+    }
+
+    let mut counts = BTreeMap::new();
+    for event in &recording.events {
+        *counts
+            .entry(event.payload.event_type().to_u8())
+            .or_insert(0_usize) += 1;
+    }
+    out.push_str("\nevents by type:\n");
+    for event_type in EventType::ALL {
+        if let Some(count) = counts.get(&event_type.to_u8()) {
+            let _ = writeln!(out, "  {:<16}{count}", event_type.name());
+        }
+    }
+
+    let bytes = pane_byte_counts(recording);
+    if !bytes.is_empty() {
+        out.push_str("\nPTY bytes by pane (output/input):\n");
+        for (pane, (output, input)) in bytes {
+            let _ = writeln!(out, "  pane {pane}: {output}/{input}");
+        }
+    }
+    out
+}
+
+/// Output and input byte totals for every pane with PTY traffic.
+fn pane_byte_counts(recording: &ParsedRecording) -> BTreeMap<u32, (usize, usize)> {
+    let mut bytes = BTreeMap::new();
+    for event in &recording.events {
+        match &event.payload {
+            EventPayload::PtyOutput { pane_id, data } => {
+                bytes.entry(*pane_id).or_insert((0, 0)).0 += data.len();
+            }
+            EventPayload::PtyInput { pane_id, data } => {
+                bytes.entry(*pane_id).or_insert((0, 0)).1 += data.len();
+            }
+            _ => {}
+        }
+    }
+    bytes
+}
+
+fn topology_pane_ids(topology: &TopologySnapshot) -> Vec<u32> {
+    topology
+        .windows
+        .iter()
+        .flat_map(|window| &window.tabs)
+        .flat_map(|tab| tab.pane_tree.node.pane_ids())
+        .collect()
+}
+
+/// Append an indented window/tab/pane tree, marking active tabs and panes
+/// with `*`.
+fn format_topology(out: &mut String, topology: &TopologySnapshot) {
+    if topology.windows.is_empty() {
+        out.push_str("  (empty)\n");
+    }
+    for window in &topology.windows {
+        let _ = writeln!(
+            out,
+            "  window {} {}x{}px",
+            window.window_id, window.size.0, window.size.1
+        );
+        for tab in &window.tabs {
+            let mark = if tab.tab_id == window.active_tab {
+                "*"
+            } else {
+                " "
+            };
+            let _ = writeln!(out, "   {mark}tab {}", tab.tab_id);
+            format_node(out, &tab.pane_tree.node, tab.active_pane, 3);
+        }
+    }
+}
+
+fn format_node(out: &mut String, node: &PaneNodeSnapshot, active: u32, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match node {
+        PaneNodeSnapshot::Leaf {
+            pane_id,
+            cols,
+            rows,
+            title,
+            ..
+        } => {
+            let mark = if *pane_id == active { "*" } else { " " };
+            let _ = writeln!(out, "{indent}{mark}pane {pane_id} {cols}x{rows} {title:?}");
+        }
+        PaneNodeSnapshot::Split {
+            direction,
+            ratio,
+            first,
+            second,
+        } => {
+            let _ = writeln!(out, "{indent} split {direction:?} {ratio:.2}");
+            format_node(out, first, active, depth + 1);
+            format_node(out, second, active, depth + 1);
+        }
+    }
+}
+
+/// `m:ss.mmm` for a duration in microseconds.
+fn format_duration(us: u64) -> String {
+    let ms = us / 1_000;
+    format!("{}:{:02}.{:03}", ms / 60_000, (ms / 1_000) % 60, ms % 1_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible_uses_caret_notation() {
+        assert_eq!(
+            make_visible(b"\x1b[1mhi\x1b[0m\r\n\tok\x7f\xff"),
+            "^[[1mhi^[[0m^M\n\tok^?\\xFF"
+        );
+    }
+
+    #[test]
+    fn event_json_flattens_payload_and_decodes_bytes() {
+        let payload = EventPayload::PtyOutput {
+            pane_id: 3,
+            data: b"ls\r\n".to_vec(),
+        };
+        let json = event_json(7, 1_500, &payload).unwrap_or_default();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "index": 7,
+                "timestamp_us": 1_500,
+                "type": "PtyOutput",
+                "pane_id": 3,
+                "data": "ls\r\n",
+            })
+        );
+    }
+
+    #[test]
+    fn duration_is_minutes_seconds_millis() {
+        assert_eq!(format_duration(83_456_789), "1:23.456");
+    }
+}
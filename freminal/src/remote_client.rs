@@ -0,0 +1,149 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! `freminal msg`: a one-shot client for a running instance's remote-control
+//! socket.
+//!
+//! Runs before config loading and logging setup, prints the result to
+//! stdout (errors to stderr), and returns the process exit code.  The
+//! protocol itself lives in [`freminal_common::remote_control`].
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use freminal_common::args::MsgArgs;
+use freminal_common::remote_control::{
+    RemoteControlError, RemoteRequest, RemoteResponse, RemoteResult, WindowInfo, resolve_socket,
+    send_request,
+};
+
+/// Exit code for a command the server rejected.
+const EXIT_COMMAND_FAILED: i32 = 1;
+
+/// Exit code for a failure to reach or talk to an instance.
+const EXIT_CONNECTION_FAILED: i32 = 2;
+
+/// Run one `freminal msg` invocation and return the process exit code.
+pub fn run(msg: MsgArgs) -> i32 {
+    let MsgArgs { to, json, command } = msg;
+    let request = RemoteRequest::new(command.into());
+    let response = match exchange(to.as_deref(), &request) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("freminal msg: {e}");
+            return EXIT_CONNECTION_FAILED;
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&response) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("freminal msg: {e}");
+                return EXIT_CONNECTION_FAILED;
+            }
+        }
+        return match response {
+            RemoteResponse::Ok { .. } => 0,
+            RemoteResponse::Error { .. } => EXIT_COMMAND_FAILED,
+        };
+    }
+
+    match response {
+        RemoteResponse::Ok { result, .. } => {
+            match result {
+                Some(RemoteResult::Windows(windows)) => print!("{}", format_tree(&windows)),
+                // Rows arrive newline-joined with trailing blanks trimmed.
+                Some(RemoteResult::Text(text)) => println!("{text}"),
+                Some(RemoteResult::Pane(id)) => println!("{id}"),
+                Some(RemoteResult::Path(path)) => println!("{}", path.display()),
+                None => {}
+            }
+            0
+        }
+        RemoteResponse::Error { message, .. } => {
+            eprintln!("freminal msg: {message}");
+            EXIT_COMMAND_FAILED
+        }
+    }
+}
+
+/// Resolve the target socket and send `request` to it.
+fn exchange(
+    to: Option<&Path>,
+    request: &RemoteRequest,
+) -> Result<RemoteResponse, RemoteControlError> {
+    let socket = resolve_socket(to)?;
+    send_request(&socket, request)
+}
+
+/// Render an `ls` answer as an indented tree, marking focused/active items
+/// with `*`.
+fn format_tree(windows: &[WindowInfo]) -> String {
+    let mut out = String::new();
+    for window in windows {
+        let mark = if window.focused { "*" } else { " " };
+        let _ = writeln!(out, "{mark}window {}", window.id);
+        for tab in &window.tabs {
+            let mark = if tab.active { "*" } else { " " };
+            let _ = writeln!(out, "  {mark}tab {} {:?}", tab.index, tab.title);
+            for pane in &tab.panes {
+                let mark = if pane.active { "*" } else { " " };
+                let _ = write!(
+                    out,
+                    "    {mark}pane {} {}x{} {:?}",
+                    pane.id, pane.columns, pane.rows, pane.title
+                );
+                if let Some(cwd) = &pane.cwd {
+                    let _ = write!(out, " {cwd}");
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use freminal_common::remote_control::{PaneInfo, TabInfo};
+
+    use super::*;
+
+    #[test]
+    fn tree_marks_focus_and_lists_panes() {
+        let windows = vec![WindowInfo {
+            id: 0,
+            focused: true,
+            tabs: vec![TabInfo {
+                index: 0,
+                title: "build".to_owned(),
+                active: true,
+                panes: vec![
+                    PaneInfo {
+                        id: 1,
+                        title: "zsh".to_owned(),
+                        active: false,
+                        cwd: Some("/src".to_owned()),
+                        columns: 80,
+                        rows: 24,
+                    },
+                    PaneInfo {
+                        id: 2,
+                        title: "vim".to_owned(),
+                        active: true,
+                        cwd: None,
+                        columns: 80,
+                        rows: 24,
+                    },
+                ],
+            }],
+        }];
+        assert_eq!(
+            format_tree(&windows),
+            "*window 0\n  *tab 0 \"build\"\n     pane 1 80x24 \"zsh\" /src\n    *pane 2 80x24 \"vim\"\n"
+        );
+    }
+}
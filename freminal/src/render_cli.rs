@@ -0,0 +1,182 @@
+// Copyright (C) 2024-2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+//! `freminal render`: run a program, a byte stream, or a recorded pane
+//! through a windowless terminal and write the final screen.
+//!
+//! Like `freminal rec`, this runs before logging setup and needs no window
+//! or GPU, so it works in CI.  The terminal and the exporters live in
+//! [`freminal_terminal_emulator::headless`] and
+//! [`freminal_terminal_emulator::screen_export`]; `--screenshot` PNGs come
+//! from [`CpuRenderer`].
+
+use std::fs::File;
+use std::io::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+use freminal_common::args::{RenderArgs, ScreenFormat};
+use freminal_common::config::{Config, load_config};
+use freminal_common::themes::{self, ThemePalette};
+use freminal_terminal_emulator::headless::{
+    HeadlessTerminal, StopConditions, StopReason, run_command, run_recording, run_stream,
+};
+use freminal_terminal_emulator::screen_export;
+use freminal_terminal_emulator::snapshot::TerminalSnapshot;
+use regex::Regex;
+
+use crate::gui::renderer::CpuRenderer;
+use crate::gui::renderer::cpu::encode_png;
+use crate::rec_cli::{RecFailure, load, only_pane};
+
+/// Terminal width when neither `--cols` nor a recording gives one.
+const DEFAULT_COLS: u16 = 80;
+
+/// Terminal height when neither `--rows` nor a recording gives one.
+const DEFAULT_ROWS: u16 = 24;
+
+/// Exit code when `--until` never matched.
+const EXIT_NOT_MATCHED: i32 = 3;
+
+/// Run one `freminal render` invocation and return the process exit code.
+///
+/// `config` is the top-level `--config` path, consulted for the theme when
+/// `--theme` is not given.
+pub fn run(render: &RenderArgs, config: Option<&Path>) -> i32 {
+    match render_screen(render, config) {
+        Ok(reason) => {
+            if render.until.is_some() && reason != StopReason::Matched {
+                eprintln!(
+                    "freminal render: --until did not match ({})",
+                    describe(reason)
+                );
+                EXIT_NOT_MATCHED
+            } else {
+                0
+            }
+        }
+        Err(failure) => {
+            eprintln!("freminal render: {}", failure.message);
+            failure.code
+        }
+    }
+}
+
+fn render_screen(render: &RenderArgs, config: Option<&Path>) -> Result<StopReason, RecFailure> {
+    // The configuration supplies the theme and the screenshot font; a
+    // broken config file should not matter when neither needs it.
+    let config = if render.theme.is_none() || render.screenshot.is_some() {
+        Some(load_config(config).map_err(|e| RecFailure::io(format!("config: {e}")))?)
+    } else {
+        None
+    };
+    let theme = resolve_theme(render.theme.as_deref(), config.as_ref())?;
+    let until = render
+        .until
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .map_err(|e| RecFailure::usage(format!("invalid --until pattern: {e}")))?;
+    let stop = StopConditions {
+        timeout: render.timeout.map(Duration::from_micros),
+        until,
+    };
+
+    let size_given = render.cols.is_some() || render.rows.is_some();
+    let mut term = HeadlessTerminal::new(
+        usize::from(render.cols.unwrap_or(DEFAULT_COLS)),
+        usize::from(render.rows.unwrap_or(DEFAULT_ROWS)),
+        theme,
+    );
+
+    let reason = if let Some(file) = &render.frec {
+        let recording = load(file)?;
+        let pane = match render.pane {
+            Some(pane) => pane,
+            None => only_pane(&recording)?,
+        };
+        run_recording(&mut term, &recording, pane, !size_given, &stop)
+    } else if let Some(input) = &render.input {
+        let result = if input.as_os_str() == "-" {
+            run_stream(&mut term, std::io::stdin(), &stop)
+        } else {
+            let file = File::open(input)
+                .map_err(|e| RecFailure::io(format!("{}: {e}", input.display())))?;
+            run_stream(&mut term, file, &stop)
+        };
+        result.map_err(RecFailure::io)?
+    } else {
+        let Some((program, args)) = render.command.split_first() else {
+            return Err(RecFailure::usage("no command to run"));
+        };
+        run_command(&mut term, program, args, &stop).map_err(RecFailure::io)?
+    };
+
+    let snapshot = term.snapshot();
+    let screen = match render.format {
+        ScreenFormat::Text => screen_export::to_text(&snapshot),
+        ScreenFormat::Ansi => screen_export::to_ansi(&snapshot),
+        ScreenFormat::Html => screen_export::to_html(&snapshot),
+        ScreenFormat::Svg => screen_export::to_svg(&snapshot),
+    };
+    let written = if let Some(path) = &render.output {
+        std::fs::write(path, screen).map_err(|e| RecFailure::io(format!("{}: {e}", path.display())))
+    } else {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(screen.as_bytes())
+            .and_then(|()| stdout.flush())
+            .map_err(RecFailure::io)
+    };
+    written?;
+
+    if let Some(path) = &render.screenshot {
+        write_screenshot(&snapshot, &config.unwrap_or_default(), path)?;
+    }
+    Ok(reason)
+}
+
+/// Draw `snapshot` with the software renderer and write it to `path` as PNG.
+fn write_screenshot(
+    snapshot: &TerminalSnapshot,
+    config: &Config,
+    path: &Path,
+) -> Result<(), RecFailure> {
+    let mut renderer =
+        CpuRenderer::new(config, 1.0).map_err(|e| RecFailure::io(format!("fonts: {e}")))?;
+    let png = encode_png(&renderer.render(snapshot))
+        .map_err(|e| RecFailure::io(format!("{}: {e}", path.display())))?;
+    std::fs::write(path, png).map_err(|e| RecFailure::io(format!("{}: {e}", path.display())))
+}
+
+/// The palette to draw with: `--theme`, else the configured theme.
+fn resolve_theme(
+    slug: Option<&str>,
+    config: Option<&Config>,
+) -> Result<&'static ThemePalette, RecFailure> {
+    if let Some(slug) = slug {
+        return themes::by_slug(slug).ok_or_else(|| {
+            let known: Vec<&str> = themes::all_themes().iter().map(|t| t.slug).collect();
+            RecFailure::usage(format!(
+                "unknown theme `{slug}` (known themes: {})",
+                known.join(", ")
+            ))
+        });
+    }
+    // There is no window to ask about the OS appearance; `auto` picks the
+    // dark variant.
+    Ok(config
+        .and_then(|config| themes::by_slug(config.theme.active_slug(true)))
+        .unwrap_or(themes::DEFAULT_THEME))
+}
+
+const fn describe(reason: StopReason) -> &'static str {
+    match reason {
+        StopReason::Exited => "the program exited",
+        StopReason::EndOfInput => "the input ended",
+        StopReason::TimedOut => "timed out",
+        StopReason::Matched => "matched",
+    }
+}
@@ -116,7 +116,7 @@ let
       };
 
       securitySection = lib.filterAttrs (_: v: v != null) {
-        inherit (s.security) allow_clipboard_read;
+        inherit (s.security) allow_clipboard_read allow_remote_control allow_checksum_reports;
       };
 
       pasteGuardSection = lib.filterAttrs (_: v: v != null) {
@@ -137,8 +137,18 @@ let
           ;
       };
 
+      recordingSection = lib.filterAttrs (_: v: v != null) {
+        inherit (s.recording)
+          flight_recorder
+          flight_recorder_minutes
+          flight_recorder_max_mb
+          redact_echo_off
+          redact_patterns
+          ;
+      };
+
       tabTitleSection = lib.filterAttrs (_: v: v != null) {
-        inherit (s.tab_title) policy separator;
+        inherit (s.tab_title) policy separator template;
       };
 
       shellIntegrationSection = lib.filterAttrs (_: v: v != null) {
@@ -208,6 +218,7 @@ let
       // lib.optionalAttrs (securitySection != { }) { security = securitySection; }
       // lib.optionalAttrs (pasteGuardSection != { }) { paste_guard = pasteGuardSection; }
       // lib.optionalAttrs (closeGuardSection != { }) { close_guard = closeGuardSection; }
+      // lib.optionalAttrs (recordingSection != { }) { recording = recordingSection; }
       // lib.optionalAttrs (tabTitleSection != { }) { tab_title = tabTitleSection; }
       // lib.optionalAttrs (shellIntegrationSection != { }) {
         shell_integration = shellIntegrationSection;
@@ -639,6 +650,28 @@ in
             Null uses the default (false).
           '';
         };
+
+        allow_remote_control = mkOption {
+          type = types.nullOr types.bool;
+          default = null;
+          description = ''
+            Listen on a per-instance Unix socket so `freminal msg` can drive
+            and read panes. Any process running as the user gains that access.
+            Takes effect at the next launch.
+            Null uses the default (false).
+          '';
+        };
+
+        allow_checksum_reports = mkOption {
+          type = types.nullOr types.bool;
+          default = null;
+          description = ''
+            Answer DECRQCRA requests with a checksum of a screen area, as
+            conformance suites such as esctest expect. The checksum lets a
+            program read back what is on screen.
+            Null uses the default (false).
+          '';
+        };
       };
 
       paste_guard = {
@@ -727,6 +760,56 @@ in
         };
       };
 
+      recording = {
+        flight_recorder = mkOption {
+          type = types.nullOr types.bool;
+          default = null;
+          description = ''
+            Keep the last few minutes of the session in memory so they can be
+            saved to a FREC file with the dump_flight_recorder action.
+            Null uses the default (false).
+          '';
+        };
+
+        flight_recorder_minutes = mkOption {
+          type = types.nullOr (types.ints.between 1 120);
+          default = null;
+          description = ''
+            Minutes of events the flight recorder keeps.
+            Null uses the default (5).
+          '';
+        };
+
+        flight_recorder_max_mb = mkOption {
+          type = types.nullOr (types.ints.between 1 1024);
+          default = null;
+          description = ''
+            Memory the flight recorder may use, in MiB.
+            Null uses the default (64).
+          '';
+        };
+
+        redact_echo_off = mkOption {
+          type = types.nullOr types.bool;
+          default = null;
+          description = ''
+            Leave input typed or pasted while the shell has echo off (password
+            prompts) out of recordings, keeping only a Redaction marker.
+            Null uses the default (true).
+          '';
+        };
+
+        redact_patterns = mkOption {
+          type = types.nullOr (types.listOf types.str);
+          default = null;
+          description = ''
+            Regex patterns (Rust regex syntax) for secrets to mask with `*` in
+            recordings. Malformed patterns are reported and skipped.
+            Null uses the default set (AWS, GitHub and Slack tokens).
+          '';
+        };
+      };
+
       tab_title = {
         policy = mkOption {
           type = types.nullOr (
@@ -758,6 +841,16 @@ in
             Null uses the default (": ").
           '';
         };
+
+        template = mkOption {
+          type = types.nullOr types.str;
+          default = null;
+          description = ''
+            Template for the shell's side of the title. Tokens: {title},
+            {cwd} and {user.<name>} (set with OSC 1337 ; SetUserVar=).
+            Null uses the default ("", the OSC title as sent).
+          '';
+        };
       };
 
       shell_integration = {
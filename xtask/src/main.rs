@@ -108,6 +108,10 @@ enum Command {
     #[command(visible_alias = "m")]
     Machete,
 
+    /// Check the `freminal-embed` public API for semver breaks against `main`
+    #[command(visible_alias = "sc")]
+    SemverChecks,
+
     /// Lint formatting, typos, clippy, and docs
     #[command(visible_alias = "l")]
     Lint,
@@ -220,6 +224,7 @@ impl Command {
             Self::CheckWindows => check_windows(),
             Self::Deny => deny(),
             Self::Machete => machete(),
+            Self::SemverChecks => semver_checks(),
             Self::CheckReadme => check_readme(),
             Self::Coverage => coverage(),
             Self::Lint => lint(),
@@ -261,6 +266,8 @@ impl Command {
 ///    features enabled. This catches `#[cfg(feature = "…")]` gating errors
 ///    where code behind a feature flag accidentally leaks into the default
 ///    build. (The previous steps use `--all-features`.)
+/// 8. `semver_checks` — compares the public API of the crates that promise
+///    semver stability (`freminal-embed`) with `main`.
 fn ci() -> Result<()> {
     lint()?;
     deny()?;
@@ -269,6 +276,7 @@ fn ci() -> Result<()> {
     test()?;
     bench_compile()?;
     test_default_features()?;
+    semver_checks()?;
     Ok(())
 }
 
@@ -283,6 +291,44 @@ fn machete() -> Result<()> {
     Ok(())
 }
 
+/// Crates whose public API is promised to follow semver.
+const SEMVER_CHECKED_CRATES: &[&str] = &["freminal-embed"];
+
+/// Branch the public API is compared against.
+const SEMVER_BASELINE_REV: &str = "origin/main";
+
+/// Check the stable library crates for semver breaks with `cargo-semver-checks`
+///
+/// A crate that does not exist on the baseline yet has nothing to break and
+/// is skipped.
+fn semver_checks() -> Result<()> {
+    for krate in SEMVER_CHECKED_CRATES {
+        let on_baseline = cmd!(
+            "git",
+            "cat-file",
+            "-e",
+            format!("{SEMVER_BASELINE_REV}:{krate}/Cargo.toml")
+        )
+        .stderr_null()
+        .unchecked()
+        .run()?
+        .status
+        .success();
+        if !on_baseline {
+            tracing::info!("{krate} is not on {SEMVER_BASELINE_REV} yet; skipping semver checks");
+            continue;
+        }
+        run_cargo(vec![
+            "semver-checks",
+            "--package",
+            krate,
+            "--baseline-rev",
+            SEMVER_BASELINE_REV,
+        ])?;
+    }
+    Ok(())
+}
+
 /// Build the project
 fn build() -> Result<()> {
     run_cargo(vec!["build", "--all-targets", "--all-features"])
@@ -541,6 +587,18 @@ fn test_default_features() -> Result<()> {
     // doesn't leak into this pass — we need to verify the code compiles and
     // passes tests with NO optional features enabled.
     run_cargo_no_features(vec!["clippy", "--all-targets", "--", "-D", "warnings"])?;
+    // The emulator's `pty` feature is on by default; `freminal-embed` builds
+    // it without, so that combination needs its own pass.
+    run_cargo_no_features(vec![
+        "clippy",
+        "--package",
+        "freminal-terminal-emulator",
+        "--no-default-features",
+        "--all-targets",
+        "--",
+        "-D",
+        "warnings",
+    ])?;
     run_cargo_no_features(vec!["test", "--lib", "--bins", "--tests", "--examples"])?;
     run_cargo_no_features(vec!["test", "--doc"])?;
     Ok(())